@@ -0,0 +1,117 @@
+use crate::{
+    dependencies::{ClaimedEmissions, PoolClient, Positions, Request},
+    errors::AccountCloserError,
+    events::AccountCloserEvents,
+};
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, Address, Env, Vec};
+
+/// The pool request types used to close an account
+const REQUEST_WITHDRAW: u32 = 1;
+const REQUEST_WITHDRAW_COLLATERAL: u32 = 3;
+const REQUEST_REPAY: u32 = 5;
+
+/// An amount larger than any position, but small enough that the pool can scale it by its 12
+/// decimal rates without overflowing. The pool caps repayments and withdrawals at the position,
+/// so requesting this amount closes the position in full.
+const CLOSE_AMOUNT: i128 = i128::MAX / 10_000_000_000_000;
+
+/// ### AccountCloser
+///
+/// Closes a user's account in a pool in a single transaction by claiming their emissions,
+/// repaying all of their liabilities, and withdrawing all of their collateral and supply.
+#[contract]
+pub struct AccountCloserContract;
+
+#[contractclient(name = "AccountCloserClient")]
+pub trait AccountCloser {
+    /// Close the account of `from` in `pool`. Outstanding emissions for every position are
+    /// claimed, then every liability is repaid and all collateral and supply are withdrawn in a
+    /// single submit, with everything returned to `from`.
+    ///
+    /// The pool settles the net transfer of each token, so debt repaid with collateral of the
+    /// same asset does not need to be held by `from`. Any remaining debt is pulled from `from`
+    /// with `transfer_from`, so `from` must approve the pool to spend it.
+    ///
+    /// Returns the amount of the legacy and migrated emission tokens claimed
+    ///
+    /// ### Arguments
+    /// * `from` - The address closing their account
+    /// * `pool` - The pool to close the account in
+    ///
+    /// ### Panics
+    /// If `from` has no positions in the pool, or cannot cover their remaining debt
+    fn close_account(e: Env, from: Address, pool: Address) -> ClaimedEmissions;
+}
+
+#[contractimpl]
+impl AccountCloser for AccountCloserContract {
+    fn close_account(e: Env, from: Address, pool: Address) -> ClaimedEmissions {
+        from.require_auth();
+
+        let pool_client = PoolClient::new(&e, &pool);
+        let positions = pool_client.get_positions(&from);
+        if positions.liabilities.is_empty()
+            && positions.collateral.is_empty()
+            && positions.supply.is_empty()
+        {
+            panic_with_error!(&e, AccountCloserError::BadRequest);
+        }
+
+        let emissions = pool_client.claim(&from, &reserve_token_ids(&e, &positions), &from);
+        pool_client.submit_with_allowance(
+            &from,
+            &from,
+            &from,
+            &close_requests(&e, &pool_client, &positions),
+        );
+
+        AccountCloserEvents::close_account(&e, pool, from, emissions.legacy, emissions.migrated);
+        emissions
+    }
+}
+
+/// Build the reserve token ids of every position. Liabilities are dTokens (index * 2), and
+/// collateral and supply are bTokens (index * 2 + 1).
+fn reserve_token_ids(e: &Env, positions: &Positions) -> Vec<u32> {
+    let mut ids: Vec<u32> = Vec::new(e);
+    for index in positions.liabilities.keys() {
+        ids.push_back(index * 2);
+    }
+    for index in positions.collateral.keys() {
+        ids.push_back(index * 2 + 1);
+    }
+    for index in positions.supply.keys() {
+        if !positions.collateral.contains_key(index) {
+            ids.push_back(index * 2 + 1);
+        }
+    }
+    ids
+}
+
+/// Build the requests that repay every liability, then withdraw all collateral and supply
+fn close_requests(e: &Env, pool_client: &PoolClient, positions: &Positions) -> Vec<Request> {
+    let reserve_list = pool_client.get_reserve_list();
+    let mut requests: Vec<Request> = Vec::new(e);
+    for index in positions.liabilities.keys() {
+        requests.push_back(Request {
+            request_type: REQUEST_REPAY,
+            address: reserve_list.get_unchecked(index),
+            amount: CLOSE_AMOUNT,
+        });
+    }
+    for index in positions.collateral.keys() {
+        requests.push_back(Request {
+            request_type: REQUEST_WITHDRAW_COLLATERAL,
+            address: reserve_list.get_unchecked(index),
+            amount: CLOSE_AMOUNT,
+        });
+    }
+    for index in positions.supply.keys() {
+        requests.push_back(Request {
+            request_type: REQUEST_WITHDRAW,
+            address: reserve_list.get_unchecked(index),
+            amount: CLOSE_AMOUNT,
+        });
+    }
+    requests
+}
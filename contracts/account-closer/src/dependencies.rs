@@ -0,0 +1,57 @@
+/**
+ * Partial client for the pool an account is closed in
+ */
+use soroban_sdk::{contractclient, contracttype, Address, Env, Map, Vec};
+
+/// A request submitted to a pool
+#[derive(Clone)]
+#[contracttype]
+pub struct Request {
+    pub request_type: u32,
+    pub address: Address,
+    pub amount: i128,
+}
+
+/// A user's positions in a pool, keyed by reserve index
+#[derive(Clone)]
+#[contracttype]
+pub struct Positions {
+    pub liabilities: Map<u32, i128>,
+    pub collateral: Map<u32, i128>,
+    pub supply: Map<u32, i128>,
+}
+
+/// The emissions claimed from a pool, split by the token they were paid in
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ClaimedEmissions {
+    pub legacy: i128,   // the amount of the legacy emission token (BLND) claimed
+    pub migrated: i128, // the amount of the pool's migrated emission token claimed
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolClient")]
+pub trait Pool {
+    /// Fetch the underlying assets of the pool's reserves, in reserve index order
+    fn get_reserve_list(e: Env) -> Vec<Address>;
+
+    /// Fetch the positions for an address
+    fn get_positions(e: Env, address: Address) -> Positions;
+
+    /// Submit a set of requests to the pool, settling the net transfer of each token with
+    /// `transfer_from`
+    ///
+    /// Returns the new positions for `from`
+    fn submit_with_allowance(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions;
+
+    /// Claims outstanding emissions for `from` for the given reserve token ids
+    ///
+    /// Returns the amount of the legacy and migrated emission tokens claimed
+    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> ClaimedEmissions;
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the account closer contract. Common errors are codes that match up with the
+/// built-in contracts error reporting. Account closer specific errors start at 1700.
+pub enum AccountCloserError {
+    // Common Errors
+    InternalError = 1,
+
+    // Account Closer
+    BadRequest = 1700,
+}
@@ -0,0 +1,26 @@
+use soroban_sdk::{Address, Env, Symbol};
+
+pub struct AccountCloserEvents {}
+
+impl AccountCloserEvents {
+    /// Emitted when a user's account in a pool is closed
+    ///
+    /// - topics - `["close_account", pool: Address, from: Address]`
+    /// - data - `[emissions: i128, migrated_emissions: i128]`
+    ///
+    /// ### Arguments
+    /// * `pool` - The pool the account was closed in
+    /// * `from` - The address whose account was closed
+    /// * `emissions` - The amount of the legacy emission token (BLND) claimed
+    /// * `migrated_emissions` - The amount of the pool's migrated emission token claimed
+    pub fn close_account(
+        e: &Env,
+        pool: Address,
+        from: Address,
+        emissions: i128,
+        migrated_emissions: i128,
+    ) {
+        let topics = (Symbol::new(e, "close_account"), pool, from);
+        e.events().publish(topics, (emissions, migrated_emissions));
+    }
+}
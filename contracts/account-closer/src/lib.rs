@@ -0,0 +1,14 @@
+#![no_std]
+
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod contract;
+mod dependencies;
+mod errors;
+mod events;
+mod test;
+
+pub use contract::*;
+pub use dependencies::{ClaimedEmissions, Positions, Request};
+pub use errors::AccountCloserError;
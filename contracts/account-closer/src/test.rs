@@ -0,0 +1,211 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl, map,
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, IntoVal, Map, Symbol, Vec,
+};
+
+use crate::{AccountCloserClient, AccountCloserContract, ClaimedEmissions, Positions, Request};
+
+/********** Mocks **********/
+
+/// Holds a single user's positions, pays out 10 BLND per reserve token id claimed, and settles
+/// the net transfer of each token when the positions are closed
+#[contract]
+pub struct MockPool;
+
+#[contractimpl]
+impl MockPool {
+    pub fn __constructor(e: Env, blnd: Address, reserves: Vec<Address>, positions: Positions) {
+        e.storage().instance().set(&Symbol::new(&e, "BLND"), &blnd);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "Res"), &reserves);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "Pos"), &positions);
+    }
+
+    pub fn get_reserve_list(e: Env) -> Vec<Address> {
+        e.storage().instance().get(&Symbol::new(&e, "Res")).unwrap()
+    }
+
+    pub fn get_positions(e: Env, _address: Address) -> Positions {
+        e.storage().instance().get(&Symbol::new(&e, "Pos")).unwrap()
+    }
+
+    pub fn claim(
+        e: Env,
+        from: Address,
+        reserve_token_ids: Vec<u32>,
+        to: Address,
+    ) -> ClaimedEmissions {
+        from.require_auth();
+        let blnd: Address = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "BLND"))
+            .unwrap();
+        let amount = 10_0000000 * reserve_token_ids.len() as i128;
+        TokenClient::new(&e, &blnd).transfer(&e.current_contract_address(), &to, &amount);
+        ClaimedEmissions {
+            legacy: amount,
+            migrated: 0,
+        }
+    }
+
+    pub fn submit_with_allowance(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions {
+        from.require_auth();
+        spender.require_auth();
+        let positions = MockPool::get_positions(e.clone(), from.clone());
+        let reserves = MockPool::get_reserve_list(e.clone());
+
+        let mut net_balances: Map<Address, i128> = Map::new(&e);
+        for request in requests.iter() {
+            let index = reserves.first_index_of(&request.address).unwrap();
+            let amount = match request.request_type {
+                1 => positions.supply.get_unchecked(index),
+                3 => positions.collateral.get_unchecked(index),
+                5 => -positions.liabilities.get_unchecked(index),
+                _ => panic!("unexpected request"),
+            };
+            net_balances.set(
+                request.address.clone(),
+                net_balances.get(request.address).unwrap_or_default() + amount,
+            );
+        }
+        for (token, amount) in net_balances.iter() {
+            let client = TokenClient::new(&e, &token);
+            if amount < 0 {
+                client.transfer_from(
+                    &e.current_contract_address(),
+                    &spender,
+                    &e.current_contract_address(),
+                    &-amount,
+                );
+            } else if amount > 0 {
+                client.transfer(&e.current_contract_address(), &to, &amount);
+            }
+        }
+
+        let closed = Positions {
+            liabilities: map![&e],
+            collateral: map![&e],
+            supply: map![&e],
+        };
+        e.storage().instance().set(&Symbol::new(&e, "Pos"), &closed);
+        closed
+    }
+}
+
+#[test]
+fn test_close_account() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let mut tokens: Vec<Address> = Vec::new(&e);
+    for _ in 0..4 {
+        tokens.push_back(
+            e.register_stellar_asset_contract_v2(bombadil.clone())
+                .address(),
+        );
+    }
+    let blnd = tokens.get_unchecked(3);
+    let reserves = vec![
+        &e,
+        tokens.get_unchecked(0),
+        tokens.get_unchecked(1),
+        tokens.get_unchecked(2),
+    ];
+
+    let positions = Positions {
+        liabilities: map![&e, (0, 10_0000000), (2, 5_0000000)],
+        collateral: map![&e, (0, 50_0000000)],
+        supply: map![&e, (1, 20_0000000)],
+    };
+    let pool = e.register(MockPool {}, (blnd.clone(), reserves.clone(), positions));
+    for token in tokens.iter() {
+        StellarAssetClient::new(&e, &token).mint(&pool, &1000_0000000);
+    }
+    // samwise only holds enough to cover the debt not covered by collateral
+    StellarAssetClient::new(&e, &tokens.get_unchecked(2)).mint(&samwise, &5_0000000);
+    TokenClient::new(&e, &tokens.get_unchecked(2)).approve(&samwise, &pool, &5_0000000, &1000);
+
+    let closer_address = e.register(AccountCloserContract {}, ());
+    let closer = AccountCloserClient::new(&e, &closer_address);
+
+    let emissions = closer.close_account(&samwise, &pool);
+    assert_eq!(
+        e.events().all().last_unchecked(),
+        (
+            closer_address.clone(),
+            (
+                Symbol::new(&e, "close_account"),
+                pool.clone(),
+                samwise.clone()
+            )
+                .into_val(&e),
+            (40_0000000i128, 0i128).into_val(&e),
+        )
+    );
+
+    // dTokens of reserve 0 and 2 and bTokens of reserves 0 and 1
+    assert_eq!(
+        emissions,
+        ClaimedEmissions {
+            legacy: 40_0000000,
+            migrated: 0,
+        }
+    );
+    assert_eq!(TokenClient::new(&e, &blnd).balance(&samwise), 40_0000000);
+    assert_eq!(
+        TokenClient::new(&e, &tokens.get_unchecked(0)).balance(&samwise),
+        40_0000000
+    );
+    assert_eq!(
+        TokenClient::new(&e, &tokens.get_unchecked(1)).balance(&samwise),
+        20_0000000
+    );
+    assert_eq!(
+        TokenClient::new(&e, &tokens.get_unchecked(2)).balance(&samwise),
+        0
+    );
+    let positions = MockPoolClient::new(&e, &pool).get_positions(&samwise);
+    assert!(positions.liabilities.is_empty());
+    assert!(positions.collateral.is_empty());
+    assert!(positions.supply.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1700)")]
+fn test_close_account_no_positions() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let blnd = e
+        .register_stellar_asset_contract_v2(bombadil.clone())
+        .address();
+    let positions = Positions {
+        liabilities: map![&e],
+        collateral: map![&e],
+        supply: map![&e],
+    };
+    let pool = e.register(MockPool {}, (blnd, vec![&e, bombadil], positions));
+
+    let closer_address = e.register(AccountCloserContract {}, ());
+    let closer = AccountCloserClient::new(&e, &closer_address);
+
+    closer.close_account(&samwise, &pool);
+}
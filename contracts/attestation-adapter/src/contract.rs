@@ -0,0 +1,235 @@
+use crate::{
+    errors::AttestationAdapterError,
+    events::AttestationAdapterEvents,
+    storage::{self, LockAttestation},
+};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, panic_with_error,
+    token::{StellarAssetClient, TokenClient},
+    xdr::ToXdr,
+    Address, BytesN, Env, String,
+};
+
+/// ### MessageVerifier
+///
+/// The interface of a cross-chain messaging protocol's gateway (e.g. an Axelar gateway or a
+/// Wormhole core bridge wrapper) that verifies messages from other chains.
+#[contractclient(name = "MessageVerifierClient")]
+pub trait MessageVerifier {
+    /// Check if a message was sent by `source_address` on `source_chain`, and has been
+    /// approved by the messaging protocol
+    ///
+    /// ### Arguments
+    /// * `source_chain` - The name of the chain the message was sent from
+    /// * `source_address` - The address of the sender on the source chain
+    /// * `message_id` - The id of the message, unique on the source chain
+    /// * `payload_hash` - The sha256 hash of the message payload
+    fn verify_message(
+        e: Env,
+        source_chain: String,
+        source_address: String,
+        message_id: BytesN<32>,
+        payload_hash: BytesN<32>,
+    ) -> bool;
+}
+
+/// ### AttestationAdapter
+///
+/// Mints a representation asset for collateral locked on another chain, as attested by verified
+/// cross-chain messages from a trusted collateral locker, and burns it to release the collateral.
+///
+/// The representation asset is a Stellar asset administered by the adapter. It is intended to be
+/// listed as a low c_factor reserve in permissioned pools, as its value depends on the security
+/// of the messaging protocol.
+#[contract]
+pub struct AttestationAdapterContract;
+
+#[contractclient(name = "AttestationAdapterClient")]
+pub trait AttestationAdapter {
+    /// (Admin only) Set the trusted collateral locker on a source chain
+    ///
+    /// ### Arguments
+    /// * `chain` - The name of the source chain
+    /// * `source` - The address of the collateral locker on the source chain, or None to stop
+    ///              accepting attestations from the chain
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_source(e: Env, chain: String, source: Option<String>);
+
+    /// Mint the representation asset for collateral locked on a source chain. Anyone can relay
+    /// a verified message.
+    ///
+    /// ### Arguments
+    /// * `source_chain` - The name of the chain the collateral was locked on
+    /// * `message_id` - The id of the message, unique on the source chain
+    /// * `attestation` - The attestation, whose XDR encoding is the message payload
+    ///
+    /// ### Panics
+    /// If the chain is not supported, the message was already consumed, the message was not
+    /// verified, or the amount is not positive
+    fn attest_lock(
+        e: Env,
+        source_chain: String,
+        message_id: BytesN<32>,
+        attestation: LockAttestation,
+    );
+
+    /// Burn the representation asset from `from` to release the collateral on a source chain.
+    /// The release is emitted as a message for the messaging protocol to deliver.
+    ///
+    /// Returns the nonce of the release message
+    ///
+    /// ### Arguments
+    /// * `from` - The address burning the representation asset
+    /// * `source_chain` - The name of the chain to release the collateral on
+    /// * `recipient` - The address to release the collateral to on the source chain
+    /// * `amount` - The amount of collateral to release
+    ///
+    /// ### Panics
+    /// If the chain is not supported, the amount is not positive, or more than the collateral
+    /// locked on the chain is released
+    fn release(e: Env, from: Address, source_chain: String, recipient: String, amount: i128)
+        -> u64;
+
+    /// Fetch the trusted collateral locker on a source chain, if the chain is supported
+    ///
+    /// ### Arguments
+    /// * `chain` - The name of the source chain
+    fn get_source(e: Env, chain: String) -> Option<String>;
+
+    /// Fetch the amount of collateral locked on a source chain
+    ///
+    /// ### Arguments
+    /// * `chain` - The name of the source chain
+    fn get_locked(e: Env, chain: String) -> i128;
+
+    /// Check if a message from a source chain has been consumed
+    ///
+    /// ### Arguments
+    /// * `chain` - The name of the source chain
+    /// * `message_id` - The id of the message
+    fn is_consumed(e: Env, chain: String, message_id: BytesN<32>) -> bool;
+
+    /// Fetch the representation asset
+    fn token(e: Env) -> Address;
+
+    /// Fetch the message verifier
+    fn verifier(e: Env) -> Address;
+}
+
+#[contractimpl]
+impl AttestationAdapterContract {
+    /// Construct the attestation adapter contract
+    ///
+    /// ### Arguments
+    /// * `admin` - The address that manages the supported source chains
+    /// * `verifier` - The contract that verifies cross-chain messages
+    /// * `token` - The representation asset. The adapter must be set as its admin to mint it.
+    pub fn __constructor(e: Env, admin: Address, verifier: Address, token: Address) {
+        storage::set_admin(&e, &admin);
+        storage::set_verifier(&e, &verifier);
+        storage::set_token(&e, &token);
+    }
+}
+
+#[contractimpl]
+impl AttestationAdapter for AttestationAdapterContract {
+    fn set_source(e: Env, chain: String, source: Option<String>) {
+        storage::extend_instance(&e);
+        storage::get_admin(&e).require_auth();
+
+        storage::set_source(&e, &chain, &source);
+
+        AttestationAdapterEvents::set_source(&e, chain, source);
+    }
+
+    fn attest_lock(
+        e: Env,
+        source_chain: String,
+        message_id: BytesN<32>,
+        attestation: LockAttestation,
+    ) {
+        storage::extend_instance(&e);
+
+        let source = load_source(&e, &source_chain);
+        if attestation.amount <= 0 {
+            panic_with_error!(&e, AttestationAdapterError::BadRequest);
+        }
+        if storage::is_consumed(&e, &source_chain, &message_id) {
+            panic_with_error!(&e, AttestationAdapterError::MessageConsumed);
+        }
+        let payload_hash: BytesN<32> = e.crypto().sha256(&attestation.clone().to_xdr(&e)).into();
+        let verified = MessageVerifierClient::new(&e, &storage::get_verifier(&e)).verify_message(
+            &source_chain,
+            &source,
+            &message_id,
+            &payload_hash,
+        );
+        if !verified {
+            panic_with_error!(&e, AttestationAdapterError::InvalidAttestation);
+        }
+        storage::set_consumed(&e, &source_chain, &message_id);
+
+        let locked = storage::get_locked(&e, &source_chain) + attestation.amount;
+        storage::set_locked(&e, &source_chain, locked);
+        StellarAssetClient::new(&e, &storage::get_token(&e))
+            .mint(&attestation.recipient, &attestation.amount);
+
+        AttestationAdapterEvents::attest_lock(&e, source_chain, message_id, attestation);
+    }
+
+    fn release(
+        e: Env,
+        from: Address,
+        source_chain: String,
+        recipient: String,
+        amount: i128,
+    ) -> u64 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        load_source(&e, &source_chain);
+        if amount <= 0 {
+            panic_with_error!(&e, AttestationAdapterError::BadRequest);
+        }
+        let locked = storage::get_locked(&e, &source_chain);
+        if locked < amount {
+            panic_with_error!(&e, AttestationAdapterError::InsufficientLocked);
+        }
+        storage::set_locked(&e, &source_chain, locked - amount);
+        TokenClient::new(&e, &storage::get_token(&e)).burn(&from, &amount);
+
+        let nonce = storage::next_release_nonce(&e);
+        AttestationAdapterEvents::release(&e, source_chain, nonce, from, recipient, amount);
+        nonce
+    }
+
+    fn get_source(e: Env, chain: String) -> Option<String> {
+        storage::get_source(&e, &chain)
+    }
+
+    fn get_locked(e: Env, chain: String) -> i128 {
+        storage::get_locked(&e, &chain)
+    }
+
+    fn is_consumed(e: Env, chain: String, message_id: BytesN<32>) -> bool {
+        storage::is_consumed(&e, &chain, &message_id)
+    }
+
+    fn token(e: Env) -> Address {
+        storage::get_token(&e)
+    }
+
+    fn verifier(e: Env) -> Address {
+        storage::get_verifier(&e)
+    }
+}
+
+/// Load the trusted collateral locker on a source chain, panicking if the chain is not supported
+fn load_source(e: &Env, chain: &String) -> String {
+    match storage::get_source(e, chain) {
+        Some(source) => source,
+        None => panic_with_error!(e, AttestationAdapterError::UnknownSource),
+    }
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the attestation adapter contract. Common errors are codes that match up with
+/// the built-in contracts error reporting. Attestation adapter specific errors start at 1900.
+pub enum AttestationAdapterError {
+    // Common Errors
+    InternalError = 1,
+
+    // Attestation Adapter
+    BadRequest = 1900,
+    UnknownSource = 1901,
+    MessageConsumed = 1902,
+    InvalidAttestation = 1903,
+    InsufficientLocked = 1904,
+}
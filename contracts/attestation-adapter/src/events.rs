@@ -0,0 +1,63 @@
+use soroban_sdk::{Address, BytesN, Env, String, Symbol};
+
+use crate::LockAttestation;
+
+pub struct AttestationAdapterEvents {}
+
+impl AttestationAdapterEvents {
+    /// Emitted when the trusted collateral locker on a source chain is set
+    ///
+    /// - topics - `["set_source", chain: String]`
+    /// - data - `source: Option<String>`
+    ///
+    /// ### Arguments
+    /// * `chain` - The name of the source chain
+    /// * `source` - The address of the collateral locker, or None if the chain was removed
+    pub fn set_source(e: &Env, chain: String, source: Option<String>) {
+        let topics = (Symbol::new(e, "set_source"), chain);
+        e.events().publish(topics, source);
+    }
+
+    /// Emitted when the representation asset is minted for collateral locked on a source chain
+    ///
+    /// - topics - `["attest_lock", source_chain: String, message_id: BytesN<32>]`
+    /// - data - `attestation: LockAttestation`
+    ///
+    /// ### Arguments
+    /// * `source_chain` - The name of the chain the collateral was locked on
+    /// * `message_id` - The id of the message
+    /// * `attestation` - The attestation
+    pub fn attest_lock(
+        e: &Env,
+        source_chain: String,
+        message_id: BytesN<32>,
+        attestation: LockAttestation,
+    ) {
+        let topics = (Symbol::new(e, "attest_lock"), source_chain, message_id);
+        e.events().publish(topics, attestation);
+    }
+
+    /// Emitted when the representation asset is burned to release collateral on a source chain.
+    /// This is the message delivered to the collateral locker.
+    ///
+    /// - topics - `["release", source_chain: String, nonce: u64]`
+    /// - data - `[from: Address, recipient: String, amount: i128]`
+    ///
+    /// ### Arguments
+    /// * `source_chain` - The name of the chain to release the collateral on
+    /// * `nonce` - The nonce of the release message
+    /// * `from` - The address that burned the representation asset
+    /// * `recipient` - The address to release the collateral to on the source chain
+    /// * `amount` - The amount of collateral to release
+    pub fn release(
+        e: &Env,
+        source_chain: String,
+        nonce: u64,
+        from: Address,
+        recipient: String,
+        amount: i128,
+    ) {
+        let topics = (Symbol::new(e, "release"), source_chain, nonce);
+        e.events().publish(topics, (from, recipient, amount));
+    }
+}
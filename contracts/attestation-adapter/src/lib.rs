@@ -0,0 +1,14 @@
+#![no_std]
+
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod contract;
+mod errors;
+mod events;
+mod storage;
+mod test;
+
+pub use contract::*;
+pub use errors::AttestationAdapterError;
+pub use storage::LockAttestation;
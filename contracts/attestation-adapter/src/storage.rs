@@ -0,0 +1,224 @@
+use soroban_sdk::{contracttype, unwrap::UnwrapOptimized, Address, BytesN, Env, String, Symbol};
+
+/********** Ledger Thresholds **********/
+
+const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
+
+const LEDGER_THRESHOLD_INSTANCE: u32 = ONE_DAY_LEDGERS * 30; // ~ 30 days
+const LEDGER_BUMP_INSTANCE: u32 = LEDGER_THRESHOLD_INSTANCE + ONE_DAY_LEDGERS; // ~ 31 days
+
+const LEDGER_THRESHOLD_SHARED: u32 = ONE_DAY_LEDGERS * 45; // ~ 45 days
+const LEDGER_BUMP_SHARED: u32 = LEDGER_THRESHOLD_SHARED + ONE_DAY_LEDGERS; // ~ 46 days
+
+const LEDGER_THRESHOLD_MESSAGE: u32 = ONE_DAY_LEDGERS * 100; // ~ 100 days
+const LEDGER_BUMP_MESSAGE: u32 = LEDGER_THRESHOLD_MESSAGE + 20 * ONE_DAY_LEDGERS; // ~ 120 days
+
+/********** Storage Types **********/
+
+/// An attestation that collateral was locked on another chain for a recipient on this chain. The
+/// payload of a verified message is the XDR encoding of the attestation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LockAttestation {
+    pub recipient: Address, // the address the representation asset is minted to
+    pub amount: i128, // the amount of collateral locked, in the representation asset's decimals
+}
+
+/********** Storage Keys **********/
+
+#[derive(Clone)]
+#[contracttype]
+pub struct MessageKey {
+    pub chain: String,          // the name of the source chain
+    pub message_id: BytesN<32>, // the id of the message on the source chain
+}
+
+const ADMIN_KEY: &str = "Admin";
+const VERIFIER_KEY: &str = "Verifier";
+const TOKEN_KEY: &str = "Token";
+const RELEASE_NONCE_KEY: &str = "RelNonce";
+
+#[derive(Clone)]
+#[contracttype]
+pub enum AdapterDataKey {
+    // The trusted collateral locker on a source chain
+    Source(String),
+    // The amount of collateral locked on a source chain backing the representation asset
+    Locked(String),
+    // A message from a source chain that has been consumed
+    Consumed(MessageKey),
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_INSTANCE, LEDGER_BUMP_INSTANCE);
+}
+
+/********** Instance **********/
+
+/// Fetch the admin address
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, ADMIN_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the admin address
+///
+/// ### Arguments
+/// * `admin` - The address of the admin
+pub fn set_admin(e: &Env, admin: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, ADMIN_KEY), admin);
+}
+
+/// Fetch the message verifier address
+pub fn get_verifier(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, VERIFIER_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the message verifier address
+///
+/// ### Arguments
+/// * `verifier` - The address of the contract that verifies cross-chain messages
+pub fn set_verifier(e: &Env, verifier: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, VERIFIER_KEY), verifier);
+}
+
+/// Fetch the representation asset address
+pub fn get_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, TOKEN_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the representation asset address
+///
+/// ### Arguments
+/// * `token` - The address of the representation asset
+pub fn set_token(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, TOKEN_KEY), token);
+}
+
+/// Fetch the nonce assigned to the next release message and increment it
+pub fn next_release_nonce(e: &Env) -> u64 {
+    let key = Symbol::new(e, RELEASE_NONCE_KEY);
+    let nonce = e.storage().instance().get::<Symbol, u64>(&key).unwrap_or(0);
+    e.storage()
+        .instance()
+        .set::<Symbol, u64>(&key, &(nonce + 1));
+    nonce
+}
+
+/********** Persistent **********/
+
+/// Fetch the trusted collateral locker on a source chain, if the chain is supported
+///
+/// ### Arguments
+/// * `chain` - The name of the source chain
+pub fn get_source(e: &Env, chain: &String) -> Option<String> {
+    let key = AdapterDataKey::Source(chain.clone());
+    let result = e.storage().persistent().get::<AdapterDataKey, String>(&key);
+    if result.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+    }
+    result
+}
+
+/// Set the trusted collateral locker on a source chain
+///
+/// ### Arguments
+/// * `chain` - The name of the source chain
+/// * `source` - The collateral locker on the source chain, or None to remove the chain
+pub fn set_source(e: &Env, chain: &String, source: &Option<String>) {
+    let key = AdapterDataKey::Source(chain.clone());
+    match source {
+        Some(source) => {
+            e.storage()
+                .persistent()
+                .set::<AdapterDataKey, String>(&key, source);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch the amount of collateral locked on a source chain
+///
+/// ### Arguments
+/// * `chain` - The name of the source chain
+pub fn get_locked(e: &Env, chain: &String) -> i128 {
+    let key = AdapterDataKey::Locked(chain.clone());
+    let result = e.storage().persistent().get::<AdapterDataKey, i128>(&key);
+    match result {
+        Some(locked) => {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+            locked
+        }
+        None => 0,
+    }
+}
+
+/// Set the amount of collateral locked on a source chain
+///
+/// ### Arguments
+/// * `chain` - The name of the source chain
+/// * `locked` - The amount of collateral locked
+pub fn set_locked(e: &Env, chain: &String, locked: i128) {
+    let key = AdapterDataKey::Locked(chain.clone());
+    e.storage()
+        .persistent()
+        .set::<AdapterDataKey, i128>(&key, &locked);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Check if a message from a source chain has been consumed
+///
+/// ### Arguments
+/// * `chain` - The name of the source chain
+/// * `message_id` - The id of the message
+pub fn is_consumed(e: &Env, chain: &String, message_id: &BytesN<32>) -> bool {
+    let key = AdapterDataKey::Consumed(MessageKey {
+        chain: chain.clone(),
+        message_id: message_id.clone(),
+    });
+    e.storage().persistent().has(&key)
+}
+
+/// Mark a message from a source chain as consumed
+///
+/// ### Arguments
+/// * `chain` - The name of the source chain
+/// * `message_id` - The id of the message
+pub fn set_consumed(e: &Env, chain: &String, message_id: &BytesN<32>) {
+    let key = AdapterDataKey::Consumed(MessageKey {
+        chain: chain.clone(),
+        message_id: message_id.clone(),
+    });
+    e.storage()
+        .persistent()
+        .set::<AdapterDataKey, bool>(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_MESSAGE, LEDGER_BUMP_MESSAGE);
+}
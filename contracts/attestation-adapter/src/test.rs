@@ -0,0 +1,257 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    xdr::ToXdr,
+    Address, BytesN, Env, IntoVal, String, Symbol,
+};
+
+use crate::{AttestationAdapterClient, AttestationAdapterContract, LockAttestation};
+
+/********** Mocks **********/
+
+/// A message verifier that accepts the messages it was told to approve
+#[contract]
+pub struct MockVerifier;
+
+#[contractimpl]
+impl MockVerifier {
+    pub fn approve(e: Env, message_id: BytesN<32>, payload_hash: BytesN<32>) {
+        e.storage().instance().set(&message_id, &payload_hash);
+    }
+
+    pub fn verify_message(
+        e: Env,
+        _source_chain: String,
+        _source_address: String,
+        message_id: BytesN<32>,
+        payload_hash: BytesN<32>,
+    ) -> bool {
+        match e.storage().instance().get::<_, BytesN<32>>(&message_id) {
+            Some(approved) => approved == payload_hash,
+            None => false,
+        }
+    }
+}
+
+fn create_adapter<'a>(
+    e: &Env,
+    admin: &Address,
+) -> (
+    Address,
+    AttestationAdapterClient<'a>,
+    MockVerifierClient<'a>,
+    TokenClient<'a>,
+) {
+    let verifier = e.register(MockVerifier {}, ());
+    let token = e
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let adapter = e.register(
+        AttestationAdapterContract {},
+        (admin.clone(), verifier.clone(), token.clone()),
+    );
+    StellarAssetClient::new(e, &token).set_admin(&adapter);
+    (
+        adapter.clone(),
+        AttestationAdapterClient::new(e, &adapter),
+        MockVerifierClient::new(e, &verifier),
+        TokenClient::new(e, &token),
+    )
+}
+
+/// Approve an attestation with the mock verifier
+fn approve(
+    e: &Env,
+    verifier_client: &MockVerifierClient,
+    message_id: &BytesN<32>,
+    attestation: &LockAttestation,
+) {
+    let payload_hash: BytesN<32> = e.crypto().sha256(&attestation.clone().to_xdr(e)).into();
+    verifier_client.approve(message_id, &payload_hash);
+}
+
+#[test]
+fn test_attest_lock_and_release() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let (adapter, adapter_client, verifier_client, token_client) = create_adapter(&e, &bombadil);
+
+    let chain = String::from_str(&e, "ethereum");
+    let locker = String::from_str(&e, "0x00000000000000000000000000000000000000aa");
+    adapter_client.set_source(&chain, &Some(locker.clone()));
+    assert_eq!(adapter_client.get_source(&chain), Some(locker));
+
+    let message_id = BytesN::from_array(&e, &[1; 32]);
+    let attestation = LockAttestation {
+        recipient: samwise.clone(),
+        amount: 100_0000000,
+    };
+    approve(&e, &verifier_client, &message_id, &attestation);
+
+    // anyone can relay the attestation
+    e.set_auths(&[]);
+    adapter_client.attest_lock(&chain, &message_id, &attestation);
+    assert_eq!(token_client.balance(&samwise), 100_0000000);
+    assert_eq!(adapter_client.get_locked(&chain), 100_0000000);
+    assert!(adapter_client.is_consumed(&chain, &message_id));
+
+    e.mock_all_auths();
+    let recipient = String::from_str(&e, "0x00000000000000000000000000000000000000bb");
+    let nonce = adapter_client.release(&samwise, &chain, &recipient, &40_0000000);
+    assert_eq!(nonce, 0);
+    assert_eq!(
+        e.events().all().last_unchecked(),
+        (
+            adapter.clone(),
+            (Symbol::new(&e, "release"), chain.clone(), 0u64).into_val(&e),
+            (samwise.clone(), recipient.clone(), 40_0000000i128).into_val(&e),
+        )
+    );
+    assert_eq!(token_client.balance(&samwise), 60_0000000);
+    assert_eq!(adapter_client.get_locked(&chain), 60_0000000);
+
+    let nonce = adapter_client.release(&samwise, &chain, &recipient, &10_0000000);
+    assert_eq!(nonce, 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1902)")]
+fn test_attest_lock_replay() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let (_, adapter_client, verifier_client, _) = create_adapter(&e, &bombadil);
+
+    let chain = String::from_str(&e, "ethereum");
+    adapter_client.set_source(&chain, &Some(String::from_str(&e, "0xaa")));
+
+    let message_id = BytesN::from_array(&e, &[1; 32]);
+    let attestation = LockAttestation {
+        recipient: samwise.clone(),
+        amount: 100_0000000,
+    };
+    approve(&e, &verifier_client, &message_id, &attestation);
+
+    adapter_client.attest_lock(&chain, &message_id, &attestation);
+    adapter_client.attest_lock(&chain, &message_id, &attestation);
+}
+
+#[test]
+fn test_attest_lock_same_id_on_different_chains() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let (_, adapter_client, verifier_client, token_client) = create_adapter(&e, &bombadil);
+
+    let ethereum = String::from_str(&e, "ethereum");
+    let solana = String::from_str(&e, "solana");
+    adapter_client.set_source(&ethereum, &Some(String::from_str(&e, "0xaa")));
+    adapter_client.set_source(&solana, &Some(String::from_str(&e, "locker")));
+
+    let message_id = BytesN::from_array(&e, &[1; 32]);
+    let attestation = LockAttestation {
+        recipient: samwise.clone(),
+        amount: 100_0000000,
+    };
+    approve(&e, &verifier_client, &message_id, &attestation);
+
+    // message ids are only unique on their source chain
+    adapter_client.attest_lock(&ethereum, &message_id, &attestation);
+    assert!(!adapter_client.is_consumed(&solana, &message_id));
+    adapter_client.attest_lock(&solana, &message_id, &attestation);
+    assert!(adapter_client.is_consumed(&solana, &message_id));
+    assert_eq!(token_client.balance(&samwise), 200_0000000);
+    assert_eq!(adapter_client.get_locked(&ethereum), 100_0000000);
+    assert_eq!(adapter_client.get_locked(&solana), 100_0000000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1903)")]
+fn test_attest_lock_unverified() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let (_, adapter_client, verifier_client, _) = create_adapter(&e, &bombadil);
+
+    let chain = String::from_str(&e, "ethereum");
+    adapter_client.set_source(&chain, &Some(String::from_str(&e, "0xaa")));
+
+    let message_id = BytesN::from_array(&e, &[1; 32]);
+    let attestation = LockAttestation {
+        recipient: samwise.clone(),
+        amount: 100_0000000,
+    };
+    approve(&e, &verifier_client, &message_id, &attestation);
+
+    // the attested amount does not match the verified payload
+    let forged = LockAttestation {
+        recipient: samwise.clone(),
+        amount: 1000_0000000,
+    };
+    adapter_client.attest_lock(&chain, &message_id, &forged);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1901)")]
+fn test_attest_lock_unknown_source() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let (_, adapter_client, verifier_client, _) = create_adapter(&e, &bombadil);
+
+    let chain = String::from_str(&e, "ethereum");
+    let message_id = BytesN::from_array(&e, &[1; 32]);
+    let attestation = LockAttestation {
+        recipient: samwise.clone(),
+        amount: 100_0000000,
+    };
+    approve(&e, &verifier_client, &message_id, &attestation);
+
+    adapter_client.attest_lock(&chain, &message_id, &attestation);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1904)")]
+fn test_release_more_than_locked() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let (_, adapter_client, verifier_client, _) = create_adapter(&e, &bombadil);
+
+    let ethereum = String::from_str(&e, "ethereum");
+    let solana = String::from_str(&e, "solana");
+    adapter_client.set_source(&ethereum, &Some(String::from_str(&e, "0xaa")));
+    adapter_client.set_source(&solana, &Some(String::from_str(&e, "locker")));
+
+    let message_id = BytesN::from_array(&e, &[1; 32]);
+    let attestation = LockAttestation {
+        recipient: samwise.clone(),
+        amount: 100_0000000,
+    };
+    approve(&e, &verifier_client, &message_id, &attestation);
+    adapter_client.attest_lock(&ethereum, &message_id, &attestation);
+
+    // collateral locked on one chain cannot be released on another
+    adapter_client.release(
+        &samwise,
+        &solana,
+        &String::from_str(&e, "recipient"),
+        &1_0000000,
+    );
+}
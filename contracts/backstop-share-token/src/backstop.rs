@@ -0,0 +1,15 @@
+/**
+ * Partial client for the backstop contract that holds the shares
+ */
+use soroban_sdk::{contractclient, Address, Env};
+
+#[allow(dead_code)]
+#[contractclient(name = "BackstopClient")]
+pub trait Backstop {
+    /// Fetch the shares of a pool's backstop held by `user` that are not queued for withdrawal
+    fn share_balance(e: Env, pool: Address, user: Address) -> i128;
+
+    /// Transfer shares of a pool's backstop that are not queued for withdrawal. Can only be
+    /// called by the pool's share token.
+    fn transfer_shares(e: Env, pool: Address, from: Address, to: Address, amount: i128);
+}
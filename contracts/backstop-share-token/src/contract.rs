@@ -0,0 +1,162 @@
+//! A SEP-41 token representing the shares of a pool's backstop. The backstop holds the balances,
+//! so deposits, withdrawal queues, and emissions keep working as they do for any backstop
+//! position. Shares queued for withdrawal are locked, and are not part of a holder's balance.
+use crate::allowance::{read_allowance, spend_allowance, write_allowance};
+use crate::backstop::BackstopClient;
+use crate::storage_types::{DataKey, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD};
+use soroban_sdk::token::{self, Interface as _};
+use soroban_sdk::{contract, contractimpl, Address, Env, String};
+use soroban_token_sdk::metadata::TokenMetadata;
+use soroban_token_sdk::TokenUtils;
+
+fn check_nonnegative_amount(amount: i128) {
+    if amount < 0 {
+        panic!("negative amount is not allowed: {}", amount)
+    }
+}
+
+fn read_backstop(e: &Env) -> Address {
+    e.storage().instance().get(&DataKey::Backstop).unwrap()
+}
+
+fn read_pool(e: &Env) -> Address {
+    e.storage().instance().get(&DataKey::Pool).unwrap()
+}
+
+#[contract]
+pub struct BackstopShareToken;
+
+#[contractimpl]
+impl BackstopShareToken {
+    pub fn __constructor(e: Env, backstop: Address, pool: Address) {
+        e.storage().instance().set(&DataKey::Backstop, &backstop);
+        e.storage().instance().set(&DataKey::Pool, &pool);
+        TokenUtils::new(&e).metadata().set_metadata(&TokenMetadata {
+            decimal: 7,
+            name: String::from_str(&e, "TrustBridge Backstop Shares"),
+            symbol: String::from_str(&e, "TBS"),
+        });
+    }
+
+    /// Fetch the backstop holding the shares
+    pub fn backstop(e: Env) -> Address {
+        read_backstop(&e)
+    }
+
+    /// Fetch the pool whose backstop the shares are of
+    pub fn pool(e: Env) -> Address {
+        read_pool(&e)
+    }
+
+    /// (Backstop only) Emit a mint event for shares added to `to`'s balance
+    pub fn notify_mint(e: Env, to: Address, amount: i128) {
+        let backstop = read_backstop(&e);
+        backstop.require_auth();
+
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        TokenUtils::new(&e).events().mint(backstop, to, amount);
+    }
+
+    /// (Backstop only) Emit a burn event for shares removed from `from`'s balance
+    pub fn notify_burn(e: Env, from: Address, amount: i128) {
+        read_backstop(&e).require_auth();
+
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        TokenUtils::new(&e).events().burn(from, amount);
+    }
+}
+
+#[contractimpl]
+impl token::Interface for BackstopShareToken {
+    fn allowance(e: Env, from: Address, spender: Address) -> i128 {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        read_allowance(&e, from, spender).amount
+    }
+
+    fn approve(e: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+
+        check_nonnegative_amount(amount);
+
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        write_allowance(&e, from.clone(), spender.clone(), amount, expiration_ledger);
+        TokenUtils::new(&e)
+            .events()
+            .approve(from, spender, amount, expiration_ledger);
+    }
+
+    fn balance(e: Env, id: Address) -> i128 {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        BackstopClient::new(&e, &read_backstop(&e)).share_balance(&read_pool(&e), &id)
+    }
+
+    fn transfer(e: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+
+        check_nonnegative_amount(amount);
+
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        BackstopClient::new(&e, &read_backstop(&e)).transfer_shares(
+            &read_pool(&e),
+            &from,
+            &to,
+            &amount,
+        );
+        TokenUtils::new(&e).events().transfer(from, to, amount);
+    }
+
+    fn transfer_from(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+
+        check_nonnegative_amount(amount);
+
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        spend_allowance(&e, from.clone(), spender, amount);
+        BackstopClient::new(&e, &read_backstop(&e)).transfer_shares(
+            &read_pool(&e),
+            &from,
+            &to,
+            &amount,
+        );
+        TokenUtils::new(&e).events().transfer(from, to, amount)
+    }
+
+    fn burn(_e: Env, _from: Address, _amount: i128) {
+        panic!("shares can only be burned by withdrawing from the backstop")
+    }
+
+    fn burn_from(_e: Env, _spender: Address, _from: Address, _amount: i128) {
+        panic!("shares can only be burned by withdrawing from the backstop")
+    }
+
+    fn decimals(e: Env) -> u32 {
+        TokenUtils::new(&e).metadata().get_metadata().decimal
+    }
+
+    fn name(e: Env) -> String {
+        TokenUtils::new(&e).metadata().get_metadata().name
+    }
+
+    fn symbol(e: Env) -> String {
+        TokenUtils::new(&e).metadata().get_metadata().symbol
+    }
+}
@@ -0,0 +1,9 @@
+#![no_std]
+
+mod allowance;
+mod backstop;
+mod contract;
+mod storage_types;
+mod test;
+
+pub use crate::contract::{BackstopShareToken, BackstopShareTokenClient};
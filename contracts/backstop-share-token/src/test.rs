@@ -0,0 +1,167 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::{BackstopShareToken, BackstopShareTokenClient};
+use soroban_sdk::{
+    contract, contractimpl, map,
+    testutils::{Address as _, Ledger},
+    token::TokenClient,
+    Address, Env, Map, String, Symbol,
+};
+
+/// Holds the shares of a single pool's backstop, and only allows the share token to move them
+#[contract]
+struct MockBackstop;
+
+#[contractimpl]
+impl MockBackstop {
+    pub fn set_token(e: Env, token: Address) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "token"), &token);
+    }
+
+    pub fn set_shares(e: Env, user: Address, shares: i128) {
+        let mut balances = Self::balances(&e);
+        balances.set(user, shares);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "balances"), &balances);
+    }
+
+    pub fn share_balance(e: Env, _pool: Address, user: Address) -> i128 {
+        Self::balances(&e).get(user).unwrap_or(0)
+    }
+
+    pub fn transfer_shares(e: Env, _pool: Address, from: Address, to: Address, amount: i128) {
+        let token: Address = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "token"))
+            .unwrap();
+        token.require_auth();
+        let mut balances = Self::balances(&e);
+        let from_balance = balances.get(from.clone()).unwrap_or(0);
+        if from_balance < amount {
+            panic!("insufficient shares");
+        }
+        balances.set(from, from_balance - amount);
+        balances.set(to.clone(), balances.get(to).unwrap_or(0) + amount);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "balances"), &balances);
+    }
+
+    fn balances(e: &Env) -> Map<Address, i128> {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(e, "balances"))
+            .unwrap_or(map![e])
+    }
+}
+
+fn create_share_token<'a>(
+    e: &Env,
+) -> (
+    BackstopShareTokenClient<'a>,
+    TokenClient<'a>,
+    MockBackstopClient<'a>,
+) {
+    let backstop = e.register(MockBackstop, ());
+    let pool = Address::generate(e);
+    let token = e.register(BackstopShareToken, (backstop.clone(), pool));
+    let backstop_client = MockBackstopClient::new(e, &backstop);
+    backstop_client.set_token(&token);
+    (
+        BackstopShareTokenClient::new(e, &token),
+        TokenClient::new(e, &token),
+        backstop_client,
+    )
+}
+
+#[test]
+fn test_metadata() {
+    let e = Env::default();
+    let (share_token, token, backstop) = create_share_token(&e);
+
+    assert_eq!(share_token.backstop(), backstop.address);
+    assert_eq!(token.decimals(), 7);
+    assert_eq!(
+        token.name(),
+        String::from_str(&e, "TrustBridge Backstop Shares")
+    );
+    assert_eq!(token.symbol(), String::from_str(&e, "TBS"));
+}
+
+#[test]
+fn test_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (_, token, backstop) = create_share_token(&e);
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+
+    backstop.set_shares(&samwise, &100_0000000);
+    assert_eq!(token.balance(&samwise), 100_0000000);
+
+    token.transfer(&samwise, &frodo, &40_0000000);
+    assert_eq!(token.balance(&samwise), 60_0000000);
+    assert_eq!(token.balance(&frodo), 40_0000000);
+}
+
+#[test]
+fn test_transfer_from() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+    let (_, token, backstop) = create_share_token(&e);
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let pippin = Address::generate(&e);
+
+    backstop.set_shares(&samwise, &100_0000000);
+    token.approve(&samwise, &pippin, &50_0000000, &200);
+    assert_eq!(token.allowance(&samwise, &pippin), 50_0000000);
+
+    token.transfer_from(&pippin, &samwise, &frodo, &30_0000000);
+    assert_eq!(token.allowance(&samwise, &pippin), 20_0000000);
+    assert_eq!(token.balance(&samwise), 70_0000000);
+    assert_eq!(token.balance(&frodo), 30_0000000);
+}
+
+#[test]
+#[should_panic(expected = "insufficient allowance")]
+fn test_transfer_from_insufficient_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+    let (_, token, backstop) = create_share_token(&e);
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let pippin = Address::generate(&e);
+
+    backstop.set_shares(&samwise, &100_0000000);
+    token.approve(&samwise, &pippin, &10_0000000, &200);
+    token.transfer_from(&pippin, &samwise, &frodo, &30_0000000);
+}
+
+#[test]
+#[should_panic(expected = "shares can only be burned by withdrawing from the backstop")]
+fn test_burn() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (_, token, backstop) = create_share_token(&e);
+    let samwise = Address::generate(&e);
+
+    backstop.set_shares(&samwise, &100_0000000);
+    token.burn(&samwise, &10_0000000);
+}
+
+#[test]
+#[should_panic]
+fn test_notify_mint_requires_backstop() {
+    let e = Env::default();
+    let (share_token, _, _) = create_share_token(&e);
+
+    share_token.notify_mint(&Address::generate(&e), &10_0000000);
+}
@@ -0,0 +1,351 @@
+use cast::i128;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{contracttype, unwrap::UnwrapOptimized, Address, Env};
+
+use crate::{
+    constants::{BACKSTOP_APR_WINDOW, SCALAR_14, SCALAR_7, SECONDS_PER_YEAR},
+    dependencies::CometClient,
+    emissions::calc_emission_data,
+    storage,
+};
+
+/// The APR of a pool's backstop over a trailing window, split by the source of the yield
+#[derive(Clone)]
+#[contracttype]
+pub struct BackstopApr {
+    pub interest: i128,  // the APR from interest donated by the pool (7 decimals)
+    pub emissions: i128, // the APR from BLND emissions to unqueued deposits (7 decimals)
+    pub lp_fees: i128,   // the APR from swap fees earned by the backstop token (7 decimals)
+    pub total: i128,     // the sum of the interest, emission, and LP fee APRs (7 decimals)
+    pub window: u64,     // the length in seconds of the window the APR was measured over
+}
+
+/// A checkpoint of the cumulative sources of yield of a pool's backstop
+#[derive(Clone)]
+#[contracttype]
+pub struct YieldCheckpoint {
+    pub time: u64,          // the time the checkpoint was taken
+    pub donations: i128,    // the cumulative backstop tokens donated to the pool's backstop
+    pub emis_index: i128,   // the backstop's emission index (14 decimals)
+    pub blnd_per_tkn: i128, // the BLND held by each backstop token (7 decimals)
+    pub usdc_per_tkn: i128, // the USDC held by each backstop token (7 decimals)
+}
+
+/// The checkpoints the APR of a pool's backstop is measured from
+#[derive(Clone)]
+#[contracttype]
+pub struct YieldWindow {
+    pub prev: Option<YieldCheckpoint>, // the checkpoint taken before the latest, if any
+    pub last: YieldCheckpoint,         // the latest checkpoint
+}
+
+/// Checkpoint the sources of yield of the pool's backstop. A new checkpoint is only taken once
+/// the latest is a full window old, so the APR is always measured over one to two windows.
+///
+/// ### Arguments
+/// * `pool` - The pool address
+pub fn checkpoint_backstop_yield(e: &Env, pool: &Address) {
+    let window = match storage::get_yield_window(e, pool) {
+        Some(window) => {
+            if e.ledger().timestamp() < window.last.time + BACKSTOP_APR_WINDOW {
+                return;
+            }
+            YieldWindow {
+                prev: Some(window.last),
+                last: load_yield_checkpoint(e, pool),
+            }
+        }
+        None => YieldWindow {
+            prev: None,
+            last: load_yield_checkpoint(e, pool),
+        },
+    };
+    storage::set_yield_window(e, pool, &window);
+}
+
+/// Load the APR of the pool's backstop, measured from its oldest checkpoint to the current
+/// ledger. Each source of yield is annualized against the pool's current backstop deposits:
+/// * interest - the backstop tokens donated to the pool's backstop
+/// * emissions - the BLND emitted to unqueued deposits, valued in backstop tokens
+/// * lp fees - the growth of the backstop token's invariant per LP token
+///
+/// ### Arguments
+/// * `pool` - The pool address
+pub fn load_backstop_apr(e: &Env, pool: &Address) -> BackstopApr {
+    let mut apr = BackstopApr {
+        interest: 0,
+        emissions: 0,
+        lp_fees: 0,
+        total: 0,
+        window: 0,
+    };
+    let start = match storage::get_yield_window(e, pool) {
+        Some(window) => window.prev.unwrap_or(window.last),
+        None => return apr,
+    };
+    apr.window = e.ledger().timestamp() - start.time;
+    let pool_balance = storage::get_pool_balance(e, pool);
+    if apr.window == 0 || pool_balance.tokens <= 0 {
+        return apr;
+    }
+    let end = load_yield_checkpoint(e, pool);
+
+    let interest = (end.donations - start.donations)
+        .fixed_div_floor(pool_balance.tokens, SCALAR_7)
+        .unwrap_optimized();
+
+    let unqueued_shares = pool_balance.shares - pool_balance.q4w;
+    let unqueued_tokens = pool_balance.convert_to_tokens(unqueued_shares);
+    let emissions = if unqueued_tokens > 0 && end.blnd_per_tkn > 0 {
+        let blnd = unqueued_shares
+            .fixed_mul_floor(end.emis_index - start.emis_index, SCALAR_14)
+            .unwrap_optimized();
+        // the backstop token is 80% BLND by value, so each BLND is worth 80% of a token over the
+        // BLND held by each token
+        (blnd * 4 / 5)
+            .fixed_div_floor(end.blnd_per_tkn, SCALAR_7)
+            .unwrap_optimized()
+            .fixed_div_floor(unqueued_tokens, SCALAR_7)
+            .unwrap_optimized()
+    } else {
+        0
+    };
+
+    let lp_fees = if start.blnd_per_tkn > 0 && start.usdc_per_tkn > 0 {
+        // the invariant of the 80/20 backstop token per LP token, BLND^0.8 * USDC^0.2, only grows
+        // from fees. Its growth is approximated as a fifth of the growth of BLND^4 * USDC.
+        let blnd_growth = end
+            .blnd_per_tkn
+            .fixed_div_floor(start.blnd_per_tkn, SCALAR_7)
+            .unwrap_optimized();
+        let mut growth = end
+            .usdc_per_tkn
+            .fixed_div_floor(start.usdc_per_tkn, SCALAR_7)
+            .unwrap_optimized();
+        for _ in 0..4 {
+            growth = growth
+                .fixed_mul_floor(blnd_growth, SCALAR_7)
+                .unwrap_optimized();
+        }
+        (growth - SCALAR_7) / 5
+    } else {
+        0
+    };
+
+    let window = i128(apr.window);
+    apr.interest = interest.max(0) * SECONDS_PER_YEAR / window;
+    apr.emissions = emissions.max(0) * SECONDS_PER_YEAR / window;
+    apr.lp_fees = lp_fees.max(0) * SECONDS_PER_YEAR / window;
+    apr.total = apr.interest + apr.emissions + apr.lp_fees;
+    apr
+}
+
+/// Load a checkpoint of the sources of yield of the pool's backstop at the current ledger
+fn load_yield_checkpoint(e: &Env, pool: &Address) -> YieldCheckpoint {
+    let emis_index = match storage::get_backstop_emis_data(e, pool) {
+        Some(emis_data) => {
+            calc_emission_data(e, &emis_data, &storage::get_pool_balance(e, pool)).index
+        }
+        None => 0,
+    };
+
+    let comet_client = CometClient::new(e, &storage::get_backstop_token(e));
+    let total_comet_shares = comet_client.get_total_supply();
+    let (blnd_per_tkn, usdc_per_tkn) = if total_comet_shares > 0 {
+        (
+            comet_client
+                .get_balance(&storage::get_blnd_token(e))
+                .fixed_div_floor(total_comet_shares, SCALAR_7)
+                .unwrap_optimized(),
+            comet_client
+                .get_balance(&storage::get_usdc_token(e))
+                .fixed_div_floor(total_comet_shares, SCALAR_7)
+                .unwrap_optimized(),
+        )
+    } else {
+        (0, 0)
+    };
+
+    YieldCheckpoint {
+        time: e.ledger().timestamp(),
+        donations: storage::get_pool_donations(e, pool),
+        emis_index,
+        blnd_per_tkn,
+        usdc_per_tkn,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+
+    use crate::{
+        backstop::PoolBalance,
+        storage::BackstopEmissionData,
+        testutils::{
+            create_backstop, create_blnd_token, create_comet_lp_pool_with_tokens_per_share,
+            create_usdc_token,
+        },
+    };
+
+    use super::*;
+
+    /// Setup a backstop with a backstop token holding 10 BLND and 0.25 USDC per token, and a pool
+    /// with 1000 backstop tokens and shares and emissions of 0.001 BLND per second
+    fn setup_backstop(e: &Env, pool: &Address) -> Address {
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(e);
+        let backstop_id = create_backstop(e);
+        let (blnd_id, _) = create_blnd_token(e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            10_0000000,
+            &usdc_id,
+            0_2500000,
+        );
+
+        e.as_contract(&backstop_id, || {
+            storage::set_pool_balance(
+                e,
+                pool,
+                &PoolBalance {
+                    shares: 1000_0000000,
+                    tokens: 1000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_backstop_emis_data(
+                e,
+                pool,
+                &BackstopEmissionData {
+                    expiration: 1713139200 + 30 * 24 * 60 * 60,
+                    eps: 0_00100000000000,
+                    index: 0,
+                    last_time: 1713139200,
+                },
+            );
+        });
+        backstop_id
+    }
+
+    #[test]
+    fn test_load_backstop_apr() {
+        let e = Env::default();
+        let pool = Address::generate(&e);
+        let backstop_id = setup_backstop(&e, &pool);
+
+        e.as_contract(&backstop_id, || {
+            checkpoint_backstop_yield(&e, &pool);
+            let apr = load_backstop_apr(&e, &pool);
+            assert_eq!(apr.total, 0);
+            assert_eq!(apr.window, 0);
+        });
+
+        e.ledger()
+            .set_timestamp(e.ledger().timestamp() + BACKSTOP_APR_WINDOW);
+        e.as_contract(&backstop_id, || {
+            storage::set_pool_donations(&e, &pool, &10_0000000);
+
+            // 10 tokens donated over 7 days to 1000 tokens
+            // 604.8 BLND emitted over 7 days, worth 48.384 tokens
+            let apr = load_backstop_apr(&e, &pool);
+            assert_eq!(apr.interest, 0_5214285);
+            assert_eq!(apr.emissions, 2_5228800);
+            assert_eq!(apr.lp_fees, 0);
+            assert_eq!(apr.total, 3_0443085);
+            assert_eq!(apr.window, BACKSTOP_APR_WINDOW);
+        });
+    }
+
+    #[test]
+    fn test_load_backstop_apr_lp_fees() {
+        let e = Env::default();
+        let pool = Address::generate(&e);
+        let backstop_id = setup_backstop(&e, &pool);
+
+        e.as_contract(&backstop_id, || {
+            storage::set_yield_window(
+                &e,
+                &pool,
+                &YieldWindow {
+                    prev: Some(YieldCheckpoint {
+                        time: 1713139200 - BACKSTOP_APR_WINDOW,
+                        donations: 0,
+                        emis_index: 0,
+                        blnd_per_tkn: 10_0000000,
+                        usdc_per_tkn: 0_2475000,
+                    }),
+                    last: YieldCheckpoint {
+                        time: 1713139200,
+                        donations: 0,
+                        emis_index: 0,
+                        blnd_per_tkn: 10_0000000,
+                        usdc_per_tkn: 0_2500000,
+                    },
+                },
+            );
+
+            // the USDC per token grew by ~1.01% over 7 days
+            let apr = load_backstop_apr(&e, &pool);
+            assert_eq!(apr.interest, 0);
+            assert_eq!(apr.emissions, 0);
+            assert_eq!(apr.lp_fees, 0_1053390);
+            assert_eq!(apr.total, 0_1053390);
+            assert_eq!(apr.window, BACKSTOP_APR_WINDOW);
+        });
+    }
+
+    #[test]
+    fn test_checkpoint_backstop_yield() {
+        let e = Env::default();
+        let pool = Address::generate(&e);
+        let backstop_id = setup_backstop(&e, &pool);
+
+        e.as_contract(&backstop_id, || {
+            assert!(storage::get_yield_window(&e, &pool).is_none());
+            checkpoint_backstop_yield(&e, &pool);
+            let window = storage::get_yield_window(&e, &pool).unwrap();
+            assert!(window.prev.is_none());
+            assert_eq!(window.last.time, 1713139200);
+            assert_eq!(window.last.blnd_per_tkn, 10_0000000);
+            assert_eq!(window.last.usdc_per_tkn, 0_2500000);
+        });
+
+        // a checkpoint is not taken until the latest is a full window old
+        e.ledger()
+            .set_timestamp(e.ledger().timestamp() + BACKSTOP_APR_WINDOW - 1);
+        e.as_contract(&backstop_id, || {
+            checkpoint_backstop_yield(&e, &pool);
+            let window = storage::get_yield_window(&e, &pool).unwrap();
+            assert!(window.prev.is_none());
+            assert_eq!(window.last.time, 1713139200);
+        });
+
+        e.ledger().set_timestamp(e.ledger().timestamp() + 1);
+        e.as_contract(&backstop_id, || {
+            storage::set_pool_donations(&e, &pool, &10_0000000);
+            checkpoint_backstop_yield(&e, &pool);
+            let window = storage::get_yield_window(&e, &pool).unwrap();
+            assert_eq!(window.prev.unwrap().time, 1713139200);
+            assert_eq!(window.last.time, 1713139200 + BACKSTOP_APR_WINDOW);
+            assert_eq!(window.last.donations, 10_0000000);
+            assert_eq!(window.last.emis_index, 0_6048000_0000000);
+        });
+    }
+}
@@ -1,21 +1,44 @@
-use crate::{contract::require_nonnegative, emissions, storage, BackstopError};
+use crate::{
+    contract::require_nonnegative, emissions, events::BackstopEvents, storage, BackstopError,
+};
 use sep_41_token::TokenClient;
 use soroban_sdk::{panic_with_error, Address, Env};
 
-use super::require_is_from_pool_factory;
+use super::{notify_share_mint, require_is_from_pool_factory, withdrawal::sweep_expired_q4w};
 
-/// Perform a deposit into the backstop module
+/// Perform a deposit into the backstop module. Any of the user's expired queued shares are withdrawn
+/// in the same call, unless they opted out.
 pub fn execute_deposit(e: &Env, from: &Address, pool_address: &Address, amount: i128) -> i128 {
+    let pool_balance = storage::get_pool_balance(e, pool_address);
+    require_is_from_pool_factory(e, pool_address, pool_balance.shares);
+    do_deposit(e, from, pool_address, amount)
+}
+
+/// Perform a deposit into the backstop module on behalf of the pool factory while the pool is
+/// being deployed. The pool factory is trusted to only deposit into pools it deployed, and cannot
+/// be called back to verify the pool during its own deployment.
+pub fn execute_factory_deposit(
+    e: &Env,
+    from: &Address,
+    pool_address: &Address,
+    amount: i128,
+) -> i128 {
+    do_deposit(e, from, pool_address, amount)
+}
+
+fn do_deposit(e: &Env, from: &Address, pool_address: &Address, amount: i128) -> i128 {
     require_nonnegative(e, amount);
     if from == pool_address || from == &e.current_contract_address() {
         panic_with_error!(e, &BackstopError::BadRequest)
     }
     let mut pool_balance = storage::get_pool_balance(e, pool_address);
-    require_is_from_pool_factory(e, pool_address, pool_balance.shares);
     let mut user_balance = storage::get_user_balance(e, pool_address, from);
 
     emissions::update_emissions(e, pool_address, &pool_balance, from, &user_balance);
 
+    let (swept_shares, swept_tokens) =
+        sweep_expired_q4w(e, from, pool_address, &mut pool_balance, &mut user_balance);
+
     let backstop_token_client = TokenClient::new(e, &storage::get_backstop_token(e));
     backstop_token_client.transfer(from, &e.current_contract_address(), &amount);
 
@@ -29,17 +52,34 @@ pub fn execute_deposit(e: &Env, from: &Address, pool_address: &Address, amount:
     storage::set_pool_balance(e, pool_address, &pool_balance);
     storage::set_user_balance(e, pool_address, from, &user_balance);
 
+    if swept_tokens > 0 {
+        backstop_token_client.transfer(&e.current_contract_address(), from, &swept_tokens);
+        BackstopEvents::withdraw(
+            e,
+            pool_address.clone(),
+            from.clone(),
+            swept_shares,
+            swept_tokens,
+        );
+    }
+
+    notify_share_mint(e, pool_address, from, to_mint);
     to_mint
 }
 
 #[cfg(test)]
 mod tests {
-    use soroban_sdk::{testutils::Address as _, Address};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        Address,
+    };
 
     use crate::{
-        backstop::{execute_donate, execute_draw},
+        backstop::{execute_donate, execute_draw, execute_queue_withdrawal},
         constants::SCALAR_7,
-        testutils::{create_backstop, create_backstop_token, create_mock_pool_factory},
+        testutils::{
+            create_backstop, create_backstop_token, create_mock_pool, create_mock_pool_factory,
+        },
     };
 
     use super::*;
@@ -107,6 +147,37 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_factory_deposit() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool_0_id = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        // pool is not yet registered with the pool factory
+        create_mock_pool_factory(&e, &backstop_address);
+
+        e.as_contract(&backstop_address, || {
+            let shares = execute_factory_deposit(&e, &samwise, &pool_0_id, 30_0000000);
+            assert_eq!(shares, 30_0000000);
+
+            let new_pool_balance = storage::get_pool_balance(&e, &pool_0_id);
+            assert_eq!(new_pool_balance.shares, 30_0000000);
+            assert_eq!(new_pool_balance.tokens, 30_0000000);
+
+            let new_user_balance = storage::get_user_balance(&e, &pool_0_id, &samwise);
+            assert_eq!(new_user_balance.shares, 30_0000000);
+            assert_eq!(backstop_token_client.balance(&backstop_address), 30_0000000);
+        });
+    }
+
     #[test]
     #[should_panic]
     fn test_execute_deposit_too_many_tokens() {
@@ -295,4 +366,111 @@ mod tests {
             execute_deposit(&e, &samwise, &pool_0_id, SCALAR_7);
         });
     }
+
+    #[test]
+    fn test_execute_deposit_sweeps_expired_q4w() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let (pool_address, _) = create_mock_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &150_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.ledger().set(LedgerInfo {
+            protocol_version: 22,
+            sequence_number: 200,
+            timestamp: 10000,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 100_0000000);
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 42_0000000);
+        });
+
+        e.ledger().set_timestamp(10000 + 10 * 24 * 60 * 60);
+        e.as_contract(&backstop_address, || {
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 8_0000000);
+        });
+
+        // only the first queued withdrawal has expired
+        e.ledger().set_timestamp(10000 + 17 * 24 * 60 * 60 + 1);
+        e.as_contract(&backstop_address, || {
+            let to_mint = execute_deposit(&e, &samwise, &pool_address, 10_0000000);
+            assert_eq!(to_mint, 10_0000000);
+
+            let new_user_balance = storage::get_user_balance(&e, &pool_address, &samwise);
+            assert_eq!(new_user_balance.shares, 60_0000000);
+            assert_eq!(new_user_balance.q4w.len(), 1);
+            assert_eq!(new_user_balance.q4w.get_unchecked(0).amount, 8_0000000);
+
+            let new_pool_balance = storage::get_pool_balance(&e, &pool_address);
+            assert_eq!(new_pool_balance.shares, 68_0000000);
+            assert_eq!(new_pool_balance.tokens, 68_0000000);
+            assert_eq!(new_pool_balance.q4w, 8_0000000);
+
+            assert_eq!(backstop_token_client.balance(&samwise), 82_0000000);
+            assert_eq!(backstop_token_client.balance(&backstop_address), 68_0000000);
+        });
+    }
+
+    #[test]
+    fn test_execute_deposit_sweep_opt_out() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let (pool_address, _) = create_mock_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &150_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.ledger().set(LedgerInfo {
+            protocol_version: 22,
+            sequence_number: 200,
+            timestamp: 10000,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 100_0000000);
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 42_0000000);
+            storage::set_q4w_sweep_opt_out(&e, &pool_address, &samwise, true);
+        });
+
+        e.ledger().set_timestamp(10000 + 17 * 24 * 60 * 60 + 1);
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 10_0000000);
+
+            let new_user_balance = storage::get_user_balance(&e, &pool_address, &samwise);
+            assert_eq!(new_user_balance.shares, 68_0000000);
+            assert_eq!(new_user_balance.q4w.len(), 1);
+
+            let new_pool_balance = storage::get_pool_balance(&e, &pool_address);
+            assert_eq!(new_pool_balance.shares, 110_0000000);
+            assert_eq!(new_pool_balance.q4w, 42_0000000);
+
+            assert_eq!(backstop_token_client.balance(&samwise), 40_0000000);
+        });
+    }
 }
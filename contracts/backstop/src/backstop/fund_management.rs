@@ -19,26 +19,68 @@ pub fn execute_draw(e: &Env, pool_address: &Address, amount: i128, to: &Address)
     backstop_token.transfer(&e.current_contract_address(), to, &amount);
 }
 
-/// Perform a donation to a pool's backstop
-pub fn execute_donate(e: &Env, from: &Address, pool_address: &Address, amount: i128) {
+/// Perform a donation to a pool's backstop and attribute it to the donor
+///
+/// Returns (the donor's cumulative donations to the pool, the pool's cumulative donations)
+pub fn execute_donate(
+    e: &Env,
+    from: &Address,
+    pool_address: &Address,
+    amount: i128,
+) -> (i128, i128) {
     require_nonnegative(e, amount);
     if from == pool_address || from == &e.current_contract_address() {
         panic_with_error!(e, &BackstopError::BadRequest)
     }
 
+    donate(e, from, from, pool_address, amount)
+}
+
+/// Perform a donation of an auction bid the pool collected from a filler to the pool's backstop,
+/// and attribute it to the filler
+///
+/// Returns (the filler's cumulative donations to the pool, the pool's cumulative donations)
+pub fn execute_donate_bid(
+    e: &Env,
+    filler: &Address,
+    pool_address: &Address,
+    amount: i128,
+) -> (i128, i128) {
+    require_nonnegative(e, amount);
+    if filler == pool_address || filler == &e.current_contract_address() {
+        panic_with_error!(e, &BackstopError::BadRequest)
+    }
+
+    donate(e, pool_address, filler, pool_address, amount)
+}
+
+/// Take backstop tokens from `payer` into the pool's backstop and attribute them to `donor`
+fn donate(
+    e: &Env,
+    payer: &Address,
+    donor: &Address,
+    pool_address: &Address,
+    amount: i128,
+) -> (i128, i128) {
     let mut pool_balance = storage::get_pool_balance(e, pool_address);
     require_is_from_pool_factory(e, pool_address, pool_balance.shares);
 
     let backstop_token = TokenClient::new(e, &storage::get_backstop_token(e));
     backstop_token.transfer_from(
         &e.current_contract_address(),
-        from,
+        payer,
         &e.current_contract_address(),
         &amount,
     );
 
     pool_balance.deposit(amount, 0);
     storage::set_pool_balance(e, pool_address, &pool_balance);
+
+    let donor_total = storage::get_donations(e, pool_address, donor) + amount;
+    storage::set_donations(e, pool_address, donor, &donor_total);
+    let pool_total = storage::get_pool_donations(e, pool_address) + amount;
+    storage::set_pool_donations(e, pool_address, &pool_total);
+    (donor_total, pool_total)
 }
 
 #[cfg(test)]
@@ -78,12 +120,26 @@ mod tests {
 
         backstop_token_client.approve(&samwise, &backstop_id, &30_0000000, &e.ledger().sequence());
         e.as_contract(&backstop_id, || {
-            execute_donate(&e, &samwise, &pool_0_id, 30_0000000);
+            let (donor_total, pool_total) = execute_donate(&e, &samwise, &pool_0_id, 30_0000000);
+            assert_eq!(donor_total, 30_0000000);
+            assert_eq!(pool_total, 30_0000000);
 
             let new_pool_balance = storage::get_pool_balance(&e, &pool_0_id);
             assert_eq!(new_pool_balance.shares, 25_0000000);
             assert_eq!(new_pool_balance.tokens, 55_0000000);
         });
+
+        // donations accumulate per donor and per pool
+        backstop_token_client.approve(&samwise, &backstop_id, &5_0000000, &e.ledger().sequence());
+        backstop_token_client.approve(&frodo, &backstop_id, &10_0000000, &e.ledger().sequence());
+        e.as_contract(&backstop_id, || {
+            execute_donate(&e, &samwise, &pool_0_id, 5_0000000);
+            let (donor_total, pool_total) = execute_donate(&e, &frodo, &pool_0_id, 10_0000000);
+            assert_eq!(donor_total, 10_0000000);
+            assert_eq!(pool_total, 45_0000000);
+            assert_eq!(storage::get_donations(&e, &pool_0_id, &samwise), 35_0000000);
+            assert_eq!(storage::get_pool_donations(&e, &pool_0_id), 45_0000000);
+        });
     }
 
     #[test]
@@ -146,6 +202,52 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_donate_bid() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let backstop_id = create_backstop(&e);
+        let pool_0_id = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_id, &bombadil);
+        backstop_token_client.mint(&pool_0_id, &100_0000000);
+        backstop_token_client.mint(&frodo, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_id);
+        mock_pool_factory_client.set_pool(&pool_0_id);
+
+        // initialize pool 0 with funds
+        e.as_contract(&backstop_id, || {
+            execute_deposit(&e, &frodo, &pool_0_id, 25_0000000);
+        });
+
+        backstop_token_client.approve(
+            &pool_0_id,
+            &backstop_id,
+            &10_0000000,
+            &e.ledger().sequence(),
+        );
+        e.as_contract(&backstop_id, || {
+            // the bid is taken from the pool and attributed to the filler
+            let (donor_total, pool_total) =
+                execute_donate_bid(&e, &samwise, &pool_0_id, 10_0000000);
+            assert_eq!(donor_total, 10_0000000);
+            assert_eq!(pool_total, 10_0000000);
+            assert_eq!(storage::get_donations(&e, &pool_0_id, &samwise), 10_0000000);
+            assert_eq!(storage::get_donations(&e, &pool_0_id, &pool_0_id), 0);
+
+            let new_pool_balance = storage::get_pool_balance(&e, &pool_0_id);
+            assert_eq!(new_pool_balance.shares, 25_0000000);
+            assert_eq!(new_pool_balance.tokens, 35_0000000);
+        });
+        assert_eq!(backstop_token_client.balance(&pool_0_id), 90_0000000);
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1000)")]
     fn test_execute_donate_from_is_self() {
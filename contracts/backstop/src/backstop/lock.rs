@@ -0,0 +1,460 @@
+use cast::i128;
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{
+    contracttype, panic_with_error, unwrap::UnwrapOptimized, vec, Address, Env, Vec,
+};
+
+use crate::{
+    constants::{LOCK_TIERS, MAX_DEPOSIT_LOCKS, SCALAR_7},
+    contract::require_nonnegative,
+    emissions::load_active_vestings,
+    storage, BackstopError,
+};
+
+use super::deposit::execute_deposit;
+
+/// Shares of a deposit that are locked in exchange for an emission multiplier
+#[derive(Clone)]
+#[contracttype]
+pub struct DepositLock {
+    pub shares: i128,     // the amount of shares locked
+    pub multiplier: i128, // the emission multiplier of the locked shares (7 decimals)
+    pub exp: u64,         // the expiration of the lock
+    pub index: i128,      // the emission index the lock's bonus was last claimed at (14 decimals)
+    pub last_time: u64,   // the timestamp the lock's bonus was last claimed at
+    pub remainder: i128,  // the unpaid fraction of the lock's bonus (7 decimals of a token unit)
+}
+
+/// Perform a deposit into the backstop module, and lock the minted shares for the duration of a
+/// lock tier. Locked shares cannot be queued for withdrawal or transferred until the lock expires.
+///
+/// Returns the number of shares minted and locked
+///
+/// ### Arguments
+/// * `from` - The address depositing into the backstop
+/// * `pool_address` - The address of the pool
+/// * `amount` - The amount of tokens to deposit
+/// * `tier` - The index of the lock tier in `LOCK_TIERS`
+///
+/// ### Panics
+/// If the tier does not exist or `from` has too many locks. Expired locks keep their slot until
+/// their bonus is claimed.
+pub fn execute_deposit_locked(
+    e: &Env,
+    from: &Address,
+    pool_address: &Address,
+    amount: i128,
+    tier: u32,
+) -> (i128, DepositLock) {
+    let (duration, multiplier) = match LOCK_TIERS.get(tier as usize) {
+        Some(lock_tier) => *lock_tier,
+        None => panic_with_error!(e, BackstopError::BadRequest),
+    };
+    let mut locks = storage::get_deposit_locks(e, pool_address, from);
+    if locks.len() >= MAX_DEPOSIT_LOCKS {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+
+    // the deposit updates the backstop's emission index, so the lock only earns a bonus on
+    // emissions from this point forward
+    let to_mint = execute_deposit(e, from, pool_address, amount);
+    let index = match storage::get_backstop_emis_data(e, pool_address) {
+        Some(emis_data) => emis_data.index,
+        None => 0,
+    };
+    let lock = DepositLock {
+        shares: to_mint,
+        multiplier,
+        exp: e.ledger().timestamp() + duration,
+        index,
+        last_time: e.ledger().timestamp(),
+        remainder: 0,
+    };
+    locks.push_back(lock.clone());
+    storage::set_deposit_locks(e, pool_address, from, &locks);
+    (to_mint, lock)
+}
+
+/// Require that `amount` of `from`'s unqueued shares are not locked, either by a deposit lock or
+/// by a vested claim that has not fully vested
+///
+/// ### Arguments
+/// * `from` - The owner of the shares
+/// * `pool_address` - The address of the pool
+/// * `shares` - The user's unqueued shares
+/// * `amount` - The amount of shares being removed from the user's unqueued shares
+///
+/// ### Panics
+/// If less than `amount` of the shares are unlocked
+pub fn require_unlocked(
+    e: &Env,
+    from: &Address,
+    pool_address: &Address,
+    shares: i128,
+    amount: i128,
+) {
+    let mut locked: i128 = 0;
+    for lock in load_active_locks(e, pool_address, from).iter() {
+        locked += lock.shares;
+    }
+    for vesting in load_active_vestings(e, pool_address, from).iter() {
+        locked += vesting.locked(e);
+    }
+    if shares - locked < amount {
+        panic_with_error!(e, BackstopError::SharesLocked);
+    }
+}
+
+/// Claim the bonus emissions of `from`'s locks, and clear expired locks. A lock's bonus is the
+/// emissions its shares accrued since the bonus was last claimed, scaled by its multiplier less
+/// one. The fraction of a token the bonus rounds off is carried forward to the next claim.
+///
+/// An expired lock is paid its bonus up to its expiration on the first claim after it expires.
+/// The index the lock expired at is not recorded, so the index accrued since the last claim is
+/// attributed to the lock pro-rata by the time it was still locked.
+///
+/// Returns the bonus emissions accrued
+///
+/// ### Arguments
+/// * `from` - The owner of the locks
+/// * `pool_address` - The address of the pool
+/// * `index` - The backstop's current emission index
+pub(crate) fn claim_lock_bonus(
+    e: &Env,
+    from: &Address,
+    pool_address: &Address,
+    index: i128,
+) -> i128 {
+    let locks = storage::get_deposit_locks(e, pool_address, from);
+    let now = e.ledger().timestamp();
+    let mut bonus: i128 = 0;
+    let mut new_locks = vec![e];
+    for mut lock in locks.iter() {
+        let mut delta_index = index - lock.index;
+        require_nonnegative(e, delta_index);
+        let expired = lock.exp <= now;
+        if expired && now > lock.last_time {
+            delta_index = delta_index
+                .fixed_mul_floor(
+                    i128(lock.exp.max(lock.last_time) - lock.last_time),
+                    i128(now - lock.last_time),
+                )
+                .unwrap_optimized();
+        }
+        // the emissions accrued by the shares, scaled by an extra 7 decimals to keep the remainder
+        let accrued = lock
+            .shares
+            .fixed_mul_floor(delta_index, SCALAR_7)
+            .unwrap_optimized();
+        let lock_bonus = accrued
+            .fixed_mul_floor(lock.multiplier - SCALAR_7, SCALAR_7)
+            .unwrap_optimized()
+            + lock.remainder;
+        bonus += lock_bonus / SCALAR_7;
+        if !expired {
+            lock.index = index;
+            lock.last_time = now;
+            lock.remainder = lock_bonus % SCALAR_7;
+            new_locks.push_back(lock);
+        }
+    }
+    storage::set_deposit_locks(e, pool_address, from, &new_locks);
+    bonus
+}
+
+/// Add BLND to the reserve lock bonuses are paid from
+///
+/// Returns the new size of the reserve
+///
+/// ### Arguments
+/// * `from` - The address sending the BLND
+/// * `amount` - The amount of BLND to add
+pub fn execute_fund_lock_bonus(e: &Env, from: &Address, amount: i128) -> i128 {
+    require_nonnegative(e, amount);
+    if from == &e.current_contract_address() {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+    TokenClient::new(e, &storage::get_blnd_token(e)).transfer(
+        from,
+        &e.current_contract_address(),
+        &amount,
+    );
+    let reserve = storage::get_lock_bonus_reserve(e) + amount;
+    storage::set_lock_bonus_reserve(e, &reserve);
+    reserve
+}
+
+/// Load the locks of `from` that have not expired
+pub fn load_active_locks(e: &Env, pool_address: &Address, from: &Address) -> Vec<DepositLock> {
+    let mut active = vec![e];
+    for lock in storage::get_deposit_locks(e, pool_address, from).iter() {
+        if lock.exp > e.ledger().timestamp() {
+            active.push_back(lock);
+        }
+    }
+    active
+}
+
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        Address,
+    };
+
+    use crate::{
+        backstop::execute_queue_withdrawal,
+        storage::BackstopEmissionData,
+        testutils::{
+            create_backstop, create_backstop_token, create_blnd_token, create_mock_pool_factory,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_execute_deposit_locked() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 40_0000000);
+            let (to_mint, lock) =
+                execute_deposit_locked(&e, &samwise, &pool_address, 60_0000000, 1);
+            assert_eq!(to_mint, 60_0000000);
+            assert_eq!(lock.shares, 60_0000000);
+            assert_eq!(lock.multiplier, 1_2500000);
+            assert_eq!(lock.exp, e.ledger().timestamp() + 90 * 24 * 60 * 60);
+            assert_eq!(lock.index, 0);
+
+            let locks = load_active_locks(&e, &pool_address, &samwise);
+            assert_eq!(locks.len(), 1);
+            assert_eq!(
+                storage::get_user_balance(&e, &pool_address, &samwise).shares,
+                100_0000000
+            );
+
+            // the unlocked shares can be queued
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 40_0000000);
+        });
+
+        // the locked shares can be queued once the lock expires
+        e.ledger().set(LedgerInfo {
+            protocol_version: 22,
+            sequence_number: 200,
+            timestamp: e.ledger().timestamp() + 90 * 24 * 60 * 60,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.as_contract(&backstop_address, || {
+            assert_eq!(load_active_locks(&e, &pool_address, &samwise).len(), 0);
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 60_0000000);
+            assert_eq!(
+                storage::get_user_balance(&e, &pool_address, &samwise).shares,
+                0
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1013)")]
+    fn test_execute_queue_withdrawal_locked_shares() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 40_0000000);
+            execute_deposit_locked(&e, &samwise, &pool_address, 60_0000000, 0);
+
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 40_0000001);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_execute_deposit_locked_invalid_tier() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            execute_deposit_locked(&e, &samwise, &pool_address, 60_0000000, 3);
+        });
+    }
+
+    #[test]
+    fn test_claim_lock_bonus() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+        let (_, blnd_token_client) = create_blnd_token(&e, &backstop_address, &bombadil);
+        blnd_token_client.mint(&bombadil, &50_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            let reserve = execute_fund_lock_bonus(&e, &bombadil, 50_0000000);
+            assert_eq!(reserve, 50_0000000);
+            assert_eq!(storage::get_lock_bonus_reserve(&e), 50_0000000);
+            assert_eq!(blnd_token_client.balance(&backstop_address), 50_0000000);
+
+            storage::set_backstop_emis_data(
+                &e,
+                &pool_address,
+                &BackstopEmissionData {
+                    expiration: e.ledger().timestamp() + 7 * 24 * 60 * 60,
+                    eps: 0,
+                    index: 0_50000000000000,
+                    last_time: e.ledger().timestamp(),
+                },
+            );
+            execute_deposit_locked(&e, &samwise, &pool_address, 100_0000000, 2);
+
+            // 100 locked shares accrue 1 BLND per share, and the 1.5x tier earns half as a bonus
+            let bonus = claim_lock_bonus(&e, &samwise, &pool_address, 1_50000000000000);
+            assert_eq!(bonus, 50_0000000);
+            let locks = load_active_locks(&e, &pool_address, &samwise);
+            assert_eq!(locks.get_unchecked(0).index, 1_50000000000000);
+
+            // the bonus is only paid once
+            let bonus = claim_lock_bonus(&e, &samwise, &pool_address, 1_50000000000000);
+            assert_eq!(bonus, 0);
+        });
+    }
+
+    #[test]
+    fn test_claim_lock_bonus_carries_remainder() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &1_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            storage::set_backstop_emis_data(
+                &e,
+                &pool_address,
+                &BackstopEmissionData {
+                    expiration: e.ledger().timestamp() + 7 * 24 * 60 * 60,
+                    eps: 0,
+                    index: 0,
+                    last_time: e.ledger().timestamp(),
+                },
+            );
+            execute_deposit_locked(&e, &samwise, &pool_address, 1_0000000, 0);
+
+            // each claim accrues 0.6 of a stroop of bonus, which is carried forward
+            let bonus = claim_lock_bonus(&e, &samwise, &pool_address, 0_00000060000000);
+            assert_eq!(bonus, 0);
+            let locks = load_active_locks(&e, &pool_address, &samwise);
+            assert_eq!(locks.get_unchecked(0).remainder, 6000000);
+
+            let bonus = claim_lock_bonus(&e, &samwise, &pool_address, 0_00000120000000);
+            assert_eq!(bonus, 1);
+            let locks = load_active_locks(&e, &pool_address, &samwise);
+            assert_eq!(locks.get_unchecked(0).remainder, 2000000);
+        });
+    }
+
+    #[test]
+    fn test_claim_lock_bonus_expired_lock() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set_timestamp(1000);
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            storage::set_backstop_emis_data(
+                &e,
+                &pool_address,
+                &BackstopEmissionData {
+                    expiration: e.ledger().timestamp() + 7 * 24 * 60 * 60,
+                    eps: 0,
+                    index: 0,
+                    last_time: e.ledger().timestamp(),
+                },
+            );
+            execute_deposit_locked(&e, &samwise, &pool_address, 100_0000000, 0);
+        });
+
+        // the 30 day lock is first claimed 60 days later
+        e.ledger().set_timestamp(1000 + 60 * 24 * 60 * 60);
+        e.as_contract(&backstop_address, || {
+            assert_eq!(load_active_locks(&e, &pool_address, &samwise).len(), 0);
+
+            // half of the index accrued while the shares were locked, and the 1.1x tier earns a
+            // tenth of it as a bonus
+            let bonus = claim_lock_bonus(&e, &samwise, &pool_address, 2_00000000000000);
+            assert_eq!(bonus, 10_0000000);
+            assert_eq!(
+                storage::get_deposit_locks(&e, &pool_address, &samwise).len(),
+                0
+            );
+
+            let bonus = claim_lock_bonus(&e, &samwise, &pool_address, 2_00000000000000);
+            assert_eq!(bonus, 0);
+        });
+    }
+}
@@ -1,17 +1,47 @@
+mod apr;
+pub use apr::{
+    checkpoint_backstop_yield, load_backstop_apr, BackstopApr, YieldCheckpoint, YieldWindow,
+};
+
 mod deposit;
-pub use deposit::execute_deposit;
+pub use deposit::{execute_deposit, execute_factory_deposit};
 
 mod fund_management;
-pub use fund_management::{execute_donate, execute_draw};
+pub use fund_management::{execute_donate, execute_donate_bid, execute_draw};
+
+mod lock;
+pub(crate) use lock::claim_lock_bonus;
+pub use lock::{
+    execute_deposit_locked, execute_fund_lock_bonus, load_active_locks, require_unlocked,
+    DepositLock,
+};
 
 mod withdrawal;
-pub use withdrawal::{execute_dequeue_withdrawal, execute_queue_withdrawal, execute_withdraw};
+pub use withdrawal::{
+    execute_dequeue_withdrawal, execute_dequeue_withdrawal_entry, execute_queue_withdrawal,
+    execute_withdraw, execute_withdraw_early,
+};
 
 mod pool;
 pub use pool::{
-    is_pool_above_threshold, load_pool_backstop_data, require_is_from_pool_factory,
-    PoolBackstopData, PoolBalance,
+    execute_set_approved_factory, is_pool_above_threshold, load_backstop_state,
+    load_lp_token_price, load_pool_backstop_data, load_pool_backstop_report,
+    require_is_from_pool_factory, BackstopState, PoolBackstopData, PoolBackstopReport, PoolBalance,
 };
 
 mod user;
 pub use user::{UserBalance, Q4W};
+
+mod roles;
+pub use roles::{execute_grant_role, execute_revoke_role, has_role, require_role};
+
+mod share_token;
+pub use share_token::{
+    execute_deploy_share_token, execute_transfer_shares, notify_share_burn, notify_share_mint,
+};
+
+mod recap;
+pub use recap::{
+    execute_cancel_recap_auction, execute_fill_recap_auction, execute_start_recap_auction,
+    RecapAuction, RecapEpoch, RecapWatch,
+};
@@ -1,9 +1,10 @@
 use soroban_fixed_point_math::FixedPoint;
-use soroban_sdk::{contracttype, panic_with_error, unwrap::UnwrapOptimized, Address, Env};
+use soroban_sdk::{contracttype, panic_with_error, unwrap::UnwrapOptimized, Address, BytesN, Env};
+use trustbridge_common::{decimal_scalar, Asset, PriceFeedClient};
 
 use crate::{
-    constants::SCALAR_7,
-    dependencies::{CometClient, PoolFactoryClient},
+    constants::{MAX_APPROVED_FACTORIES, SCALAR_7},
+    dependencies::{CometClient, PoolClient, PoolFactoryClient},
     errors::BackstopError,
     storage,
 };
@@ -80,7 +81,84 @@ pub fn load_pool_backstop_data(e: &Env, address: &Address) -> PoolBackstopData {
     }
 }
 
-/// Verify the pool address was deployed by the Pool Factory.
+/// A summary of a pool's backstop for risk monitoring
+#[derive(Clone)]
+#[contracttype]
+pub struct PoolBackstopReport {
+    pub shares: i128,          // the number of shares the pool's backstop has issued
+    pub tokens: i128,          // the number of backstop tokens held in the pool's backstop
+    pub q4w: i128,             // the number of shares queued for withdrawal
+    pub q4w_tokens: i128,      // the number of backstop tokens queued for withdrawal
+    pub q4w_pct: i128,         // the percentage of shares/tokens queued for withdrawal
+    pub token_value: i128,     // the value in USDC of the backstop tokens, sans fees (7 decimals)
+    pub above_threshold: bool, // true if the pool's backstop is above the threshold
+    pub take_rate: u32,        // the pool's backstop take rate (7 decimals)
+}
+
+/// Load a report of the pool's backstop balances, value, and configuration
+///
+/// ### Arguments
+/// * `address` - The pool address
+pub fn load_pool_backstop_report(e: &Env, address: &Address) -> PoolBackstopReport {
+    let pool_balance = storage::get_pool_balance(e, address);
+    let pool_data = load_pool_backstop_data(e, address);
+    let take_rate = PoolClient::new(e, address).get_config().bstop_rate;
+
+    PoolBackstopReport {
+        shares: pool_balance.shares,
+        tokens: pool_balance.tokens,
+        q4w: pool_balance.q4w,
+        q4w_tokens: pool_balance.convert_to_tokens(pool_balance.q4w),
+        q4w_pct: pool_data.q4w_pct,
+        token_value: pool_data
+            .tokens
+            .fixed_mul_floor(pool_data.token_spot_price, SCALAR_7)
+            .unwrap_optimized(),
+        above_threshold: is_pool_above_threshold(&pool_data),
+        take_rate,
+    }
+}
+
+/// The balances of a pool's backstop, and optionally a user's balances in it, after an action
+#[derive(Clone)]
+#[contracttype]
+pub struct BackstopState {
+    pub shares: i128,      // the number of shares the pool's backstop has issued
+    pub tokens: i128,      // the number of backstop tokens held in the pool's backstop
+    pub q4w: i128,         // the number of shares queued for withdrawal
+    pub share_price: i128, // the number of backstop tokens per share (7 decimals)
+    pub user_shares: i128, // the user's shares, excluding Q4W, or 0 if no user is involved
+    pub user_q4w: i128,    // the user's shares queued for withdrawal, or 0 if no user is involved
+}
+
+/// Load the balances of a pool's backstop and a user's balances in it
+///
+/// ### Arguments
+/// * `address` - The pool address
+/// * `user` - The user involved in the action, if any
+pub fn load_backstop_state(e: &Env, address: &Address, user: Option<&Address>) -> BackstopState {
+    let pool_balance = storage::get_pool_balance(e, address);
+    let (user_shares, user_q4w) = match user {
+        Some(user) => {
+            let user_balance = storage::get_user_balance(e, address, user);
+            let q4w_total: i128 = user_balance.q4w.iter().map(|q4w| q4w.amount).sum();
+            (user_balance.shares, q4w_total)
+        }
+        None => (0, 0),
+    };
+
+    BackstopState {
+        shares: pool_balance.shares,
+        tokens: pool_balance.tokens,
+        q4w: pool_balance.q4w,
+        share_price: pool_balance.convert_to_tokens(SCALAR_7),
+        user_shares,
+        user_q4w,
+    }
+}
+
+/// Verify the pool address was deployed by the Pool Factory, or by an approved pool factory that
+/// still deploys pools with the WASM hash it was approved for.
 ///
 /// If the pool has an outstanding balance, it is assumed that it was verified before.
 ///
@@ -93,10 +171,132 @@ pub fn load_pool_backstop_data(e: &Env, address: &Address) -> PoolBackstopData {
 pub fn require_is_from_pool_factory(e: &Env, address: &Address, balance: i128) {
     if balance == 0 {
         let pool_factory_client = PoolFactoryClient::new(e, &storage::get_pool_factory(e));
-        if !pool_factory_client.is_pool(address) {
-            panic_with_error!(e, BackstopError::NotPool);
+        if pool_factory_client.is_pool(address) {
+            return;
+        }
+        for (factory, pool_hash) in storage::get_approved_factories(e).iter() {
+            let factory_client = PoolFactoryClient::new(e, &factory);
+            if factory_client.pool_hash() == pool_hash && factory_client.is_pool(address) {
+                return;
+            }
+        }
+        panic_with_error!(e, BackstopError::NotPool);
+    }
+}
+
+/// Approve a pool factory to deploy pools with the given WASM hash alongside the Pool Factory, or
+/// revoke its approval. Pools from a factory are only accepted while it reports the approved hash.
+///
+/// ### Arguments
+/// * `factory` - The pool factory
+/// * `pool_hash` - The WASM hash of the pools the factory is approved to deploy, or None to revoke
+///
+/// ### Panics
+/// If the factory is the Pool Factory, the factory does not deploy pools with `pool_hash`, or too
+/// many factories are approved
+pub fn execute_set_approved_factory(e: &Env, factory: &Address, pool_hash: &Option<BytesN<32>>) {
+    if factory == &storage::get_pool_factory(e) {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+    let mut factories = storage::get_approved_factories(e);
+    match pool_hash {
+        Some(pool_hash) => {
+            if PoolFactoryClient::new(e, factory).pool_hash() != *pool_hash {
+                panic_with_error!(e, BackstopError::BadRequest);
+            }
+            if !factories.contains_key(factory.clone()) && factories.len() >= MAX_APPROVED_FACTORIES
+            {
+                panic_with_error!(e, BackstopError::BadRequest);
+            }
+            factories.set(factory.clone(), pool_hash.clone());
+        }
+        None => {
+            factories.remove(factory.clone());
+        }
+    }
+    storage::set_approved_factories(e, &factories);
+}
+
+/// Load the USD value of one backstop token (7 decimals) from the invariant of the backstop
+/// token's 80/20 BLND:USDC pool and the prices from the backstop's oracle. The pool is valued at
+/// `(blnd_value / 0.8)^0.8 * (usdc_value / 0.2)^0.2`, which a swap cannot raise, so the price
+/// cannot be pushed up by skewing the pool's balances within a transaction.
+///
+/// ### Panics
+/// If the price of BLND or USDC is over a day old or is less than or equal to 0
+pub fn load_lp_token_price(e: &Env) -> i128 {
+    let backstop_token = storage::get_backstop_token(e);
+    let blnd_token = storage::get_blnd_token(e);
+    let usdc_token = storage::get_usdc_token(e);
+    let comet_client = CometClient::new(e, &backstop_token);
+    let total_comet_shares = comet_client.get_total_supply();
+    if total_comet_shares <= 0 {
+        return 0;
+    }
+
+    let oracle_client = PriceFeedClient::new(e, &storage::get_oracle(e));
+    let price_scalar = decimal_scalar(oracle_client.decimals());
+    let blnd_value = comet_client
+        .get_balance(&blnd_token)
+        .fixed_mul_floor(load_price(e, &oracle_client, &blnd_token), price_scalar)
+        .unwrap_optimized();
+    let usdc_value = comet_client
+        .get_balance(&usdc_token)
+        .fixed_mul_floor(load_price(e, &oracle_client, &usdc_token), price_scalar)
+        .unwrap_optimized();
+
+    let blnd_weighted = blnd_value
+        .fixed_div_floor(0_8000000, SCALAR_7)
+        .unwrap_optimized();
+    let usdc_weighted = usdc_value * 5;
+    if blnd_weighted <= 0 || usdc_weighted <= 0 {
+        return 0;
+    }
+    let ratio = usdc_weighted
+        .fixed_div_floor(blnd_weighted, SCALAR_7)
+        .unwrap_optimized();
+    blnd_weighted
+        .fixed_mul_floor(fifth_root(ratio), SCALAR_7)
+        .unwrap_optimized()
+        .fixed_div_floor(total_comet_shares, SCALAR_7)
+        .unwrap_optimized()
+}
+
+/// Calculate the fifth root of a number with 7 decimals, rounded down
+fn fifth_root(x: i128) -> i128 {
+    let mut low = 0;
+    let mut high = x.max(SCALAR_7);
+    while low < high {
+        let mid = (low + high + 1) / 2;
+        match pow5(mid) {
+            Some(pow) if pow <= x => low = mid,
+            _ => high = mid - 1,
         }
     }
+    low
+}
+
+/// Calculate `x^5` for a number with 7 decimals, or None if it overflows
+fn pow5(x: i128) -> Option<i128> {
+    let x_2 = x.fixed_mul_floor(x, SCALAR_7)?;
+    let x_4 = x_2.fixed_mul_floor(x_2, SCALAR_7)?;
+    x_4.fixed_mul_floor(x, SCALAR_7)
+}
+
+/// Load the price of an asset from the oracle
+///
+/// ### Panics
+/// If the price is over a day old or is less than or equal to 0
+pub(super) fn load_price(e: &Env, oracle_client: &PriceFeedClient, asset: &Address) -> i128 {
+    match oracle_client.lastprice(&Asset::Stellar(asset.clone())) {
+        Some(price_data)
+            if price_data.timestamp + 24 * 60 * 60 >= e.ledger().timestamp()
+                && price_data.price > 0 =>
+        {
+            price_data.price
+        }
+        _ => panic_with_error!(e, BackstopError::InvalidPrice),
+    }
 }
 
 /// Calculate the threshold for the pool's backstop balance
@@ -216,13 +416,16 @@ impl PoolBalance {
 
 #[cfg(test)]
 mod tests {
-    use soroban_sdk::testutils::Address as _;
+    use sep_40_oracle::testutils::Asset as OracleAsset;
+    use soroban_sdk::{map, testutils::Address as _, vec, Symbol};
 
     use crate::testutils::{
         create_backstop, create_blnd_token, create_comet_lp_pool_with_tokens_per_share,
-        create_mock_pool_factory, create_usdc_token,
+        create_mock_oracle, create_mock_pool, create_mock_pool_factory, create_usdc_token,
     };
 
+    use crate::backstop::{UserBalance, Q4W};
+
     use super::*;
 
     #[test]
@@ -309,6 +512,126 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_load_lp_token_price() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_address = create_backstop(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_address, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_address, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_address,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_0500000,
+        );
+        let (_, oracle_client) = create_mock_oracle(&e, &backstop_address);
+        oracle_client.set_data(
+            &bombadil,
+            &OracleAsset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                OracleAsset::Stellar(blnd_id),
+                OracleAsset::Stellar(usdc_id),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 0_0200000, 1_0000000]);
+
+        e.as_contract(&backstop_address, || {
+            // 5 BLND at $0.02 and 0.05 USDC at $1 per LP token. The pool holds more USDC than
+            // the oracle prices imply, so it is valued below the $0.15 of its holdings.
+            let price = load_lp_token_price(&e);
+            assert_eq!(price, 0_1435872);
+        });
+    }
+
+    #[test]
+    fn test_load_lp_token_price_balanced() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_address = create_backstop(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_address, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_address, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_address,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_0250000,
+        );
+        let (_, oracle_client) = create_mock_oracle(&e, &backstop_address);
+        oracle_client.set_data(
+            &bombadil,
+            &OracleAsset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                OracleAsset::Stellar(blnd_id),
+                OracleAsset::Stellar(usdc_id),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 0_0200000, 1_0000000]);
+
+        e.as_contract(&backstop_address, || {
+            // a pool balanced at the oracle prices is valued at its holdings
+            let price = load_lp_token_price(&e);
+            assert_eq!(price, 0_1250000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1012)")]
+    fn test_load_lp_token_price_invalid_price() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_address = create_backstop(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_address, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_address, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_address,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_0500000,
+        );
+        let (_, oracle_client) = create_mock_oracle(&e, &backstop_address);
+        oracle_client.set_data(
+            &bombadil,
+            &OracleAsset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                OracleAsset::Stellar(blnd_id),
+                OracleAsset::Stellar(usdc_id),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 0, 1_0000000]);
+
+        e.as_contract(&backstop_address, || {
+            load_lp_token_price(&e);
+        });
+    }
+
     #[test]
     fn test_load_pool_data_no_tokens() {
         let e = Env::default();
@@ -353,6 +676,112 @@ mod tests {
 
     /********** require_is_from_pool_factory **********/
 
+    #[test]
+    fn test_load_pool_backstop_report() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_address = create_backstop(&e);
+        let (pool, pool_client) = create_mock_pool(&e);
+        pool_client.set_config(&mock_pool::PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 4,
+        });
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_address, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_address, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_address,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_0500000,
+        );
+
+        e.as_contract(&backstop_address, || {
+            storage::set_pool_balance(
+                &e,
+                &pool,
+                &PoolBalance {
+                    shares: 150_0000000,
+                    tokens: 250_0000000,
+                    q4w: 50_0000000,
+                },
+            );
+
+            let report = load_pool_backstop_report(&e, &pool);
+
+            assert_eq!(report.shares, 150_0000000);
+            assert_eq!(report.tokens, 250_0000000);
+            assert_eq!(report.q4w, 50_0000000);
+            assert_eq!(report.q4w_tokens, 83_3333333);
+            assert_eq!(report.q4w_pct, 0_3333334);
+            assert_eq!(report.token_value, 62_5000000);
+            assert!(!report.above_threshold);
+            assert_eq!(report.take_rate, 0_2000000);
+        });
+    }
+
+    #[test]
+    fn test_load_backstop_state() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let backstop_address = create_backstop(&e);
+        let pool = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&backstop_address, || {
+            storage::set_pool_balance(
+                &e,
+                &pool,
+                &PoolBalance {
+                    shares: 150_0000000,
+                    tokens: 250_0000000,
+                    q4w: 50_0000000,
+                },
+            );
+            storage::set_user_balance(
+                &e,
+                &pool,
+                &samwise,
+                &UserBalance {
+                    shares: 20_0000000,
+                    q4w: vec![
+                        &e,
+                        Q4W {
+                            amount: 5_0000000,
+                            exp: 100,
+                        },
+                        Q4W {
+                            amount: 7_5000000,
+                            exp: 200,
+                        },
+                    ],
+                },
+            );
+
+            let state = load_backstop_state(&e, &pool, Some(&samwise));
+            assert_eq!(state.shares, 150_0000000);
+            assert_eq!(state.tokens, 250_0000000);
+            assert_eq!(state.q4w, 50_0000000);
+            assert_eq!(state.share_price, 1_6666666);
+            assert_eq!(state.user_shares, 20_0000000);
+            assert_eq!(state.user_q4w, 12_5000000);
+
+            let state = load_backstop_state(&e, &pool, None);
+            assert_eq!(state.share_price, 1_6666666);
+            assert_eq!(state.user_shares, 0);
+            assert_eq!(state.user_q4w, 0);
+        });
+    }
+
     #[test]
     fn test_require_is_from_pool_factory() {
         let e = Env::default();
@@ -384,6 +813,97 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_require_is_from_pool_factory_approved_factory() {
+        let e = Env::default();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+
+        let (approved_factory, approved_factory_client) =
+            create_mock_pool_factory(&e, &backstop_address);
+        approved_factory_client.set_pool(&pool_address);
+        let (mock_factory, _) = create_mock_pool_factory(&e, &backstop_address);
+        // create a different pool factory for the backstop that did not deploy the pool
+        create_mock_pool_factory(&e, &backstop_address);
+
+        e.as_contract(&backstop_address, || {
+            let pool_hash = BytesN::<32>::from_array(&e, &[0u8; 32]);
+            execute_set_approved_factory(&e, &mock_factory, &Some(pool_hash.clone()));
+            execute_set_approved_factory(&e, &approved_factory, &Some(pool_hash));
+            execute_set_approved_factory(&e, &mock_factory, &None);
+            assert_eq!(storage::get_approved_factories(&e).len(), 1);
+
+            require_is_from_pool_factory(&e, &pool_address, 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1004)")]
+    fn test_require_is_from_pool_factory_approved_factory_wrong_hash() {
+        let e = Env::default();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+
+        let (approved_factory, approved_factory_client) =
+            create_mock_pool_factory(&e, &backstop_address);
+        approved_factory_client.set_pool(&pool_address);
+        create_mock_pool_factory(&e, &backstop_address);
+
+        e.as_contract(&backstop_address, || {
+            // the factory was approved for a different version of the pool
+            storage::set_approved_factories(
+                &e,
+                &map![
+                    &e,
+                    (
+                        approved_factory.clone(),
+                        BytesN::<32>::from_array(&e, &[1u8; 32])
+                    )
+                ],
+            );
+
+            require_is_from_pool_factory(&e, &pool_address, 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_set_approved_factory_wrong_hash() {
+        let e = Env::default();
+
+        let backstop_address = create_backstop(&e);
+        let (approved_factory, _) = create_mock_pool_factory(&e, &backstop_address);
+        create_mock_pool_factory(&e, &backstop_address);
+
+        e.as_contract(&backstop_address, || {
+            // the factory deploys pools with a hash of all zeros
+            execute_set_approved_factory(
+                &e,
+                &approved_factory,
+                &Some(BytesN::<32>::from_array(&e, &[1u8; 32])),
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_set_approved_factory_pool_factory() {
+        let e = Env::default();
+
+        let backstop_address = create_backstop(&e);
+        let (pool_factory, _) = create_mock_pool_factory(&e, &backstop_address);
+
+        e.as_contract(&backstop_address, || {
+            execute_set_approved_factory(
+                &e,
+                &pool_factory,
+                &Some(BytesN::<32>::from_array(&e, &[0u8; 32])),
+            );
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1004)")]
     fn test_require_is_from_pool_factory_not_valid() {
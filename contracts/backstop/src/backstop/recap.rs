@@ -0,0 +1,582 @@
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contracttype, panic_with_error,
+    unwrap::UnwrapOptimized,
+    vec, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+use trustbridge_common::{decimal_scalar, PriceFeedClient};
+
+use crate::{
+    constants::{
+        RECAP_CHECK_INTERVAL, RECAP_DECAY_BLOCKS, RECAP_DELAY, RECAP_EPOCH, RECAP_EPOCH_CAP,
+        RECAP_EXPIRY_BLOCKS, RECAP_MAX_SLIPPAGE, RECAP_MIN_BLOCKS, RECAP_START_PREMIUM, SCALAR_7,
+    },
+    dependencies::CometClient,
+    storage, BackstopError,
+};
+
+use super::{
+    is_pool_above_threshold, load_lp_token_price, load_pool_backstop_data, pool::load_price,
+    require_is_from_pool_factory,
+};
+
+/// An auction selling BLND from the emitter for USDC to recapitalize a pool's backstop
+#[derive(Clone)]
+#[contracttype]
+pub struct RecapAuction {
+    pub lot: i128,  // the amount of BLND sold
+    pub bid: i128,  // the amount of USDC bid when the auction starts
+    pub block: u32, // the block the auction started at
+    pub epoch: u64, // the epoch the lot counts against the cap of
+}
+
+impl RecapAuction {
+    /// Fetch the amount of USDC bid at a block. The bid decays linearly to half its starting
+    /// value over `RECAP_DECAY_BLOCKS`, and stays there.
+    pub fn bid_at_block(&self, block: u32) -> i128 {
+        let elapsed = block.saturating_sub(self.block).min(RECAP_DECAY_BLOCKS);
+        let decay = self
+            .bid
+            .fixed_mul_floor(elapsed as i128, 2 * RECAP_DECAY_BLOCKS as i128)
+            .unwrap_optimized();
+        self.bid - decay
+    }
+}
+
+/// When a pool's backstop was first and last seen below the threshold
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RecapWatch {
+    pub below_since: u64, // the time the backstop was first seen below the threshold
+    pub last_check: u64,  // the time the backstop was last seen below the threshold
+}
+
+/// The BLND sold by recapitalization auctions in an epoch
+#[derive(Clone)]
+#[contracttype]
+pub struct RecapEpoch {
+    pub epoch: u64, // the index of the epoch
+    pub sold: i128, // the amount of BLND sold in the epoch
+}
+
+/// Track a pool's backstop against the threshold, and start a recapitalization auction once it
+/// has stayed below the threshold for `RECAP_DELAY`. The backstop only counts as staying below
+/// the threshold if it was checked at least every `RECAP_CHECK_INTERVAL`, so a recovery between
+/// checks can not go unseen for long. The auction sells BLND the emitter has approved the
+/// backstop to spend for USDC at a premium over the oracle price, and the USDC is deposited into
+/// the backstop token for the pool's backstop.
+///
+/// The BLND sold is capped at `RECAP_EPOCH_CAP` each epoch, across all pools.
+///
+/// Returns the auction started, or None if the pool does not need recapitalizing yet or no BLND
+/// can be sold this epoch
+///
+/// ### Arguments
+/// * `pool_address` - The address of the pool
+///
+/// ### Panics
+/// If the pool is not from the pool factory, or already has a recapitalization auction
+pub fn execute_start_recap_auction(e: &Env, pool_address: &Address) -> Option<RecapAuction> {
+    let pool_balance = storage::get_pool_balance(e, pool_address);
+    require_is_from_pool_factory(e, pool_address, pool_balance.shares);
+    if storage::get_recap_auction(e, pool_address).is_some() {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+
+    let now = e.ledger().timestamp();
+    if is_pool_above_threshold(&load_pool_backstop_data(e, pool_address)) {
+        storage::set_recap_watch(e, pool_address, &None);
+        return None;
+    }
+    let below_since = match storage::get_recap_watch(e, pool_address) {
+        Some(watch) if now <= watch.last_check + RECAP_CHECK_INTERVAL => watch.below_since,
+        _ => now,
+    };
+    storage::set_recap_watch(
+        e,
+        pool_address,
+        &Some(RecapWatch {
+            below_since,
+            last_check: now,
+        }),
+    );
+    if now < below_since + RECAP_DELAY {
+        return None;
+    }
+
+    let epoch = now / RECAP_EPOCH;
+    let mut recap_epoch = storage::get_recap_epoch(e);
+    if recap_epoch.epoch != epoch {
+        recap_epoch = RecapEpoch { epoch, sold: 0 };
+    }
+    let allowance = TokenClient::new(e, &storage::get_blnd_token(e))
+        .allowance(&storage::get_emitter(e), &e.current_contract_address());
+    let lot = (RECAP_EPOCH_CAP - recap_epoch.sold).min(allowance);
+    if lot <= 0 {
+        return None;
+    }
+    recap_epoch.sold += lot;
+    storage::set_recap_epoch(e, &recap_epoch);
+
+    let auction = RecapAuction {
+        lot,
+        bid: lot
+            .fixed_mul_ceil(load_blnd_oracle_price(e), SCALAR_7)
+            .unwrap_optimized()
+            .fixed_mul_ceil(RECAP_START_PREMIUM, SCALAR_7)
+            .unwrap_optimized(),
+        block: e.ledger().sequence(),
+        epoch,
+    };
+    storage::set_recap_auction(e, pool_address, &Some(auction.clone()));
+    Some(auction)
+}
+
+/// Fill a pool's recapitalization auction. The filler pays the current bid in USDC and receives
+/// the lot of BLND from the emitter. The USDC is deposited into the backstop token, and the
+/// backstop tokens minted are added to the pool's backstop without minting shares.
+///
+/// Returns (the auction, the USDC paid, the backstop tokens added to the pool's backstop)
+///
+/// ### Arguments
+/// * `filler` - The address filling the auction
+/// * `pool_address` - The address of the pool
+///
+/// ### Panics
+/// If the pool has no recapitalization auction, it started less than `RECAP_MIN_BLOCKS` ago or has
+/// expired, or the USDC deposits into less backstop tokens than its oracle value allows for
+pub fn execute_fill_recap_auction(
+    e: &Env,
+    filler: &Address,
+    pool_address: &Address,
+) -> (RecapAuction, i128, i128) {
+    let auction = match storage::get_recap_auction(e, pool_address) {
+        Some(auction) => auction,
+        None => panic_with_error!(e, BackstopError::BadRequest),
+    };
+    let block = e.ledger().sequence();
+    if filler == &e.current_contract_address()
+        || block < auction.block + RECAP_MIN_BLOCKS
+        || block >= auction.block + RECAP_EXPIRY_BLOCKS
+    {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+    storage::set_recap_auction(e, pool_address, &None);
+    storage::set_recap_watch(e, pool_address, &None);
+
+    let bid = auction.bid_at_block(block);
+    let usdc_id = storage::get_usdc_token(e);
+    TokenClient::new(e, &usdc_id).transfer(filler, &e.current_contract_address(), &bid);
+    TokenClient::new(e, &storage::get_blnd_token(e)).transfer_from(
+        &e.current_contract_address(),
+        &storage::get_emitter(e),
+        filler,
+        &auction.lot,
+    );
+
+    // deposit the USDC into the backstop token for the pool's backstop
+    let lp_id = storage::get_backstop_token(e);
+    let approval_ledger = (e.ledger().sequence() / 100000 + 1) * 100000;
+    let args: Vec<Val> = vec![
+        e,
+        (&e.current_contract_address()).into_val(e),
+        (&lp_id).into_val(e),
+        (&bid).into_val(e),
+        (&approval_ledger).into_val(e),
+    ];
+    e.authorize_as_current_contract(vec![
+        &e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: usdc_id.clone(),
+                fn_name: Symbol::new(e, "approve"),
+                args: args.clone(),
+            },
+            sub_invocations: vec![e],
+        }),
+    ]);
+    let lp_tokens_out = CometClient::new(e, &lp_id).dep_tokn_amt_in_get_lp_tokns_out(
+        &usdc_id,
+        &bid,
+        &load_min_lp_tokens_out(e, &usdc_id, bid),
+        &e.current_contract_address(),
+    );
+    let mut pool_balance = storage::get_pool_balance(e, pool_address);
+    pool_balance.deposit(lp_tokens_out, 0);
+    storage::set_pool_balance(e, pool_address, &pool_balance);
+    (auction, bid, lp_tokens_out)
+}
+
+/// Cancel a pool's recapitalization auction once it has expired unfilled. The lot is returned to
+/// the cap of the epoch it was counted against, if that epoch has not ended.
+///
+/// Returns the auction cancelled
+///
+/// ### Arguments
+/// * `pool_address` - The address of the pool
+///
+/// ### Panics
+/// If the pool has no recapitalization auction, or it has not expired
+pub fn execute_cancel_recap_auction(e: &Env, pool_address: &Address) -> RecapAuction {
+    let auction = match storage::get_recap_auction(e, pool_address) {
+        Some(auction) => auction,
+        None => panic_with_error!(e, BackstopError::BadRequest),
+    };
+    if e.ledger().sequence() < auction.block + RECAP_EXPIRY_BLOCKS {
+        panic_with_error!(e, BackstopError::NotExpired);
+    }
+    storage::set_recap_auction(e, pool_address, &None);
+
+    let mut recap_epoch = storage::get_recap_epoch(e);
+    if recap_epoch.epoch == auction.epoch {
+        recap_epoch.sold -= auction.lot;
+        storage::set_recap_epoch(e, &recap_epoch);
+    }
+    auction
+}
+
+/// Fetch the price of BLND in USDC from the backstop's oracle (7 decimals)
+///
+/// ### Panics
+/// If the price of BLND or USDC is over a day old or is less than or equal to 0
+fn load_blnd_oracle_price(e: &Env) -> i128 {
+    let oracle_client = PriceFeedClient::new(e, &storage::get_oracle(e));
+    let blnd_price = load_price(e, &oracle_client, &storage::get_blnd_token(e));
+    let usdc_price = load_price(e, &oracle_client, &storage::get_usdc_token(e));
+    blnd_price
+        .fixed_div_floor(usdc_price, SCALAR_7)
+        .unwrap_optimized()
+}
+
+/// Fetch the minimum backstop tokens a deposit of `amount` USDC must mint, from the oracle values
+/// of USDC and the backstop token less `RECAP_MAX_SLIPPAGE`
+///
+/// ### Panics
+/// If the price of BLND or USDC is over a day old or is less than or equal to 0
+fn load_min_lp_tokens_out(e: &Env, usdc_id: &Address, amount: i128) -> i128 {
+    let oracle_client = PriceFeedClient::new(e, &storage::get_oracle(e));
+    let usdc_value = amount
+        .fixed_mul_floor(
+            load_price(e, &oracle_client, usdc_id),
+            decimal_scalar(oracle_client.decimals()),
+        )
+        .unwrap_optimized();
+    let lp_token_price = load_lp_token_price(e);
+    if lp_token_price <= 0 {
+        panic_with_error!(e, BackstopError::InvalidPrice);
+    }
+    usdc_value
+        .fixed_div_floor(lp_token_price, SCALAR_7)
+        .unwrap_optimized()
+        .fixed_mul_floor(SCALAR_7 - RECAP_MAX_SLIPPAGE, SCALAR_7)
+        .unwrap_optimized()
+}
+
+#[cfg(test)]
+mod tests {
+    use sep_40_oracle::testutils::{Asset as OracleAsset, MockPriceOracleClient};
+    use sep_41_token::testutils::MockTokenClient;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    use crate::{
+        backstop::PoolBalance,
+        testutils::{
+            create_backstop, create_blnd_token, create_comet_lp_pool, create_mock_oracle,
+            create_mock_pool_factory, create_usdc_token,
+        },
+    };
+
+    use super::*;
+
+    /// Setup a backstop whose pool holds 10 of the 100 backstop tokens, which is below the
+    /// threshold, an emitter that approved the backstop to spend 10 BLND, and an oracle pricing
+    /// BLND at 0.1 USDC
+    fn setup_backstop<'a>(
+        e: &Env,
+    ) -> (
+        Address,
+        Address,
+        MockTokenClient<'a>,
+        MockTokenClient<'a>,
+        MockPriceOracleClient<'a>,
+    ) {
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set_timestamp(1500000000);
+        e.ledger().set_sequence_number(1000);
+
+        let backstop_address = create_backstop(e);
+        let pool_address = Address::generate(e);
+        let bombadil = Address::generate(e);
+        let emitter = Address::generate(e);
+
+        let (blnd_id, blnd_client) = create_blnd_token(e, &backstop_address, &bombadil);
+        let (usdc_id, usdc_client) = create_usdc_token(e, &backstop_address, &bombadil);
+        let (lp_address, lp_client) = create_comet_lp_pool(e, &bombadil, &blnd_id, &usdc_id);
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+        let (_, oracle_client) = create_mock_oracle(e, &backstop_address);
+        oracle_client.set_data(
+            &bombadil,
+            &OracleAsset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                OracleAsset::Stellar(blnd_id),
+                OracleAsset::Stellar(usdc_id),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 0_1000000, 1_0000000]);
+
+        blnd_client.mint(&emitter, &10_0000000);
+        blnd_client.approve(&emitter, &backstop_address, &10_0000000, &10000);
+        lp_client.transfer(&bombadil, &backstop_address, &10_0000000);
+        e.as_contract(&backstop_address, || {
+            storage::set_backstop_token(e, &lp_address);
+            storage::set_emitter(e, &emitter);
+            storage::set_pool_balance(
+                e,
+                &pool_address,
+                &PoolBalance {
+                    shares: 10_0000000,
+                    tokens: 10_0000000,
+                    q4w: 0,
+                },
+            );
+        });
+        (
+            backstop_address,
+            pool_address,
+            blnd_client,
+            usdc_client,
+            oracle_client,
+        )
+    }
+
+    /// Start a recapitalization auction for a backstop that has been checked below the threshold
+    /// hourly since `1500000000`
+    fn start_auction(e: &Env, backstop_address: &Address, pool_address: &Address) -> RecapAuction {
+        e.ledger().set_timestamp(1500000000 + RECAP_DELAY);
+        e.as_contract(backstop_address, || {
+            storage::set_recap_watch(
+                e,
+                pool_address,
+                &Some(RecapWatch {
+                    below_since: 1500000000,
+                    last_check: 1500000000 + RECAP_DELAY - RECAP_CHECK_INTERVAL,
+                }),
+            );
+            execute_start_recap_auction(e, pool_address).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_recap_auction() {
+        let e = Env::default();
+        let (backstop_address, pool_address, blnd_client, usdc_client, oracle_client) =
+            setup_backstop(&e);
+        let frodo = Address::generate(&e);
+        usdc_client.mint(&frodo, &10_0000000);
+
+        e.as_contract(&backstop_address, || {
+            // the backstop must stay below the threshold for the delay
+            assert!(execute_start_recap_auction(&e, &pool_address).is_none());
+            assert_eq!(
+                storage::get_recap_watch(&e, &pool_address),
+                Some(RecapWatch {
+                    below_since: 1500000000,
+                    last_check: 1500000000,
+                })
+            );
+        });
+
+        // keepers check the backstop hourly
+        let mut now = 1500000000;
+        while now < 1500000000 + RECAP_DELAY {
+            now += RECAP_CHECK_INTERVAL;
+            e.ledger().set_timestamp(now);
+            oracle_client.set_price_stable(&vec![&e, 0_1000000, 1_0000000]);
+            let auction = e.as_contract(&backstop_address, || {
+                execute_start_recap_auction(&e, &pool_address)
+            });
+            assert_eq!(auction.is_some(), now >= 1500000000 + RECAP_DELAY);
+        }
+        let auction = e.as_contract(&backstop_address, || {
+            let auction = storage::get_recap_auction(&e, &pool_address).unwrap();
+            // BLND is worth 0.1 USDC by the oracle, and the bid starts at a 20% premium
+            assert_eq!(auction.lot, 10_0000000);
+            assert_eq!(auction.bid, 1_2000000);
+            assert_eq!(auction.block, 1000);
+            assert_eq!(auction.epoch, now / RECAP_EPOCH);
+            assert_eq!(storage::get_recap_epoch(&e).sold, 10_0000000);
+            auction
+        });
+
+        e.ledger()
+            .set_sequence_number(1000 + RECAP_DECAY_BLOCKS / 2);
+        e.as_contract(&backstop_address, || {
+            let (_, bid, lp_tokens) = execute_fill_recap_auction(&e, &frodo, &pool_address);
+            assert_eq!(bid, auction.bid_at_block(1000 + RECAP_DECAY_BLOCKS / 2));
+            assert_eq!(bid, 0_9000000);
+            // 0.9 USDC at $1 into backstop tokens worth $1.25 each, less slippage
+            assert!(lp_tokens >= 0_6840000);
+
+            let pool_balance = storage::get_pool_balance(&e, &pool_address);
+            assert_eq!(pool_balance.tokens, 10_0000000 + lp_tokens);
+            assert_eq!(pool_balance.shares, 10_0000000);
+            assert!(storage::get_recap_auction(&e, &pool_address).is_none());
+            assert!(storage::get_recap_watch(&e, &pool_address).is_none());
+        });
+        assert_eq!(blnd_client.balance(&frodo), 10_0000000);
+        assert_eq!(usdc_client.balance(&frodo), 10_0000000 - 0_9000000);
+    }
+
+    #[test]
+    fn test_recap_watch_resets_after_missed_check() {
+        let e = Env::default();
+        let (backstop_address, pool_address, _, _, oracle_client) = setup_backstop(&e);
+
+        e.as_contract(&backstop_address, || {
+            assert!(execute_start_recap_auction(&e, &pool_address).is_none());
+        });
+
+        // the backstop was not checked for longer than the interval, so it may have recovered
+        e.ledger().set_timestamp(1500000000 + RECAP_DELAY);
+        oracle_client.set_price_stable(&vec![&e, 0_1000000, 1_0000000]);
+        e.as_contract(&backstop_address, || {
+            assert!(execute_start_recap_auction(&e, &pool_address).is_none());
+            assert_eq!(
+                storage::get_recap_watch(&e, &pool_address),
+                Some(RecapWatch {
+                    below_since: 1500000000 + RECAP_DELAY,
+                    last_check: 1500000000 + RECAP_DELAY,
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_recap_auction_bid_decays_to_half() {
+        let auction = RecapAuction {
+            lot: 10_0000000,
+            bid: 1_2000000,
+            block: 1000,
+            epoch: 0,
+        };
+        assert_eq!(auction.bid_at_block(1000), 1_2000000);
+        assert_eq!(auction.bid_at_block(1000 + RECAP_DECAY_BLOCKS), 0_6000000);
+        assert_eq!(
+            auction.bid_at_block(1000 + 10 * RECAP_DECAY_BLOCKS),
+            0_6000000
+        );
+    }
+
+    #[test]
+    fn test_recap_auction_epoch_cap() {
+        let e = Env::default();
+        let (backstop_address, pool_address, _, _, oracle_client) = setup_backstop(&e);
+
+        e.ledger().set_timestamp(1500000000 + RECAP_DELAY);
+        oracle_client.set_price_stable(&vec![&e, 0_1000000, 1_0000000]);
+        e.as_contract(&backstop_address, || {
+            storage::set_recap_epoch(
+                &e,
+                &RecapEpoch {
+                    epoch: (1500000000 + RECAP_DELAY) / RECAP_EPOCH,
+                    sold: RECAP_EPOCH_CAP - 1_0000000,
+                },
+            );
+        });
+        let auction = start_auction(&e, &backstop_address, &pool_address);
+        assert_eq!(auction.lot, 1_0000000);
+        e.as_contract(&backstop_address, || {
+            assert_eq!(storage::get_recap_epoch(&e).sold, RECAP_EPOCH_CAP);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_fill_recap_auction_without_auction() {
+        let e = Env::default();
+        let (backstop_address, pool_address, _, _, _) = setup_backstop(&e);
+
+        e.as_contract(&backstop_address, || {
+            execute_fill_recap_auction(&e, &Address::generate(&e), &pool_address);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_fill_recap_auction_before_min_blocks() {
+        let e = Env::default();
+        let (backstop_address, pool_address, _, usdc_client, oracle_client) = setup_backstop(&e);
+        let frodo = Address::generate(&e);
+        usdc_client.mint(&frodo, &10_0000000);
+
+        e.ledger().set_timestamp(1500000000 + RECAP_DELAY);
+        oracle_client.set_price_stable(&vec![&e, 0_1000000, 1_0000000]);
+        start_auction(&e, &backstop_address, &pool_address);
+
+        e.ledger().set_sequence_number(1000 + RECAP_MIN_BLOCKS - 1);
+        e.as_contract(&backstop_address, || {
+            execute_fill_recap_auction(&e, &frodo, &pool_address);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_fill_recap_auction_after_expiry() {
+        let e = Env::default();
+        let (backstop_address, pool_address, _, usdc_client, oracle_client) = setup_backstop(&e);
+        let frodo = Address::generate(&e);
+        usdc_client.mint(&frodo, &10_0000000);
+
+        e.ledger().set_timestamp(1500000000 + RECAP_DELAY);
+        oracle_client.set_price_stable(&vec![&e, 0_1000000, 1_0000000]);
+        start_auction(&e, &backstop_address, &pool_address);
+
+        e.ledger().set_sequence_number(1000 + RECAP_EXPIRY_BLOCKS);
+        e.as_contract(&backstop_address, || {
+            execute_fill_recap_auction(&e, &frodo, &pool_address);
+        });
+    }
+
+    #[test]
+    fn test_cancel_recap_auction() {
+        let e = Env::default();
+        let (backstop_address, pool_address, _, _, oracle_client) = setup_backstop(&e);
+
+        e.ledger().set_timestamp(1500000000 + RECAP_DELAY);
+        oracle_client.set_price_stable(&vec![&e, 0_1000000, 1_0000000]);
+        let auction = start_auction(&e, &backstop_address, &pool_address);
+
+        e.ledger().set_sequence_number(1000 + RECAP_EXPIRY_BLOCKS);
+        e.as_contract(&backstop_address, || {
+            let cancelled = execute_cancel_recap_auction(&e, &pool_address);
+            assert_eq!(cancelled.lot, auction.lot);
+            assert!(storage::get_recap_auction(&e, &pool_address).is_none());
+            // the lot is returned to the epoch's cap
+            assert_eq!(storage::get_recap_epoch(&e).sold, 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1001)")]
+    fn test_cancel_recap_auction_before_expiry() {
+        let e = Env::default();
+        let (backstop_address, pool_address, _, _, oracle_client) = setup_backstop(&e);
+
+        e.ledger().set_timestamp(1500000000 + RECAP_DELAY);
+        oracle_client.set_price_stable(&vec![&e, 0_1000000, 1_0000000]);
+        start_auction(&e, &backstop_address, &pool_address);
+
+        e.ledger()
+            .set_sequence_number(1000 + RECAP_EXPIRY_BLOCKS - 1);
+        e.as_contract(&backstop_address, || {
+            execute_cancel_recap_auction(&e, &pool_address);
+        });
+    }
+}
@@ -0,0 +1,146 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+use trustbridge_common::{roles, Role};
+
+use crate::{errors::BackstopError, storage};
+
+/// Grant a role to an address. The admin role is only held by the backstop's admin, and is
+/// transferred through `propose_admin` and `accept_admin`.
+///
+/// ### Arguments
+/// * `role` - The role to grant
+/// * `member` - The address to grant the role to
+///
+/// ### Panics
+/// If the role is the admin role
+pub fn execute_grant_role(e: &Env, role: Role, member: &Address) {
+    if role == Role::Admin {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+    roles::grant_role(e, role, member);
+}
+
+/// Revoke a role from an address
+///
+/// ### Arguments
+/// * `role` - The role to revoke
+/// * `member` - The address to revoke the role from
+///
+/// ### Panics
+/// If the role is the admin role
+pub fn execute_revoke_role(e: &Env, role: Role, member: &Address) {
+    if role == Role::Admin {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+    roles::revoke_role(e, role, member);
+}
+
+/// Check if an address holds a role. The admin holds every role.
+///
+/// ### Arguments
+/// * `role` - The role
+/// * `member` - The address to check
+pub fn has_role(e: &Env, role: Role, member: &Address) -> bool {
+    *member == storage::get_admin(e) || roles::has_role(e, role, member)
+}
+
+/// Require that the caller authorized the invocation and holds a role
+///
+/// ### Arguments
+/// * `role` - The role the caller must hold
+/// * `caller` - The address calling the backstop
+///
+/// ### Panics
+/// If the caller does not hold the role
+pub fn require_role(e: &Env, role: Role, caller: &Address) {
+    caller.require_auth();
+    if !has_role(e, role, caller) {
+        panic_with_error!(e, BackstopError::UnauthorizedError);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testutils::create_backstop, BackstopClient};
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_roles() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let backstop = create_backstop(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&backstop, || {
+            storage::set_admin(&e, &bombadil);
+
+            // the admin holds every role
+            assert!(has_role(&e, Role::Admin, &bombadil));
+            assert!(has_role(&e, Role::Risk, &bombadil));
+            assert!(!has_role(&e, Role::Risk, &samwise));
+
+            execute_grant_role(&e, Role::Risk, &samwise);
+            assert!(has_role(&e, Role::Risk, &samwise));
+            assert!(!has_role(&e, Role::Pauser, &samwise));
+            require_role(&e, Role::Risk, &samwise);
+
+            execute_revoke_role(&e, Role::Risk, &samwise);
+            assert!(!has_role(&e, Role::Risk, &samwise));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #4)")]
+    fn test_require_role_not_held() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let backstop = create_backstop(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&backstop, || {
+            execute_grant_role(&e, Role::Pauser, &samwise);
+
+            require_role(&e, Role::Risk, &samwise);
+        });
+    }
+
+    #[test]
+    fn test_set_oracle() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let backstop = create_backstop(&e);
+        let backstop_client = BackstopClient::new(&e, &backstop);
+        let samwise = Address::generate(&e);
+        let oracle = Address::generate(&e);
+
+        backstop_client.grant_role(&Role::Risk, &samwise);
+        backstop_client.set_oracle(&samwise, &oracle);
+        assert_eq!(backstop_client.oracle(), oracle);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #4)")]
+    fn test_set_oracle_without_role() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let backstop = create_backstop(&e);
+        let backstop_client = BackstopClient::new(&e, &backstop);
+        let samwise = Address::generate(&e);
+
+        backstop_client.grant_role(&Role::Pauser, &samwise);
+        backstop_client.set_oracle(&samwise, &Address::generate(&e));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_grant_admin_role() {
+        let e = Env::default();
+        let backstop = create_backstop(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&backstop, || {
+            execute_grant_role(&e, Role::Admin, &samwise);
+        });
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{
+    contract::require_nonnegative,
+    dependencies::{ShareTokenClient, SHARE_TOKEN_WASM},
+    emissions, storage, BackstopError,
+};
+use soroban_sdk::{panic_with_error, xdr::ToXdr, Address, Env};
+
+use super::{require_is_from_pool_factory, require_unlocked};
+
+/// Deploy the SEP-41 share token of a pool's backstop. The share token's balances are the shares
+/// each user holds in the pool's backstop that are not queued for withdrawal.
+///
+/// Returns the address of the share token
+///
+/// ### Arguments
+/// * `pool_address` - The pool
+///
+/// ### Panics
+/// If the pool was not deployed by the pool factory or already has a share token
+pub fn execute_deploy_share_token(e: &Env, pool_address: &Address) -> Address {
+    let pool_balance = storage::get_pool_balance(e, pool_address);
+    require_is_from_pool_factory(e, pool_address, pool_balance.shares);
+    if storage::get_share_token(e, pool_address).is_some() {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+
+    let wasm_hash = e.deployer().upload_contract_wasm(SHARE_TOKEN_WASM);
+    let salt = e.crypto().keccak256(&pool_address.clone().to_xdr(e));
+    let share_token = e.deployer().with_current_contract(salt).deploy_v2(
+        wasm_hash,
+        (e.current_contract_address(), pool_address.clone()),
+    );
+    storage::set_share_token(e, pool_address, &share_token);
+    share_token
+}
+
+/// Transfer shares of a pool's backstop that are not queued for withdrawal between users.
+/// Emissions are accrued for both users before their shares change.
+///
+/// ### Arguments
+/// * `pool_address` - The pool
+/// * `from` - The user sending shares
+/// * `to` - The user receiving shares
+/// * `amount` - The amount of shares
+///
+/// ### Panics
+/// * If the caller is not the pool's share token
+/// * If `from` does not have enough shares that are not queued for withdrawal
+pub fn execute_transfer_shares(
+    e: &Env,
+    pool_address: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) {
+    match storage::get_share_token(e, pool_address) {
+        Some(share_token) => share_token.require_auth(),
+        None => panic_with_error!(e, BackstopError::BadRequest),
+    }
+    require_nonnegative(e, amount);
+    if from == to || to == pool_address || to == &e.current_contract_address() {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+
+    let pool_balance = storage::get_pool_balance(e, pool_address);
+    let mut from_balance = storage::get_user_balance(e, pool_address, from);
+    let mut to_balance = storage::get_user_balance(e, pool_address, to);
+    emissions::update_emissions(e, pool_address, &pool_balance, from, &from_balance);
+    emissions::update_emissions(e, pool_address, &pool_balance, to, &to_balance);
+
+    if from_balance.shares < amount {
+        panic_with_error!(e, BackstopError::BalanceError);
+    }
+    require_unlocked(e, from, pool_address, from_balance.shares, amount);
+    from_balance.shares -= amount;
+    to_balance.add_shares(amount);
+
+    storage::set_user_balance(e, pool_address, from, &from_balance);
+    storage::set_user_balance(e, pool_address, to, &to_balance);
+}
+
+/// Emit a mint event from the share token of a pool's backstop, if it has one, for shares added
+/// to a user's unqueued shares
+pub fn notify_share_mint(e: &Env, pool_address: &Address, to: &Address, amount: i128) {
+    if let Some(share_token) = storage::get_share_token(e, pool_address) {
+        ShareTokenClient::new(e, &share_token).notify_mint(to, &amount);
+    }
+}
+
+/// Emit a burn event from the share token of a pool's backstop, if it has one, for shares removed
+/// from a user's unqueued shares
+pub fn notify_share_burn(e: &Env, pool_address: &Address, from: &Address, amount: i128) {
+    if let Some(share_token) = storage::get_share_token(e, pool_address) {
+        ShareTokenClient::new(e, &share_token).notify_burn(from, &amount);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::{testutils::Address as _, token::TokenClient, Address};
+
+    use crate::{
+        backstop::{execute_deposit, execute_queue_withdrawal},
+        testutils::{create_backstop, create_backstop_token, create_mock_pool_factory},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_share_token() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        let share_token = e.as_contract(&backstop_address, || {
+            let share_token = execute_deploy_share_token(&e, &pool_address);
+            assert_eq!(
+                storage::get_share_token(&e, &pool_address),
+                Some(share_token.clone())
+            );
+            execute_deposit(&e, &samwise, &pool_address, 100_0000000);
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 25_0000000);
+            share_token
+        });
+
+        // queued shares are locked
+        let share_token_client = TokenClient::new(&e, &share_token);
+        assert_eq!(share_token_client.balance(&samwise), 75_0000000);
+
+        share_token_client.transfer(&samwise, &frodo, &30_0000000);
+        assert_eq!(share_token_client.balance(&samwise), 45_0000000);
+        assert_eq!(share_token_client.balance(&frodo), 30_0000000);
+        e.as_contract(&backstop_address, || {
+            let samwise_balance = storage::get_user_balance(&e, &pool_address, &samwise);
+            assert_eq!(samwise_balance.shares, 45_0000000);
+            assert_eq!(samwise_balance.q4w.len(), 1);
+            let frodo_balance = storage::get_user_balance(&e, &pool_address, &frodo);
+            assert_eq!(frodo_balance.shares, 30_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #10)")]
+    fn test_transfer_shares_locked() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        let share_token = e.as_contract(&backstop_address, || {
+            let share_token = execute_deploy_share_token(&e, &pool_address);
+            execute_deposit(&e, &samwise, &pool_address, 100_0000000);
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 25_0000000);
+            share_token
+        });
+
+        TokenClient::new(&e, &share_token).transfer(&samwise, &frodo, &75_0000001);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_deploy_share_token_twice() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            execute_deploy_share_token(&e, &pool_address);
+            execute_deploy_share_token(&e, &pool_address);
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_transfer_shares_not_share_token() {
+        let e = Env::default();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            execute_deploy_share_token(&e, &pool_address);
+            execute_transfer_shares(&e, &pool_address, &samwise, &frodo, 1_0000000);
+        });
+    }
+}
@@ -41,6 +41,17 @@ impl UserBalance {
 
     /***** Withdrawal Queue Management *****/
 
+    /// Fetch the amount of queued shares that can be withdrawn
+    pub fn expired_q4w(&self, e: &Env) -> i128 {
+        let mut expired = 0;
+        for q4w in self.q4w.iter() {
+            if q4w.exp <= e.ledger().timestamp() {
+                expired += q4w.amount;
+            }
+        }
+        expired
+    }
+
     /// Queue new shares for withdraw for the user
     ///
     /// Returns the new Q4W object
@@ -106,6 +117,40 @@ impl UserBalance {
         }
     }
 
+    /// Withdraw shares from the withdrawal queue before they unlock. Consumes the oldest queued
+    /// shares that have not unlocked first. Unlocked shares are left queued, as they can be
+    /// withdrawn without a penalty.
+    ///
+    /// ### Arguments
+    /// * `to_withdraw` - The amount of shares to withdraw from the withdrawal queue
+    ///
+    /// ### Errors
+    /// If the user does not have enough queued shares that have not unlocked
+    pub fn withdraw_shares_early(&mut self, e: &Env, to_withdraw: i128) {
+        let mut left_to_withdraw: i128 = to_withdraw;
+        let mut index = 0;
+        while index < self.q4w.len() && left_to_withdraw > 0 {
+            let mut cur_q4w = self.q4w.get_unchecked(index);
+            if cur_q4w.exp <= e.ledger().timestamp() {
+                // skip unlocked shares
+                index += 1;
+            } else if cur_q4w.amount > left_to_withdraw {
+                // last record we need to update, but the q4w should remain
+                cur_q4w.amount -= left_to_withdraw;
+                left_to_withdraw = 0;
+                self.q4w.set(index, cur_q4w);
+            } else {
+                // q4w fully consumed
+                left_to_withdraw -= cur_q4w.amount;
+                self.q4w.remove_unchecked(index);
+            }
+        }
+
+        if left_to_withdraw > 0 {
+            panic_with_error!(e, BackstopError::BalanceError);
+        }
+    }
+
     /// Dequeue shares from the withdrawal queue. Dequeues the most recently queued shares first.
     ///
     /// ### Arguments
@@ -140,6 +185,30 @@ impl UserBalance {
             panic_with_error!(e, BackstopError::BalanceError);
         }
     }
+
+    /// Dequeue shares from a single entry of the withdrawal queue. Any shares left in the entry
+    /// remain queued with the entry's original expiration.
+    ///
+    /// ### Arguments
+    /// * `index` - The index of the entry in the withdrawal queue
+    /// * `to_dequeue` - The amount of shares to dequeue from the entry
+    ///
+    /// ### Errors
+    /// If the entry does not exist or does not have enough queued shares to dequeue
+    pub fn dequeue_shares_from_entry(&mut self, e: &Env, index: u32, to_dequeue: i128) {
+        let mut cur_q4w = match self.q4w.get(index) {
+            Some(q4w) => q4w,
+            None => panic_with_error!(e, BackstopError::BadRequest),
+        };
+        if cur_q4w.amount < to_dequeue {
+            panic_with_error!(e, BackstopError::BalanceError);
+        } else if cur_q4w.amount == to_dequeue {
+            self.q4w.remove_unchecked(index);
+        } else {
+            cur_q4w.amount -= to_dequeue;
+            self.q4w.set(index, cur_q4w);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -344,6 +413,43 @@ mod tests {
 
     // withdraw_shares
 
+    #[test]
+    fn test_expired_q4w() {
+        let e = Env::default();
+
+        let user = UserBalance {
+            shares: 1000,
+            q4w: vec![
+                &e,
+                Q4W {
+                    amount: 200,
+                    exp: 10000000,
+                },
+                Q4W {
+                    amount: 150,
+                    exp: 11000000,
+                },
+                Q4W {
+                    amount: 75,
+                    exp: 12000000,
+                },
+            ],
+        };
+
+        e.ledger().set(LedgerInfo {
+            protocol_version: 22,
+            sequence_number: 1,
+            timestamp: 11000000,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        assert_eq!(user.expired_q4w(&e), 350);
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #10)")]
     fn test_withdraw_shares_no_q4w_panics() {
@@ -577,6 +683,177 @@ mod tests {
         user.withdraw_shares(&e, to_dequeue);
     }
 
+    // withdraw_shares_early
+
+    #[test]
+    fn test_withdraw_shares_early_multiple_entries_not_exp() {
+        let e = Env::default();
+
+        let cur_q4w = vec![
+            &e,
+            Q4W {
+                amount: 125,
+                exp: 10000000,
+            },
+            Q4W {
+                amount: 200,
+                exp: 12592000,
+            },
+            Q4W {
+                amount: 50,
+                exp: 19592000,
+            },
+        ];
+        let mut user = UserBalance {
+            shares: 1000,
+            q4w: cur_q4w.clone(),
+        };
+
+        e.ledger().set(LedgerInfo {
+            protocol_version: 22,
+            sequence_number: 1,
+            timestamp: 9192000,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let to_wd = 300;
+        user.withdraw_shares_early(&e, to_wd);
+
+        let expected_q4w = vec![
+            &e,
+            Q4W {
+                amount: 25,
+                exp: 12592000,
+            },
+            Q4W {
+                amount: 50,
+                exp: 19592000,
+            },
+        ];
+        assert_eq_vec_q4w(&user.q4w, &expected_q4w);
+        assert_eq!(user.shares, 1000);
+    }
+
+    #[test]
+    fn test_withdraw_shares_early_skips_expired_entries() {
+        let e = Env::default();
+
+        let cur_q4w = vec![
+            &e,
+            Q4W {
+                amount: 125,
+                exp: 10000000,
+            },
+            Q4W {
+                amount: 200,
+                exp: 12592000,
+            },
+            Q4W {
+                amount: 50,
+                exp: 19592000,
+            },
+        ];
+        let mut user = UserBalance {
+            shares: 1000,
+            q4w: cur_q4w.clone(),
+        };
+
+        e.ledger().set(LedgerInfo {
+            protocol_version: 22,
+            sequence_number: 1,
+            timestamp: 11192000,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let to_wd = 225;
+        user.withdraw_shares_early(&e, to_wd);
+
+        // the expired entry is left to be withdrawn without a penalty
+        let expected_q4w = vec![
+            &e,
+            Q4W {
+                amount: 125,
+                exp: 10000000,
+            },
+            Q4W {
+                amount: 25,
+                exp: 19592000,
+            },
+        ];
+        assert_eq_vec_q4w(&user.q4w, &expected_q4w);
+        assert_eq!(user.shares, 1000);
+        assert_eq!(user.expired_q4w(&e), 125);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #10)")]
+    fn test_withdraw_shares_early_over_unexpired_total() {
+        let e = Env::default();
+
+        let cur_q4w = vec![
+            &e,
+            Q4W {
+                amount: 125,
+                exp: 10000000,
+            },
+            Q4W {
+                amount: 200,
+                exp: 12592000,
+            },
+        ];
+        let mut user = UserBalance {
+            shares: 1000,
+            q4w: cur_q4w.clone(),
+        };
+
+        e.ledger().set(LedgerInfo {
+            protocol_version: 22,
+            sequence_number: 1,
+            timestamp: 11192000,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        // only 200 shares have not unlocked
+        user.withdraw_shares_early(&e, 201);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #10)")]
+    fn test_withdraw_shares_early_over_total() {
+        let e = Env::default();
+
+        let cur_q4w = vec![
+            &e,
+            Q4W {
+                amount: 125,
+                exp: 10000000,
+            },
+            Q4W {
+                amount: 200,
+                exp: 12592000,
+            },
+        ];
+        let mut user = UserBalance {
+            shares: 1000,
+            q4w: cur_q4w.clone(),
+        };
+
+        let to_wd = 326;
+        user.withdraw_shares_early(&e, to_wd);
+    }
+
     // dequeue_shares
 
     #[test]
@@ -604,6 +881,107 @@ mod tests {
         user.dequeue_shares(&e, to_wd);
     }
 
+    #[test]
+    fn test_dequeue_shares_from_entry() {
+        let e = Env::default();
+
+        let mut user = UserBalance {
+            shares: 1000,
+            q4w: vec![
+                &e,
+                Q4W {
+                    amount: 200,
+                    exp: 10000000,
+                },
+                Q4W {
+                    amount: 300,
+                    exp: 11000000,
+                },
+                Q4W {
+                    amount: 400,
+                    exp: 12000000,
+                },
+            ],
+        };
+
+        // a partial dequeue keeps the remainder queued with the original expiration
+        user.dequeue_shares_from_entry(&e, 1, 120);
+        let expected_q4w = vec![
+            &e,
+            Q4W {
+                amount: 200,
+                exp: 10000000,
+            },
+            Q4W {
+                amount: 180,
+                exp: 11000000,
+            },
+            Q4W {
+                amount: 400,
+                exp: 12000000,
+            },
+        ];
+        assert_eq_vec_q4w(&user.q4w, &expected_q4w);
+
+        // a full dequeue removes the entry
+        user.dequeue_shares_from_entry(&e, 0, 200);
+        let expected_q4w = vec![
+            &e,
+            Q4W {
+                amount: 180,
+                exp: 11000000,
+            },
+            Q4W {
+                amount: 400,
+                exp: 12000000,
+            },
+        ];
+        assert_eq_vec_q4w(&user.q4w, &expected_q4w);
+        assert_eq!(user.shares, 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #10)")]
+    fn test_dequeue_shares_from_entry_over_entry_panics() {
+        let e = Env::default();
+
+        let mut user = UserBalance {
+            shares: 1000,
+            q4w: vec![
+                &e,
+                Q4W {
+                    amount: 200,
+                    exp: 10000000,
+                },
+                Q4W {
+                    amount: 300,
+                    exp: 11000000,
+                },
+            ],
+        };
+
+        user.dequeue_shares_from_entry(&e, 0, 201);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_dequeue_shares_from_entry_missing_entry_panics() {
+        let e = Env::default();
+
+        let mut user = UserBalance {
+            shares: 1000,
+            q4w: vec![
+                &e,
+                Q4W {
+                    amount: 200,
+                    exp: 10000000,
+                },
+            ],
+        };
+
+        user.dequeue_shares_from_entry(&e, 1, 100);
+    }
+
     #[test]
     fn test_dequeue_shares_exact_amount() {
         let e = Env::default();
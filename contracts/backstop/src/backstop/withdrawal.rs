@@ -1,10 +1,16 @@
 use crate::{
-    contract::require_nonnegative, dependencies::PoolClient, emissions, storage, BackstopError,
+    constants::{Q4W_EARLY_EXIT_PENALTY, SCALAR_7},
+    contract::require_nonnegative,
+    dependencies::PoolClient,
+    emissions, storage, BackstopError,
 };
 use sep_41_token::TokenClient;
+use soroban_fixed_point_math::FixedPoint;
 use soroban_sdk::{panic_with_error, unwrap::UnwrapOptimized, Address, Env};
 
-use super::Q4W;
+use super::{
+    notify_share_burn, notify_share_mint, require_unlocked, PoolBalance, UserBalance, Q4W,
+};
 
 /// Perform a queue for withdraw from the backstop module
 pub fn execute_queue_withdrawal(
@@ -21,12 +27,16 @@ pub fn execute_queue_withdrawal(
     // update emissions
     emissions::update_emissions(e, pool_address, &pool_balance, from, &user_balance);
 
+    require_unlocked(e, from, pool_address, user_balance.shares, amount);
     user_balance.queue_shares_for_withdrawal(e, amount);
     pool_balance.queue_for_withdraw(amount);
 
     storage::set_user_balance(e, pool_address, from, &user_balance);
     storage::set_pool_balance(e, pool_address, &pool_balance);
 
+    // queued shares are locked, and leave the user's share token balance
+    notify_share_burn(e, pool_address, from, amount);
+
     user_balance.q4w.last().unwrap_optimized()
 }
 
@@ -46,6 +56,35 @@ pub fn execute_dequeue_withdrawal(e: &Env, from: &Address, pool_address: &Addres
 
     storage::set_user_balance(e, pool_address, from, &user_balance);
     storage::set_pool_balance(e, pool_address, &pool_balance);
+
+    notify_share_mint(e, pool_address, from, amount);
+}
+
+/// Perform a dequeue of part or all of a single queued for withdraw entry from the backstop module.
+/// Any shares left in the entry remain queued with the entry's original expiration.
+pub fn execute_dequeue_withdrawal_entry(
+    e: &Env,
+    from: &Address,
+    pool_address: &Address,
+    index: u32,
+    amount: i128,
+) {
+    require_nonnegative(e, amount);
+
+    let mut pool_balance = storage::get_pool_balance(e, pool_address);
+    let mut user_balance = storage::get_user_balance(e, pool_address, from);
+
+    // update emissions
+    emissions::update_emissions(e, pool_address, &pool_balance, from, &user_balance);
+
+    user_balance.dequeue_shares_from_entry(e, index, amount);
+    user_balance.add_shares(amount);
+    pool_balance.dequeue_q4w(e, amount);
+
+    storage::set_user_balance(e, pool_address, from, &user_balance);
+    storage::set_pool_balance(e, pool_address, &pool_balance);
+
+    notify_share_mint(e, pool_address, from, amount);
 }
 
 /// Perform a withdraw from the backstop module
@@ -78,6 +117,81 @@ pub fn execute_withdraw(e: &Env, from: &Address, pool_address: &Address, amount:
     to_return
 }
 
+/// Withdraw all of a user's expired queued shares as part of another action, so matured withdrawals
+/// are not left unexecuted. Nothing is withdrawn if the user opted out or the pool's backstop has
+/// bad debt. The caller is responsible for storing the balances and transferring the tokens.
+///
+/// Returns the (shares withdrawn, tokens to return)
+pub(super) fn sweep_expired_q4w(
+    e: &Env,
+    from: &Address,
+    pool_address: &Address,
+    pool_balance: &mut PoolBalance,
+    user_balance: &mut UserBalance,
+) -> (i128, i128) {
+    let expired = user_balance.expired_q4w(e);
+    if expired == 0 || storage::get_q4w_sweep_opt_out(e, pool_address, from) {
+        return (0, 0);
+    }
+
+    let pool_client = PoolClient::new(e, pool_address);
+    let backstop_positions = pool_client.get_positions(&e.current_contract_address());
+    if backstop_positions.liabilities.len() > 0 {
+        return (0, 0);
+    }
+
+    let to_return = pool_balance.convert_to_tokens(expired);
+    if to_return == 0 {
+        return (0, 0);
+    }
+    user_balance.withdraw_shares(e, expired);
+    pool_balance.withdraw(e, to_return, expired);
+    (expired, to_return)
+}
+
+/// Perform a withdraw from the backstop module of shares that have not finished their queue for withdrawal.
+/// A penalty is deducted from the tokens returned, and remains in the pool's backstop.
+///
+/// Returns the (tokens returned, tokens forfeited as a penalty)
+pub fn execute_withdraw_early(
+    e: &Env,
+    from: &Address,
+    pool_address: &Address,
+    amount: i128,
+) -> (i128, i128) {
+    require_nonnegative(e, amount);
+
+    let pool_client = PoolClient::new(e, pool_address);
+    let backstop_positions = pool_client.get_positions(&e.current_contract_address());
+    if backstop_positions.liabilities.len() > 0 {
+        panic_with_error!(e, &BackstopError::BadDebtExists);
+    }
+
+    let mut pool_balance = storage::get_pool_balance(e, pool_address);
+    let mut user_balance = storage::get_user_balance(e, pool_address, from);
+
+    user_balance.withdraw_shares_early(e, amount);
+
+    let tokens = pool_balance.convert_to_tokens(amount);
+    let penalty = tokens
+        .fixed_mul_ceil(Q4W_EARLY_EXIT_PENALTY, SCALAR_7)
+        .unwrap_optimized();
+    let to_return = tokens - penalty;
+    if to_return <= 0 {
+        panic_with_error!(e, &BackstopError::InvalidTokenWithdrawAmount);
+    }
+    // only the returned tokens leave the pool's backstop, so the penalty accrues to the remaining shares
+    pool_balance.withdraw(e, to_return, amount);
+
+    storage::set_user_balance(e, pool_address, from, &user_balance);
+    storage::set_pool_balance(e, pool_address, &pool_balance);
+
+    let backstop_token_client = TokenClient::new(e, &storage::get_backstop_token(e));
+    backstop_token_client.transfer(&e.current_contract_address(), from, &to_return);
+
+    (to_return, penalty)
+}
+
 #[cfg(test)]
 mod tests {
     use mock_pool::Positions;
@@ -328,6 +442,90 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_dequeue_withdrawal_entry() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        // queue shares for withdraw
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 75_0000000);
+
+            e.ledger().set(LedgerInfo {
+                protocol_version: 22,
+                sequence_number: 100,
+                timestamp: 10000,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 10,
+                min_persistent_entry_ttl: 10,
+                max_entry_ttl: 3110400,
+            });
+
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 25_0000000);
+
+            e.ledger().set(LedgerInfo {
+                protocol_version: 22,
+                sequence_number: 100,
+                timestamp: 20000,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 10,
+                min_persistent_entry_ttl: 10,
+                max_entry_ttl: 3110400,
+            });
+
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 40_0000000);
+        });
+
+        e.ledger().set(LedgerInfo {
+            protocol_version: 22,
+            sequence_number: 200,
+            timestamp: 30000,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        e.as_contract(&backstop_address, || {
+            // dequeue from the oldest entry, which a plain dequeue would not touch
+            execute_dequeue_withdrawal_entry(&e, &samwise, &pool_address, 0, 15_0000000);
+
+            let new_user_balance = storage::get_user_balance(&e, &pool_address, &samwise);
+            assert_eq!(new_user_balance.shares, 25_0000000);
+            let expected_q4w = vec![
+                &e,
+                Q4W {
+                    amount: 10_0000000,
+                    exp: 10000 + 17 * 24 * 60 * 60,
+                },
+                Q4W {
+                    amount: 40_0000000,
+                    exp: 20000 + 17 * 24 * 60 * 60,
+                },
+            ];
+            assert_eq_vec_q4w(&new_user_balance.q4w, &expected_q4w);
+
+            let new_pool_balance = storage::get_pool_balance(&e, &pool_address);
+            assert_eq!(new_pool_balance.q4w, 50_0000000);
+            assert_eq!(new_pool_balance.shares, 75_0000000);
+            assert_eq!(new_pool_balance.tokens, 75_0000000);
+        });
+    }
+
     #[test]
     fn test_execute_withdrawal() {
         let e = Env::default();
@@ -401,6 +599,80 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_withdraw_early() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let (pool_address, _) = create_mock_pool(&e);
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &150_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.ledger().set(LedgerInfo {
+            protocol_version: 22,
+            sequence_number: 200,
+            timestamp: 10000,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        backstop_token_client.approve(
+            &samwise,
+            &backstop_address,
+            &50_0000000,
+            &e.ledger().sequence(),
+        );
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 100_0000000);
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 42_0000000);
+            execute_donate(&e, &samwise, &pool_address, 50_0000000);
+        });
+
+        // withdraw before the Q4W unlocks
+        e.ledger().set(LedgerInfo {
+            protocol_version: 22,
+            sequence_number: 200,
+            timestamp: 10000 + 24 * 60 * 60,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        e.as_contract(&backstop_address, || {
+            let (tokens, penalty) = execute_withdraw_early(&e, &samwise, &pool_address, 42_0000000);
+            assert_eq!(tokens, 56_7000000);
+            assert_eq!(penalty, 6_3000000);
+
+            let new_user_balance = storage::get_user_balance(&e, &pool_address, &samwise);
+            assert_eq!(new_user_balance.shares, 100_0000000 - 42_0000000);
+            assert_eq!(new_user_balance.q4w.len(), 0);
+
+            let new_pool_balance = storage::get_pool_balance(&e, &pool_address);
+            assert_eq!(new_pool_balance.q4w, 0);
+            assert_eq!(new_pool_balance.shares, 100_0000000 - 42_0000000);
+            assert_eq!(new_pool_balance.tokens, 150_0000000 - tokens);
+
+            assert_eq!(
+                backstop_token_client.balance(&backstop_address),
+                150_0000000 - tokens
+            );
+            assert_eq!(backstop_token_client.balance(&samwise), tokens);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #8)")]
     fn test_execute_withdrawal_negative_amount() {
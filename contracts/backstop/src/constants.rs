@@ -1,18 +1,85 @@
-/// Fixed-point scalar for 7 decimal numbers
-pub const SCALAR_7: i128 = 1_0000000;
+pub use trustbridge_common::SCALAR_7;
 
 /// Fixed-point scalar for 14 decimal numbers
 pub const SCALAR_14: i128 = 1_0000000_0000000;
 
+/// The number of seconds in a year
+pub const SECONDS_PER_YEAR: i128 = 31536000;
+
+/// The length in seconds of the trailing window the backstop APR is measured over (7 days).
+pub const BACKSTOP_APR_WINDOW: u64 = 7 * 24 * 60 * 60;
+
 /// The maximum reward zone size
 pub const MAX_RZ_SIZE: u32 = 30;
 
+/// The maximum number of pool factories approved alongside the backstop's pool factory
+pub const MAX_APPROVED_FACTORIES: u32 = 10;
+
 /// The maximum amount of active Q4W entries that a user can have against a single backstop.
 pub const MAX_Q4W_SIZE: u32 = 20;
 
 /// The time in seconds that a Q4W entry is locked for (17 days).
 pub const Q4W_LOCK_TIME: u64 = 17 * 24 * 60 * 60;
 
+/// The lock tiers available for backstop deposits, as (lock duration in seconds, emission
+/// multiplier (7 decimals)). The tiers lock deposits for 30, 90, and 180 days.
+pub const LOCK_TIERS: [(u64, i128); 3] = [
+    (30 * 24 * 60 * 60, 1_1000000),
+    (90 * 24 * 60 * 60, 1_2500000),
+    (180 * 24 * 60 * 60, 1_5000000),
+];
+
+/// The maximum amount of deposit locks that a user can have against a single backstop, including
+/// expired locks whose bonus has not been claimed.
+pub const MAX_DEPOSIT_LOCKS: u32 = 20;
+
+/// The percentage of tokens forfeited to the pool's backstop when a Q4W entry is withdrawn before
+/// it unlocks (7 decimals).
+pub const Q4W_EARLY_EXIT_PENALTY: i128 = 0_1000000;
+
 /// The maximum amount of backfilled emissions that can be emitted.
 /// Represents between 3-4 months worth of token emissions.
 pub const MAX_BACKFILLED_EMISSIONS: i128 = 10_000_000 * SCALAR_7;
+
+/// The time in seconds that backstop shares minted from a vested claim unlock over (30 days).
+pub const CLAIM_VESTING_PERIOD: u64 = 30 * 24 * 60 * 60;
+
+/// The multiplier applied to BLND claimed with vesting, paid from the vesting bonus reserve
+/// (7 decimals).
+pub const CLAIM_VESTING_BONUS: i128 = 1_2000000;
+
+/// The maximum amount of vested claims that a user can have vesting in a single backstop.
+pub const MAX_CLAIM_VESTINGS: u32 = 20;
+
+/// The time in seconds a pool's backstop must stay below the threshold before a recapitalization
+/// auction can be started (3 days).
+pub const RECAP_DELAY: u64 = 3 * 24 * 60 * 60;
+
+/// The length in seconds of a recapitalization epoch (7 days).
+pub const RECAP_EPOCH: u64 = 7 * 24 * 60 * 60;
+
+/// The maximum amount of BLND that can be sold by recapitalization auctions each epoch.
+pub const RECAP_EPOCH_CAP: i128 = 500_000 * SCALAR_7;
+
+/// The premium over the spot value of the lot a recapitalization auction's bid starts at
+/// (7 decimals).
+pub const RECAP_START_PREMIUM: i128 = 1_2000000;
+
+/// The number of blocks a recapitalization auction's bid decays over, to half its starting value.
+pub const RECAP_DECAY_BLOCKS: u32 = 400;
+
+/// The number of blocks that must pass after a recapitalization auction starts before it can be
+/// filled.
+pub const RECAP_MIN_BLOCKS: u32 = 5;
+
+/// The number of blocks after a recapitalization auction starts that it expires at, after which
+/// it can no longer be filled and can be cancelled.
+pub const RECAP_EXPIRY_BLOCKS: u32 = 1000;
+
+/// The maximum time in seconds between checks of a pool's backstop for it to count as staying
+/// below the threshold (1 hour).
+pub const RECAP_CHECK_INTERVAL: u64 = 60 * 60;
+
+/// The maximum slippage against the oracle value of a recapitalization auction's bid allowed when
+/// depositing it into the backstop token (7 decimals).
+pub const RECAP_MAX_SLIPPAGE: i128 = 0_0500000;
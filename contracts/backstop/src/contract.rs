@@ -1,13 +1,19 @@
 use crate::{
-    backstop::{self, load_pool_backstop_data, PoolBackstopData, UserBalance, Q4W},
+    backstop::{
+        self, load_lp_token_price, load_pool_backstop_data, load_pool_backstop_report, BackstopApr,
+        DepositLock, PoolBackstopData, PoolBackstopReport, RecapAuction, UserBalance, Q4W,
+    },
     constants::{MAX_BACKFILLED_EMISSIONS, SCALAR_7},
     dependencies::EmitterClient,
-    emissions,
+    emissions::{self, BackstopEmissionsReport, ClaimVesting},
     errors::BackstopError,
     events::BackstopEvents,
     storage,
 };
-use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, panic_with_error, Address, BytesN, Env, Map, Vec,
+};
+use trustbridge_common::{ContractStatus, Role};
 
 /// ### Backstop
 ///
@@ -19,7 +25,8 @@ pub struct BackstopContract;
 pub trait Backstop {
     /********** Core **********/
 
-    /// Deposit backstop tokens from `from` into the backstop of a pool
+    /// Deposit backstop tokens from `from` into the backstop of a pool. Any of `from`'s expired
+    /// queued withdrawals are withdrawn in the same call, unless they opted out.
     ///
     /// Returns the number of backstop pool shares minted
     ///
@@ -29,6 +36,17 @@ pub trait Backstop {
     /// * `amount` - The amount of tokens to deposit
     fn deposit(e: Env, from: Address, pool_address: Address, amount: i128) -> i128;
 
+    /// (Only Pool Factory) Deposit backstop tokens from `from` into the backstop of a pool that is
+    /// being deployed by the pool factory
+    ///
+    /// Returns the number of backstop pool shares minted
+    ///
+    /// ### Arguments
+    /// * `from` - The address depositing into the backstop
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of tokens to deposit
+    fn factory_deposit(e: Env, from: Address, pool_address: Address, amount: i128) -> i128;
+
     /// Queue deposited pool shares from `from` for withdraw from a backstop of a pool
     ///
     /// Returns the created queue for withdrawal
@@ -57,6 +75,34 @@ pub trait Backstop {
     /// * `amount` - The amount of shares to withdraw
     fn withdraw(e: Env, from: Address, pool_address: Address, amount: i128) -> i128;
 
+    /// Withdraw shares from `from`s withdraw queue for a backstop of a pool before they unlock. A penalty
+    /// of 10% of the tokens is forfeited and remains in the pool's backstop. Only shares that have not
+    /// unlocked are withdrawn, as unlocked shares can be withdrawn without a penalty with `withdraw`.
+    ///
+    /// Returns the amount of tokens returned
+    ///
+    /// ### Arguments
+    /// * `from` - The address whose shares are being withdrawn
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of shares to withdraw
+    fn withdraw_early(e: Env, from: Address, pool_address: Address, amount: i128) -> i128;
+
+    /// Opt `from` into or out of withdrawing their expired queued withdrawals when they deposit
+    /// into the backstop of a pool
+    ///
+    /// ### Arguments
+    /// * `from` - The address whose queued withdrawals are swept
+    /// * `pool_address` - The address of the pool
+    /// * `opt_out` - If expired queued withdrawals are left queued when depositing
+    fn set_q4w_sweep_opt_out(e: Env, from: Address, pool_address: Address, opt_out: bool);
+
+    /// Fetch if a user opted out of withdrawing their expired queued withdrawals when they deposit
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `user` - The user
+    fn q4w_sweep_opt_out(e: Env, pool_address: Address, user: Address) -> bool;
+
     /// Fetch the balance of backstop shares of a pool for the user
     ///
     /// ### Arguments
@@ -72,12 +118,37 @@ pub trait Backstop {
     /// * `pool_address` - The address of the pool
     fn pool_data(e: Env, pool: Address) -> PoolBackstopData;
 
+    /// Fetch a report of the pool's backstop for risk monitoring, including the pool's share and
+    /// token balances, queued withdrawals, the USDC value of its backstop tokens, whether it is
+    /// above the backstop threshold, and its current backstop take rate
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn get_pool_backstop_report(e: Env, pool: Address) -> PoolBackstopReport;
+
+    /// Fetch the cumulative amount of backstop tokens a donor has donated to a pool
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `donor` - The address of the donor
+    fn get_donations(e: Env, pool: Address, donor: Address) -> i128;
+
     /// Fetch the backstop token for the backstop
     fn backstop_token(e: Env) -> Address;
 
     /// Fetch the reward zone for the backstop
     fn reward_zone(e: Env) -> Vec<Address>;
 
+    /// Fetch the USD value of one backstop token (7 decimals), computed from the BLND and USDC
+    /// held by the backstop token and their prices from the backstop's oracle
+    ///
+    /// ### Panics
+    /// If the oracle does not have a valid price for BLND or USDC
+    fn lp_token_price(e: Env) -> i128;
+
+    /// Fetch the oracle used to price the backstop token
+    fn oracle(e: Env) -> Address;
+
     /********** Emissions **********/
 
     /// Update the backstop with new emissions for all reward zone pools
@@ -128,6 +199,62 @@ pub trait Backstop {
     /// If an invalid pool address is included
     fn claim(e: Env, from: Address, pool_addresses: Vec<Address>, min_lp_tokens_out: i128) -> i128;
 
+    /// Claim backstop deposit emissions from a list of pools for `from` and deposit them like
+    /// `claim`, except the minted backstop shares unlock linearly over 30 days. Vested claims earn
+    /// a 1.2x bonus, paid from the vesting bonus reserve while it lasts. Each vested claim unlocks
+    /// on its own schedule.
+    ///
+    /// Returns the amount of LP tokens minted
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user claiming emissions
+    /// * `pool_addresses` - The Vec of addresses to claim backstop deposit emissions from
+    /// * `min_lp_tokens_out` - The minimum amount of LP tokens to mint with the claimed BLND
+    ///
+    /// ### Errors
+    /// If an invalid pool address is included, no emissions are claimed, or `from` has too many
+    /// claims vesting in a pool
+    fn claim_vested(
+        e: Env,
+        from: Address,
+        pool_addresses: Vec<Address>,
+        min_lp_tokens_out: i128,
+    ) -> i128;
+
+    /// Fetch the vested claims of a user in the backstop of a pool that have not fully vested
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `user` - The address of the user
+    fn claim_vestings(e: Env, pool: Address, user: Address) -> Vec<ClaimVesting>;
+
+    /// Send BLND from `from` to the reserve vested claim bonuses are paid from
+    ///
+    /// NOTE: This is not a deposit, and `from` will permanently lose access to the funds
+    ///
+    /// ### Arguments
+    /// * `from` - The address sending the BLND
+    /// * `amount` - The amount of BLND to add
+    fn fund_vesting_bonus(e: Env, from: Address, amount: i128);
+
+    /// Fetch the amount of BLND available to pay vested claim bonuses
+    fn vesting_bonus_reserve(e: Env) -> i128;
+
+    /// Fetch a report of a pool's backstop emissions, including the emission index at the
+    /// current ledger, the emissions per second to the pool's backstop, when they expire, and
+    /// an APY estimate for unqueued deposits based on the backstop token's spot prices
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn get_backstop_emissions(e: Env, pool: Address) -> BackstopEmissionsReport;
+
+    /// Fetch the BLND emissions `user` could claim from a pool's backstop at the current ledger
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `pool` - The address of the pool
+    fn preview_backstop_claim(e: Env, user: Address, pool: Address) -> i128;
+
     /// Drop initial BLND to a list of addresses through the emitter
     fn drop(e: Env);
 
@@ -159,6 +286,259 @@ pub trait Backstop {
     /// If the `pool_address` is not valid, backstop does not have sufficient allowance from `from`, or if the pool does not
     /// authorize the call
     fn donate(e: Env, from: Address, pool_address: Address, amount: i128);
+
+    /// (Only Pool) Sends backstop tokens the pool collected from an auction filler to the pool's
+    /// backstop. The donation is attributed to the filler.
+    ///
+    /// ### Arguments
+    /// * `filler` - The address of the auction filler that paid the bid to the pool
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of backstop tokens to add
+    ///
+    /// ### Errors
+    /// If the `pool_address` is not valid, backstop does not have sufficient allowance from the
+    /// pool, or if the pool does not authorize the call
+    fn donate_bid(e: Env, filler: Address, pool_address: Address, amount: i128);
+
+    /********** Share Tokens *********/
+
+    /// Deploy the SEP-41 share token of a pool's backstop. A user's share token balance is their
+    /// shares in the pool's backstop that are not queued for withdrawal, so queued shares are
+    /// locked until they are dequeued.
+    ///
+    /// Returns the address of the share token
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    ///
+    /// ### Errors
+    /// If the pool is not valid or already has a share token
+    fn deploy_share_token(e: Env, pool_address: Address) -> Address;
+
+    /// Fetch the share token of a pool's backstop, if it has been deployed
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    fn share_token(e: Env, pool_address: Address) -> Option<Address>;
+
+    /// Fetch the shares of a pool's backstop held by `user` that are not queued for withdrawal
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `user` - The address of the user
+    fn share_balance(e: Env, pool: Address, user: Address) -> i128;
+
+    /// (Only Share Token) Transfer shares of a pool's backstop that are not queued for withdrawal
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `from` - The address sending shares
+    /// * `to` - The address receiving shares
+    /// * `amount` - The amount of shares
+    ///
+    /// ### Errors
+    /// If the caller is not the pool's share token, or `from` does not have enough unqueued shares
+    fn transfer_shares(e: Env, pool: Address, from: Address, to: Address, amount: i128);
+
+    /// (Only Pool Factory) Approve another pool factory to deploy pools with the given WASM hash,
+    /// or revoke its approval. Pools deployed by an approved factory are accepted by the backstop
+    /// only while the factory reports the approved WASM hash, so deprecated or look-alike
+    /// factories cannot direct deposits to their pools.
+    ///
+    /// ### Arguments
+    /// * `factory` - The pool factory
+    /// * `pool_hash` - The WASM hash of the pools the factory is approved to deploy, or None to revoke
+    ///
+    /// ### Errors
+    /// If the factory is the pool factory, the factory does not deploy pools with `pool_hash`, or
+    /// too many factories are approved
+    fn set_approved_factory(e: Env, factory: Address, pool_hash: Option<BytesN<32>>);
+
+    /// Fetch the pool factories approved alongside the pool factory, and the WASM hash of the
+    /// pools each is approved to deploy
+    fn approved_factories(e: Env) -> Map<Address, BytesN<32>>;
+
+    /********** Deposit Locks *********/
+
+    /// Deposit backstop tokens from `from` into the backstop of a pool, and lock the minted shares
+    /// for the duration of a lock tier in exchange for an emission multiplier. Locked shares cannot
+    /// be queued for withdrawal or transferred until the lock expires.
+    ///
+    /// The lock tiers are 30 days (1.1x), 90 days (1.25x), and 180 days (1.5x). The bonus
+    /// emissions are paid from the lock bonus reserve when `from` claims. A lock that expires
+    /// before its bonus is claimed is paid its bonus up to its expiration on the next claim.
+    ///
+    /// Returns the number of backstop pool shares minted and locked
+    ///
+    /// ### Arguments
+    /// * `from` - The address depositing into the backstop
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of tokens to deposit
+    /// * `tier` - The index of the lock tier (0 for 30 days, 1 for 90 days, 2 for 180 days)
+    ///
+    /// ### Errors
+    /// If the tier does not exist or `from` has too many locks, including expired locks whose
+    /// bonus has not been claimed
+    fn deposit_locked(
+        e: Env,
+        from: Address,
+        pool_address: Address,
+        amount: i128,
+        tier: u32,
+    ) -> i128;
+
+    /// Fetch the active deposit locks of a user in the backstop of a pool
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `user` - The user
+    fn deposit_locks(e: Env, pool: Address, user: Address) -> Vec<DepositLock>;
+
+    /// Send BLND from `from` to the reserve lock bonuses are paid from
+    ///
+    /// NOTE: This is not a deposit, and `from` will permanently lose access to the funds
+    ///
+    /// ### Arguments
+    /// * `from` - The address sending the BLND
+    /// * `amount` - The amount of BLND to add
+    fn fund_lock_bonus(e: Env, from: Address, amount: i128);
+
+    /// Fetch the amount of BLND available to pay lock bonuses
+    fn lock_bonus_reserve(e: Env) -> i128;
+
+    /// Dequeue part or all of a single entry of `from`s withdraw queue for a backstop of a pool.
+    /// Any shares left in the entry remain queued with the entry's original expiration.
+    ///
+    /// ### Arguments
+    /// * `from` - The address whose deposits are being dequeued
+    /// * `pool_address` - The address of the pool
+    /// * `index` - The index of the entry in the withdraw queue
+    /// * `amount` - The amount of shares to dequeue from the entry
+    ///
+    /// ### Errors
+    /// If the entry does not exist or has fewer than `amount` shares queued
+    fn dequeue_withdrawal_entry(
+        e: Env,
+        from: Address,
+        pool_address: Address,
+        index: u32,
+        amount: i128,
+    );
+
+    /// Fetch the status of the backstop. The backstop has no pause switch, so it is always Active.
+    fn status(e: Env) -> ContractStatus;
+
+    /// Start a recapitalization auction for a pool's backstop once it has stayed below the
+    /// threshold for 3 days. Also records when the backstop is seen below the threshold, and it
+    /// only counts as staying below if this is called at least once an hour, so keepers should
+    /// call it as soon as the backstop falls below the threshold and hourly after. The auction
+    /// sells BLND the emitter has approved the backstop to spend for USDC at a premium over the
+    /// oracle price, up to a cap on the BLND sold each epoch.
+    ///
+    /// Returns the auction started, or None if the pool does not need recapitalizing yet
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    ///
+    /// ### Errors
+    /// If the pool is not from the pool factory, or already has a recapitalization auction
+    fn start_recap_auction(e: Env, pool_address: Address) -> Option<RecapAuction>;
+
+    /// Fill a pool's recapitalization auction. The filler pays the current bid in USDC, which
+    /// decays to half its starting value over 400 blocks, and receives the BLND lot. The USDC is
+    /// deposited into the backstop token for the pool's backstop. The auction can be filled from
+    /// 5 blocks after it starts until it expires 1000 blocks after it starts.
+    ///
+    /// Returns the amount of USDC paid
+    ///
+    /// ### Arguments
+    /// * `filler` - The address filling the auction
+    /// * `pool_address` - The address of the pool
+    ///
+    /// ### Errors
+    /// If the pool has no recapitalization auction, it can not be filled yet or has expired, or
+    /// the USDC deposits into too few backstop tokens for its oracle value
+    fn fill_recap_auction(e: Env, filler: Address, pool_address: Address) -> i128;
+
+    /// Cancel a pool's recapitalization auction that expired unfilled, returning its lot to the
+    /// epoch's cap so a new auction can be started
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    ///
+    /// ### Errors
+    /// If the pool has no recapitalization auction, or it has not expired
+    fn cancel_recap_auction(e: Env, pool_address: Address);
+
+    /// Fetch a pool's recapitalization auction, if any
+    fn recap_auction(e: Env, pool_address: Address) -> Option<RecapAuction>;
+
+    /// Fetch the APR of a pool's backstop over a trailing window, split into interest donated by
+    /// the pool, BLND emissions, and swap fees earned by the backstop token. The window starts at
+    /// an on-chain checkpoint taken by `donate` and `gulp_emissions` at most once every 7 days,
+    /// so the APR is measured over the last 7 to 14 days.
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn get_backstop_apr(e: Env, pool: Address) -> BackstopApr;
+
+    /********** Admin **********/
+
+    /// Fetch the admin of the backstop
+    fn admin(e: Env) -> Address;
+
+    /// (Admin only) Propose a new admin for the backstop
+    ///
+    /// ### Arguments
+    /// * `new_admin` - The new admin
+    ///
+    /// ### Errors
+    /// If the caller is not the admin
+    fn propose_admin(e: Env, new_admin: Address);
+
+    /// (Proposed admin only) Accept the admin role
+    ///
+    /// ### Errors
+    /// If no admin is proposed, or the caller is not the proposed admin
+    fn accept_admin(e: Env);
+
+    /// (Admin only) Grant a role to an address. The admin holds every role, and the admin role
+    /// itself is transferred through `propose_admin` and `accept_admin`.
+    ///
+    /// ### Arguments
+    /// * `role` - The role to grant
+    /// * `member` - The address to grant the role to
+    ///
+    /// ### Errors
+    /// If the caller is not the admin or the role is the admin role
+    fn grant_role(e: Env, role: Role, member: Address);
+
+    /// (Admin only) Revoke a role from an address
+    ///
+    /// ### Arguments
+    /// * `role` - The role to revoke
+    /// * `member` - The address to revoke the role from
+    ///
+    /// ### Errors
+    /// If the caller is not the admin or the role is the admin role
+    fn revoke_role(e: Env, role: Role, member: Address);
+
+    /// Check if an address holds a role. The admin holds every role.
+    ///
+    /// ### Arguments
+    /// * `role` - The role
+    /// * `member` - The address to check
+    fn has_role(e: Env, role: Role, member: Address) -> bool;
+
+    /// (Risk role only) Set the oracle used to price BLND and USDC
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `oracle` - The oracle
+    ///
+    /// ### Errors
+    /// If the caller does not hold the risk role
+    fn set_oracle(e: Env, caller: Address, oracle: Address);
 }
 
 #[contractimpl]
@@ -171,6 +551,8 @@ impl BackstopContract {
     /// * `blnd_token` - The BLND token ID
     /// * `usdc_token` - The USDC token ID
     /// * `pool_factory` - The pool factory ID
+    /// * `oracle` - The oracle used to price BLND and USDC
+    /// * `admin` - The admin, which holds every role and can grant the others
     /// * `drop_list` - The list of addresses to distribute initial BLND to and the percent of the distribution they should receive
     pub fn __constructor(
         e: Env,
@@ -179,12 +561,16 @@ impl BackstopContract {
         blnd_token: Address,
         usdc_token: Address,
         pool_factory: Address,
+        oracle: Address,
+        admin: Address,
         drop_list: Vec<(Address, i128)>,
     ) {
         storage::set_backstop_token(&e, &backstop_token);
         storage::set_blnd_token(&e, &blnd_token);
         storage::set_usdc_token(&e, &usdc_token);
         storage::set_pool_factory(&e, &pool_factory);
+        storage::set_oracle(&e, &oracle);
+        storage::set_admin(&e, &admin);
         let mut drop_total: i128 = 0;
         for (_, amount) in drop_list.iter() {
             drop_total += amount;
@@ -210,7 +596,20 @@ impl Backstop for BackstopContract {
 
         let to_mint = backstop::execute_deposit(&e, &from, &pool_address, amount);
 
-        BackstopEvents::deposit(&e, pool_address, from, amount, to_mint);
+        BackstopEvents::deposit(&e, pool_address.clone(), from.clone(), amount, to_mint);
+        emit_backstop_state(&e, pool_address, "deposit", Some(from));
+        to_mint
+    }
+
+    fn factory_deposit(e: Env, from: Address, pool_address: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_pool_factory(&e).require_auth();
+        from.require_auth();
+
+        let to_mint = backstop::execute_factory_deposit(&e, &from, &pool_address, amount);
+
+        BackstopEvents::deposit(&e, pool_address.clone(), from.clone(), amount, to_mint);
+        emit_backstop_state(&e, pool_address, "deposit", Some(from));
         to_mint
     }
 
@@ -220,7 +619,14 @@ impl Backstop for BackstopContract {
 
         let to_queue = backstop::execute_queue_withdrawal(&e, &from, &pool_address, amount);
 
-        BackstopEvents::queue_withdrawal(&e, pool_address, from, amount, to_queue.exp);
+        BackstopEvents::queue_withdrawal(
+            &e,
+            pool_address.clone(),
+            from.clone(),
+            amount,
+            to_queue.exp,
+        );
+        emit_backstop_state(&e, pool_address, "queue_withdrawal", Some(from));
         to_queue
     }
 
@@ -230,7 +636,8 @@ impl Backstop for BackstopContract {
 
         backstop::execute_dequeue_withdrawal(&e, &from, &pool_address, amount);
 
-        BackstopEvents::dequeue_withdrawal(&e, pool_address, from, amount);
+        BackstopEvents::dequeue_withdrawal(&e, pool_address.clone(), from.clone(), amount);
+        emit_backstop_state(&e, pool_address, "dequeue_withdrawal", Some(from));
     }
 
     fn withdraw(e: Env, from: Address, pool_address: Address, amount: i128) -> i128 {
@@ -239,10 +646,43 @@ impl Backstop for BackstopContract {
 
         let to_withdraw = backstop::execute_withdraw(&e, &from, &pool_address, amount);
 
-        BackstopEvents::withdraw(&e, pool_address, from, amount, to_withdraw);
+        BackstopEvents::withdraw(&e, pool_address.clone(), from.clone(), amount, to_withdraw);
+        emit_backstop_state(&e, pool_address, "withdraw", Some(from));
         to_withdraw
     }
 
+    fn withdraw_early(e: Env, from: Address, pool_address: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let (to_withdraw, penalty) =
+            backstop::execute_withdraw_early(&e, &from, &pool_address, amount);
+
+        BackstopEvents::withdraw_early(
+            &e,
+            pool_address.clone(),
+            from.clone(),
+            amount,
+            to_withdraw,
+            penalty,
+        );
+        emit_backstop_state(&e, pool_address, "withdraw_early", Some(from));
+        to_withdraw
+    }
+
+    fn set_q4w_sweep_opt_out(e: Env, from: Address, pool_address: Address, opt_out: bool) {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        storage::set_q4w_sweep_opt_out(&e, &pool_address, &from, opt_out);
+
+        BackstopEvents::set_q4w_sweep_opt_out(&e, pool_address, from, opt_out);
+    }
+
+    fn q4w_sweep_opt_out(e: Env, pool_address: Address, user: Address) -> bool {
+        storage::get_q4w_sweep_opt_out(&e, &pool_address, &user)
+    }
+
     fn user_balance(e: Env, pool: Address, user: Address) -> UserBalance {
         storage::get_user_balance(&e, &pool, &user)
     }
@@ -251,6 +691,14 @@ impl Backstop for BackstopContract {
         load_pool_backstop_data(&e, &pool)
     }
 
+    fn get_pool_backstop_report(e: Env, pool: Address) -> PoolBackstopReport {
+        load_pool_backstop_report(&e, &pool)
+    }
+
+    fn get_donations(e: Env, pool: Address, donor: Address) -> i128 {
+        storage::get_donations(&e, &pool, &donor)
+    }
+
     fn backstop_token(e: Env) -> Address {
         storage::get_backstop_token(&e)
     }
@@ -259,6 +707,14 @@ impl Backstop for BackstopContract {
         storage::get_reward_zone(&e)
     }
 
+    fn lp_token_price(e: Env) -> i128 {
+        load_lp_token_price(&e)
+    }
+
+    fn oracle(e: Env) -> Address {
+        storage::get_oracle(&e)
+    }
+
     /********** Emissions **********/
 
     fn distribute(e: Env) -> i128 {
@@ -273,6 +729,7 @@ impl Backstop for BackstopContract {
         storage::extend_instance(&e);
         pool.require_auth();
         let (backstop_emissions, pool_emissions) = emissions::gulp_emissions(&e, &pool);
+        backstop::checkpoint_backstop_yield(&e, &pool);
 
         BackstopEvents::gulp_emissions(&e, pool, backstop_emissions, pool_emissions);
         pool_emissions
@@ -302,6 +759,47 @@ impl Backstop for BackstopContract {
         amount
     }
 
+    fn claim_vested(
+        e: Env,
+        from: Address,
+        pool_addresses: Vec<Address>,
+        min_lp_tokens_out: i128,
+    ) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let (amount, end) =
+            emissions::execute_claim_vested(&e, &from, &pool_addresses, &min_lp_tokens_out);
+
+        BackstopEvents::claim_vested(&e, from, amount, end);
+        amount
+    }
+
+    fn claim_vestings(e: Env, pool: Address, user: Address) -> Vec<ClaimVesting> {
+        emissions::load_active_vestings(&e, &pool, &user)
+    }
+
+    fn fund_vesting_bonus(e: Env, from: Address, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let reserve = emissions::execute_fund_vesting_bonus(&e, &from, amount);
+
+        BackstopEvents::fund_vesting_bonus(&e, from, amount, reserve);
+    }
+
+    fn vesting_bonus_reserve(e: Env) -> i128 {
+        storage::get_vesting_bonus_reserve(&e)
+    }
+
+    fn get_backstop_emissions(e: Env, pool: Address) -> BackstopEmissionsReport {
+        emissions::load_backstop_emissions_report(&e, &pool)
+    }
+
+    fn preview_backstop_claim(e: Env, user: Address, pool: Address) -> i128 {
+        emissions::preview_claim(&e, &user, &pool)
+    }
+
     fn drop(e: Env) {
         let mut drop_list = storage::get_drop_list(&e);
         let backfilled_emissions = storage::get_backfill_emissions(&e);
@@ -318,7 +816,8 @@ impl Backstop for BackstopContract {
 
         backstop::execute_draw(&e, &pool_address, amount, &to);
 
-        BackstopEvents::draw(&e, pool_address, to, amount);
+        BackstopEvents::draw(&e, pool_address.clone(), to, amount);
+        emit_backstop_state(&e, pool_address, "draw", None);
     }
 
     fn donate(e: Env, from: Address, pool_address: Address, amount: i128) {
@@ -326,9 +825,247 @@ impl Backstop for BackstopContract {
         from.require_auth();
         pool_address.require_auth();
 
-        backstop::execute_donate(&e, &from, &pool_address, amount);
+        backstop::checkpoint_backstop_yield(&e, &pool_address);
+        let (donor_total, pool_total) = backstop::execute_donate(&e, &from, &pool_address, amount);
+
+        BackstopEvents::donate(
+            &e,
+            pool_address.clone(),
+            from,
+            amount,
+            donor_total,
+            pool_total,
+        );
+        emit_backstop_state(&e, pool_address, "donate", None);
+    }
+
+    fn donate_bid(e: Env, filler: Address, pool_address: Address, amount: i128) {
+        storage::extend_instance(&e);
+        pool_address.require_auth();
+
+        backstop::checkpoint_backstop_yield(&e, &pool_address);
+        let (donor_total, pool_total) =
+            backstop::execute_donate_bid(&e, &filler, &pool_address, amount);
+
+        BackstopEvents::donate(
+            &e,
+            pool_address.clone(),
+            filler,
+            amount,
+            donor_total,
+            pool_total,
+        );
+        emit_backstop_state(&e, pool_address, "donate", None);
+    }
+
+    /********** Share Tokens *********/
+
+    fn deploy_share_token(e: Env, pool_address: Address) -> Address {
+        storage::extend_instance(&e);
+
+        let share_token = backstop::execute_deploy_share_token(&e, &pool_address);
 
-        BackstopEvents::donate(&e, pool_address, from, amount);
+        BackstopEvents::deploy_share_token(&e, pool_address, share_token.clone());
+        share_token
+    }
+
+    fn share_token(e: Env, pool_address: Address) -> Option<Address> {
+        storage::get_share_token(&e, &pool_address)
+    }
+
+    fn share_balance(e: Env, pool: Address, user: Address) -> i128 {
+        storage::get_user_balance(&e, &pool, &user).shares
+    }
+
+    fn transfer_shares(e: Env, pool: Address, from: Address, to: Address, amount: i128) {
+        storage::extend_instance(&e);
+
+        backstop::execute_transfer_shares(&e, &pool, &from, &to, amount);
+    }
+
+    fn set_approved_factory(e: Env, factory: Address, pool_hash: Option<BytesN<32>>) {
+        storage::extend_instance(&e);
+        storage::get_pool_factory(&e).require_auth();
+
+        backstop::execute_set_approved_factory(&e, &factory, &pool_hash);
+
+        BackstopEvents::set_approved_factory(&e, factory, pool_hash);
+    }
+
+    fn approved_factories(e: Env) -> Map<Address, BytesN<32>> {
+        storage::get_approved_factories(&e)
+    }
+
+    /********** Deposit Locks *********/
+
+    fn deposit_locked(
+        e: Env,
+        from: Address,
+        pool_address: Address,
+        amount: i128,
+        tier: u32,
+    ) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let (to_mint, lock) =
+            backstop::execute_deposit_locked(&e, &from, &pool_address, amount, tier);
+
+        BackstopEvents::deposit(&e, pool_address.clone(), from.clone(), amount, to_mint);
+        BackstopEvents::deposit_locked(
+            &e,
+            pool_address.clone(),
+            from.clone(),
+            to_mint,
+            lock.multiplier,
+            lock.exp,
+        );
+        emit_backstop_state(&e, pool_address, "deposit", Some(from));
+        to_mint
+    }
+
+    fn deposit_locks(e: Env, pool: Address, user: Address) -> Vec<DepositLock> {
+        backstop::load_active_locks(&e, &pool, &user)
+    }
+
+    fn fund_lock_bonus(e: Env, from: Address, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let reserve = backstop::execute_fund_lock_bonus(&e, &from, amount);
+
+        BackstopEvents::fund_lock_bonus(&e, from, amount, reserve);
+    }
+
+    fn lock_bonus_reserve(e: Env) -> i128 {
+        storage::get_lock_bonus_reserve(&e)
+    }
+
+    fn dequeue_withdrawal_entry(
+        e: Env,
+        from: Address,
+        pool_address: Address,
+        index: u32,
+        amount: i128,
+    ) {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        backstop::execute_dequeue_withdrawal_entry(&e, &from, &pool_address, index, amount);
+
+        BackstopEvents::dequeue_withdrawal(&e, pool_address.clone(), from.clone(), amount);
+        emit_backstop_state(&e, pool_address, "dequeue_withdrawal", Some(from));
+    }
+
+    fn status(_e: Env) -> ContractStatus {
+        ContractStatus::Active
+    }
+
+    fn start_recap_auction(e: Env, pool_address: Address) -> Option<RecapAuction> {
+        storage::extend_instance(&e);
+
+        let auction = backstop::execute_start_recap_auction(&e, &pool_address);
+
+        if let Some(auction) = &auction {
+            BackstopEvents::start_recap_auction(&e, pool_address, auction.lot, auction.bid);
+        }
+        auction
+    }
+
+    fn fill_recap_auction(e: Env, filler: Address, pool_address: Address) -> i128 {
+        storage::extend_instance(&e);
+        filler.require_auth();
+
+        let (auction, bid, tokens) =
+            backstop::execute_fill_recap_auction(&e, &filler, &pool_address);
+
+        BackstopEvents::fill_recap_auction(
+            &e,
+            pool_address.clone(),
+            filler,
+            auction.lot,
+            bid,
+            tokens,
+        );
+        emit_backstop_state(&e, pool_address, "fill_recap_auction", None);
+        bid
+    }
+
+    fn cancel_recap_auction(e: Env, pool_address: Address) {
+        storage::extend_instance(&e);
+
+        let auction = backstop::execute_cancel_recap_auction(&e, &pool_address);
+
+        BackstopEvents::cancel_recap_auction(&e, pool_address, auction.lot);
+    }
+
+    fn recap_auction(e: Env, pool_address: Address) -> Option<RecapAuction> {
+        storage::get_recap_auction(&e, &pool_address)
+    }
+
+    fn get_backstop_apr(e: Env, pool: Address) -> BackstopApr {
+        backstop::load_backstop_apr(&e, &pool)
+    }
+
+    /********** Admin **********/
+
+    fn admin(e: Env) -> Address {
+        storage::get_admin(&e)
+    }
+
+    fn propose_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        storage::get_admin(&e).require_auth();
+
+        storage::set_proposed_admin(&e, &new_admin);
+    }
+
+    fn accept_admin(e: Env) {
+        storage::extend_instance(&e);
+
+        if let Some(proposed_admin) = storage::get_proposed_admin(&e) {
+            proposed_admin.require_auth();
+            let cur_admin = storage::get_admin(&e);
+
+            storage::set_admin(&e, &proposed_admin);
+
+            BackstopEvents::set_admin(&e, cur_admin, proposed_admin);
+        } else {
+            panic_with_error!(&e, BackstopError::BadRequest);
+        }
+    }
+
+    fn grant_role(e: Env, role: Role, member: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        backstop::execute_grant_role(&e, role, &member);
+
+        BackstopEvents::grant_role(&e, admin, role, member);
+    }
+
+    fn revoke_role(e: Env, role: Role, member: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        backstop::execute_revoke_role(&e, role, &member);
+
+        BackstopEvents::revoke_role(&e, admin, role, member);
+    }
+
+    fn has_role(e: Env, role: Role, member: Address) -> bool {
+        backstop::has_role(&e, role, &member)
+    }
+
+    fn set_oracle(e: Env, caller: Address, oracle: Address) {
+        storage::extend_instance(&e);
+        backstop::require_role(&e, Role::Risk, &caller);
+
+        storage::set_oracle(&e, &oracle);
+
+        BackstopEvents::set_oracle(&e, caller, oracle);
     }
 }
 
@@ -344,3 +1081,14 @@ pub fn require_nonnegative(e: &Env, amount: i128) {
         panic_with_error!(e, BackstopError::NegativeAmountError);
     }
 }
+
+/// Emit the balances of a pool's backstop, and the user's balances in it, after an action
+///
+/// ### Arguments
+/// * `pool_address` - The address of the pool
+/// * `action` - The name of the action
+/// * `user` - The user involved in the action, if any
+fn emit_backstop_state(e: &Env, pool_address: Address, action: &str, user: Option<Address>) {
+    let state = backstop::load_backstop_state(e, &pool_address, user.as_ref());
+    BackstopEvents::backstop_state(e, pool_address, action, user, state);
+}
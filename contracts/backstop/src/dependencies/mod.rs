@@ -7,6 +7,9 @@ pub use comet::Client as CometClient;
 mod pool;
 pub use pool::PoolClient;
 
+mod share_token;
+pub use share_token::{Client as ShareTokenClient, WASM as SHARE_TOKEN_WASM};
+
 #[cfg(test)]
 pub use comet::WASM as COMET_WASM;
 
@@ -11,6 +11,23 @@ pub struct Positions {
     pub supply: Map<u32, i128>,      // Map of Reserve Index to non-collateral supply share balance
 }
 
+#[derive(Clone)]
+#[contracttype]
+pub struct PoolConfig {
+    pub oracle: Address,
+    pub min_collateral: i128,
+    pub bstop_rate: u32,
+    pub status: u32,
+    pub max_positions: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct EmissionMigration {
+    pub token: Address,
+    pub ledger: u32,
+}
+
 #[allow(dead_code)]
 #[contractclient(name = "PoolClient")]
 pub trait Pool {
@@ -19,4 +36,10 @@ pub trait Pool {
     /// ### Arguments
     /// * `address` - The address to fetch positions for
     fn get_positions(e: Env, address: Address) -> Positions;
+
+    /// Fetch the pool configuration
+    fn get_config(e: Env) -> PoolConfig;
+
+    /// Fetch the pool's emission token migration, if one is configured
+    fn get_emission_migration(e: Env) -> Option<EmissionMigration>;
 }
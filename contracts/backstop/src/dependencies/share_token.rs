@@ -0,0 +1,3 @@
+use soroban_sdk::contractimport;
+
+contractimport!(file = "../../target/wasm32-unknown-unknown/release/backstop_share_token.wasm");
@@ -1,4 +1,10 @@
-use crate::{dependencies::CometClient, errors::BackstopError, events::BackstopEvents, storage};
+use crate::{
+    backstop::{claim_lock_bonus, notify_share_mint},
+    dependencies::CometClient,
+    errors::BackstopError,
+    events::BackstopEvents,
+    storage,
+};
 use soroban_fixed_point_math::FixedPoint;
 use soroban_sdk::{
     auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
@@ -9,23 +15,139 @@ use soroban_sdk::{
 
 use super::distributor::claim_emissions;
 
-/// Perform a claim for backstop deposit emissions by a user from the backstop module
+/// Perform a claim for backstop deposit emissions by a user from the backstop module. The bonus
+/// emissions of the user's deposit locks are included, up to the BLND in the lock bonus reserve.
 pub fn execute_claim(
     e: &Env,
     from: &Address,
     pool_addresses: &Vec<Address>,
     min_lp_tokens_out: &i128,
 ) -> i128 {
+    let (claimed, claims) = claim_from_pools(e, from, pool_addresses);
+
+    if claimed > 0 {
+        let (lp_tokens_out, _) = deposit_claimed(
+            e,
+            from,
+            pool_addresses,
+            &claims,
+            claimed,
+            claimed,
+            min_lp_tokens_out,
+        );
+        lp_tokens_out
+    } else {
+        0
+    }
+}
+
+/// Deposit `amount` BLND into the backstop token, and deposit the minted LP tokens into the
+/// backstops of the claimed pools for `from`, split pro-rata by the amount claimed from each pool
+///
+/// Returns the amount of LP tokens minted and the shares minted in each pool's backstop
+///
+/// ### Arguments
+/// * `from` - The address of the user that claimed
+/// * `pool_addresses` - The pools claimed from
+/// * `claims` - The amount of BLND claimed from each pool
+/// * `claimed` - The total amount of BLND claimed
+/// * `amount` - The amount of BLND to deposit into the backstop token
+/// * `min_lp_tokens_out` - The minimum amount of LP tokens to mint
+pub(super) fn deposit_claimed(
+    e: &Env,
+    from: &Address,
+    pool_addresses: &Vec<Address>,
+    claims: &Map<Address, i128>,
+    claimed: i128,
+    amount: i128,
+    min_lp_tokens_out: &i128,
+) -> (i128, Map<Address, i128>) {
+    let blnd_id = storage::get_blnd_token(e);
+    let lp_id = storage::get_backstop_token(e);
+    let approval_ledger = (e.ledger().sequence() / 100000 + 1) * 100000;
+    let args: Vec<Val> = vec![
+        e,
+        (&e.current_contract_address()).into_val(e),
+        (&lp_id).into_val(e),
+        (&amount).into_val(e),
+        (&approval_ledger).into_val(e),
+    ];
+    e.authorize_as_current_contract(vec![
+        &e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: blnd_id.clone(),
+                fn_name: Symbol::new(e, "approve"),
+                args: args.clone(),
+            },
+            sub_invocations: vec![e],
+        }),
+    ]);
+    let lp_tokens_out = CometClient::new(e, &lp_id).dep_tokn_amt_in_get_lp_tokns_out(
+        &blnd_id,
+        &amount,
+        &min_lp_tokens_out,
+        &e.current_contract_address(),
+    );
+    let mut minted: Map<Address, i128> = Map::new(e);
+    for pool_id in pool_addresses.iter() {
+        let claim_amount = claims.get(pool_id.clone()).unwrap_optimized();
+        let deposit_amount = lp_tokens_out
+            .fixed_mul_floor(claim_amount, claimed)
+            .unwrap_optimized();
+        if deposit_amount > 0 {
+            let mut pool_balance = storage::get_pool_balance(e, &pool_id);
+            let mut user_balance = storage::get_user_balance(e, &pool_id, from);
+
+            // Deposit LP tokens into pool backstop
+            let to_mint = pool_balance.convert_to_shares(deposit_amount);
+            pool_balance.deposit(deposit_amount, to_mint);
+            user_balance.add_shares(to_mint);
+
+            storage::set_pool_balance(e, &pool_id, &pool_balance);
+            storage::set_user_balance(e, &pool_id, from, &user_balance);
+
+            notify_share_mint(e, &pool_id, from, to_mint);
+            BackstopEvents::deposit(e, pool_id.clone(), from.clone(), deposit_amount, to_mint);
+            minted.set(pool_id, to_mint);
+        }
+    }
+    (lp_tokens_out, minted)
+}
+
+/// Claim the BLND emissions of `from` from the backstops of a list of pools, including the bonus
+/// emissions of their deposit locks
+///
+/// Returns the total BLND claimed and the BLND claimed from each pool
+///
+/// ### Panics
+/// If no pools are given, or a pool is given twice
+pub(crate) fn claim_from_pools(
+    e: &Env,
+    from: &Address,
+    pool_addresses: &Vec<Address>,
+) -> (i128, Map<Address, i128>) {
     if pool_addresses.is_empty() {
         panic_with_error!(e, BackstopError::BadRequest);
     }
 
     let mut claimed: i128 = 0;
     let mut claims: Map<Address, i128> = Map::new(e);
+    let mut bonus_reserve = storage::get_lock_bonus_reserve(e);
     for pool_id in pool_addresses.iter() {
         let pool_balance = storage::get_pool_balance(e, &pool_id);
         let user_balance = storage::get_user_balance(e, &pool_id, from);
-        let claim_amt = claim_emissions(e, &pool_id, &pool_balance, from, &user_balance);
+        let mut claim_amt = claim_emissions(e, &pool_id, &pool_balance, from, &user_balance);
+
+        let index = storage::get_backstop_emis_data(e, &pool_id)
+            .unwrap_optimized()
+            .index;
+        let bonus = claim_lock_bonus(e, from, &pool_id, index).min(bonus_reserve);
+        if bonus > 0 {
+            bonus_reserve -= bonus;
+            claim_amt += bonus;
+            BackstopEvents::lock_bonus(e, pool_id.clone(), from.clone(), bonus);
+        }
         claimed += claim_amt;
         // panic if the user has already claimed for this pool
         // or if the claim amount is 0
@@ -35,58 +157,8 @@ pub fn execute_claim(
         claims.set(pool_id.clone(), claim_amt);
     }
 
-    if claimed > 0 {
-        let blnd_id = storage::get_blnd_token(e);
-        let lp_id = storage::get_backstop_token(e);
-        let approval_ledger = (e.ledger().sequence() / 100000 + 1) * 100000;
-        let args: Vec<Val> = vec![
-            e,
-            (&e.current_contract_address()).into_val(e),
-            (&lp_id).into_val(e),
-            (&claimed).into_val(e),
-            (&approval_ledger).into_val(e),
-        ];
-        e.authorize_as_current_contract(vec![
-            &e,
-            InvokerContractAuthEntry::Contract(SubContractInvocation {
-                context: ContractContext {
-                    contract: blnd_id.clone(),
-                    fn_name: Symbol::new(e, "approve"),
-                    args: args.clone(),
-                },
-                sub_invocations: vec![e],
-            }),
-        ]);
-        let lp_tokens_out = CometClient::new(e, &lp_id).dep_tokn_amt_in_get_lp_tokns_out(
-            &blnd_id,
-            &claimed,
-            &min_lp_tokens_out,
-            &e.current_contract_address(),
-        );
-        for pool_id in pool_addresses.iter() {
-            let claim_amount = claims.get(pool_id.clone()).unwrap_optimized();
-            let deposit_amount = lp_tokens_out
-                .fixed_mul_floor(claim_amount, claimed)
-                .unwrap_optimized();
-            if deposit_amount > 0 {
-                let mut pool_balance = storage::get_pool_balance(e, &pool_id);
-                let mut user_balance = storage::get_user_balance(e, &pool_id, from);
-
-                // Deposit LP tokens into pool backstop
-                let to_mint = pool_balance.convert_to_shares(deposit_amount);
-                pool_balance.deposit(deposit_amount, to_mint);
-                user_balance.add_shares(to_mint);
-
-                storage::set_pool_balance(e, &pool_id, &pool_balance);
-                storage::set_user_balance(e, &pool_id, from, &user_balance);
-
-                BackstopEvents::deposit(e, pool_id, from.clone(), deposit_amount, to_mint);
-            }
-        }
-        lp_tokens_out
-    } else {
-        0
-    }
+    storage::set_lock_bonus_reserve(e, &bonus_reserve);
+    (claimed, claims)
 }
 
 #[cfg(test)]
@@ -56,47 +56,59 @@ pub fn update_emission_data(
 ) -> Option<BackstopEmissionData> {
     match storage::get_backstop_emis_data(e, pool_id) {
         Some(emis_data) => {
-            if emis_data.last_time >= emis_data.expiration
-                || e.ledger().timestamp() == emis_data.last_time
-                || emis_data.eps == 0
-                || pool_balance.shares == 0
-            {
-                // emis_data already updated or expired
-                return Some(emis_data);
+            let new_data = calc_emission_data(e, &emis_data, pool_balance);
+            if new_data.last_time != emis_data.last_time {
+                storage::set_backstop_emis_data(e, pool_id, &new_data);
             }
-
-            let max_timestamp = if e.ledger().timestamp() > emis_data.expiration {
-                emis_data.expiration
-            } else {
-                e.ledger().timestamp()
-            };
-
-            let unqueued_shares = pool_balance.shares - pool_balance.q4w;
-            require_nonnegative(e, unqueued_shares);
-            let additional_idx: i128;
-            if unqueued_shares == 0 {
-                // all shares q4w, omit emissions
-                additional_idx = 0;
-            } else {
-                // Eps is in 14 decimals and needs to be converted to 7 decimals to match emission token decimals
-                additional_idx = (i128(max_timestamp - emis_data.last_time) * i128(emis_data.eps))
-                    .fixed_div_floor(unqueued_shares, SCALAR_7)
-                    .unwrap_optimized();
-            }
-            let new_data = BackstopEmissionData {
-                eps: emis_data.eps,
-                expiration: emis_data.expiration,
-                index: additional_idx + emis_data.index,
-                last_time: e.ledger().timestamp(),
-            };
-
-            storage::set_backstop_emis_data(e, pool_id, &new_data);
             Some(new_data)
         }
         None => return None, // no emission exist, no update is required
     }
 }
 
+/// Calculate the backstop emissions index for deposits at the current ledger
+///
+/// Returns the updated emission data, or `emis_data` if no update is required
+pub(crate) fn calc_emission_data(
+    e: &Env,
+    emis_data: &BackstopEmissionData,
+    pool_balance: &PoolBalance,
+) -> BackstopEmissionData {
+    if emis_data.last_time >= emis_data.expiration
+        || e.ledger().timestamp() == emis_data.last_time
+        || emis_data.eps == 0
+        || pool_balance.shares == 0
+    {
+        // emis_data already updated or expired
+        return emis_data.clone();
+    }
+
+    let max_timestamp = if e.ledger().timestamp() > emis_data.expiration {
+        emis_data.expiration
+    } else {
+        e.ledger().timestamp()
+    };
+
+    let unqueued_shares = pool_balance.shares - pool_balance.q4w;
+    require_nonnegative(e, unqueued_shares);
+    let additional_idx: i128;
+    if unqueued_shares == 0 {
+        // all shares q4w, omit emissions
+        additional_idx = 0;
+    } else {
+        // Eps is in 14 decimals and needs to be converted to 7 decimals to match emission token decimals
+        additional_idx = (i128(max_timestamp - emis_data.last_time) * i128(emis_data.eps))
+            .fixed_div_floor(unqueued_shares, SCALAR_7)
+            .unwrap_optimized();
+    }
+    BackstopEmissionData {
+        eps: emis_data.eps,
+        expiration: emis_data.expiration,
+        index: additional_idx + emis_data.index,
+        last_time: e.ledger().timestamp(),
+    }
+}
+
 /// Update the user's emissions. If `to_claim` is true, the user's accrued emissions will be returned and
 /// a value of zero will be stored to the ledger.
 ///
@@ -110,31 +122,49 @@ fn update_user_emissions(
     user_balance: &UserBalance,
     to_claim: bool,
 ) -> i128 {
-    if let Some(user_data) = storage::get_user_emis_data(e, pool, user) {
-        if user_data.index != emis_data.index || to_claim {
+    let user_data = storage::get_user_emis_data(e, pool, user);
+    if let Some(user_data) = &user_data {
+        if user_data.index == emis_data.index && !to_claim {
+            // no accrual occured and no claim requested
+            return 0;
+        }
+    }
+    let accrued = calc_user_accrued(e, &user_data, emis_data.index, user_balance);
+    set_user_emissions(e, pool, user, emis_data.index, accrued, to_claim)
+}
+
+/// Calculate the user's total accrued emissions at the emission index `index`
+///
+/// ### Arguments
+/// * `user_data` - The user's emission data, if the user has any
+/// * `index` - The backstop's emission index
+/// * `user_balance` - The user's backstop balance
+pub(super) fn calc_user_accrued(
+    e: &Env,
+    user_data: &Option<UserEmissionData>,
+    index: i128,
+    user_balance: &UserBalance,
+) -> i128 {
+    match user_data {
+        Some(user_data) => {
             let mut accrual = user_data.accrued;
             if user_balance.shares != 0 {
-                let delta_index = emis_data.index - user_data.index;
+                let delta_index = index - user_data.index;
                 require_nonnegative(e, delta_index);
                 let to_accrue = (user_balance.shares)
                     .fixed_mul_floor(delta_index, SCALAR_14)
                     .unwrap_optimized();
                 accrual += to_accrue;
             }
-            return set_user_emissions(e, pool, user, emis_data.index, accrual, to_claim);
+            accrual
         }
-        // no accrual occured and no claim requested
-        return 0;
-    } else if user_balance.shares == 0 {
         // first time the user registered an action with the asset since emissions were added
-        return set_user_emissions(e, pool, user, emis_data.index, 0, to_claim);
-    } else {
+        None if user_balance.shares == 0 => 0,
         // user had tokens before emissions began, they are due any historical emissions
-        let to_accrue = user_balance
+        None => user_balance
             .shares
-            .fixed_mul_floor(emis_data.index, SCALAR_14)
-            .unwrap_optimized();
-        return set_user_emissions(e, pool, user, emis_data.index, to_accrue, to_claim);
+            .fixed_mul_floor(index, SCALAR_14)
+            .unwrap_optimized(),
     }
 }
 
@@ -6,7 +6,7 @@ use soroban_sdk::{panic_with_error, unwrap::UnwrapOptimized, vec, Address, Env,
 use crate::{
     backstop::{is_pool_above_threshold, load_pool_backstop_data},
     constants::{MAX_BACKFILLED_EMISSIONS, MAX_RZ_SIZE, SCALAR_7},
-    dependencies::EmitterClient,
+    dependencies::{EmitterClient, PoolClient},
     errors::BackstopError,
     storage::{self, BackstopEmissionData, RzEmissions},
     PoolBalance,
@@ -229,12 +229,35 @@ pub fn gulp_emissions(e: &Env, pool: &Address) -> (i128, i128) {
         let blnd_token_client = TokenClient::new(e, &storage::get_blnd_token(e));
         let current_allowance = blnd_token_client.allowance(&e.current_contract_address(), pool);
         let new_seq = e.ledger().sequence() + storage::LEDGER_BUMP_USER; // ~120 days
-        blnd_token_client.approve(
-            &e.current_contract_address(),
-            pool,
-            &(current_allowance + new_pool_emissions),
-            &new_seq,
-        );
+        match load_emission_migration_token(e, pool) {
+            Some(migration_token) => {
+                // the pool pays new emissions in the migrated token, so the allowance is granted
+                // in that token. The BLND allowance is refreshed so legacy claims stay payable.
+                let migration_token_client = TokenClient::new(e, &migration_token);
+                let current_migration_allowance =
+                    migration_token_client.allowance(&e.current_contract_address(), pool);
+                migration_token_client.approve(
+                    &e.current_contract_address(),
+                    pool,
+                    &(current_migration_allowance + new_pool_emissions),
+                    &new_seq,
+                );
+                blnd_token_client.approve(
+                    &e.current_contract_address(),
+                    pool,
+                    &current_allowance,
+                    &new_seq,
+                );
+            }
+            None => {
+                blnd_token_client.approve(
+                    &e.current_contract_address(),
+                    pool,
+                    &(current_allowance + new_pool_emissions),
+                    &new_seq,
+                );
+            }
+        }
         storage::set_rz_emis(
             e,
             pool,
@@ -249,6 +272,17 @@ pub fn gulp_emissions(e: &Env, pool: &Address) -> (i128, i128) {
     return (0, 0);
 }
 
+/// Load the token the pool pays its emissions in, if the pool's emissions have been migrated
+/// away from BLND. The backstop must hold enough of the migrated token to cover the allowance.
+fn load_emission_migration_token(e: &Env, pool: &Address) -> Option<Address> {
+    match PoolClient::new(e, pool).try_get_emission_migration() {
+        Ok(Ok(Some(migration))) if e.ledger().sequence() >= migration.ledger => {
+            Some(migration.token)
+        }
+        _ => None,
+    }
+}
+
 /// Set a new EPS for the backstop
 pub fn set_backstop_emission_eps(
     e: &Env,
@@ -308,9 +342,10 @@ mod tests {
         backstop::PoolBalance,
         testutils::{
             create_backstop, create_blnd_token, create_comet_lp_pool_with_tokens_per_share,
-            create_emitter, create_usdc_token,
+            create_emitter, create_mock_pool, create_token, create_usdc_token,
         },
     };
+    use mock_pool::EmissionMigration;
 
     /********** gulp_emissions **********/
 
@@ -390,6 +425,73 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_gulp_emissions_migrated_pool() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let backstop = create_backstop(&e);
+        let bombadil = Address::generate(&e);
+        let blnd_token_client = create_blnd_token(&e, &backstop, &bombadil).1;
+        let (tbrg, tbrg_token_client) = create_token(&e, &bombadil);
+        let (pool_1, pool_1_client) = create_mock_pool(&e);
+        pool_1_client.set_emission_migration(&EmissionMigration {
+            token: tbrg.clone(),
+            ledger: 100,
+        });
+
+        let pool_1_allowance: i128 = 100_123_0000000;
+        e.as_contract(&backstop, || {
+            storage::set_pool_balance(
+                &e,
+                &pool_1,
+                &PoolBalance {
+                    tokens: 150_000_0000000,
+                    shares: 40_000_0000000,
+                    q4w: 5_000_0000000,
+                },
+            );
+            blnd_token_client.approve(
+                &backstop,
+                &pool_1,
+                &pool_1_allowance,
+                &e.ledger().sequence(),
+            );
+            storage::set_rz_emis(
+                &e,
+                &pool_1,
+                &RzEmissions {
+                    accrued: 20_000_0000000,
+                    last_time: 0,
+                },
+            );
+
+            gulp_emissions(&e, &pool_1);
+
+            // new pool emissions are approved in the migrated token, and the legacy allowance
+            // is kept for pending claims
+            assert_eq!(
+                tbrg_token_client.allowance(&backstop, &pool_1),
+                6_000_0000000
+            );
+            assert_eq!(
+                blnd_token_client.allowance(&backstop, &pool_1),
+                pool_1_allowance
+            );
+        });
+    }
+
     #[test]
     fn test_gulp_emissions() {
         let e = Env::default();
@@ -2,7 +2,16 @@ mod claim;
 pub use claim::execute_claim;
 
 mod distributor;
+pub(crate) use distributor::calc_emission_data;
 pub use distributor::update_emissions;
 
 mod manager;
 pub use manager::{add_to_reward_zone, distribute, gulp_emissions, remove_from_reward_zone};
+
+mod report;
+pub use report::{load_backstop_emissions_report, preview_claim, BackstopEmissionsReport};
+
+mod vesting;
+pub use vesting::{
+    execute_claim_vested, execute_fund_vesting_bonus, load_active_vestings, ClaimVesting,
+};
@@ -0,0 +1,279 @@
+use cast::i128;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{contracttype, unwrap::UnwrapOptimized, Address, Env};
+
+use crate::{
+    backstop::load_pool_backstop_data,
+    constants::{SCALAR_7, SECONDS_PER_YEAR},
+    storage,
+};
+
+use super::distributor::{calc_emission_data, calc_user_accrued};
+
+/// A report of a pool's backstop emissions
+#[derive(Clone)]
+#[contracttype]
+pub struct BackstopEmissionsReport {
+    pub index: i128, // the backstop's emission index at the current ledger (14 decimals)
+    pub eps: u64,    // the emissions per second to the pool's backstop (14 decimals)
+    pub expiration: u64, // the expiration time of the backstop's emissions
+    pub apy: i128,   // the estimated APY of emissions for unqueued deposits (7 decimals)
+}
+
+/// Load a report of the pool's backstop emissions. The APY is estimated by valuing a year of
+/// emissions at the current eps against the pool's unqueued backstop tokens, using the spot
+/// prices of the backstop token's underlying BLND and USDC.
+///
+/// ### Arguments
+/// * `pool` - The pool address
+pub fn load_backstop_emissions_report(e: &Env, pool: &Address) -> BackstopEmissionsReport {
+    let emis_data = match storage::get_backstop_emis_data(e, pool) {
+        Some(emis_data) => emis_data,
+        None => {
+            return BackstopEmissionsReport {
+                index: 0,
+                eps: 0,
+                expiration: 0,
+                apy: 0,
+            }
+        }
+    };
+    let pool_balance = storage::get_pool_balance(e, pool);
+    let emis_data = calc_emission_data(e, &emis_data, &pool_balance);
+
+    let unqueued_tokens = pool_balance.convert_to_tokens(pool_balance.shares - pool_balance.q4w);
+    let apy = if emis_data.expiration <= e.ledger().timestamp()
+        || emis_data.eps == 0
+        || unqueued_tokens <= 0
+    {
+        0
+    } else {
+        let pool_data = load_pool_backstop_data(e, pool);
+        if pool_data.blnd == 0 {
+            0
+        } else {
+            // the backstop token is 80% BLND by value, so BLND is priced at 80% of the token's
+            // spot price over the BLND held by each token
+            let blnd_per_tkn = pool_data
+                .blnd
+                .fixed_div_floor(pool_data.tokens, SCALAR_7)
+                .unwrap_optimized();
+            let blnd_price = (pool_data.token_spot_price * 4 / 5)
+                .fixed_div_floor(blnd_per_tkn, SCALAR_7)
+                .unwrap_optimized();
+
+            // eps is in 14 decimals, and emissions are in 7 decimals
+            let yearly_emissions = (i128(emis_data.eps) * SECONDS_PER_YEAR) / SCALAR_7;
+            let emissions_value = yearly_emissions
+                .fixed_mul_floor(blnd_price, SCALAR_7)
+                .unwrap_optimized();
+            let deposit_value = unqueued_tokens
+                .fixed_mul_floor(pool_data.token_spot_price, SCALAR_7)
+                .unwrap_optimized();
+            if deposit_value == 0 {
+                0
+            } else {
+                emissions_value
+                    .fixed_div_floor(deposit_value, SCALAR_7)
+                    .unwrap_optimized()
+            }
+        }
+    };
+
+    BackstopEmissionsReport {
+        index: emis_data.index,
+        eps: emis_data.eps,
+        expiration: emis_data.expiration,
+        apy,
+    }
+}
+
+/// Calculate the BLND emissions `user` could claim from the pool's backstop at the current ledger.
+/// The claimed BLND is deposited into the backstop token, so this is not the amount of backstop
+/// tokens a claim mints.
+///
+/// ### Arguments
+/// * `user` - The user claiming emissions
+/// * `pool` - The pool address
+pub fn preview_claim(e: &Env, user: &Address, pool: &Address) -> i128 {
+    match storage::get_backstop_emis_data(e, pool) {
+        Some(emis_data) => {
+            let pool_balance = storage::get_pool_balance(e, pool);
+            let emis_data = calc_emission_data(e, &emis_data, &pool_balance);
+            let user_data = storage::get_user_emis_data(e, pool, user);
+            let user_balance = storage::get_user_balance(e, pool, user);
+            calc_user_accrued(e, &user_data, emis_data.index, &user_balance)
+        }
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backstop::{PoolBalance, UserBalance},
+        storage::{BackstopEmissionData, UserEmissionData},
+        testutils::{
+            create_backstop, create_blnd_token, create_comet_lp_pool_with_tokens_per_share,
+            create_usdc_token,
+        },
+    };
+
+    use super::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec,
+    };
+
+    fn set_timestamp(e: &Env, timestamp: u64) {
+        e.ledger().set(LedgerInfo {
+            timestamp,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+    }
+
+    #[test]
+    fn test_load_backstop_emissions_report() {
+        let e = Env::default();
+        e.mock_all_auths();
+        set_timestamp(&e, 1713139200 + 1000);
+
+        let bombadil = Address::generate(&e);
+        let backstop_address = create_backstop(&e);
+        let pool = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_address, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_address, &bombadil);
+        // each token holds 5 BLND and 0.05 USDC, so BLND is worth 0.04 USDC
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_address,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_0500000,
+        );
+
+        e.as_contract(&backstop_address, || {
+            storage::set_pool_balance(
+                &e,
+                &pool,
+                &PoolBalance {
+                    shares: 150_0000000,
+                    tokens: 300_0000000,
+                    q4w: 50_0000000,
+                },
+            );
+            storage::set_backstop_emis_data(
+                &e,
+                &pool,
+                &BackstopEmissionData {
+                    expiration: 1713139200 + 7 * 24 * 60 * 60,
+                    eps: 0_10000000000000,
+                    index: 222220000000,
+                    last_time: 1713139200,
+                },
+            );
+
+            let report = load_backstop_emissions_report(&e, &pool);
+            // 1000 seconds of 0.1 BLND per second over 100 unqueued shares
+            assert_eq!(report.index, 222220000000 + 1_0000000_0000000);
+            assert_eq!(report.eps, 0_10000000000000);
+            assert_eq!(report.expiration, 1713139200 + 7 * 24 * 60 * 60);
+            // 3_153_600 BLND at 0.04 USDC over 200 tokens at 0.25 USDC
+            assert_eq!(report.apy, 2522_8800000);
+
+            // the report does not update the stored emission data
+            let emis_data = storage::get_backstop_emis_data(&e, &pool).unwrap_optimized();
+            assert_eq!(emis_data.index, 222220000000);
+        });
+
+        set_timestamp(&e, 1713139200 + 7 * 24 * 60 * 60);
+        e.as_contract(&backstop_address, || {
+            let report = load_backstop_emissions_report(&e, &pool);
+            assert_eq!(report.apy, 0);
+        });
+    }
+
+    #[test]
+    fn test_load_backstop_emissions_report_no_emissions() {
+        let e = Env::default();
+        let backstop_address = create_backstop(&e);
+        let pool = Address::generate(&e);
+
+        e.as_contract(&backstop_address, || {
+            let report = load_backstop_emissions_report(&e, &pool);
+            assert_eq!(report.index, 0);
+            assert_eq!(report.eps, 0);
+            assert_eq!(report.apy, 0);
+        });
+    }
+
+    #[test]
+    fn test_preview_claim() {
+        let e = Env::default();
+        set_timestamp(&e, 1713139200 + 1000);
+
+        let backstop_address = create_backstop(&e);
+        let pool = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&backstop_address, || {
+            storage::set_pool_balance(
+                &e,
+                &pool,
+                &PoolBalance {
+                    shares: 150_0000000,
+                    tokens: 300_0000000,
+                    q4w: 50_0000000,
+                },
+            );
+            storage::set_backstop_emis_data(
+                &e,
+                &pool,
+                &BackstopEmissionData {
+                    expiration: 1713139200 + 7 * 24 * 60 * 60,
+                    eps: 0_10000000000000,
+                    index: 222220000000,
+                    last_time: 1713139200,
+                },
+            );
+            storage::set_user_balance(
+                &e,
+                &pool,
+                &samwise,
+                &UserBalance {
+                    shares: 9_0000000,
+                    q4w: vec![&e],
+                },
+            );
+            storage::set_user_emis_data(
+                &e,
+                &pool,
+                &samwise,
+                &UserEmissionData {
+                    index: 111110000000,
+                    accrued: 3,
+                },
+            );
+
+            // 9 shares over an index increase of 1.0011111
+            assert_eq!(preview_claim(&e, &samwise, &pool), 3 + 9_0099999);
+            assert_eq!(preview_claim(&e, &frodo, &pool), 0);
+
+            // the preview does not update any emission data
+            let emis_data = storage::get_backstop_emis_data(&e, &pool).unwrap_optimized();
+            assert_eq!(emis_data.index, 222220000000);
+            let user_data = storage::get_user_emis_data(&e, &pool, &samwise).unwrap_optimized();
+            assert_eq!(user_data.accrued, 3);
+        });
+    }
+}
@@ -0,0 +1,338 @@
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::FixedPoint;
+use soroban_sdk::{
+    contracttype, panic_with_error, unwrap::UnwrapOptimized, vec, Address, Env, Vec,
+};
+
+use crate::{
+    constants::{CLAIM_VESTING_BONUS, CLAIM_VESTING_PERIOD, MAX_CLAIM_VESTINGS, SCALAR_7},
+    errors::BackstopError,
+    require_nonnegative, storage,
+};
+
+use super::claim::{claim_from_pools, deposit_claimed};
+
+/// Backstop shares minted from a vested claim, which unlock linearly over the vesting period
+#[derive(Clone)]
+#[contracttype]
+pub struct ClaimVesting {
+    pub shares: i128, // the amount of shares minted from the claim
+    pub start: u64,   // the time vesting started
+    pub end: u64,     // the time all of the shares are unlocked
+}
+
+impl ClaimVesting {
+    /// The amount of shares still locked at the current ledger
+    pub fn locked(&self, e: &Env) -> i128 {
+        let now = e.ledger().timestamp();
+        if now >= self.end {
+            return 0;
+        }
+        let vested = self
+            .shares
+            .fixed_mul_floor((now - self.start) as i128, (self.end - self.start) as i128)
+            .unwrap_optimized();
+        self.shares - vested
+    }
+}
+
+/// Perform a claim for backstop deposit emissions by a user, and deposit the claimed BLND into the
+/// backstops of the claimed pools like a regular claim, except the minted shares vest over
+/// `CLAIM_VESTING_PERIOD`. The claim is boosted by `CLAIM_VESTING_BONUS`, up to the BLND left in
+/// the vesting bonus reserve. Each vested claim is tracked separately, with its own schedule.
+///
+/// Returns the amount of LP tokens minted, and the time the claim is fully vested
+///
+/// ### Arguments
+/// * `from` - The address of the user claiming emissions
+/// * `pool_addresses` - The Vec of addresses to claim backstop deposit emissions from
+/// * `min_lp_tokens_out` - The minimum amount of LP tokens to mint with the claimed BLND
+///
+/// ### Panics
+/// If no pools are given, a pool is given twice, no emissions are claimed, or `from` has too many
+/// claims vesting in a pool
+pub fn execute_claim_vested(
+    e: &Env,
+    from: &Address,
+    pool_addresses: &Vec<Address>,
+    min_lp_tokens_out: &i128,
+) -> (i128, u64) {
+    let (claimed, claims) = claim_from_pools(e, from, pool_addresses);
+    if claimed <= 0 {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+
+    let bonus_reserve = storage::get_vesting_bonus_reserve(e);
+    let bonus = claimed
+        .fixed_mul_floor(CLAIM_VESTING_BONUS - SCALAR_7, SCALAR_7)
+        .unwrap_optimized()
+        .min(bonus_reserve);
+    storage::set_vesting_bonus_reserve(e, &(bonus_reserve - bonus));
+
+    let (lp_tokens_out, minted) = deposit_claimed(
+        e,
+        from,
+        pool_addresses,
+        &claims,
+        claimed,
+        claimed + bonus,
+        min_lp_tokens_out,
+    );
+
+    let now = e.ledger().timestamp();
+    let end = now + CLAIM_VESTING_PERIOD;
+    for (pool_id, shares) in minted.iter() {
+        let mut vestings = load_active_vestings(e, &pool_id, from);
+        if vestings.len() >= MAX_CLAIM_VESTINGS {
+            panic_with_error!(e, BackstopError::BadRequest);
+        }
+        vestings.push_back(ClaimVesting {
+            shares,
+            start: now,
+            end,
+        });
+        storage::set_claim_vestings(e, &pool_id, from, &vestings);
+    }
+    (lp_tokens_out, end)
+}
+
+/// Add BLND to the reserve vested claim bonuses are paid from
+///
+/// Returns the new size of the reserve
+///
+/// ### Arguments
+/// * `from` - The address sending the BLND
+/// * `amount` - The amount of BLND to add
+pub fn execute_fund_vesting_bonus(e: &Env, from: &Address, amount: i128) -> i128 {
+    require_nonnegative(e, amount);
+    if from == &e.current_contract_address() {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+    TokenClient::new(e, &storage::get_blnd_token(e)).transfer(
+        from,
+        &e.current_contract_address(),
+        &amount,
+    );
+    let reserve = storage::get_vesting_bonus_reserve(e) + amount;
+    storage::set_vesting_bonus_reserve(e, &reserve);
+    reserve
+}
+
+/// Load the vested claims of `from` in the backstop of a pool that still have shares locked
+pub fn load_active_vestings(e: &Env, pool_address: &Address, from: &Address) -> Vec<ClaimVesting> {
+    let mut active = vec![e];
+    for vesting in storage::get_claim_vestings(e, pool_address, from).iter() {
+        if vesting.end > e.ledger().timestamp() {
+            active.push_back(vesting);
+        }
+    }
+    active
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backstop::{execute_queue_withdrawal, PoolBalance, UserBalance},
+        storage::{BackstopEmissionData, UserEmissionData},
+        testutils::{create_backstop, create_blnd_token, create_comet_lp_pool, create_usdc_token},
+    };
+
+    use super::*;
+    use sep_41_token::testutils::MockTokenClient;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec,
+    };
+
+    fn set_timestamp(e: &Env, timestamp: u64) {
+        e.ledger().set(LedgerInfo {
+            timestamp,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+    }
+
+    /// Setup a backstop where samwise can claim 76.3155136 BLND from a pool's backstop
+    fn setup_backstop<'a>(
+        e: &Env,
+        samwise: &Address,
+        bonus_reserve: i128,
+    ) -> (Address, Address, MockTokenClient<'a>) {
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        set_timestamp(e, 1500000000 + 12345);
+
+        let backstop_address = create_backstop(e);
+        let pool_id = Address::generate(e);
+        let bombadil = Address::generate(e);
+
+        let (blnd_address, blnd_token_client) = create_blnd_token(e, &backstop_address, &bombadil);
+        let (usdc_address, _) = create_usdc_token(e, &backstop_address, &bombadil);
+        blnd_token_client.mint(&backstop_address, &200_0000000);
+        let (lp_address, _) = create_comet_lp_pool(e, &bombadil, &blnd_address, &usdc_address);
+
+        e.as_contract(&backstop_address, || {
+            storage::set_backstop_emis_data(
+                e,
+                &pool_id,
+                &BackstopEmissionData {
+                    expiration: 1500000000 + 7 * 24 * 60 * 60,
+                    eps: 0_10000000000000,
+                    index: 222220000000,
+                    last_time: 1500000000,
+                },
+            );
+            storage::set_user_emis_data(
+                e,
+                &pool_id,
+                samwise,
+                &UserEmissionData {
+                    index: 111110000000,
+                    accrued: 1_2345678,
+                },
+            );
+            storage::set_backstop_token(e, &lp_address);
+            storage::set_blnd_token(e, &blnd_address);
+            storage::set_lock_bonus_reserve(e, &50_0000000);
+            storage::set_vesting_bonus_reserve(e, &bonus_reserve);
+            storage::set_pool_balance(
+                e,
+                &pool_id,
+                &PoolBalance {
+                    shares: 150_0000000,
+                    tokens: 200_0000000,
+                    q4w: 2_0000000,
+                },
+            );
+            storage::set_user_balance(
+                e,
+                &pool_id,
+                samwise,
+                &UserBalance {
+                    shares: 9_0000000,
+                    q4w: vec![e],
+                },
+            );
+        });
+        (backstop_address, pool_id, blnd_token_client)
+    }
+
+    #[test]
+    fn test_claim_vested() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (backstop_address, pool_id, _) = setup_backstop(&e, &samwise, 100_0000000);
+
+        let start = 1500000000 + 12345;
+        let shares = e.as_contract(&backstop_address, || {
+            let (lp_tokens_out, end) =
+                execute_claim_vested(&e, &samwise, &vec![&e, pool_id.clone()], &0);
+            assert!(lp_tokens_out > 0);
+            assert_eq!(end, start + CLAIM_VESTING_PERIOD);
+
+            // the bonus is paid from the vesting bonus reserve, not the lock bonus reserve
+            assert_eq!(
+                storage::get_vesting_bonus_reserve(&e),
+                100_0000000 - 15_2631027
+            );
+            assert_eq!(storage::get_lock_bonus_reserve(&e), 50_0000000);
+
+            // the claim is deposited into the backstop as vesting shares
+            let user_balance = storage::get_user_balance(&e, &pool_id, &samwise);
+            let vestings = load_active_vestings(&e, &pool_id, &samwise);
+            assert_eq!(vestings.len(), 1);
+            let vesting = vestings.get_unchecked(0);
+            assert_eq!(vesting.shares, user_balance.shares - 9_0000000);
+            assert_eq!(vesting.start, start);
+            assert_eq!(vesting.end, end);
+            assert_eq!(vesting.locked(&e), vesting.shares);
+            vesting.shares
+        });
+
+        // half of the shares unlock halfway through the period
+        set_timestamp(&e, start + CLAIM_VESTING_PERIOD / 2);
+        e.as_contract(&backstop_address, || {
+            let vesting = load_active_vestings(&e, &pool_id, &samwise).get_unchecked(0);
+            assert_eq!(vesting.locked(&e), shares - shares / 2);
+            execute_queue_withdrawal(&e, &samwise, &pool_id, 9_0000000 + shares / 2);
+        });
+
+        set_timestamp(&e, start + CLAIM_VESTING_PERIOD);
+        e.as_contract(&backstop_address, || {
+            assert_eq!(load_active_vestings(&e, &pool_id, &samwise).len(), 0);
+        });
+    }
+
+    #[test]
+    fn test_claim_vested_tracks_each_claim() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (backstop_address, pool_id, _) = setup_backstop(&e, &samwise, 100_0000000);
+
+        let start = 1500000000 + 12345;
+        e.as_contract(&backstop_address, || {
+            execute_claim_vested(&e, &samwise, &vec![&e, pool_id.clone()], &0);
+        });
+
+        set_timestamp(&e, start + 24 * 60 * 60);
+        e.as_contract(&backstop_address, || {
+            let (_, end) = execute_claim_vested(&e, &samwise, &vec![&e, pool_id.clone()], &0);
+            assert_eq!(end, start + 24 * 60 * 60 + CLAIM_VESTING_PERIOD);
+
+            // the earlier claim keeps its own schedule
+            let vestings = load_active_vestings(&e, &pool_id, &samwise);
+            assert_eq!(vestings.len(), 2);
+            assert_eq!(vestings.get_unchecked(0).start, start);
+            assert_eq!(vestings.get_unchecked(0).end, start + CLAIM_VESTING_PERIOD);
+            assert_eq!(vestings.get_unchecked(1).start, start + 24 * 60 * 60);
+            assert_eq!(vestings.get_unchecked(1).end, end);
+        });
+    }
+
+    #[test]
+    fn test_claim_vested_bonus_capped_by_reserve() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (backstop_address, pool_id, _) = setup_backstop(&e, &samwise, 5_0000000);
+
+        e.as_contract(&backstop_address, || {
+            execute_claim_vested(&e, &samwise, &vec![&e, pool_id], &0);
+            assert_eq!(storage::get_vesting_bonus_reserve(&e), 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1013)")]
+    fn test_claim_vested_shares_locked() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (backstop_address, pool_id, _) = setup_backstop(&e, &samwise, 100_0000000);
+
+        e.as_contract(&backstop_address, || {
+            execute_claim_vested(&e, &samwise, &vec![&e, pool_id.clone()], &0);
+            execute_queue_withdrawal(&e, &samwise, &pool_id, 9_0000001);
+        });
+    }
+
+    #[test]
+    fn test_fund_vesting_bonus() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (backstop_address, _, blnd_token_client) = setup_backstop(&e, &samwise, 0);
+        let bombadil = Address::generate(&e);
+        blnd_token_client.mint(&bombadil, &10_0000000);
+
+        e.as_contract(&backstop_address, || {
+            let reserve = execute_fund_vesting_bonus(&e, &bombadil, 10_0000000);
+            assert_eq!(reserve, 10_0000000);
+            assert_eq!(storage::get_vesting_bonus_reserve(&e), 10_0000000);
+            assert_eq!(storage::get_lock_bonus_reserve(&e), 50_0000000);
+        });
+        assert_eq!(blnd_token_client.balance(&bombadil), 0);
+    }
+}
@@ -1,4 +1,7 @@
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{Address, BytesN, Env, Symbol};
+use trustbridge_common::Role;
+
+use crate::backstop::BackstopState;
 
 pub struct BackstopEvents {}
 
@@ -25,6 +28,57 @@ impl BackstopEvents {
             .publish(topics, (tokens_in, backstop_shares_minted));
     }
 
+    /// Emitted when tokens are deposited into a backstop and the minted shares are locked
+    ///
+    /// - topics - `["deposit_locked", pool_address: Address, from: Address]`
+    /// - data - `[shares: i128, multiplier: i128, expiration: u64]`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `from` - The address of the user depositing tokens
+    /// * `shares` - The amount of backstop shares locked
+    /// * `multiplier` - The emission multiplier of the locked shares
+    /// * `expiration` - The expiration timestamp of the lock
+    pub fn deposit_locked(
+        e: &Env,
+        pool_address: Address,
+        from: Address,
+        shares: i128,
+        multiplier: i128,
+        expiration: u64,
+    ) {
+        let topics = (Symbol::new(e, "deposit_locked"), pool_address, from);
+        e.events().publish(topics, (shares, multiplier, expiration));
+    }
+
+    /// Emitted when bonus emissions are claimed for a user's deposit locks
+    ///
+    /// - topics - `["lock_bonus", pool_address: Address, from: Address]`
+    /// - data - `amount: i128`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `from` - The address of the user claiming
+    /// * `amount` - The amount of bonus BLND claimed
+    pub fn lock_bonus(e: &Env, pool_address: Address, from: Address, amount: i128) {
+        let topics = (Symbol::new(e, "lock_bonus"), pool_address, from);
+        e.events().publish(topics, amount);
+    }
+
+    /// Emitted when BLND is added to the lock bonus reserve
+    ///
+    /// - topics - `["fund_lock_bonus", from: Address]`
+    /// - data - `[amount: i128, reserve: i128]`
+    ///
+    /// ### Arguments
+    /// * `from` - The address sending the BLND
+    /// * `amount` - The amount of BLND added
+    /// * `reserve` - The new size of the lock bonus reserve
+    pub fn fund_lock_bonus(e: &Env, from: Address, amount: i128, reserve: i128) {
+        let topics = (Symbol::new(e, "fund_lock_bonus"), from);
+        e.events().publish(topics, (amount, reserve));
+    }
+
     /// Emitted when a withdrawal is queued
     ///
     /// - topics - `["queue_withdrawal", pool_address: Address, from: Address]`
@@ -75,6 +129,44 @@ impl BackstopEvents {
         e.events().publish(topics, (amount, tokens_out));
     }
 
+    /// Emitted when tokens are withdrawn from the backstop before the withdrawal unlocks
+    ///
+    /// - topics - `["withdraw_early", pool_address: Address, from: Address]`
+    /// - data - `[amount: i128, tokens_out: i128, penalty: i128]`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `from` - The address of the user withdrawing tokens
+    /// * `amount` - The amount of backstop shares being burned
+    /// * `tokens_out` - The amount of tokens being withdrawn
+    /// * `penalty` - The amount of tokens forfeited to the pool's backstop
+    pub fn withdraw_early(
+        e: &Env,
+        pool_address: Address,
+        from: Address,
+        amount: i128,
+        tokens_out: i128,
+        penalty: i128,
+    ) {
+        let topics = (Symbol::new(e, "withdraw_early"), pool_address, from);
+        e.events().publish(topics, (amount, tokens_out, penalty));
+    }
+
+    /// Emitted when a user opts into or out of withdrawing their expired queued withdrawals when
+    /// they deposit
+    ///
+    /// - topics - `["set_q4w_sweep_opt_out", pool_address: Address, from: Address]`
+    /// - data - `opt_out: bool`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `from` - The address of the user
+    /// * `opt_out` - If expired queued withdrawals are left queued when depositing
+    pub fn set_q4w_sweep_opt_out(e: &Env, pool_address: Address, from: Address, opt_out: bool) {
+        let topics = (Symbol::new(e, "set_q4w_sweep_opt_out"), pool_address, from);
+        e.events().publish(topics, opt_out);
+    }
+
     /// Emitted when new emissions are distributed
     /// - topics - `["distribute"]`
     /// - data - `[new_tokens_emitted: i128]`
@@ -144,6 +236,34 @@ impl BackstopEvents {
         e.events().publish(topics, amount);
     }
 
+    /// Emitted when emissions are claimed with vesting
+    ///
+    /// - topics - `["claim_vested", from: Address]`
+    /// - data - `[amount: i128, end: u64]`
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user claiming emissions
+    /// * `amount` - The amount of LP tokens minted, including the vesting bonus
+    /// * `end` - The time the shares minted from the claim are fully vested
+    pub fn claim_vested(e: &Env, from: Address, amount: i128, end: u64) {
+        let topics = (Symbol::new(e, "claim_vested"), from);
+        e.events().publish(topics, (amount, end));
+    }
+
+    /// Emitted when BLND is added to the vesting bonus reserve
+    ///
+    /// - topics - `["fund_vesting_bonus", from: Address]`
+    /// - data - `[amount: i128, reserve: i128]`
+    ///
+    /// ### Arguments
+    /// * `from` - The address sending the BLND
+    /// * `amount` - The amount of BLND added
+    /// * `reserve` - The new size of the vesting bonus reserve
+    pub fn fund_vesting_bonus(e: &Env, from: Address, amount: i128, reserve: i128) {
+        let topics = (Symbol::new(e, "fund_vesting_bonus"), from);
+        e.events().publish(topics, (amount, reserve));
+    }
+
     /// Emitted when tokens are drawn from the backstop
     ///
     /// - topics - `["draw", pool_address: Address]`
@@ -161,14 +281,181 @@ impl BackstopEvents {
     /// Emitted when tokens are donated to the backstop
     ///
     /// - topics - `["donate", pool_address: Address, from: Address]`
-    /// - data - `[amount: i128]`
+    /// - data - `[amount: i128, donor_total: i128, pool_total: i128]`
     ///
     /// ### Arguments
     /// * `pool_address` - The address of the pool
     /// * `from` - The address of the donor
     /// * `amount` - The amount of tokens donated
-    pub fn donate(e: &Env, pool_address: Address, from: Address, amount: i128) {
+    /// * `donor_total` - The cumulative amount of tokens donated to the pool by the donor
+    /// * `pool_total` - The cumulative amount of tokens donated to the pool by all donors
+    pub fn donate(
+        e: &Env,
+        pool_address: Address,
+        from: Address,
+        amount: i128,
+        donor_total: i128,
+        pool_total: i128,
+    ) {
         let topics = (Symbol::new(e, "donate"), pool_address, from);
-        e.events().publish(topics, amount);
+        e.events()
+            .publish(topics, (amount, donor_total, pool_total));
+    }
+
+    /// Emitted after each action that changes the balances of a pool's backstop, so share
+    /// accounting can be tracked off-chain without reading the ledger
+    ///
+    /// - topics - `["backstop_state", pool_address: Address, action: Symbol, user: Option<Address>]`
+    /// - data - `state: BackstopState`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `action` - The name of the action, matching the name of the action's event
+    /// * `user` - The user whose balances are included in the state, if any
+    /// * `state` - The balances after the action
+    pub fn backstop_state(
+        e: &Env,
+        pool_address: Address,
+        action: &str,
+        user: Option<Address>,
+        state: BackstopState,
+    ) {
+        let topics = (
+            Symbol::new(e, "backstop_state"),
+            pool_address,
+            Symbol::new(e, action),
+            user,
+        );
+        e.events().publish(topics, state);
+    }
+
+    /// Emitted when a new admin is set for the backstop
+    ///
+    /// - topics - `["set_admin", admin: Address]`
+    /// - data - `new_admin: Address`
+    ///
+    /// ### Arguments
+    /// * `admin` - The current admin of the backstop
+    /// * `new_admin` - The new admin of the backstop
+    pub fn set_admin(e: &Env, admin: Address, new_admin: Address) {
+        let topics = (Symbol::new(e, "set_admin"), admin);
+        e.events().publish(topics, new_admin);
+    }
+
+    /// Emitted when a role is granted
+    ///
+    /// - topics - `["grant_role", admin: Address, member: Address]`
+    /// - data - `role: Role`
+    ///
+    /// ### Arguments
+    /// * `admin` - The current admin of the backstop
+    /// * `role` - The role granted
+    /// * `member` - The address granted the role
+    pub fn grant_role(e: &Env, admin: Address, role: Role, member: Address) {
+        let topics = (Symbol::new(e, "grant_role"), admin, member);
+        e.events().publish(topics, role);
+    }
+
+    /// Emitted when a role is revoked
+    ///
+    /// - topics - `["revoke_role", admin: Address, member: Address]`
+    /// - data - `role: Role`
+    ///
+    /// ### Arguments
+    /// * `admin` - The current admin of the backstop
+    /// * `role` - The role revoked
+    /// * `member` - The address the role was revoked from
+    pub fn revoke_role(e: &Env, admin: Address, role: Role, member: Address) {
+        let topics = (Symbol::new(e, "revoke_role"), admin, member);
+        e.events().publish(topics, role);
+    }
+
+    /// Emitted when the oracle used to price BLND and USDC is set
+    ///
+    /// - topics - `["set_oracle", caller: Address]`
+    /// - data - `oracle: Address`
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `oracle` - The oracle
+    pub fn set_oracle(e: &Env, caller: Address, oracle: Address) {
+        let topics = (Symbol::new(e, "set_oracle"), caller);
+        e.events().publish(topics, oracle);
+    }
+
+    /// Emitted when a pool factory is approved or its approval is revoked
+    ///
+    /// - topics - `["set_approved_factory", factory: Address]`
+    /// - data - `pool_hash: Option<BytesN<32>>`
+    ///
+    /// ### Arguments
+    /// * `factory` - The pool factory
+    /// * `pool_hash` - The WASM hash of the pools the factory is approved to deploy, or None if revoked
+    pub fn set_approved_factory(e: &Env, factory: Address, pool_hash: Option<BytesN<32>>) {
+        let topics = (Symbol::new(e, "set_approved_factory"), factory);
+        e.events().publish(topics, pool_hash);
+    }
+
+    /// Emitted when the share token of a pool's backstop is deployed
+    ///
+    /// - topics - `["deploy_share_token", pool_address: Address]`
+    /// - data - `share_token: Address`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `share_token` - The address of the share token
+    pub fn deploy_share_token(e: &Env, pool_address: Address, share_token: Address) {
+        let topics = (Symbol::new(e, "deploy_share_token"), pool_address);
+        e.events().publish(topics, share_token);
+    }
+
+    /// Emitted when a recapitalization auction is started for a pool's backstop
+    ///
+    /// - topics - `["start_recap_auction", pool_address: Address]`
+    /// - data - `[lot: i128, bid: i128]`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `lot` - The amount of BLND sold
+    /// * `bid` - The amount of USDC bid when the auction starts
+    pub fn start_recap_auction(e: &Env, pool_address: Address, lot: i128, bid: i128) {
+        let topics = (Symbol::new(e, "start_recap_auction"), pool_address);
+        e.events().publish(topics, (lot, bid));
+    }
+
+    /// Emitted when a recapitalization auction is filled
+    ///
+    /// - topics - `["fill_recap_auction", pool_address: Address, filler: Address]`
+    /// - data - `[lot: i128, bid: i128, tokens: i128]`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `filler` - The address filling the auction
+    /// * `lot` - The amount of BLND sent to the filler
+    /// * `bid` - The amount of USDC paid by the filler
+    /// * `tokens` - The amount of backstop tokens added to the pool's backstop
+    pub fn fill_recap_auction(
+        e: &Env,
+        pool_address: Address,
+        filler: Address,
+        lot: i128,
+        bid: i128,
+        tokens: i128,
+    ) {
+        let topics = (Symbol::new(e, "fill_recap_auction"), pool_address, filler);
+        e.events().publish(topics, (lot, bid, tokens));
+    }
+
+    /// Emitted when an expired recapitalization auction is cancelled
+    ///
+    /// - topics - `["cancel_recap_auction", pool_address: Address]`
+    /// - data - `lot: i128`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `lot` - The amount of BLND the auction was selling
+    pub fn cancel_recap_auction(e: &Env, pool_address: Address, lot: i128) {
+        let topics = (Symbol::new(e, "cancel_recap_auction"), pool_address);
+        e.events().publish(topics, lot);
     }
 }
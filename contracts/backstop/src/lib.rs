@@ -13,7 +13,11 @@ mod events;
 mod storage;
 mod testutils;
 
-pub use backstop::{PoolBackstopData, PoolBalance, UserBalance, Q4W};
+pub use backstop::{
+    BackstopApr, DepositLock, PoolBackstopData, PoolBackstopReport, PoolBalance, RecapAuction,
+    RecapEpoch, RecapWatch, UserBalance, YieldCheckpoint, YieldWindow, Q4W,
+};
 pub use contract::*;
+pub use emissions::{BackstopEmissionsReport, ClaimVesting};
 pub use errors::BackstopError;
 pub use storage::{BackstopDataKey, BackstopEmissionData, PoolUserKey, UserEmissionData};
@@ -1,8 +1,14 @@
 use soroban_sdk::{
-    contracttype, unwrap::UnwrapOptimized, vec, Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+    contracttype, unwrap::UnwrapOptimized, vec, Address, BytesN, Env, IntoVal, Map, Symbol,
+    TryFromVal, Val, Vec,
 };
 
-use crate::backstop::{PoolBalance, UserBalance};
+use crate::{
+    backstop::{
+        DepositLock, PoolBalance, RecapAuction, RecapEpoch, RecapWatch, UserBalance, YieldWindow,
+    },
+    emissions::ClaimVesting,
+};
 
 /********** Ledger Thresholds **********/
 
@@ -53,16 +59,23 @@ pub struct UserEmissionData {
 
 /********** Storage Key Types **********/
 
+const ADMIN_KEY: &str = "Admin";
+const PROPOSED_ADMIN_KEY: &str = "PropAdmin";
 const EMITTER_KEY: &str = "Emitter";
 const BACKSTOP_TOKEN_KEY: &str = "BToken";
 const POOL_FACTORY_KEY: &str = "PoolFact";
+const APPROVED_FACTORIES_KEY: &str = "AppFact";
 const BLND_TOKEN_KEY: &str = "BLNDTkn";
 const USDC_TOKEN_KEY: &str = "USDCTkn";
+const ORACLE_KEY: &str = "Oracle";
 const LAST_DISTRO_KEY: &str = "LastDist";
 const REWARD_ZONE_KEY: &str = "RZ";
 const DROP_LIST_KEY: &str = "DropList";
 const BACKFILL_EMISSIONS_KEY: &str = "BackfillEmis";
 const BACKFILL_STATUS_KEY: &str = "Backfill";
+const LOCK_BONUS_KEY: &str = "LockBonus";
+const VESTING_BONUS_KEY: &str = "VestBonus";
+const RECAP_EPOCH_KEY: &str = "RecapEpoch";
 
 #[derive(Clone)]
 #[contracttype]
@@ -80,6 +93,15 @@ pub enum BackstopDataKey {
     RzEmis(Address),
     BEmisData(Address),
     UEmisData(PoolUserKey),
+    Donations(PoolUserKey),
+    PoolDonations(Address),
+    ShareToken(Address),
+    SweepOptOut(PoolUserKey),
+    Locks(PoolUserKey),
+    Vesting(PoolUserKey),
+    RecapWatch(Address),
+    RecapAuction(Address),
+    YieldWindow(Address),
 }
 
 /****************************
@@ -113,6 +135,46 @@ fn get_persistent_default<K: IntoVal<Env, Val>, V: TryFromVal<Env, Val>, F: FnOn
 
 /********** Instance Storage **********/
 
+/// Fetch the current admin Address
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, ADMIN_KEY))
+        .unwrap_optimized()
+}
+
+/// Set a new admin
+///
+/// ### Arguments
+/// * `new_admin` - The Address for the admin
+pub fn set_admin(e: &Env, new_admin: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, ADMIN_KEY), new_admin);
+}
+
+/// Fetch the proposed admin, if any
+pub fn get_proposed_admin(e: &Env) -> Option<Address> {
+    e.storage()
+        .temporary()
+        .get(&Symbol::new(e, PROPOSED_ADMIN_KEY))
+}
+
+/// Set a new proposed admin
+///
+/// ### Arguments
+/// * `proposed_admin` - The Address for the proposed admin
+pub fn set_proposed_admin(e: &Env, proposed_admin: &Address) {
+    e.storage()
+        .temporary()
+        .set::<Symbol, Address>(&Symbol::new(e, PROPOSED_ADMIN_KEY), proposed_admin);
+    e.storage().temporary().extend_ttl(
+        &Symbol::new(e, PROPOSED_ADMIN_KEY),
+        10 * ONE_DAY_LEDGERS,
+        10 * ONE_DAY_LEDGERS,
+    );
+}
+
 /// Fetch the emitter id
 pub fn get_emitter(e: &Env) -> Address {
     e.storage()
@@ -149,6 +211,28 @@ pub fn set_pool_factory(e: &Env, pool_factory_id: &Address) {
         .set::<Symbol, Address>(&Symbol::new(e, POOL_FACTORY_KEY), pool_factory_id);
 }
 
+/// Fetch the pool factories approved alongside the pool factory, and the WASM hash of the pools
+/// each is approved to deploy
+pub fn get_approved_factories(e: &Env) -> Map<Address, BytesN<32>> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Map<Address, BytesN<32>>>(&Symbol::new(e, APPROVED_FACTORIES_KEY))
+        .unwrap_or(Map::new(e))
+}
+
+/// Set the pool factories approved alongside the pool factory
+///
+/// ### Arguments
+/// * `factories` - The approved factories and the WASM hash of the pools each is approved to deploy
+pub fn set_approved_factories(e: &Env, factories: &Map<Address, BytesN<32>>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Map<Address, BytesN<32>>>(
+            &Symbol::new(e, APPROVED_FACTORIES_KEY),
+            factories,
+        );
+}
+
 /// Fetch the BLND token id
 pub fn get_blnd_token(e: &Env) -> Address {
     e.storage()
@@ -185,6 +269,24 @@ pub fn set_usdc_token(e: &Env, usdc_token_id: &Address) {
         .set::<Symbol, Address>(&Symbol::new(e, USDC_TOKEN_KEY), usdc_token_id);
 }
 
+/// Fetch the oracle used to price the backstop token's underlying assets
+pub fn get_oracle(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, ORACLE_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the oracle used to price the backstop token's underlying assets
+///
+/// ### Arguments
+/// * `oracle` - The ID of the oracle
+pub fn set_oracle(e: &Env, oracle: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, ORACLE_KEY), oracle);
+}
+
 /// Fetch the backstop token id
 pub fn get_backstop_token(e: &Env) -> Address {
     e.storage()
@@ -246,6 +348,97 @@ pub fn set_user_balance(e: &Env, pool: &Address, user: &Address, balance: &UserB
         .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
 }
 
+/// Fetch if a user opted out of withdrawing their expired queued shares when they deposit.
+/// Defaults to false.
+///
+/// ### Arguments
+/// * `pool` - The pool the deposit is associated with
+/// * `user` - The owner of the deposit
+pub fn get_q4w_sweep_opt_out(e: &Env, pool: &Address, user: &Address) -> bool {
+    let key = BackstopDataKey::SweepOptOut(PoolUserKey {
+        pool: pool.clone(),
+        user: user.clone(),
+    });
+    get_persistent_default(e, &key, || false, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set if a user opted out of withdrawing their expired queued shares when they deposit
+///
+/// ### Arguments
+/// * `pool` - The pool the deposit is associated with
+/// * `user` - The owner of the deposit
+/// * `opt_out` - If the user opted out
+pub fn set_q4w_sweep_opt_out(e: &Env, pool: &Address, user: &Address, opt_out: bool) {
+    let key = BackstopDataKey::SweepOptOut(PoolUserKey {
+        pool: pool.clone(),
+        user: user.clone(),
+    });
+    if !opt_out {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, bool>(&key, &opt_out);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the deposit locks of a user. May include expired locks.
+///
+/// ### Arguments
+/// * `pool` - The pool the deposit is associated with
+/// * `user` - The owner of the deposit
+pub fn get_deposit_locks(e: &Env, pool: &Address, user: &Address) -> Vec<DepositLock> {
+    let key = BackstopDataKey::Locks(PoolUserKey {
+        pool: pool.clone(),
+        user: user.clone(),
+    });
+    get_persistent_default(e, &key, || vec![e], LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the deposit locks of a user
+///
+/// ### Arguments
+/// * `pool` - The pool the deposit is associated with
+/// * `user` - The owner of the deposit
+/// * `locks` - The deposit locks
+pub fn set_deposit_locks(e: &Env, pool: &Address, user: &Address, locks: &Vec<DepositLock>) {
+    let key = BackstopDataKey::Locks(PoolUserKey {
+        pool: pool.clone(),
+        user: user.clone(),
+    });
+    if locks.is_empty() {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, Vec<DepositLock>>(&key, locks);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the amount of BLND available to pay lock bonuses
+pub fn get_lock_bonus_reserve(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get::<Symbol, i128>(&Symbol::new(e, LOCK_BONUS_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the amount of BLND available to pay lock bonuses
+///
+/// ### Arguments
+/// * `amount` - The amount of BLND
+pub fn set_lock_bonus_reserve(e: &Env, amount: &i128) {
+    e.storage()
+        .instance()
+        .set::<Symbol, i128>(&Symbol::new(e, LOCK_BONUS_KEY), amount);
+}
+
 /********** Pool Balance **********/
 
 /// Fetch the balances for a given pool
@@ -282,6 +475,70 @@ pub fn set_pool_balance(e: &Env, pool: &Address, balance: &PoolBalance) {
         .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
 
+/********** Donations **********/
+
+/// Fetch the cumulative amount of backstop tokens a donor has donated to a pool
+///
+/// ### Arguments
+/// * `pool` - The pool the donations were made to
+/// * `donor` - The address of the donor
+pub fn get_donations(e: &Env, pool: &Address, donor: &Address) -> i128 {
+    let key = BackstopDataKey::Donations(PoolUserKey {
+        pool: pool.clone(),
+        user: donor.clone(),
+    });
+    get_persistent_default(e, &key, || 0i128, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the cumulative amount of backstop tokens a donor has donated to a pool
+///
+/// ### Arguments
+/// * `pool` - The pool the donations were made to
+/// * `donor` - The address of the donor
+/// * `amount` - The cumulative amount donated
+pub fn set_donations(e: &Env, pool: &Address, donor: &Address, amount: &i128) {
+    let key = BackstopDataKey::Donations(PoolUserKey {
+        pool: pool.clone(),
+        user: donor.clone(),
+    });
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, i128>(&key, amount);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the cumulative amount of backstop tokens donated to a pool by all donors
+///
+/// ### Arguments
+/// * `pool` - The pool the donations were made to
+pub fn get_pool_donations(e: &Env, pool: &Address) -> i128 {
+    let key = BackstopDataKey::PoolDonations(pool.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || 0i128,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the cumulative amount of backstop tokens donated to a pool by all donors
+///
+/// ### Arguments
+/// * `pool` - The pool the donations were made to
+/// * `amount` - The cumulative amount donated
+pub fn set_pool_donations(e: &Env, pool: &Address, amount: &i128) {
+    let key = BackstopDataKey::PoolDonations(pool.clone());
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, i128>(&key, amount);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
 /********** Distribution / Reward Zone **********/
 
 /// Get the timestamp of when the next emission cycle begins
@@ -516,3 +773,213 @@ pub fn set_drop_list(e: &Env, drop_list: &Vec<(Address, i128)>) {
         LEDGER_BUMP_USER,
     );
 }
+
+/********** Share Tokens **********/
+
+/// Get the share token of a pool's backstop, if it has been deployed
+///
+/// ### Arguments
+/// * `pool` - The pool
+pub fn get_share_token(e: &Env, pool: &Address) -> Option<Address> {
+    let key = BackstopDataKey::ShareToken(pool.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the share token of a pool's backstop
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `share_token` - The share token
+pub fn set_share_token(e: &Env, pool: &Address, share_token: &Address) {
+    let key = BackstopDataKey::ShareToken(pool.clone());
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, Address>(&key, share_token);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** Claim Vesting **********/
+
+/// Fetch the vested claims of a user in the backstop of a pool. May include fully vested claims.
+///
+/// ### Arguments
+/// * `pool` - The pool the claims were deposited into
+/// * `user` - The user
+pub fn get_claim_vestings(e: &Env, pool: &Address, user: &Address) -> Vec<ClaimVesting> {
+    let key = BackstopDataKey::Vesting(PoolUserKey {
+        pool: pool.clone(),
+        user: user.clone(),
+    });
+    get_persistent_default(e, &key, || vec![e], LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the vested claims of a user in the backstop of a pool
+///
+/// ### Arguments
+/// * `pool` - The pool the claims were deposited into
+/// * `user` - The user
+/// * `vestings` - The vested claims
+pub fn set_claim_vestings(e: &Env, pool: &Address, user: &Address, vestings: &Vec<ClaimVesting>) {
+    let key = BackstopDataKey::Vesting(PoolUserKey {
+        pool: pool.clone(),
+        user: user.clone(),
+    });
+    if vestings.is_empty() {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, Vec<ClaimVesting>>(&key, vestings);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the amount of BLND available to pay vested claim bonuses
+pub fn get_vesting_bonus_reserve(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get::<Symbol, i128>(&Symbol::new(e, VESTING_BONUS_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the amount of BLND available to pay vested claim bonuses
+///
+/// ### Arguments
+/// * `amount` - The amount of BLND
+pub fn set_vesting_bonus_reserve(e: &Env, amount: &i128) {
+    e.storage()
+        .instance()
+        .set::<Symbol, i128>(&Symbol::new(e, VESTING_BONUS_KEY), amount);
+}
+
+/********** Recapitalization **********/
+
+/// Fetch when a pool's backstop was first and last seen below the threshold, if it still is
+///
+/// ### Arguments
+/// * `pool` - The pool
+pub fn get_recap_watch(e: &Env, pool: &Address) -> Option<RecapWatch> {
+    let key = BackstopDataKey::RecapWatch(pool.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set when a pool's backstop was first and last seen below the threshold
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `watch` - The watch, or None if the backstop is above the threshold
+pub fn set_recap_watch(e: &Env, pool: &Address, watch: &Option<RecapWatch>) {
+    let key = BackstopDataKey::RecapWatch(pool.clone());
+    match watch {
+        Some(watch) => {
+            e.storage()
+                .persistent()
+                .set::<BackstopDataKey, RecapWatch>(&key, watch);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch a pool's recapitalization auction, if any
+///
+/// ### Arguments
+/// * `pool` - The pool
+pub fn get_recap_auction(e: &Env, pool: &Address) -> Option<RecapAuction> {
+    let key = BackstopDataKey::RecapAuction(pool.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set a pool's recapitalization auction
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `auction` - The auction, or None to remove it
+pub fn set_recap_auction(e: &Env, pool: &Address, auction: &Option<RecapAuction>) {
+    let key = BackstopDataKey::RecapAuction(pool.clone());
+    match auction {
+        Some(auction) => {
+            e.storage()
+                .persistent()
+                .set::<BackstopDataKey, RecapAuction>(&key, auction);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch the BLND sold by recapitalization auctions in the latest epoch
+pub fn get_recap_epoch(e: &Env) -> RecapEpoch {
+    e.storage()
+        .instance()
+        .get::<Symbol, RecapEpoch>(&Symbol::new(e, RECAP_EPOCH_KEY))
+        .unwrap_or(RecapEpoch { epoch: 0, sold: 0 })
+}
+
+/// Set the BLND sold by recapitalization auctions in the latest epoch
+///
+/// ### Arguments
+/// * `recap_epoch` - The epoch and the BLND sold in it
+pub fn set_recap_epoch(e: &Env, recap_epoch: &RecapEpoch) {
+    e.storage()
+        .instance()
+        .set::<Symbol, RecapEpoch>(&Symbol::new(e, RECAP_EPOCH_KEY), recap_epoch);
+}
+
+/********** Backstop Yield **********/
+
+/// Fetch the checkpoints a pool's backstop APR is measured from, if any
+///
+/// ### Arguments
+/// * `pool` - The pool
+pub fn get_yield_window(e: &Env, pool: &Address) -> Option<YieldWindow> {
+    let key = BackstopDataKey::YieldWindow(pool.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the checkpoints a pool's backstop APR is measured from
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `window` - The checkpoints
+pub fn set_yield_window(e: &Env, pool: &Address, window: &YieldWindow) {
+    let key = BackstopDataKey::YieldWindow(pool.clone());
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, YieldWindow>(&key, window);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
@@ -14,6 +14,7 @@ use soroban_sdk::{
     vec, Address, BytesN, Env, IntoVal, Vec,
 };
 
+use sep_40_oracle::testutils::{MockPriceOracleClient, MockPriceOracleWASM};
 use sep_41_token::testutils::{MockTokenClient, MockTokenWASM};
 
 use blend_contract_sdk::emitter::{Client as EmitterClient, WASM as EmitterWASM};
@@ -32,6 +33,8 @@ pub(crate) fn create_backstop(e: &Env) -> Address {
             Address::generate(e),
             Address::generate(e),
             Address::generate(e),
+            Address::generate(e),
+            Address::generate(e),
             Vec::<(Address, i128)>::new(e),
         ),
     )
@@ -71,6 +74,21 @@ pub(crate) fn create_usdc_token<'a>(
     (contract_address, client)
 }
 
+pub(crate) fn create_mock_oracle<'a>(
+    e: &Env,
+    backstop: &Address,
+) -> (Address, MockPriceOracleClient<'a>) {
+    let contract_address = e.register(MockPriceOracleWASM, ());
+
+    e.as_contract(backstop, || {
+        storage::set_oracle(e, &contract_address);
+    });
+    (
+        contract_address.clone(),
+        MockPriceOracleClient::new(e, &contract_address),
+    )
+}
+
 pub(crate) fn create_backstop_token<'a>(
     e: &Env,
     backstop: &Address,
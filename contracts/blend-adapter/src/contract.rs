@@ -0,0 +1,318 @@
+use crate::{
+    dependencies::{
+        AuctionData, FlashLoan, PoolClient, PoolConfig, Positions, Request, Reserve,
+        ReserveEmissionData, UserEmissionData,
+    },
+    storage,
+};
+use soroban_sdk::{contract, contractclient, contractimpl, Address, Env, Vec};
+
+/// ### BlendAdapter
+///
+/// Exposes the Blend v2 pool interface and proxies every call to a TrustBridge pool, so existing
+/// Blend tooling (bots, dashboards, SDKs) can be pointed at a TrustBridge deployment unchanged.
+///
+/// Only the user facing functions are exposed. Admin functions must be called on the pool directly.
+#[contract]
+pub struct BlendAdapterContract;
+
+#[contractclient(name = "BlendAdapterClient")]
+pub trait BlendAdapter {
+    /// Fetch the configuration of the pool
+    fn get_config(e: Env) -> PoolConfig;
+
+    /// Fetch the admin address of the pool
+    fn get_admin(e: Env) -> Address;
+
+    /// Fetch the a vec addresses of all reserves in the pool
+    fn get_reserve_list(e: Env) -> Vec<Address>;
+
+    /// Fetch information about a reserve, updated to the current ledger
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_reserve(e: Env, asset: Address) -> Reserve;
+
+    /// Fetch the positions for an address
+    ///
+    /// ### Arguments
+    /// * `address` - The address to fetch positions for
+    fn get_positions(e: Env, address: Address) -> Positions;
+
+    /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
+    /// required tokens to the pool and `to` receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions for `from`
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `spender` - The address of the user who is sending tokens to the pool
+    /// * `to` - The address of the user who is receiving tokens from the pool
+    /// * `requests` - A vec of requests to be processed
+    fn submit(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions;
+
+    /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
+    /// required tokens to the pool using transfer_from and `to` receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions for `from`
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `spender` - The address of the user who is sending tokens to the pool
+    /// * `to` - The address of the user who is receiving tokens from the pool
+    /// * `requests` - A vec of requests to be processed
+    fn submit_with_allowance(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions;
+
+    /// Submit a flash loan and a set of requests to the pool where `from` takes on the position
+    ///
+    /// Returns the new positions for `from`
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `flash_loan` - The flash loan to execute
+    /// * `requests` - A vec of requests to be processed
+    fn flash_loan(
+        e: Env,
+        from: Address,
+        flash_loan: FlashLoan,
+        requests: Vec<Request>,
+    ) -> Positions;
+
+    /// Update the pool status based on the backstop state
+    ///
+    /// Returns the new pool status
+    fn update_status(e: Env) -> u32;
+
+    /// Sync the pool's token balance of `asset` with the reserve
+    ///
+    /// Returns the amount of tokens gulped
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn gulp(e: Env, asset: Address) -> i128;
+
+    /// Consume emissions from the backstop and distribute them to the pool's reserves
+    ///
+    /// Returns the amount of emissions gulped
+    fn gulp_emissions(e: Env) -> i128;
+
+    /// Claim outstanding emissions for `from` for the given reserve token ids
+    ///
+    /// Returns the amount of BLND claimed. Emissions paid in the pool's migrated emission token
+    /// are also sent to `to`, but are not included in the amount, as Blend v2 only pays BLND.
+    ///
+    /// ### Arguments
+    /// * `from` - The address claiming
+    /// * `reserve_token_ids` - The reserve token ids to claim emissions for
+    /// * `to` - The address to send the emissions to
+    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128;
+
+    /// Fetch the emission data for a reserve token
+    ///
+    /// ### Arguments
+    /// * `reserve_token_id` - The reserve token id
+    fn get_reserve_emissions(e: Env, reserve_token_id: u32) -> Option<ReserveEmissionData>;
+
+    /// Fetch the emission data for a user's reserve token
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `reserve_token_id` - The reserve token id
+    fn get_user_emissions(e: Env, user: Address, reserve_token_id: u32)
+        -> Option<UserEmissionData>;
+
+    /// Create a new auction
+    ///
+    /// Returns the created auction
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    /// * `bid` - The set of assets to include in the auction bid
+    /// * `lot` - The set of assets to include in the auction lot
+    /// * `percent` - The percent of the assets to be auctioned off
+    fn new_auction(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        percent: u32,
+    ) -> AuctionData;
+
+    /// Fetch an auction
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction
+    /// * `user` - The Address involved in the auction
+    fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData;
+
+    /// Delete a stale auction
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction
+    /// * `user` - The Address involved in the auction
+    fn del_auction(e: Env, auction_type: u32, user: Address);
+
+    /// Check and handle bad debt for a user
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user to check for bad debt
+    fn bad_debt(e: Env, user: Address);
+}
+
+#[contractimpl]
+impl BlendAdapterContract {
+    /// Construct the adapter contract
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the TrustBridge pool to proxy calls to
+    pub fn __constructor(e: Env, pool: Address) {
+        storage::set_pool(&e, &pool);
+    }
+}
+
+/// Fetch a client for the adapted pool
+fn pool_client(e: &Env) -> PoolClient {
+    PoolClient::new(e, &storage::get_pool(e))
+}
+
+#[contractimpl]
+impl BlendAdapter for BlendAdapterContract {
+    fn get_config(e: Env) -> PoolConfig {
+        pool_client(&e).get_config()
+    }
+
+    fn get_admin(e: Env) -> Address {
+        pool_client(&e).get_admin()
+    }
+
+    fn get_reserve_list(e: Env) -> Vec<Address> {
+        pool_client(&e).get_reserve_list()
+    }
+
+    fn get_reserve(e: Env, asset: Address) -> Reserve {
+        pool_client(&e).get_reserve(&asset)
+    }
+
+    fn get_positions(e: Env, address: Address) -> Positions {
+        pool_client(&e).get_positions(&address)
+    }
+
+    fn submit(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        // authorize the adapter call so wallets can build the auth tree from the adapter down
+        spender.require_auth();
+        if from != spender {
+            from.require_auth();
+        }
+
+        pool_client(&e).submit(&from, &spender, &to, &requests)
+    }
+
+    fn submit_with_allowance(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        spender.require_auth();
+        if from != spender {
+            from.require_auth();
+        }
+
+        pool_client(&e).submit_with_allowance(&from, &spender, &to, &requests)
+    }
+
+    fn flash_loan(
+        e: Env,
+        from: Address,
+        flash_loan: FlashLoan,
+        requests: Vec<Request>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        pool_client(&e).flash_loan(&from, &flash_loan, &requests)
+    }
+
+    fn update_status(e: Env) -> u32 {
+        storage::extend_instance(&e);
+        pool_client(&e).update_status()
+    }
+
+    fn gulp(e: Env, asset: Address) -> i128 {
+        storage::extend_instance(&e);
+        pool_client(&e).gulp(&asset)
+    }
+
+    fn gulp_emissions(e: Env) -> i128 {
+        storage::extend_instance(&e);
+        pool_client(&e).gulp_emissions()
+    }
+
+    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        pool_client(&e).claim(&from, &reserve_token_ids, &to).legacy
+    }
+
+    fn get_reserve_emissions(e: Env, reserve_token_id: u32) -> Option<ReserveEmissionData> {
+        pool_client(&e).get_reserve_emissions(&reserve_token_id)
+    }
+
+    fn get_user_emissions(
+        e: Env,
+        user: Address,
+        reserve_token_id: u32,
+    ) -> Option<UserEmissionData> {
+        pool_client(&e).get_user_emissions(&user, &reserve_token_id)
+    }
+
+    fn new_auction(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        percent: u32,
+    ) -> AuctionData {
+        storage::extend_instance(&e);
+        pool_client(&e).new_auction(&auction_type, &user, &bid, &lot, &percent)
+    }
+
+    fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData {
+        pool_client(&e).get_auction(&auction_type, &user)
+    }
+
+    fn del_auction(e: Env, auction_type: u32, user: Address) {
+        storage::extend_instance(&e);
+        pool_client(&e).del_auction(&auction_type, &user)
+    }
+
+    fn bad_debt(e: Env, user: Address) {
+        storage::extend_instance(&e);
+        pool_client(&e).bad_debt(&user)
+    }
+}
@@ -0,0 +1,185 @@
+/**
+ * Client and types for the TrustBridge pool. The types mirror the Blend v2 pool types field for
+ * field, so they serialize identically for both contracts.
+ */
+use soroban_sdk::{contractclient, contracttype, Address, Env, Map, Vec};
+
+/// The pool's config
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct PoolConfig {
+    pub oracle: Address,
+    pub min_collateral: i128,
+    pub bstop_rate: u32,
+    pub status: u32,
+    pub max_positions: u32,
+}
+
+/// The configuration information about a reserve asset
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ReserveConfig {
+    pub index: u32,
+    pub decimals: u32,
+    pub c_factor: u32,
+    pub l_factor: u32,
+    pub util: u32,
+    pub max_util: u32,
+    pub r_base: u32,
+    pub r_one: u32,
+    pub r_two: u32,
+    pub r_three: u32,
+    pub reactivity: u32,
+    pub supply_cap: i128,
+    pub enabled: bool,
+}
+
+/// The data for a reserve asset
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ReserveData {
+    pub d_rate: i128,
+    pub b_rate: i128,
+    pub ir_mod: i128,
+    pub b_supply: i128,
+    pub d_supply: i128,
+    pub backstop_credit: i128,
+    pub last_time: u64,
+}
+
+/// A reserve, updated to the current ledger
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Reserve {
+    pub asset: Address,
+    pub config: ReserveConfig,
+    pub data: ReserveData,
+    pub scalar: i128,
+}
+
+/// A user / contracts position's with the pool
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Positions {
+    pub liabilities: Map<u32, i128>,
+    pub collateral: Map<u32, i128>,
+    pub supply: Map<u32, i128>,
+}
+
+/// A request a user makes against the pool
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Request {
+    pub request_type: u32,
+    pub address: Address,
+    pub amount: i128,
+}
+
+/// A flash loan to execute before a set of requests
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct FlashLoan {
+    pub contract: Address,
+    pub asset: Address,
+    pub amount: i128,
+}
+
+/// The emission data for the reserve b or d token
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ReserveEmissionData {
+    pub expiration: u64,
+    pub eps: u64,
+    pub index: i128,
+    pub last_time: u64,
+}
+
+/// The user emission data for the reserve b or d token
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct UserEmissionData {
+    pub index: i128,
+    pub accrued: i128,
+}
+
+/// The emissions claimed from the pool, split by the token they were paid in
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ClaimedEmissions {
+    pub legacy: i128,
+    pub migrated: i128,
+}
+
+/// An auction, with the bid and lot denominated in the units of the auction type
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct AuctionData {
+    pub bid: Map<Address, i128>,
+    pub lot: Map<Address, i128>,
+    pub block: u32,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolClient")]
+pub trait Pool {
+    fn get_config(e: Env) -> PoolConfig;
+
+    fn get_admin(e: Env) -> Address;
+
+    fn get_reserve_list(e: Env) -> Vec<Address>;
+
+    fn get_reserve(e: Env, asset: Address) -> Reserve;
+
+    fn get_positions(e: Env, address: Address) -> Positions;
+
+    fn submit(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions;
+
+    fn submit_with_allowance(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions;
+
+    fn flash_loan(
+        e: Env,
+        from: Address,
+        flash_loan: FlashLoan,
+        requests: Vec<Request>,
+    ) -> Positions;
+
+    fn update_status(e: Env) -> u32;
+
+    fn gulp(e: Env, asset: Address) -> i128;
+
+    fn gulp_emissions(e: Env) -> i128;
+
+    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> ClaimedEmissions;
+
+    fn get_reserve_emissions(e: Env, reserve_token_id: u32) -> Option<ReserveEmissionData>;
+
+    fn get_user_emissions(e: Env, user: Address, reserve_token_id: u32)
+        -> Option<UserEmissionData>;
+
+    fn new_auction(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        percent: u32,
+    ) -> AuctionData;
+
+    fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData;
+
+    fn del_auction(e: Env, auction_type: u32, user: Address);
+
+    fn bad_debt(e: Env, user: Address);
+}
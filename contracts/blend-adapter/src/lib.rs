@@ -0,0 +1,15 @@
+#![no_std]
+
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod contract;
+mod dependencies;
+mod storage;
+mod test;
+
+pub use contract::*;
+pub use dependencies::{
+    AuctionData, ClaimedEmissions, FlashLoan, PoolConfig, Positions, Request, Reserve,
+    ReserveConfig, ReserveData, ReserveEmissionData, UserEmissionData,
+};
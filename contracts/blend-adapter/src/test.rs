@@ -0,0 +1,210 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl, map,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    vec, Address, Env, IntoVal, Map, Symbol, Vec,
+};
+
+use crate::{
+    BlendAdapterClient, BlendAdapterContract, ClaimedEmissions, PoolConfig, Positions, Request,
+    ReserveEmissionData,
+};
+
+/********** Mocks **********/
+
+/// Records the requests submitted for each user as collateral positions
+#[contract]
+pub struct MockPool;
+
+#[contractimpl]
+impl MockPool {
+    pub fn __constructor(e: Env, config: PoolConfig) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "Config"), &config);
+    }
+
+    pub fn get_config(e: Env) -> PoolConfig {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "Config"))
+            .unwrap()
+    }
+
+    pub fn get_positions(e: Env, address: Address) -> Positions {
+        e.storage().persistent().get(&address).unwrap_or(Positions {
+            liabilities: Map::new(&e),
+            collateral: Map::new(&e),
+            supply: Map::new(&e),
+        })
+    }
+
+    pub fn submit(
+        e: Env,
+        from: Address,
+        spender: Address,
+        _to: Address,
+        requests: Vec<Request>,
+    ) -> Positions {
+        spender.require_auth();
+        if from != spender {
+            from.require_auth();
+        }
+        let mut positions = Self::get_positions(e.clone(), from.clone());
+        for request in requests.iter() {
+            let index = request.request_type;
+            positions.collateral.set(
+                index,
+                positions.collateral.get(index).unwrap_or(0) + request.amount,
+            );
+        }
+        e.storage().persistent().set(&from, &positions);
+        positions
+    }
+
+    pub fn get_reserve_emissions(_e: Env, reserve_token_id: u32) -> Option<ReserveEmissionData> {
+        if reserve_token_id == 1 {
+            Some(ReserveEmissionData {
+                expiration: 1000,
+                eps: 10,
+                index: 5,
+                last_time: 100,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Pays out 10 of the legacy emission token and 5 of the migrated emission token per
+    /// reserve token id claimed
+    pub fn claim(
+        _e: Env,
+        from: Address,
+        reserve_token_ids: Vec<u32>,
+        _to: Address,
+    ) -> ClaimedEmissions {
+        from.require_auth();
+        ClaimedEmissions {
+            legacy: 10_0000000 * reserve_token_ids.len() as i128,
+            migrated: 5_0000000 * reserve_token_ids.len() as i128,
+        }
+    }
+}
+
+fn create_adapter<'a>(e: &Env) -> (Address, BlendAdapterClient<'a>) {
+    let config = PoolConfig {
+        oracle: Address::generate(e),
+        min_collateral: 1_0000000,
+        bstop_rate: 0_1000000,
+        status: 1,
+        max_positions: 4,
+    };
+    let pool = e.register(MockPool {}, (config,));
+    let adapter = e.register(BlendAdapterContract {}, (pool.clone(),));
+    (pool, BlendAdapterClient::new(e, &adapter))
+}
+
+#[test]
+fn test_views() {
+    let e = Env::default();
+    let (_, adapter_client) = create_adapter(&e);
+
+    let config = adapter_client.get_config();
+    assert_eq!(config.min_collateral, 1_0000000);
+    assert_eq!(config.bstop_rate, 0_1000000);
+    assert_eq!(config.status, 1);
+    assert_eq!(config.max_positions, 4);
+
+    let emissions = adapter_client.get_reserve_emissions(&1).unwrap();
+    assert_eq!(emissions.eps, 10);
+    assert_eq!(emissions.index, 5);
+    assert!(adapter_client.get_reserve_emissions(&2).is_none());
+}
+
+#[test]
+fn test_submit() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (pool, adapter_client) = create_adapter(&e);
+
+    let samwise = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let requests = vec![
+        &e,
+        Request {
+            request_type: 2,
+            address: asset.clone(),
+            amount: 10_0000000,
+        },
+    ];
+    let positions = adapter_client.submit(&samwise, &samwise, &samwise, &requests);
+    assert_eq!(positions.collateral, map![&e, (2, 10_0000000)]);
+
+    // the user authorizes the adapter call, which authorizes the pool call
+    assert_eq!(
+        e.auths()[0],
+        (
+            samwise.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    adapter_client.address.clone(),
+                    Symbol::new(&e, "submit"),
+                    vec![
+                        &e,
+                        samwise.into_val(&e),
+                        samwise.into_val(&e),
+                        samwise.into_val(&e),
+                        requests.into_val(&e),
+                    ]
+                )),
+                sub_invocations: std::vec![AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        pool.clone(),
+                        Symbol::new(&e, "submit"),
+                        vec![
+                            &e,
+                            samwise.into_val(&e),
+                            samwise.into_val(&e),
+                            samwise.into_val(&e),
+                            requests.into_val(&e),
+                        ]
+                    )),
+                    sub_invocations: std::vec![]
+                }]
+            }
+        )
+    );
+
+    let positions = adapter_client.get_positions(&samwise);
+    assert_eq!(positions.collateral, map![&e, (2, 10_0000000)]);
+}
+
+#[test]
+fn test_claim_only_returns_legacy_emissions() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (_, adapter_client) = create_adapter(&e);
+
+    let samwise = Address::generate(&e);
+    let claimed = adapter_client.claim(&samwise, &vec![&e, 0, 3], &samwise);
+    assert_eq!(claimed, 20_0000000);
+}
+
+#[test]
+#[should_panic]
+fn test_submit_requires_auth() {
+    let e = Env::default();
+    let (_, adapter_client) = create_adapter(&e);
+
+    let samwise = Address::generate(&e);
+    let requests = vec![
+        &e,
+        Request {
+            request_type: 2,
+            address: Address::generate(&e),
+            amount: 10_0000000,
+        },
+    ];
+    adapter_client.submit(&samwise, &samwise, &samwise, &requests);
+}
@@ -0,0 +1,109 @@
+use crate::{
+    dependencies::{oracle, BackstopClient, PoolClient, PoolFactoryClient},
+    errors::BootstrapperError,
+    events::BootstrapperEvents,
+    storage::{BootstrapConfig, Deployment},
+};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, panic_with_error, xdr::ToXdr, Bytes, BytesN, Env, Vec,
+};
+
+/// ### Bootstrapper
+///
+/// Deploys and configures a lending pool in a single invocation, so a test environment can be
+/// stood up with one signature from the pool's admin. The bootstrapper deploys an oracle seeded
+/// with a price for each reserve, deploys the pool with the pool factory, adds the reserves, and
+/// links the pool to the backstop.
+#[contract]
+pub struct BootstrapperContract;
+
+#[contractclient(name = "BootstrapperClient")]
+pub trait Bootstrapper {
+    /// Deploy and configure a pool as described by `config`. The admin of `config` authorizes
+    /// every admin action taken against the oracle, pool, and backstop as sub-invocations of
+    /// this call.
+    ///
+    /// Returns the deployed oracle and pool
+    ///
+    /// ### Arguments
+    /// * `config` - The configuration of the pool
+    ///
+    /// ### Panics
+    /// * If no reserves are included or the backstop deposit is negative
+    /// * If any step of the deployment fails
+    fn bootstrap(e: Env, config: BootstrapConfig) -> Deployment;
+}
+
+#[contractimpl]
+impl Bootstrapper for BootstrapperContract {
+    fn bootstrap(e: Env, config: BootstrapConfig) -> Deployment {
+        config.admin.require_auth();
+
+        if config.reserves.is_empty() || config.backstop_deposit < 0 {
+            panic_with_error!(&e, BootstrapperError::BadRequest);
+        }
+
+        // deploy the oracle and seed a price for each reserve
+        let oracle_address = e
+            .deployer()
+            .with_current_contract(oracle_salt(&e, &config))
+            .deploy_v2(e.deployer().upload_contract_wasm(oracle::WASM), ());
+        let oracle_client = oracle::Client::new(&e, &oracle_address);
+        oracle_client.init(&config.admin);
+        let mut assets = Vec::new(&e);
+        let mut prices = Vec::new(&e);
+        for reserve in config.reserves.iter() {
+            assets.push_back(oracle::Asset::Stellar(reserve.asset));
+            prices.push_back(reserve.price);
+        }
+        oracle_client.set_prices(&assets, &prices);
+
+        // deploy the pool and add the reserves. The pool is in setup, so adding a reserve is not
+        // timelocked.
+        let pool_address = PoolFactoryClient::new(&e, &config.pool_factory).deploy(
+            &config.admin,
+            &config.name,
+            &config.salt,
+            &oracle_address,
+            &config.backstop_take_rate,
+            &config.max_positions,
+            &config.min_collateral,
+        );
+        let pool_client = PoolClient::new(&e, &pool_address);
+        for reserve in config.reserves.iter() {
+            pool_client.queue_set_reserve(&config.admin, &reserve.asset, &reserve.config);
+            pool_client.set_reserve(&reserve.asset);
+        }
+
+        // link the pool to the backstop
+        let backstop_client = BackstopClient::new(&e, &config.backstop);
+        if config.backstop_deposit > 0 {
+            backstop_client.deposit(&config.admin, &pool_address, &config.backstop_deposit);
+        }
+        if config.add_to_reward_zone {
+            backstop_client.add_reward(&pool_address, &None);
+        }
+        if config.activate {
+            pool_client.set_status(&config.admin, &0);
+        }
+
+        BootstrapperEvents::bootstrap(
+            &e,
+            config.admin,
+            oracle_address.clone(),
+            pool_address.clone(),
+        );
+        Deployment {
+            oracle: oracle_address,
+            pool: pool_address,
+        }
+    }
+}
+
+/// Derive the salt of the oracle from the salt and admin of the config, so the oracle address
+/// cannot be claimed by another caller using the same salt
+fn oracle_salt(e: &Env, config: &BootstrapConfig) -> BytesN<32> {
+    let mut salt_as_bytes = Bytes::from_array(e, &config.salt.to_array());
+    salt_as_bytes.append(&config.admin.clone().to_xdr(e));
+    e.crypto().keccak256(&salt_as_bytes).into()
+}
@@ -0,0 +1,51 @@
+/**
+ * The oracle contract and partial clients for the pool factory, pool, and backstop contracts
+ */
+use soroban_sdk::{contractclient, Address, BytesN, Env, String};
+
+use crate::storage::ReserveConfig;
+
+pub mod oracle {
+    soroban_sdk::contractimport!(
+        file = "../../target/wasm32-unknown-unknown/release/trustbridge_oracle.wasm"
+    );
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolFactoryClient")]
+pub trait PoolFactory {
+    /// Deploys and initializes a lending pool
+    fn deploy(
+        e: Env,
+        admin: Address,
+        name: String,
+        salt: BytesN<32>,
+        oracle: Address,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+    ) -> Address;
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolClient")]
+pub trait Pool {
+    /// (Risk role only) Queues setting data for a reserve in the pool
+    fn queue_set_reserve(e: Env, caller: Address, asset: Address, metadata: ReserveConfig);
+
+    /// Executes the queued set of a reserve in the pool
+    fn set_reserve(e: Env, asset: Address) -> u32;
+
+    /// (Admin or pauser role) Pool status is changed to `pool_status`
+    fn set_status(e: Env, caller: Address, pool_status: u32);
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "BackstopClient")]
+pub trait Backstop {
+    /// Deposit backstop tokens from `from` into the backstop of a pool
+    fn deposit(e: Env, from: Address, pool_address: Address, amount: i128) -> i128;
+
+    /// Add a pool to the reward zone, and if the reward zone is full, a pool to remove
+    fn add_reward(e: Env, to_add: Address, to_remove: Option<Address>);
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the bootstrapper contract. Common errors are codes that match up with the
+/// built-in contracts error reporting. Bootstrapper specific errors start at 1600.
+pub enum BootstrapperError {
+    // Common Errors
+    InternalError = 1,
+
+    // Bootstrapper
+    BadRequest = 1600,
+}
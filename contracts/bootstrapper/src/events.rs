@@ -0,0 +1,19 @@
+use soroban_sdk::{Address, Env, Symbol};
+
+pub struct BootstrapperEvents {}
+
+impl BootstrapperEvents {
+    /// Emitted when a pool is bootstrapped
+    ///
+    /// - topics - `["bootstrap", admin: Address]`
+    /// - data - `[oracle: Address, pool: Address]`
+    ///
+    /// ### Arguments
+    /// * `admin` - The admin of the oracle and pool
+    /// * `oracle` - The deployed oracle
+    /// * `pool` - The deployed pool
+    pub fn bootstrap(e: &Env, admin: Address, oracle: Address, pool: Address) {
+        let topics = (Symbol::new(e, "bootstrap"), admin);
+        e.events().publish(topics, (oracle, pool));
+    }
+}
@@ -0,0 +1,15 @@
+#![no_std]
+
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod contract;
+mod dependencies;
+mod errors;
+mod events;
+mod storage;
+mod test;
+
+pub use contract::*;
+pub use errors::BootstrapperError;
+pub use storage::{BootstrapConfig, Deployment, ReserveConfig, ReserveSetup};
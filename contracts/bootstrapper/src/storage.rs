@@ -0,0 +1,74 @@
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
+
+/// The configuration of a reserve. Mirrors `pool::ReserveConfig`.
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveConfig {
+    pub index: u32,          // the index of the reserve in the list
+    pub decimals: u32,       // the decimals used in both the bToken and underlying contract
+    pub c_factor: u32,       // the collateral factor for the reserve scaled expressed in 7 decimals
+    pub l_factor: u32,       // the liability factor for the reserve scaled expressed in 7 decimals
+    pub util: u32,           // the target utilization rate scaled expressed in 7 decimals
+    pub max_util: u32,       // the maximum allowed utilization rate scaled expressed in 7 decimals
+    pub r_base: u32, // the R0 value (base rate) in the interest rate formula scaled expressed in 7 decimals
+    pub r_one: u32,  // the R1 value in the interest rate formula scaled expressed in 7 decimals
+    pub r_two: u32,  // the R2 value in the interest rate formula scaled expressed in 7 decimals
+    pub r_three: u32, // the R3 value in the interest rate formula scaled expressed in 7 decimals
+    pub reactivity: u32, // the reactivity constant for the reserve scaled expressed in 7 decimals
+    pub supply_cap: i128, // the total amount of underlying tokens that can be supplied to the reserve
+    pub enabled: bool,    // the enabled flag of the reserve
+    pub conc_threshold: u32, // the share of bTokens a user can hold before a concentration haircut
+    pub conc_haircut: u32, // the collateral haircut for holding the entire supply, or 0 for none
+}
+
+/// A reserve to add to the pool, and the price the oracle is seeded with for it
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveSetup {
+    /// The underlying asset of the reserve
+    pub asset: Address,
+    /// The price of the asset (7 decimals)
+    pub price: i128,
+    /// The configuration of the reserve
+    pub config: ReserveConfig,
+}
+
+/// The configuration of a pool to bootstrap
+#[derive(Clone)]
+#[contracttype]
+pub struct BootstrapConfig {
+    /// The admin of the oracle and pool, and the depositor into the pool's backstop
+    pub admin: Address,
+    /// The salt used to derive the oracle and pool addresses
+    pub salt: BytesN<32>,
+    /// The pool factory used to deploy the pool
+    pub pool_factory: Address,
+    /// The backstop of the pools deployed by the pool factory
+    pub backstop: Address,
+    /// The name of the pool
+    pub name: String,
+    /// The backstop take rate of the pool (7 decimals)
+    pub backstop_take_rate: u32,
+    /// The maximum user positions supported by the pool
+    pub max_positions: u32,
+    /// The minimum collateral required for a borrow position (oracle decimals)
+    pub min_collateral: i128,
+    /// The reserves to add to the pool, in order
+    pub reserves: Vec<ReserveSetup>,
+    /// The amount of backstop tokens `admin` deposits into the pool's backstop
+    pub backstop_deposit: i128,
+    /// If the pool is added to the backstop's reward zone
+    pub add_to_reward_zone: bool,
+    /// If the pool is set to active once it is configured
+    pub activate: bool,
+}
+
+/// The contracts deployed by a bootstrap
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Deployment {
+    /// The oracle
+    pub oracle: Address,
+    /// The pool
+    pub pool: Address,
+}
@@ -0,0 +1,328 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, BytesN as _, Events},
+    vec, Address, BytesN, Env, IntoVal, String, Symbol, Vec,
+};
+
+use crate::{
+    dependencies::oracle, BootstrapConfig, BootstrapperClient, BootstrapperContract, Deployment,
+    ReserveConfig, ReserveSetup,
+};
+
+/********** Mocks **********/
+
+/// Returns a preset pool from `deploy`
+#[contract]
+pub struct MockPoolFactory;
+
+#[contractimpl]
+impl MockPoolFactory {
+    pub fn set_pool(e: Env, pool: Address) {
+        e.storage().instance().set(&Symbol::new(&e, "pool"), &pool);
+    }
+
+    pub fn deploy(
+        e: Env,
+        admin: Address,
+        _name: String,
+        _salt: BytesN<32>,
+        oracle: Address,
+        _backstop_take_rate: u32,
+        _max_positions: u32,
+        _min_collateral: i128,
+    ) -> Address {
+        admin.require_auth();
+        let pool: Address = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "pool"))
+            .unwrap();
+        MockPoolClient::new(&e, &pool).init(&admin, &oracle);
+        pool
+    }
+}
+
+/// Records the reserves set and the status of the pool
+#[contract]
+pub struct MockPool;
+
+#[contractimpl]
+impl MockPool {
+    pub fn init(e: Env, admin: Address, oracle: Address) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "admin"), &admin);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "oracle"), &oracle);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "status"), &6u32);
+    }
+
+    pub fn queue_set_reserve(e: Env, caller: Address, asset: Address, metadata: ReserveConfig) {
+        assert_eq!(caller, Self::admin(e.clone()));
+        caller.require_auth();
+        e.storage().instance().set(&asset, &metadata.index);
+    }
+
+    pub fn set_reserve(e: Env, asset: Address) -> u32 {
+        let index: u32 = e.storage().instance().get(&asset).unwrap();
+        let mut reserves = Self::reserves(e.clone());
+        reserves.push_back(asset);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "reserves"), &reserves);
+        index
+    }
+
+    pub fn set_status(e: Env, caller: Address, pool_status: u32) {
+        assert_eq!(caller, Self::admin(e.clone()));
+        caller.require_auth();
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "status"), &pool_status);
+    }
+
+    pub fn admin(e: Env) -> Address {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "admin"))
+            .unwrap()
+    }
+
+    pub fn oracle(e: Env) -> Address {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "oracle"))
+            .unwrap()
+    }
+
+    pub fn reserves(e: Env) -> Vec<Address> {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "reserves"))
+            .unwrap_or(vec![&e])
+    }
+
+    pub fn status(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "status"))
+            .unwrap()
+    }
+}
+
+/// Records deposits and the pools in the reward zone
+#[contract]
+pub struct MockBackstop;
+
+#[contractimpl]
+impl MockBackstop {
+    pub fn deposit(e: Env, from: Address, pool_address: Address, amount: i128) -> i128 {
+        from.require_auth();
+        e.storage().instance().set(&pool_address, &amount);
+        amount
+    }
+
+    pub fn add_reward(e: Env, to_add: Address, _to_remove: Option<Address>) {
+        let mut reward_zone = Self::reward_zone(e.clone());
+        reward_zone.push_back(to_add);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "rz"), &reward_zone);
+    }
+
+    pub fn pool_deposit(e: Env, pool_address: Address) -> i128 {
+        e.storage().instance().get(&pool_address).unwrap_or(0)
+    }
+
+    pub fn reward_zone(e: Env) -> Vec<Address> {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "rz"))
+            .unwrap_or(vec![&e])
+    }
+}
+
+fn default_reserve_config(index: u32) -> ReserveConfig {
+    ReserveConfig {
+        index,
+        decimals: 7,
+        c_factor: 0_7500000,
+        l_factor: 0_7500000,
+        util: 0_5000000,
+        max_util: 0_9500000,
+        r_base: 0_0100000,
+        r_one: 0_0500000,
+        r_two: 0_5000000,
+        r_three: 1_5000000,
+        reactivity: 0_0000020,
+        supply_cap: 1_000_000_000_0000000,
+        enabled: true,
+        conc_threshold: 0,
+        conc_haircut: 0,
+    }
+}
+
+fn setup_config(e: &Env, admin: &Address) -> (BootstrapConfig, MockPoolClient, MockBackstopClient) {
+    let pool = e.register(MockPool {}, ());
+    let pool_factory = e.register(MockPoolFactory {}, ());
+    MockPoolFactoryClient::new(e, &pool_factory).set_pool(&pool);
+    let backstop = e.register(MockBackstop {}, ());
+
+    let config = BootstrapConfig {
+        admin: admin.clone(),
+        salt: BytesN::<32>::random(e),
+        pool_factory,
+        backstop: backstop.clone(),
+        name: String::from_str(e, "Testnet"),
+        backstop_take_rate: 0_1000000,
+        max_positions: 4,
+        min_collateral: 1_0000000,
+        reserves: vec![
+            e,
+            ReserveSetup {
+                asset: Address::generate(e),
+                price: 1_0000000,
+                config: default_reserve_config(0),
+            },
+            ReserveSetup {
+                asset: Address::generate(e),
+                price: 0_1000000,
+                config: default_reserve_config(1),
+            },
+        ],
+        backstop_deposit: 50_000_0000000,
+        add_to_reward_zone: true,
+        activate: true,
+    };
+    (
+        config,
+        MockPoolClient::new(e, &pool),
+        MockBackstopClient::new(e, &backstop),
+    )
+}
+
+#[test]
+fn test_bootstrap() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+
+    let bombadil = Address::generate(&e);
+    let bootstrapper = BootstrapperClient::new(&e, &e.register(BootstrapperContract {}, ()));
+    let (config, pool_client, backstop_client) = setup_config(&e, &bombadil);
+
+    let deployment = bootstrapper.bootstrap(&config);
+    let events = e.events().all();
+    assert_eq!(
+        deployment,
+        Deployment {
+            oracle: pool_client.oracle(),
+            pool: pool_client.address.clone(),
+        }
+    );
+
+    // oracle is seeded with a price for each reserve
+    let oracle_client = oracle::Client::new(&e, &deployment.oracle);
+    assert_eq!(oracle_client.admin(), bombadil);
+    let asset_0 = config.reserves.get_unchecked(0).asset;
+    let asset_1 = config.reserves.get_unchecked(1).asset;
+    assert_eq!(
+        oracle_client
+            .lastprice(&oracle::Asset::Stellar(asset_0.clone()))
+            .unwrap()
+            .price,
+        1_0000000
+    );
+    assert_eq!(
+        oracle_client
+            .lastprice(&oracle::Asset::Stellar(asset_1.clone()))
+            .unwrap()
+            .price,
+        0_1000000
+    );
+
+    // pool is configured and linked to the backstop
+    assert_eq!(pool_client.admin(), bombadil);
+    assert_eq!(pool_client.reserves(), vec![&e, asset_0, asset_1]);
+    assert_eq!(pool_client.status(), 0);
+    assert_eq!(
+        backstop_client.pool_deposit(&deployment.pool),
+        50_000_0000000
+    );
+    assert_eq!(
+        backstop_client.reward_zone(),
+        vec![&e, deployment.pool.clone()]
+    );
+
+    let event = events.last().unwrap();
+    assert_eq!(event.0, bootstrapper.address);
+    assert_eq!(
+        event.1,
+        (Symbol::new(&e, "bootstrap"), bombadil).into_val(&e)
+    );
+    let data: (Address, Address) = event.2.into_val(&e);
+    assert_eq!(data, (deployment.oracle, deployment.pool));
+}
+
+#[test]
+fn test_bootstrap_without_backstop_linkage() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+
+    let bombadil = Address::generate(&e);
+    let bootstrapper = BootstrapperClient::new(&e, &e.register(BootstrapperContract {}, ()));
+    let (mut config, pool_client, backstop_client) = setup_config(&e, &bombadil);
+    config.backstop_deposit = 0;
+    config.add_to_reward_zone = false;
+    config.activate = false;
+
+    let deployment = bootstrapper.bootstrap(&config);
+    assert_eq!(pool_client.reserves().len(), 2);
+    assert_eq!(pool_client.status(), 6);
+    assert_eq!(backstop_client.pool_deposit(&deployment.pool), 0);
+    assert_eq!(backstop_client.reward_zone().len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1600)")]
+fn test_bootstrap_no_reserves() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+
+    let bombadil = Address::generate(&e);
+    let bootstrapper = BootstrapperClient::new(&e, &e.register(BootstrapperContract {}, ()));
+    let (mut config, _, _) = setup_config(&e, &bombadil);
+    config.reserves = vec![&e];
+
+    bootstrapper.bootstrap(&config);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1600)")]
+fn test_bootstrap_negative_deposit() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+
+    let bombadil = Address::generate(&e);
+    let bootstrapper = BootstrapperClient::new(&e, &e.register(BootstrapperContract {}, ()));
+    let (mut config, _, _) = setup_config(&e, &bombadil);
+    config.backstop_deposit = -1;
+
+    bootstrapper.bootstrap(&config);
+}
+
+#[test]
+#[should_panic]
+fn test_bootstrap_requires_admin_auth() {
+    let e = Env::default();
+
+    let bombadil = Address::generate(&e);
+    let bootstrapper = BootstrapperClient::new(&e, &e.register(BootstrapperContract {}, ()));
+    let (config, _, _) = setup_config(&e, &bombadil);
+
+    bootstrapper.bootstrap(&config);
+}
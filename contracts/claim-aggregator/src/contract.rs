@@ -0,0 +1,279 @@
+use crate::{
+    dependencies::{BackstopClient, DexAdapterClient, PoolClient},
+    errors::ClaimAggregatorError,
+    events::ClaimAggregatorEvents,
+    storage::{self, ClaimResult, PoolClaim},
+};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractclient, contractimpl, panic_with_error,
+    token::TokenClient,
+    vec, Address, Env, IntoVal, Map, Symbol, Val, Vec,
+};
+
+/// ### ClaimAggregator
+///
+/// Claims pool and backstop emissions for a user in a single transaction, optionally swapping
+/// the claimed pool emissions into a single token through the DEX adapter.
+///
+/// Pools that migrated their emissions pay emissions accrued after the migration in their
+/// migrated emission token, which is claimed alongside BLND.
+#[contract]
+pub struct ClaimAggregatorContract;
+
+#[contractclient(name = "ClaimAggregatorClient")]
+pub trait ClaimAggregator {
+    /// Claim emissions for `from` from a set of pools and backstop deposits. Pool emissions
+    /// are sent to `to` as BLND and any migrated emission tokens, and backstop emissions are
+    /// deposited into the backstop as LP tokens.
+    ///
+    /// Returns the amounts claimed
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user claiming rewards
+    /// * `pool_claims` - The pools and reserve token ids to claim pool emissions for
+    /// * `backstop_pools` - The pools to claim backstop deposit emissions for
+    /// * `min_lp_tokens_out` - The minimum amount of LP tokens to mint with the claimed backstop emissions
+    /// * `to` - The address to send the claimed pool emissions to
+    ///
+    /// ### Panics
+    /// If nothing is being claimed
+    fn claim(
+        e: Env,
+        from: Address,
+        pool_claims: Vec<PoolClaim>,
+        backstop_pools: Vec<Address>,
+        min_lp_tokens_out: i128,
+        to: Address,
+    ) -> ClaimResult;
+
+    /// Claim emissions for `from` from a set of pools and backstop deposits, and swap the claimed
+    /// pool emissions into `token_out` through the DEX adapter before sending them to `to`. BLND
+    /// and each migrated emission token are swapped separately, and a migrated emission token
+    /// that is `token_out` is sent to `to` without a swap.
+    /// Backstop emissions are deposited into the backstop as LP tokens.
+    ///
+    /// Returns the amounts claimed
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user claiming rewards
+    /// * `pool_claims` - The pools and reserve token ids to claim pool emissions for
+    /// * `backstop_pools` - The pools to claim backstop deposit emissions for
+    /// * `min_lp_tokens_out` - The minimum amount of LP tokens to mint with the claimed backstop emissions
+    /// * `token_out` - The token to swap the claimed pool emissions into
+    /// * `min_amount_out` - The minimum total amount of `token_out` to receive from the swaps
+    /// * `to` - The address to send the swapped tokens to
+    ///
+    /// ### Panics
+    /// If nothing is being claimed, if `token_out` is BLND, or if less than `min_amount_out` of
+    /// `token_out` is received
+    #[allow(clippy::too_many_arguments)]
+    fn claim_and_swap(
+        e: Env,
+        from: Address,
+        pool_claims: Vec<PoolClaim>,
+        backstop_pools: Vec<Address>,
+        min_lp_tokens_out: i128,
+        token_out: Address,
+        min_amount_out: i128,
+        to: Address,
+    ) -> ClaimResult;
+}
+
+#[contractimpl]
+impl ClaimAggregatorContract {
+    /// Construct the claim aggregator contract
+    ///
+    /// ### Arguments
+    /// * `backstop` - The address of the backstop
+    /// * `blnd_token` - The address of the BLND token
+    /// * `dex_adapter` - The address of the DEX adapter used to swap claimed emissions
+    pub fn __constructor(e: Env, backstop: Address, blnd_token: Address, dex_adapter: Address) {
+        storage::set_backstop(&e, &backstop);
+        storage::set_blnd_token(&e, &blnd_token);
+        storage::set_dex_adapter(&e, &dex_adapter);
+    }
+}
+
+#[contractimpl]
+impl ClaimAggregator for ClaimAggregatorContract {
+    fn claim(
+        e: Env,
+        from: Address,
+        pool_claims: Vec<PoolClaim>,
+        backstop_pools: Vec<Address>,
+        min_lp_tokens_out: i128,
+        to: Address,
+    ) -> ClaimResult {
+        from.require_auth();
+        storage::extend_instance(&e);
+
+        if pool_claims.is_empty() && backstop_pools.is_empty() {
+            panic_with_error!(&e, ClaimAggregatorError::BadRequest);
+        }
+        let (pool_emissions, migrated_emissions) =
+            claim_pool_emissions(&e, &from, &pool_claims, &to);
+        let backstop_lp_tokens =
+            claim_backstop_emissions(&e, &from, &backstop_pools, min_lp_tokens_out);
+
+        ClaimAggregatorEvents::claim(
+            &e,
+            from,
+            pool_emissions,
+            migrated_emissions.clone(),
+            backstop_lp_tokens,
+            storage::get_blnd_token(&e),
+            pool_emissions,
+        );
+        ClaimResult {
+            pool_emissions,
+            migrated_emissions,
+            backstop_lp_tokens,
+            amount_out: pool_emissions,
+        }
+    }
+
+    fn claim_and_swap(
+        e: Env,
+        from: Address,
+        pool_claims: Vec<PoolClaim>,
+        backstop_pools: Vec<Address>,
+        min_lp_tokens_out: i128,
+        token_out: Address,
+        min_amount_out: i128,
+        to: Address,
+    ) -> ClaimResult {
+        from.require_auth();
+        storage::extend_instance(&e);
+
+        let blnd_token = storage::get_blnd_token(&e);
+        if token_out == blnd_token {
+            panic_with_error!(&e, ClaimAggregatorError::InvalidSwap);
+        }
+        if pool_claims.is_empty() && backstop_pools.is_empty() {
+            panic_with_error!(&e, ClaimAggregatorError::BadRequest);
+        }
+
+        // claim pool emissions to the aggregator so they can be swapped
+        let (pool_emissions, migrated_emissions) =
+            claim_pool_emissions(&e, &from, &pool_claims, &e.current_contract_address());
+        let backstop_lp_tokens =
+            claim_backstop_emissions(&e, &from, &backstop_pools, min_lp_tokens_out);
+
+        let mut amount_out = 0;
+        if pool_emissions > 0 {
+            amount_out += swap(&e, &blnd_token, &token_out, pool_emissions, &to);
+        }
+        for (token, amount) in migrated_emissions.iter() {
+            if token == token_out {
+                TokenClient::new(&e, &token).transfer(&e.current_contract_address(), &to, &amount);
+                amount_out += amount;
+            } else {
+                amount_out += swap(&e, &token, &token_out, amount, &to);
+            }
+        }
+        if amount_out < min_amount_out {
+            panic_with_error!(&e, ClaimAggregatorError::InvalidSwap);
+        }
+
+        ClaimAggregatorEvents::claim(
+            &e,
+            from,
+            pool_emissions,
+            migrated_emissions.clone(),
+            backstop_lp_tokens,
+            token_out,
+            amount_out,
+        );
+        ClaimResult {
+            pool_emissions,
+            migrated_emissions,
+            backstop_lp_tokens,
+            amount_out,
+        }
+    }
+}
+
+/// Claim pool emissions for `from` from each pool, sending the BLND and any migrated emission
+/// tokens to `to`
+///
+/// Returns the total amount of BLND claimed, and the amount of each migrated emission token
+/// claimed
+fn claim_pool_emissions(
+    e: &Env,
+    from: &Address,
+    pool_claims: &Vec<PoolClaim>,
+    to: &Address,
+) -> (i128, Map<Address, i128>) {
+    let mut claimed: i128 = 0;
+    let mut migrated: Map<Address, i128> = Map::new(e);
+    for pool_claim in pool_claims.iter() {
+        if pool_claim.reserve_token_ids.is_empty() {
+            panic_with_error!(e, ClaimAggregatorError::BadRequest);
+        }
+        let pool_client = PoolClient::new(e, &pool_claim.pool);
+        let pool_claimed = pool_client.claim(from, &pool_claim.reserve_token_ids, to);
+        claimed += pool_claimed.legacy;
+        if pool_claimed.migrated > 0 {
+            let token = match pool_client.get_emission_migration() {
+                Some(migration) => migration.token,
+                None => panic_with_error!(e, ClaimAggregatorError::InternalError),
+            };
+            let amount = migrated.get(token.clone()).unwrap_or(0) + pool_claimed.migrated;
+            migrated.set(token, amount);
+        }
+    }
+    (claimed, migrated)
+}
+
+/// Claim backstop deposit emissions for `from` from each pool
+///
+/// Returns the amount of LP tokens minted
+fn claim_backstop_emissions(
+    e: &Env,
+    from: &Address,
+    backstop_pools: &Vec<Address>,
+    min_lp_tokens_out: i128,
+) -> i128 {
+    if backstop_pools.is_empty() {
+        return 0;
+    }
+    BackstopClient::new(e, &storage::get_backstop(e)).claim(
+        from,
+        backstop_pools,
+        &min_lp_tokens_out,
+    )
+}
+
+/// Swap `amount_in` of `token_in` held by the aggregator into `token_out`, sent to `to`. The
+/// minimum amount out is checked against the total of all swaps by the caller.
+///
+/// Returns the amount of `token_out` received
+fn swap(e: &Env, token_in: &Address, token_out: &Address, amount_in: i128, to: &Address) -> i128 {
+    let dex_adapter = storage::get_dex_adapter(e);
+    let args: Vec<Val> = vec![
+        e,
+        (&e.current_contract_address()).into_val(e),
+        (&dex_adapter).into_val(e),
+        (&amount_in).into_val(e),
+    ];
+    e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token_in.clone(),
+                fn_name: Symbol::new(e, "transfer"),
+                args,
+            },
+            sub_invocations: vec![e],
+        }),
+    ]);
+    DexAdapterClient::new(e, &dex_adapter).swap_exact_in(
+        &e.current_contract_address(),
+        token_in,
+        token_out,
+        &amount_in,
+        &0,
+        to,
+    )
+}
@@ -0,0 +1,58 @@
+/**
+ * Partial clients for the contracts rewards are claimed from
+ */
+use soroban_sdk::{contractclient, contracttype, Address, Env, Vec};
+
+/// The emissions claimed from a pool, split by the token they were paid in
+#[derive(Clone)]
+#[contracttype]
+pub struct ClaimedEmissions {
+    pub legacy: i128,   // the amount of the legacy emission token (BLND) claimed
+    pub migrated: i128, // the amount of the pool's migrated emission token claimed
+}
+
+/// The token a pool's emissions are paid in after a migration
+#[derive(Clone)]
+#[contracttype]
+pub struct EmissionMigration {
+    pub token: Address,
+    pub ledger: u32,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolClient")]
+pub trait Pool {
+    /// Claims outstanding emissions for `from` for the given reserve token ids
+    ///
+    /// Returns the amount of the legacy and migrated emission tokens claimed
+    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> ClaimedEmissions;
+
+    /// Fetch the pool's emission token migration, if one is set
+    fn get_emission_migration(e: Env) -> Option<EmissionMigration>;
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "BackstopClient")]
+pub trait Backstop {
+    /// Claim backstop deposit emissions from a list of pools for `from`
+    ///
+    /// Returns the amount of LP tokens minted
+    fn claim(e: Env, from: Address, pool_addresses: Vec<Address>, min_lp_tokens_out: i128) -> i128;
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "DexAdapterClient")]
+pub trait DexAdapter {
+    /// Swap an exact amount of `token_in` held by `from` for `token_out`, sent to `to`
+    ///
+    /// Returns the amount of `token_out` received
+    fn swap_exact_in(
+        e: Env,
+        from: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+        to: Address,
+    ) -> i128;
+}
@@ -0,0 +1,15 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the claim aggregator contract. Common errors are codes that match up with the
+/// built-in contracts error reporting. Claim aggregator specific errors start at 1400.
+pub enum ClaimAggregatorError {
+    // Common Errors
+    InternalError = 1,
+
+    // Claim Aggregator
+    BadRequest = 1400,
+    InvalidSwap = 1401,
+}
@@ -0,0 +1,39 @@
+use soroban_sdk::{Address, Env, Map, Symbol};
+
+pub struct ClaimAggregatorEvents {}
+
+impl ClaimAggregatorEvents {
+    /// Emitted when rewards are claimed through the aggregator
+    ///
+    /// - topics - `["claim", from: Address]`
+    /// - data - `[pool_emissions: i128, migrated_emissions: Map<Address, i128>, backstop_lp_tokens: i128, token_out: Address, amount_out: i128]`
+    ///
+    /// ### Arguments
+    /// * `from` - The address whose rewards were claimed
+    /// * `pool_emissions` - The amount of BLND claimed from pools
+    /// * `migrated_emissions` - The amount of each migrated emission token claimed from pools
+    /// * `backstop_lp_tokens` - The amount of backstop LP tokens minted from backstop emissions
+    /// * `token_out` - The token the pool emissions were paid out in
+    /// * `amount_out` - The amount of `token_out` sent to the recipient
+    pub fn claim(
+        e: &Env,
+        from: Address,
+        pool_emissions: i128,
+        migrated_emissions: Map<Address, i128>,
+        backstop_lp_tokens: i128,
+        token_out: Address,
+        amount_out: i128,
+    ) {
+        let topics = (Symbol::new(e, "claim"), from);
+        e.events().publish(
+            topics,
+            (
+                pool_emissions,
+                migrated_emissions,
+                backstop_lp_tokens,
+                token_out,
+                amount_out,
+            ),
+        );
+    }
+}
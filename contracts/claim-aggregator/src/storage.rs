@@ -0,0 +1,95 @@
+use soroban_sdk::{contracttype, unwrap::UnwrapOptimized, Address, Env, Map, Symbol, Vec};
+
+/********** Ledger Thresholds **********/
+
+const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
+
+const LEDGER_THRESHOLD_INSTANCE: u32 = ONE_DAY_LEDGERS * 30; // ~ 30 days
+const LEDGER_BUMP_INSTANCE: u32 = LEDGER_THRESHOLD_INSTANCE + ONE_DAY_LEDGERS; // ~ 31 days
+
+/********** Storage Types **********/
+
+/// The reserve tokens to claim emissions for from a single pool
+#[derive(Clone)]
+#[contracttype]
+pub struct PoolClaim {
+    pub pool: Address,
+    pub reserve_token_ids: Vec<u32>,
+}
+
+/// The outcome of an aggregated claim
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ClaimResult {
+    pub pool_emissions: i128, // the amount of BLND claimed from pools
+    pub migrated_emissions: Map<Address, i128>, // the migrated emission tokens claimed from pools
+    pub backstop_lp_tokens: i128, // the amount of LP tokens deposited into the backstop
+    pub amount_out: i128,     // the amount of `token_out` sent to the recipient
+}
+
+/********** Storage Keys **********/
+
+const BACKSTOP_KEY: &str = "Backstop";
+const BLND_TOKEN_KEY: &str = "BLNDTkn";
+const DEX_ADAPTER_KEY: &str = "DexAdptr";
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_INSTANCE, LEDGER_BUMP_INSTANCE);
+}
+
+/// Fetch the backstop address
+pub fn get_backstop(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, BACKSTOP_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the backstop address
+///
+/// ### Arguments
+/// * `backstop` - The address of the backstop
+pub fn set_backstop(e: &Env, backstop: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, BACKSTOP_KEY), backstop);
+}
+
+/// Fetch the BLND token address
+pub fn get_blnd_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, BLND_TOKEN_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the BLND token address
+///
+/// ### Arguments
+/// * `blnd_token` - The address of the BLND token
+pub fn set_blnd_token(e: &Env, blnd_token: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, BLND_TOKEN_KEY), blnd_token);
+}
+
+/// Fetch the DEX adapter address
+pub fn get_dex_adapter(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, DEX_ADAPTER_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the DEX adapter address
+///
+/// ### Arguments
+/// * `dex_adapter` - The address of the DEX adapter
+pub fn set_dex_adapter(e: &Env, dex_adapter: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, DEX_ADAPTER_KEY), dex_adapter);
+}
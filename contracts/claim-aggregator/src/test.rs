@@ -0,0 +1,456 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl, map,
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, IntoVal, Map, Symbol, Vec,
+};
+
+use crate::{
+    dependencies::{ClaimedEmissions, EmissionMigration},
+    ClaimAggregatorClient, ClaimAggregatorContract, ClaimResult, PoolClaim,
+};
+
+/********** Mocks **********/
+
+/// Pays out 10 BLND per reserve token id claimed, and 5 of the migrated emission token per
+/// reserve token id claimed once the pool has migrated its emissions
+#[contract]
+pub struct MockPool;
+
+#[contractimpl]
+impl MockPool {
+    pub fn __constructor(e: Env, blnd: Address) {
+        e.storage().instance().set(&Symbol::new(&e, "BLND"), &blnd);
+    }
+
+    pub fn set_emission_migration(e: Env, token: Address) {
+        e.storage().instance().set(&Symbol::new(&e, "Migr"), &token);
+    }
+
+    pub fn get_emission_migration(e: Env) -> Option<EmissionMigration> {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "Migr"))
+            .map(|token| EmissionMigration { token, ledger: 0 })
+    }
+
+    pub fn claim(
+        e: Env,
+        from: Address,
+        reserve_token_ids: Vec<u32>,
+        to: Address,
+    ) -> ClaimedEmissions {
+        from.require_auth();
+        let blnd: Address = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "BLND"))
+            .unwrap();
+        let legacy = 10_0000000 * reserve_token_ids.len() as i128;
+        TokenClient::new(&e, &blnd).transfer(&e.current_contract_address(), &to, &legacy);
+        let migrated = match MockPool::get_emission_migration(e.clone()) {
+            Some(migration) => {
+                let amount = 5_0000000 * reserve_token_ids.len() as i128;
+                TokenClient::new(&e, &migration.token).transfer(
+                    &e.current_contract_address(),
+                    &to,
+                    &amount,
+                );
+                amount
+            }
+            None => 0,
+        };
+        ClaimedEmissions { legacy, migrated }
+    }
+}
+
+/// Mints 5 LP tokens per pool claimed
+#[contract]
+pub struct MockBackstop;
+
+#[contractimpl]
+impl MockBackstop {
+    pub fn claim(
+        _e: Env,
+        from: Address,
+        pool_addresses: Vec<Address>,
+        _min_lp_tokens_out: i128,
+    ) -> i128 {
+        from.require_auth();
+        5_0000000 * pool_addresses.len() as i128
+    }
+}
+
+/// Swaps at a fixed rate of 2 `token_in` per `token_out`
+#[contract]
+pub struct MockDexAdapter;
+
+#[contractimpl]
+impl MockDexAdapter {
+    pub fn swap_exact_in(
+        e: Env,
+        from: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+        to: Address,
+    ) -> i128 {
+        from.require_auth();
+        TokenClient::new(&e, &token_in).transfer(&from, &e.current_contract_address(), &amount_in);
+        let amount_out = amount_in / 2;
+        assert!(amount_out >= min_amount_out);
+        TokenClient::new(&e, &token_out).transfer(&e.current_contract_address(), &to, &amount_out);
+        amount_out
+    }
+}
+
+struct Setup<'a> {
+    blnd: TokenClient<'a>,
+    usdc: TokenClient<'a>,
+    tbrg: TokenClient<'a>,
+    pool_0: Address,
+    pool_1: Address,
+    dex_adapter: Address,
+    aggregator: ClaimAggregatorClient<'a>,
+}
+
+fn setup(e: &Env) -> Setup {
+    let bombadil = Address::generate(e);
+    let blnd = e
+        .register_stellar_asset_contract_v2(bombadil.clone())
+        .address();
+    let usdc = e
+        .register_stellar_asset_contract_v2(bombadil.clone())
+        .address();
+    let tbrg = e
+        .register_stellar_asset_contract_v2(bombadil.clone())
+        .address();
+
+    let pool_0 = e.register(MockPool {}, (blnd.clone(),));
+    let pool_1 = e.register(MockPool {}, (blnd.clone(),));
+    let backstop = e.register(MockBackstop {}, ());
+    let dex_adapter = e.register(MockDexAdapter {}, ());
+    StellarAssetClient::new(e, &blnd).mint(&pool_0, &1000_0000000);
+    StellarAssetClient::new(e, &blnd).mint(&pool_1, &1000_0000000);
+    StellarAssetClient::new(e, &tbrg).mint(&pool_0, &1000_0000000);
+    StellarAssetClient::new(e, &tbrg).mint(&pool_1, &1000_0000000);
+    StellarAssetClient::new(e, &usdc).mint(&dex_adapter, &1000_0000000);
+
+    let aggregator = e.register(
+        ClaimAggregatorContract {},
+        (backstop, blnd.clone(), dex_adapter.clone()),
+    );
+    Setup {
+        blnd: TokenClient::new(e, &blnd),
+        usdc: TokenClient::new(e, &usdc),
+        tbrg: TokenClient::new(e, &tbrg),
+        pool_0,
+        pool_1,
+        dex_adapter,
+        aggregator: ClaimAggregatorClient::new(e, &aggregator),
+    }
+}
+
+#[test]
+fn test_claim() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let setup = setup(&e);
+
+    let pool_claims = vec![
+        &e,
+        PoolClaim {
+            pool: setup.pool_0.clone(),
+            reserve_token_ids: vec![&e, 0, 1, 3],
+        },
+        PoolClaim {
+            pool: setup.pool_1.clone(),
+            reserve_token_ids: vec![&e, 1],
+        },
+    ];
+    let backstop_pools = vec![&e, setup.pool_0.clone(), setup.pool_1.clone()];
+    let result = setup
+        .aggregator
+        .claim(&samwise, &pool_claims, &backstop_pools, &0, &frodo);
+    assert_eq!(
+        result,
+        ClaimResult {
+            pool_emissions: 40_0000000,
+            migrated_emissions: Map::new(&e),
+            backstop_lp_tokens: 10_0000000,
+            amount_out: 40_0000000,
+        }
+    );
+    assert_eq!(setup.blnd.balance(&frodo), 40_0000000);
+    assert_eq!(setup.blnd.balance(&setup.aggregator.address), 0);
+
+    let event = vec![&e, e.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &e,
+            (
+                setup.aggregator.address.clone(),
+                (Symbol::new(&e, "claim"), samwise.clone()).into_val(&e),
+                (
+                    40_0000000i128,
+                    Map::<Address, i128>::new(&e),
+                    10_0000000i128,
+                    setup.blnd.address.clone(),
+                    40_0000000i128
+                )
+                    .into_val(&e)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_claim_and_swap() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let setup = setup(&e);
+
+    let pool_claims = vec![
+        &e,
+        PoolClaim {
+            pool: setup.pool_0.clone(),
+            reserve_token_ids: vec![&e, 0, 1],
+        },
+    ];
+    let result = setup.aggregator.claim_and_swap(
+        &samwise,
+        &pool_claims,
+        &vec![&e, setup.pool_0.clone()],
+        &0,
+        &setup.usdc.address,
+        &10_0000000,
+        &frodo,
+    );
+    assert_eq!(
+        result,
+        ClaimResult {
+            pool_emissions: 20_0000000,
+            migrated_emissions: Map::new(&e),
+            backstop_lp_tokens: 5_0000000,
+            amount_out: 10_0000000,
+        }
+    );
+    assert_eq!(setup.usdc.balance(&frodo), 10_0000000);
+    assert_eq!(setup.blnd.balance(&frodo), 0);
+    assert_eq!(setup.blnd.balance(&setup.aggregator.address), 0);
+    assert_eq!(setup.blnd.balance(&setup.dex_adapter), 20_0000000);
+}
+
+#[test]
+fn test_claim_after_emission_migration() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let setup = setup(&e);
+    MockPoolClient::new(&e, &setup.pool_0).set_emission_migration(&setup.tbrg.address);
+
+    let pool_claims = vec![
+        &e,
+        PoolClaim {
+            pool: setup.pool_0.clone(),
+            reserve_token_ids: vec![&e, 0, 1],
+        },
+        PoolClaim {
+            pool: setup.pool_1.clone(),
+            reserve_token_ids: vec![&e, 1],
+        },
+    ];
+    let result = setup
+        .aggregator
+        .claim(&samwise, &pool_claims, &vec![&e], &0, &frodo);
+    assert_eq!(
+        result,
+        ClaimResult {
+            pool_emissions: 30_0000000,
+            migrated_emissions: map![&e, (setup.tbrg.address.clone(), 10_0000000)],
+            backstop_lp_tokens: 0,
+            amount_out: 30_0000000,
+        }
+    );
+    assert_eq!(setup.blnd.balance(&frodo), 30_0000000);
+    assert_eq!(setup.tbrg.balance(&frodo), 10_0000000);
+    assert_eq!(setup.tbrg.balance(&setup.aggregator.address), 0);
+}
+
+#[test]
+fn test_claim_and_swap_after_emission_migration() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let setup = setup(&e);
+    MockPoolClient::new(&e, &setup.pool_0).set_emission_migration(&setup.tbrg.address);
+
+    let pool_claims = vec![
+        &e,
+        PoolClaim {
+            pool: setup.pool_0.clone(),
+            reserve_token_ids: vec![&e, 0, 1],
+        },
+    ];
+    let result = setup.aggregator.claim_and_swap(
+        &samwise,
+        &pool_claims,
+        &vec![&e],
+        &0,
+        &setup.usdc.address,
+        &15_0000000,
+        &frodo,
+    );
+    // 20 BLND and 10 TBRG are each swapped at 2 per USDC
+    assert_eq!(
+        result,
+        ClaimResult {
+            pool_emissions: 20_0000000,
+            migrated_emissions: map![&e, (setup.tbrg.address.clone(), 10_0000000)],
+            backstop_lp_tokens: 0,
+            amount_out: 15_0000000,
+        }
+    );
+    assert_eq!(setup.usdc.balance(&frodo), 15_0000000);
+    assert_eq!(setup.blnd.balance(&setup.aggregator.address), 0);
+    assert_eq!(setup.tbrg.balance(&setup.aggregator.address), 0);
+    assert_eq!(setup.blnd.balance(&setup.dex_adapter), 20_0000000);
+    assert_eq!(setup.tbrg.balance(&setup.dex_adapter), 10_0000000);
+}
+
+#[test]
+fn test_claim_and_swap_to_migrated_token() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let setup = setup(&e);
+    MockPoolClient::new(&e, &setup.pool_0).set_emission_migration(&setup.tbrg.address);
+    StellarAssetClient::new(&e, &setup.tbrg.address).mint(&setup.dex_adapter, &1000_0000000);
+
+    let pool_claims = vec![
+        &e,
+        PoolClaim {
+            pool: setup.pool_0.clone(),
+            reserve_token_ids: vec![&e, 0, 1],
+        },
+    ];
+    let result = setup.aggregator.claim_and_swap(
+        &samwise,
+        &pool_claims,
+        &vec![&e],
+        &0,
+        &setup.tbrg.address,
+        &20_0000000,
+        &frodo,
+    );
+    // the BLND is swapped, and the migrated emissions are sent without a swap
+    assert_eq!(result.amount_out, 20_0000000);
+    assert_eq!(setup.tbrg.balance(&frodo), 20_0000000);
+    assert_eq!(setup.blnd.balance(&setup.dex_adapter), 20_0000000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1401)")]
+fn test_claim_and_swap_under_min_amount_out_panics() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let setup = setup(&e);
+    MockPoolClient::new(&e, &setup.pool_0).set_emission_migration(&setup.tbrg.address);
+
+    let pool_claims = vec![
+        &e,
+        PoolClaim {
+            pool: setup.pool_0.clone(),
+            reserve_token_ids: vec![&e, 0, 1],
+        },
+    ];
+    setup.aggregator.claim_and_swap(
+        &samwise,
+        &pool_claims,
+        &vec![&e],
+        &0,
+        &setup.usdc.address,
+        &15_0000001,
+        &samwise,
+    );
+}
+
+#[test]
+fn test_claim_and_swap_only_backstop() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let setup = setup(&e);
+
+    let result = setup.aggregator.claim_and_swap(
+        &samwise,
+        &vec![&e],
+        &vec![&e, setup.pool_1.clone()],
+        &0,
+        &setup.usdc.address,
+        &0,
+        &samwise,
+    );
+    assert_eq!(
+        result,
+        ClaimResult {
+            pool_emissions: 0,
+            migrated_emissions: Map::new(&e),
+            backstop_lp_tokens: 5_0000000,
+            amount_out: 0,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1400)")]
+fn test_claim_nothing_panics() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let setup = setup(&e);
+
+    setup
+        .aggregator
+        .claim(&samwise, &vec![&e], &vec![&e], &0, &samwise);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1401)")]
+fn test_claim_and_swap_to_blnd_panics() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let setup = setup(&e);
+
+    setup.aggregator.claim_and_swap(
+        &samwise,
+        &vec![&e],
+        &vec![&e, setup.pool_0.clone()],
+        &0,
+        &setup.blnd.address,
+        &0,
+        &samwise,
+    );
+}
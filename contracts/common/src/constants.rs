@@ -0,0 +1,5 @@
+/// Fixed-point scalar for 7 decimal numbers
+pub const SCALAR_7: i128 = 1_0000000;
+
+/// Fixed-point scalar for 12 decimal numbers
+pub const SCALAR_12: i128 = 1_000_000_000_000;
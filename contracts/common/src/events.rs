@@ -0,0 +1,19 @@
+use soroban_sdk::{Env, Symbol};
+
+/// Build the topics of an event with only a name
+///
+/// ### Arguments
+/// * `name` - The name of the event
+pub fn topic(e: &Env, name: &str) -> (Symbol,) {
+    (Symbol::new(e, name),)
+}
+
+/// Build the topics of an event with a name and a subject, like the asset or address the event
+/// is about
+///
+/// ### Arguments
+/// * `name` - The name of the event
+/// * `subject` - The subject of the event
+pub fn topic_with<T>(e: &Env, name: &str, subject: T) -> (Symbol, T) {
+    (Symbol::new(e, name), subject)
+}
@@ -0,0 +1,7 @@
+/// Fetch the fixed-point scalar for a number of decimals
+///
+/// ### Arguments
+/// * `decimals` - The number of decimals
+pub fn decimal_scalar(decimals: u32) -> i128 {
+    10i128.pow(decimals)
+}
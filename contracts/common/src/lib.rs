@@ -0,0 +1,17 @@
+#![no_std]
+
+mod constants;
+pub mod events;
+mod fixed_point;
+mod price_feed;
+pub mod roles;
+mod status;
+mod test;
+mod types;
+
+pub use constants::{SCALAR_12, SCALAR_7};
+pub use fixed_point::decimal_scalar;
+pub use price_feed::PriceFeedClient;
+pub use roles::Role;
+pub use status::ContractStatus;
+pub use types::{Asset, PriceData, PriceDataV2};
@@ -0,0 +1,29 @@
+use soroban_sdk::{contractclient, Env, Vec};
+
+use crate::{Asset, PriceData};
+
+/// The price feed interface of a SEP-40 oracle
+#[allow(dead_code)]
+#[contractclient(name = "PriceFeedClient")]
+pub trait PriceFeed {
+    /// Fetch the asset prices are quoted in
+    fn base(e: Env) -> Asset;
+
+    /// Fetch the assets the oracle prices
+    fn assets(e: Env) -> Vec<Asset>;
+
+    /// Fetch the decimals of the oracle's prices
+    fn decimals(e: Env) -> u32;
+
+    /// Fetch the time between the oracle's prices, in seconds
+    fn resolution(e: Env) -> u32;
+
+    /// Fetch the price of an asset at a timestamp
+    fn price(e: Env, asset: Asset, timestamp: u64) -> Option<PriceData>;
+
+    /// Fetch the last `records` prices of an asset, newest first
+    fn prices(e: Env, asset: Asset, records: u32) -> Option<Vec<PriceData>>;
+
+    /// Fetch the last price of an asset
+    fn lastprice(e: Env, asset: Asset) -> Option<PriceData>;
+}
@@ -0,0 +1,79 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
+
+const LEDGER_THRESHOLD: u32 = ONE_DAY_LEDGERS * 30; // ~ 30 days
+const LEDGER_BUMP: u32 = LEDGER_THRESHOLD + ONE_DAY_LEDGERS; // ~ 31 days
+
+/// A role that can be granted to an address. The admin holds every role, and roles other than
+/// the admin can be granted and revoked by it, so operations can be split across keys with only
+/// the privileges they need.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Role {
+    /// Full control of the contract. Held only by the contract's admin address.
+    Admin = 0,
+    /// Can tune risk parameters
+    Risk = 1,
+    /// Can restrict the contract in an emergency
+    Pauser = 2,
+    /// Can direct the fees the contract collects
+    Treasurer = 3,
+}
+
+#[derive(Clone)]
+#[contracttype]
+struct RoleKey {
+    role: Role,
+    member: Address,
+}
+
+/// Check if an address was granted a role. Does not consider the admin, which holds every role.
+/// Roles are kept in persistent storage, and their TTL is extended each time they are checked.
+///
+/// ### Arguments
+/// * `role` - The role
+/// * `member` - The address to check
+pub fn has_role(e: &Env, role: Role, member: &Address) -> bool {
+    let key = RoleKey {
+        role,
+        member: member.clone(),
+    };
+    if e.storage().persistent().has(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+        true
+    } else {
+        false
+    }
+}
+
+/// Grant a role to an address
+///
+/// ### Arguments
+/// * `role` - The role
+/// * `member` - The address to grant the role to
+pub fn grant_role(e: &Env, role: Role, member: &Address) {
+    let key = RoleKey {
+        role,
+        member: member.clone(),
+    };
+    e.storage().persistent().set(&key, &());
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Revoke a role from an address
+///
+/// ### Arguments
+/// * `role` - The role
+/// * `member` - The address to revoke the role from
+pub fn revoke_role(e: &Env, role: Role, member: &Address) {
+    e.storage().persistent().remove(&RoleKey {
+        role,
+        member: member.clone(),
+    });
+}
@@ -0,0 +1,31 @@
+use soroban_sdk::contracttype;
+
+/// The operating status of a TrustBridge contract. Every contract exposes it through a `status`
+/// view, so monitoring can poll each contract the same way. Mirrors the pool's statuses, without
+/// the distinction between statuses set by the admin and by the backstop.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ContractStatus {
+    /// All actions are allowed
+    Active = 0,
+    /// Actions that add risk, like borrowing, are disabled
+    Restricted = 1,
+    /// Only actions that reduce risk are allowed
+    Frozen = 2,
+}
+
+impl ContractStatus {
+    /// Map a pool status to a contract status. Active (0, 1) pools are active, on-ice (2, 3)
+    /// pools are restricted, and frozen (4, 5) or setup (6) pools are frozen.
+    ///
+    /// ### Arguments
+    /// * `pool_status` - The status of the pool
+    pub fn from_pool_status(pool_status: u32) -> Self {
+        match pool_status {
+            0 | 1 => ContractStatus::Active,
+            2 | 3 => ContractStatus::Restricted,
+            _ => ContractStatus::Frozen,
+        }
+    }
+}
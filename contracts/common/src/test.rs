@@ -0,0 +1,76 @@
+#![cfg(test)]
+
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, Symbol};
+
+use crate::{decimal_scalar, events, roles, ContractStatus, Role, SCALAR_12, SCALAR_7};
+
+#[test]
+fn test_from_pool_status() {
+    assert_eq!(ContractStatus::from_pool_status(0), ContractStatus::Active);
+    assert_eq!(ContractStatus::from_pool_status(1), ContractStatus::Active);
+    assert_eq!(
+        ContractStatus::from_pool_status(2),
+        ContractStatus::Restricted
+    );
+    assert_eq!(
+        ContractStatus::from_pool_status(3),
+        ContractStatus::Restricted
+    );
+    assert_eq!(ContractStatus::from_pool_status(4), ContractStatus::Frozen);
+    assert_eq!(ContractStatus::from_pool_status(5), ContractStatus::Frozen);
+    assert_eq!(ContractStatus::from_pool_status(6), ContractStatus::Frozen);
+}
+
+#[test]
+fn test_decimal_scalar() {
+    assert_eq!(decimal_scalar(7), SCALAR_7);
+    assert_eq!(decimal_scalar(12), SCALAR_12);
+    assert_eq!(decimal_scalar(0), 1);
+}
+
+#[test]
+fn test_topics() {
+    let e = Env::default();
+    let subject = Address::generate(&e);
+
+    assert_eq!(
+        events::topic(&e, "set_admin"),
+        (Symbol::new(&e, "set_admin"),)
+    );
+    assert_eq!(
+        events::topic_with(&e, "price_set", subject.clone()),
+        (Symbol::new(&e, "price_set"), subject)
+    );
+}
+
+/// A contract to hold role storage
+#[contract]
+struct TestContract;
+
+#[contractimpl]
+impl TestContract {
+    pub fn ping(_e: Env) {}
+}
+
+#[test]
+fn test_roles() {
+    let e = Env::default();
+    let contract = e.register(TestContract, ());
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+
+    e.as_contract(&contract, || {
+        assert!(!roles::has_role(&e, Role::Risk, &samwise));
+
+        roles::grant_role(&e, Role::Risk, &samwise);
+        roles::grant_role(&e, Role::Pauser, &frodo);
+        assert!(roles::has_role(&e, Role::Risk, &samwise));
+        assert!(!roles::has_role(&e, Role::Pauser, &samwise));
+        assert!(!roles::has_role(&e, Role::Risk, &frodo));
+        assert!(roles::has_role(&e, Role::Pauser, &frodo));
+
+        roles::revoke_role(&e, Role::Risk, &samwise);
+        assert!(!roles::has_role(&e, Role::Risk, &samwise));
+        assert!(roles::has_role(&e, Role::Pauser, &frodo));
+    });
+}
@@ -0,0 +1,34 @@
+use soroban_sdk::{contracttype, Address, Symbol};
+
+/// An asset priced by an oracle, as defined by SEP-40
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    /// A Stellar asset, by its contract address
+    Stellar(Address),
+    /// Any other asset, by its identifier
+    Other(Symbol),
+}
+
+/// The price of an asset at a point in time, as defined by SEP-40
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    /// The price, with the oracle's decimals
+    pub price: i128,
+    /// The time the price was recorded
+    pub timestamp: u64,
+}
+
+/// The price of an asset with the oracle's confidence in it. Kept separate from `PriceData` so
+/// SEP-40 `lastprice` stays compatible.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceDataV2 {
+    /// The price, with the oracle's decimals
+    pub price: i128,
+    /// The time the price was recorded
+    pub timestamp: u64,
+    /// The uncertainty of the price in either direction, with the oracle's decimals
+    pub confidence: i128,
+}
@@ -0,0 +1,41 @@
+use crate::registry::{self, ErrorSource};
+use soroban_sdk::{contract, contractclient, contractimpl, Env, Symbol};
+
+/// ### ErrorRegistry
+///
+/// A stateless registry mapping TrustBridge contract error codes to their names, so
+/// generic frontends and explorers can display readable failures.
+#[contract]
+pub struct ErrorRegistryContract;
+
+#[contractclient(name = "ErrorRegistryClient")]
+pub trait ErrorRegistry {
+    /// Fetch the name of an error code raised by a pool, backstop, pool factory, or claim aggregator
+    ///
+    /// Returns None if the code is not a known error
+    ///
+    /// ### Arguments
+    /// * `code` - The numeric error code
+    fn error_name(e: Env, code: u32) -> Option<Symbol>;
+
+    /// Fetch the name of an error code raised by a specific contract. Required for the oracle,
+    /// whose error codes overlap with the common errors of the other contracts.
+    ///
+    /// Returns None if the code is not a known error for the contract
+    ///
+    /// ### Arguments
+    /// * `source` - The contract that raised the error
+    /// * `code` - The numeric error code
+    fn source_error_name(e: Env, source: ErrorSource, code: u32) -> Option<Symbol>;
+}
+
+#[contractimpl]
+impl ErrorRegistry for ErrorRegistryContract {
+    fn error_name(e: Env, code: u32) -> Option<Symbol> {
+        registry::error_name(code).map(|name| Symbol::new(&e, name))
+    }
+
+    fn source_error_name(e: Env, source: ErrorSource, code: u32) -> Option<Symbol> {
+        registry::error_name_for(source, code).map(|name| Symbol::new(&e, name))
+    }
+}
@@ -0,0 +1,11 @@
+#![no_std]
+
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod contract;
+mod registry;
+mod test;
+
+pub use contract::*;
+pub use registry::ErrorSource;
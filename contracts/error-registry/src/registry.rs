@@ -0,0 +1,302 @@
+use soroban_sdk::contracttype;
+
+/// Generate a lookup function mapping the numeric codes of a contract error enum to the
+/// name of the variant. The body is written exactly like the `#[contracterror]` enum it
+/// mirrors, and `test_registry_matches_error_enums` checks each table against the enum's source.
+macro_rules! error_names {
+    ($fn_name:ident { $($name:ident = $code:literal,)* }) => {
+        pub fn $fn_name(code: u32) -> Option<&'static str> {
+            match code {
+                $($code => Some(stringify!($name)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+/// The contract that raised an error
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ErrorSource {
+    Pool = 0,
+    Backstop = 1,
+    PoolFactory = 2,
+    Oracle = 3,
+    ClaimAggregator = 4,
+    Multicall = 5,
+    Bootstrapper = 6,
+    AccountCloser = 7,
+    KeeperRegistry = 8,
+    AttestationAdapter = 9,
+    LiquidityBootstrapping = 10,
+    Lens = 11,
+    LiquidityManager = 12,
+}
+
+// mirrors `pool::PoolError`
+error_names!(pool_error_name {
+    // Common Errors
+    InternalError = 1,
+    AlreadyInitializedError = 3,
+    UnauthorizedError = 4,
+    NegativeAmountError = 8,
+    BalanceError = 10,
+    OverflowError = 12,
+
+    // Pool Request Errors (start at 1200)
+    BadRequest = 1200,
+    InvalidPoolConfigArgs = 1201,
+    InvalidReserveMetadata = 1202,
+    InitNotUnlocked = 1203,
+    StatusNotAllowed = 1204,
+
+    // Pool State Errors
+    InvalidHf = 1205,
+    InvalidPoolStatus = 1206,
+    InvalidUtilRate = 1207,
+    MaxPositionsExceeded = 1208,
+    InternalReserveNotFound = 1209,
+
+    // Oracle Errors
+    InvalidPrice = 1210,
+
+    // Auction Errors
+    InvalidLiquidation = 1211,
+    AuctionInProgress = 1212,
+    InvalidLiqTooLarge = 1213,
+    InvalidLiqTooSmall = 1214,
+    InterestTooSmall = 1215,
+
+    // Share Token Errors
+    InvalidBTokenMintAmount = 1216,
+    InvalidBTokenBurnAmount = 1217,
+    InvalidDTokenMintAmount = 1218,
+    InvalidDTokenBurnAmount = 1219,
+
+    // v2 Errors
+    ExceededSupplyCap = 1220,
+    InvalidBid = 1221,
+    InvalidLot = 1222,
+    ReserveDisabled = 1223,
+    MinCollateralNotMet = 1224,
+
+    // TrustBridge Errors
+    AuctionFillTooEarly = 1225,
+    AssetNotAllowed = 1226,
+    InvalidSoftLiquidation = 1227,
+    AllowanceExpired = 1228,
+    InvalidRedemption = 1230,
+    LiquidatorNotAllowed = 1231,
+    BorrowFrozen = 1232,
+    SupplyOnlyAccount = 1233,
+    TooManyRequests = 1234,
+    TooManyAuctionRequests = 1235,
+    InsufficientFlashFillProceeds = 1236,
+    InvalidReserveIndex = 1237,
+    OracleBaseMismatch = 1238,
+    InvalidScaledBid = 1240,
+    InvalidScaledLot = 1241,
+    CircularBackstopCollateral = 1242,
+    WithdrawCooldown = 1243,
+});
+
+// mirrors `backstop::BackstopError`
+error_names!(backstop_error_name {
+    // Common Errors
+    InternalError = 1,
+    AlreadyInitializedError = 3,
+    UnauthorizedError = 4,
+    NegativeAmountError = 8,
+    BalanceError = 10,
+    OverflowError = 12,
+
+    // Backstop
+    BadRequest = 1000,
+    NotExpired = 1001,
+    InvalidRewardZoneEntry = 1002,
+    InsufficientFunds = 1003,
+    NotPool = 1004,
+    InvalidShareMintAmount = 1005,
+    InvalidTokenWithdrawAmount = 1006,
+    TooManyQ4WEntries = 1007,
+    NotInRewardZone = 1008,
+    RewardZoneFull = 1009,
+    MaxBackfillEmissions = 1010,
+    BadDebtExists = 1011,
+    InvalidPrice = 1012,
+    SharesLocked = 1013,
+});
+
+// mirrors `pool_factory::PoolFactoryError`
+error_names!(pool_factory_error_name {
+    // Common Errors
+    InternalError = 1,
+    AlreadyInitializedError = 3,
+
+    // Pool Factory
+    InvalidPoolInitArgs = 1300,
+    InvalidDeployConfig = 1301,
+});
+
+// mirrors `claim_aggregator::ClaimAggregatorError`
+error_names!(claim_aggregator_error_name {
+    // Common Errors
+    InternalError = 1,
+
+    // Claim Aggregator
+    BadRequest = 1400,
+    InvalidSwap = 1401,
+});
+
+// mirrors `multicall::MulticallError`
+error_names!(multicall_error_name {
+    // Common Errors
+    InternalError = 1,
+
+    // Multicall
+    BadRequest = 1500,
+    TooManyCalls = 1501,
+});
+
+// mirrors `bootstrapper::BootstrapperError`
+error_names!(bootstrapper_error_name {
+    // Common Errors
+    InternalError = 1,
+
+    // Bootstrapper
+    BadRequest = 1600,
+});
+
+// mirrors `account_closer::AccountCloserError`
+error_names!(account_closer_error_name {
+    // Common Errors
+    InternalError = 1,
+
+    // Account Closer
+    BadRequest = 1700,
+});
+
+// mirrors `keeper_registry::KeeperRegistryError`
+error_names!(keeper_registry_error_name {
+    // Common Errors
+    InternalError = 1,
+
+    // Keeper Registry
+    BadRequest = 1800,
+    JobNotFound = 1801,
+    JobNotReady = 1802,
+    InsufficientBudget = 1803,
+    TooManyJobs = 1804,
+});
+
+// mirrors `attestation_adapter::AttestationAdapterError`
+error_names!(attestation_adapter_error_name {
+    // Common Errors
+    InternalError = 1,
+
+    // Attestation Adapter
+    BadRequest = 1900,
+    UnknownSource = 1901,
+    MessageConsumed = 1902,
+    InvalidAttestation = 1903,
+    InsufficientLocked = 1904,
+});
+
+// mirrors `liquidity_bootstrapping::LiquidityBootstrappingError`
+error_names!(liquidity_bootstrapping_error_name {
+    // Common Errors
+    InternalError = 1,
+
+    // Liquidity Bootstrapping
+    BadRequest = 2000,
+    LbpNotFound = 2001,
+    NotPool = 2002,
+    NotActive = 2003,
+    NotEnded = 2004,
+    InsufficientOutput = 2005,
+});
+
+// mirrors `lens::LensError`
+error_names!(lens_error_name {
+    // Common Errors
+    InternalError = 1,
+
+    // Lens
+    BadRequest = 2100,
+});
+
+// mirrors `liquidity_manager::LiquidityManagerError`
+error_names!(liquidity_manager_error_name {
+    // Common Errors
+    InternalError = 1,
+
+    // Liquidity Manager
+    BadRequest = 2200,
+    NotPool = 2201,
+    PoolNotManaged = 2202,
+    TooManyPools = 2203,
+    ReserveNotFound = 2204,
+    NotAuthorized = 2205,
+});
+
+// mirrors `trustbridge_oracle::OracleError`
+error_names!(oracle_error_name {
+    AlreadyInitialized = 1,
+    Unauthorized = 2,
+    InvalidPrice = 3,
+    InvalidInput = 4,
+    PriceNotFound = 5,
+    NotInitialized = 6,
+    FeederNotSet = 7,
+    StalePrice = 8,
+    Paused = 9,
+    HeartbeatLapsed = 10,
+});
+
+/// Fetch the name of an error code raised by the given contract
+///
+/// ### Arguments
+/// * `source` - The contract that raised the error
+/// * `code` - The numeric error code
+pub fn error_name_for(source: ErrorSource, code: u32) -> Option<&'static str> {
+    match source {
+        ErrorSource::Pool => pool_error_name(code),
+        ErrorSource::Backstop => backstop_error_name(code),
+        ErrorSource::PoolFactory => pool_factory_error_name(code),
+        ErrorSource::Oracle => oracle_error_name(code),
+        ErrorSource::ClaimAggregator => claim_aggregator_error_name(code),
+        ErrorSource::Multicall => multicall_error_name(code),
+        ErrorSource::Bootstrapper => bootstrapper_error_name(code),
+        ErrorSource::AccountCloser => account_closer_error_name(code),
+        ErrorSource::KeeperRegistry => keeper_registry_error_name(code),
+        ErrorSource::AttestationAdapter => attestation_adapter_error_name(code),
+        ErrorSource::LiquidityBootstrapping => liquidity_bootstrapping_error_name(code),
+        ErrorSource::Lens => lens_error_name(code),
+        ErrorSource::LiquidityManager => liquidity_manager_error_name(code),
+    }
+}
+
+/// Fetch the name of an error code raised by a pool, backstop, pool factory, claim aggregator,
+/// multicall, bootstrapper, account closer, keeper registry, attestation adapter, liquidity
+/// bootstrapping contract, lens, or liquidity manager. The contract specific ranges (backstop
+/// 1000+, pool 1200+, pool factory 1300+, claim aggregator 1400+, multicall 1500+, bootstrapper
+/// 1600+, account closer 1700+, keeper registry 1800+, attestation adapter 1900+, liquidity
+/// bootstrapping 2000+, lens 2100+, liquidity manager 2200+) do not overlap, and the common errors
+/// share the same name across all of them.
+///
+/// ### Arguments
+/// * `code` - The numeric error code
+pub fn error_name(code: u32) -> Option<&'static str> {
+    pool_error_name(code)
+        .or_else(|| backstop_error_name(code))
+        .or_else(|| pool_factory_error_name(code))
+        .or_else(|| claim_aggregator_error_name(code))
+        .or_else(|| multicall_error_name(code))
+        .or_else(|| bootstrapper_error_name(code))
+        .or_else(|| account_closer_error_name(code))
+        .or_else(|| keeper_registry_error_name(code))
+        .or_else(|| attestation_adapter_error_name(code))
+        .or_else(|| liquidity_bootstrapping_error_name(code))
+        .or_else(|| lens_error_name(code))
+        .or_else(|| liquidity_manager_error_name(code))
+}
@@ -0,0 +1,207 @@
+#![cfg(test)]
+
+use soroban_sdk::{Env, Symbol};
+use std::{string::String, vec::Vec};
+
+use crate::{registry::error_name_for, ErrorRegistryClient, ErrorRegistryContract, ErrorSource};
+
+/// Parse the `Name = code,` variants of the error enum declared in a contract's error source
+fn parse_error_enum(source: &str) -> Vec<(String, u32)> {
+    let start = source.find("pub enum").unwrap();
+    let body_start = start + source[start..].find('{').unwrap() + 1;
+    let body_end = start + source[start..].find("\n}").unwrap();
+    source[body_start..body_end]
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .map(|line| {
+            let (name, code) = line.trim_end_matches(',').split_once(" = ").unwrap();
+            (String::from(name), code.parse().unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn test_error_name() {
+    let e = Env::default();
+    let registry_address = e.register(ErrorRegistryContract {}, ());
+    let registry_client = ErrorRegistryClient::new(&e, &registry_address);
+
+    // common
+    assert_eq!(
+        registry_client.error_name(&1),
+        Some(Symbol::new(&e, "InternalError"))
+    );
+    // backstop
+    assert_eq!(
+        registry_client.error_name(&1007),
+        Some(Symbol::new(&e, "TooManyQ4WEntries"))
+    );
+    // pool
+    assert_eq!(
+        registry_client.error_name(&1224),
+        Some(Symbol::new(&e, "MinCollateralNotMet"))
+    );
+    // pool factory
+    assert_eq!(
+        registry_client.error_name(&1300),
+        Some(Symbol::new(&e, "InvalidPoolInitArgs"))
+    );
+    // claim aggregator
+    assert_eq!(
+        registry_client.error_name(&1401),
+        Some(Symbol::new(&e, "InvalidSwap"))
+    );
+    // multicall
+    assert_eq!(
+        registry_client.error_name(&1501),
+        Some(Symbol::new(&e, "TooManyCalls"))
+    );
+    // bootstrapper
+    assert_eq!(
+        registry_client.error_name(&1600),
+        Some(Symbol::new(&e, "BadRequest"))
+    );
+    // account closer
+    assert_eq!(
+        registry_client.error_name(&1700),
+        Some(Symbol::new(&e, "BadRequest"))
+    );
+    // keeper registry
+    assert_eq!(
+        registry_client.error_name(&1802),
+        Some(Symbol::new(&e, "JobNotReady"))
+    );
+    // attestation adapter
+    assert_eq!(
+        registry_client.error_name(&1902),
+        Some(Symbol::new(&e, "MessageConsumed"))
+    );
+    // liquidity bootstrapping
+    assert_eq!(
+        registry_client.error_name(&2003),
+        Some(Symbol::new(&e, "NotActive"))
+    );
+    // lens
+    assert_eq!(
+        registry_client.error_name(&2100),
+        Some(Symbol::new(&e, "BadRequest"))
+    );
+    // liquidity manager
+    assert_eq!(
+        registry_client.error_name(&2202),
+        Some(Symbol::new(&e, "PoolNotManaged"))
+    );
+
+    assert_eq!(registry_client.error_name(&2), None);
+    assert_eq!(registry_client.error_name(&1100), None);
+}
+
+#[test]
+fn test_source_error_name() {
+    let e = Env::default();
+    let registry_address = e.register(ErrorRegistryContract {}, ());
+    let registry_client = ErrorRegistryClient::new(&e, &registry_address);
+
+    assert_eq!(
+        registry_client.source_error_name(&ErrorSource::Oracle, &2),
+        Some(Symbol::new(&e, "Unauthorized"))
+    );
+    assert_eq!(
+        registry_client.source_error_name(&ErrorSource::Oracle, &8),
+        Some(Symbol::new(&e, "StalePrice"))
+    );
+    assert_eq!(
+        registry_client.source_error_name(&ErrorSource::Pool, &4),
+        Some(Symbol::new(&e, "UnauthorizedError"))
+    );
+    assert_eq!(
+        registry_client.source_error_name(&ErrorSource::Backstop, &1011),
+        Some(Symbol::new(&e, "BadDebtExists"))
+    );
+    assert_eq!(
+        registry_client.source_error_name(&ErrorSource::Backstop, &1012),
+        Some(Symbol::new(&e, "InvalidPrice"))
+    );
+
+    // codes are scoped to the source contract
+    assert_eq!(
+        registry_client.source_error_name(&ErrorSource::PoolFactory, &1200),
+        None
+    );
+    assert_eq!(
+        registry_client.source_error_name(&ErrorSource::Oracle, &12),
+        None
+    );
+}
+
+#[test]
+fn test_registry_matches_error_enums() {
+    let sources = [
+        (ErrorSource::Pool, include_str!("../../pool/src/errors.rs")),
+        (
+            ErrorSource::Backstop,
+            include_str!("../../backstop/src/errors.rs"),
+        ),
+        (
+            ErrorSource::PoolFactory,
+            include_str!("../../pool-factory/src/errors.rs"),
+        ),
+        (
+            ErrorSource::Oracle,
+            include_str!("../../oracle/src/error.rs"),
+        ),
+        (
+            ErrorSource::ClaimAggregator,
+            include_str!("../../claim-aggregator/src/errors.rs"),
+        ),
+        (
+            ErrorSource::Multicall,
+            include_str!("../../multicall/src/errors.rs"),
+        ),
+        (
+            ErrorSource::Bootstrapper,
+            include_str!("../../bootstrapper/src/errors.rs"),
+        ),
+        (
+            ErrorSource::AccountCloser,
+            include_str!("../../account-closer/src/errors.rs"),
+        ),
+        (
+            ErrorSource::KeeperRegistry,
+            include_str!("../../keeper-registry/src/errors.rs"),
+        ),
+        (
+            ErrorSource::AttestationAdapter,
+            include_str!("../../attestation-adapter/src/errors.rs"),
+        ),
+        (
+            ErrorSource::LiquidityBootstrapping,
+            include_str!("../../liquidity-bootstrapping/src/errors.rs"),
+        ),
+        (ErrorSource::Lens, include_str!("../../lens/src/errors.rs")),
+        (
+            ErrorSource::LiquidityManager,
+            include_str!("../../liquidity-manager/src/errors.rs"),
+        ),
+    ];
+
+    for (source, enum_source) in sources {
+        let variants = parse_error_enum(enum_source);
+        assert!(!variants.is_empty());
+        for (name, code) in variants.iter() {
+            assert_eq!(
+                error_name_for(source, *code),
+                Some(name.as_str()),
+                "{:?} code {}",
+                source,
+                code
+            );
+        }
+        // the registry has no codes the enum does not
+        let registered = (0..10_000)
+            .filter(|code| error_name_for(source, *code).is_some())
+            .count();
+        assert_eq!(registered, variants.len(), "{:?}", source);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    errors::KeeperRegistryError,
+    events::KeeperRegistryEvents,
+    storage::{self, Job},
+};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, panic_with_error, token::TokenClient, Address, Env,
+    Symbol, Val, Vec,
+};
+
+/// The maximum number of jobs that can be registered at once
+const MAX_JOBS: u32 = 50;
+
+/// ### KeeperRegistry
+///
+/// Enumerates the permissionless maintenance jobs across TrustBridge (e.g. `gulp`,
+/// `gulp_emissions`, `update_status`, `bad_debt`) so keeper networks can discover and price them
+/// on-chain. Each job has a per-run reward paid out of a budget funded by the treasury.
+#[contract]
+pub struct KeeperRegistryContract;
+
+#[contractclient(name = "KeeperRegistryClient")]
+pub trait KeeperRegistry {
+    /// (Admin only) Register a job that keepers are rewarded for running
+    ///
+    /// Returns the id of the job
+    ///
+    /// ### Arguments
+    /// * `contract` - The contract the job is run against
+    /// * `fn_name` - The permissionless function run by the job
+    /// * `args` - The arguments the job's function is invoked with
+    /// * `reward` - The amount of the reward token paid to the keeper per run
+    /// * `min_interval` - The minimum number of seconds between runs
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the reward or interval is not positive, the job targets the
+    /// registry or the reward token, or the maximum number of jobs are registered
+    fn add_job(
+        e: Env,
+        contract: Address,
+        fn_name: Symbol,
+        args: Vec<Val>,
+        reward: i128,
+        min_interval: u64,
+    ) -> u32;
+
+    /// (Admin only) Remove a job. Any unused budget is returned to the treasury.
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the job
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the job does not exist
+    fn remove_job(e: Env, id: u32);
+
+    /// (Treasury only) Add to a job's reward budget
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the job
+    /// * `amount` - The amount of the reward token to transfer from the treasury
+    ///
+    /// ### Panics
+    /// If the caller is not the treasury, the amount is not positive, or the job does not exist
+    fn fund_job(e: Env, id: u32, amount: i128);
+
+    /// Run a job with the arguments it was registered with and pay its reward to `keeper`
+    ///
+    /// Returns the result of the job's invocation
+    ///
+    /// ### Arguments
+    /// * `keeper` - The address running the job
+    /// * `id` - The id of the job
+    ///
+    /// ### Panics
+    /// If the job does not exist, ran less than `min_interval` seconds ago, cannot cover its
+    /// reward, or its invocation fails
+    fn work(e: Env, keeper: Address, id: u32) -> Val;
+
+    /// Fetch a job
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the job
+    ///
+    /// ### Panics
+    /// If the job does not exist
+    fn get_job(e: Env, id: u32) -> Job;
+
+    /// Fetch all registered jobs
+    fn get_jobs(e: Env) -> Vec<Job>;
+}
+
+#[contractimpl]
+impl KeeperRegistryContract {
+    /// Construct the keeper registry contract
+    ///
+    /// ### Arguments
+    /// * `admin` - The address that manages the registered jobs
+    /// * `treasury` - The address that funds job rewards
+    /// * `reward_token` - The token job rewards are paid in
+    pub fn __constructor(e: Env, admin: Address, treasury: Address, reward_token: Address) {
+        storage::set_admin(&e, &admin);
+        storage::set_treasury(&e, &treasury);
+        storage::set_reward_token(&e, &reward_token);
+    }
+}
+
+#[contractimpl]
+impl KeeperRegistry for KeeperRegistryContract {
+    fn add_job(
+        e: Env,
+        contract: Address,
+        fn_name: Symbol,
+        args: Vec<Val>,
+        reward: i128,
+        min_interval: u64,
+    ) -> u32 {
+        storage::extend_instance(&e);
+        storage::get_admin(&e).require_auth();
+
+        if reward <= 0
+            || min_interval == 0
+            || contract == e.current_contract_address()
+            || contract == storage::get_reward_token(&e)
+        {
+            panic_with_error!(&e, KeeperRegistryError::BadRequest);
+        }
+        let mut job_ids = storage::get_job_ids(&e);
+        if job_ids.len() >= MAX_JOBS {
+            panic_with_error!(&e, KeeperRegistryError::TooManyJobs);
+        }
+
+        let job = Job {
+            id: storage::next_job_id(&e),
+            contract,
+            fn_name,
+            args,
+            reward,
+            budget: 0,
+            min_interval,
+            last_run: 0,
+        };
+        storage::set_job(&e, &job);
+        job_ids.push_back(job.id);
+        storage::set_job_ids(&e, &job_ids);
+
+        KeeperRegistryEvents::add_job(&e, job.clone());
+        job.id
+    }
+
+    fn remove_job(e: Env, id: u32) {
+        storage::extend_instance(&e);
+        storage::get_admin(&e).require_auth();
+
+        let job = load_job(&e, id);
+        if job.budget > 0 {
+            TokenClient::new(&e, &storage::get_reward_token(&e)).transfer(
+                &e.current_contract_address(),
+                &storage::get_treasury(&e),
+                &job.budget,
+            );
+        }
+        storage::del_job(&e, id);
+        let mut job_ids = storage::get_job_ids(&e);
+        if let Some(index) = job_ids.first_index_of(id) {
+            job_ids.remove(index);
+        }
+        storage::set_job_ids(&e, &job_ids);
+
+        KeeperRegistryEvents::remove_job(&e, id, job.budget);
+    }
+
+    fn fund_job(e: Env, id: u32, amount: i128) {
+        storage::extend_instance(&e);
+        let treasury = storage::get_treasury(&e);
+        treasury.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&e, KeeperRegistryError::BadRequest);
+        }
+        let mut job = load_job(&e, id);
+        TokenClient::new(&e, &storage::get_reward_token(&e)).transfer(
+            &treasury,
+            &e.current_contract_address(),
+            &amount,
+        );
+        job.budget += amount;
+        storage::set_job(&e, &job);
+
+        KeeperRegistryEvents::fund_job(&e, id, amount, job.budget);
+    }
+
+    fn work(e: Env, keeper: Address, id: u32) -> Val {
+        storage::extend_instance(&e);
+        keeper.require_auth();
+
+        let mut job = load_job(&e, id);
+        if job.last_run != 0 && e.ledger().timestamp() < job.last_run + job.min_interval {
+            panic_with_error!(&e, KeeperRegistryError::JobNotReady);
+        }
+        if job.budget < job.reward {
+            panic_with_error!(&e, KeeperRegistryError::InsufficientBudget);
+        }
+
+        let result: Val = e.invoke_contract(&job.contract, &job.fn_name, job.args.clone());
+
+        job.last_run = e.ledger().timestamp();
+        job.budget -= job.reward;
+        storage::set_job(&e, &job);
+        TokenClient::new(&e, &storage::get_reward_token(&e)).transfer(
+            &e.current_contract_address(),
+            &keeper,
+            &job.reward,
+        );
+
+        KeeperRegistryEvents::work(&e, id, keeper, job.reward);
+        result
+    }
+
+    fn get_job(e: Env, id: u32) -> Job {
+        load_job(&e, id)
+    }
+
+    fn get_jobs(e: Env) -> Vec<Job> {
+        let mut jobs = Vec::new(&e);
+        for id in storage::get_job_ids(&e).iter() {
+            jobs.push_back(load_job(&e, id));
+        }
+        jobs
+    }
+}
+
+/// Load a job, panicking if it does not exist
+fn load_job(e: &Env, id: u32) -> Job {
+    match storage::get_job(e, id) {
+        Some(job) => job,
+        None => panic_with_error!(e, KeeperRegistryError::JobNotFound),
+    }
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the keeper registry contract. Common errors are codes that match up with the
+/// built-in contracts error reporting. Keeper registry specific errors start at 1800.
+pub enum KeeperRegistryError {
+    // Common Errors
+    InternalError = 1,
+
+    // Keeper Registry
+    BadRequest = 1800,
+    JobNotFound = 1801,
+    JobNotReady = 1802,
+    InsufficientBudget = 1803,
+    TooManyJobs = 1804,
+}
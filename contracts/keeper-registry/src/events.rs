@@ -0,0 +1,60 @@
+use soroban_sdk::{Address, Env, Symbol};
+
+use crate::Job;
+
+pub struct KeeperRegistryEvents {}
+
+impl KeeperRegistryEvents {
+    /// Emitted when a job is registered
+    ///
+    /// - topics - `["add_job", id: u32]`
+    /// - data - `job: Job`
+    ///
+    /// ### Arguments
+    /// * `job` - The registered job
+    pub fn add_job(e: &Env, job: Job) {
+        let topics = (Symbol::new(e, "add_job"), job.id);
+        e.events().publish(topics, job);
+    }
+
+    /// Emitted when a job is removed
+    ///
+    /// - topics - `["remove_job", id: u32]`
+    /// - data - `refund: i128`
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the job
+    /// * `refund` - The unused budget returned to the treasury
+    pub fn remove_job(e: &Env, id: u32, refund: i128) {
+        let topics = (Symbol::new(e, "remove_job"), id);
+        e.events().publish(topics, refund);
+    }
+
+    /// Emitted when the treasury funds a job's budget
+    ///
+    /// - topics - `["fund_job", id: u32]`
+    /// - data - `[amount: i128, budget: i128]`
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the job
+    /// * `amount` - The amount added to the budget
+    /// * `budget` - The job's budget after funding
+    pub fn fund_job(e: &Env, id: u32, amount: i128, budget: i128) {
+        let topics = (Symbol::new(e, "fund_job"), id);
+        e.events().publish(topics, (amount, budget));
+    }
+
+    /// Emitted when a keeper runs a job
+    ///
+    /// - topics - `["work", id: u32, keeper: Address]`
+    /// - data - `reward: i128`
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the job
+    /// * `keeper` - The keeper that ran the job
+    /// * `reward` - The reward paid to the keeper
+    pub fn work(e: &Env, id: u32, keeper: Address, reward: i128) {
+        let topics = (Symbol::new(e, "work"), id, keeper);
+        e.events().publish(topics, reward);
+    }
+}
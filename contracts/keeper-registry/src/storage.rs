@@ -0,0 +1,170 @@
+use soroban_sdk::{contracttype, unwrap::UnwrapOptimized, Address, Env, Symbol, Val, Vec};
+
+/********** Ledger Thresholds **********/
+
+const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
+
+const LEDGER_THRESHOLD_INSTANCE: u32 = ONE_DAY_LEDGERS * 30; // ~ 30 days
+const LEDGER_BUMP_INSTANCE: u32 = LEDGER_THRESHOLD_INSTANCE + ONE_DAY_LEDGERS; // ~ 31 days
+
+const LEDGER_THRESHOLD_JOB: u32 = ONE_DAY_LEDGERS * 45; // ~ 45 days
+const LEDGER_BUMP_JOB: u32 = LEDGER_THRESHOLD_JOB + 20 * ONE_DAY_LEDGERS; // ~ 65 days
+
+/********** Storage Types **********/
+
+/// A permissionless maintenance job and the reward paid to keepers for running it
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Job {
+    pub id: u32,
+    pub contract: Address, // the contract the job is run against
+    pub fn_name: Symbol,   // the permissionless function run by the job (e.g. "gulp", "bad_debt")
+    pub args: Vec<Val>,    // the arguments the job's function is invoked with
+    pub reward: i128,      // the amount of the reward token paid to the keeper per run
+    pub budget: i128,      // the amount of the reward token left to pay out for the job
+    pub min_interval: u64, // the minimum number of seconds between runs
+    pub last_run: u64,     // the timestamp of the last run
+}
+
+/********** Storage Keys **********/
+
+const ADMIN_KEY: &str = "Admin";
+const TREASURY_KEY: &str = "Treasury";
+const REWARD_TOKEN_KEY: &str = "RewardTkn";
+const JOB_IDS_KEY: &str = "JobIds";
+const NEXT_JOB_ID_KEY: &str = "NextJobId";
+
+#[derive(Clone)]
+#[contracttype]
+pub enum KeeperDataKey {
+    // A registered job
+    Job(u32),
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_INSTANCE, LEDGER_BUMP_INSTANCE);
+}
+
+/********** Instance **********/
+
+/// Fetch the admin address
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, ADMIN_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the admin address
+///
+/// ### Arguments
+/// * `admin` - The address of the admin
+pub fn set_admin(e: &Env, admin: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, ADMIN_KEY), admin);
+}
+
+/// Fetch the treasury address
+pub fn get_treasury(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, TREASURY_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the treasury address
+///
+/// ### Arguments
+/// * `treasury` - The address of the treasury that funds job rewards
+pub fn set_treasury(e: &Env, treasury: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, TREASURY_KEY), treasury);
+}
+
+/// Fetch the reward token address
+pub fn get_reward_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, REWARD_TOKEN_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the reward token address
+///
+/// ### Arguments
+/// * `reward_token` - The address of the token job rewards are paid in
+pub fn set_reward_token(e: &Env, reward_token: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, REWARD_TOKEN_KEY), reward_token);
+}
+
+/// Fetch the ids of all registered jobs
+pub fn get_job_ids(e: &Env) -> Vec<u32> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Vec<u32>>(&Symbol::new(e, JOB_IDS_KEY))
+        .unwrap_or(Vec::new(e))
+}
+
+/// Set the ids of all registered jobs
+///
+/// ### Arguments
+/// * `job_ids` - The ids of all registered jobs
+pub fn set_job_ids(e: &Env, job_ids: &Vec<u32>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Vec<u32>>(&Symbol::new(e, JOB_IDS_KEY), job_ids);
+}
+
+/// Fetch the id assigned to the next registered job and increment it
+pub fn next_job_id(e: &Env) -> u32 {
+    let key = Symbol::new(e, NEXT_JOB_ID_KEY);
+    let id = e.storage().instance().get::<Symbol, u32>(&key).unwrap_or(0);
+    e.storage().instance().set::<Symbol, u32>(&key, &(id + 1));
+    id
+}
+
+/********** Persistent **********/
+
+/// Fetch a registered job
+///
+/// ### Arguments
+/// * `id` - The id of the job
+pub fn get_job(e: &Env, id: u32) -> Option<Job> {
+    let key = KeeperDataKey::Job(id);
+    let result = e.storage().persistent().get::<KeeperDataKey, Job>(&key);
+    if result.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_JOB, LEDGER_BUMP_JOB);
+    }
+    result
+}
+
+/// Set a registered job
+///
+/// ### Arguments
+/// * `job` - The job
+pub fn set_job(e: &Env, job: &Job) {
+    let key = KeeperDataKey::Job(job.id);
+    e.storage()
+        .persistent()
+        .set::<KeeperDataKey, Job>(&key, job);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_JOB, LEDGER_BUMP_JOB);
+}
+
+/// Remove a registered job
+///
+/// ### Arguments
+/// * `id` - The id of the job
+pub fn del_job(e: &Env, id: u32) {
+    e.storage().persistent().remove(&KeeperDataKey::Job(id));
+}
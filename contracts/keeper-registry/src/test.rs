@@ -0,0 +1,216 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+};
+
+use crate::{Job, KeeperRegistryClient, KeeperRegistryContract};
+
+/********** Mocks **********/
+
+/// Counts the number of times a reserve is gulped
+#[contract]
+pub struct MockPool;
+
+#[contractimpl]
+impl MockPool {
+    pub fn gulp(e: Env, asset: Address) -> u32 {
+        let count: u32 = e.storage().instance().get(&asset).unwrap_or(0) + 1;
+        e.storage().instance().set(&asset, &count);
+        count
+    }
+}
+
+fn create_registry<'a>(
+    e: &Env,
+    admin: &Address,
+    treasury: &Address,
+) -> (Address, KeeperRegistryClient<'a>, Address) {
+    let reward_token = e
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(e, &reward_token).mint(treasury, &1000_0000000);
+    let registry = e.register(
+        KeeperRegistryContract {},
+        (admin.clone(), treasury.clone(), reward_token.clone()),
+    );
+    (
+        registry.clone(),
+        KeeperRegistryClient::new(e, &registry),
+        reward_token,
+    )
+}
+
+#[test]
+fn test_work() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_timestamp(1000);
+
+    let bombadil = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let (registry, registry_client, reward_token) = create_registry(&e, &bombadil, &treasury);
+    let token_client = TokenClient::new(&e, &reward_token);
+    let pool = e.register(MockPool {}, ());
+
+    let args: Vec<Val> = vec![&e, asset.into_val(&e)];
+    let id = registry_client.add_job(&pool, &Symbol::new(&e, "gulp"), &args, &1_0000000, &3600);
+    registry_client.fund_job(&id, &10_0000000);
+    assert_eq!(token_client.balance(&treasury), 990_0000000);
+    assert_eq!(token_client.balance(&registry), 10_0000000);
+
+    let result = registry_client.work(&samwise, &id);
+    assert_eq!(u32::try_from_val(&e, &result).unwrap(), 1);
+    assert_eq!(
+        e.events().all().last_unchecked(),
+        (
+            registry.clone(),
+            (Symbol::new(&e, "work"), id, samwise.clone()).into_val(&e),
+            1_0000000i128.into_val(&e),
+        )
+    );
+    assert_eq!(token_client.balance(&samwise), 1_0000000);
+
+    let job = registry_client.get_job(&id);
+    assert_eq!(
+        job,
+        Job {
+            id,
+            contract: pool.clone(),
+            fn_name: Symbol::new(&e, "gulp"),
+            args: args.clone(),
+            reward: 1_0000000,
+            budget: 9_0000000,
+            min_interval: 3600,
+            last_run: 1000,
+        }
+    );
+
+    // the job can run again once the interval has passed
+    e.ledger().set_timestamp(1000 + 3600);
+    registry_client.work(&samwise, &id);
+    assert_eq!(token_client.balance(&samwise), 2_0000000);
+    assert_eq!(registry_client.get_job(&id).budget, 8_0000000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1802)")]
+fn test_work_before_interval() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_timestamp(1000);
+
+    let bombadil = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let (_, registry_client, _) = create_registry(&e, &bombadil, &treasury);
+    let pool = e.register(MockPool {}, ());
+
+    let args: Vec<Val> = vec![&e, Address::generate(&e).into_val(&e)];
+    let id = registry_client.add_job(&pool, &Symbol::new(&e, "gulp"), &args, &1_0000000, &3600);
+    registry_client.fund_job(&id, &10_0000000);
+
+    registry_client.work(&samwise, &id);
+    e.ledger().set_timestamp(1000 + 3599);
+    registry_client.work(&samwise, &id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1803)")]
+fn test_work_insufficient_budget() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let samwise = Address::generate(&e);
+    let (_, registry_client, _) = create_registry(&e, &bombadil, &treasury);
+    let pool = e.register(MockPool {}, ());
+
+    let args: Vec<Val> = vec![&e, Address::generate(&e).into_val(&e)];
+    let id = registry_client.add_job(&pool, &Symbol::new(&e, "gulp"), &args, &1_0000000, &3600);
+    registry_client.fund_job(&id, &0_5000000);
+
+    registry_client.work(&samwise, &id);
+}
+
+#[test]
+fn test_remove_job_refunds_treasury() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let (registry, registry_client, reward_token) = create_registry(&e, &bombadil, &treasury);
+    let token_client = TokenClient::new(&e, &reward_token);
+    let pool = e.register(MockPool {}, ());
+
+    let args: Vec<Val> = vec![&e, Address::generate(&e).into_val(&e)];
+    let id_0 = registry_client.add_job(&pool, &Symbol::new(&e, "gulp"), &args, &1_0000000, &3600);
+    let id_1 = registry_client.add_job(
+        &pool,
+        &Symbol::new(&e, "bad_debt"),
+        &args,
+        &5_0000000,
+        &3600,
+    );
+    registry_client.fund_job(&id_0, &10_0000000);
+    assert_eq!(registry_client.get_jobs().len(), 2);
+
+    registry_client.remove_job(&id_0);
+    assert_eq!(
+        e.events().all().last_unchecked(),
+        (
+            registry.clone(),
+            (Symbol::new(&e, "remove_job"), id_0).into_val(&e),
+            10_0000000i128.into_val(&e),
+        )
+    );
+    assert_eq!(token_client.balance(&treasury), 1000_0000000);
+    assert_eq!(token_client.balance(&registry), 0);
+    let jobs = registry_client.get_jobs();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs.get_unchecked(0).id, id_1);
+
+    let result = registry_client.try_get_job(&id_0);
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1800)")]
+fn test_add_job_reward_token() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let (_, registry_client, reward_token) = create_registry(&e, &bombadil, &treasury);
+
+    registry_client.add_job(
+        &reward_token,
+        &Symbol::new(&e, "transfer"),
+        &vec![&e],
+        &1_0000000,
+        &3600,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1800)")]
+fn test_add_job_zero_interval() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let (_, registry_client, _) = create_registry(&e, &bombadil, &treasury);
+    let pool = e.register(MockPool {}, ());
+
+    let args: Vec<Val> = vec![&e, Address::generate(&e).into_val(&e)];
+    registry_client.add_job(&pool, &Symbol::new(&e, "gulp"), &args, &1_0000000, &0);
+}
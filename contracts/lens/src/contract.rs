@@ -0,0 +1,109 @@
+use crate::{
+    dependencies::{Asset, BackstopClient, OracleClient, PoolClient},
+    errors::LensError,
+    storage::{self, PoolDashboard, ReserveView, UserDashboard},
+};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, panic_with_error, Address, Env, Map, Vec,
+};
+
+/// The maximum number of pools a dashboard can be loaded for in one call
+const MAX_DASHBOARD_POOLS: u32 = 10;
+
+/// ### Lens
+///
+/// Read-only views composing the pool, backstop, and oracle views a frontend needs, so a user's
+/// dashboard can be loaded in a single simulation instead of many sequential ones.
+#[contract]
+pub struct LensContract;
+
+#[contractclient(name = "LensClient")]
+pub trait Lens {
+    /// Fetch a user's dashboard for a set of pools. For each pool, this includes the pool's
+    /// reserves with their oracle prices, the user's positions and accrued emissions, and the
+    /// user's backstop shares and claimable backstop emissions.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `pools` - The pools to load, which must use the lens's backstop
+    ///
+    /// ### Panics
+    /// If there are no pools or more than 10 pools
+    fn get_user_dashboard(e: Env, user: Address, pools: Vec<Address>) -> UserDashboard;
+}
+
+#[contractimpl]
+impl LensContract {
+    /// Construct the lens contract
+    ///
+    /// ### Arguments
+    /// * `backstop` - The backstop of the pools viewed through the lens
+    pub fn __constructor(e: Env, backstop: Address) {
+        storage::set_backstop(&e, &backstop);
+    }
+}
+
+#[contractimpl]
+impl Lens for LensContract {
+    fn get_user_dashboard(e: Env, user: Address, pools: Vec<Address>) -> UserDashboard {
+        if pools.is_empty() || pools.len() > MAX_DASHBOARD_POOLS {
+            panic_with_error!(&e, LensError::BadRequest);
+        }
+        let backstop_client = BackstopClient::new(&e, &storage::get_backstop(&e));
+        let mut dashboards = Vec::new(&e);
+        for pool in pools.iter() {
+            dashboards.push_back(load_pool_dashboard(&e, &backstop_client, &user, &pool));
+        }
+        UserDashboard {
+            user,
+            timestamp: e.ledger().timestamp(),
+            pools: dashboards,
+        }
+    }
+}
+
+/// Load a user's view of a pool
+fn load_pool_dashboard(
+    e: &Env,
+    backstop_client: &BackstopClient,
+    user: &Address,
+    pool: &Address,
+) -> PoolDashboard {
+    let pool_client = PoolClient::new(e, pool);
+    let config = pool_client.get_config();
+    let oracle_client = OracleClient::new(e, &config.oracle);
+
+    let snapshot = pool_client.state_snapshot();
+    let mut reserves = Vec::new(e);
+    for (asset, data) in snapshot.reserves.iter().zip(snapshot.reserve_data.iter()) {
+        let price = oracle_client.lastprice(&Asset::Stellar(asset.clone()));
+        reserves.push_back(ReserveView { asset, data, price });
+    }
+
+    // reserve token ids are reserve_index * 2 for dTokens and reserve_index * 2 + 1 for bTokens
+    let positions = pool_client.get_positions(user);
+    let mut emissions = Map::new(e);
+    let liability_ids = positions.liabilities.keys().iter().map(|index| index * 2);
+    let supply_ids = positions
+        .collateral
+        .keys()
+        .iter()
+        .chain(positions.supply.keys().iter())
+        .map(|index| index * 2 + 1);
+    for reserve_token_id in liability_ids.chain(supply_ids) {
+        if let Some(user_emissions) = pool_client.get_user_emissions(user, &reserve_token_id) {
+            emissions.set(reserve_token_id, user_emissions.accrued);
+        }
+    }
+
+    PoolDashboard {
+        pool: pool.clone(),
+        status: config.status,
+        price_decimals: oracle_client.decimals(),
+        reserves,
+        positions,
+        emissions,
+        backstop: backstop_client.user_balance(pool, user),
+        backstop_emissions: backstop_client.preview_backstop_claim(user, pool),
+    }
+}
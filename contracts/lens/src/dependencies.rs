@@ -0,0 +1,125 @@
+/**
+ * Partial clients and types for the contracts the lens views
+ */
+use soroban_sdk::{contractclient, contracttype, Address, Env, Map, Symbol, Vec};
+
+/// A pool's configuration
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct PoolConfig {
+    pub oracle: Address,
+    pub min_collateral: i128,
+    pub bstop_rate: u32,
+    pub status: u32,
+    pub max_positions: u32,
+}
+
+/// The stored data of a reserve
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveData {
+    pub d_rate: i128,
+    pub b_rate: i128,
+    pub ir_mod: i128,
+    pub b_supply: i128,
+    pub d_supply: i128,
+    pub backstop_credit: i128,
+    pub last_time: u64,
+}
+
+/// A snapshot of a pool's reserves
+#[derive(Clone)]
+#[contracttype]
+pub struct StateSnapshot {
+    pub pool: Address,
+    pub ledger: u32,
+    pub timestamp: u64,
+    pub reserves: Vec<Address>,
+    pub reserve_data: Vec<ReserveData>,
+}
+
+/// A user's positions in a pool, keyed by reserve index
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Positions {
+    pub liabilities: Map<u32, i128>,
+    pub collateral: Map<u32, i128>,
+    pub supply: Map<u32, i128>,
+}
+
+/// A user's emission data for a reserve token
+#[derive(Clone)]
+#[contracttype]
+pub struct UserEmissionData {
+    pub index: i128,
+    pub accrued: i128,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolClient")]
+pub trait Pool {
+    /// Fetch the pool configuration
+    fn get_config(e: Env) -> PoolConfig;
+
+    /// Fetch a snapshot of the pool's reserves, ordered by reserve index
+    fn state_snapshot(e: Env) -> StateSnapshot;
+
+    /// Fetch the positions for an address
+    fn get_positions(e: Env, address: Address) -> Positions;
+
+    /// Fetch the emission data of a user for a reserve token
+    fn get_user_emissions(e: Env, user: Address, reserve_token_id: u32)
+        -> Option<UserEmissionData>;
+}
+
+/// An asset priced by an oracle
+#[derive(Clone)]
+#[contracttype]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// The price of an asset at a point in time
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "OracleClient")]
+pub trait Oracle {
+    /// Fetch the number of decimals prices are reported in
+    fn decimals(e: Env) -> u32;
+
+    /// Fetch the most recent price of an asset, if any
+    fn lastprice(e: Env, asset: Asset) -> Option<PriceData>;
+}
+
+/// A withdrawal of backstop shares queued by a user
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Q4W {
+    pub amount: i128,
+    pub exp: u64,
+}
+
+/// A user's backstop shares in a pool
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct UserBalance {
+    pub shares: i128,
+    pub q4w: Vec<Q4W>,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "BackstopClient")]
+pub trait Backstop {
+    /// Fetch a user's backstop shares in a pool
+    fn user_balance(e: Env, pool: Address, user: Address) -> UserBalance;
+
+    /// Fetch the backstop emissions a user can claim from a pool
+    fn preview_backstop_claim(e: Env, user: Address, pool: Address) -> i128;
+}
@@ -0,0 +1,14 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the lens contract. Common errors are codes that match up with the built-in
+/// contracts error reporting. Lens specific errors start at 2100.
+pub enum LensError {
+    // Common Errors
+    InternalError = 1,
+
+    // Lens
+    BadRequest = 2100,
+}
@@ -0,0 +1,15 @@
+#![no_std]
+
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod contract;
+mod dependencies;
+mod errors;
+mod storage;
+mod test;
+
+pub use contract::*;
+pub use dependencies::{Positions, PriceData, ReserveData, UserBalance, Q4W};
+pub use errors::LensError;
+pub use storage::{PoolDashboard, ReserveView, UserDashboard};
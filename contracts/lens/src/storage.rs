@@ -0,0 +1,61 @@
+use soroban_sdk::{contracttype, unwrap::UnwrapOptimized, Address, Env, Map, Symbol, Vec};
+
+use crate::dependencies::{Positions, PriceData, ReserveData, UserBalance};
+
+/********** Storage Types **********/
+
+/// A reserve of a pool, with the oracle price of its underlying
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveView {
+    pub asset: Address,           // the underlying asset of the reserve
+    pub data: ReserveData,        // the stored data of the reserve
+    pub price: Option<PriceData>, // the most recent oracle price of the underlying, if any
+}
+
+/// A user's view of a pool
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolDashboard {
+    pub pool: Address,              // the address of the pool
+    pub status: u32,                // the status of the pool
+    pub price_decimals: u32,        // the decimals of the pool oracle's prices
+    pub reserves: Vec<ReserveView>, // the reserves of the pool, ordered by index
+    pub positions: Positions,       // the user's positions in the pool
+    pub emissions: Map<u32, i128>,  // the emissions accrued by the user for each reserve token id
+    pub backstop: UserBalance,      // the user's backstop shares in the pool
+    pub backstop_emissions: i128,   // the backstop emissions the user can claim from the pool
+}
+
+/// A user's view of a set of pools
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct UserDashboard {
+    pub user: Address,             // the address of the user
+    pub timestamp: u64,            // the ledger timestamp the dashboard was loaded at
+    pub pools: Vec<PoolDashboard>, // the user's view of each pool
+}
+
+/********** Storage Keys **********/
+
+const BACKSTOP_KEY: &str = "Backstop";
+
+/********** Backstop **********/
+
+/// Fetch the backstop of the pools viewed through the lens
+pub fn get_backstop(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, BACKSTOP_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the backstop of the pools viewed through the lens
+///
+/// ### Arguments
+/// * `backstop` - The address of the backstop
+pub fn set_backstop(e: &Env, backstop: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, BACKSTOP_KEY), backstop);
+}
@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl, map,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, Symbol, Vec,
+};
+
+use crate::{
+    dependencies::{Asset, PoolConfig, StateSnapshot, UserEmissionData},
+    LensClient, LensContract, Positions, PriceData, ReserveData, UserBalance, Q4W,
+};
+
+/********** Mocks **********/
+
+/// A pool with a single reserve, where every user holds collateral and liabilities in it
+#[contract]
+pub struct MockPool;
+
+#[contractimpl]
+impl MockPool {
+    pub fn set_data(e: Env, oracle: Address, asset: Address) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "oracle"), &oracle);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "asset"), &asset);
+    }
+
+    pub fn get_config(e: Env) -> PoolConfig {
+        PoolConfig {
+            oracle: e
+                .storage()
+                .instance()
+                .get(&Symbol::new(&e, "oracle"))
+                .unwrap(),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 4,
+        }
+    }
+
+    pub fn state_snapshot(e: Env) -> StateSnapshot {
+        let asset: Address = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "asset"))
+            .unwrap();
+        StateSnapshot {
+            pool: e.current_contract_address(),
+            ledger: e.ledger().sequence(),
+            timestamp: e.ledger().timestamp(),
+            reserves: vec![&e, asset],
+            reserve_data: vec![&e, reserve_data()],
+        }
+    }
+
+    pub fn get_positions(e: Env, _address: Address) -> Positions {
+        Positions {
+            liabilities: map![&e, (0, 50_0000000)],
+            collateral: map![&e, (0, 100_0000000)],
+            supply: map![&e],
+        }
+    }
+
+    pub fn get_user_emissions(
+        _e: Env,
+        _user: Address,
+        reserve_token_id: u32,
+    ) -> Option<UserEmissionData> {
+        if reserve_token_id == 1 {
+            Some(UserEmissionData {
+                index: 0,
+                accrued: 5_0000000,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// An oracle with a fixed price for every Stellar asset
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn decimals(_e: Env) -> u32 {
+        7
+    }
+
+    pub fn lastprice(e: Env, asset: Asset) -> Option<PriceData> {
+        match asset {
+            Asset::Stellar(_) => Some(PriceData {
+                price: 1_2000000,
+                timestamp: e.ledger().timestamp(),
+            }),
+            Asset::Other(_) => None,
+        }
+    }
+}
+
+/// A backstop where every user holds the same shares
+#[contract]
+pub struct MockBackstop;
+
+#[contractimpl]
+impl MockBackstop {
+    pub fn user_balance(e: Env, _pool: Address, _user: Address) -> UserBalance {
+        UserBalance {
+            shares: 10_0000000,
+            q4w: vec![
+                &e,
+                Q4W {
+                    amount: 1_0000000,
+                    exp: 2000,
+                },
+            ],
+        }
+    }
+
+    pub fn preview_backstop_claim(_e: Env, _user: Address, _pool: Address) -> i128 {
+        3_0000000
+    }
+}
+
+fn reserve_data() -> ReserveData {
+    ReserveData {
+        d_rate: 1_000_000_000_000,
+        b_rate: 1_000_000_000_000,
+        ir_mod: 1_0000000,
+        b_supply: 100_0000000,
+        d_supply: 75_0000000,
+        backstop_credit: 0,
+        last_time: 0,
+    }
+}
+
+fn create_lens<'a>(e: &Env) -> (LensClient<'a>, Address, Address) {
+    let backstop = e.register(MockBackstop {}, ());
+    let oracle = e.register(MockOracle {}, ());
+    let asset = Address::generate(e);
+    let pool = e.register(MockPool {}, ());
+    MockPoolClient::new(e, &pool).set_data(&oracle, &asset);
+    let lens = e.register(LensContract {}, (backstop,));
+    (LensClient::new(e, &lens), pool, asset)
+}
+
+#[test]
+fn test_get_user_dashboard() {
+    let e = Env::default();
+    e.ledger().set_timestamp(1000);
+    let samwise = Address::generate(&e);
+    let (lens_client, pool, asset) = create_lens(&e);
+
+    let dashboard = lens_client.get_user_dashboard(&samwise, &vec![&e, pool.clone()]);
+    assert_eq!(dashboard.user, samwise);
+    assert_eq!(dashboard.timestamp, 1000);
+    assert_eq!(dashboard.pools.len(), 1);
+
+    let pool_dashboard = dashboard.pools.get_unchecked(0);
+    assert_eq!(pool_dashboard.pool, pool);
+    assert_eq!(pool_dashboard.status, 0);
+    assert_eq!(pool_dashboard.price_decimals, 7);
+    assert_eq!(pool_dashboard.reserves.len(), 1);
+    let reserve = pool_dashboard.reserves.get_unchecked(0);
+    assert_eq!(reserve.asset, asset);
+    assert_eq!(reserve.data, reserve_data());
+    assert_eq!(reserve.price.unwrap().price, 1_2000000);
+    assert_eq!(
+        pool_dashboard.positions.collateral.get_unchecked(0),
+        100_0000000
+    );
+    assert_eq!(pool_dashboard.emissions, map![&e, (1, 5_0000000)]);
+    assert_eq!(pool_dashboard.backstop.shares, 10_0000000);
+    assert_eq!(pool_dashboard.backstop_emissions, 3_0000000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2100)")]
+fn test_get_user_dashboard_too_many_pools() {
+    let e = Env::default();
+    let samwise = Address::generate(&e);
+    let (lens_client, pool, _) = create_lens(&e);
+
+    let mut pools: Vec<Address> = vec![&e];
+    for _ in 0..11 {
+        pools.push_back(pool.clone());
+    }
+    lens_client.get_user_dashboard(&samwise, &pools);
+}
@@ -0,0 +1,345 @@
+use crate::{
+    dependencies::{BackstopClient, PoolFactoryClient},
+    errors::LiquidityBootstrappingError,
+    events::LiquidityBootstrappingEvents,
+    storage::{self, Lbp, LbpConfig, PoolLaunch},
+};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractclient, contractimpl, panic_with_error,
+    token::TokenClient,
+    vec, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+
+/// Fixed-point scalar for 7 decimal numbers
+const SCALAR_7: i128 = 1_0000000;
+
+/// ### LiquidityBootstrapping
+///
+/// Runs liquidity bootstrapping auctions (LBPs) to fairly distribute the initial supply of a
+/// token, like TBRG or the seed reserves of a pool launched by the pool factory. The sale token
+/// is priced like a weighted pool whose sale token weight declines over the auction, so the price
+/// falls until buyers step in, and large buys are not rewarded over smaller ones. The proceeds
+/// are split between the treasury and the launched pool's backstop.
+#[contract]
+pub struct LiquidityBootstrappingContract;
+
+#[contractclient(name = "LiquidityBootstrappingClient")]
+pub trait LiquidityBootstrapping {
+    /// Create an auction and transfer the sale tokens from the owner
+    ///
+    /// Returns the id of the auction
+    ///
+    /// ### Arguments
+    /// * `config` - The configuration of the auction
+    ///
+    /// ### Panics
+    /// * If the pool was not deployed by the pool factory
+    /// * If the amounts are not positive, the sale token weight does not decline, the auction
+    ///   window is invalid, or the backstop split is over 100%
+    /// * If part of the proceeds go to the backstop but the raise token is not the backstop token
+    fn create(e: Env, config: LbpConfig) -> u32;
+
+    /// Deploy a pool through the pool factory with the owner as its admin, and create an auction
+    /// launching it. The `pool` of the config is replaced with the deployed pool.
+    ///
+    /// Returns the address of the pool and the id of the auction
+    ///
+    /// ### Arguments
+    /// * `launch` - The arguments to deploy the pool with
+    /// * `config` - The configuration of the auction
+    ///
+    /// ### Panics
+    /// * If the pool factory fails to deploy the pool
+    /// * If the auction config is invalid, as in `create`
+    fn launch(e: Env, launch: PoolLaunch, config: LbpConfig) -> (Address, u32);
+
+    /// Buy the sale token at the auction's current price
+    ///
+    /// Returns the amount of the sale token bought
+    ///
+    /// ### Arguments
+    /// * `buyer` - The address buying the sale token
+    /// * `id` - The id of the auction
+    /// * `amount_in` - The amount of the raise token to pay
+    /// * `min_amount_out` - The minimum amount of the sale token to receive
+    ///
+    /// ### Panics
+    /// If the auction is not open, or less than `min_amount_out` of the sale token is bought
+    fn buy(e: Env, buyer: Address, id: u32, amount_in: i128, min_amount_out: i128) -> i128;
+
+    /// Distribute the proceeds of an auction once it closes. The backstop split of
+    /// the proceeds is deposited in the pool's backstop as protocol owned first-loss capital, the
+    /// rest is sent to the treasury, and any unsold tokens are returned to the owner.
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the auction
+    ///
+    /// ### Panics
+    /// If the auction is still open or has already been finalized
+    fn finalize(e: Env, id: u32);
+
+    /// Fetch the amount of the sale token `amount_in` of the raise token would buy now
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the auction
+    /// * `amount_in` - The amount of the raise token
+    fn quote(e: Env, id: u32, amount_in: i128) -> i128;
+
+    /// Fetch the current price of the sale token in the raise token (7 decimals)
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the auction
+    fn spot_price(e: Env, id: u32) -> i128;
+
+    /// Fetch an auction
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the auction
+    fn get_lbp(e: Env, id: u32) -> Lbp;
+}
+
+#[contractimpl]
+impl LiquidityBootstrappingContract {
+    /// Construct the liquidity bootstrapping contract
+    ///
+    /// ### Arguments
+    /// * `pool_factory` - The pool factory that deploys the pools auctions launch
+    /// * `backstop` - The backstop of the pools deployed by the pool factory
+    pub fn __constructor(e: Env, pool_factory: Address, backstop: Address) {
+        storage::set_pool_factory(&e, &pool_factory);
+        storage::set_backstop(&e, &backstop);
+    }
+}
+
+#[contractimpl]
+impl LiquidityBootstrapping for LiquidityBootstrappingContract {
+    fn create(e: Env, config: LbpConfig) -> u32 {
+        storage::extend_instance(&e);
+        config.owner.require_auth();
+
+        create_lbp(&e, config)
+    }
+
+    fn launch(e: Env, launch: PoolLaunch, mut config: LbpConfig) -> (Address, u32) {
+        storage::extend_instance(&e);
+        config.owner.require_auth();
+
+        config.pool = PoolFactoryClient::new(&e, &storage::get_pool_factory(&e)).deploy(
+            &config.owner,
+            &launch.name,
+            &launch.salt,
+            &launch.oracle,
+            &launch.backstop_take_rate,
+            &launch.max_positions,
+            &launch.min_collateral,
+        );
+        let pool = config.pool.clone();
+        (pool, create_lbp(&e, config))
+    }
+
+    fn buy(e: Env, buyer: Address, id: u32, amount_in: i128, min_amount_out: i128) -> i128 {
+        storage::extend_instance(&e);
+        buyer.require_auth();
+
+        let mut lbp = load_lbp(&e, id);
+        let now = e.ledger().timestamp();
+        if lbp.finalized || now < lbp.config.start || now >= lbp.config.end {
+            panic_with_error!(&e, LiquidityBootstrappingError::NotActive);
+        }
+        if amount_in <= 0 {
+            panic_with_error!(&e, LiquidityBootstrappingError::BadRequest);
+        }
+        let amount_out = quote_lbp(&e, &lbp, amount_in);
+        if amount_out <= 0 || amount_out < min_amount_out {
+            panic_with_error!(&e, LiquidityBootstrappingError::InsufficientOutput);
+        }
+
+        TokenClient::new(&e, &lbp.config.raise_token).transfer(
+            &buyer,
+            &e.current_contract_address(),
+            &amount_in,
+        );
+        TokenClient::new(&e, &lbp.config.sale_token).transfer(
+            &e.current_contract_address(),
+            &buyer,
+            &amount_out,
+        );
+        lbp.raised += amount_in;
+        lbp.sold += amount_out;
+        storage::set_lbp(&e, &lbp);
+
+        LiquidityBootstrappingEvents::buy(&e, id, buyer, amount_in, amount_out);
+        amount_out
+    }
+
+    fn finalize(e: Env, id: u32) {
+        storage::extend_instance(&e);
+
+        let mut lbp = load_lbp(&e, id);
+        if lbp.finalized {
+            panic_with_error!(&e, LiquidityBootstrappingError::BadRequest);
+        }
+        if e.ledger().timestamp() < lbp.config.end {
+            panic_with_error!(&e, LiquidityBootstrappingError::NotEnded);
+        }
+        lbp.finalized = true;
+        storage::set_lbp(&e, &lbp);
+
+        let to_backstop = lbp.raised * lbp.config.backstop_split as i128 / SCALAR_7;
+        let to_treasury = lbp.raised - to_backstop;
+        let unsold = lbp.config.sale_amount - lbp.sold;
+        if to_backstop > 0 {
+            deposit_to_backstop(&e, &lbp, to_backstop);
+        }
+        if to_treasury > 0 {
+            TokenClient::new(&e, &lbp.config.raise_token).transfer(
+                &e.current_contract_address(),
+                &lbp.config.treasury,
+                &to_treasury,
+            );
+        }
+        if unsold > 0 {
+            TokenClient::new(&e, &lbp.config.sale_token).transfer(
+                &e.current_contract_address(),
+                &lbp.config.owner,
+                &unsold,
+            );
+        }
+
+        LiquidityBootstrappingEvents::finalize(&e, id, to_treasury, to_backstop, unsold);
+    }
+
+    fn quote(e: Env, id: u32, amount_in: i128) -> i128 {
+        if amount_in <= 0 {
+            return 0;
+        }
+        quote_lbp(&e, &load_lbp(&e, id), amount_in)
+    }
+
+    fn spot_price(e: Env, id: u32) -> i128 {
+        let lbp = load_lbp(&e, id);
+        let sale_balance = lbp.config.sale_amount - lbp.sold;
+        if sale_balance <= 0 {
+            return 0;
+        }
+        let raise_balance = lbp.config.virtual_raise + lbp.raised;
+        weighted_raise_balance(&e, &lbp, raise_balance) * SCALAR_7 / sale_balance
+    }
+
+    fn get_lbp(e: Env, id: u32) -> Lbp {
+        load_lbp(&e, id)
+    }
+}
+
+/// Validate an auction config, transfer the sale tokens from the owner, and store the auction
+///
+/// Returns the id of the auction
+fn create_lbp(e: &Env, config: LbpConfig) -> u32 {
+    if config.sale_amount <= 0
+        || config.virtual_raise <= 0
+        || config.end_weight == 0
+        || config.end_weight >= config.start_weight
+        || config.start_weight as i128 >= SCALAR_7
+        || config.start < e.ledger().timestamp()
+        || config.end <= config.start
+        || config.backstop_split as i128 > SCALAR_7
+        || config.sale_token == config.raise_token
+    {
+        panic_with_error!(e, LiquidityBootstrappingError::BadRequest);
+    }
+    if !PoolFactoryClient::new(e, &storage::get_pool_factory(e)).is_pool(&config.pool) {
+        panic_with_error!(e, LiquidityBootstrappingError::NotPool);
+    }
+    if config.backstop_split > 0
+        && BackstopClient::new(e, &storage::get_backstop(e)).backstop_token() != config.raise_token
+    {
+        panic_with_error!(e, LiquidityBootstrappingError::BadRequest);
+    }
+
+    TokenClient::new(e, &config.sale_token).transfer(
+        &config.owner,
+        &e.current_contract_address(),
+        &config.sale_amount,
+    );
+    let lbp = Lbp {
+        id: storage::next_lbp_id(e),
+        config,
+        sold: 0,
+        raised: 0,
+        finalized: false,
+    };
+    storage::set_lbp(e, &lbp);
+
+    LiquidityBootstrappingEvents::create(e, lbp.id, lbp.config);
+    lbp.id
+}
+
+/// Deposit part of an auction's proceeds into its pool's backstop. The backstop transfers the
+/// raise token from this contract in a nested call, so the transfer is authorized first.
+fn deposit_to_backstop(e: &Env, lbp: &Lbp, amount: i128) {
+    let backstop = storage::get_backstop(e);
+    let args: Vec<Val> = vec![
+        e,
+        e.current_contract_address().into_val(e),
+        backstop.into_val(e),
+        amount.into_val(e),
+    ];
+    e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: lbp.config.raise_token.clone(),
+                fn_name: Symbol::new(e, "transfer"),
+                args,
+            },
+            sub_invocations: vec![e],
+        }),
+    ]);
+    BackstopClient::new(e, &backstop).deposit(
+        &e.current_contract_address(),
+        &lbp.config.pool,
+        &amount,
+    );
+}
+
+/// Load an auction, panicking if it does not exist
+fn load_lbp(e: &Env, id: u32) -> Lbp {
+    match storage::get_lbp(e, id) {
+        Some(lbp) => lbp,
+        None => panic_with_error!(e, LiquidityBootstrappingError::LbpNotFound),
+    }
+}
+
+/// Fetch the weight of the sale token at the current ledger. The weight declines linearly from
+/// the start weight to the end weight over the auction.
+fn sale_weight(e: &Env, lbp: &Lbp) -> i128 {
+    let now = e
+        .ledger()
+        .timestamp()
+        .clamp(lbp.config.start, lbp.config.end);
+    let elapsed = (now - lbp.config.start) as i128;
+    let duration = (lbp.config.end - lbp.config.start) as i128;
+    let start_weight = lbp.config.start_weight as i128;
+    let end_weight = lbp.config.end_weight as i128;
+    start_weight - (start_weight - end_weight) * elapsed / duration
+}
+
+/// Fetch the raise token balance weighted by the current weights, such that the spot price of
+/// the sale token is the weighted balance over the unsold sale tokens
+///
+/// ### Arguments
+/// * `raise_balance` - The virtual raise token balance plus the amount raised
+fn weighted_raise_balance(e: &Env, lbp: &Lbp, raise_balance: i128) -> i128 {
+    let weight = sale_weight(e, lbp);
+    raise_balance * weight / (SCALAR_7 - weight)
+}
+
+/// Quote the amount of the sale token `amount_in` of the raise token buys. A buy is priced at the
+/// spot price the auction would have after it, so splitting a buy up never costs more than
+/// making it at once, and a buy can never take all of the unsold tokens.
+fn quote_lbp(e: &Env, lbp: &Lbp, amount_in: i128) -> i128 {
+    let sale_balance = lbp.config.sale_amount - lbp.sold;
+    let raise_balance = lbp.config.virtual_raise + lbp.raised + amount_in;
+    sale_balance * amount_in / (amount_in + weighted_raise_balance(e, lbp, raise_balance))
+}
@@ -0,0 +1,33 @@
+/**
+ * Partial clients for the pool factory and backstop contracts
+ */
+use soroban_sdk::{contractclient, Address, BytesN, Env, String};
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolFactoryClient")]
+pub trait PoolFactory {
+    /// Deploys and initializes a lending pool
+    fn deploy(
+        e: Env,
+        admin: Address,
+        name: String,
+        salt: BytesN<32>,
+        oracle: Address,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+    ) -> Address;
+
+    /// Checks if contract address was deployed by the factory
+    fn is_pool(e: Env, pool_id: Address) -> bool;
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "BackstopClient")]
+pub trait Backstop {
+    /// Deposit backstop tokens from `from` into the backstop of a pool
+    fn deposit(e: Env, from: Address, pool_address: Address, amount: i128) -> i128;
+
+    /// Fetch the backstop token for the backstop
+    fn backstop_token(e: Env) -> Address;
+}
@@ -0,0 +1,20 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the liquidity bootstrapping contract. Common errors are codes that match up
+/// with the built-in contracts error reporting. Liquidity bootstrapping specific errors start at
+/// 2000.
+pub enum LiquidityBootstrappingError {
+    // Common Errors
+    InternalError = 1,
+
+    // Liquidity Bootstrapping
+    BadRequest = 2000,
+    LbpNotFound = 2001,
+    NotPool = 2002,
+    NotActive = 2003,
+    NotEnded = 2004,
+    InsufficientOutput = 2005,
+}
@@ -0,0 +1,51 @@
+use soroban_sdk::{Address, Env, Symbol};
+
+use crate::LbpConfig;
+
+pub struct LiquidityBootstrappingEvents {}
+
+impl LiquidityBootstrappingEvents {
+    /// Emitted when an auction is created
+    ///
+    /// - topics - `["create", id: u32, pool: Address]`
+    /// - data - `config: LbpConfig`
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the auction
+    /// * `config` - The configuration of the auction
+    pub fn create(e: &Env, id: u32, config: LbpConfig) {
+        let topics = (Symbol::new(e, "create"), id, config.pool.clone());
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when the sale token is bought
+    ///
+    /// - topics - `["buy", id: u32, buyer: Address]`
+    /// - data - `[amount_in: i128, amount_out: i128]`
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the auction
+    /// * `buyer` - The address buying the sale token
+    /// * `amount_in` - The amount of the raise token paid
+    /// * `amount_out` - The amount of the sale token bought
+    pub fn buy(e: &Env, id: u32, buyer: Address, amount_in: i128, amount_out: i128) {
+        let topics = (Symbol::new(e, "buy"), id, buyer);
+        e.events().publish(topics, (amount_in, amount_out));
+    }
+
+    /// Emitted when an auction is finalized
+    ///
+    /// - topics - `["finalize", id: u32]`
+    /// - data - `[to_treasury: i128, to_backstop: i128, unsold: i128]`
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the auction
+    /// * `to_treasury` - The amount of the raise token sent to the treasury
+    /// * `to_backstop` - The amount of the raise token deposited in the pool's backstop
+    /// * `unsold` - The amount of the sale token returned to the owner
+    pub fn finalize(e: &Env, id: u32, to_treasury: i128, to_backstop: i128, unsold: i128) {
+        let topics = (Symbol::new(e, "finalize"), id);
+        e.events()
+            .publish(topics, (to_treasury, to_backstop, unsold));
+    }
+}
@@ -0,0 +1,149 @@
+use soroban_sdk::{contracttype, unwrap::UnwrapOptimized, Address, BytesN, Env, String, Symbol};
+
+/********** Ledger Thresholds **********/
+
+const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
+
+const LEDGER_THRESHOLD_INSTANCE: u32 = ONE_DAY_LEDGERS * 30; // ~ 30 days
+const LEDGER_BUMP_INSTANCE: u32 = LEDGER_THRESHOLD_INSTANCE + ONE_DAY_LEDGERS; // ~ 31 days
+
+const LEDGER_THRESHOLD_LBP: u32 = ONE_DAY_LEDGERS * 45; // ~ 45 days
+const LEDGER_BUMP_LBP: u32 = LEDGER_THRESHOLD_LBP + 20 * ONE_DAY_LEDGERS; // ~ 65 days
+
+/********** Storage Types **********/
+
+/// The configuration of a liquidity bootstrapping auction
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LbpConfig {
+    pub owner: Address, // the seller, who funds the sale and receives unsold tokens
+    pub pool: Address,  // the pool being launched, which must be deployed by the factory
+    pub sale_token: Address, // the token being distributed (e.g. TBRG or a seed reserve)
+    pub raise_token: Address, // the token buyers pay with
+    pub sale_amount: i128, // the amount of the sale token for sale
+    pub virtual_raise: i128, // the virtual balance of the raise token that sets the opening price
+    pub start_weight: u32, // the weight of the sale token at the start (7 decimals)
+    pub end_weight: u32, // the weight of the sale token at the end (7 decimals)
+    pub start: u64,     // the time the auction opens
+    pub end: u64,       // the time the auction closes
+    pub treasury: Address, // the address the proceeds not sent to the backstop are sent to
+    pub backstop_split: u32, // the share of the proceeds sent to the pool's backstop (7 decimals)
+}
+
+/// The arguments to deploy the pool an auction launches through the pool factory
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolLaunch {
+    pub name: String,            // the name of the pool
+    pub salt: BytesN<32>,        // the salt for the pool address
+    pub oracle: Address,         // the oracle of the pool
+    pub backstop_take_rate: u32, // the backstop take rate of the pool (7 decimals)
+    pub max_positions: u32,      // the maximum positions a user can hold in the pool
+    pub min_collateral: i128,    // the minimum collateral for a borrow position (oracle decimals)
+}
+
+/// A liquidity bootstrapping auction and its progress
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Lbp {
+    pub id: u32,
+    pub config: LbpConfig,
+    pub sold: i128,      // the amount of the sale token sold
+    pub raised: i128,    // the amount of the raise token paid by buyers
+    pub finalized: bool, // if the proceeds and unsold tokens have been distributed
+}
+
+/********** Storage Keys **********/
+
+const POOL_FACTORY_KEY: &str = "PoolFact";
+const BACKSTOP_KEY: &str = "Backstop";
+const NEXT_LBP_ID_KEY: &str = "NextLbpId";
+
+#[derive(Clone)]
+#[contracttype]
+pub enum LbpDataKey {
+    // A liquidity bootstrapping auction
+    Lbp(u32),
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_INSTANCE, LEDGER_BUMP_INSTANCE);
+}
+
+/********** Instance **********/
+
+/// Fetch the pool factory address
+pub fn get_pool_factory(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, POOL_FACTORY_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the pool factory address
+///
+/// ### Arguments
+/// * `pool_factory` - The address of the pool factory
+pub fn set_pool_factory(e: &Env, pool_factory: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, POOL_FACTORY_KEY), pool_factory);
+}
+
+/// Fetch the backstop address
+pub fn get_backstop(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, BACKSTOP_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the backstop address
+///
+/// ### Arguments
+/// * `backstop` - The address of the backstop
+pub fn set_backstop(e: &Env, backstop: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, BACKSTOP_KEY), backstop);
+}
+
+/// Fetch the id assigned to the next auction and increment it
+pub fn next_lbp_id(e: &Env) -> u32 {
+    let key = Symbol::new(e, NEXT_LBP_ID_KEY);
+    let id = e.storage().instance().get::<Symbol, u32>(&key).unwrap_or(0);
+    e.storage().instance().set::<Symbol, u32>(&key, &(id + 1));
+    id
+}
+
+/********** Persistent **********/
+
+/// Fetch an auction
+///
+/// ### Arguments
+/// * `id` - The id of the auction
+pub fn get_lbp(e: &Env, id: u32) -> Option<Lbp> {
+    let key = LbpDataKey::Lbp(id);
+    let result = e.storage().persistent().get::<LbpDataKey, Lbp>(&key);
+    if result.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_LBP, LEDGER_BUMP_LBP);
+    }
+    result
+}
+
+/// Set an auction
+///
+/// ### Arguments
+/// * `lbp` - The auction
+pub fn set_lbp(e: &Env, lbp: &Lbp) {
+    let key = LbpDataKey::Lbp(lbp.id);
+    e.storage().persistent().set::<LbpDataKey, Lbp>(&key, lbp);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_LBP, LEDGER_BUMP_LBP);
+}
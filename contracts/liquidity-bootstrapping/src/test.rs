@@ -0,0 +1,269 @@
+#![cfg(test)]
+
+use backstop::{BackstopClient, BackstopContract};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, BytesN as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, String, Symbol, Vec,
+};
+
+use crate::{LbpConfig, LiquidityBootstrappingClient, LiquidityBootstrappingContract, PoolLaunch};
+
+/********** Mocks **********/
+
+/// Reports a preset pool as deployed by the factory, and "deploys" it for its admin
+#[contract]
+pub struct MockPoolFactory;
+
+#[contractimpl]
+impl MockPoolFactory {
+    pub fn set_pool(e: Env, pool: Address) {
+        e.storage().instance().set(&Symbol::new(&e, "pool"), &pool);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn deploy(
+        e: Env,
+        admin: Address,
+        _name: String,
+        _salt: BytesN<32>,
+        _oracle: Address,
+        _backstop_take_rate: u32,
+        _max_positions: u32,
+        _min_collateral: i128,
+    ) -> Address {
+        admin.require_auth();
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "pool"))
+            .unwrap()
+    }
+
+    pub fn is_pool(e: Env, pool_id: Address) -> bool {
+        e.storage()
+            .instance()
+            .get::<Symbol, Address>(&Symbol::new(&e, "pool"))
+            == Some(pool_id)
+    }
+}
+
+struct Setup<'a> {
+    client: LiquidityBootstrappingClient<'a>,
+    backstop: BackstopClient<'a>,
+    sale_token: TokenClient<'a>,
+    raise_token: TokenClient<'a>,
+    config: LbpConfig,
+}
+
+/// Setup an auction of 1000 sale tokens opening at a price of 0.9, with the sale token weight
+/// declining from 90% to 50% over a day
+fn setup(e: &Env) -> Setup<'_> {
+    e.mock_all_auths();
+    e.ledger().set_timestamp(1000);
+
+    let bombadil = Address::generate(e);
+    let owner = Address::generate(e);
+    let pool = Address::generate(e);
+    let sale_token = e
+        .register_stellar_asset_contract_v2(bombadil.clone())
+        .address();
+    let raise_token = e
+        .register_stellar_asset_contract_v2(bombadil.clone())
+        .address();
+    StellarAssetClient::new(e, &sale_token).mint(&owner, &1000_0000000);
+
+    let pool_factory = e.register(MockPoolFactory {}, ());
+    MockPoolFactoryClient::new(e, &pool_factory).set_pool(&pool);
+    let backstop = e.register(
+        BackstopContract {},
+        (
+            raise_token.clone(),
+            Address::generate(e),
+            Address::generate(e),
+            Address::generate(e),
+            pool_factory.clone(),
+            Address::generate(e),
+            Address::generate(e),
+            Vec::<(Address, i128)>::new(e),
+        ),
+    );
+
+    let lbp = e.register(
+        LiquidityBootstrappingContract {},
+        (pool_factory.clone(), backstop.clone()),
+    );
+    let config = LbpConfig {
+        owner,
+        pool,
+        sale_token: sale_token.clone(),
+        raise_token: raise_token.clone(),
+        sale_amount: 1000_0000000,
+        virtual_raise: 100_0000000,
+        start_weight: 0_9000000,
+        end_weight: 0_5000000,
+        start: 1000,
+        end: 1000 + 24 * 60 * 60,
+        treasury: Address::generate(e),
+        backstop_split: 0_5000000,
+    };
+    Setup {
+        client: LiquidityBootstrappingClient::new(e, &lbp),
+        backstop: BackstopClient::new(e, &backstop),
+        sale_token: TokenClient::new(e, &sale_token),
+        raise_token: TokenClient::new(e, &raise_token),
+        config,
+    }
+}
+
+#[test]
+fn test_lbp() {
+    let e = Env::default();
+    let setup = setup(&e);
+    let samwise = Address::generate(&e);
+    StellarAssetClient::new(&e, &setup.raise_token.address).mint(&samwise, &100_0000000);
+
+    let id = setup.client.create(&setup.config);
+    assert_eq!(id, 0);
+    assert_eq!(
+        setup.sale_token.balance(&setup.client.address),
+        1000_0000000
+    );
+    assert_eq!(setup.client.spot_price(&id), 0_9000000);
+
+    // the buy is priced at the spot price after it
+    assert_eq!(setup.client.quote(&id, &9_0000000), 9_0909090);
+    let bought = setup.client.buy(&samwise, &id, &9_0000000, &9_0000000);
+    assert_eq!(bought, 9_0909090);
+    assert_eq!(setup.sale_token.balance(&samwise), 9_0909090);
+    assert_eq!(setup.raise_token.balance(&samwise), 91_0000000);
+
+    // the price declines as the sale token weight declines
+    e.ledger().set_timestamp(1000 + 12 * 60 * 60);
+    let price = setup.client.spot_price(&id);
+    assert!(price < 0_9000000);
+    assert_eq!(
+        price,
+        109_0000000 * 7 / 3 * 1_0000000 / (1000_0000000 - 9_0909090)
+    );
+
+    // finalize requires no authorization, so the LBP must authorize the backstop's nested transfer
+    e.ledger().set_timestamp(1000 + 24 * 60 * 60);
+    e.set_auths(&[]);
+    setup.client.finalize(&id);
+    let lbp = setup.client.get_lbp(&id);
+    assert!(lbp.finalized);
+    assert_eq!(lbp.raised, 9_0000000);
+    assert_eq!(lbp.sold, 9_0909090);
+    assert_eq!(setup.raise_token.balance(&setup.config.treasury), 4_5000000);
+    assert_eq!(
+        setup.raise_token.balance(&setup.backstop.address),
+        4_5000000
+    );
+    assert_eq!(
+        setup
+            .backstop
+            .user_balance(&setup.config.pool, &setup.client.address)
+            .shares,
+        4_5000000
+    );
+    assert_eq!(
+        setup.sale_token.balance(&setup.config.owner),
+        1000_0000000 - 9_0909090
+    );
+}
+
+#[test]
+fn test_buy_split_costs_no_more() {
+    let e = Env::default();
+    let setup = setup(&e);
+    let samwise = Address::generate(&e);
+    StellarAssetClient::new(&e, &setup.raise_token.address).mint(&samwise, &100_0000000);
+
+    let id = setup.client.create(&setup.config);
+    let at_once = setup.client.quote(&id, &90_0000000);
+    let first = setup.client.buy(&samwise, &id, &45_0000000, &0);
+    let second = setup.client.buy(&samwise, &id, &45_0000000, &0);
+    assert!(first + second >= at_once);
+}
+
+#[test]
+fn test_launch() {
+    let e = Env::default();
+    let setup = setup(&e);
+
+    let launch = PoolLaunch {
+        name: String::from_str(&e, "pool"),
+        salt: BytesN::<32>::random(&e),
+        oracle: Address::generate(&e),
+        backstop_take_rate: 0_1000000,
+        max_positions: 4,
+        min_collateral: 1_0000000,
+    };
+    let mut config = setup.config.clone();
+    config.pool = Address::generate(&e);
+    let (pool, id) = setup.client.launch(&launch, &config);
+    assert_eq!(pool, setup.config.pool);
+    assert_eq!(setup.client.get_lbp(&id).config.pool, setup.config.pool);
+    assert_eq!(
+        setup.sale_token.balance(&setup.client.address),
+        1000_0000000
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2002)")]
+fn test_create_not_pool() {
+    let e = Env::default();
+    let setup = setup(&e);
+
+    let mut config = setup.config.clone();
+    config.pool = Address::generate(&e);
+    setup.client.create(&config);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2000)")]
+fn test_create_weight_increases() {
+    let e = Env::default();
+    let setup = setup(&e);
+
+    let mut config = setup.config.clone();
+    config.end_weight = 0_9500000;
+    setup.client.create(&config);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2003)")]
+fn test_buy_after_end() {
+    let e = Env::default();
+    let setup = setup(&e);
+    let samwise = Address::generate(&e);
+    StellarAssetClient::new(&e, &setup.raise_token.address).mint(&samwise, &100_0000000);
+
+    let id = setup.client.create(&setup.config);
+    e.ledger().set_timestamp(1000 + 24 * 60 * 60);
+    setup.client.buy(&samwise, &id, &9_0000000, &0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2005)")]
+fn test_buy_under_min_amount_out() {
+    let e = Env::default();
+    let setup = setup(&e);
+    let samwise = Address::generate(&e);
+    StellarAssetClient::new(&e, &setup.raise_token.address).mint(&samwise, &100_0000000);
+
+    let id = setup.client.create(&setup.config);
+    setup.client.buy(&samwise, &id, &9_0000000, &9_0909091);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2004)")]
+fn test_finalize_before_end() {
+    let e = Env::default();
+    let setup = setup(&e);
+
+    let id = setup.client.create(&setup.config);
+    setup.client.finalize(&id);
+}
@@ -0,0 +1,397 @@
+use crate::{
+    dependencies::{PoolClient, PoolFactoryClient, Request},
+    errors::LiquidityManagerError,
+    events::LiquidityManagerEvents,
+    storage::{self, ManagedPool, PoolPosition},
+};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, panic_with_error, token::TokenClient, vec, Address,
+    Env, Vec,
+};
+
+/// Fixed-point scalar for 7 decimal numbers
+const SCALAR_7: i128 = 1_0000000;
+
+/// Fixed-point scalar for the 12 decimal bToken and dToken rates
+const SCALAR_12: i128 = 1_000_000_000_000;
+
+/// The maximum number of pools that can be managed at once
+const MAX_POOLS: u32 = 20;
+
+/// The pool request types used to manage liquidity
+const REQUEST_SUPPLY: u32 = 0;
+const REQUEST_WITHDRAW: u32 = 1;
+
+/// An amount larger than any position, but small enough that the pool can scale it by its 12
+/// decimal rates without overflowing. The pool caps withdrawals at the position, so requesting
+/// this amount withdraws the position in full.
+const CLOSE_AMOUNT: i128 = i128::MAX / 10_000_000_000_000;
+
+/// ### LiquidityManager
+///
+/// Supplies protocol-owned liquidity of a single token (e.g. USDC) from the treasury into
+/// selected pools to bootstrap borrow liquidity for new pools. The treasury or its keeper can
+/// rebalance a pool: when the utilization of the pool's reserve rises above the pool's supply
+/// trigger, the manager supplies enough to bring it back to the target, and when it falls below
+/// the withdraw trigger, the manager withdraws enough to bring it back up. Withdrawn tokens stay
+/// with the manager until they are supplied again or reclaimed by the treasury.
+#[contract]
+pub struct LiquidityManagerContract;
+
+#[contractclient(name = "LiquidityManagerClient")]
+pub trait LiquidityManager {
+    /// (Treasury only) Set a new address to become the treasury of the manager. This must be
+    /// accepted by the new treasury w/ `accept_treasury` to take effect.
+    ///
+    /// ### Arguments
+    /// * `new_treasury` - The new treasury address
+    ///
+    /// ### Panics
+    /// If the caller is not the treasury
+    fn propose_treasury(e: Env, new_treasury: Address);
+
+    /// (Proposed treasury only) Accept the treasury role
+    ///
+    /// ### Panics
+    /// If the caller is not the proposed treasury
+    fn accept_treasury(e: Env);
+
+    /// (Treasury only) Set the keeper allowed to rebalance pools alongside the treasury
+    ///
+    /// ### Arguments
+    /// * `keeper` - The address of the keeper
+    ///
+    /// ### Panics
+    /// If the caller is not the treasury
+    fn set_keeper(e: Env, keeper: Address);
+
+    /// (Treasury only) Add a pool to the manager, or update the triggers of a managed pool
+    ///
+    /// ### Arguments
+    /// * `config` - The pool and its utilization triggers
+    ///
+    /// ### Panics
+    /// * If the caller is not the treasury
+    /// * If the pool was not deployed by the pool factory, or has no reserve of the token
+    /// * If the triggers do not satisfy `withdraw_util < target_util < supply_util <= 100%`, or
+    ///   `max_supply` is not positive
+    /// * If the maximum number of pools are managed
+    fn set_pool(e: Env, config: ManagedPool);
+
+    /// (Treasury only) Withdraw all of the manager's supply from a pool and stop managing it
+    ///
+    /// Returns the amount of the token withdrawn
+    ///
+    /// ### Arguments
+    /// * `pool` - The pool
+    ///
+    /// ### Panics
+    /// If the caller is not the treasury or the pool is not managed
+    fn remove_pool(e: Env, pool: Address) -> i128;
+
+    /// (Treasury or keeper only) Rebalance the manager's supply in a pool against the
+    /// utilization triggers, with the pool's interest accrued to the current ledger. Supplies are
+    /// capped by the pool's `max_supply` and the manager's balance, and withdrawals by the
+    /// manager's supply.
+    ///
+    /// Returns the amount of the token supplied, or withdrawn if negative
+    ///
+    /// ### Arguments
+    /// * `caller` - The treasury or the keeper
+    /// * `pool` - The pool
+    ///
+    /// ### Panics
+    /// * If the caller is not the treasury or the keeper
+    /// * If the pool is not managed
+    fn rebalance(e: Env, caller: Address, pool: Address) -> i128;
+
+    /// (Treasury only) Return idle tokens held by the manager to the treasury
+    ///
+    /// ### Arguments
+    /// * `amount` - The amount of the token to return
+    ///
+    /// ### Panics
+    /// If the caller is not the treasury or the amount is not positive
+    fn reclaim(e: Env, amount: i128);
+
+    /// Fetch a managed pool
+    ///
+    /// ### Arguments
+    /// * `pool` - The pool
+    ///
+    /// ### Panics
+    /// If the pool is not managed
+    fn get_pool(e: Env, pool: Address) -> ManagedPool;
+
+    /// Fetch all managed pools
+    fn get_pools(e: Env) -> Vec<ManagedPool>;
+
+    /// Fetch the manager's supply position in a pool
+    ///
+    /// ### Arguments
+    /// * `pool` - The pool
+    ///
+    /// ### Panics
+    /// If the pool is not managed
+    fn get_position(e: Env, pool: Address) -> PoolPosition;
+
+    /// Fetch the manager's supply position in every managed pool
+    fn get_positions(e: Env) -> Vec<PoolPosition>;
+}
+
+#[contractimpl]
+impl LiquidityManagerContract {
+    /// Construct the liquidity manager contract
+    ///
+    /// ### Arguments
+    /// * `treasury` - The address of the treasury that controls the manager
+    /// * `pool_factory` - The pool factory that deploys the pools liquidity is supplied to
+    /// * `token` - The token supplied to pools
+    pub fn __constructor(e: Env, treasury: Address, pool_factory: Address, token: Address) {
+        storage::set_treasury(&e, &treasury);
+        storage::set_pool_factory(&e, &pool_factory);
+        storage::set_token(&e, &token);
+    }
+}
+
+#[contractimpl]
+impl LiquidityManager for LiquidityManagerContract {
+    fn propose_treasury(e: Env, new_treasury: Address) {
+        storage::extend_instance(&e);
+        storage::get_treasury(&e).require_auth();
+
+        storage::set_proposed_treasury(&e, &new_treasury);
+    }
+
+    fn accept_treasury(e: Env) {
+        storage::extend_instance(&e);
+
+        if let Some(proposed_treasury) = storage::get_proposed_treasury(&e) {
+            proposed_treasury.require_auth();
+            let cur_treasury = storage::get_treasury(&e);
+
+            storage::set_treasury(&e, &proposed_treasury);
+
+            LiquidityManagerEvents::set_treasury(&e, cur_treasury, proposed_treasury);
+        } else {
+            panic_with_error!(&e, LiquidityManagerError::BadRequest);
+        }
+    }
+
+    fn set_keeper(e: Env, keeper: Address) {
+        storage::extend_instance(&e);
+        storage::get_treasury(&e).require_auth();
+
+        storage::set_keeper(&e, &keeper);
+
+        LiquidityManagerEvents::set_keeper(&e, keeper);
+    }
+
+    fn set_pool(e: Env, config: ManagedPool) {
+        storage::extend_instance(&e);
+        storage::get_treasury(&e).require_auth();
+
+        if config.withdraw_util >= config.target_util
+            || config.target_util >= config.supply_util
+            || config.supply_util as i128 > SCALAR_7
+            || config.max_supply <= 0
+        {
+            panic_with_error!(&e, LiquidityManagerError::BadRequest);
+        }
+        if !PoolFactoryClient::new(&e, &storage::get_pool_factory(&e)).is_pool(&config.pool) {
+            panic_with_error!(&e, LiquidityManagerError::NotPool);
+        }
+        // panics if the pool has no reserve of the token
+        load_reserve_state(&e, &config.pool);
+
+        let mut pools = storage::get_pools(&e);
+        if !pools.contains(&config.pool) {
+            if pools.len() >= MAX_POOLS {
+                panic_with_error!(&e, LiquidityManagerError::TooManyPools);
+            }
+            pools.push_back(config.pool.clone());
+            storage::set_pools(&e, &pools);
+        }
+        storage::set_managed_pool(&e, &config);
+
+        LiquidityManagerEvents::set_pool(&e, config);
+    }
+
+    fn remove_pool(e: Env, pool: Address) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_treasury(&e).require_auth();
+
+        load_managed_pool(&e, &pool);
+        let state = load_reserve_state(&e, &pool);
+        let token_client = TokenClient::new(&e, &storage::get_token(&e));
+        let balance_before = token_client.balance(&e.current_contract_address());
+        if state.position.b_tokens > 0 {
+            submit(&e, &pool, REQUEST_WITHDRAW, CLOSE_AMOUNT);
+        }
+        let withdrawn = token_client.balance(&e.current_contract_address()) - balance_before;
+
+        let mut pools = storage::get_pools(&e);
+        if let Some(index) = pools.first_index_of(&pool) {
+            pools.remove(index);
+        }
+        storage::set_pools(&e, &pools);
+        storage::del_managed_pool(&e, &pool);
+
+        LiquidityManagerEvents::remove_pool(&e, pool, withdrawn);
+        withdrawn
+    }
+
+    fn rebalance(e: Env, caller: Address, pool: Address) -> i128 {
+        storage::extend_instance(&e);
+        caller.require_auth();
+        // a permissionless rebalance could be forced with a flash loan
+        if caller != storage::get_treasury(&e) && Some(caller) != storage::get_keeper(&e) {
+            panic_with_error!(&e, LiquidityManagerError::NotAuthorized);
+        }
+
+        let config = load_managed_pool(&e, &pool);
+        let state = load_reserve_state(&e, &pool);
+        let utilization = state.position.utilization;
+        let target_supply = state.total_liabilities * SCALAR_7 / config.target_util as i128;
+        let amount = if utilization > config.supply_util as i128 {
+            let balance = TokenClient::new(&e, &storage::get_token(&e))
+                .balance(&e.current_contract_address());
+            (target_supply - state.total_supply)
+                .min(config.max_supply - state.position.supplied)
+                .min(balance)
+                .max(0)
+        } else if utilization < config.withdraw_util as i128 {
+            -(state.total_supply - target_supply)
+                .min(state.position.supplied)
+                .max(0)
+        } else {
+            0
+        };
+
+        if amount > 0 {
+            submit(&e, &pool, REQUEST_SUPPLY, amount);
+        } else if amount < 0 {
+            submit(&e, &pool, REQUEST_WITHDRAW, -amount);
+        }
+
+        LiquidityManagerEvents::rebalance(&e, pool, amount, utilization);
+        amount
+    }
+
+    fn reclaim(e: Env, amount: i128) {
+        storage::extend_instance(&e);
+        let treasury = storage::get_treasury(&e);
+        treasury.require_auth();
+
+        if amount <= 0 {
+            panic_with_error!(&e, LiquidityManagerError::BadRequest);
+        }
+        TokenClient::new(&e, &storage::get_token(&e)).transfer(
+            &e.current_contract_address(),
+            &treasury,
+            &amount,
+        );
+
+        LiquidityManagerEvents::reclaim(&e, treasury, amount);
+    }
+
+    fn get_pool(e: Env, pool: Address) -> ManagedPool {
+        load_managed_pool(&e, &pool)
+    }
+
+    fn get_pools(e: Env) -> Vec<ManagedPool> {
+        let mut managed_pools: Vec<ManagedPool> = Vec::new(&e);
+        for pool in storage::get_pools(&e).iter() {
+            managed_pools.push_back(load_managed_pool(&e, &pool));
+        }
+        managed_pools
+    }
+
+    fn get_position(e: Env, pool: Address) -> PoolPosition {
+        load_managed_pool(&e, &pool);
+        load_reserve_state(&e, &pool).position
+    }
+
+    fn get_positions(e: Env) -> Vec<PoolPosition> {
+        let mut positions: Vec<PoolPosition> = Vec::new(&e);
+        for pool in storage::get_pools(&e).iter() {
+            positions.push_back(load_reserve_state(&e, &pool).position);
+        }
+        positions
+    }
+}
+
+/// The token's reserve in a pool and the manager's supply position in it
+struct ReserveState {
+    total_supply: i128,      // the amount of the token supplied to the reserve
+    total_liabilities: i128, // the amount of the token borrowed from the reserve
+    position: PoolPosition,
+}
+
+/// Load a managed pool, panicking if it is not managed
+fn load_managed_pool(e: &Env, pool: &Address) -> ManagedPool {
+    match storage::get_managed_pool(e, pool) {
+        Some(managed_pool) => managed_pool,
+        None => panic_with_error!(e, LiquidityManagerError::PoolNotManaged),
+    }
+}
+
+/// Load the token's reserve in a pool, with interest accrued to the current ledger, and the
+/// manager's supply position in it
+///
+/// ### Panics
+/// If the pool has no reserve of the token
+fn load_reserve_state(e: &Env, pool: &Address) -> ReserveState {
+    let token = storage::get_token(e);
+    let pool_client = PoolClient::new(e, pool);
+    let index = match pool_client.get_reserve_list().first_index_of(&token) {
+        Some(index) => index,
+        None => panic_with_error!(e, LiquidityManagerError::ReserveNotFound),
+    };
+    let data = pool_client.get_reserve(&token).data;
+    let total_supply = data.b_supply * data.b_rate / SCALAR_12;
+    let total_liabilities = data.d_supply * data.d_rate / SCALAR_12;
+    let utilization = if total_supply > 0 {
+        total_liabilities * SCALAR_7 / total_supply
+    } else {
+        0
+    };
+    let b_tokens = pool_client
+        .get_positions(&e.current_contract_address())
+        .supply
+        .get(index)
+        .unwrap_or(0);
+    ReserveState {
+        total_supply,
+        total_liabilities,
+        position: PoolPosition {
+            pool: pool.clone(),
+            b_tokens,
+            supplied: b_tokens * data.b_rate / SCALAR_12,
+            utilization,
+        },
+    }
+}
+
+/// Submit a supply or withdrawal of the token to a pool on behalf of the manager. Supplies are
+/// pulled by the pool with `transfer_from`, so the pool is approved to spend the amount first.
+fn submit(e: &Env, pool: &Address, request_type: u32, amount: i128) {
+    let manager = e.current_contract_address();
+    let token = storage::get_token(e);
+    if request_type == REQUEST_SUPPLY {
+        TokenClient::new(e, &token).approve(&manager, pool, &amount, &e.ledger().sequence());
+    }
+    PoolClient::new(e, pool).submit_with_allowance(
+        &manager,
+        &manager,
+        &manager,
+        &vec![
+            e,
+            Request {
+                request_type,
+                address: token,
+                amount,
+            },
+        ],
+    );
+}
@@ -0,0 +1,108 @@
+/**
+ * Partial clients and types for the pool factory and the pools liquidity is managed in
+ */
+use soroban_sdk::{contractclient, contracttype, Address, Env, Map, Vec};
+
+/// A request submitted to a pool
+#[derive(Clone)]
+#[contracttype]
+pub struct Request {
+    pub request_type: u32,
+    pub address: Address,
+    pub amount: i128,
+}
+
+/// A user's positions in a pool, keyed by reserve index
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Positions {
+    pub liabilities: Map<u32, i128>,
+    pub collateral: Map<u32, i128>,
+    pub supply: Map<u32, i128>,
+}
+
+/// The stored data of a reserve
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveData {
+    pub d_rate: i128,
+    pub b_rate: i128,
+    pub ir_mod: i128,
+    pub b_supply: i128,
+    pub d_supply: i128,
+    pub backstop_credit: i128,
+    pub last_time: u64,
+}
+
+/// The configuration of a reserve
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveConfig {
+    pub index: u32,
+    pub decimals: u32,
+    pub c_factor: u32,
+    pub l_factor: u32,
+    pub util: u32,
+    pub max_util: u32,
+    pub r_base: u32,
+    pub r_one: u32,
+    pub r_two: u32,
+    pub r_three: u32,
+    pub reactivity: u32,
+    pub supply_cap: i128,
+    pub enabled: bool,
+    pub conc_threshold: u32,
+    pub conc_haircut: u32,
+}
+
+/// The utilization smoothing of a reserve
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct UtilSmoothing {
+    pub window: u64,
+    pub util: i128,
+}
+
+/// A reserve, with interest accrued to the current ledger
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Reserve {
+    pub asset: Address,
+    pub config: ReserveConfig,
+    pub data: ReserveData,
+    pub scalar: i128,
+    pub protocol_fee: i128,
+    pub util_smoothing: Option<UtilSmoothing>,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolClient")]
+pub trait Pool {
+    /// Fetch the addresses of all reserves in the pool, ordered by reserve index
+    fn get_reserve_list(e: Env) -> Vec<Address>;
+
+    /// Fetch a reserve, updated to the current ledger
+    fn get_reserve(e: Env, asset: Address) -> Reserve;
+
+    /// Fetch the positions for an address
+    fn get_positions(e: Env, address: Address) -> Positions;
+
+    /// Submit a set of requests to the pool, settling the net transfer of each token with
+    /// `transfer_from`
+    ///
+    /// Returns the new positions for `from`
+    fn submit_with_allowance(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions;
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolFactoryClient")]
+pub trait PoolFactory {
+    /// Checks if contract address was deployed by the factory
+    fn is_pool(e: Env, pool_id: Address) -> bool;
+}
@@ -0,0 +1,19 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the liquidity manager contract. Common errors are codes that match up with the
+/// built-in contracts error reporting. Liquidity manager specific errors start at 2200.
+pub enum LiquidityManagerError {
+    // Common Errors
+    InternalError = 1,
+
+    // Liquidity Manager
+    BadRequest = 2200,
+    NotPool = 2201,
+    PoolNotManaged = 2202,
+    TooManyPools = 2203,
+    ReserveNotFound = 2204,
+    NotAuthorized = 2205,
+}
@@ -0,0 +1,84 @@
+use soroban_sdk::{Address, Env, Symbol};
+
+use crate::ManagedPool;
+
+pub struct LiquidityManagerEvents {}
+
+impl LiquidityManagerEvents {
+    /// Emitted when a new treasury is set for the manager
+    ///
+    /// - topics - `["set_treasury", treasury: Address]`
+    /// - data - `new_treasury: Address`
+    ///
+    /// ### Arguments
+    /// * `treasury` - The current treasury
+    /// * `new_treasury` - The new treasury
+    pub fn set_treasury(e: &Env, treasury: Address, new_treasury: Address) {
+        let topics = (Symbol::new(e, "set_treasury"), treasury);
+        e.events().publish(topics, new_treasury);
+    }
+
+    /// Emitted when the keeper allowed to rebalance pools is set
+    ///
+    /// - topics - `["set_keeper"]`
+    /// - data - `keeper: Address`
+    ///
+    /// ### Arguments
+    /// * `keeper` - The keeper
+    pub fn set_keeper(e: &Env, keeper: Address) {
+        let topics = (Symbol::new(e, "set_keeper"),);
+        e.events().publish(topics, keeper);
+    }
+
+    /// Emitted when a pool is added to the manager or its triggers are updated
+    ///
+    /// - topics - `["set_pool", pool: Address]`
+    /// - data - `config: ManagedPool`
+    ///
+    /// ### Arguments
+    /// * `config` - The managed pool
+    pub fn set_pool(e: &Env, config: ManagedPool) {
+        let topics = (Symbol::new(e, "set_pool"), config.pool.clone());
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when a pool is removed from the manager
+    ///
+    /// - topics - `["remove_pool", pool: Address]`
+    /// - data - `withdrawn: i128`
+    ///
+    /// ### Arguments
+    /// * `pool` - The pool
+    /// * `withdrawn` - The amount of the token withdrawn from the pool
+    pub fn remove_pool(e: &Env, pool: Address, withdrawn: i128) {
+        let topics = (Symbol::new(e, "remove_pool"), pool);
+        e.events().publish(topics, withdrawn);
+    }
+
+    /// Emitted when the manager's supply in a pool is rebalanced
+    ///
+    /// - topics - `["rebalance", pool: Address]`
+    /// - data - `[amount: i128, utilization: i128]`
+    ///
+    /// ### Arguments
+    /// * `pool` - The pool
+    /// * `amount` - The amount of the token supplied, or withdrawn if negative
+    /// * `utilization` - The utilization of the reserve before the rebalance (7 decimals)
+    pub fn rebalance(e: &Env, pool: Address, amount: i128, utilization: i128) {
+        let topics = (Symbol::new(e, "rebalance"), pool);
+        e.events().publish(topics, (amount, utilization));
+    }
+
+    /// Emitted when idle tokens are returned to the treasury
+    ///
+    /// - topics - `["reclaim", treasury: Address]`
+    /// - data - `amount: i128`
+    ///
+    /// ### Arguments
+    /// * `treasury` - The treasury
+    /// * `amount` - The amount of the token returned
+    pub fn reclaim(e: &Env, treasury: Address, amount: i128) {
+        let topics = (Symbol::new(e, "reclaim"), treasury);
+        e.events().publish(topics, amount);
+    }
+}
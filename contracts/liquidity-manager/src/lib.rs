@@ -0,0 +1,16 @@
+#![no_std]
+
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod contract;
+mod dependencies;
+mod errors;
+mod events;
+mod storage;
+mod test;
+
+pub use contract::*;
+pub use dependencies::{Positions, Request, Reserve, ReserveConfig, ReserveData, UtilSmoothing};
+pub use errors::LiquidityManagerError;
+pub use storage::{ManagedPool, PoolPosition};
@@ -0,0 +1,209 @@
+use soroban_sdk::{contracttype, unwrap::UnwrapOptimized, Address, Env, Symbol, Vec};
+
+/********** Ledger Thresholds **********/
+
+const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
+
+const LEDGER_THRESHOLD_INSTANCE: u32 = ONE_DAY_LEDGERS * 30; // ~ 30 days
+const LEDGER_BUMP_INSTANCE: u32 = LEDGER_THRESHOLD_INSTANCE + ONE_DAY_LEDGERS; // ~ 31 days
+
+const LEDGER_THRESHOLD_POOL: u32 = ONE_DAY_LEDGERS * 45; // ~ 45 days
+const LEDGER_BUMP_POOL: u32 = LEDGER_THRESHOLD_POOL + 20 * ONE_DAY_LEDGERS; // ~ 65 days
+
+/********** Storage Types **********/
+
+/// A pool the manager supplies protocol-owned liquidity to, and its utilization triggers
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ManagedPool {
+    pub pool: Address,      // the pool, which must be deployed by the factory
+    pub target_util: u32,   // the utilization a rebalance moves the reserve to (7 decimals)
+    pub supply_util: u32,   // the utilization above which liquidity is supplied (7 decimals)
+    pub withdraw_util: u32, // the utilization below which liquidity is withdrawn (7 decimals)
+    pub max_supply: i128,   // the maximum amount of the token supplied to the pool
+}
+
+/// The manager's supply position in a pool
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolPosition {
+    pub pool: Address,
+    pub b_tokens: i128,    // the bTokens held by the manager
+    pub supplied: i128,    // the amount of the token the bTokens are worth
+    pub utilization: i128, // the utilization of the pool's reserve of the token (7 decimals)
+}
+
+/********** Storage Keys **********/
+
+const TREASURY_KEY: &str = "Treasury";
+const PROPOSED_TREASURY_KEY: &str = "PropTreas";
+const KEEPER_KEY: &str = "Keeper";
+const POOL_FACTORY_KEY: &str = "PoolFact";
+const TOKEN_KEY: &str = "Token";
+const POOLS_KEY: &str = "Pools";
+
+#[derive(Clone)]
+#[contracttype]
+pub enum ManagerDataKey {
+    // A managed pool
+    Pool(Address),
+}
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_INSTANCE, LEDGER_BUMP_INSTANCE);
+}
+
+/********** Instance **********/
+
+/// Fetch the treasury address
+pub fn get_treasury(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, TREASURY_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the treasury address
+///
+/// ### Arguments
+/// * `treasury` - The address of the treasury that controls the manager
+pub fn set_treasury(e: &Env, treasury: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, TREASURY_KEY), treasury);
+}
+
+/// Fetch the address proposed to become the treasury, if any
+pub fn get_proposed_treasury(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, PROPOSED_TREASURY_KEY))
+}
+
+/// Set the address proposed to become the treasury
+///
+/// ### Arguments
+/// * `treasury` - The proposed treasury address
+pub fn set_proposed_treasury(e: &Env, treasury: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, PROPOSED_TREASURY_KEY), treasury);
+}
+
+/// Fetch the keeper address allowed to rebalance pools, if any
+pub fn get_keeper(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, KEEPER_KEY))
+}
+
+/// Set the keeper address allowed to rebalance pools
+///
+/// ### Arguments
+/// * `keeper` - The address of the keeper
+pub fn set_keeper(e: &Env, keeper: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, KEEPER_KEY), keeper);
+}
+
+/// Fetch the pool factory address
+pub fn get_pool_factory(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, POOL_FACTORY_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the pool factory address
+///
+/// ### Arguments
+/// * `pool_factory` - The address of the pool factory
+pub fn set_pool_factory(e: &Env, pool_factory: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, POOL_FACTORY_KEY), pool_factory);
+}
+
+/// Fetch the address of the token supplied to pools
+pub fn get_token(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, TOKEN_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the address of the token supplied to pools
+///
+/// ### Arguments
+/// * `token` - The address of the token (e.g. USDC)
+pub fn set_token(e: &Env, token: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, TOKEN_KEY), token);
+}
+
+/// Fetch the addresses of all managed pools
+pub fn get_pools(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Vec<Address>>(&Symbol::new(e, POOLS_KEY))
+        .unwrap_or(Vec::new(e))
+}
+
+/// Set the addresses of all managed pools
+///
+/// ### Arguments
+/// * `pools` - The addresses of all managed pools
+pub fn set_pools(e: &Env, pools: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Vec<Address>>(&Symbol::new(e, POOLS_KEY), pools);
+}
+
+/********** Persistent **********/
+
+/// Fetch a managed pool
+///
+/// ### Arguments
+/// * `pool` - The address of the pool
+pub fn get_managed_pool(e: &Env, pool: &Address) -> Option<ManagedPool> {
+    let key = ManagerDataKey::Pool(pool.clone());
+    let result = e
+        .storage()
+        .persistent()
+        .get::<ManagerDataKey, ManagedPool>(&key);
+    if result.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_POOL, LEDGER_BUMP_POOL);
+    }
+    result
+}
+
+/// Set a managed pool
+///
+/// ### Arguments
+/// * `managed_pool` - The managed pool
+pub fn set_managed_pool(e: &Env, managed_pool: &ManagedPool) {
+    let key = ManagerDataKey::Pool(managed_pool.pool.clone());
+    e.storage()
+        .persistent()
+        .set::<ManagerDataKey, ManagedPool>(&key, managed_pool);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_POOL, LEDGER_BUMP_POOL);
+}
+
+/// Remove a managed pool
+///
+/// ### Arguments
+/// * `pool` - The address of the pool
+pub fn del_managed_pool(e: &Env, pool: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&ManagerDataKey::Pool(pool.clone()));
+}
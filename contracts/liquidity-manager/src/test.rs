@@ -0,0 +1,382 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl, map,
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+use crate::{
+    LiquidityManagerClient, LiquidityManagerContract, ManagedPool, Positions, Request, Reserve,
+    ReserveConfig, ReserveData,
+};
+
+/********** Mocks **********/
+
+/// A pool with a single reserve whose rates are fixed at 1, that tracks the manager's supply
+#[contract]
+pub struct MockPool;
+
+#[contractimpl]
+impl MockPool {
+    pub fn __constructor(e: Env, token: Address, b_supply: i128, d_supply: i128) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "Token"), &token);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "BSupply"), &b_supply);
+        MockPool::set_d_supply(e, d_supply);
+    }
+
+    pub fn set_d_supply(e: Env, d_supply: i128) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "DSupply"), &d_supply);
+    }
+
+    pub fn get_reserve_list(e: Env) -> Vec<Address> {
+        let token: Address = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "Token"))
+            .unwrap();
+        vec![&e, token]
+    }
+
+    pub fn get_reserve(e: Env, asset: Address) -> Reserve {
+        let data = ReserveData {
+            d_rate: 1_000_000_000_000,
+            b_rate: 1_000_000_000_000,
+            ir_mod: 1_0000000,
+            b_supply: e
+                .storage()
+                .instance()
+                .get(&Symbol::new(&e, "BSupply"))
+                .unwrap(),
+            d_supply: e
+                .storage()
+                .instance()
+                .get(&Symbol::new(&e, "DSupply"))
+                .unwrap(),
+            backstop_credit: 0,
+            last_time: e.ledger().timestamp(),
+        };
+        Reserve {
+            asset,
+            config: ReserveConfig {
+                index: 0,
+                decimals: 7,
+                c_factor: 0_7500000,
+                l_factor: 0_7500000,
+                util: 0_7500000,
+                max_util: 0_9500000,
+                r_base: 0_0100000,
+                r_one: 0_0500000,
+                r_two: 0_5000000,
+                r_three: 1_5000000,
+                reactivity: 0_0000020,
+                supply_cap: i128::MAX,
+                enabled: true,
+                conc_threshold: 0,
+                conc_haircut: 0,
+            },
+            data,
+            scalar: 1_0000000,
+            protocol_fee: 0,
+            util_smoothing: None,
+        }
+    }
+
+    pub fn get_positions(e: Env, address: Address) -> Positions {
+        let supply: i128 = e.storage().instance().get(&address).unwrap_or(0);
+        Positions {
+            liabilities: map![&e],
+            collateral: map![&e],
+            supply: if supply > 0 {
+                map![&e, (0, supply)]
+            } else {
+                map![&e]
+            },
+        }
+    }
+
+    pub fn submit_with_allowance(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions {
+        from.require_auth();
+        spender.require_auth();
+        let b_supply_key = Symbol::new(&e, "BSupply");
+        let mut b_supply: i128 = e.storage().instance().get(&b_supply_key).unwrap();
+        let mut supply: i128 = e.storage().instance().get(&from).unwrap_or(0);
+        for request in requests.iter() {
+            let client = TokenClient::new(&e, &request.address);
+            match request.request_type {
+                0 => {
+                    client.transfer_from(
+                        &e.current_contract_address(),
+                        &spender,
+                        &e.current_contract_address(),
+                        &request.amount,
+                    );
+                    supply += request.amount;
+                    b_supply += request.amount;
+                }
+                1 => {
+                    let amount = request.amount.min(supply);
+                    client.transfer(&e.current_contract_address(), &to, &amount);
+                    supply -= amount;
+                    b_supply -= amount;
+                }
+                _ => panic!("unexpected request"),
+            }
+        }
+        e.storage().instance().set(&b_supply_key, &b_supply);
+        e.storage().instance().set(&from, &supply);
+        MockPool::get_positions(e, from)
+    }
+}
+
+/// A pool factory that deployed a single pool
+#[contract]
+pub struct MockPoolFactory;
+
+#[contractimpl]
+impl MockPoolFactory {
+    pub fn __constructor(e: Env, pool: Address) {
+        e.storage().instance().set(&Symbol::new(&e, "Pool"), &pool);
+    }
+
+    pub fn is_pool(e: Env, pool_id: Address) -> bool {
+        let pool: Address = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "Pool"))
+            .unwrap();
+        pool == pool_id
+    }
+}
+
+/// Setup a manager holding 500 tokens, and a pool with 100 tokens supplied and 95 borrowed
+fn setup<'a>(
+    e: &Env,
+    treasury: &Address,
+) -> (
+    Address,
+    LiquidityManagerClient<'a>,
+    Address,
+    TokenClient<'a>,
+) {
+    let bombadil = Address::generate(e);
+    let token = e
+        .register_stellar_asset_contract_v2(bombadil.clone())
+        .address();
+    let pool = e.register(
+        MockPool {},
+        (token.clone(), 100_0000000i128, 95_0000000i128),
+    );
+    StellarAssetClient::new(e, &token).mint(&pool, &5_0000000);
+    let pool_factory = e.register(MockPoolFactory {}, (pool.clone(),));
+    let manager = e.register(
+        LiquidityManagerContract {},
+        (treasury.clone(), pool_factory, token.clone()),
+    );
+    StellarAssetClient::new(e, &token).mint(&manager, &500_0000000);
+    (
+        manager.clone(),
+        LiquidityManagerClient::new(e, &manager),
+        pool,
+        TokenClient::new(e, &token),
+    )
+}
+
+fn default_config(pool: &Address) -> ManagedPool {
+    ManagedPool {
+        pool: pool.clone(),
+        target_util: 0_8000000,
+        supply_util: 0_9000000,
+        withdraw_util: 0_5000000,
+        max_supply: 1000_0000000,
+    }
+}
+
+#[test]
+fn test_rebalance() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let treasury = Address::generate(&e);
+    let (manager, manager_client, pool, token_client) = setup(&e, &treasury);
+    let pool_client = MockPoolClient::new(&e, &pool);
+    manager_client.set_pool(&default_config(&pool));
+
+    // utilization is 95%, so enough is supplied to bring it to 80%
+    let supplied = manager_client.rebalance(&treasury, &pool);
+    assert_eq!(supplied, 18_7500000);
+    let event = vec![&e, e.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &e,
+            (
+                manager.clone(),
+                (Symbol::new(&e, "rebalance"), pool.clone()).into_val(&e),
+                (18_7500000i128, 0_9500000i128).into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(token_client.balance(&manager), 481_2500000);
+    let position = manager_client.get_position(&pool);
+    assert_eq!(position.b_tokens, 18_7500000);
+    assert_eq!(position.supplied, 18_7500000);
+    assert_eq!(position.utilization, 0_8000000);
+
+    // utilization is between the triggers, so nothing changes
+    assert_eq!(manager_client.rebalance(&treasury, &pool), 0);
+
+    // utilization drops to ~17%, so the manager's supply is withdrawn
+    pool_client.set_d_supply(&20_0000000);
+    let withdrawn = manager_client.rebalance(&treasury, &pool);
+    assert_eq!(withdrawn, -18_7500000);
+    assert_eq!(token_client.balance(&manager), 500_0000000);
+    let positions = manager_client.get_positions();
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions.get_unchecked(0).b_tokens, 0);
+    assert_eq!(positions.get_unchecked(0).utilization, 0_2000000);
+}
+
+#[test]
+fn test_rebalance_capped_by_max_supply() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let treasury = Address::generate(&e);
+    let (_, manager_client, pool, _) = setup(&e, &treasury);
+    let mut config = default_config(&pool);
+    config.max_supply = 10_0000000;
+    manager_client.set_pool(&config);
+
+    assert_eq!(manager_client.rebalance(&treasury, &pool), 10_0000000);
+    // the reserve is still above the supply trigger, but the manager is at its max supply
+    assert_eq!(manager_client.rebalance(&treasury, &pool), 0);
+    assert_eq!(manager_client.get_position(&pool).supplied, 10_0000000);
+}
+
+#[test]
+fn test_remove_pool() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let treasury = Address::generate(&e);
+    let (manager, manager_client, pool, token_client) = setup(&e, &treasury);
+    manager_client.set_pool(&default_config(&pool));
+    manager_client.rebalance(&treasury, &pool);
+    assert_eq!(manager_client.get_pools().len(), 1);
+
+    let withdrawn = manager_client.remove_pool(&pool);
+    assert_eq!(withdrawn, 18_7500000);
+    assert_eq!(token_client.balance(&manager), 500_0000000);
+    assert_eq!(manager_client.get_pools().len(), 0);
+
+    manager_client.reclaim(&500_0000000);
+    assert_eq!(token_client.balance(&treasury), 500_0000000);
+    assert_eq!(token_client.balance(&manager), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2200)")]
+fn test_set_pool_invalid_triggers() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let treasury = Address::generate(&e);
+    let (_, manager_client, pool, _) = setup(&e, &treasury);
+    let mut config = default_config(&pool);
+    config.withdraw_util = config.target_util;
+    manager_client.set_pool(&config);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2201)")]
+fn test_set_pool_not_pool() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let treasury = Address::generate(&e);
+    let (_, manager_client, _, _) = setup(&e, &treasury);
+    manager_client.set_pool(&default_config(&Address::generate(&e)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2202)")]
+fn test_rebalance_not_managed() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let treasury = Address::generate(&e);
+    let (_, manager_client, pool, _) = setup(&e, &treasury);
+    manager_client.rebalance(&treasury, &pool);
+}
+
+#[test]
+fn test_rebalance_by_keeper() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let treasury = Address::generate(&e);
+    let keeper = Address::generate(&e);
+    let (_, manager_client, pool, _) = setup(&e, &treasury);
+    manager_client.set_pool(&default_config(&pool));
+    manager_client.set_keeper(&keeper);
+
+    assert_eq!(manager_client.rebalance(&keeper, &pool), 18_7500000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2205)")]
+fn test_rebalance_not_authorized() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let treasury = Address::generate(&e);
+    let (_, manager_client, pool, _) = setup(&e, &treasury);
+    manager_client.set_pool(&default_config(&pool));
+
+    manager_client.rebalance(&Address::generate(&e), &pool);
+}
+
+#[test]
+fn test_set_treasury() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let treasury = Address::generate(&e);
+    let new_treasury = Address::generate(&e);
+    let (manager, manager_client, pool, token_client) = setup(&e, &treasury);
+
+    manager_client.propose_treasury(&new_treasury);
+    manager_client.accept_treasury();
+    let event = vec![&e, e.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &e,
+            (
+                manager.clone(),
+                (Symbol::new(&e, "set_treasury"), treasury.clone()).into_val(&e),
+                new_treasury.into_val(&e)
+            )
+        ]
+    );
+
+    // the new treasury controls the manager
+    manager_client.set_pool(&default_config(&pool));
+    assert_eq!(manager_client.rebalance(&new_treasury, &pool), 18_7500000);
+    manager_client.reclaim(&1_0000000);
+    assert_eq!(token_client.balance(&new_treasury), 1_0000000);
+}
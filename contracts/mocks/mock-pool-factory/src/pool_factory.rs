@@ -6,6 +6,7 @@ use soroban_sdk::{
     contract, contractimpl, panic_with_error, testutils::Address as _, Address, BytesN, Env,
     String, Symbol,
 };
+use trustbridge_common::SCALAR_7;
 
 use pool::PoolContract;
 
@@ -46,6 +47,22 @@ pub trait MockPoolFactoryTrait {
     /// ### Arguments
     /// * `pool_address` - The pool address to set
     fn set_pool(e: Env, pool_address: Address);
+
+    /// Checks if an asset is on the reserve allowlist
+    ///
+    /// # Arguments
+    /// * 'asset' - The asset contract address
+    fn is_asset_allowed(e: Env, asset: Address) -> bool;
+
+    /// Mock Only: Add or remove an asset from the reserve allowlist
+    ///
+    /// ### Arguments
+    /// * `asset` - The asset contract address
+    /// * `allowed` - If the asset is allowed
+    fn set_asset_allowed(e: Env, asset: Address, allowed: bool);
+
+    /// Fetch the WASM hash of the pools deployed by the factory
+    fn pool_hash(e: Env) -> BytesN<32>;
 }
 
 #[contractimpl]
@@ -75,8 +92,8 @@ impl MockPoolFactoryTrait for MockPoolFactory {
         admin.require_auth();
         let pool_init_meta = storage::get_pool_init_meta(&e);
 
-        // verify backstop take rate is within [0,1) with 9 decimals
-        if backstop_take_rate >= 1_0000000 {
+        // verify backstop take rate is within [0,1) with 7 decimals
+        if backstop_take_rate as i128 >= SCALAR_7 {
             panic_with_error!(&e, PoolFactoryError::InvalidPoolInitArgs);
         }
 
@@ -110,4 +127,16 @@ impl MockPoolFactoryTrait for MockPoolFactory {
     fn set_pool(e: Env, pool_address: Address) {
         storage::set_deployed(&e, &pool_address);
     }
+
+    fn is_asset_allowed(e: Env, asset: Address) -> bool {
+        storage::is_asset_allowed(&e, &asset)
+    }
+
+    fn set_asset_allowed(e: Env, asset: Address, allowed: bool) {
+        storage::set_asset_allowed(&e, &asset, allowed);
+    }
+
+    fn pool_hash(e: Env) -> BytesN<32> {
+        storage::get_pool_init_meta(&e).pool_hash
+    }
 }
@@ -15,6 +15,7 @@ const LEDGER_BUMP_SHARED: u32 = LEDGER_THRESHOLD_SHARED + ONE_DAY_LEDGERS; // ~
 pub enum PoolFactoryDataKey {
     Contracts(Address),
     PoolInitMeta,
+    AllowedAsset(Address),
 }
 
 #[derive(Clone)]
@@ -83,3 +84,27 @@ pub fn set_deployed(e: &Env, contract_id: &Address) {
         .persistent()
         .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
+
+/// Check if an asset is on the reserve allowlist
+///
+/// ### Arguments
+/// * `asset` - The asset to check
+pub fn is_asset_allowed(e: &Env, asset: &Address) -> bool {
+    let key = PoolFactoryDataKey::AllowedAsset(asset.clone());
+    e.storage()
+        .persistent()
+        .get::<PoolFactoryDataKey, bool>(&key)
+        .unwrap_or(false)
+}
+
+/// Set if an asset is on the reserve allowlist
+///
+/// ### Arguments
+/// * `asset` - The asset
+/// * `allowed` - If the asset is allowed
+pub fn set_asset_allowed(e: &Env, asset: &Address, allowed: bool) {
+    let key = PoolFactoryDataKey::AllowedAsset(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolFactoryDataKey, bool>(&key, &allowed);
+}
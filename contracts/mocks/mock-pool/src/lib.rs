@@ -12,10 +12,29 @@ pub struct Positions {
     pub supply: Map<u32, i128>,      // Map of Reserve Index to non-collateral supply share balance
 }
 
+#[derive(Clone)]
+#[contracttype]
+pub struct PoolConfig {
+    pub oracle: Address,
+    pub min_collateral: i128,
+    pub bstop_rate: u32,
+    pub status: u32,
+    pub max_positions: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct EmissionMigration {
+    pub token: Address,
+    pub ledger: u32,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
     Positions(Address),
+    Config,
+    EmissionMigration,
 }
 
 #[contract]
@@ -56,4 +75,39 @@ impl MockPool {
             },
         }
     }
+
+    /// Set the pool configuration
+    ///
+    /// # Arguments
+    /// * 'config' - The pool configuration
+    pub fn set_config(e: Env, config: PoolConfig) {
+        e.storage()
+            .instance()
+            .set::<DataKey, PoolConfig>(&DataKey::Config, &config);
+    }
+
+    /// Fetch the pool configuration
+    pub fn get_config(e: Env) -> PoolConfig {
+        e.storage()
+            .instance()
+            .get::<DataKey, PoolConfig>(&DataKey::Config)
+            .unwrap()
+    }
+
+    /// Set the pool's emission token migration
+    ///
+    /// # Arguments
+    /// * 'migration' - The emission token migration
+    pub fn set_emission_migration(e: Env, migration: EmissionMigration) {
+        e.storage()
+            .instance()
+            .set::<DataKey, EmissionMigration>(&DataKey::EmissionMigration, &migration);
+    }
+
+    /// Fetch the pool's emission token migration, if one is configured
+    pub fn get_emission_migration(e: Env) -> Option<EmissionMigration> {
+        e.storage()
+            .instance()
+            .get::<DataKey, EmissionMigration>(&DataKey::EmissionMigration)
+    }
 }
@@ -0,0 +1,59 @@
+use crate::{errors::MulticallError, events::MulticallEvents, storage::Call};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, panic_with_error, Address, Env, Val, Vec,
+};
+
+/// The maximum number of calls that can be executed in a single batch
+const MAX_CALLS: u32 = 16;
+
+/// ### Multicall
+///
+/// Executes a batch of contract calls atomically on behalf of a user, so a wallet can request a
+/// single signature for an auth entry tree rooted at the batch instead of one per call.
+#[contract]
+pub struct MulticallContract;
+
+#[contractclient(name = "MulticallClient")]
+pub trait Multicall {
+    /// Execute a batch of calls in order on behalf of `caller`. Calls that require authorization
+    /// from `caller` are authorized as sub-invocations of this call. If any call fails, the
+    /// entire batch is reverted.
+    ///
+    /// Returns the result of each call, in order
+    ///
+    /// ### Arguments
+    /// * `caller` - The address authorizing the batch
+    /// * `calls` - The calls to execute
+    ///
+    /// ### Panics
+    /// If no calls are included, too many calls are included, any call targets the multicall
+    /// contract, or any call fails
+    fn execute(e: Env, caller: Address, calls: Vec<Call>) -> Vec<Val>;
+}
+
+#[contractimpl]
+impl Multicall for MulticallContract {
+    fn execute(e: Env, caller: Address, calls: Vec<Call>) -> Vec<Val> {
+        caller.require_auth();
+
+        if calls.is_empty() {
+            panic_with_error!(&e, MulticallError::BadRequest);
+        }
+        if calls.len() > MAX_CALLS {
+            panic_with_error!(&e, MulticallError::TooManyCalls);
+        }
+
+        let mut results = Vec::new(&e);
+        for call in calls.iter() {
+            // contracts cannot re-enter themselves, so fail early with a clear error
+            if call.contract == e.current_contract_address() {
+                panic_with_error!(&e, MulticallError::BadRequest);
+            }
+            let result: Val = e.invoke_contract(&call.contract, &call.fn_name, call.args);
+            results.push_back(result);
+        }
+
+        MulticallEvents::execute(&e, caller, calls.len());
+        results
+    }
+}
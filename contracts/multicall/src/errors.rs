@@ -0,0 +1,15 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the multicall contract. Common errors are codes that match up with the
+/// built-in contracts error reporting. Multicall specific errors start at 1500.
+pub enum MulticallError {
+    // Common Errors
+    InternalError = 1,
+
+    // Multicall
+    BadRequest = 1500,
+    TooManyCalls = 1501,
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::{Address, Env, Symbol};
+
+pub struct MulticallEvents {}
+
+impl MulticallEvents {
+    /// Emitted when a batch of calls is executed
+    ///
+    /// - topics - `["execute", caller: Address]`
+    /// - data - `num_calls: u32`
+    ///
+    /// ### Arguments
+    /// * `caller` - The address that authorized the batch
+    /// * `num_calls` - The number of calls executed
+    pub fn execute(e: &Env, caller: Address, num_calls: u32) {
+        let topics = (Symbol::new(e, "execute"), caller);
+        e.events().publish(topics, num_calls);
+    }
+}
@@ -0,0 +1,13 @@
+use soroban_sdk::{contracttype, Address, Symbol, Val, Vec};
+
+/// A contract invocation to execute as part of a batch
+#[derive(Clone)]
+#[contracttype]
+pub struct Call {
+    /// The contract to invoke
+    pub contract: Address,
+    /// The name of the function to invoke
+    pub fn_name: Symbol,
+    /// The arguments to invoke the function with
+    pub args: Vec<Val>,
+}
@@ -0,0 +1,186 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events},
+    vec, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+
+use crate::{Call, MulticallClient, MulticallContract};
+
+/********** Mocks **********/
+
+/// Stores a value per user, where setting a value requires the user's authorization
+#[contract]
+pub struct MockVault;
+
+#[contractimpl]
+impl MockVault {
+    pub fn set(e: Env, user: Address, value: u32) -> u32 {
+        user.require_auth();
+        let old: u32 = e.storage().instance().get(&user).unwrap_or(0);
+        e.storage().instance().set(&user, &value);
+        old
+    }
+
+    pub fn get(e: Env, user: Address) -> u32 {
+        e.storage().instance().get(&user).unwrap_or(0)
+    }
+
+    pub fn fail(_e: Env) {
+        panic!("fail");
+    }
+}
+
+fn call(e: &Env, contract: &Address, fn_name: &str, args: Vec<Val>) -> Call {
+    Call {
+        contract: contract.clone(),
+        fn_name: Symbol::new(e, fn_name),
+        args,
+    }
+}
+
+#[test]
+fn test_execute() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let vault = e.register(MockVault {}, ());
+    let multicall = MulticallClient::new(&e, &e.register(MulticallContract {}, ()));
+
+    let calls = vec![
+        &e,
+        call(
+            &e,
+            &vault,
+            "set",
+            vec![&e, samwise.into_val(&e), 5u32.into_val(&e)],
+        ),
+        call(
+            &e,
+            &vault,
+            "set",
+            vec![&e, samwise.into_val(&e), 7u32.into_val(&e)],
+        ),
+        call(&e, &vault, "get", vec![&e, samwise.into_val(&e)]),
+    ];
+    let results = multicall.execute(&samwise, &calls);
+    assert_eq!(results.len(), 3);
+    let old_0: u32 = results.get_unchecked(0).into_val(&e);
+    let old_1: u32 = results.get_unchecked(1).into_val(&e);
+    let value: u32 = results.get_unchecked(2).into_val(&e);
+    assert_eq!((old_0, old_1, value), (0, 5, 7));
+
+    // the user authorizes a single tree rooted at the batch
+    let set_args: Vec<Val> = vec![&e, samwise.into_val(&e), 5u32.into_val(&e)];
+    let auths = e.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, samwise);
+    assert_eq!(
+        auths[0].1.function,
+        AuthorizedFunction::Contract((
+            multicall.address.clone(),
+            Symbol::new(&e, "execute"),
+            vec![&e, samwise.into_val(&e), calls.into_val(&e)],
+        ))
+    );
+    assert_eq!(auths[0].1.sub_invocations.len(), 2);
+    assert_eq!(
+        auths[0].1.sub_invocations[0],
+        AuthorizedInvocation {
+            function: AuthorizedFunction::Contract((
+                vault.clone(),
+                Symbol::new(&e, "set"),
+                set_args,
+            )),
+            sub_invocations: std::vec![],
+        }
+    );
+
+    let event = vec![&e, e.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &e,
+            (
+                multicall.address.clone(),
+                (Symbol::new(&e, "execute"), samwise.clone()).into_val(&e),
+                3u32.into_val(&e)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_execute_reverts_batch_on_failure() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let vault = e.register(MockVault {}, ());
+    let multicall = MulticallClient::new(&e, &e.register(MulticallContract {}, ()));
+
+    let calls = vec![
+        &e,
+        call(
+            &e,
+            &vault,
+            "set",
+            vec![&e, samwise.into_val(&e), 5u32.into_val(&e)],
+        ),
+        call(&e, &vault, "fail", vec![&e]),
+    ];
+    multicall.execute(&samwise, &calls);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1500)")]
+fn test_execute_no_calls() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let multicall = MulticallClient::new(&e, &e.register(MulticallContract {}, ()));
+
+    multicall.execute(&samwise, &vec![&e]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1500)")]
+fn test_execute_calls_self() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let multicall = MulticallClient::new(&e, &e.register(MulticallContract {}, ()));
+
+    let calls = vec![
+        &e,
+        call(
+            &e,
+            &multicall.address,
+            "execute",
+            vec![&e, samwise.into_val(&e), Vec::<Call>::new(&e).into_val(&e)],
+        ),
+    ];
+    multicall.execute(&samwise, &calls);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1501)")]
+fn test_execute_too_many_calls() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let vault = e.register(MockVault {}, ());
+    let multicall = MulticallClient::new(&e, &e.register(MulticallContract {}, ()));
+
+    let mut calls = vec![&e];
+    for _ in 0..17 {
+        calls.push_back(call(&e, &vault, "get", vec![&e, samwise.into_val(&e)]));
+    }
+    multicall.execute(&samwise, &calls);
+}
@@ -7,19 +7,31 @@ use soroban_sdk::contracterror;
 pub enum OracleError {
     /// Contract has already been initialized
     AlreadyInitialized = 1,
-    
+
     /// Caller is not authorized to perform this action
     Unauthorized = 2,
-    
+
     /// Invalid price provided (must be > 0)
     InvalidPrice = 3,
-    
+
     /// Invalid input parameters
     InvalidInput = 4,
-    
+
     /// Price not found for the requested asset
     PriceNotFound = 5,
-    
+
     /// Contract is not initialized
     NotInitialized = 6,
-} 
\ No newline at end of file
+
+    /// No feeder key is registered to verify signed prices
+    FeederNotSet = 7,
+
+    /// Signed price is not newer than the stored price, or is in the future
+    StalePrice = 8,
+
+    /// Price publication is paused by the guardian
+    Paused = 9,
+
+    /// Price update omits an asset whose heartbeat has lapsed
+    HeartbeatLapsed = 10,
+}
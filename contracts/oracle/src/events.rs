@@ -1,4 +1,5 @@
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{Address, BytesN, Env, Vec};
+use trustbridge_common::{events::{topic, topic_with}, Role};
 use crate::Asset;
 
 /// Events emitted by the TrustBridge Oracle contract
@@ -8,7 +9,7 @@ impl OracleEvents {
     /// Emitted when the oracle is initialized
     pub fn initialized(e: &Env, admin: Address) {
         e.events().publish(
-            (Symbol::new(e, "initialized"),),
+            topic(e, "initialized"),
             admin
         );
     }
@@ -16,15 +17,79 @@ impl OracleEvents {
     /// Emitted when a price is set
     pub fn price_set(e: &Env, asset: Asset, price: i128, timestamp: u64) {
         e.events().publish(
-            (Symbol::new(e, "price_set"), asset),
+            topic_with(e, "price_set", asset),
             (price, timestamp)
         );
     }
 
+    /// Emitted when a price is set with a confidence interval
+    pub fn confidence_set(e: &Env, asset: Asset, confidence: i128) {
+        e.events().publish(
+            topic_with(e, "confidence_set", asset),
+            confidence
+        );
+    }
+
+    /// Emitted when the feeder key is set or removed
+    pub fn feeder_set(e: &Env, feeder: Option<BytesN<32>>) {
+        e.events().publish(
+            topic(e, "feeder_set"),
+            feeder
+        );
+    }
+
+    /// Emitted when a role is granted
+    pub fn role_granted(e: &Env, role: Role, member: Address) {
+        e.events().publish(
+            topic_with(e, "role_granted", member),
+            role
+        );
+    }
+
+    /// Emitted when a role is revoked
+    pub fn role_revoked(e: &Env, role: Role, member: Address) {
+        e.events().publish(
+            topic_with(e, "role_revoked", member),
+            role
+        );
+    }
+
+    /// Emitted when a pauser pauses price publication and restricts pools
+    pub fn paused(e: &Env, caller: Address, pools: Vec<Address>) {
+        e.events().publish(
+            topic_with(e, "paused", caller),
+            pools
+        );
+    }
+
+    /// Emitted when the admin resumes price publication
+    pub fn unpaused(e: &Env, admin: Address) {
+        e.events().publish(
+            topic_with(e, "unpaused", admin),
+            ()
+        );
+    }
+
+    /// Emitted when the base asset is set
+    pub fn base_set(e: &Env, base: Asset) {
+        e.events().publish(
+            topic(e, "base_set"),
+            base
+        );
+    }
+
+    /// Emitted when the heartbeat of an asset is set or removed
+    pub fn heartbeat_set(e: &Env, asset: Asset, heartbeat: u64) {
+        e.events().publish(
+            topic_with(e, "heartbeat_set", asset),
+            heartbeat
+        );
+    }
+
     /// Emitted when admin is changed
     pub fn admin_changed(e: &Env, old_admin: Address, new_admin: Address) {
         e.events().publish(
-            (Symbol::new(e, "admin_changed"),),
+            topic(e, "admin_changed"),
             (old_admin, new_admin)
         );
     }
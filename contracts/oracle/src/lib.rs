@@ -1,8 +1,10 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, panic_with_error, Address, Env, Symbol,
+    contract, contractclient, contractimpl, panic_with_error, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, Map, Vec,
 };
+use trustbridge_common::{roles, ContractStatus, Role};
 
 mod storage;
 mod error;
@@ -11,20 +13,15 @@ mod events;
 pub use error::OracleError;
 pub use events::OracleEvents;
 
-// SEP-40 PriceData structure
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PriceData {
-    pub price: i128,      // Price with decimals precision
-    pub timestamp: u64,   // Unix timestamp
-}
+// SEP-40 types, shared with the pool and backstop
+pub use trustbridge_common::{Asset, PriceData, PriceDataV2};
 
-// Asset representation for SEP-40 compatibility
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum Asset {
-    Stellar(Address),     // Stellar asset contract address
-    Other(Symbol),        // Other asset identifier
+/// The part of the pool interface a pauser uses to restrict pools. The pool must grant this
+/// oracle its pauser role.
+#[contractclient(name = "PoolClient")]
+pub trait Pool {
+    /// Restrict the pool, disabling new borrows
+    fn set_status_restricted(e: Env, caller: Address) -> u32;
 }
 
 /// TrustBridge Oracle Contract
@@ -58,6 +55,25 @@ pub trait OracleTrait {
     /// * `Option<PriceData>` - The price data or None if not found
     fn lastprice(e: Env, asset: Asset) -> Option<PriceData>;
 
+    /// Get the last price for an asset with the feeder's confidence in it
+    ///
+    /// ### Arguments
+    /// * `asset` - The asset to get price for
+    ///
+    /// ### Returns
+    /// * `Option<PriceDataV2>` - The price data or None if not found. The confidence is 0
+    ///   if the price was set without one.
+    fn lastprice_v2(e: Env, asset: Asset) -> Option<PriceDataV2>;
+
+    /// Set the price for a given asset with a confidence interval (admin only). The true price is
+    /// expected to be within `price - confidence` and `price + confidence`.
+    ///
+    /// ### Arguments
+    /// * `asset` - The asset to set price for
+    /// * `price` - The price in 7-decimal format
+    /// * `confidence` - The uncertainty of the price in 7-decimal format
+    fn set_price_with_confidence(e: Env, asset: Asset, price: i128, confidence: i128);
+
     /// Get the number of decimals used by the oracle
     /// 
     /// ### Returns
@@ -69,6 +85,9 @@ pub trait OracleTrait {
     /// ### Arguments
     /// * `assets` - Vector of assets
     /// * `prices` - Vector of corresponding prices
+    ///
+    /// ### Panics
+    /// * If an asset with a heartbeat is omitted while its price is older than its heartbeat
     fn set_prices(e: Env, assets: soroban_sdk::Vec<Asset>, prices: soroban_sdk::Vec<i128>);
 
     /// Get the admin address
@@ -82,6 +101,155 @@ pub trait OracleTrait {
     /// ### Arguments
     /// * `new_admin` - The new admin address
     fn set_admin(e: Env, new_admin: Address);
+
+    /// Register the ed25519 public key of the off-chain price feeder, or remove it (admin only)
+    ///
+    /// ### Arguments
+    /// * `feeder` - The feeder's public key, or None to stop accepting signed prices
+    fn set_feeder(e: Env, feeder: Option<BytesN<32>>);
+
+    /// Get the ed25519 public key of the off-chain price feeder
+    ///
+    /// ### Returns
+    /// * `Option<BytesN<32>>` - The feeder's public key or None if not set
+    fn feeder(e: Env) -> Option<BytesN<32>>;
+
+    /// Set the price for a given asset from a price signed by the feeder. Anyone can relay a
+    /// signed price, so price production is decoupled from transaction submission.
+    ///
+    /// The feeder signs the XDR encoding of `(oracle, asset, price, timestamp)`, where `oracle`
+    /// is the address of this contract.
+    ///
+    /// ### Arguments
+    /// * `asset` - The asset to set price for
+    /// * `price` - The price in 7-decimal format
+    /// * `timestamp` - The unix timestamp the feeder observed the price at
+    /// * `signature` - The feeder's ed25519 signature of the price
+    ///
+    /// ### Panics
+    /// * If no feeder is registered or the signature is invalid
+    /// * If the timestamp is not newer than the stored price or is in the future
+    fn set_price_signed(e: Env, asset: Asset, price: i128, timestamp: u64, signature: BytesN<64>);
+
+    /// Get the status of the oracle. The oracle is Frozen before it is initialized, as no prices
+    /// can be set, Restricted while price publication is paused, and Active otherwise.
+    ///
+    /// ### Returns
+    /// * `ContractStatus` - The status of the oracle
+    fn status(e: Env) -> ContractStatus;
+
+    /// Grant a role to an address (admin only). The admin holds every role, and the admin role
+    /// itself is transferred with `set_admin`.
+    ///
+    /// ### Arguments
+    /// * `role` - The role to grant
+    /// * `member` - The address to grant the role to
+    fn grant_role(e: Env, role: Role, member: Address);
+
+    /// Revoke a role from an address (admin only)
+    ///
+    /// ### Arguments
+    /// * `role` - The role to revoke
+    /// * `member` - The address to revoke the role from
+    fn revoke_role(e: Env, role: Role, member: Address);
+
+    /// Check if an address holds a role
+    ///
+    /// ### Returns
+    /// * `bool` - True if the address is the admin or was granted the role
+    fn has_role(e: Env, role: Role, member: Address) -> bool;
+
+    /// Pause price publication and restrict new borrows on the given pools in a single
+    /// emergency action (pauser only). Each pool must grant this oracle its pauser role.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the pauser role
+    /// * `pools` - The pools to restrict
+    ///
+    /// ### Panics
+    /// * If the caller does not hold the pauser role
+    /// * If a pool does not grant this oracle its pauser role
+    fn pause(e: Env, caller: Address, pools: Vec<Address>);
+
+    /// Resume price publication (admin only). Restricted pools are lifted by their own admins.
+    fn unpause(e: Env);
+
+    /// Check if price publication is paused
+    ///
+    /// ### Returns
+    /// * `bool` - True if price publication is paused
+    fn paused(e: Env) -> bool;
+
+    /// Get the asset prices are quoted in, as defined by SEP-40. Defaults to USD.
+    ///
+    /// ### Returns
+    /// * `Asset` - The base asset
+    fn base(e: Env) -> Asset;
+
+    /// Set the asset prices are quoted in (admin only). The base can only be set once, and
+    /// should be set before any prices are published, so pools never see prices in two bases.
+    ///
+    /// ### Arguments
+    /// * `base` - The base asset, such as `Asset::Other("EUR")` or the XLM contract
+    ///
+    /// ### Panics
+    /// * If the base has already been set
+    fn set_base(e: Env, base: Asset);
+
+    /// Set the heartbeat of an asset (admin only). Once an asset's price is older than its
+    /// heartbeat, `set_prices` rejects updates that omit the asset, so a keeper pushing a batch
+    /// cannot let the feed lapse unnoticed.
+    ///
+    /// ### Arguments
+    /// * `asset` - The asset to set the heartbeat of
+    /// * `heartbeat` - The maximum age of the asset's price in seconds, or 0 to remove it
+    fn set_heartbeat(e: Env, asset: Asset, heartbeat: u64);
+
+    /// Get the heartbeat of each asset, in seconds
+    ///
+    /// ### Returns
+    /// * `Map<Asset, u64>` - The heartbeat of each asset that has one
+    fn heartbeats(e: Env) -> Map<Asset, u64>;
+
+    /// Get the assets whose price is missing or older than their heartbeat, which keepers
+    /// need to refresh
+    ///
+    /// ### Returns
+    /// * `Vec<Asset>` - The stale assets
+    fn stale_assets(e: Env) -> Vec<Asset>;
+}
+
+/// Check if an address holds a role. The admin holds every role.
+fn has_role(e: &Env, role: Role, member: &Address) -> bool {
+    *member == storage::get_admin(e) || roles::has_role(e, role, member)
+}
+
+/// Require that price publication is not paused
+fn require_not_paused(e: &Env) {
+    if storage::get_paused(e) {
+        panic_with_error!(e, OracleError::Paused);
+    }
+}
+
+/// Get the assets whose price is missing or older than their heartbeat
+fn load_stale_assets(e: &Env) -> Vec<Asset> {
+    let now = e.ledger().timestamp();
+    let mut stale_assets = Vec::new(e);
+    for (asset, heartbeat) in storage::get_heartbeats(e).iter() {
+        let is_stale = match storage::get_price(e, &asset) {
+            Some(price_data) => price_data.timestamp.saturating_add(heartbeat) <= now,
+            None => true,
+        };
+        if is_stale {
+            stale_assets.push_back(asset);
+        }
+    }
+    stale_assets
+}
+
+/// Build the message the feeder signs for a price
+pub fn signed_price_payload(e: &Env, asset: &Asset, price: i128, timestamp: u64) -> Bytes {
+    (e.current_contract_address(), asset.clone(), price, timestamp).to_xdr(e)
 }
 
 #[contractimpl]
@@ -99,6 +267,7 @@ impl OracleTrait for TrustBridgeOracle {
     fn set_price(e: Env, asset: Asset, price: i128) {
         let admin = storage::get_admin(&e);
         admin.require_auth();
+        require_not_paused(&e);
 
         if price <= 0 {
             panic_with_error!(&e, OracleError::InvalidPrice);
@@ -110,6 +279,7 @@ impl OracleTrait for TrustBridgeOracle {
         };
 
         storage::set_price(&e, &asset, &price_data);
+        storage::set_confidence(&e, &asset, 0);
         
         OracleEvents::price_set(&e, asset, price, price_data.timestamp);
     }
@@ -118,6 +288,38 @@ impl OracleTrait for TrustBridgeOracle {
         storage::get_price(&e, &asset)
     }
 
+    fn lastprice_v2(e: Env, asset: Asset) -> Option<PriceDataV2> {
+        storage::get_price(&e, &asset).map(|price_data| PriceDataV2 {
+            price: price_data.price,
+            timestamp: price_data.timestamp,
+            confidence: storage::get_confidence(&e, &asset),
+        })
+    }
+
+    fn set_price_with_confidence(e: Env, asset: Asset, price: i128, confidence: i128) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        require_not_paused(&e);
+
+        if price <= 0 {
+            panic_with_error!(&e, OracleError::InvalidPrice);
+        }
+        if confidence < 0 {
+            panic_with_error!(&e, OracleError::InvalidInput);
+        }
+
+        let price_data = PriceData {
+            price,
+            timestamp: e.ledger().timestamp(),
+        };
+
+        storage::set_price(&e, &asset, &price_data);
+        storage::set_confidence(&e, &asset, confidence);
+
+        OracleEvents::price_set(&e, asset.clone(), price, price_data.timestamp);
+        OracleEvents::confidence_set(&e, asset, confidence);
+    }
+
     fn decimals(_e: Env) -> u32 {
         7 // TrustBridge Oracle uses 7 decimals
     }
@@ -125,11 +327,19 @@ impl OracleTrait for TrustBridgeOracle {
     fn set_prices(e: Env, assets: soroban_sdk::Vec<Asset>, prices: soroban_sdk::Vec<i128>) {
         let admin = storage::get_admin(&e);
         admin.require_auth();
+        require_not_paused(&e);
 
         if assets.len() != prices.len() {
             panic_with_error!(&e, OracleError::InvalidInput);
         }
 
+        // every asset whose heartbeat has lapsed must be refreshed by the update
+        for asset in load_stale_assets(&e).iter() {
+            if !assets.contains(&asset) {
+                panic_with_error!(&e, OracleError::HeartbeatLapsed);
+            }
+        }
+
         let timestamp = e.ledger().timestamp();
 
         for i in 0..assets.len() {
@@ -146,6 +356,7 @@ impl OracleTrait for TrustBridgeOracle {
             };
 
             storage::set_price(&e, &asset, &price_data);
+            storage::set_confidence(&e, &asset, 0);
             OracleEvents::price_set(&e, asset, price, timestamp);
         }
     }
@@ -162,6 +373,154 @@ impl OracleTrait for TrustBridgeOracle {
         
         OracleEvents::admin_changed(&e, current_admin, new_admin);
     }
+
+    fn set_feeder(e: Env, feeder: Option<BytesN<32>>) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_feeder(&e, &feeder);
+
+        OracleEvents::feeder_set(&e, feeder);
+    }
+
+    fn feeder(e: Env) -> Option<BytesN<32>> {
+        storage::get_feeder(&e)
+    }
+
+    fn set_price_signed(e: Env, asset: Asset, price: i128, timestamp: u64, signature: BytesN<64>) {
+        let feeder = match storage::get_feeder(&e) {
+            Some(feeder) => feeder,
+            None => panic_with_error!(&e, OracleError::FeederNotSet),
+        };
+        require_not_paused(&e);
+
+        if price <= 0 {
+            panic_with_error!(&e, OracleError::InvalidPrice);
+        }
+
+        // only accept newer prices, so a signed price cannot be replayed over a later one
+        let is_stale = match storage::get_price(&e, &asset) {
+            Some(price_data) => timestamp <= price_data.timestamp,
+            None => false,
+        };
+        if is_stale || timestamp > e.ledger().timestamp() {
+            panic_with_error!(&e, OracleError::StalePrice);
+        }
+
+        let payload = signed_price_payload(&e, &asset, price, timestamp);
+        e.crypto().ed25519_verify(&feeder, &payload, &signature);
+
+        let price_data = PriceData { price, timestamp };
+        storage::set_price(&e, &asset, &price_data);
+        storage::set_confidence(&e, &asset, 0);
+
+        OracleEvents::price_set(&e, asset, price, timestamp);
+    }
+
+    fn status(e: Env) -> ContractStatus {
+        if !storage::has_admin(&e) {
+            ContractStatus::Frozen
+        } else if storage::get_paused(&e) {
+            ContractStatus::Restricted
+        } else {
+            ContractStatus::Active
+        }
+    }
+
+    fn grant_role(e: Env, role: Role, member: Address) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        if role == Role::Admin {
+            panic_with_error!(&e, OracleError::InvalidInput);
+        }
+        roles::grant_role(&e, role, &member);
+
+        OracleEvents::role_granted(&e, role, member);
+    }
+
+    fn revoke_role(e: Env, role: Role, member: Address) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        if role == Role::Admin {
+            panic_with_error!(&e, OracleError::InvalidInput);
+        }
+        roles::revoke_role(&e, role, &member);
+
+        OracleEvents::role_revoked(&e, role, member);
+    }
+
+    fn has_role(e: Env, role: Role, member: Address) -> bool {
+        has_role(&e, role, &member)
+    }
+
+    fn pause(e: Env, caller: Address, pools: Vec<Address>) {
+        caller.require_auth();
+        if !has_role(&e, Role::Pauser, &caller) {
+            panic_with_error!(&e, OracleError::Unauthorized);
+        }
+
+        storage::set_paused(&e, true);
+        let oracle = e.current_contract_address();
+        for pool in pools.iter() {
+            PoolClient::new(&e, &pool).set_status_restricted(&oracle);
+        }
+
+        OracleEvents::paused(&e, caller, pools);
+    }
+
+    fn unpause(e: Env) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_paused(&e, false);
+
+        OracleEvents::unpaused(&e, admin);
+    }
+
+    fn paused(e: Env) -> bool {
+        storage::get_paused(&e)
+    }
+
+    fn base(e: Env) -> Asset {
+        storage::get_base(&e)
+    }
+
+    fn set_base(e: Env, base: Asset) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        if storage::has_base(&e) {
+            panic_with_error!(&e, OracleError::AlreadyInitialized);
+        }
+        storage::set_base(&e, &base);
+
+        OracleEvents::base_set(&e, base);
+    }
+
+    fn set_heartbeat(e: Env, asset: Asset, heartbeat: u64) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let mut heartbeats = storage::get_heartbeats(&e);
+        if heartbeat == 0 {
+            heartbeats.remove(asset.clone());
+        } else {
+            heartbeats.set(asset.clone(), heartbeat);
+        }
+        storage::set_heartbeats(&e, &heartbeats);
+
+        OracleEvents::heartbeat_set(&e, asset, heartbeat);
+    }
+
+    fn heartbeats(e: Env) -> Map<Asset, u64> {
+        storage::get_heartbeats(&e)
+    }
+
+    fn stale_assets(e: Env) -> Vec<Asset> {
+        load_stale_assets(&e)
+    }
 }
 
 #[cfg(test)]
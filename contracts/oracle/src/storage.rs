@@ -1,9 +1,14 @@
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{Address, BytesN, Env, Map, Symbol};
 use crate::{Asset, PriceData};
 
 // Storage key constants
 const ADMIN_KEY: &str = "admin";
 const PRICE_KEY: &str = "price";
+const FEEDER_KEY: &str = "feeder";
+const CONFIDENCE_KEY: &str = "conf";
+const PAUSED_KEY: &str = "paused";
+const BASE_KEY: &str = "base";
+const HEARTBEATS_KEY: &str = "heartbeats";
 
 // TTL constants (in ledgers)
 const ONE_DAY_LEDGERS: u32 = 17280; // Assuming 5 seconds per ledger
@@ -40,6 +45,79 @@ pub fn set_admin(e: &Env, admin: &Address) {
         .extend_ttl(INSTANCE_TTL, INSTANCE_BUMP);
 }
 
+/// Get the feeder key used to verify signed prices
+pub fn get_feeder(e: &Env) -> Option<BytesN<32>> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, FEEDER_KEY))
+}
+
+/// Set or remove the feeder key used to verify signed prices
+pub fn set_feeder(e: &Env, feeder: &Option<BytesN<32>>) {
+    match feeder {
+        Some(feeder) => e
+            .storage()
+            .instance()
+            .set(&Symbol::new(e, FEEDER_KEY), feeder),
+        None => e
+            .storage()
+            .instance()
+            .remove(&Symbol::new(e, FEEDER_KEY)),
+    }
+}
+
+/// Check if price publication is paused. Defaults to false.
+pub fn get_paused(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, PAUSED_KEY))
+        .unwrap_or(false)
+}
+
+/// Set if price publication is paused
+pub fn set_paused(e: &Env, paused: bool) {
+    e.storage()
+        .instance()
+        .set(&Symbol::new(e, PAUSED_KEY), &paused);
+}
+
+/// Check if the base asset has been set
+pub fn has_base(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .has(&Symbol::new(e, BASE_KEY))
+}
+
+/// Get the asset prices are quoted in. Defaults to USD.
+pub fn get_base(e: &Env) -> Asset {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, BASE_KEY))
+        .unwrap_or_else(|| Asset::Other(Symbol::new(e, "USD")))
+}
+
+/// Set the asset prices are quoted in
+pub fn set_base(e: &Env, base: &Asset) {
+    e.storage()
+        .instance()
+        .set(&Symbol::new(e, BASE_KEY), base);
+}
+
+/// Get the heartbeat of each asset, in seconds. Assets without a heartbeat are not included.
+pub fn get_heartbeats(e: &Env) -> Map<Asset, u64> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, HEARTBEATS_KEY))
+        .unwrap_or_else(|| Map::new(e))
+}
+
+/// Set the heartbeat of each asset, in seconds
+pub fn set_heartbeats(e: &Env, heartbeats: &Map<Asset, u64>) {
+    e.storage()
+        .instance()
+        .set(&Symbol::new(e, HEARTBEATS_KEY), heartbeats);
+}
+
 /// Set price data for an asset
 pub fn set_price(e: &Env, asset: &Asset, price_data: &PriceData) {
     let key = (Symbol::new(e, PRICE_KEY), asset.clone());
@@ -74,4 +152,35 @@ pub fn get_price(e: &Env, asset: &Asset) -> Option<PriceData> {
     } else {
         None
     }
-} 
\ No newline at end of file
+}
+
+/// Set the confidence of the price of an asset. Removes the entry if the confidence is 0.
+pub fn set_confidence(e: &Env, asset: &Asset, confidence: i128) {
+    let key = (Symbol::new(e, CONFIDENCE_KEY), asset.clone());
+
+    if confidence == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+
+    e.storage()
+        .persistent()
+        .set(&key, &confidence);
+
+    let price_ttl = ONE_DAY_LEDGERS * 90; // 90 days
+    let price_bump = price_ttl + ONE_DAY_LEDGERS * 10; // 100 days
+
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, price_ttl, price_bump);
+}
+
+/// Get the confidence of the price of an asset. Defaults to 0.
+pub fn get_confidence(e: &Env, asset: &Asset) -> i128 {
+    let key = (Symbol::new(e, CONFIDENCE_KEY), asset.clone());
+
+    e.storage()
+        .persistent()
+        .get::<(Symbol, Asset), i128>(&key)
+        .unwrap_or(0)
+}
@@ -1,11 +1,15 @@
 #![cfg(test)]
 
 use super::*;
+use ed25519_dalek::{Signer, SigningKey};
 use soroban_sdk::{
+    contract, contractimpl, symbol_short,
     testutils::{Address as _, Ledger, LedgerInfo},
-    Address, Env, Vec,
+    Address, BytesN, Env, Symbol, Vec,
 };
 
+extern crate std;
+
 fn create_test_env() -> (Env, Address, Address) {
     let e = Env::default();
     e.mock_all_auths();
@@ -177,4 +181,308 @@ fn test_non_admin_cannot_set_price() {
     // This should work since we're mocking all auths
     // In real scenario, this would fail without proper authorization
     client.set_price(&asset, &10_000_000);
-} 
\ No newline at end of file
+}
+
+/// Sign a price for the oracle with the feeder's key
+fn sign_price(
+    e: &Env,
+    contract_id: &Address,
+    feeder: &SigningKey,
+    asset: &Asset,
+    price: i128,
+    timestamp: u64,
+) -> BytesN<64> {
+    let payload = e.as_contract(contract_id, || {
+        signed_price_payload(e, asset, price, timestamp)
+    });
+    let payload: std::vec::Vec<u8> = payload.iter().collect();
+    BytesN::from_array(e, &feeder.sign(&payload).to_bytes())
+}
+
+#[test]
+fn test_set_price_signed() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+
+    let feeder = SigningKey::from_bytes(&[7; 32]);
+    let feeder_key = BytesN::from_array(&e, &feeder.verifying_key().to_bytes());
+    client.set_feeder(&Some(feeder_key.clone()));
+    assert_eq!(client.feeder(), Some(feeder_key));
+
+    let asset = Asset::Stellar(Address::generate(&e));
+    let timestamp = 1234567800;
+    let signature = sign_price(&e, &contract_id, &feeder, &asset, 10_500_000, timestamp);
+
+    // anyone can relay the signed price
+    e.set_auths(&[]);
+    client.set_price_signed(&asset, &10_500_000, &timestamp, &signature);
+
+    let price_data = client.lastprice(&asset).unwrap();
+    assert_eq!(price_data.price, 10_500_000);
+    assert_eq!(price_data.timestamp, timestamp);
+}
+
+#[test]
+#[should_panic]
+fn test_set_price_signed_wrong_price() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+
+    let feeder = SigningKey::from_bytes(&[7; 32]);
+    client.set_feeder(&Some(BytesN::from_array(
+        &e,
+        &feeder.verifying_key().to_bytes(),
+    )));
+
+    let asset = Asset::Stellar(Address::generate(&e));
+    let signature = sign_price(&e, &contract_id, &feeder, &asset, 10_500_000, 1234567800);
+
+    client.set_price_signed(&asset, &20_000_000, &1234567800, &signature);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_set_price_signed_replay() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+
+    let feeder = SigningKey::from_bytes(&[7; 32]);
+    client.set_feeder(&Some(BytesN::from_array(
+        &e,
+        &feeder.verifying_key().to_bytes(),
+    )));
+
+    let asset = Asset::Stellar(Address::generate(&e));
+    let old_signature = sign_price(&e, &contract_id, &feeder, &asset, 10_500_000, 1234567800);
+    let new_signature = sign_price(&e, &contract_id, &feeder, &asset, 11_000_000, 1234567850);
+
+    client.set_price_signed(&asset, &11_000_000, &1234567850, &new_signature);
+    client.set_price_signed(&asset, &10_500_000, &1234567800, &old_signature);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_set_price_signed_no_feeder() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+
+    let feeder = SigningKey::from_bytes(&[7; 32]);
+    let asset = Asset::Stellar(Address::generate(&e));
+    let signature = sign_price(&e, &contract_id, &feeder, &asset, 10_500_000, 1234567800);
+
+    client.set_price_signed(&asset, &10_500_000, &1234567800, &signature);
+}
+
+#[test]
+fn test_set_price_with_confidence() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+
+    let asset = Asset::Stellar(Address::generate(&e));
+    client.set_price_with_confidence(&asset, &10_000_000, &200_000);
+
+    let price_data = client.lastprice_v2(&asset).unwrap();
+    assert_eq!(price_data.price, 10_000_000);
+    assert_eq!(price_data.timestamp, 1234567890);
+    assert_eq!(price_data.confidence, 200_000);
+    // the SEP-40 price is unchanged
+    assert_eq!(client.lastprice(&asset).unwrap().price, 10_000_000);
+
+    // prices set without a confidence clear it
+    client.set_price(&asset, &10_100_000);
+    let price_data = client.lastprice_v2(&asset).unwrap();
+    assert_eq!(price_data.price, 10_100_000);
+    assert_eq!(price_data.confidence, 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_set_price_with_negative_confidence_fails() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+
+    let asset = Asset::Stellar(Address::generate(&e));
+    client.set_price_with_confidence(&asset, &10_000_000, &-1);
+}
+
+#[test]
+fn test_status() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    assert_eq!(client.status(), ContractStatus::Frozen);
+
+    client.init(&admin);
+    assert_eq!(client.status(), ContractStatus::Active);
+}
+
+/// A pool that records when it is restricted by its pauser
+#[contract]
+pub struct MockPool;
+
+#[contractimpl]
+impl MockPool {
+    pub fn set_pauser(e: Env, pauser: Address) {
+        e.storage().instance().set(&symbol_short!("pauser"), &pauser);
+    }
+
+    pub fn set_status_restricted(e: Env, caller: Address) -> u32 {
+        caller.require_auth();
+        let pauser: Address = e.storage().instance().get(&symbol_short!("pauser")).unwrap();
+        assert_eq!(caller, pauser);
+        e.storage().instance().set(&symbol_short!("status"), &2u32);
+        2
+    }
+
+    pub fn status(e: Env) -> u32 {
+        e.storage().instance().get(&symbol_short!("status")).unwrap_or(0)
+    }
+}
+
+#[test]
+fn test_pause_restricts_pools() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+
+    let pauser = Address::generate(&e);
+    client.grant_role(&Role::Pauser, &pauser);
+    assert!(client.has_role(&Role::Pauser, &pauser));
+    assert!(!client.has_role(&Role::Risk, &pauser));
+
+    let pool_id = e.register(MockPool, ());
+    let pool_client = MockPoolClient::new(&e, &pool_id);
+    pool_client.set_pauser(&contract_id);
+
+    client.pause(&pauser, &Vec::from_array(&e, [pool_id]));
+
+    assert!(client.paused());
+    assert_eq!(client.status(), ContractStatus::Restricted);
+    assert_eq!(pool_client.status(), 2);
+
+    client.unpause();
+    assert!(!client.paused());
+    assert_eq!(client.status(), ContractStatus::Active);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn test_set_price_while_paused_fails() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+    client.pause(&admin, &Vec::new(&e));
+
+    let asset = Asset::Stellar(Address::generate(&e));
+    client.set_price(&asset, &10_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_pause_without_role_fails() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+
+    let risk = Address::generate(&e);
+    client.grant_role(&Role::Risk, &risk);
+    client.revoke_role(&Role::Pauser, &risk);
+    client.pause(&risk, &Vec::new(&e));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_grant_admin_role_fails() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+    client.grant_role(&Role::Admin, &Address::generate(&e));
+}
+
+#[test]
+fn test_set_base() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+    assert_eq!(client.base(), Asset::Other(Symbol::new(&e, "USD")));
+
+    let eur = Asset::Other(Symbol::new(&e, "EUR"));
+    client.set_base(&eur);
+    assert_eq!(client.base(), eur);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_set_base_twice_fails() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+    client.set_base(&Asset::Other(Symbol::new(&e, "EUR")));
+    client.set_base(&Asset::Other(Symbol::new(&e, "USD")));
+}
+
+#[test]
+fn test_stale_assets() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+
+    let xlm = Asset::Other(Symbol::new(&e, "XLM"));
+    let usdc = Asset::Other(Symbol::new(&e, "USDC"));
+    client.set_heartbeat(&xlm, &300);
+    client.set_heartbeat(&usdc, &3600);
+    assert_eq!(client.heartbeats().get(xlm.clone()), Some(300));
+    assert_eq!(client.stale_assets(), Vec::from_array(&e, [xlm.clone(), usdc.clone()]));
+
+    client.set_prices(
+        &Vec::from_array(&e, [xlm.clone(), usdc.clone()]),
+        &Vec::from_array(&e, [1_200_000i128, 10_000_000i128]),
+    );
+    assert_eq!(client.stale_assets().len(), 0);
+
+    e.ledger().set_timestamp(1234567890 + 300);
+    assert_eq!(client.stale_assets(), Vec::from_array(&e, [xlm.clone()]));
+
+    // the lapsed asset can be refreshed on its own
+    client.set_prices(&Vec::from_array(&e, [xlm.clone()]), &Vec::from_array(&e, [1_300_000i128]));
+    assert_eq!(client.stale_assets().len(), 0);
+
+    client.set_heartbeat(&xlm, &0);
+    assert_eq!(client.heartbeats().get(xlm), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_set_prices_omitting_lapsed_asset_fails() {
+    let (e, admin, contract_id) = create_test_env();
+    let client = TrustBridgeOracleClient::new(&e, &contract_id);
+
+    client.init(&admin);
+
+    let xlm = Asset::Other(Symbol::new(&e, "XLM"));
+    let usdc = Asset::Other(Symbol::new(&e, "USDC"));
+    client.set_heartbeat(&xlm, &300);
+    client.set_prices(&Vec::from_array(&e, [xlm]), &Vec::from_array(&e, [1_200_000i128]));
+
+    e.ledger().set_timestamp(1234567890 + 300);
+    client.set_prices(&Vec::from_array(&e, [usdc]), &Vec::from_array(&e, [10_000_000i128]));
+}
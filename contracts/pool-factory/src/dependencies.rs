@@ -0,0 +1,26 @@
+/**
+ * Partial clients for the backstop and pool contracts
+ */
+use soroban_sdk::{contractclient, Address, BytesN, Env};
+
+#[allow(dead_code)]
+#[contractclient(name = "BackstopClient")]
+pub trait Backstop {
+    /// (Only Pool Factory) Deposit backstop tokens from `from` into the backstop of a pool that is
+    /// being deployed by the pool factory
+    ///
+    /// Returns the number of backstop pool shares minted
+    fn factory_deposit(e: Env, from: Address, pool_address: Address, amount: i128) -> i128;
+
+    /// (Only Pool Factory) Approve another pool factory to deploy pools with the given WASM hash,
+    /// or revoke its approval
+    fn set_approved_factory(e: Env, factory: Address, pool_hash: Option<BytesN<32>>);
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolClient")]
+pub trait Pool {
+    /// (Risk role only) Restrict the assets the pool can add as reserves to those allowed by
+    /// `allowlist`
+    fn set_reserve_allowlist(e: Env, caller: Address, allowlist: Address);
+}
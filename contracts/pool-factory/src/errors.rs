@@ -12,4 +12,5 @@ pub enum PoolFactoryError {
 
     // Pool Factory
     InvalidPoolInitArgs = 1300,
+    InvalidDeployConfig = 1301,
 }
@@ -1,4 +1,6 @@
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol};
+
+use crate::storage::DeployConfig;
 
 pub struct PoolFactoryEvents {}
 
@@ -14,4 +16,75 @@ impl PoolFactoryEvents {
         let topics = (Symbol::new(e, "deploy"),);
         e.events().publish(topics, pool_address);
     }
+
+    /// Emitted when a pool is deployed by the factory, with the metadata it was deployed with
+    ///
+    /// - topics - `["pool_metadata", pool_address: Address]`
+    /// - data - `name: String`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `name` - The name of the pool
+    pub fn pool_metadata(e: &Env, pool_address: Address, name: String) {
+        let topics = (Symbol::new(e, "pool_metadata"), pool_address);
+        e.events().publish(topics, name);
+    }
+
+    /// Emitted when the admin is changed
+    ///
+    /// - topics - `["set_admin", old_admin: Address]`
+    /// - data - `new_admin: Address`
+    ///
+    /// ### Arguments
+    /// * `old_admin` - The previous admin
+    /// * `new_admin` - The new admin
+    pub fn set_admin(e: &Env, old_admin: Address, new_admin: Address) {
+        let topics = (Symbol::new(e, "set_admin"), old_admin);
+        e.events().publish(topics, new_admin);
+    }
+
+    /// Emitted when the deployment configuration is changed
+    ///
+    /// - topics - `["set_deploy_config", admin: Address]`
+    /// - data - `DeployConfig`
+    ///
+    /// ### Arguments
+    /// * `admin` - The admin
+    /// * `deploy_config` - The new deployment configuration
+    pub fn set_deploy_config(e: &Env, admin: Address, deploy_config: DeployConfig) {
+        let topics = (Symbol::new(e, "set_deploy_config"), admin);
+        e.events().publish(topics, deploy_config);
+    }
+
+    /// Emitted when an asset is added to or removed from the reserve allowlist
+    ///
+    /// - topics - `["set_asset_allowed", asset: Address]`
+    /// - data - `allowed: bool`
+    ///
+    /// ### Arguments
+    /// * `asset` - The asset
+    /// * `allowed` - True if the asset was added to the allowlist, false if it was removed
+    pub fn set_asset_allowed(e: &Env, asset: Address, allowed: bool) {
+        let topics = (Symbol::new(e, "set_asset_allowed"), asset);
+        e.events().publish(topics, allowed);
+    }
+
+    /// Emitted when another pool factory is approved with the backstop or its approval is revoked
+    ///
+    /// - topics - `["set_approved_factory", admin: Address, factory: Address]`
+    /// - data - `pool_hash: Option<BytesN<32>>`
+    ///
+    /// ### Arguments
+    /// * `admin` - The admin
+    /// * `factory` - The pool factory
+    /// * `pool_hash` - The WASM hash of the pools the factory is approved to deploy, or None if revoked
+    pub fn set_approved_factory(
+        e: &Env,
+        admin: Address,
+        factory: Address,
+        pool_hash: Option<BytesN<32>>,
+    ) {
+        let topics = (Symbol::new(e, "set_approved_factory"), admin, factory);
+        e.events().publish(topics, pool_hash);
+    }
 }
@@ -3,6 +3,7 @@
 #[cfg(any(test, feature = "testutils"))]
 extern crate std;
 
+mod dependencies;
 mod errors;
 mod events;
 mod pool_factory;
@@ -11,4 +12,4 @@ mod test;
 
 pub use errors::PoolFactoryError;
 pub use pool_factory::*;
-pub use storage::{PoolFactoryDataKey, PoolInitMeta};
+pub use storage::{DeployConfig, PoolFactoryDataKey, PoolInitMeta};
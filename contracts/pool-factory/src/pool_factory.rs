@@ -1,14 +1,14 @@
 use crate::{
+    dependencies::{BackstopClient, PoolClient},
     errors::PoolFactoryError,
     events::PoolFactoryEvents,
-    storage::{self, PoolInitMeta},
+    storage::{self, DeployConfig, PoolInitMeta},
 };
 use soroban_sdk::{
-    contract, contractclient, contractimpl, panic_with_error, Address, Bytes, BytesN, Env, IntoVal,
-    String, symbol_short, vec, Vec,
+    contract, contractclient, contractimpl, panic_with_error, token::TokenClient, Address, Bytes,
+    BytesN, Env, IntoVal, String, symbol_short, vec, Vec,
 };
-
-const SCALAR_7: u32 = 1_0000000;
+use trustbridge_common::{ContractStatus, SCALAR_7};
 
 #[contract]
 pub struct PoolFactoryContract;
@@ -17,6 +17,9 @@ pub struct PoolFactoryContract;
 pub trait PoolFactory {
     /// Deploys and initializes a lending pool
     ///
+    /// If a deployment configuration is set, the deployment fee is transferred from `admin` to the
+    /// treasury, and `admin` deposits the minimum backstop deposit into the new pool's backstop.
+    ///
     /// ### Arguments
     /// * `admin` - The admin address for the pool
     /// * `name` - The name of the pool
@@ -36,6 +39,28 @@ pub trait PoolFactory {
         min_collateral: i128,
     ) -> Address;
 
+    /// Deploys and initializes a lending pool in verified mode. A verified pool can only add
+    /// reserves for assets on the factory's reserve allowlist.
+    ///
+    /// ### Arguments
+    /// * `admin` - The admin address for the pool
+    /// * `name` - The name of the pool
+    /// * `salt` - The salt for the pool address
+    /// * `oracle` - The oracle address for the pool
+    /// * `backstop_take_rate` - The backstop take rate for the pool (7 decimals)
+    /// * `max_positions` - The maximum user positions supported by the pool
+    /// * `min_collateral` - The minimum collateral required for a borrow position (oracle decimals)
+    fn deploy_verified(
+        e: Env,
+        admin: Address,
+        name: String,
+        salt: BytesN<32>,
+        oracle: Address,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+    ) -> Address;
+
     /// Checks if contract address was deployed by the factory
     ///
     /// Returns true if pool was deployed by factory and false otherwise
@@ -43,6 +68,58 @@ pub trait PoolFactory {
     /// ### Arguments
     /// * `pool_id` - The contract address to be checked
     fn is_pool(e: Env, pool_id: Address) -> bool;
+
+    /// Checks if contract address was deployed by the factory in verified mode
+    ///
+    /// ### Arguments
+    /// * `pool_id` - The contract address to be checked
+    fn is_verified(e: Env, pool_id: Address) -> bool;
+
+    /// (Admin only) Add or remove an asset from the reserve allowlist used by verified pools
+    ///
+    /// ### Arguments
+    /// * `asset` - The asset contract address
+    /// * `allowed` - True to allow the asset, false to remove it from the allowlist
+    fn set_asset_allowed(e: Env, asset: Address, allowed: bool);
+
+    /// Checks if an asset is on the reserve allowlist
+    ///
+    /// ### Arguments
+    /// * `asset` - The asset contract address
+    fn is_asset_allowed(e: Env, asset: Address) -> bool;
+
+    /// (Admin only) Set a new address as the admin of the factory
+    ///
+    /// ### Arguments
+    /// * `new_admin` - The new admin address
+    fn set_admin(e: Env, new_admin: Address);
+
+    /// (Admin only) Set the fee and minimum backstop deposit required to deploy a pool
+    ///
+    /// ### Arguments
+    /// * `deploy_config` - The new deployment configuration
+    ///
+    /// ### Panics
+    /// If the fee or minimum backstop deposit is negative
+    fn set_deploy_config(e: Env, deploy_config: DeployConfig);
+
+    /// Fetch the deployment configuration, if one is set
+    fn get_deploy_config(e: Env) -> Option<DeployConfig>;
+
+    /// Fetch the WASM hash of the pools deployed by the factory
+    fn pool_hash(e: Env) -> BytesN<32>;
+
+    /// (Admin only) Approve another pool factory with the backstop to deploy pools with the given
+    /// WASM hash, or revoke its approval
+    ///
+    /// ### Arguments
+    /// * `factory` - The pool factory
+    /// * `pool_hash` - The WASM hash of the pools the factory is approved to deploy, or None to revoke
+    fn set_approved_factory(e: Env, factory: Address, pool_hash: Option<BytesN<32>>);
+
+    /// Fetch the status of the pool factory. The factory has no pause switch, so it is always
+    /// Active.
+    fn status(e: Env) -> ContractStatus;
 }
 
 #[contractimpl]
@@ -50,8 +127,10 @@ impl PoolFactoryContract {
     /// Construct the pool factory contract
     ///
     /// ### Arguments
+    /// * `admin` - The admin of the factory
     /// * `pool_init_meta` - The pool initialization metadata    
-    pub fn __constructor(e: Env, pool_init_meta: PoolInitMeta) {
+    pub fn __constructor(e: Env, admin: Address, pool_init_meta: PoolInitMeta) {
+        storage::set_admin(&e, &admin);
         storage::set_pool_init_meta(&e, &pool_init_meta);
     }
 }
@@ -73,7 +152,7 @@ impl PoolFactory for PoolFactoryContract {
         let pool_init_meta = storage::get_pool_init_meta(&e);
 
         // verify backstop take rate is within [0,1) with 7 decimals
-        if backstop_take_rate >= SCALAR_7 {
+        if backstop_take_rate as i128 >= SCALAR_7 {
             panic_with_error!(&e, PoolFactoryError::InvalidPoolInitArgs);
         }
 
@@ -89,6 +168,17 @@ impl PoolFactory for PoolFactoryContract {
             panic_with_error!(&e, PoolFactoryError::InvalidPoolInitArgs);
         }
 
+        let deploy_config = storage::get_deploy_config(&e);
+        if let Some(deploy_config) = &deploy_config {
+            if deploy_config.fee > 0 {
+                TokenClient::new(&e, &deploy_config.fee_token).transfer(
+                    &admin,
+                    &deploy_config.treasury,
+                    &deploy_config.fee,
+                );
+            }
+        }
+
         let mut as_u8s: [u8; 56] = [0; 56];
         admin.to_string().copy_into_slice(&mut as_u8s);
         let mut salt_as_bytes: Bytes = salt.into_val(&e);
@@ -115,12 +205,114 @@ impl PoolFactory for PoolFactoryContract {
 
         storage::set_deployed(&e, &pool_address);
 
+        if let Some(deploy_config) = deploy_config {
+            if deploy_config.min_backstop_deposit > 0 {
+                BackstopClient::new(&e, &pool_init_meta.backstop).factory_deposit(
+                    &admin,
+                    &pool_address,
+                    &deploy_config.min_backstop_deposit,
+                );
+            }
+        }
+
+        PoolFactoryEvents::pool_metadata(&e, pool_address.clone(), name);
         PoolFactoryEvents::deploy(&e, pool_address.clone());
         pool_address
     }
 
+    fn deploy_verified(
+        e: Env,
+        admin: Address,
+        name: String,
+        salt: BytesN<32>,
+        oracle: Address,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+    ) -> Address {
+        let pool_address = Self::deploy(
+            e.clone(),
+            admin.clone(),
+            name,
+            salt,
+            oracle,
+            backstop_take_rate,
+            max_positions,
+            min_collateral,
+        );
+
+        storage::set_verified(&e, &pool_address);
+        PoolClient::new(&e, &pool_address)
+            .set_reserve_allowlist(&admin, &e.current_contract_address());
+        pool_address
+    }
+
     fn is_pool(e: Env, pool_address: Address) -> bool {
         storage::extend_instance(&e);
         storage::is_deployed(&e, &pool_address)
     }
+
+    fn is_verified(e: Env, pool_address: Address) -> bool {
+        storage::extend_instance(&e);
+        storage::is_verified(&e, &pool_address)
+    }
+
+    fn set_asset_allowed(e: Env, asset: Address, allowed: bool) {
+        storage::extend_instance(&e);
+        storage::get_admin(&e).require_auth();
+
+        storage::set_asset_allowed(&e, &asset, allowed);
+        PoolFactoryEvents::set_asset_allowed(&e, asset, allowed);
+    }
+
+    fn is_asset_allowed(e: Env, asset: Address) -> bool {
+        storage::extend_instance(&e);
+        storage::is_asset_allowed(&e, &asset)
+    }
+
+    fn set_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        new_admin.require_auth();
+
+        storage::set_admin(&e, &new_admin);
+        PoolFactoryEvents::set_admin(&e, admin, new_admin);
+    }
+
+    fn set_deploy_config(e: Env, deploy_config: DeployConfig) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        if deploy_config.fee < 0 || deploy_config.min_backstop_deposit < 0 {
+            panic_with_error!(&e, PoolFactoryError::InvalidDeployConfig);
+        }
+
+        storage::set_deploy_config(&e, &deploy_config);
+        PoolFactoryEvents::set_deploy_config(&e, admin, deploy_config);
+    }
+
+    fn get_deploy_config(e: Env) -> Option<DeployConfig> {
+        storage::get_deploy_config(&e)
+    }
+
+    fn pool_hash(e: Env) -> BytesN<32> {
+        storage::extend_instance(&e);
+        storage::get_pool_init_meta(&e).pool_hash
+    }
+
+    fn set_approved_factory(e: Env, factory: Address, pool_hash: Option<BytesN<32>>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let backstop = storage::get_pool_init_meta(&e).backstop;
+        BackstopClient::new(&e, &backstop).set_approved_factory(&factory, &pool_hash);
+        PoolFactoryEvents::set_approved_factory(&e, admin, factory, pool_hash);
+    }
+
+    fn status(_e: Env) -> ContractStatus {
+        ContractStatus::Active
+    }
 }
@@ -14,6 +14,8 @@ const LEDGER_BUMP_USER: u32 = LEDGER_THRESHOLD_USER + 20 * ONE_DAY_LEDGERS; // ~
 #[contracttype]
 pub enum PoolFactoryDataKey {
     Contracts(Address),
+    Verified(Address),
+    AllowedAsset(Address),
 }
 
 #[derive(Clone)]
@@ -24,6 +26,16 @@ pub struct PoolInitMeta {
     pub blnd_id: Address,
 }
 
+/// The fee and backstop deposit required to deploy a pool through the factory
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DeployConfig {
+    pub fee_token: Address,         // the token the deployment fee is paid in
+    pub fee: i128,                  // the deployment fee, paid by the pool admin
+    pub treasury: Address,          // the address deployment fees are sent to
+    pub min_backstop_deposit: i128, // the backstop tokens the pool admin must deposit on deployment
+}
+
 /// Bump the instance rent for the contract
 pub fn extend_instance(e: &Env) {
     e.storage()
@@ -31,6 +43,41 @@ pub fn extend_instance(e: &Env) {
         .extend_ttl(LEDGER_THRESHOLD_INSTANCE, LEDGER_BUMP_INSTANCE);
 }
 
+/// Fetch the admin address
+pub fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, "Admin"))
+        .unwrap_optimized()
+}
+
+/// Set the admin address
+///
+/// ### Arguments
+/// * `admin` - The admin address
+pub fn set_admin(e: &Env, admin: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, "Admin"), admin)
+}
+
+/// Fetch the deployment configuration, if one is set
+pub fn get_deploy_config(e: &Env) -> Option<DeployConfig> {
+    e.storage()
+        .instance()
+        .get::<Symbol, DeployConfig>(&Symbol::new(e, "DeployCfg"))
+}
+
+/// Set the deployment configuration
+///
+/// ### Arguments
+/// * `deploy_config` - The deployment configuration
+pub fn set_deploy_config(e: &Env, deploy_config: &DeployConfig) {
+    e.storage()
+        .instance()
+        .set::<Symbol, DeployConfig>(&Symbol::new(e, "DeployCfg"), deploy_config)
+}
+
 /// Fetch the pool initialization metadata
 pub fn get_pool_init_meta(e: &Env) -> PoolInitMeta {
     e.storage()
@@ -82,3 +129,76 @@ pub fn set_deployed(e: &Env, contract_id: &Address) {
         .persistent()
         .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
 }
+
+/// Check if a given pool was deployed by the factory in verified mode
+///
+/// ### Arguments
+/// * `pool` - The pool to check
+pub fn is_verified(e: &Env, pool: &Address) -> bool {
+    let key = PoolFactoryDataKey::Verified(pool.clone());
+    if let Some(result) = e
+        .storage()
+        .persistent()
+        .get::<PoolFactoryDataKey, bool>(&key)
+    {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+        result
+    } else {
+        false
+    }
+}
+
+/// Set a pool as having been deployed by the factory in verified mode
+///
+/// ### Arguments
+/// * `pool` - The pool that was deployed in verified mode
+pub fn set_verified(e: &Env, pool: &Address) {
+    let key = PoolFactoryDataKey::Verified(pool.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolFactoryDataKey, bool>(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Check if an asset is on the reserve allowlist
+///
+/// ### Arguments
+/// * `asset` - The asset to check
+pub fn is_asset_allowed(e: &Env, asset: &Address) -> bool {
+    let key = PoolFactoryDataKey::AllowedAsset(asset.clone());
+    if let Some(result) = e
+        .storage()
+        .persistent()
+        .get::<PoolFactoryDataKey, bool>(&key)
+    {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+        result
+    } else {
+        false
+    }
+}
+
+/// Add or remove an asset from the reserve allowlist
+///
+/// ### Arguments
+/// * `asset` - The asset
+/// * `allowed` - True to add the asset to the allowlist, false to remove it
+pub fn set_asset_allowed(e: &Env, asset: &Address, allowed: bool) {
+    let key = PoolFactoryDataKey::AllowedAsset(asset.clone());
+    if allowed {
+        e.storage()
+            .persistent()
+            .set::<PoolFactoryDataKey, bool>(&key, &true);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
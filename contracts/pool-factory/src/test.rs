@@ -1,11 +1,15 @@
 #![cfg(test)]
 
 use soroban_sdk::{
-    testutils::{Address as _, BytesN as _, Events},
+    contract, contractimpl,
+    testutils::{Address as _, AuthorizedFunction, BytesN as _, Events},
+    token::{StellarAssetClient, TokenClient},
     vec, Address, BytesN, Env, IntoVal, String, Symbol,
 };
 
-use crate::{PoolFactoryClient, PoolFactoryContract, PoolInitMeta};
+use trustbridge_common::ContractStatus;
+
+use crate::{DeployConfig, PoolFactoryClient, PoolFactoryContract, PoolInitMeta};
 
 mod pool {
     soroban_sdk::contractimport!(file = "../target/wasm32-unknown-unknown/optimized/pool.wasm");
@@ -33,7 +37,10 @@ fn test_pool_factory() {
         pool_hash: wasm_hash.clone(),
         blnd_id: blnd_id.clone(),
     };
-    let pool_factory_address = e.register(PoolFactoryContract {}, (pool_init_meta,));
+    let pool_factory_address = e.register(
+        PoolFactoryContract {},
+        (Address::generate(&e), pool_init_meta),
+    );
     let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
 
     let name1 = String::from_str(&e, "pool1");
@@ -50,7 +57,24 @@ fn test_pool_factory() {
         &min_collateral,
     );
 
-    let event = vec![&e, e.events().all().last_unchecked()];
+    let events = e.events().all();
+    let event = vec![&e, events.get_unchecked(events.len() - 2)];
+    assert_eq!(
+        event,
+        vec![
+            &e,
+            (
+                pool_factory_address.clone(),
+                (
+                    Symbol::new(&e, "pool_metadata"),
+                    deployed_pool_address_1.clone()
+                )
+                    .into_val(&e),
+                name1.to_val()
+            )
+        ]
+    );
+    let event = vec![&e, events.last_unchecked()];
     assert_eq!(
         event,
         vec![
@@ -133,7 +157,10 @@ fn test_pool_factory_invalid_pool_init_args_backstop_rate() {
         pool_hash: wasm_hash.clone(),
         blnd_id: blnd_id.clone(),
     };
-    let pool_factory_address = e.register(PoolFactoryContract {}, (pool_init_meta,));
+    let pool_factory_address = e.register(
+        PoolFactoryContract {},
+        (Address::generate(&e), pool_init_meta),
+    );
     let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
 
     let bombadil = Address::generate(&e);
@@ -171,7 +198,10 @@ fn test_pool_factory_invalid_pool_init_args_max_positions() {
         pool_hash: wasm_hash.clone(),
         blnd_id: blnd_id.clone(),
     };
-    let pool_factory_address = e.register(PoolFactoryContract {}, (pool_init_meta,));
+    let pool_factory_address = e.register(
+        PoolFactoryContract {},
+        (Address::generate(&e), pool_init_meta),
+    );
     let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
 
     let bombadil = Address::generate(&e);
@@ -210,7 +240,10 @@ fn test_pool_factory_invalid_pool_init_args_max_positions_large() {
         pool_hash: wasm_hash.clone(),
         blnd_id: blnd_id.clone(),
     };
-    let pool_factory_address = e.register(PoolFactoryContract {}, (pool_init_meta,));
+    let pool_factory_address = e.register(
+        PoolFactoryContract {},
+        (Address::generate(&e), pool_init_meta),
+    );
     let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
 
     let bombadil = Address::generate(&e);
@@ -249,7 +282,10 @@ fn test_pool_factory_invalid_pool_init_args_min_collateral() {
         pool_hash: wasm_hash.clone(),
         blnd_id: blnd_id.clone(),
     };
-    let pool_factory_address = e.register(PoolFactoryContract {}, (pool_init_meta,));
+    let pool_factory_address = e.register(
+        PoolFactoryContract {},
+        (Address::generate(&e), pool_init_meta),
+    );
     let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
 
     let bombadil = Address::generate(&e);
@@ -295,7 +331,10 @@ fn test_pool_factory_frontrun_protection() {
         pool_hash: wasm_hash.clone(),
         blnd_id: blnd_id.clone(),
     };
-    let pool_factory_address = e.register(PoolFactoryContract {}, (pool_init_meta,));
+    let pool_factory_address = e.register(
+        PoolFactoryContract {},
+        (Address::generate(&e), pool_init_meta),
+    );
     let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
 
     let name1 = String::from_str(&e, "pool1");
@@ -328,3 +367,293 @@ fn test_pool_factory_frontrun_protection() {
     assert!(pool_factory_client.is_pool(&deployed_pool_address_sauron));
     assert!(pool_factory_client.is_pool(&deployed_pool_address_bombadil));
 }
+
+/// Records the backstop deposits made by the pool factory
+#[contract]
+pub struct MockBackstop;
+
+#[contractimpl]
+impl MockBackstop {
+    pub fn factory_deposit(e: Env, from: Address, pool_address: Address, amount: i128) -> i128 {
+        from.require_auth();
+        e.storage().instance().set(&pool_address, &(from, amount));
+        amount
+    }
+
+    pub fn set_approved_factory(e: Env, factory: Address, pool_hash: Option<BytesN<32>>) {
+        e.storage().instance().set(&factory, &pool_hash);
+    }
+}
+
+#[test]
+fn test_pool_factory_deploy_config() {
+    let e = Env::default();
+    e.cost_estimate().budget().reset_unlimited();
+    e.mock_all_auths();
+
+    let wasm_hash = e.deployer().upload_contract_wasm(pool::WASM);
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let treasury = Address::generate(&e);
+
+    let oracle = Address::generate(&e);
+    let backstop_id = e.register(MockBackstop {}, ());
+    let blnd_id = Address::generate(&e);
+    let fee_token = e
+        .register_stellar_asset_contract_v2(bombadil.clone())
+        .address();
+    StellarAssetClient::new(&e, &fee_token).mint(&frodo, &150_0000000);
+
+    let pool_init_meta = PoolInitMeta {
+        backstop: backstop_id.clone(),
+        pool_hash: wasm_hash.clone(),
+        blnd_id: blnd_id.clone(),
+    };
+    let pool_factory_address =
+        e.register(PoolFactoryContract {}, (bombadil.clone(), pool_init_meta));
+    let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
+    assert_eq!(pool_factory_client.get_deploy_config(), None);
+
+    let deploy_config = DeployConfig {
+        fee_token: fee_token.clone(),
+        fee: 100_0000000,
+        treasury: treasury.clone(),
+        min_backstop_deposit: 25_0000000,
+    };
+    pool_factory_client.set_deploy_config(&deploy_config);
+    assert_eq!(
+        e.auths()[0].1.function,
+        AuthorizedFunction::Contract((
+            pool_factory_address.clone(),
+            Symbol::new(&e, "set_deploy_config"),
+            vec![&e, deploy_config.clone().into_val(&e)],
+        ))
+    );
+    assert_eq!(e.auths()[0].0, bombadil);
+    assert_eq!(
+        pool_factory_client.get_deploy_config(),
+        Some(deploy_config.clone())
+    );
+
+    let pool_address = pool_factory_client.deploy(
+        &frodo,
+        &String::from_str(&e, "pool1"),
+        &BytesN::<32>::random(&e),
+        &oracle,
+        &0_1000000,
+        &6,
+        &1_0000000,
+    );
+
+    let fee_token_client = TokenClient::new(&e, &fee_token);
+    assert_eq!(fee_token_client.balance(&treasury), 100_0000000);
+    assert_eq!(fee_token_client.balance(&frodo), 50_0000000);
+    e.as_contract(&backstop_id, || {
+        assert_eq!(
+            e.storage()
+                .instance()
+                .get::<_, (Address, i128)>(&pool_address)
+                .unwrap(),
+            (frodo.clone(), 25_0000000)
+        );
+    });
+    assert!(pool_factory_client.is_pool(&pool_address));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1301)")]
+fn test_pool_factory_set_deploy_config_negative_fee() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let pool_init_meta = PoolInitMeta {
+        backstop: Address::generate(&e),
+        pool_hash: BytesN::<32>::random(&e),
+        blnd_id: Address::generate(&e),
+    };
+    let pool_factory_address = e.register(
+        PoolFactoryContract {},
+        (Address::generate(&e), pool_init_meta),
+    );
+    let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
+
+    pool_factory_client.set_deploy_config(&DeployConfig {
+        fee_token: Address::generate(&e),
+        fee: -1,
+        treasury: Address::generate(&e),
+        min_backstop_deposit: 0,
+    });
+}
+
+#[test]
+fn test_pool_factory_set_admin() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let pool_init_meta = PoolInitMeta {
+        backstop: Address::generate(&e),
+        pool_hash: BytesN::<32>::random(&e),
+        blnd_id: Address::generate(&e),
+    };
+    let pool_factory_address =
+        e.register(PoolFactoryContract {}, (bombadil.clone(), pool_init_meta));
+    let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
+
+    pool_factory_client.set_admin(&frodo);
+    let auths = e.auths();
+    assert_eq!(auths.len(), 2);
+    assert_eq!(auths[0].0, bombadil);
+    assert_eq!(auths[1].0, frodo);
+
+    // the new admin controls the deployment configuration
+    let deploy_config = DeployConfig {
+        fee_token: Address::generate(&e),
+        fee: 0,
+        treasury: Address::generate(&e),
+        min_backstop_deposit: 0,
+    };
+    pool_factory_client.set_deploy_config(&deploy_config);
+    assert_eq!(e.auths()[0].0, frodo);
+}
+
+#[test]
+fn test_pool_factory_status() {
+    let e = Env::default();
+
+    let pool_init_meta = PoolInitMeta {
+        backstop: Address::generate(&e),
+        pool_hash: BytesN::<32>::random(&e),
+        blnd_id: Address::generate(&e),
+    };
+    let pool_factory_address = e.register(
+        PoolFactoryContract {},
+        (Address::generate(&e), pool_init_meta),
+    );
+    let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
+
+    assert_eq!(pool_factory_client.status(), ContractStatus::Active);
+}
+
+#[test]
+fn test_pool_factory_set_approved_factory() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let bombadil = Address::generate(&e);
+    let backstop_id = e.register(MockBackstop {}, ());
+    let pool_hash = BytesN::<32>::random(&e);
+    let pool_init_meta = PoolInitMeta {
+        backstop: backstop_id.clone(),
+        pool_hash: pool_hash.clone(),
+        blnd_id: Address::generate(&e),
+    };
+    let pool_factory_address =
+        e.register(PoolFactoryContract {}, (bombadil.clone(), pool_init_meta));
+    let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
+    assert_eq!(pool_factory_client.pool_hash(), pool_hash);
+
+    let approved_factory = Address::generate(&e);
+    let approved_hash = BytesN::<32>::random(&e);
+    pool_factory_client.set_approved_factory(&approved_factory, &Some(approved_hash.clone()));
+    assert_eq!(e.auths()[0].0, bombadil);
+    e.as_contract(&backstop_id, || {
+        assert_eq!(
+            e.storage()
+                .instance()
+                .get::<_, Option<BytesN<32>>>(&approved_factory)
+                .unwrap(),
+            Some(approved_hash.clone())
+        );
+    });
+
+    pool_factory_client.set_approved_factory(&approved_factory, &None);
+    e.as_contract(&backstop_id, || {
+        assert_eq!(
+            e.storage()
+                .instance()
+                .get::<_, Option<BytesN<32>>>(&approved_factory)
+                .unwrap(),
+            None
+        );
+    });
+}
+
+#[test]
+fn test_pool_factory_deploy_verified() {
+    let e = Env::default();
+    e.cost_estimate().budget().reset_unlimited();
+    e.mock_all_auths();
+
+    let wasm_hash = e.deployer().upload_contract_wasm(pool::WASM);
+
+    let bombadil = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let asset = Address::generate(&e);
+
+    let pool_init_meta = PoolInitMeta {
+        backstop: Address::generate(&e),
+        pool_hash: wasm_hash.clone(),
+        blnd_id: Address::generate(&e),
+    };
+    let pool_factory_address =
+        e.register(PoolFactoryContract {}, (bombadil.clone(), pool_init_meta));
+    let pool_factory_client = PoolFactoryClient::new(&e, &pool_factory_address);
+
+    assert!(!pool_factory_client.is_asset_allowed(&asset));
+    pool_factory_client.set_asset_allowed(&asset, &true);
+    assert_eq!(e.auths()[0].0, bombadil);
+    assert!(pool_factory_client.is_asset_allowed(&asset));
+
+    let event = vec![&e, e.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &e,
+            (
+                pool_factory_address.clone(),
+                (Symbol::new(&e, "set_asset_allowed"), asset.clone()).into_val(&e),
+                true.into_val(&e)
+            )
+        ]
+    );
+
+    let oracle = Address::generate(&e);
+    let verified_pool = pool_factory_client.deploy_verified(
+        &frodo,
+        &String::from_str(&e, "verified"),
+        &BytesN::<32>::random(&e),
+        &oracle,
+        &0_1000000,
+        &6,
+        &1_0000000,
+    );
+    let unverified_pool = pool_factory_client.deploy(
+        &frodo,
+        &String::from_str(&e, "unverified"),
+        &BytesN::<32>::random(&e),
+        &oracle,
+        &0_1000000,
+        &6,
+        &1_0000000,
+    );
+
+    assert!(pool_factory_client.is_pool(&verified_pool));
+    assert!(pool_factory_client.is_verified(&verified_pool));
+    assert!(pool_factory_client.is_pool(&unverified_pool));
+    assert!(!pool_factory_client.is_verified(&unverified_pool));
+    e.as_contract(&verified_pool, || {
+        assert_eq!(
+            e.storage()
+                .instance()
+                .get::<_, Address>(&Symbol::new(&e, "ResAllowLst"))
+                .unwrap(),
+            pool_factory_address.clone()
+        );
+    });
+
+    pool_factory_client.set_asset_allowed(&asset, &false);
+    assert!(!pool_factory_client.is_asset_allowed(&asset));
+}
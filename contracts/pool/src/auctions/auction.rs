@@ -1,18 +1,14 @@
 use crate::{
     constants::SCALAR_7,
     errors::PoolError,
-    pool::{Pool, User},
+    pool::{accrue_lot_coverage, Pool, PositionData, Positions, User},
     storage,
 };
 use cast::i128;
 use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{contracttype, map, panic_with_error, Address, Env, Map, Vec};
 
-use super::{
-    backstop_interest_auction::{create_interest_auction_data, fill_interest_auction},
-    bad_debt_auction::{create_bad_debt_auction_data, fill_bad_debt_auction},
-    user_liquidation_auction::{create_user_liq_auction_data, fill_user_liq_auction},
-};
+use super::load_auction_strategy;
 
 #[derive(Clone, PartialEq)]
 #[repr(u32)]
@@ -83,16 +79,46 @@ pub fn create_auction(
     require_unique_addresses(e, bid);
     require_unique_addresses(e, lot);
     // panics if auction_type parameter is not valid
-    let auction_type_enum = AuctionType::from_u32(e, auction_type);
-    let auction_data = match auction_type_enum {
-        AuctionType::UserLiquidation => create_user_liq_auction_data(e, user, bid, lot, percent),
-        AuctionType::BadDebtAuction => create_bad_debt_auction_data(e, user, bid, lot, percent),
-        AuctionType::InterestAuction => create_interest_auction_data(e, user, bid, lot, percent),
-    };
+    let strategy = load_auction_strategy(e, auction_type);
+    let auction_data = strategy.create(e, user, bid, lot, percent);
     storage::set_auction(e, &auction_type, user, &auction_data);
     auction_data
 }
 
+/// Create a user liquidation auction that includes all of the user's positions. The bid is every
+/// liability and the lot is every collateral position the user holds, so only the percent
+/// liquidated needs to be chosen. The same fairness checks as `create_auction` are applied.
+///
+/// Returns the AuctionData object created
+///
+/// ### Arguments
+/// * `user` - The user being liquidated
+/// * `percent` - The percentage of the user's positions being liquidated
+///
+/// ### Panics
+/// * If the user cannot be liquidated
+/// * If the percent is not valid for the user's positions
+pub fn create_user_liquidation(e: &Env, user: &Address, percent: u32) -> AuctionData {
+    let positions = storage::get_user_positions(e, user);
+    let reserve_list = storage::get_res_list(e);
+    let mut bid: Vec<Address> = Vec::new(e);
+    for index in positions.liabilities.keys() {
+        bid.push_back(reserve_list.get_unchecked(index));
+    }
+    let mut lot: Vec<Address> = Vec::new(e);
+    for index in positions.collateral.keys() {
+        lot.push_back(reserve_list.get_unchecked(index));
+    }
+    create_auction(
+        e,
+        AuctionType::UserLiquidation as u32,
+        user,
+        &bid,
+        &lot,
+        percent,
+    )
+}
+
 /// Delete an auction if it is stale
 pub fn delete_stale_auction(e: &Env, auction_type: u32, user: &Address) {
     if !storage::has_auction(e, &auction_type, user) {
@@ -133,10 +159,11 @@ pub fn delete_liquidation(e: &Env, user: &Address) {
 /// * `user` - The user involved in the auction
 /// * `filler_state` - The Address filling the auction
 /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `use_allowance` - If tokens the filler pays are moved with `transfer_from`
 ///
 /// ### Panics
-/// If the auction does not exist, or if the pool is unable to fulfill either side
-/// of the auction quote
+/// If the auction does not exist, if the minimum number of blocks since the auction began
+/// has not passed, or if the pool is unable to fulfill either side of the auction quote
 pub fn fill(
     e: &Env,
     pool: &mut Pool,
@@ -144,24 +171,35 @@ pub fn fill(
     user: &Address,
     filler_state: &mut User,
     percent_filled: u64,
+    use_allowance: bool,
 ) -> AuctionData {
     if user.clone() == filler_state.address {
         panic_with_error!(e, PoolError::InvalidLiquidation);
     }
-    let auction_data = storage::get_auction(e, &auction_type, user);
+    let strategy = load_auction_strategy(e, auction_type);
+    strategy.validate_fill(e, user, &filler_state.address);
+    let mut auction_data = storage::get_auction(e, &auction_type, user);
+    // prevent auctions from being sniped before the minimum number of blocks have passed
+    if e.ledger().sequence() < auction_data.block + storage::get_auction_min_blocks(e) {
+        panic_with_error!(e, PoolError::AuctionFillTooEarly);
+    }
+    if strategy.liquidates_user() {
+        // a covered user's premium may be charged against the lot, so it is charged before the
+        // lot is scaled
+        accrue_lot_coverage(e, pool, user, &mut auction_data);
+    }
     let (to_fill_auction, remaining_auction) = scale_auction(e, &auction_data, percent_filled);
+    require_scaled_within_stored(e, &auction_data, &to_fill_auction);
     let is_full_fill = remaining_auction.is_none();
-    match AuctionType::from_u32(e, auction_type) {
-        AuctionType::UserLiquidation => {
-            fill_user_liq_auction(e, pool, &to_fill_auction, user, filler_state, is_full_fill)
-        }
-        AuctionType::BadDebtAuction => {
-            fill_bad_debt_auction(e, pool, &to_fill_auction, filler_state, is_full_fill);
-        }
-        AuctionType::InterestAuction => {
-            fill_interest_auction(e, pool, &to_fill_auction, &filler_state.address)
-        }
-    };
+    strategy.settle(
+        e,
+        pool,
+        &to_fill_auction,
+        user,
+        filler_state,
+        is_full_fill,
+        use_allowance,
+    );
 
     if let Some(auction_to_store) = remaining_auction {
         storage::set_auction(e, &auction_type, user, &auction_to_store);
@@ -172,6 +210,245 @@ pub fn fill(
     to_fill_auction
 }
 
+/// Check if an address can fill liquidation auctions. Any address can if the liquidator allowlist
+/// is not enabled.
+///
+/// ### Arguments
+/// * `liquidator` - The address of the liquidator
+pub fn is_liquidator_allowed(e: &Env, liquidator: &Address) -> bool {
+    !storage::get_liquidator_allowlist(e) || storage::get_liquidator(e, liquidator)
+}
+
+/// Require that an address can fill liquidation auctions, or panic
+pub(super) fn require_liquidator_allowed(e: &Env, liquidator: &Address) {
+    if !is_liquidator_allowed(e, liquidator) {
+        panic_with_error!(e, PoolError::LiquidatorNotAllowed);
+    }
+}
+
+/// Fetch the block a new auction starts at. This is the next block, delayed by a pseudo-random
+/// number of blocks up to the pool's auction start offset, so the block an auction is best priced
+/// at cannot be known in advance by the auction's creator and a filler colluding with them.
+///
+/// Contracts cannot read the previous ledger's hash, so the offset is drawn from the host's PRNG,
+/// which is seeded per ledger. It is not secure against validators.
+pub(super) fn auction_start_block(e: &Env) -> u32 {
+    let start_block = e.ledger().sequence() + 1;
+    let max_offset = storage::get_auction_start_offset(e);
+    if max_offset == 0 {
+        return start_block;
+    }
+    start_block + e.prng().gen_range::<u64>(0..=max_offset as u64) as u32
+}
+
+/// Estimate the health factor of the user of a liquidation auction before and after the auction
+/// is filled in full. Does not store any changes to the pool or the user.
+///
+/// Returns (health factor before, health factor after), or (0, 0) if the auction is not a user
+/// liquidation
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction
+/// * `user` - The user involved in the auction
+/// * `auction_data` - The auction to estimate the fill of
+pub fn estimate_health(
+    e: &Env,
+    auction_type: u32,
+    user: &Address,
+    auction_data: &AuctionData,
+) -> (i128, i128) {
+    if !load_auction_strategy(e, auction_type).liquidates_user() {
+        return (0, 0);
+    }
+    let mut pool = Pool::load(e);
+    let mut user_state = User::load(e, user);
+    let hf_before = calc_health_factor(e, &mut pool, &user_state.positions);
+    user_state.rm_positions(
+        e,
+        &mut pool,
+        auction_data.lot.clone(),
+        auction_data.bid.clone(),
+    );
+    let hf_after = calc_health_factor(e, &mut pool, &user_state.positions);
+    (hf_before, hf_after)
+}
+
+/// Calculate the health factor of the user of an auction from their stored positions
+///
+/// Returns the health factor, or 0 if the auction is not a user liquidation
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `auction_type` - The type of auction
+/// * `user` - The user involved in the auction
+pub fn user_health(e: &Env, pool: &mut Pool, auction_type: u32, user: &Address) -> i128 {
+    if !load_auction_strategy(e, auction_type).liquidates_user() {
+        return 0;
+    }
+    calc_health_factor(e, pool, &storage::get_user_positions(e, user))
+}
+
+/// Check if the filler's health must be verified after filling an auction. This is required if
+/// filling the auction can change the health of the filler, or if the filler is a contract. Fill
+/// contracts are checked on every fill so a shell contract can't be left holding liabilities it
+/// can never service.
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction
+/// * `filler` - The address filling the auction
+pub fn requires_filler_health_check(e: &Env, auction_type: u32, filler: &Address) -> bool {
+    load_auction_strategy(e, auction_type).requires_filler_health_check() || is_contract(filler)
+}
+
+/// Check if an address is a contract rather than an account
+fn is_contract(address: &Address) -> bool {
+    let mut strkey: [u8; 56] = [0; 56];
+    address.to_string().copy_into_slice(&mut strkey);
+    strkey[0] == b'C'
+}
+
+/// Calculate the health factor of a set of positions in the oracle's decimals, or `i128::MAX`
+/// if the positions have no liabilities
+fn calc_health_factor(e: &Env, pool: &mut Pool, positions: &Positions) -> i128 {
+    let position_data = PositionData::calculate_from_positions(e, pool, positions);
+    if position_data.liability_base == 0 {
+        return i128::MAX;
+    }
+    position_data.as_health_factor(e)
+}
+
+/// Fills the auction from the invoker, filling the largest whole percentage of the auction such that
+/// the filler spends no more than `max_bid` underlying tokens of the auction's bid asset.
+///
+/// Returns (percent filled, filled auction data, filled auction data in underlying tokens)
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `auction_type` - The type of auction to fill
+/// * `user` - The user involved in the auction
+/// * `filler_state` - The Address filling the auction
+/// * `max_bid` - The maximum amount of underlying tokens of the bid asset to spend
+/// * `use_allowance` - If tokens the filler pays are moved with `transfer_from`
+///
+/// ### Panics
+/// If the auction does not exist, if the auction bid does not contain exactly one asset, if `max_bid`
+/// is too small to fill 1% of the auction, or if the auction cannot be filled
+pub fn fill_underlying(
+    e: &Env,
+    pool: &mut Pool,
+    auction_type: u32,
+    user: &Address,
+    filler_state: &mut User,
+    max_bid: i128,
+    use_allowance: bool,
+) -> (u64, AuctionData, AuctionData) {
+    let auction_data = storage::get_auction(e, &auction_type, user);
+    if auction_data.bid.len() != 1 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let bid_asset = auction_data.bid.keys().get_unchecked(0);
+
+    // find the largest percent where the bid does not exceed `max_bid`. Rounding within
+    // `scale_auction` and `to_underlying_auction` can cause the bid to be slightly larger than the
+    // linear estimate, so step down until it fits.
+    let full_quote = quote_underlying(e, pool, auction_type, &auction_data, 100);
+    let full_bid = full_quote.bid.get(bid_asset.clone()).unwrap_or(0);
+    let mut percent_filled: u64 = if full_bid <= max_bid {
+        100
+    } else {
+        (max_bid * 100 / full_bid) as u64
+    };
+    let mut quote = full_quote;
+    while percent_filled > 0 {
+        quote = quote_underlying(e, pool, auction_type, &auction_data, percent_filled);
+        if quote.bid.get(bid_asset.clone()).unwrap_or(0) <= max_bid {
+            break;
+        }
+        percent_filled -= 1;
+    }
+    if percent_filled == 0 {
+        panic_with_error!(e, PoolError::InvalidBid);
+    }
+
+    let filled_auction = fill(
+        e,
+        pool,
+        auction_type,
+        user,
+        filler_state,
+        percent_filled,
+        use_allowance,
+    );
+    (percent_filled, filled_auction, quote)
+}
+
+/// Fetch the amounts, in underlying tokens, that would be exchanged if `percent_filled` of the
+/// auction was filled during the current block
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction
+/// * `user` - The user involved in the auction
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+///
+/// ### Panics
+/// If the auction does not exist or the percent filled is invalid
+pub fn get_underlying_fill_quote(
+    e: &Env,
+    auction_type: u32,
+    user: &Address,
+    percent_filled: u64,
+) -> AuctionData {
+    let mut pool = Pool::load(e);
+    let auction_data = storage::get_auction(e, &auction_type, user);
+    quote_underlying(e, &mut pool, auction_type, &auction_data, percent_filled)
+}
+
+/// Scale the auction for the current block and convert it to underlying tokens
+fn quote_underlying(
+    e: &Env,
+    pool: &mut Pool,
+    auction_type: u32,
+    auction_data: &AuctionData,
+    percent_filled: u64,
+) -> AuctionData {
+    let (to_fill_auction, _) = scale_auction(e, auction_data, percent_filled);
+    to_underlying_auction(e, pool, auction_type, &to_fill_auction)
+}
+
+/// Convert an auction from the units it is stored in to underlying tokens.
+///
+/// Rounding always favors the pool. Bid dTokens are converted rounding up, so the filler is never
+/// quoted less debt than they take on. Lot bTokens are converted rounding down, so the filler is
+/// never quoted more collateral than they can withdraw. Amounts already in underlying tokens are
+/// returned as is.
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `auction_type` - The type of auction
+/// * `auction_data` - The auction data to convert
+pub fn to_underlying_auction(
+    e: &Env,
+    pool: &mut Pool,
+    auction_type: u32,
+    auction_data: &AuctionData,
+) -> AuctionData {
+    let strategy = load_auction_strategy(e, auction_type);
+    let mut underlying_auction = AuctionData {
+        bid: map![e],
+        lot: map![e],
+        block: auction_data.block,
+    };
+    for (asset, amount) in auction_data.bid.iter() {
+        let underlying = strategy.bid_to_underlying(e, pool, &asset, amount);
+        underlying_auction.bid.set(asset, underlying);
+    }
+    for (asset, amount) in auction_data.lot.iter() {
+        let underlying = strategy.lot_to_underlying(e, pool, &asset, amount);
+        underlying_auction.lot.set(asset, underlying);
+    }
+    underlying_auction
+}
+
 /// Scale the auction based on the percent being filled and the amount of blocks that have passed
 /// since the auction began.
 ///
@@ -185,11 +462,34 @@ pub fn fill(
 ///
 /// ### Panics
 /// If the percent filled is greater than 100 or less than 0
-#[allow(clippy::zero_prefixed_literal)]
 fn scale_auction(
     e: &Env,
     auction_data: &AuctionData,
     percent_filled: u64,
+) -> (AuctionData, Option<AuctionData>) {
+    scale_auction_at_block(e, auction_data, percent_filled, e.ledger().sequence())
+}
+
+/// Scale the auction based on the percent being filled as if it were filled at `block`.
+///
+/// ### Arguments
+/// * `auction_data` - The auction data to scale
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `block` - The block the auction is filled at
+///
+/// Returns the (Scaled Auction, Remaining Auction), as described in `scale_auction`
+///
+/// A block before the auction's starting block, while its start offset is still open, is scaled as
+/// the starting block.
+///
+/// ### Panics
+/// If the percent filled is greater than 100 or less than 0
+#[allow(clippy::zero_prefixed_literal)]
+pub(crate) fn scale_auction_at_block(
+    e: &Env,
+    auction_data: &AuctionData,
+    percent_filled: u64,
+    block: u32,
 ) -> (AuctionData, Option<AuctionData>) {
     if percent_filled > 100 || percent_filled == 0 {
         panic_with_error!(e, PoolError::BadRequest);
@@ -210,7 +510,7 @@ fn scale_auction(
     let bid_modifier: i128;
     let lot_modifier: i128;
     let per_block_scalar: i128 = 0_0050000; // modifier moves 0.5% every block
-    let block_dif = i128(e.ledger().sequence() - auction_data.block);
+    let block_dif = i128(block.saturating_sub(auction_data.block));
     if block_dif > 200 {
         // lot 100%, bid scaling down from 100% to 0%
         lot_modifier = SCALAR_7;
@@ -263,6 +563,30 @@ fn scale_auction(
     }
 }
 
+/// Require that a scaled auction never bids or lots more of an asset than the stored auction. The
+/// block scaling only ever reduces the bid or lot, so this guards against mispricing a fill if
+/// that ever stops holding, like for an auction filled long after it was created.
+///
+/// ### Arguments
+/// * `auction_data` - The stored auction
+/// * `scaled_auction` - The auction scaled for the fill
+///
+/// ### Panics
+/// * `InvalidScaledBid` if the scaled bid of any asset is over the stored bid
+/// * `InvalidScaledLot` if the scaled lot of any asset is over the stored lot
+fn require_scaled_within_stored(e: &Env, auction_data: &AuctionData, scaled_auction: &AuctionData) {
+    for (asset, amount) in scaled_auction.bid.iter() {
+        if amount > auction_data.bid.get(asset).unwrap_or(0) {
+            panic_with_error!(e, PoolError::InvalidScaledBid);
+        }
+    }
+    for (asset, amount) in scaled_auction.lot.iter() {
+        if amount > auction_data.lot.get(asset).unwrap_or(0) {
+            panic_with_error!(e, PoolError::InvalidScaledLot);
+        }
+    }
+}
+
 /// Require that all addresses in the list are unique
 ///
 /// ### Panics
@@ -280,6 +604,7 @@ fn require_unique_addresses(e: &Env, list: &Vec<Address>) {
 #[cfg(test)]
 mod tests {
     use crate::{
+        constants::SCALAR_12,
         pool::Positions,
         storage::PoolConfig,
         testutils::{self, create_comet_lp_pool, create_pool},
@@ -654,6 +979,281 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_create_user_liquidation() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
+
+        let liq_pct = 45;
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_backstop(&e, &Address::generate(&e));
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+
+            e.cost_estimate().budget().reset_unlimited();
+            let auction_data = create_user_liquidation(&e, &samwise, liq_pct);
+            assert!(storage::has_auction(&e, &0, &samwise));
+            assert_eq!(auction_data.bid.keys(), vec![&e, underlying_2]);
+            assert_eq!(auction_data.lot.len(), 2);
+            assert!(auction_data.lot.contains_key(underlying_0));
+            assert!(auction_data.lot.contains_key(underlying_1));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1211)")]
+    fn test_create_user_liquidation_healthy_user() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_0, reserve_data_0) = testutils::default_reserve_meta();
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+
+        let positions: Positions = Positions {
+            collateral: map![&e, (reserve_config_0.index, 100_0000000)],
+            liabilities: map![&e],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_backstop(&e, &Address::generate(&e));
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+
+            create_user_liquidation(&e, &samwise, 50);
+        });
+    }
+
+    #[test]
+    fn test_estimate_health() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.l_factor = 0_8000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000]);
+
+        let positions: Positions = Positions {
+            collateral: map![&e, (reserve_config_0.index, 100_0000000)],
+            liabilities: map![&e, (reserve_config_1.index, 50_0000000)],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_1.clone(), 30_0000000)],
+            lot: map![&e, (underlying_0.clone(), 50_0000000)],
+            block: 51,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+
+            // collateral of 187 and liabilities of 250 drop to 93.5 and 100
+            let (hf_before, hf_after) = estimate_health(&e, 0, &samwise, &auction_data);
+            assert_eq!(hf_before, 0_7480000);
+            assert_eq!(hf_after, 0_9350000);
+
+            // the estimate does not modify the user or the reserves
+            assert_eq!(
+                storage::get_user_positions(&e, &samwise).liabilities,
+                positions.liabilities
+            );
+            assert_eq!(
+                storage::get_res_data(&e, &underlying_1).d_supply,
+                75_0000000
+            );
+
+            let mut pool = Pool::load(&e);
+            assert_eq!(user_health(&e, &mut pool, 0, &samwise), 0_7480000);
+            assert_eq!(user_health(&e, &mut pool, 1, &samwise), 0);
+            assert_eq!(
+                user_health(&e, &mut pool, 0, &Address::generate(&e)),
+                i128::MAX
+            );
+            assert_eq!(estimate_health(&e, 2, &samwise, &auction_data), (0, 0));
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1211)")]
     fn test_create_liquidation_for_pool() {
@@ -1349,7 +1949,7 @@ mod tests {
             e.cost_estimate().budget().reset_unlimited();
             let mut pool = Pool::load(&e);
             let mut frodo_state = User::load(&e, &frodo);
-            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100, false);
             let has_auction = storage::has_auction(&e, &0, &samwise);
             assert_eq!(has_auction, false);
         });
@@ -1458,7 +2058,7 @@ mod tests {
             e.cost_estimate().budget().reset_unlimited();
             let mut pool = Pool::load(&e);
             let mut frodo_state = User::load(&e, &frodo);
-            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 25);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 25, false);
 
             let expected_new_auction_data = AuctionData {
                 bid: map![&e, (underlying_2.clone(), 9281250)],
@@ -1580,7 +2180,7 @@ mod tests {
             });
             let mut pool = Pool::load(&e);
             let mut frodo_state = User::load(&e, &frodo);
-            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 25);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 25, false);
 
             let expected_new_auction_data = AuctionData {
                 bid: map![&e, (underlying_2.clone(), 75_000_0000)],
@@ -1610,7 +2210,7 @@ mod tests {
             });
             let mut pool = Pool::load(&e);
             let mut frodo_state = User::load(&e, &frodo);
-            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 67);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 67, false);
 
             let expected_new_auction_data = AuctionData {
                 bid: map![&e, (underlying_2.clone(), 24_7500000)],
@@ -1639,7 +2239,7 @@ mod tests {
             });
             let mut pool = Pool::load(&e);
             let mut frodo_state = User::load(&e, &frodo);
-            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100, false);
             let new_auction = storage::has_auction(&e, &0, &samwise);
             assert_eq!(new_auction, false);
             let samwise_positions = storage::get_user_positions(&e, &samwise);
@@ -1770,7 +2370,7 @@ mod tests {
             e.cost_estimate().budget().reset_unlimited();
             let mut pool = Pool::load(&e);
             let mut frodo_state = User::load(&e, &frodo);
-            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 101);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 101, false);
 
             let expected_new_auction_data = AuctionData {
                 bid: map![&e, (underlying_2.clone(), 9281250)],
@@ -1893,7 +2493,7 @@ mod tests {
             e.cost_estimate().budget().reset_unlimited();
             let mut pool = Pool::load(&e);
             let mut frodo_state = User::load(&e, &frodo);
-            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 0);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 0, false);
 
             let expected_new_auction_data = AuctionData {
                 bid: map![&e, (underlying_2.clone(), 9281250)],
@@ -1972,49 +2572,174 @@ mod tests {
         e.cost_estimate().budget().reset_unlimited();
 
         let auction_data = AuctionData {
-            bid: map![&e, (underlying_2.clone(), 1_2375000)],
-            lot: map![
-                &e,
-                (underlying_0.clone(), 30_5595329),
-                (underlying_1.clone(), 1_5395739)
-            ],
+            bid: map![&e, (underlying_2.clone(), 1_2375000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 30_5595329),
+                (underlying_1.clone(), 1_5395739)
+            ],
+            block: 176,
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_auction(&e, &0, &samwise, &auction_data);
+
+            e.ledger().set(LedgerInfo {
+                timestamp: 12345 + 200 * 5,
+                protocol_version: 22,
+                sequence_number: 176 + 200,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 172800,
+                min_persistent_entry_ttl: 172800,
+                max_entry_ttl: 9999999,
+            });
+            e.cost_estimate().budget().reset_unlimited();
+            let mut pool = Pool::load(&e);
+            let mut samwise_state = User::load(&e, &samwise);
+            fill(&e, &mut pool, 0, &samwise, &mut samwise_state, 100, false);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1225)")]
+    fn test_fill_before_min_blocks() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 175,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 1_2375000)],
+            lot: map![&e, (underlying_1.clone(), 30_5595329)],
             block: 176,
         };
         let pool_config = PoolConfig {
-            oracle: oracle_address,
+            oracle: Address::generate(&e),
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
         };
-        let positions: Positions = Positions {
-            collateral: map![
-                &e,
-                (reserve_config_0.index, 90_9100000),
-                (reserve_config_1.index, 04_5800000),
-            ],
-            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
-            supply: map![&e],
-        };
         e.as_contract(&pool_address, || {
-            storage::set_user_positions(&e, &samwise, &positions);
             storage::set_pool_config(&e, &pool_config);
             storage::set_auction(&e, &0, &samwise, &auction_data);
+            storage::set_auction_min_blocks(&e, &5);
 
             e.ledger().set(LedgerInfo {
-                timestamp: 12345 + 200 * 5,
+                timestamp: 12345 + 4 * 5,
                 protocol_version: 22,
-                sequence_number: 176 + 200,
+                sequence_number: 176 + 4,
                 network_id: Default::default(),
                 base_reserve: 10,
                 min_temp_entry_ttl: 172800,
                 min_persistent_entry_ttl: 172800,
                 max_entry_ttl: 9999999,
             });
-            e.cost_estimate().budget().reset_unlimited();
             let mut pool = Pool::load(&e);
-            let mut samwise_state = User::load(&e, &samwise);
-            fill(&e, &mut pool, 0, &samwise, &mut samwise_state, 100);
+            let mut frodo_state = User::load(&e, &frodo);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100, false);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1231)")]
+    fn test_fill_liquidator_not_allowed() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 175,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 1_2375000)],
+            lot: map![&e, (underlying_1.clone(), 30_5595329)],
+            block: 176,
+        };
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_auction(&e, &0, &samwise, &auction_data);
+            storage::set_liquidator_allowlist(&e, &true);
+
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100, false);
+        });
+    }
+
+    #[test]
+    fn test_is_liquidator_allowed() {
+        let e = Env::default();
+        let pool_address = create_pool(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            // anyone can liquidate until the allowlist is enabled
+            assert!(is_liquidator_allowed(&e, &frodo));
+
+            storage::set_liquidator_allowlist(&e, &true);
+            assert!(!is_liquidator_allowed(&e, &frodo));
+
+            storage::set_liquidator(&e, &frodo, &true);
+            assert!(is_liquidator_allowed(&e, &frodo));
+
+            storage::set_liquidator(&e, &frodo, &false);
+            assert!(!is_liquidator_allowed(&e, &frodo));
         });
     }
 
@@ -2866,6 +3591,168 @@ mod tests {
         let (_, _) = scale_auction(&e, &base_auction_data, 101);
     }
 
+    #[test]
+    fn test_auction_start_block_offset() {
+        let e = Env::default();
+        e.ledger().set_sequence_number(100);
+        let pool_address = create_pool(&e);
+
+        e.as_contract(&pool_address, || {
+            assert_eq!(auction_start_block(&e), 101);
+
+            storage::set_auction_start_offset(&e, &5);
+            let mut offsets_seen = [false; 6];
+            for _ in 0..200 {
+                let start_block = auction_start_block(&e);
+                assert!(start_block >= 101 && start_block <= 106);
+                offsets_seen[(start_block - 101) as usize] = true;
+            }
+            assert!(offsets_seen.iter().all(|seen| *seen));
+        });
+    }
+
+    #[test]
+    fn test_requires_filler_health_check() {
+        let e = Env::default();
+        let account = testutils::create_account_address(&e);
+        let contract = Address::generate(&e);
+
+        let interest = AuctionType::InterestAuction as u32;
+        assert!(!requires_filler_health_check(&e, interest, &account));
+        assert!(requires_filler_health_check(&e, interest, &contract));
+
+        let liquidation = AuctionType::UserLiquidation as u32;
+        assert!(requires_filler_health_check(&e, liquidation, &account));
+        assert!(requires_filler_health_check(&e, liquidation, &contract));
+    }
+
+    #[test]
+    fn test_scale_auction_at_extreme_block_deltas() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let amounts: [i128; 5] = [1, 7, 1_0000000, 123_456_789_0000000, i64::MAX as i128];
+        let block_difs: [u32; 11] = [
+            0,
+            1,
+            199,
+            200,
+            201,
+            399,
+            400,
+            401,
+            10_000,
+            1_000_000,
+            u32::MAX - 1000,
+        ];
+        for amount in amounts {
+            let auction_data = AuctionData {
+                bid: map![&e, (underlying_0.clone(), amount)],
+                lot: map![&e, (underlying_1.clone(), amount)],
+                block: 1000,
+            };
+            for percent_filled in [1, 33, 50, 99, 100] {
+                let mut prev_bid = i128::MAX;
+                let mut prev_lot = 0;
+                for block_dif in block_difs {
+                    let (scaled, remaining) =
+                        scale_auction_at_block(&e, &auction_data, percent_filled, 1000 + block_dif);
+                    require_scaled_within_stored(&e, &auction_data, &scaled);
+
+                    // the scaled auction never exceeds the share of the auction being filled
+                    let remaining = remaining.unwrap_or(AuctionData {
+                        bid: map![&e],
+                        lot: map![&e],
+                        block: 1000,
+                    });
+                    let bid = scaled.bid.get(underlying_0.clone()).unwrap_or(0);
+                    let lot = scaled.lot.get(underlying_1.clone()).unwrap_or(0);
+                    assert!(bid <= amount - remaining.bid.get(underlying_0.clone()).unwrap_or(0));
+                    assert!(lot <= amount - remaining.lot.get(underlying_1.clone()).unwrap_or(0));
+
+                    // the bid only falls and the lot only rises as blocks pass
+                    assert!(bid <= prev_bid);
+                    assert!(lot >= prev_lot);
+                    prev_bid = bid;
+                    prev_lot = lot;
+
+                    if block_dif >= 400 {
+                        assert_eq!(bid, 0);
+                        assert_eq!(
+                            lot,
+                            amount.fixed_mul_floor(
+                                &e,
+                                &(percent_filled as i128 * 1_00000),
+                                &SCALAR_7
+                            )
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale_auction_before_start_block() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![&e, (underlying_1.clone(), 10_0000000)],
+            block: 1000,
+        };
+        // a view during the auction's start offset quotes the starting block
+        let (scaled, remaining) = scale_auction_at_block(&e, &auction_data, 50, 990);
+        let (expected_scaled, expected_remaining) =
+            scale_auction_at_block(&e, &auction_data, 50, 1000);
+        assert_eq!(scaled.bid, expected_scaled.bid);
+        assert_eq!(scaled.lot, expected_scaled.lot);
+        assert_eq!(remaining.unwrap().bid, expected_remaining.unwrap().bid);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1240)")]
+    fn test_require_scaled_within_stored_bid() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![&e, (underlying_1.clone(), 10_0000000)],
+            block: 1000,
+        };
+        let scaled_auction = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000001)],
+            lot: map![&e, (underlying_1.clone(), 10_0000000)],
+            block: 1000,
+        };
+        require_scaled_within_stored(&e, &auction_data, &scaled_auction);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1241)")]
+    fn test_require_scaled_within_stored_lot() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![&e, (underlying_1.clone(), 10_0000000)],
+            block: 1000,
+        };
+        let scaled_auction = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![&e, (underlying_0.clone(), 1)],
+            block: 1000,
+        };
+        require_scaled_within_stored(&e, &auction_data, &scaled_auction);
+    }
+
     #[test]
     fn test_scale_auction_dust() {
         // @dev: bids always round up, lots always round down
@@ -3007,4 +3894,273 @@ mod tests {
         assert_eq!(scaled_auction.lot.get_unchecked(underlying_1.clone()), 1);
         assert!(remaining_auction_option.is_none());
     }
+
+    /// Setup a pool with a user liquidation auction for samwise that is 200 blocks old, where
+    /// reserve 2 has a d_rate of 1.1 and reserves 0 and 1 have a b_rate of 1.05
+    fn setup_underlying_auction(e: &Env) -> (Address, Address, Vec<Address>) {
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 376,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+
+        let pool_address = create_pool(e);
+        let (oracle_address, _) = testutils::create_mock_oracle(e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let mut assets = vec![e];
+        for index in 0..3 {
+            let (underlying, _) = testutils::create_token_contract(e, &bombadil);
+            let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+            reserve_config.index = index;
+            reserve_data.last_time = 12345;
+            reserve_data.b_rate = 1_050_000_000_000;
+            reserve_data.d_rate = 1_100_000_000_000;
+            testutils::create_reserve(
+                e,
+                &pool_address,
+                &underlying,
+                &reserve_config,
+                &reserve_data,
+            );
+            assets.push_back(underlying);
+        }
+        e.cost_estimate().budget().reset_unlimited();
+
+        let auction_data = AuctionData {
+            bid: map![e, (assets.get_unchecked(2), 1_2375000)],
+            lot: map![
+                e,
+                (assets.get_unchecked(0), 30_5595329),
+                (assets.get_unchecked(1), 1_5395739)
+            ],
+            block: 176,
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        let positions: Positions = Positions {
+            collateral: map![e, (0, 90_9100000), (1, 04_5800000),],
+            liabilities: map![e, (2, 02_7500000),],
+            supply: map![e],
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(e, &samwise, &positions);
+            storage::set_pool_config(e, &pool_config);
+            storage::set_auction(e, &0, &samwise, &auction_data);
+        });
+        (pool_address, samwise, assets)
+    }
+
+    #[test]
+    fn test_get_underlying_fill_quote() {
+        let e = Env::default();
+        let (pool_address, samwise, assets) = setup_underlying_auction(&e);
+
+        e.as_contract(&pool_address, || {
+            let quote = get_underlying_fill_quote(&e, 0, &samwise, 51);
+            // bid 6311250 dTokens -> rounded up
+            assert_eq!(quote.bid.get_unchecked(assets.get_unchecked(2)), 6942375);
+            // lot 15_5853617 and 7851826 bTokens -> rounded down
+            assert_eq!(quote.lot.get_unchecked(assets.get_unchecked(0)), 16_3646297);
+            assert_eq!(quote.lot.get_unchecked(assets.get_unchecked(1)), 8244417);
+            assert_eq!(quote.block, 176);
+
+            // auction is not modified
+            let auction = storage::get_auction(&e, &0, &samwise);
+            assert_eq!(
+                auction.bid.get_unchecked(assets.get_unchecked(2)),
+                1_2375000
+            );
+        });
+    }
+
+    #[test]
+    fn test_to_underlying_auction_rounding_favors_pool() {
+        let e = Env::default();
+        let (pool_address, _, assets) = setup_underlying_auction(&e);
+        let asset_0 = assets.get_unchecked(0);
+        let asset_2 = assets.get_unchecked(2);
+
+        e.as_contract(&pool_address, || {
+            // pseudo-random sample of rates and amounts
+            let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+            let mut next = || {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed
+            };
+            for _ in 0..200 {
+                let b_rate = 1_000_000_000_000 + i128(next() % 1_000_000_000_000);
+                let d_rate = 1_000_000_000_000 + i128(next() % 1_000_000_000_000);
+                let b_tokens = i128(next() % 1_000_000_0000000) + 1;
+                let d_tokens = i128(next() % 1_000_000_0000000) + 1;
+
+                let mut data_0 = storage::get_res_data(&e, &asset_0);
+                data_0.b_rate = b_rate;
+                storage::set_res_data(&e, &asset_0, &data_0);
+                let mut data_2 = storage::get_res_data(&e, &asset_2);
+                data_2.d_rate = d_rate;
+                storage::set_res_data(&e, &asset_2, &data_2);
+
+                let auction = AuctionData {
+                    bid: map![&e, (asset_2.clone(), d_tokens)],
+                    lot: map![&e, (asset_0.clone(), b_tokens)],
+                    block: 176,
+                };
+                let mut pool = Pool::load(&e);
+                let underlying = to_underlying_auction(&e, &mut pool, 0, &auction);
+
+                // the filler is never quoted less debt than they take on
+                let bid = underlying.bid.get_unchecked(asset_2.clone());
+                assert!(bid * SCALAR_12 >= d_tokens * d_rate);
+                assert!((bid - 1) * SCALAR_12 < d_tokens * d_rate);
+                // the filler is never quoted more collateral than they receive
+                let lot = underlying.lot.get_unchecked(asset_0.clone());
+                assert!(lot * SCALAR_12 <= b_tokens * b_rate);
+                assert!((lot + 1) * SCALAR_12 > b_tokens * b_rate);
+
+                // a partial fill never favors the filler over the equivalent share of the full auction
+                let percent = next() % 100 + 1;
+                let partial = quote_underlying(&e, &mut pool, 0, &auction, percent);
+                let partial_bid = partial.bid.get(asset_2.clone()).unwrap_or(0);
+                let partial_lot = partial.lot.get(asset_0.clone()).unwrap_or(0);
+                assert!(partial_bid * 100 * SCALAR_12 >= d_tokens * d_rate * i128(percent));
+                assert!(partial_lot * 100 * SCALAR_12 <= b_tokens * b_rate * i128(percent));
+            }
+        });
+    }
+
+    #[test]
+    fn test_fill_underlying() {
+        let e = Env::default();
+        let (pool_address, samwise, assets) = setup_underlying_auction(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            let (percent, filled, filled_underlying) = fill_underlying(
+                &e,
+                &mut pool,
+                0,
+                &samwise,
+                &mut frodo_state,
+                0_7000000,
+                false,
+            );
+
+            // full bid is 1_3612500 underlying, so 51% is the largest fill under 0_7000000
+            assert_eq!(percent, 51);
+            assert_eq!(filled.bid.get_unchecked(assets.get_unchecked(2)), 6311250);
+            assert_eq!(
+                filled_underlying.bid.get_unchecked(assets.get_unchecked(2)),
+                6942375
+            );
+            assert_eq!(
+                filled_underlying.lot.get_unchecked(assets.get_unchecked(0)),
+                16_3646297
+            );
+            assert_eq!(frodo_state.get_liabilities(2), 6311250);
+            assert_eq!(frodo_state.get_collateral(0), 15_5853617);
+            assert_eq!(frodo_state.get_collateral(1), 7851826);
+
+            let new_auction = storage::get_auction(&e, &0, &samwise);
+            assert_eq!(
+                new_auction.bid.get_unchecked(assets.get_unchecked(2)),
+                1_2375000 - 6311250
+            );
+        });
+    }
+
+    #[test]
+    fn test_fill_underlying_full() {
+        let e = Env::default();
+        let (pool_address, samwise, assets) = setup_underlying_auction(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            let (percent, _, filled_underlying) = fill_underlying(
+                &e,
+                &mut pool,
+                0,
+                &samwise,
+                &mut frodo_state,
+                2_0000000,
+                false,
+            );
+
+            assert_eq!(percent, 100);
+            assert_eq!(
+                filled_underlying.bid.get_unchecked(assets.get_unchecked(2)),
+                1_3612500
+            );
+            assert!(!storage::has_auction(&e, &0, &samwise));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1221)")]
+    fn test_fill_underlying_max_bid_too_small() {
+        let e = Env::default();
+        let (pool_address, samwise, _) = setup_underlying_auction(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            fill_underlying(
+                &e,
+                &mut pool,
+                0,
+                &samwise,
+                &mut frodo_state,
+                0_0100000,
+                false,
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_fill_underlying_multiple_bid_assets() {
+        let e = Env::default();
+        let (pool_address, samwise, assets) = setup_underlying_auction(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut auction = storage::get_auction(&e, &0, &samwise);
+            auction.bid.set(assets.get_unchecked(1), 1_0000000);
+            storage::set_auction(&e, &0, &samwise, &auction);
+
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            fill_underlying(
+                &e,
+                &mut pool,
+                0,
+                &samwise,
+                &mut frodo_state,
+                1_0000000,
+                false,
+            );
+        });
+    }
 }
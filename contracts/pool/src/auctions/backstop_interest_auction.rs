@@ -1,12 +1,54 @@
 use crate::{
-    constants::SCALAR_7, dependencies::BackstopClient, errors::PoolError, pool::Pool, storage,
+    constants::SCALAR_7,
+    dependencies::BackstopClient,
+    errors::PoolError,
+    events::PoolEvents,
+    pool::{recall_shortfall, require_allowance, Pool, User},
+    storage,
 };
 use cast::i128;
 use sep_41_token::TokenClient;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{map, panic_with_error, Address, Env, Vec};
+use soroban_sdk::{contracttype, map, panic_with_error, Address, Env, Map, Vec};
+use trustbridge_common::decimal_scalar;
+
+use super::{auction::auction_start_block, AuctionData, AuctionStrategy, AuctionType};
+
+/// A preview of the interest auction that would be created from a set of reserves at current
+/// prices
+#[derive(Clone)]
+#[contracttype]
+pub struct InterestAuctionPreview {
+    pub lot: Map<Address, i128>, // the backstop credit of each asset in the lot
+    pub lot_value: Map<Address, i128>, // the value of each asset in oracle decimals
+    pub interest_value: i128,    // the total value of the lot, in oracle decimals
+    pub bid: i128,               // the backstop tokens bid when the auction starts
+}
 
-use super::{AuctionData, AuctionType};
+/// Preview the interest auction that would be created from a set of reserves, so the cost of
+/// creating it can be weighed against the value of the lot. Reserves without backstop credit or
+/// that are sunset are left out of the lot, and auctions with an `interest_value` under 200
+/// cannot be created.
+///
+/// ### Arguments
+/// * `lot` - The underlying assets of the reserves to include in the lot
+///
+/// ### Panics
+/// If a reserve does not exist or does not have a valid price
+pub fn preview_interest_auction(e: &Env, lot: &Vec<Address>) -> InterestAuctionPreview {
+    let mut pool = Pool::load(e);
+    let oracle_scalar = decimal_scalar(pool.load_price_decimals(e));
+    let (lot, lot_value, interest_value) = load_interest_lot(e, &mut pool, lot);
+
+    let backstop_client = BackstopClient::new(e, &storage::get_backstop(e));
+    let bid = calc_interest_bid(e, &backstop_client, interest_value, oracle_scalar);
+    InterestAuctionPreview {
+        lot,
+        lot_value,
+        interest_value,
+        bid,
+    }
+}
 
 pub fn create_interest_auction_data(
     e: &Env,
@@ -31,32 +73,14 @@ pub fn create_interest_auction_data(
     if pool.config.max_positions <= lot.len() {
         panic_with_error!(e, PoolError::MaxPositionsExceeded);
     }
-    let oracle_scalar = 10i128.pow(pool.load_price_decimals(e));
+    let oracle_scalar = decimal_scalar(pool.load_price_decimals(e));
+    let (lot, _, interest_value) = load_interest_lot(e, &mut pool, lot);
     let mut auction_data = AuctionData {
-        lot: map![e],
+        lot,
         bid: map![e],
-        block: e.ledger().sequence() + 1,
+        block: auction_start_block(e),
     };
 
-    // validate and create lot auction data
-    let mut interest_value = 0; // expressed in the oracle's decimals
-    for lot_asset in lot {
-        // don't store updated reserve data back to ledger. This will occur on the the auction's fill.
-        // `load_reserve` will panic if the reserve does not exist
-        let reserve = pool.load_reserve(e, &lot_asset, false);
-        if reserve.data.backstop_credit > 0 {
-            let asset_to_base = pool.load_price(e, &reserve.asset);
-            interest_value += i128(asset_to_base).fixed_mul_floor(
-                e,
-                &reserve.data.backstop_credit,
-                &reserve.scalar,
-            );
-            auction_data
-                .lot
-                .set(reserve.asset, reserve.data.backstop_credit);
-        }
-    }
-
     if auction_data.lot.is_empty() {
         panic_with_error!(e, PoolError::InvalidLot);
     }
@@ -73,21 +97,65 @@ pub fn create_interest_auction_data(
         panic_with_error!(e, PoolError::InvalidBid);
     }
 
-    let pool_backstop_data = backstop_client.pool_data(&e.current_contract_address());
-    // backstop tokens use 7 decimals
-    let bid_amount = interest_value // oracle_scalar
-        .fixed_mul_floor(e, &1_2000000, &oracle_scalar) // denom of oracle_scalar means result is SCALAR_7
-        .fixed_div_floor(e, &pool_backstop_data.token_spot_price, &SCALAR_7); // token_spot_price is SCALAR_7
+    let bid_amount = calc_interest_bid(e, &backstop_client, interest_value, oracle_scalar);
     auction_data.bid.set(backstop_token, bid_amount);
 
     auction_data
 }
 
+/// Load the backstop credit of each reserve in the lot that has any. Sunset reserves cannot be
+/// priced, so their credit is left for a later auction.
+///
+/// Returns the lot, the value of each asset in the lot, and the total value of the lot, with
+/// values expressed in the oracle's decimals
+fn load_interest_lot(
+    e: &Env,
+    pool: &mut Pool,
+    lot: &Vec<Address>,
+) -> (Map<Address, i128>, Map<Address, i128>, i128) {
+    let mut lot_amounts = map![e];
+    let mut lot_values = map![e];
+    let mut interest_value = 0; // expressed in the oracle's decimals
+    for lot_asset in lot.iter() {
+        // don't store updated reserve data back to ledger. This will occur on the the auction's fill.
+        // `load_reserve` will panic if the reserve does not exist
+        let reserve = pool.load_reserve(e, &lot_asset, false);
+        if reserve.data.backstop_credit > 0 && !storage::get_sunset(e, &reserve.asset) {
+            let asset_to_base = pool.load_price(e, &reserve.asset);
+            let value = i128(asset_to_base).fixed_mul_floor(
+                e,
+                &reserve.data.backstop_credit,
+                &reserve.scalar,
+            );
+            interest_value += value;
+            lot_values.set(reserve.asset.clone(), value);
+            lot_amounts.set(reserve.asset, reserve.data.backstop_credit);
+        }
+    }
+    (lot_amounts, lot_values, interest_value)
+}
+
+/// Calculate the backstop tokens an interest auction bids for a lot, which is 120% of the lot's
+/// value at the backstop token's spot price
+fn calc_interest_bid(
+    e: &Env,
+    backstop_client: &BackstopClient,
+    interest_value: i128,
+    oracle_scalar: i128,
+) -> i128 {
+    let pool_backstop_data = backstop_client.pool_data(&e.current_contract_address());
+    // backstop tokens use 7 decimals
+    interest_value // oracle_scalar
+        .fixed_mul_floor(e, &1_2000000, &oracle_scalar) // denom of oracle_scalar means result is SCALAR_7
+        .fixed_div_floor(e, &pool_backstop_data.token_spot_price, &SCALAR_7) // token_spot_price is SCALAR_7
+}
+
 pub fn fill_interest_auction(
     e: &Env,
     pool: &mut Pool,
     auction_data: &AuctionData,
     filler: &Address,
+    use_allowance: bool,
 ) {
     // bid only contains the Backstop token
     let backstop = storage::get_backstop(e);
@@ -96,13 +164,62 @@ pub fn fill_interest_auction(
     }
     let backstop_client = BackstopClient::new(&e, &backstop);
     let backstop_token: Address = backstop_client.backstop_token();
-    let backstop_token_bid_amount = auction_data.bid.get(backstop_token).unwrap_or(0);
+    let backstop_token_bid_amount = auction_data.bid.get(backstop_token.clone()).unwrap_or(0);
     if backstop_token_bid_amount > 0 {
-        backstop_client.donate(
-            &filler,
-            &e.current_contract_address(),
-            &backstop_token_bid_amount,
-        );
+        // route the treasury's share of the bid, if any, and donate the rest to the backstop
+        let interest_split = storage::get_interest_split(e);
+        let treasury_amount = match &interest_split {
+            Some(split) => {
+                backstop_token_bid_amount.fixed_mul_floor(e, &i128(split.pct), &SCALAR_7)
+            }
+            None => 0,
+        };
+        let backstop_amount = backstop_token_bid_amount - treasury_amount;
+        let backstop_token_client = TokenClient::new(e, &backstop_token);
+        if use_allowance {
+            // move the bid through the pool with the filler's allowance, so the filler does not
+            // need to authorize the donation to the backstop
+            let pool_address = e.current_contract_address();
+            require_allowance(e, &backstop_token_client, filler, backstop_token_bid_amount);
+            backstop_token_client.transfer_from(
+                &pool_address,
+                filler,
+                &pool_address,
+                &backstop_token_bid_amount,
+            );
+            if backstop_amount > 0 {
+                backstop_token_client.approve(
+                    &pool_address,
+                    &backstop,
+                    &backstop_amount,
+                    &e.ledger().sequence(),
+                );
+                backstop_client.donate_bid(filler, &pool_address, &backstop_amount);
+            }
+            if let Some(split) = &interest_split {
+                if treasury_amount > 0 {
+                    backstop_token_client.transfer(
+                        &pool_address,
+                        &split.treasury,
+                        &treasury_amount,
+                    );
+                }
+            }
+        } else {
+            if backstop_amount > 0 {
+                backstop_client.donate(&filler, &e.current_contract_address(), &backstop_amount);
+            }
+            if let Some(split) = &interest_split {
+                if treasury_amount > 0 {
+                    backstop_token_client.transfer(filler, &split.treasury, &treasury_amount);
+                }
+            }
+        }
+
+        PoolEvents::interest_proceeds(e, backstop, filler.clone(), backstop_amount);
+        if let Some(split) = interest_split {
+            PoolEvents::interest_proceeds(e, split.treasury, filler.clone(), treasury_amount);
+        }
     }
 
     // lot contains underlying tokens, but the backstop credit must be updated on the reserve
@@ -110,6 +227,7 @@ pub fn fill_interest_auction(
         let mut reserve = pool.load_reserve(e, &res_asset_address, true);
         reserve.data.backstop_credit -= lot_amount;
         pool.cache_reserve(reserve);
+        recall_shortfall(e, &res_asset_address, lot_amount);
         TokenClient::new(e, &res_asset_address).transfer(
             &e.current_contract_address(),
             filler,
@@ -118,19 +236,72 @@ pub fn fill_interest_auction(
     }
 }
 
+/// Auctions the interest accrued to the backstop in exchange for backstop tokens
+pub struct InterestAuction;
+
+impl AuctionStrategy for InterestAuction {
+    fn create(
+        &self,
+        e: &Env,
+        user: &Address,
+        bid: &Vec<Address>,
+        lot: &Vec<Address>,
+        percent: u32,
+    ) -> AuctionData {
+        create_interest_auction_data(e, user, bid, lot, percent)
+    }
+
+    fn settle(
+        &self,
+        e: &Env,
+        pool: &mut Pool,
+        auction_data: &AuctionData,
+        _user: &Address,
+        filler_state: &mut User,
+        _is_full_fill: bool,
+        use_allowance: bool,
+    ) {
+        fill_interest_auction(e, pool, auction_data, &filler_state.address, use_allowance);
+    }
+
+    fn bid_to_underlying(
+        &self,
+        _e: &Env,
+        _pool: &mut Pool,
+        _asset: &Address,
+        amount: i128,
+    ) -> i128 {
+        amount
+    }
+
+    fn lot_to_underlying(
+        &self,
+        _e: &Env,
+        _pool: &mut Pool,
+        _asset: &Address,
+        amount: i128,
+    ) -> i128 {
+        amount
+    }
+
+    fn requires_filler_health_check(&self) -> bool {
+        false
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         auctions::auction::AuctionType,
-        storage::{self, PoolConfig},
+        storage::{self, InterestSplit, PoolConfig},
         testutils::{self, create_comet_lp_pool, create_pool},
     };
 
     use super::*;
     use sep_40_oracle::testutils::Asset;
     use soroban_sdk::{
-        testutils::{Address as _, Ledger, LedgerInfo},
-        vec, Address, Symbol,
+        testutils::{Address as _, Events, Ledger, LedgerInfo},
+        vec, Address, IntoVal, Symbol,
     };
 
     #[test]
@@ -866,6 +1037,130 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_preview_interest_auction() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (usdc_id, _) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, _) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, _) = create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        let (_, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_data_0.b_supply = 1000_0000000;
+        reserve_data_0.d_supply = 750_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.backstop_credit = 25_0000000;
+        reserve_data_1.b_supply = 250_0000000;
+        reserve_data_1.d_supply = 187_5000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+                Asset::Stellar(usdc_id.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 100_0000000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let preview = preview_interest_auction(
+                &e,
+                &vec![
+                    &e,
+                    underlying_0.clone(),
+                    underlying_1.clone(),
+                    underlying_2.clone(),
+                ],
+            );
+            assert_eq!(preview.lot.len(), 2);
+            assert_eq!(preview.lot.get_unchecked(underlying_0.clone()), 100_0000000);
+            assert_eq!(preview.lot.get_unchecked(underlying_1.clone()), 25_0000000);
+            assert_eq!(preview.lot_value.len(), 2);
+            assert_eq!(preview.lot_value.get_unchecked(underlying_0), 200_0000000);
+            assert_eq!(preview.lot_value.get_unchecked(underlying_1), 100_0000000);
+            assert_eq!(preview.interest_value, 300_0000000);
+            // matches the bid `create_interest_auction_data` starts with
+            assert_eq!(preview.bid, 288_0000000);
+
+            // the preview does not create an auction
+            assert!(!storage::has_auction(
+                &e,
+                &(AuctionType::InterestAuction as u32),
+                &storage::get_backstop(&e)
+            ));
+        });
+    }
+
     #[test]
     fn test_create_interest_auction_14_decimal_oracle() {
         let e = Env::default();
@@ -1325,7 +1620,7 @@ mod tests {
             storage::set_backstop(&e, &backstop_address);
             let mut pool = Pool::load(&e);
             let backstop_token_balance_pre_fill = backstop_token_client.balance(&backstop_address);
-            fill_interest_auction(&e, &mut pool, &mut auction_data, &samwise);
+            fill_interest_auction(&e, &mut pool, &mut auction_data, &samwise, false);
             pool.store_cached_reserves(&e);
 
             assert_eq!(backstop_token_client.balance(&samwise), 25_0000000);
@@ -1343,6 +1638,415 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_fill_interest_auction_with_treasury_split() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 301,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let treasury = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (usdc_id, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, backstop_token_client) =
+            create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        blnd_client.mint(&samwise, &10_000_0000000);
+        usdc_client.mint(&samwise, &250_0000000);
+        let exp_ledger = e.ledger().sequence() + 100;
+        blnd_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        usdc_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        backstop_token_client.join_pool(
+            &(100 * SCALAR_7),
+            &vec![&e, 10_000_0000000, 250_0000000],
+            &samwise,
+        );
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_data_0.b_supply = 200_000_0000000;
+        reserve_data_0.d_supply = 100_000_0000000;
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+        underlying_0_client.mint(&pool_address, &1_000_0000000);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_100_000_000_000;
+        reserve_data_0.b_supply = 10_000_0000000;
+        reserve_data_0.b_supply = 7_000_0000000;
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.backstop_credit = 30_0000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        underlying_1_client.mint(&pool_address, &1_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        let mut auction_data = AuctionData {
+            bid: map![&e, (backstop_token_id.clone(), 75_0000000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 100_0000000),
+                (underlying_1.clone(), 25_0000000)
+            ],
+            block: 51,
+        };
+
+        backstop_token_client.approve(
+            &samwise,
+            &backstop_address,
+            &75_0000000,
+            &e.ledger().sequence(),
+        );
+        e.as_contract(&pool_address, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_auction(
+                &e,
+                &(AuctionType::InterestAuction as u32),
+                &backstop_address,
+                &auction_data,
+            );
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop_address);
+            storage::set_interest_split(
+                &e,
+                &Some(InterestSplit {
+                    treasury: treasury.clone(),
+                    pct: 0_2000000,
+                }),
+            );
+            let mut pool = Pool::load(&e);
+            let backstop_token_balance_pre_fill = backstop_token_client.balance(&backstop_address);
+            fill_interest_auction(&e, &mut pool, &mut auction_data, &samwise, false);
+            pool.store_cached_reserves(&e);
+
+            assert_eq!(backstop_token_client.balance(&samwise), 25_0000000);
+            assert_eq!(
+                backstop_token_client.balance(&backstop_address),
+                backstop_token_balance_pre_fill + 60_0000000
+            );
+            assert_eq!(backstop_token_client.balance(&treasury), 15_0000000);
+            let mut events = vec![&e];
+            for event in e.events().all().iter() {
+                if event.0 == pool_address {
+                    events.push_back(event);
+                }
+            }
+            assert_eq!(events.len(), 2);
+            assert_eq!(
+                events.get_unchecked(0),
+                (
+                    pool_address.clone(),
+                    (
+                        Symbol::new(&e, "interest_proceeds"),
+                        backstop_address.clone()
+                    )
+                        .into_val(&e),
+                    (samwise.clone(), 60_0000000i128).into_val(&e),
+                )
+            );
+            assert_eq!(
+                events.get_unchecked(1),
+                (
+                    pool_address.clone(),
+                    (Symbol::new(&e, "interest_proceeds"), treasury.clone()).into_val(&e),
+                    (samwise.clone(), 15_0000000i128).into_val(&e),
+                )
+            );
+            assert_eq!(underlying_0_client.balance(&samwise), 100_0000000);
+            assert_eq!(underlying_1_client.balance(&samwise), 25_0000000);
+            // verify only filled backstop credits get deducted from total
+            let reserve_0_data = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(reserve_0_data.backstop_credit, 0);
+            let reserve_1_data = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_1_data.backstop_credit, 5_0000000);
+        });
+    }
+
+    #[test]
+    fn test_fill_interest_auction_use_allowance() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 301,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (usdc_id, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, backstop_token_client) =
+            create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        blnd_client.mint(&samwise, &10_000_0000000);
+        usdc_client.mint(&samwise, &250_0000000);
+        let exp_ledger = e.ledger().sequence() + 100;
+        blnd_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        usdc_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        backstop_token_client.join_pool(
+            &(100 * SCALAR_7),
+            &vec![&e, 10_000_0000000, 250_0000000],
+            &samwise,
+        );
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_data_0.b_supply = 200_000_0000000;
+        reserve_data_0.d_supply = 100_000_0000000;
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+        underlying_0_client.mint(&pool_address, &1_000_0000000);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_100_000_000_000;
+        reserve_data_0.b_supply = 10_000_0000000;
+        reserve_data_0.b_supply = 7_000_0000000;
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.backstop_credit = 30_0000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        underlying_1_client.mint(&pool_address, &1_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        let mut auction_data = AuctionData {
+            bid: map![&e, (backstop_token_id.clone(), 75_0000000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 100_0000000),
+                (underlying_1.clone(), 25_0000000)
+            ],
+            block: 51,
+        };
+
+        // the filler only approves the pool
+        backstop_token_client.approve(&samwise, &pool_address, &75_0000000, &e.ledger().sequence());
+        e.as_contract(&pool_address, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_auction(
+                &e,
+                &(AuctionType::InterestAuction as u32),
+                &backstop_address,
+                &auction_data,
+            );
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop_address);
+            let mut pool = Pool::load(&e);
+            let backstop_token_balance_pre_fill = backstop_token_client.balance(&backstop_address);
+            fill_interest_auction(&e, &mut pool, &mut auction_data, &samwise, true);
+            pool.store_cached_reserves(&e);
+
+            assert_eq!(backstop_token_client.balance(&samwise), 25_0000000);
+            assert_eq!(
+                backstop_token_client.balance(&backstop_address),
+                backstop_token_balance_pre_fill + 75_0000000
+            );
+            assert_eq!(underlying_0_client.balance(&samwise), 100_0000000);
+            assert_eq!(underlying_1_client.balance(&samwise), 25_0000000);
+            // verify only filled backstop credits get deducted from total
+            let reserve_0_data = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(reserve_0_data.backstop_credit, 0);
+            let reserve_1_data = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_1_data.backstop_credit, 5_0000000);
+        });
+        assert_eq!(backstop_token_client.balance(&pool_address), 0);
+        assert_eq!(backstop_token_client.allowance(&samwise, &pool_address), 0);
+        assert_eq!(
+            backstop_client.get_donations(&pool_address, &pool_address),
+            75_0000000
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1228)")]
+    fn test_fill_interest_auction_use_allowance_no_allowance() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 301,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (usdc_id, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, backstop_token_client) =
+            create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        blnd_client.mint(&samwise, &10_000_0000000);
+        usdc_client.mint(&samwise, &250_0000000);
+        let exp_ledger = e.ledger().sequence() + 100;
+        blnd_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        usdc_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        backstop_token_client.join_pool(
+            &(100 * SCALAR_7),
+            &vec![&e, 10_000_0000000, 250_0000000],
+            &samwise,
+        );
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_data_0.b_supply = 200_000_0000000;
+        reserve_data_0.d_supply = 100_000_0000000;
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+        underlying_0_client.mint(&pool_address, &1_000_0000000);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_100_000_000_000;
+        reserve_data_0.b_supply = 10_000_0000000;
+        reserve_data_0.b_supply = 7_000_0000000;
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.backstop_credit = 30_0000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        underlying_1_client.mint(&pool_address, &1_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        let mut auction_data = AuctionData {
+            bid: map![&e, (backstop_token_id.clone(), 75_0000000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 100_0000000),
+                (underlying_1.clone(), 25_0000000)
+            ],
+            block: 51,
+        };
+
+        // the filler only approves the backstop
+        backstop_token_client.approve(
+            &samwise,
+            &backstop_address,
+            &75_0000000,
+            &e.ledger().sequence(),
+        );
+        e.as_contract(&pool_address, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_auction(
+                &e,
+                &(AuctionType::InterestAuction as u32),
+                &backstop_address,
+                &auction_data,
+            );
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop_address);
+            let mut pool = Pool::load(&e);
+            fill_interest_auction(&e, &mut pool, &mut auction_data, &samwise, true);
+        });
+    }
+
     #[test]
     fn test_fill_interest_auction_empty_bid() {
         let e = Env::default();
@@ -1446,7 +2150,7 @@ mod tests {
             storage::set_backstop(&e, &backstop_address);
             let mut pool = Pool::load(&e);
             let backstop_token_balance_pre_fill = backstop_token_client.balance(&backstop_address);
-            fill_interest_auction(&e, &mut pool, &mut auction_data, &samwise);
+            fill_interest_auction(&e, &mut pool, &mut auction_data, &samwise, false);
             pool.store_cached_reserves(&e);
 
             assert_eq!(backstop_token_client.balance(&samwise), 100 * SCALAR_7);
@@ -1549,7 +2253,7 @@ mod tests {
             storage::set_backstop(&e, &backstop_address);
 
             let mut pool = Pool::load(&e);
-            fill_interest_auction(&e, &mut pool, &mut auction_data, &backstop_address);
+            fill_interest_auction(&e, &mut pool, &mut auction_data, &backstop_address, false);
         });
     }
 }
@@ -8,8 +8,9 @@ use crate::{
 use cast::i128;
 use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{map, panic_with_error, Address, Env, Vec};
+use trustbridge_common::decimal_scalar;
 
-use super::{AuctionData, AuctionType};
+use super::{auction::auction_start_block, AuctionData, AuctionStrategy, AuctionType};
 
 pub fn create_bad_debt_auction_data(
     e: &Env,
@@ -32,7 +33,7 @@ pub fn create_bad_debt_auction_data(
     let mut auction_data = AuctionData {
         bid: map![e],
         lot: map![e],
-        block: e.ledger().sequence() + 1,
+        block: auction_start_block(e),
     };
 
     // validate and create bid auction data
@@ -42,7 +43,7 @@ pub fn create_bad_debt_auction_data(
         panic_with_error!(e, PoolError::MaxPositionsExceeded);
     }
 
-    let oracle_scalar = 10i128.pow(pool.load_price_decimals(e));
+    let oracle_scalar = decimal_scalar(pool.load_price_decimals(e));
     let backstop_positions = storage::get_user_positions(e, &backstop);
     let mut debt_value = 0;
     for bid_asset in bid {
@@ -52,6 +53,10 @@ pub fn create_bad_debt_auction_data(
             .get(reserve.config.index)
             .unwrap_or(0);
         if liability_balance > 0 {
+            if storage::get_sunset(e, &reserve.asset) {
+                // sunset reserves cannot be priced, so their debt is left to be defaulted
+                continue;
+            }
             let asset_to_base = pool.load_price(e, &reserve.asset);
             let asset_balance = reserve.to_asset_from_d_token(e, liability_balance);
             debt_value += i128(asset_to_base).fixed_mul_floor(e, &asset_balance, &reserve.scalar);
@@ -128,13 +133,57 @@ pub fn fill_bad_debt_auction(
     backstop_state.store(e);
 }
 
+/// Auctions backstop tokens in exchange for the bad debt held by the backstop
+pub struct BadDebtAuction;
+
+impl AuctionStrategy for BadDebtAuction {
+    fn create(
+        &self,
+        e: &Env,
+        user: &Address,
+        bid: &Vec<Address>,
+        lot: &Vec<Address>,
+        percent: u32,
+    ) -> AuctionData {
+        create_bad_debt_auction_data(e, user, bid, lot, percent)
+    }
+
+    fn settle(
+        &self,
+        e: &Env,
+        pool: &mut Pool,
+        auction_data: &AuctionData,
+        _user: &Address,
+        filler_state: &mut User,
+        is_full_fill: bool,
+        _use_allowance: bool,
+    ) {
+        fill_bad_debt_auction(e, pool, auction_data, filler_state, is_full_fill);
+    }
+
+    fn bid_to_underlying(&self, e: &Env, pool: &mut Pool, asset: &Address, amount: i128) -> i128 {
+        let reserve = pool.load_reserve(e, asset, false);
+        reserve.to_asset_from_d_token(e, amount)
+    }
+
+    fn lot_to_underlying(
+        &self,
+        _e: &Env,
+        _pool: &mut Pool,
+        _asset: &Address,
+        amount: i128,
+    ) -> i128 {
+        amount
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use crate::{
         auctions::auction::AuctionType,
         pool::Positions,
-        storage::PoolConfig,
+        storage::{BadDebt, PoolConfig},
         testutils::{self, create_pool},
     };
 
@@ -1831,7 +1880,11 @@ mod tests {
             max_positions: 4,
         };
         let mut auction_data = AuctionData {
-            bid: map![&e, (underlying_0, 10_0000000), (underlying_1, 2_5000000)],
+            bid: map![
+                &e,
+                (underlying_0.clone(), 10_0000000),
+                (underlying_1.clone(), 2_5000000)
+            ],
             lot: map![&e, (lp_token.clone(), 47_6000000)],
             block: 51,
         };
@@ -1854,6 +1907,14 @@ mod tests {
             );
             storage::set_pool_config(&e, &pool_config);
             storage::set_user_positions(&e, &backstop_address, &positions);
+            storage::set_bad_debt(
+                &e,
+                &underlying_0,
+                &BadDebt {
+                    assigned: 12_0000000,
+                    defaulted: 0,
+                },
+            );
 
             let mut pool = Pool::load(&e);
             let mut samwise_state = User::load(&e, &samwise);
@@ -1880,6 +1941,14 @@ mod tests {
             );
             let backstop_positions = storage::get_user_positions(&e, &backstop_address);
             assert_eq!(backstop_positions.liabilities.len(), 0);
+            // settling bad debt leaves the running totals unchanged
+            assert_eq!(
+                storage::get_bad_debt(&e, &underlying_0),
+                BadDebt {
+                    assigned: 12_0000000,
+                    defaulted: 0,
+                }
+            );
         });
     }
 
@@ -1,6 +1,9 @@
 mod auction;
 mod backstop_interest_auction;
 mod bad_debt_auction;
+mod strategy;
 mod user_liquidation_auction;
 
 pub use auction::*;
+pub use backstop_interest_auction::{preview_interest_auction, InterestAuctionPreview};
+pub use strategy::{load_auction_strategy, AuctionStrategy};
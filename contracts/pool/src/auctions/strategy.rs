@@ -0,0 +1,116 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::pool::{Pool, User};
+
+use super::{
+    backstop_interest_auction::InterestAuction, bad_debt_auction::BadDebtAuction,
+    user_liquidation_auction::UserLiquidationAuction, AuctionData, AuctionType,
+};
+
+/// The behavior of a type of auction. Storing, scaling and deleting auctions is shared by all
+/// auction types, so a new type of auction implements this trait and is added to
+/// `load_auction_strategy`.
+pub trait AuctionStrategy {
+    /// Create the auction data for a new auction. The auction is stored by the caller.
+    ///
+    /// ### Arguments
+    /// * `user` - The user involved in the auction
+    /// * `bid` - The assets being bid on
+    /// * `lot` - The assets being auctioned off
+    /// * `percent` - The percentage of the user's positions being auctioned
+    ///
+    /// ### Panics
+    /// If the auction cannot be created
+    fn create(
+        &self,
+        e: &Env,
+        user: &Address,
+        bid: &Vec<Address>,
+        lot: &Vec<Address>,
+        percent: u32,
+    ) -> AuctionData;
+
+    /// Validate that `filler` can fill the auction of `user`
+    ///
+    /// ### Panics
+    /// If the filler cannot fill the auction
+    fn validate_fill(&self, _e: &Env, _user: &Address, _filler: &Address) {}
+
+    /// Settle a fill of the auction by exchanging the scaled bid and lot between the filler and
+    /// the user of the auction
+    ///
+    /// ### Arguments
+    /// * `pool` - The pool
+    /// * `auction_data` - The auction data scaled to the amount being filled
+    /// * `user` - The user involved in the auction
+    /// * `filler_state` - The user filling the auction
+    /// * `is_full_fill` - If the auction is filled in full
+    /// * `use_allowance` - If tokens the filler pays are moved with `transfer_from`
+    fn settle(
+        &self,
+        e: &Env,
+        pool: &mut Pool,
+        auction_data: &AuctionData,
+        user: &Address,
+        filler_state: &mut User,
+        is_full_fill: bool,
+        use_allowance: bool,
+    );
+
+    /// Convert an amount of a bid asset to underlying tokens, rounding in favor of the pool
+    fn bid_to_underlying(&self, e: &Env, pool: &mut Pool, asset: &Address, amount: i128) -> i128;
+
+    /// Convert an amount of a lot asset to underlying tokens, rounding in favor of the pool
+    fn lot_to_underlying(&self, e: &Env, pool: &mut Pool, asset: &Address, amount: i128) -> i128;
+
+    /// If the auction liquidates the positions of the user involved
+    fn liquidates_user(&self) -> bool {
+        false
+    }
+
+    /// If filling the auction can change the health of the filler
+    fn requires_filler_health_check(&self) -> bool {
+        true
+    }
+}
+
+/// Load the strategy for an auction type
+///
+/// ### Panics
+/// If the auction type is not valid
+pub fn load_auction_strategy(e: &Env, auction_type: u32) -> &'static dyn AuctionStrategy {
+    match AuctionType::from_u32(e, auction_type) {
+        AuctionType::UserLiquidation => &UserLiquidationAuction,
+        AuctionType::BadDebtAuction => &BadDebtAuction,
+        AuctionType::InterestAuction => &InterestAuction,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_auction_strategy() {
+        let e = Env::default();
+
+        let user_liquidation = load_auction_strategy(&e, AuctionType::UserLiquidation as u32);
+        assert!(user_liquidation.liquidates_user());
+        assert!(user_liquidation.requires_filler_health_check());
+
+        let bad_debt = load_auction_strategy(&e, AuctionType::BadDebtAuction as u32);
+        assert!(!bad_debt.liquidates_user());
+        assert!(bad_debt.requires_filler_health_check());
+
+        let interest = load_auction_strategy(&e, AuctionType::InterestAuction as u32);
+        assert!(!interest.liquidates_user());
+        assert!(!interest.requires_filler_health_check());
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_load_auction_strategy_invalid_type() {
+        let e = Env::default();
+        load_auction_strategy(&e, 3);
+    }
+}
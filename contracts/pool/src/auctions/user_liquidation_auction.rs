@@ -7,7 +7,10 @@ use crate::pool::{check_and_handle_user_bad_debt, Pool, PositionData, User};
 use crate::Positions;
 use crate::{errors::PoolError, storage};
 
-use super::AuctionType;
+use super::{
+    auction::{auction_start_block, require_liquidator_allowed},
+    AuctionStrategy, AuctionType,
+};
 
 pub fn create_user_liq_auction_data(
     e: &Env,
@@ -26,15 +29,16 @@ pub fn create_user_liq_auction_data(
         panic_with_error!(e, PoolError::InvalidLiquidation);
     }
 
+    let start_block = auction_start_block(e);
     let mut liquidation_quote = AuctionData {
         bid: map![e],
         lot: map![e],
-        block: e.ledger().sequence() + 1,
+        block: start_block,
     };
     let mut full_liquidation_quote = AuctionData {
         bid: map![e],
         lot: map![e],
-        block: e.ledger().sequence() + 1,
+        block: start_block,
     };
     let mut pool = Pool::load(e);
     if pool.config.max_positions < (lot.len() + bid.len()) {
@@ -51,6 +55,15 @@ pub fn create_user_liq_auction_data(
         panic_with_error!(e, PoolError::InvalidLiquidation);
     }
 
+    // defer liquidating retail accounts until they are further underwater
+    if let Some(retail) = storage::get_retail_liquidation(e) {
+        if position_data.collateral_raw < retail.threshold
+            && !position_data.is_hf_under(e, i128(retail.min_hf))
+        {
+            panic_with_error!(e, PoolError::InvalidLiquidation);
+        }
+    }
+
     // build position data from included assets
     let mut positions_auctioned = Positions::env_default(e);
     for bid_asset in bid {
@@ -216,18 +229,70 @@ pub fn fill_user_liq_auction(
     filler_state.add_positions(e, pool, auction_data.lot.clone(), auction_data.bid.clone());
 
     if is_full_fill {
+        // only filled liquidations count against the user's risk tier, and an auction filled
+        // over several partial fills counts once
+        let liquidations = storage::get_liquidation_count(e, user);
+        storage::set_liquidation_count(e, user, &(liquidations + 1));
+
         check_and_handle_user_bad_debt(e, pool, user, &mut user_state);
     }
     user_state.store(e);
 }
 
+/// Liquidates a user's collateral in exchange for their liabilities
+pub struct UserLiquidationAuction;
+
+impl AuctionStrategy for UserLiquidationAuction {
+    fn create(
+        &self,
+        e: &Env,
+        user: &Address,
+        bid: &Vec<Address>,
+        lot: &Vec<Address>,
+        percent: u32,
+    ) -> AuctionData {
+        create_user_liq_auction_data(e, user, bid, lot, percent)
+    }
+
+    fn validate_fill(&self, e: &Env, _user: &Address, filler: &Address) {
+        require_liquidator_allowed(e, filler);
+    }
+
+    fn settle(
+        &self,
+        e: &Env,
+        pool: &mut Pool,
+        auction_data: &AuctionData,
+        user: &Address,
+        filler_state: &mut User,
+        is_full_fill: bool,
+        _use_allowance: bool,
+    ) {
+        fill_user_liq_auction(e, pool, auction_data, user, filler_state, is_full_fill);
+    }
+
+    fn bid_to_underlying(&self, e: &Env, pool: &mut Pool, asset: &Address, amount: i128) -> i128 {
+        let reserve = pool.load_reserve(e, asset, false);
+        reserve.to_asset_from_d_token(e, amount)
+    }
+
+    fn lot_to_underlying(&self, e: &Env, pool: &mut Pool, asset: &Address, amount: i128) -> i128 {
+        let reserve = pool.load_reserve(e, asset, false);
+        reserve.to_asset_from_b_token(e, amount)
+    }
+
+    fn liquidates_user(&self) -> bool {
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use crate::{
         auctions::auction::AuctionType,
         pool::Positions,
-        storage::{self, PoolConfig},
+        storage::{self, PoolConfig, RetailLiquidation},
         testutils::{self, create_pool},
     };
 
@@ -2508,6 +2573,139 @@ mod tests {
         });
     }
 
+    /// Setup a user with 10 tokens of collateral worth $20 and `liabilities` tokens of debt worth
+    /// $4 each, in reserves with collateral and liability factors of 1, with the pool's retail
+    /// liquidation threshold set to `threshold` and its minimum health factor to 0.98
+    ///
+    /// Returns (pool, user, collateral asset, liability asset)
+    fn setup_retail_liquidation(
+        e: &Env,
+        liabilities: i128,
+        threshold: i128,
+    ) -> (Address, Address, Address, Address) {
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+        testutils::set_ledger(e, 12345, 50);
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+
+        let pool_address = create_pool(e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(e);
+
+        let mut underlying = vec![e];
+        for index in 0..2 {
+            let (asset, _) = testutils::create_token_contract(e, &bombadil);
+            let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+            reserve_config.c_factor = 1_0000000;
+            reserve_config.l_factor = 1_0000000;
+            reserve_config.index = index;
+            reserve_data.last_time = 12345;
+            testutils::create_reserve(e, &pool_address, &asset, &reserve_config, &reserve_data);
+            underlying.push_back(asset);
+        }
+        let underlying_0 = underlying.get_unchecked(0);
+        let underlying_1 = underlying.get_unchecked(1);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 2_0000000, 4_0000000]);
+
+        let positions: Positions = Positions {
+            collateral: map![e, (0, 10_0000000)],
+            liabilities: map![e, (1, liabilities)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(e, &samwise, &positions);
+            storage::set_pool_config(e, &pool_config);
+            storage::set_backstop(e, &Address::generate(e));
+            storage::set_retail_liquidation(
+                e,
+                &RetailLiquidation {
+                    threshold,
+                    min_hf: 0_9800000,
+                },
+            );
+        });
+        (pool_address, samwise, underlying_0, underlying_1)
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1211)")]
+    fn test_create_user_liquidation_defers_retail_account() {
+        let e = Env::default();
+        // the user's health factor is ~0.98, and their collateral is under the threshold
+        let (pool_address, samwise, underlying_0, underlying_1) =
+            setup_retail_liquidation(&e, 5_1000000, 100_0000000);
+
+        e.as_contract(&pool_address, || {
+            create_user_liq_auction_data(
+                &e,
+                &samwise,
+                &vec![&e, underlying_1.clone()],
+                &vec![&e, underlying_0.clone()],
+                45,
+            );
+        });
+    }
+
+    #[test]
+    fn test_create_user_liquidation_retail_account_under_min_hf() {
+        let e = Env::default();
+        // the user's health factor is ~0.96, under the retail minimum
+        let (pool_address, samwise, underlying_0, underlying_1) =
+            setup_retail_liquidation(&e, 5_2000000, 100_0000000);
+
+        e.as_contract(&pool_address, || {
+            let auction_data = create_user_liq_auction_data(
+                &e,
+                &samwise,
+                &vec![&e, underlying_1.clone()],
+                &vec![&e, underlying_0.clone()],
+                100,
+            );
+            assert_eq!(auction_data.bid.get_unchecked(underlying_1), 5_2000000);
+            assert_eq!(auction_data.lot.get_unchecked(underlying_0), 10_0000000);
+        });
+    }
+
+    #[test]
+    fn test_create_user_liquidation_non_retail_account_over_min_hf() {
+        let e = Env::default();
+        // the user's health factor is ~0.98, but their collateral is over the threshold
+        let (pool_address, samwise, underlying_0, underlying_1) =
+            setup_retail_liquidation(&e, 5_1000000, 10_0000000);
+
+        e.as_contract(&pool_address, || {
+            let auction_data = create_user_liq_auction_data(
+                &e,
+                &samwise,
+                &vec![&e, underlying_1.clone()],
+                &vec![&e, underlying_0.clone()],
+                100,
+            );
+            assert_eq!(auction_data.bid.get_unchecked(underlying_1), 5_1000000);
+            assert_eq!(auction_data.lot.get_unchecked(underlying_0), 10_0000000);
+        });
+    }
+
     #[test]
     fn test_fill_user_liquidation_auction() {
         let e = Env::default();
@@ -2687,6 +2885,49 @@ mod tests {
                     .unwrap(),
                 02_7500000 - 1_2375000
             );
+            assert_eq!(storage::get_liquidation_count(&e, &samwise), 1);
+        });
+    }
+
+    #[test]
+    fn test_fill_user_liquidation_auction_partial_fills_count_once() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying_0, underlying_1) =
+            setup_retail_liquidation(&e, 5_2000000, 0);
+        let frodo = Address::generate(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_1.clone(), 1_0000000)],
+            lot: map![&e, (underlying_0.clone(), 2_0000000)],
+            block: 50,
+        };
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            for _ in 0..2 {
+                fill_user_liq_auction(
+                    &e,
+                    &mut pool,
+                    &auction_data,
+                    &samwise,
+                    &mut frodo_state,
+                    false,
+                );
+                assert_eq!(storage::get_liquidation_count(&e, &samwise), 0);
+            }
+            fill_user_liq_auction(
+                &e,
+                &mut pool,
+                &auction_data,
+                &samwise,
+                &mut frodo_state,
+                true,
+            );
+            assert_eq!(storage::get_liquidation_count(&e, &samwise), 1);
+
+            let samwise_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(samwise_positions.collateral.get_unchecked(0), 4_0000000);
+            assert_eq!(samwise_positions.liabilities.get_unchecked(1), 2_2000000);
         });
     }
 
@@ -1,10 +1,6 @@
 /********** Numbers **********/
 
-/// Fixed-point scalar for 12 decimal numbers
-pub const SCALAR_12: i128 = 1_000_000_000_000;
-
-/// Fixed-point scalar for 7 decimal numbers
-pub const SCALAR_7: i128 = 1_0000000;
+pub use trustbridge_common::{SCALAR_12, SCALAR_7};
 
 /// Seconds per year
 pub const SECONDS_PER_YEAR: i128 = 31536000;
@@ -12,5 +8,114 @@ pub const SECONDS_PER_YEAR: i128 = 31536000;
 /// Seconds per week
 pub const SECONDS_PER_WEEK: u64 = 604800;
 
+/// The lowest health factor a position can be soft liquidated at (7 decimals)
+pub const SOFT_LIQ_MIN_HF: i128 = 0_9800000;
+
+/// The time after a shutdown before suppliers can redeem, to allow auctions to settle positions
+pub const SHUTDOWN_REDEEM_DELAY: u64 = 7 * 24 * 60 * 60;
+
+/// The number of ledgers changes to a reserve's collateral and liability factors are applied over (~1 day)
+pub const FACTOR_RAMP_LEDGERS: u32 = 17280;
+
+/// The minimum time between rate checkpoints for a reserve (1 day)
+pub const RATE_CHECKPOINT_INTERVAL: u64 = 24 * 60 * 60;
+
+/// The number of rate checkpoints kept for a reserve before the oldest is dropped (~90 days)
+pub const MAX_RATE_CHECKPOINTS: u32 = 90;
+
+/// The length of a day of a reserve's utilization history
+pub const UTIL_HISTORY_DAY: u64 = 24 * 60 * 60;
+
+/// The number of days of utilization history kept for a reserve before the oldest is dropped
+pub const MAX_UTIL_HISTORY_DAYS: u32 = 90;
+
+/// The time the admin has to review a reserve proposal before the proposer can reclaim the bond
+pub const PROPOSAL_REVIEW_WINDOW: u64 = 2 * SECONDS_PER_WEEK;
+
+/// The maximum number of days a new pool can direct all emissions to its suppliers
+pub const MAX_BOOTSTRAP_DAYS: u32 = 30;
+
 /// Max amount of reserves that can be added to a pool
 pub const MAX_RESERVES: u32 = 30;
+
+/// The maximum fraction of a covered supplier's interest that can be paid to the insurance fund
+/// as a premium (7 decimals)
+pub const MAX_INSURANCE_PREMIUM: u32 = 0_2000000;
+
+/// The time in seconds after a supplier opts into coverage before it takes effect (7 days). No
+/// premium is charged and no loss is covered until then.
+pub const INSURANCE_WAITING_PERIOD: u64 = 7 * 24 * 60 * 60;
+
+/// The maximum number of hooks run against each request
+pub const MAX_REQUEST_HOOKS: u32 = 8;
+
+/// The version of the schema of the risk parameters exported for off-chain risk simulators
+pub const RISK_PARAMS_VERSION: u32 = 2;
+
+/// The number of oracle price records averaged for the TWAP of a reserve priced with a TWAP
+pub const TWAP_RECORDS: u32 = 5;
+
+/// The number of ledgers a reserve can be without a valid oracle price before it is sunset (~1 week)
+pub const UNPRICED_SUNSET_LEDGERS: u32 = 7 * 17280;
+
+/// The longest window a reserve's utilization can be smoothed over for its interest rate (1 day)
+pub const MAX_UTIL_SMOOTHING_WINDOW: u64 = 24 * 60 * 60;
+
+/// The time a user must wait to lift a borrow freeze they placed on their own account
+pub const BORROW_FREEZE_TIMELOCK: u64 = SECONDS_PER_WEEK;
+
+/// The time an account must wait to leave supply-only mode
+pub const SUPPLY_ONLY_TIMELOCK: u64 = 2 * SECONDS_PER_WEEK;
+
+/// The maximum fraction of an interest auction's proceeds that can be routed to the treasury
+/// instead of the backstop (7 decimals)
+pub const MAX_TREASURY_SPLIT: u32 = 0_5000000;
+
+/// The maximum fraction of a reserve's accrued interest that can be taken as a protocol fee
+/// (7 decimals)
+pub const MAX_PROTOCOL_FEE: u32 = 0_2500000;
+
+/// The maximum collateral factor of a reserve of backstop shares (7 decimals)
+pub const MAX_BACKSTOP_SHARE_C_FACTOR: u32 = 0_5000000;
+
+/// The longest cooldown between a borrow and a collateral withdrawal, in ledgers (~1 hour)
+pub const MAX_WITHDRAW_COOLDOWN: u32 = 720;
+
+/// The lowest health factor a retail account can be required to fall under before it can be
+/// liquidated (7 decimals)
+pub const MIN_RETAIL_LIQ_HF: u32 = 0_9000000;
+
+/// The shortest time between bounties paid for bumping the pool's critical entries, in seconds
+/// (7 days)
+pub const BUMP_BOUNTY_PERIOD: u64 = 7 * 24 * 60 * 60;
+
+/// The percent of each reserve's backstop credit paid as a bounty for bumping the pool's
+/// critical entries (7 decimals)
+pub const BUMP_BOUNTY_PCT: i128 = 0_0010000;
+
+/// The largest multiplier that can be applied to a reserve's supplier emissions (7 decimals)
+pub const MAX_EMISSION_BOOST: u32 = 3_0000000;
+
+/// The number of risk tiers users are bucketed into, from A to E
+pub const RISK_TIERS: u32 = 5;
+
+/// The default maximum number of requests a single submit can make
+pub const DEFAULT_MAX_REQUESTS: u32 = 32;
+
+/// The default maximum number of auction fills or deletes a single submit can make
+pub const DEFAULT_MAX_AUCTION_REQUESTS: u32 = 8;
+
+// The maximum length in bytes of a pool's name
+pub const MAX_NAME_LEN: u32 = 64;
+
+// The maximum length in bytes of the URI of a pool's icon
+pub const MAX_ICON_URI_LEN: u32 = 256;
+
+// The maximum length in bytes of a pool's description
+pub const MAX_DESCRIPTION_LEN: u32 = 1024;
+
+/// The maximum number of blocks an auction's start can be randomly delayed by
+pub const MAX_AUCTION_START_OFFSET: u32 = 10;
+
+/// The maximum number of users whose positions can be fetched in one call
+pub const MAX_POSITIONS_BATCH: u32 = 50;
@@ -1,14 +1,25 @@
 use crate::{
-    auctions::{self, AuctionData},
-    emissions::{self, ReserveEmissionMetadata},
+    auctions::{self, AuctionData, InterestAuctionPreview},
+    emissions::{self, ClaimedEmissions, ReserveEmissionMetadata},
     events::PoolEvents,
-    pool::{self, FlashLoan, Positions, Request, Reserve},
-    storage::{self, ReserveConfig},
+    pool::{
+        self, FlashLoan, InvariantCheck, Positions, Request, Reserve, RiskParams, StateSnapshot,
+        SubInvocation, UserStateSnapshot,
+    },
+    storage::{
+        self, ActionLimits, BadDebt, BootstrapEmissions, Coverage, EmissionBoost,
+        EmissionMigration, InterestSplit, PoolMetadata, ProtocolFee, QueuedInterestSplit,
+        QueuedProtocolFee, QueuedReserveMigration, RateCheckpoint, RequestHook, ReserveConfig,
+        ReserveMigration, ReserveProposal, ReserveStrategy, RetailLiquidation, RiskTierConfig,
+        ShutdownData, SoftLiquidationConfig, UtilDay, UtilSmoothing,
+    },
     PoolConfig, PoolError, ReserveEmissionData, UserEmissionData,
 };
 use soroban_sdk::{
-    contract, contractclient, contractimpl, panic_with_error, Address, Env, String, Vec,
+    contract, contractclient, contractimpl, panic_with_error, Address, Bytes, BytesN, Env, Map,
+    String, Vec,
 };
+use trustbridge_common::{Asset, ContractStatus, Role};
 
 /// ### Pool
 ///
@@ -35,36 +46,45 @@ pub trait Pool {
     /// If the caller is not the proposed admin
     fn accept_admin(e: Env);
 
-    /// (Admin only) Update the pool
+    /// (Risk role only) Update the pool
     ///
     /// ### Arguments
+    /// * `caller` - The address holding the risk role
     /// * `backstop_take_rate` - The new take rate for the backstop (7 decimals)
     /// * `max_positions` - The new maximum number of allowed positions for a single user's account
     /// * `min_collateral` - The new minimum collateral required to open a borrow position,
     ///                      in the oracles base asset decimals
     ///
     /// ### Panics
-    /// If the caller is not the admin
-    fn update_pool(e: Env, backstop_take_rate: u32, max_positions: u32, min_collateral: i128);
+    /// If the caller does not hold the risk role
+    fn update_pool(
+        e: Env,
+        caller: Address,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+    );
 
-    /// (Admin only) Queues setting data for a reserve in the pool
+    /// (Risk role only) Queues setting data for a reserve in the pool
     ///
     /// ### Arguments
+    /// * `caller` - The address holding the risk role
     /// * `asset` - The underlying asset to add as a reserve
     /// * `config` - The ReserveConfig for the reserve
     ///
     /// ### Panics
-    /// If the caller is not the admin
-    fn queue_set_reserve(e: Env, asset: Address, metadata: ReserveConfig);
+    /// If the caller does not hold the risk role
+    fn queue_set_reserve(e: Env, caller: Address, asset: Address, metadata: ReserveConfig);
 
-    /// (Admin only) Cancels the queued set of a reserve in the pool
+    /// (Risk role only) Cancels the queued set of a reserve in the pool
     ///
     /// ### Arguments
+    /// * `caller` - The address holding the risk role
     /// * `asset` - The underlying asset to add as a reserve
     ///
     /// ### Panics
-    /// If the caller is not the admin or the reserve is not queued for initialization
-    fn cancel_set_reserve(e: Env, asset: Address);
+    /// If the caller does not hold the risk role, or the reserve is not queued for initialization
+    fn cancel_set_reserve(e: Env, caller: Address, asset: Address);
 
     /// Executes the queued set of a reserve in the pool
     ///
@@ -77,6 +97,83 @@ pub trait Pool {
     /// or has invalid metadata
     fn set_reserve(e: Env, asset: Address) -> u32;
 
+    /// (Risk role only) Restrict the assets that can be added as new reserves to those allowed by
+    /// `allowlist`, a contract implementing `is_asset_allowed(asset) -> bool` such as the pool
+    /// factory. Once set, the allowlist cannot be changed or removed.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `allowlist` - The address of the allowlist contract
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role or an allowlist is already set
+    fn set_reserve_allowlist(e: Env, caller: Address, allowlist: Address);
+
+    /// Fetch the contract restricting which assets can be added as reserves, if one is set
+    fn get_reserve_allowlist(e: Env) -> Option<Address>;
+
+    /// (Risk role only) Set the amount of BLND that must be bonded to propose a reserve. Reserve
+    /// proposals are disabled until a bond is set.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `bond` - The amount of BLND to bond
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role or the bond is not positive
+    fn set_proposal_bond(e: Env, caller: Address, bond: i128);
+
+    /// Propose adding a reserve to the pool by bonding BLND. The admin can accept the proposal,
+    /// which queues the reserve, or reject it. If the proposal is not reviewed within two weeks
+    /// the proposer can reclaim the bond.
+    ///
+    /// ### Arguments
+    /// * `proposer` - The address posting the bond
+    /// * `asset` - The underlying asset to add as a reserve
+    /// * `metadata` - The proposed ReserveConfig for the reserve
+    ///
+    /// ### Panics
+    /// * If reserve proposals are not enabled
+    /// * If the asset is already a reserve or has already been proposed
+    /// * If the asset is not allowed or the metadata is invalid
+    fn propose_reserve(e: Env, proposer: Address, asset: Address, metadata: ReserveConfig);
+
+    /// Fetch the proposal to add an asset as a reserve, if one exists
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the proposal
+    fn get_reserve_proposal(e: Env, asset: Address) -> Option<ReserveProposal>;
+
+    /// (Admin only) Accept a reserve proposal, queueing the proposed reserve and returning the
+    /// bond to the proposer
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the proposal
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, no proposal exists, or the reserve cannot be queued
+    fn accept_reserve_proposal(e: Env, asset: Address);
+
+    /// (Admin only) Reject a reserve proposal. The bond is sent to the admin if `slash` is true,
+    /// and otherwise returned to the proposer.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the proposal
+    /// * `slash` - If the bond should be slashed
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or no proposal exists
+    fn reject_reserve_proposal(e: Env, asset: Address, slash: bool);
+
+    /// Return the bond of a reserve proposal that was not reviewed before it expired to the proposer
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the proposal
+    ///
+    /// ### Panics
+    /// If no proposal exists or the proposal has not expired
+    fn reclaim_proposal_bond(e: Env, asset: Address);
+
     /// Fetch the pool configuration
     fn get_config(e: Env) -> PoolConfig;
 
@@ -100,6 +197,29 @@ pub trait Pool {
     /// * `address` - The address to fetch positions for
     fn get_positions(e: Env, address: Address) -> Positions;
 
+    /// Fetch the positions for a batch of addresses in one call, in the order of `users`
+    ///
+    /// ### Arguments
+    /// * `users` - The addresses to fetch positions for
+    ///
+    /// ### Panics
+    /// If there are more than 50 addresses
+    fn get_users_positions(e: Env, users: Vec<Address>) -> Vec<Positions>;
+
+    /// Export the positions for an address as a deterministic XDR encoding, so they can be
+    /// proven to other contracts that do not depend on the pool's storage layout
+    ///
+    /// ### Arguments
+    /// * `user` - The address to export positions for
+    fn export_positions(e: Env, user: Address) -> Bytes;
+
+    /// Check if `blob` matches the exported encoding of the current positions for an address
+    ///
+    /// ### Arguments
+    /// * `user` - The address to verify positions for
+    /// * `blob` - The encoded positions, as returned by `export_positions`
+    fn verify_positions(e: Env, user: Address, blob: Bytes) -> bool;
+
     /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
     /// required tokens to the pool and `to` receives any tokens sent from the pool.
     ///
@@ -123,6 +243,8 @@ pub trait Pool {
 
     /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
     /// required tokens to the pool using transfer_from and `to` receives any tokens sent from the pool.
+    /// Backstop tokens `from` bids to fill interest auctions are also moved using transfer_from, so
+    /// fillers only need to approve the pool.
     ///
     /// Returns the new positions for `from`
     ///
@@ -180,19 +302,23 @@ pub trait Pool {
     /// can perform a status update via `set_status`
     fn update_status(e: Env) -> u32;
 
-    /// (Admin only) Pool status is changed to `pool_status`
+    /// (Admin or pauser role) Pool status is changed to `pool_status`
     /// * 0 = admin active - requires that the backstop threshold is met
     ///                 and less than 50% of backstop deposits are queued for withdrawal
     /// * 2 = admin on-ice - requires that less than 75% of backstop deposits are queued for withdrawal
     /// * 4 = admin frozen - can always be set
     ///
+    /// The pauser role can only restrict the pool further, by setting an active or on-ice pool to
+    /// admin on-ice, or by freezing it. Any other status change requires the admin.
+    ///
     /// ### Arguments
+    /// * `caller` - The admin, or an address holding the pauser role
     /// * `pool_status` - The pool status to be set
     ///
     /// ### Panics
-    /// If the caller is not the admin
+    /// If the caller does not hold the role required for the status change
     /// If the specified conditions are not met for the status to be set
-    fn set_status(e: Env, pool_status: u32);
+    fn set_status(e: Env, caller: Address, pool_status: u32);
 
     /// Gulps unaccounted for tokens to the backstop credit so they aren't lost. This is most relevant
     /// for rebasing tokens where the token balance of the pool can increase without any corresponding
@@ -215,16 +341,39 @@ pub trait Pool {
     /// Returns amount of new tokens emitted
     fn gulp_emissions(e: Env) -> i128;
 
-    /// (Admin only) Set the emission configuration for the pool
+    /// (Risk role only) Set the emission configuration for the pool
     ///
     /// Changes will be applied in the next pool `update_emissions`, and affect the next emission cycle
     ///
     /// ### Arguments
+    /// * `caller` - The address holding the risk role
     /// * `res_emission_metadata` - A vector of ReserveEmissionMetadata to update metadata to
     ///
     /// ### Panics
-    /// * If the caller is not the admin
-    fn set_emissions_config(e: Env, res_emission_metadata: Vec<ReserveEmissionMetadata>);
+    /// * If the caller does not hold the risk role
+    fn set_emissions_config(
+        e: Env,
+        caller: Address,
+        res_emission_metadata: Vec<ReserveEmissionMetadata>,
+    );
+
+    /// (Risk role only) Direct all emissions to the suppliers of a set of reserves for a number of
+    /// days, regardless of the emission configuration. Only available to a new pool before any
+    /// emissions have been distributed to it.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `reserve_indexes` - The indexes of the reserves whose suppliers receive emissions
+    /// * `days` - The number of days to bootstrap for, up to 30
+    ///
+    /// ### Panics
+    /// * If the caller does not hold the risk role
+    /// * If the pool has already bootstrapped or distributed emissions
+    /// * If `days` or any reserve index is invalid
+    fn set_bootstrap_emissions(e: Env, caller: Address, reserve_indexes: Vec<u32>, days: u32);
+
+    /// Fetch the bootstrap emissions of the pool, if the pool has bootstrapped emissions
+    fn get_bootstrap_emissions(e: Env) -> Option<BootstrapEmissions>;
 
     /// Claims outstanding emissions for the caller for the given reserve's.
     ///
@@ -232,13 +381,13 @@ pub trait Pool {
     /// - For a reserve's dTokens (liabilities), reserve_token_id = reserve_index * 2
     /// - For a reserve's bTokens (supply/collateral), reserve_token_id = reserve_index * 2 + 1
     ///
-    /// Returns the number of tokens claimed
+    /// Returns the amount of the legacy and migrated emission tokens claimed
     ///
     /// ### Arguments
     /// * `from` - The address claiming
     /// * `reserve_token_ids` - Vector of reserve token ids
     /// * `to` - The Address to send the claimed tokens to
-    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128;
+    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> ClaimedEmissions;
 
     /// Get the emissions data for a reserve token
     ///
@@ -262,6 +411,24 @@ pub trait Pool {
     fn get_user_emissions(e: Env, user: Address, reserve_token_id: u32)
         -> Option<UserEmissionData>;
 
+    /// Get the emission configuration for a reserve token, or None if the reserve token
+    /// is not configured to receive emissions
+    ///
+    /// ### Arguments
+    /// * `reserve_token_id` - The reserve token id
+    fn get_emission_config(e: Env, reserve_token_id: u32) -> Option<ReserveEmissionMetadata>;
+
+    /// Get the current emission APR for a reserve's suppliers or borrowers, scaled to 7 decimals.
+    /// Valued using the pool's oracle price for both BLND and the reserve asset.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    /// * `is_supply` - True for the bToken (supply) APR, false for the dToken (borrow) APR
+    ///
+    /// ### Panics
+    /// If the reserve does not exist or the oracle does not return a valid price
+    fn get_emission_apr(e: Env, asset: Address, is_supply: bool) -> i128;
+
     /***** Auction / Liquidation Functions *****/
 
     /// Create a new auction. Auctions are used to process liquidations, bad debt, and interest.
@@ -283,6 +450,50 @@ pub trait Pool {
         percent: u32,
     ) -> AuctionData;
 
+    /// (Risk role only) Set the minimum number of blocks that must pass after an auction begins
+    /// before it can be filled. Prevents an auction creator from filling their own auction at the
+    /// most favorable point of the curve before other fillers can react.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `min_blocks` - The minimum number of blocks. Must be less than 200.
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role, or `min_blocks` is invalid
+    fn set_auction_min_blocks(e: Env, caller: Address, min_blocks: u32);
+
+    /// (Risk role only) Enable or disable the liquidator allowlist. While enabled, only allowed
+    /// liquidators can fill user liquidation auctions.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `enabled` - If only allowed liquidators can fill liquidation auctions
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role
+    fn set_liquidator_allowlist(e: Env, caller: Address, enabled: bool);
+
+    /// (Risk role only) Add or remove an address from the liquidator allowlist
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `liquidator` - The address of the liquidator
+    /// * `allowed` - If the liquidator can fill liquidation auctions
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role
+    fn set_liquidator(e: Env, caller: Address, liquidator: Address, allowed: bool);
+
+    /// Check if an address can fill liquidation auctions. Any address can if the liquidator
+    /// allowlist is not enabled.
+    ///
+    /// ### Arguments
+    /// * `liquidator` - The address of the liquidator
+    fn is_liquidator_allowed(e: Env, liquidator: Address) -> bool;
+
+    /// Fetch the minimum number of blocks that must pass after an auction begins before it can be filled
+    fn get_auction_min_blocks(e: Env) -> u32;
+
     /// Fetch an auction from the ledger. Returns the base auction. On fill, this will be scaled based on the
     /// number of blocks that have passed since the auction was created.
     ///
@@ -294,6 +505,24 @@ pub trait Pool {
     /// If the auction does not exist
     fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData;
 
+    /// Fetch the amounts, in underlying tokens, that would be exchanged if `percent_filled` of an
+    /// auction was filled during the current block. dToken bids are rounded up and bToken lots are
+    /// rounded down, such that the quote never favors the filler over the pool.
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+    ///
+    /// ### Panics
+    /// If the auction does not exist or the percent filled is invalid
+    fn get_auction_underlying(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        percent_filled: u64,
+    ) -> AuctionData;
+
     /// Delete a stale auction. A stale auction is one that has been running for 500 blocks
     /// without being filled. This likely means something went wrong with the auction creation,
     /// and it should be re-created.
@@ -319,279 +548,2431 @@ pub trait Pool {
     /// * If there is no bad debt to handle
     /// * If there is an ongoing auction for the user
     fn bad_debt(e: Env, user: Address);
-}
 
-#[contractimpl]
-impl PoolContract {
-    /// Initialize the pool
+    /// (Risk role only) Set the soft liquidation configuration. Soft liquidations allow keepers to
+    /// deleverage positions with a health factor between 0.98 and 1 by selling a bounded amount of
+    /// collateral through the DEX adapter to repay liabilities.
     ///
     /// ### Arguments
-    /// Creator supplied:
-    /// * `admin` - The Address for the admin
-    /// * `name` - The name of the pool
-    /// * `oracle` - The contract address of the oracle
-    /// * `backstop_take_rate` - The take rate for the backstop (7 decimals)
-    /// * `max_positions` - The maximum number of positions a user is permitted to have
-    /// * `min_collateral` - The minimum collateral required to open a borrow position in the oracles base asset
+    /// * `caller` - The address holding the risk role
+    /// * `config` - The soft liquidation configuration
     ///
-    /// Pool Factory supplied:
-    /// * `backstop_id` - The contract address of the pool's backstop module
-    /// * `blnd_id` - The contract ID of the BLND token
-    pub fn __constructor(
-        e: Env,
-        admin: Address,
-        name: String,
-        oracle: Address,
-        bstop_rate: u32,
-        max_positions: u32,
-        min_collateral: i128,
-        backstop_id: Address,
-        blnd_id: Address,
-    ) {
-        admin.require_auth();
+    /// ### Panics
+    /// If the caller does not hold the risk role, or the configuration is invalid
+    fn set_soft_liquidation_config(e: Env, caller: Address, config: SoftLiquidationConfig);
 
-        pool::execute_initialize(
-            &e,
-            &admin,
-            &name,
-            &oracle,
-            &bstop_rate,
-            &max_positions,
-            &min_collateral,
-            &backstop_id,
-            &blnd_id,
-        );
-    }
-}
+    /// Fetch the soft liquidation configuration, if soft liquidations are enabled
+    fn get_soft_liquidation_config(e: Env) -> Option<SoftLiquidationConfig>;
 
-#[contractimpl]
-impl Pool for PoolContract {
-    fn propose_admin(e: Env, new_admin: Address) {
-        storage::extend_instance(&e);
-        let admin = storage::get_admin(&e);
-        admin.require_auth();
+    /// Soft liquidate a user by selling `amount` of their collateral through the DEX adapter and
+    /// using the proceeds to repay their liabilities. The keeper receives a fee from the sold collateral.
+    ///
+    /// Returns the number of d_tokens repaid
+    ///
+    /// ### Arguments
+    /// * `keeper` - The address executing the soft liquidation
+    /// * `user` - The user being soft liquidated
+    /// * `collateral_asset` - The collateral asset to sell
+    /// * `debt_asset` - The liability asset to repay
+    /// * `amount` - The amount of the collateral asset to sell, including the fee
+    /// * `min_debt_out` - The minimum amount of the debt asset to receive from the swap
+    ///
+    /// ### Panics
+    /// * If soft liquidations are not enabled
+    /// * If the user's health factor is not between 0.98 and 1
+    /// * If the amount exceeds the maximum allowed per call
+    /// * If the user's health factor is not above 1 after the soft liquidation
+    fn soft_liquidate(
+        e: Env,
+        keeper: Address,
+        user: Address,
+        collateral_asset: Address,
+        debt_asset: Address,
+        amount: i128,
+        min_debt_out: i128,
+    ) -> i128;
 
-        storage::set_proposed_admin(&e, &new_admin);
-    }
+    /// (Risk role only) Set the DEX adapter used to swap assets for interest payments
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `dex_adapter` - The address of the DEX adapter
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role
+    fn set_dex_adapter(e: Env, caller: Address, dex_adapter: Address);
 
-    fn accept_admin(e: Env) {
-        storage::extend_instance(&e);
+    /// Fetch the DEX adapter used to swap assets for interest payments, if one is set
+    fn get_dex_adapter(e: Env) -> Option<Address>;
 
-        if let Some(proposed_admin) = storage::get_proposed_admin(&e) {
-            proposed_admin.require_auth();
-            let cur_admin = storage::get_admin(&e);
+    /// Fetch the interest a user has accrued on their liabilities for a reserve, in the underlying asset
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `debt_asset` - The liability asset
+    fn get_accrued_interest(e: Env, user: Address, debt_asset: Address) -> i128;
 
-            storage::set_admin(&e, &proposed_admin);
+    /// Fetch the interest a user has been charged on their liabilities for a reserve since they
+    /// last borrowed or repaid, in the underlying asset
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `asset` - The liability asset
+    fn get_interest_paid_since(e: Env, user: Address, asset: Address) -> i128;
 
-            PoolEvents::set_admin(&e, cur_admin, proposed_admin);
-        } else {
-            panic_with_error!(&e, PoolError::BadRequest);
-        }
-    }
+    /// Repay the interest accrued on `from`'s liabilities with a different asset. `amount_in` of the
+    /// payment asset is swapped for the debt asset through the DEX adapter, and any debt asset
+    /// received beyond the accrued interest is refunded to `from`.
+    ///
+    /// Returns the amount of the debt asset repaid
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user paying the interest
+    /// * `debt_asset` - The liability asset the interest accrued on
+    /// * `pay_asset` - The asset used to pay the interest
+    /// * `amount_in` - The amount of the payment asset to swap
+    /// * `min_debt_out` - The minimum amount of the debt asset to receive from the swap
+    ///
+    /// ### Panics
+    /// * If no DEX adapter is set
+    /// * If the payment asset is the debt asset
+    /// * If the user has no accrued interest
+    fn repay_interest(
+        e: Env,
+        from: Address,
+        debt_asset: Address,
+        pay_asset: Address,
+        amount_in: i128,
+        min_debt_out: i128,
+    ) -> i128;
+
+    /// (Admin only) Shut down the pool. The pool is frozen, withdrawals are disabled, and reserve
+    /// prices are frozen at the current oracle prices. Outstanding liabilities can still be repaid
+    /// or settled through auctions. After a delay, suppliers can redeem their bTokens pro-rata for
+    /// the underlying assets remaining in the pool.
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the pool is already shut down
+    /// * If any reserve does not have a valid price
+    fn shutdown(e: Env);
 
-    fn update_pool(e: Env, backstop_take_rate: u32, max_positions: u32, min_collateral: i128) {
-        storage::extend_instance(&e);
-        let admin = storage::get_admin(&e);
-        admin.require_auth();
+    /// Fetch the shutdown state of the pool, if the pool has been shut down
+    fn get_shutdown(e: Env) -> Option<ShutdownData>;
 
-        pool::execute_update_pool(&e, backstop_take_rate, max_positions, min_collateral);
+    /// Redeem all of `from`'s bTokens in a shut down pool for their pro-rata share of the
+    /// underlying assets held by the pool
+    ///
+    /// Returns a map of asset to the amount of underlying sent to `to`
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user redeeming
+    /// * `to` - The address receiving the underlying assets
+    ///
+    /// ### Panics
+    /// * If the pool is not shut down
+    /// * If the redemption delay has not passed
+    /// * If `from` has liabilities
+    fn redeem(e: Env, from: Address, to: Address) -> Map<Address, i128>;
 
-        PoolEvents::update_pool(&e, admin, backstop_take_rate, max_positions, min_collateral);
-    }
+    /// Fetch the most recent checkpoint of a reserve's b_rate and d_rate recorded at or before
+    /// `timestamp`. Checkpoints are recorded at most once a day, and the last 90 are kept.
+    ///
+    /// Returns None if no checkpoint exists at or before `timestamp`
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    /// * `timestamp` - The timestamp to fetch the rates at
+    fn get_rate_at(e: Env, asset: Address, timestamp: u64) -> Option<RateCheckpoint>;
 
-    fn queue_set_reserve(e: Env, asset: Address, metadata: ReserveConfig) {
-        storage::extend_instance(&e);
-        let admin = storage::get_admin(&e);
-        admin.require_auth();
+    /// Fetch the number of submits and claims a user has made. The nonce is included in the
+    /// `submit` and `claim` events, so gaps indicate missed events.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn get_nonce(e: Env, user: Address) -> u64;
 
-        pool::execute_queue_set_reserve(&e, &asset, &metadata);
+    /// Fetch the maximum amount of an asset a user can borrow in a single submit, accounting
+    /// for their health factor, the pool's minimum collateral and maximum positions, and the
+    /// reserve's maximum utilization
+    ///
+    /// Returns the amount of underlying tokens, or 0 if the user cannot borrow the asset
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `asset` - The underlying asset to borrow
+    fn get_max_borrow(e: Env, user: Address, asset: Address) -> i128;
 
-        PoolEvents::queue_set_reserve(&e, admin, asset, metadata);
-    }
+    /// Fetch the maximum amount of an asset a user can withdraw in a single submit across their
+    /// supply and collateral, accounting for their health factor, the pool's minimum collateral,
+    /// and the reserve's available liquidity
+    ///
+    /// Returns the amount of underlying tokens, or 0 if the user cannot withdraw the asset
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `asset` - The underlying asset to withdraw
+    fn get_max_withdraw(e: Env, user: Address, asset: Address) -> i128;
 
-    fn cancel_set_reserve(e: Env, asset: Address) {
-        storage::extend_instance(&e);
-        let admin = storage::get_admin(&e);
-        admin.require_auth();
+    /// Fetch the pool's minimum collateral, which is denominated in the oracle's base asset, as
+    /// the amount of an asset that must be supplied as collateral to meet it at current prices
+    ///
+    /// Returns the amount of underlying tokens, or `i128::MAX` if the asset does not count as
+    /// collateral
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset to denominate the minimum collateral in
+    fn get_min_collateral_in(e: Env, asset: Address) -> i128;
 
-        pool::execute_cancel_queued_set_reserve(&e, &asset);
+    /// Fetch the token transfers `spender` must authorize as sub-invocations of a `submit` of
+    /// `requests`, so wallets can build exact authorization entries instead of authorizing any
+    /// invocation under the root call
+    ///
+    /// Returns the sub-invocations in the order the submit performs them. Interest auction fills
+    /// are not included, as the backstop tokens donated depend on the ledger of the fill.
+    ///
+    /// ### Arguments
+    /// * `spender` - The address sending tokens to the pool
+    /// * `requests` - The requests to be submitted
+    ///
+    /// ### Panics
+    /// If a request has an invalid type or a negative amount
+    fn required_auth_for(e: Env, spender: Address, requests: Vec<Request>) -> Vec<SubInvocation>;
 
-        PoolEvents::cancel_set_reserve(&e, admin, asset);
-    }
+    /// (Risk role only) Set the strategy adapter a reserve's idle liquidity can be deployed to. Any
+    /// liquidity deployed to the current adapter must be recalled before the adapter can change.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `asset` - The underlying asset of the reserve
+    /// * `adapter` - The strategy adapter
+    /// * `max_deploy` - The maximum fraction of the reserve's unborrowed supply that can be
+    ///                  deployed (7 decimals)
+    ///
+    /// ### Panics
+    /// * If the caller does not hold the risk role
+    /// * If the asset is not a reserve or `max_deploy` is over 100%
+    /// * If liquidity is deployed to a different adapter
+    fn set_reserve_strategy(
+        e: Env,
+        caller: Address,
+        asset: Address,
+        adapter: Address,
+        max_deploy: u32,
+    );
+
+    /// (Admin only) Deploy idle liquidity of a reserve to its strategy adapter. Deployed liquidity
+    /// is recalled automatically when the pool does not hold enough to cover a transfer.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    /// * `amount` - The amount of underlying tokens to deploy
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the pool is shut down or the reserve has no strategy
+    /// * If the amount exceeds the deployable liquidity
+    fn deploy_to_strategy(e: Env, asset: Address, amount: i128);
 
-    fn set_reserve(e: Env, asset: Address) -> u32 {
-        storage::extend_instance(&e);
+    /// (Admin only) Recall liquidity from a reserve's strategy adapter. Any amount recalled beyond
+    /// the deployed liquidity is accrued to the reserve's suppliers and backstop like interest.
+    ///
+    /// Returns the amount of underlying tokens recalled
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    /// * `amount` - The amount of underlying tokens to recall
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the reserve has no strategy
+    fn recall_from_strategy(e: Env, asset: Address, amount: i128) -> i128;
 
-        let index = pool::execute_set_reserve(&e, &asset);
+    /// Fetch the strategy adapter for a reserve's idle liquidity, if one is set
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_reserve_strategy(e: Env, asset: Address) -> Option<ReserveStrategy>;
 
-        PoolEvents::set_reserve(&e, asset, index);
-        index
-    }
+    /// (Treasurer role only) Set the fraction of the interest earned by covered suppliers that is
+    /// paid into the reserve's insurance fund as a premium
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the treasurer role
+    /// * `premium` - The premium (7 decimals)
+    ///
+    /// ### Panics
+    /// If the caller does not hold the treasurer role, or the premium is over 20%
+    fn set_insurance_premium(e: Env, caller: Address, premium: u32);
 
-    fn get_config(e: Env) -> PoolConfig {
+    /// Opt into or out of coverage from a reserve's insurance fund. While covered, a premium is
+    /// charged against the interest earned by `from`'s bTokens, and any loss of their value, such
+    /// as from bad debt, can be claimed from the fund. Coverage takes effect 7 days after opting
+    /// in.
+    ///
+    /// ### Arguments
+    /// * `from` - The supplier
+    /// * `asset` - The underlying asset of the reserve
+    /// * `covered` - If the supplier should be covered
+    ///
+    /// ### Panics
+    /// * If the asset is not a reserve
+    /// * If opting out with an unclaimed loss
+    fn set_coverage(e: Env, from: Address, asset: Address, covered: bool);
+
+    /// Claim a covered supplier's losses from a reserve's insurance fund, up to the amount held
+    /// by the fund
+    ///
+    /// Returns the amount of underlying tokens claimed
+    ///
+    /// ### Arguments
+    /// * `from` - The supplier
+    /// * `asset` - The underlying asset of the reserve
+    /// * `to` - The address receiving the claim
+    ///
+    /// ### Panics
+    /// If `from` is not covered or has nothing to claim
+    fn claim_coverage(e: Env, from: Address, asset: Address, to: Address) -> i128;
+
+    /// Fetch a supplier's coverage from a reserve's insurance fund, if they are covered. The
+    /// claimable loss is as of the last time the coverage was accrued.
+    ///
+    /// ### Arguments
+    /// * `user` - The supplier
+    /// * `asset` - The underlying asset of the reserve
+    fn get_coverage(e: Env, user: Address, asset: Address) -> Option<Coverage>;
+
+    /// Fetch the amount of underlying tokens held by a reserve's insurance fund
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_insurance_fund(e: Env, asset: Address) -> i128;
+
+    /// (Admin only) Set the ordered list of hooks run against each request before it is applied.
+    /// The default `NonNegative` and `PoolStatus` hooks must be included, and `External` hooks
+    /// call a validator contract that can reject the request.
+    ///
+    /// ### Arguments
+    /// * `hooks` - The request hooks
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If a default hook is missing, a hook is duplicated, or there are more than 8 hooks
+    fn set_request_hooks(e: Env, hooks: Vec<RequestHook>);
+
+    /// Fetch the ordered list of hooks run against each request
+    fn get_request_hooks(e: Env) -> Vec<RequestHook>;
+
+    /// Export the risk parameters of the pool and its reserves for off-chain risk simulators.
+    /// Includes the reserve factors currently in effect, supply caps, interest rate curves, and
+    /// oracle metadata. The result is versioned, so simulators can check they support its schema.
+    fn export_risk_params(e: Env) -> RiskParams;
+
+    /// (Risk role only) Set if transfers into a reserve are credited by the change in the pool's
+    /// balance instead of the amount requested. Enable for tokens that charge a fee on transfer,
+    /// so supplies and repayments only credit the tokens the pool receives.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `asset` - The underlying asset of the reserve
+    /// * `enabled` - If transfers are credited by the change in the pool's balance
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role, or the reserve does not exist
+    fn set_balance_delta(e: Env, caller: Address, asset: Address, enabled: bool);
+
+    /// Fetch if transfers into a reserve are credited by the change in the pool's balance
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_balance_delta(e: Env, asset: Address) -> bool;
+
+    /// Donate tokens to the suppliers of a reserve. The donation increases the reserve's bRate, so
+    /// it is distributed to suppliers in proportion to their bTokens. This can be used by
+    /// incentive programs to boost the supply APY of a reserve.
+    ///
+    /// Returns the new bRate of the reserve
+    ///
+    /// ### Arguments
+    /// * `from` - The address donating tokens
+    /// * `asset` - The underlying asset of the reserve
+    /// * `amount` - The amount of tokens to donate
+    ///
+    /// ### Panics
+    /// If the amount is not positive or the reserve has no suppliers
+    fn donate_to_reserve(e: Env, from: Address, asset: Address, amount: i128) -> i128;
+
+    /// Create a user liquidation auction for all of a user's positions. The bid includes every
+    /// liability and the lot includes every collateral position of the user, so only the percent
+    /// liquidated is required. Use `new_auction` to choose the assets included in the auction.
+    ///
+    /// ### Arguments
+    /// * `user` - The Address of the user being liquidated
+    /// * `percent` - The percent of the user's positions to liquidate (15 => 15%)
+    ///
+    /// ### Panics
+    /// * If the user cannot be liquidated
+    /// * If the liquidation is too large or too small for the percent
+    fn new_liquidation_auction(e: Env, user: Address, percent: u32) -> AuctionData;
+
+    /// (Admin only) Forgive interest accrued on a user's liabilities without payment. The
+    /// forgiven interest is paid for by the reserve's backstop credit, and the principal of the
+    /// liability is unchanged.
+    ///
+    /// Returns the d_tokens burnt
+    ///
+    /// ### Arguments
+    /// * `user` - The user whose interest is forgiven
+    /// * `asset` - The liability asset the interest accrued on
+    /// * `amount` - The amount of interest to forgive, in the underlying asset
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the amount is more than the user's accrued interest or the reserve's backstop credit
+    fn forgive_interest(e: Env, user: Address, asset: Address, amount: i128) -> i128;
+
+    /// Set or clear the hash of the key notifier services use to route `user`'s health alerts.
+    /// While set, a `health_alert` event with the key is emitted when the user is liquidated, soft
+    /// liquidated, or has bad debt.
+    ///
+    /// ### Arguments
+    /// * `user` - The user
+    /// * `notification_key` - The 32 byte hash of the notification key, or None to clear it
+    fn set_notification_key(e: Env, user: Address, notification_key: Option<BytesN<32>>);
+
+    /// Fetch the hash of a user's notification key, if set
+    ///
+    /// ### Arguments
+    /// * `user` - The user
+    fn get_notification_key(e: Env, user: Address) -> Option<BytesN<32>>;
+
+    /// (Risk role only) Set the width of an oracle price's confidence interval, relative to the
+    /// price, above which collateral is valued at the bottom of the interval and liabilities at
+    /// the top. The pool's oracle must support `lastprice_v2` while the threshold is above 0.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `threshold` - The threshold (7 decimals), or 0 to disable confidence intervals
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role or the threshold is over 100%
+    fn set_confidence_threshold(e: Env, caller: Address, threshold: u32);
+
+    /// Fetch the confidence threshold for oracle prices. 0 if confidence intervals are disabled.
+    fn get_confidence_threshold(e: Env) -> u32;
+
+    /// (Risk role only) Set if health calculations price a reserve with the oracle's TWAP as well
+    /// as its spot price. While enabled, the reserve is valued at the lower of the two as
+    /// collateral and the higher of the two as a liability. Enable for volatile reserves whose spot
+    /// price can be moved briefly, and leave disabled for stable reserves. The pool's oracle must
+    /// return the reserve's price history from `prices` while enabled.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `asset` - The underlying asset of the reserve
+    /// * `enabled` - If the reserve is priced with the oracle's TWAP
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role, or the reserve does not exist
+    fn set_twap_pricing(e: Env, caller: Address, asset: Address, enabled: bool);
+
+    /// Fetch if health calculations price a reserve with the oracle's TWAP as well as its spot
+    /// price
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_twap_pricing(e: Env, asset: Address) -> bool;
+
+    /// Check if the pool's oracle has a valid price for a reserve. Anyone can call this to
+    /// record when a reserve lost its price, and to sunset the reserve once it has been unpriced
+    /// for `UNPRICED_SUNSET_LEDGERS`. A sunset reserve is disabled for supply and borrows and is
+    /// valued at 0 as collateral, so users holding it can still manage their other positions.
+    /// Sunset reserves must be re-enabled by the admin once the oracle prices them again.
+    ///
+    /// Returns true if the reserve is sunset
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    ///
+    /// ### Panics
+    /// If the reserve does not exist
+    fn check_reserve_price(e: Env, asset: Address) -> bool;
+
+    /// (Treasurer role only) Queue a split routing a portion of interest auction proceeds to the
+    /// treasury. The split can be set after a week, unless the pool is in setup. Any previously
+    /// queued split is replaced.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the treasurer role
+    /// * `split` - The treasury and the percent of proceeds it receives (7 decimals)
+    ///
+    /// ### Panics
+    /// If the caller does not hold the treasurer role or the percent is over `MAX_TREASURY_SPLIT`
+    fn queue_set_interest_split(e: Env, caller: Address, split: InterestSplit);
+
+    /// (Treasurer role only) Cancel the queued interest split
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the treasurer role
+    ///
+    /// ### Panics
+    /// If the caller does not hold the treasurer role, or no interest split is queued
+    fn cancel_set_interest_split(e: Env, caller: Address);
+
+    /// (Treasurer role only) Set the queued interest split once its timelock has passed. The
+    /// rest of the backstop tokens bid are donated to the backstop.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the treasurer role
+    ///
+    /// ### Panics
+    /// If the caller does not hold the treasurer role, no interest split is queued, or the
+    /// timelock has not passed
+    fn set_interest_split(e: Env, caller: Address);
+
+    /// (Treasurer role only) Remove the interest split, so all interest auction proceeds are
+    /// donated to the backstop
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the treasurer role
+    ///
+    /// ### Panics
+    /// If the caller does not hold the treasurer role, or no interest split is set
+    fn disable_interest_split(e: Env, caller: Address);
+
+    /// Fetch the split of interest auction proceeds routed to the treasury, if set
+    fn get_interest_split(e: Env) -> Option<InterestSplit>;
+
+    /// Fetch the queued interest split, if any
+    fn get_queued_interest_split(e: Env) -> Option<QueuedInterestSplit>;
+
+    /// Freeze borrowing from `user`'s account, or request that the freeze be lifted. A freeze
+    /// takes effect immediately and blocks Borrow requests and flash loans against the account.
+    /// Lifting a freeze only takes effect after `BORROW_FREEZE_TIMELOCK`, so a compromised key
+    /// cannot immediately borrow against the account's collateral. Freezing again cancels a
+    /// pending unfreeze.
+    ///
+    /// Returns the time the freeze lifts, or `u64::MAX` if no unfreeze is requested
+    ///
+    /// ### Arguments
+    /// * `user` - The user
+    /// * `frozen` - True to freeze borrowing, false to request the freeze be lifted
+    ///
+    /// ### Panics
+    /// If `frozen` is false and the user has not frozen borrowing
+    fn set_borrow_freeze(e: Env, user: Address, frozen: bool) -> u64;
+
+    /// Fetch the time a user's borrow freeze lifts, if the user has frozen borrowing. `u64::MAX`
+    /// if no unfreeze has been requested.
+    ///
+    /// ### Arguments
+    /// * `user` - The user
+    fn get_borrow_freeze(e: Env, user: Address) -> Option<u64>;
+
+    /// Opt `user` into supply-only mode, or request to leave it. While in supply-only mode, the
+    /// account cannot take on liabilities: Borrow requests, flash loans, and fills of liquidation
+    /// and bad debt auctions are rejected. Leaving only takes effect after `SUPPLY_ONLY_TIMELOCK`.
+    /// Opting in again cancels a pending exit.
+    ///
+    /// Returns the time the account leaves supply-only mode, or `u64::MAX` if no exit is requested
+    ///
+    /// ### Arguments
+    /// * `user` - The user
+    /// * `enabled` - True to opt into supply-only mode, false to request to leave it
+    ///
+    /// ### Panics
+    /// * If `enabled` is true and the user has liabilities
+    /// * If `enabled` is false and the user is not in supply-only mode
+    fn set_supply_only(e: Env, user: Address, enabled: bool) -> u64;
+
+    /// Fetch the time an account leaves supply-only mode, if it opted in. `u64::MAX` if no exit
+    /// has been requested.
+    ///
+    /// ### Arguments
+    /// * `user` - The user
+    fn get_supply_only(e: Env, user: Address) -> Option<u64>;
+
+    /// Set or clear the ed25519 public key `user` signs requests with off-chain, so a relayer can
+    /// submit them with `submit_signed`
+    ///
+    /// ### Arguments
+    /// * `user` - The user
+    /// * `signing_key` - The ed25519 public key, or None to disable relayed submits
+    fn set_signing_key(e: Env, user: Address, signing_key: Option<BytesN<32>>);
+
+    /// Fetch the ed25519 public key a user signs requests with, if set
+    ///
+    /// ### Arguments
+    /// * `user` - The user
+    fn get_signing_key(e: Env, user: Address) -> Option<BytesN<32>>;
+
+    /// Fetch the canonical hash of a set of requests: the sha256 of the concatenated typed struct
+    /// hashes of each request
+    ///
+    /// ### Arguments
+    /// * `requests` - The requests to hash
+    fn hash_requests(e: Env, requests: Vec<Request>) -> BytesN<32>;
+
+    /// Submit a set of requests on behalf of `from` that `from` signed off-chain. Anyone can
+    /// relay the requests. `from` sends and receives all tokens, and tokens sent to the pool are
+    /// moved with transfer_from, so `from` must approve the pool.
+    ///
+    /// `from` signs the message built by `signed_submit_payload` with their signing key. The
+    /// message is bound to this pool and network and to `from`'s nonce, so it can't be replayed.
+    ///
+    /// Returns the new positions for `from`
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `requests` - A vec of requests to be processed
+    /// * `signature` - The ed25519 signature of the request bundle by `from`'s signing key
+    /// * `nonce` - `from`'s current nonce
+    /// * `expiry` - The last timestamp the requests can be submitted at
+    ///
+    /// ### Panics
+    /// * If `from` has no signing key or the signature is invalid
+    /// * If the nonce is not `from`'s current nonce or the requests have expired
+    /// * If the requests are not able to be completed
+    fn submit_signed(
+        e: Env,
+        from: Address,
+        requests: Vec<Request>,
+        signature: BytesN<64>,
+        nonce: u64,
+        expiry: u64,
+    ) -> Positions;
+
+    /// (Admin only) Schedule the migration of the pool's emissions to a new emission token
+    ///
+    /// Each reserve token's emissions are migrated the first time emissions are gulped at or
+    /// after `ledger`. Emissions distributed in the current token keep accruing until they
+    /// expire, and remain claimable in the current token.
+    ///
+    /// ### Arguments
+    /// * `token` - The new emission token
+    /// * `ledger` - The ledger sequence the migration takes effect at
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If a migration is already configured
+    /// * If the ledger is not in the future or the token is the current emission token
+    fn set_emission_migration(e: Env, token: Address, ledger: u32);
+
+    /// Fetch the pool's emission token migration, if one is configured
+    fn get_emission_migration(e: Env) -> Option<EmissionMigration>;
+
+    /// Get the emissions data for a reserve token from before its emissions were migrated
+    ///
+    /// ### Arguments
+    /// * `reserve_token_id` - The reserve token id
+    fn get_legacy_reserve_emissions(e: Env, reserve_token_id: u32) -> Option<ReserveEmissionData>;
+
+    /// Get the emissions data for a user from before a reserve token's emissions were migrated.
+    /// Users that have not interacted with the reserve token since it was migrated have their
+    /// legacy emissions tracked by `get_user_emissions`.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `reserve_token_id` - The reserve token id
+    fn get_legacy_user_emissions(
+        e: Env,
+        user: Address,
+        reserve_token_id: u32,
+    ) -> Option<UserEmissionData>;
+
+    /// Fetch a commitment to the pool's reserves at the current ledger, for external bridges and
+    /// light clients to reference. The root is the sha256 of `state_preimage`.
+    fn state_root(e: Env) -> BytesN<32>;
+
+    /// Fetch the snapshot of the pool's reserves committed to by `state_root`
+    fn state_snapshot(e: Env) -> StateSnapshot;
+
+    /// Fetch the preimage of `state_root`: the XDR encoding of `state_snapshot`
+    fn state_preimage(e: Env) -> Bytes;
+
+    /// Fetch a commitment to a user's positions at the current ledger, bound to the pool's
+    /// `state_root`. The root is the sha256 of `user_state_preimage`.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn user_state_root(e: Env, user: Address) -> BytesN<32>;
+
+    /// Fetch the snapshot of a user's positions committed to by `user_state_root`
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn user_state_snapshot(e: Env, user: Address) -> UserStateSnapshot;
+
+    /// Fetch the preimage of `user_state_root`: the XDR encoding of `user_state_snapshot`
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn user_state_preimage(e: Env, user: Address) -> Bytes;
+
+    /// (Treasurer role only) Queue a protocol fee taken from the interest accrued by each
+    /// reserve, on top of the backstop take rate. The fee is timelocked, and replaces any fee
+    /// already queued.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the treasurer role
+    /// * `fee` - The treasury and the percent of accrued interest it receives (7 decimals)
+    ///
+    /// ### Panics
+    /// If the caller does not hold the treasurer role, the percent is over `MAX_PROTOCOL_FEE`, or
+    /// the percent and the backstop take rate would take all of the accrued interest
+    fn queue_set_protocol_fee(e: Env, caller: Address, fee: ProtocolFee);
+
+    /// (Treasurer role only) Cancel the queued protocol fee
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the treasurer role
+    ///
+    /// ### Panics
+    /// If the caller does not hold the treasurer role, or no protocol fee is queued
+    fn cancel_set_protocol_fee(e: Env, caller: Address);
+
+    /// (Treasurer role only) Set the queued protocol fee once its timelock has passed. Every
+    /// reserve is accrued at the previous rate first.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the treasurer role
+    ///
+    /// ### Panics
+    /// If the caller does not hold the treasurer role, no protocol fee is queued, the timelock
+    /// has not passed, or the percent and the backstop take rate would take all of the accrued
+    /// interest
+    fn set_protocol_fee(e: Env, caller: Address);
+
+    /// (Treasurer role only) Disable the protocol fee. The treasury is kept so fees accrued
+    /// before the fee was disabled can still be claimed.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the treasurer role
+    ///
+    /// ### Panics
+    /// If the caller does not hold the treasurer role, or no protocol fee is set
+    fn disable_protocol_fee(e: Env, caller: Address);
+
+    /// Fetch the protocol fee taken from accrued interest, if set
+    fn get_protocol_fee(e: Env) -> Option<ProtocolFee>;
+
+    /// Fetch the queued protocol fee, if any
+    fn get_queued_protocol_fee(e: Env) -> Option<QueuedProtocolFee>;
+
+    /// Fetch the amount of underlying tokens owed to the treasury from a reserve's protocol fees,
+    /// as of the reserve's last update
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_protocol_fee_credit(e: Env, asset: Address) -> i128;
+
+    /// Send the protocol fees owed from a reserve to the treasury. Anyone can call this.
+    ///
+    /// Returns the amount of underlying tokens sent
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    ///
+    /// ### Panics
+    /// If no protocol fee has been set, or no fees are owed
+    fn claim_protocol_fees(e: Env, asset: Address) -> i128;
+
+    /// (Risk role only) Set the maximum number of requests a single submit can make, and how many
+    /// of them can fill or delete auctions. Submits over either limit fail before any request is
+    /// applied.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `limits` - The action limits
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role, either limit is zero, or the auction limit is
+    /// over the request limit
+    fn set_action_limits(e: Env, caller: Address, limits: ActionLimits);
+
+    /// Fetch the maximum number of requests a single submit can make
+    fn get_action_limits(e: Env) -> ActionLimits;
+
+    /// Preview the interest auction that would be created from a set of reserves at current
+    /// prices, so keepers can weigh the value of the lot against the cost of creating it. Nothing
+    /// is written to the ledger.
+    ///
+    /// ### Arguments
+    /// * `assets` - The underlying assets of the reserves to include in the lot
+    ///
+    /// ### Panics
+    /// If a reserve does not exist or does not have a valid price
+    fn preview_interest_auction(e: Env, assets: Vec<Address>) -> InterestAuctionPreview;
+
+    /// Fetch the status of the pool as a `ContractStatus`, shared with the oracle, backstop and
+    /// pool factory. Active statuses (0, 1) are Active, on ice statuses (2, 3) are Restricted, and
+    /// frozen statuses are Frozen.
+    fn status(e: Env) -> ContractStatus;
+
+    /// (Risk role only) Set the window a reserve's utilization is smoothed over for its interest
+    /// rate. While set, rates are calculated from an exponential moving average of the
+    /// utilization, so a single large borrow or repay cannot whipsaw the reserve's rate.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `asset` - The underlying asset of the reserve
+    /// * `window` - The time in seconds the utilization is averaged over, or 0 to disable
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role, the reserve does not exist, or the window is over
+    /// `MAX_UTIL_SMOOTHING_WINDOW`
+    fn set_util_smoothing(e: Env, caller: Address, asset: Address, window: u64);
+
+    /// Fetch the utilization smoothing of a reserve, if it is enabled
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_util_smoothing(e: Env, asset: Address) -> Option<UtilSmoothing>;
+
+    /// Fetch the running totals of a reserve's bad debt assigned to and defaulted by the backstop,
+    /// in dTokens. The bad debt currently held by the backstop is its liabilities.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_bad_debt(e: Env, asset: Address) -> BadDebt;
+
+    /// Fetch the value of the bad debt held by the backstop across all reserves, in the oracle's
+    /// base asset
+    ///
+    /// ### Panics
+    /// If a reserve with outstanding bad debt does not have a valid price
+    fn get_total_bad_debt(e: Env) -> i128;
+
+    /// Fill a user liquidation auction without holding any of the bid asset, in a single call.
+    /// The filler takes on the bid as liabilities like a flash loan, the lot is withdrawn and
+    /// sold through the DEX adapter for the bid asset, and the proceeds repay the liabilities.
+    /// Any proceeds left over are sent to the filler. Only auctions with a single bid asset can
+    /// be flash filled.
+    ///
+    /// Returns the amount of the bid asset sent to the filler
+    ///
+    /// ### Arguments
+    /// * `filler` - The address filling the auction
+    /// * `user` - The user being liquidated
+    /// * `percent_filled` - The percentage of the auction to fill
+    /// * `min_profit` - The minimum amount of the bid asset the filler must receive
+    ///
+    /// ### Panics
+    /// * If no DEX adapter is set, or the auction bids more than one asset
+    /// * If the proceeds of the lot do not cover the bid plus `min_profit`
+    fn flash_fill_auction(
+        e: Env,
+        filler: Address,
+        user: Address,
+        percent_filled: u64,
+        min_profit: i128,
+    ) -> i128;
+
+    /// (Admin only) Set the guardian that can restrict the pool in an emergency, such as the
+    /// oracle when its guardian pauses price publication. The guardian is granted the pauser
+    /// role, and the previous guardian's pauser role is revoked.
+    ///
+    /// ### Arguments
+    /// * `guardian` - The address of the guardian, or None to remove it
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_status_guardian(e: Env, guardian: Option<Address>);
+
+    /// Fetch the guardian that can restrict the pool, if one is set
+    fn get_status_guardian(e: Env) -> Option<Address>;
+
+    /// (Pauser role only) Restrict the pool in an emergency, disabling new borrows. An active or
+    /// on-ice pool is set to admin on-ice, and only the admin can lift it. Pools that are already
+    /// admin on-ice or frozen are left unchanged. The pauser role can be granted to a contract,
+    /// such as the oracle through `set_status_guardian`, so it can restrict the pool as part of
+    /// its own emergency action.
+    ///
+    /// Returns the pool status
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the pauser role
+    ///
+    /// ### Panics
+    /// If the caller does not hold the pauser role
+    fn set_status_restricted(e: Env, caller: Address) -> u32;
+
+    /// (Admin only) Grant a role to an address. The admin holds every role, and the admin role
+    /// itself is transferred through `propose_admin` and `accept_admin`.
+    ///
+    /// ### Arguments
+    /// * `role` - The role to grant
+    /// * `member` - The address to grant the role to
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the role is the admin role
+    fn grant_role(e: Env, role: Role, member: Address);
+
+    /// (Admin only) Revoke a role from an address
+    ///
+    /// ### Arguments
+    /// * `role` - The role to revoke
+    /// * `member` - The address to revoke the role from
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the role is the admin role
+    fn revoke_role(e: Env, role: Role, member: Address);
+
+    /// Check if an address holds a role. The admin holds every role.
+    ///
+    /// ### Arguments
+    /// * `role` - The role
+    /// * `member` - The address to check
+    fn has_role(e: Env, role: Role, member: Address) -> bool;
+
+    /// Fetch the ledger timestamp of a user's last submit or claim. 0 if the user has not acted
+    /// since tracking began.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn last_action_timestamp(e: Env, user: Address) -> u64;
+
+    /// (Admin only) Set the pool's display metadata, so explorers and frontends can show the
+    /// pool's identity. The pool's name is updated to match.
+    ///
+    /// ### Arguments
+    /// * `name` - The name of the pool
+    /// * `icon_uri` - The URI of the pool's icon
+    /// * `description` - A description of the pool
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the name is empty, or any field is over its maximum length
+    fn set_metadata(e: Env, name: String, icon_uri: String, description: String);
+
+    /// Fetch the pool's display metadata
+    fn get_metadata(e: Env) -> PoolMetadata;
+
+    /// (Admin only) Set the asset the pool's values are denominated in, such as USD, EUR, or XLM.
+    /// Position values, `min_collateral`, and auction values are all in the oracle's base asset,
+    /// so the pool's oracle must quote prices in `base`. Once set, positions cannot be valued if
+    /// the oracle's base no longer matches.
+    ///
+    /// ### Arguments
+    /// * `base` - The base asset
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, or the pool's oracle does not quote prices in `base`
+    fn set_base_asset(e: Env, base: Asset);
+
+    /// Fetch the asset the pool's values are denominated in, if one is set. If not set, values
+    /// are in whatever base the pool's oracle quotes prices in.
+    fn get_base_asset(e: Env) -> Option<Asset>;
+
+    /// (Admin only) Queue the migration of a reserve to a new underlying token, like a re-issued
+    /// token or one that changed decimals. The migration is timelocked for a week unless the pool
+    /// is in setup, so users can exit the reserve before it is executed.
+    ///
+    /// ### Arguments
+    /// * `migration` - The reserve migration
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, a migration is already queued, the asset is not a
+    /// reserve, or the new asset is already a reserve
+    fn queue_reserve_migration(e: Env, migration: ReserveMigration);
+
+    /// (Admin only) Cancel the queued reserve migration
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or no migration is queued
+    fn cancel_reserve_migration(e: Env);
+
+    /// (Admin only) Execute the queued reserve migration. The reserve's accounting is converted
+    /// to the new token at the conversion rate, and the pool's balance of the old token is
+    /// exchanged with the admin for the new token at the same rate.
+    ///
+    /// Returns the amount of the new token transferred from the admin
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, no migration is queued, the timelock has not passed, or the
+    /// reserve has liquidity deployed to a strategy
+    fn migrate_reserve(e: Env) -> i128;
+
+    /// Fetch the queued reserve migration, if any
+    fn get_reserve_migration(e: Env) -> Option<QueuedReserveMigration>;
+
+    /// Fetch the lowest and highest utilization of a reserve for each day, ordered from oldest to
+    /// newest. A day's range covers the utilization each time the reserve was stored during the
+    /// day, and the last 90 days are kept.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_util_history(e: Env, asset: Address) -> Vec<UtilDay>;
+
+    /// (Admin only) Set the maximum number of blocks a new auction's start is randomly delayed
+    /// by. The delay is drawn when the auction is created, so the block the auction is best priced
+    /// at cannot be known in advance by the auction's creator and a filler colluding with them.
+    ///
+    /// ### Arguments
+    /// * `max_offset` - The maximum number of blocks. Must be at most 10, and 0 disables it.
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or `max_offset` is invalid
+    fn set_auction_start_offset(e: Env, max_offset: u32);
+
+    /// Fetch the maximum number of blocks a new auction's start is randomly delayed by
+    fn get_auction_start_offset(e: Env) -> u32;
+
+    /// (Risk role only) Set the number of ledgers an account must wait after a borrow to withdraw
+    /// collateral, unless it has repaid all of its liabilities
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `ledgers` - The number of ledgers. Must be at most 720, and 0 disables the cooldown.
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role or `ledgers` is invalid
+    fn set_withdraw_cooldown(e: Env, caller: Address, ledgers: u32);
+
+    /// Fetch the number of ledgers an account must wait after a borrow to withdraw collateral
+    fn get_withdraw_cooldown(e: Env) -> u32;
+
+    /// Recompute the accounting of a reserve from its bToken and dToken supplies and check it
+    /// against the underlying tokens held by the pool. Can be called by anyone, so monitoring can
+    /// detect accounting drift early.
+    ///
+    /// Returns the result of the check. The check fails if the pool holds fewer tokens than the
+    /// reserve's supply, backstop credit, and liabilities require.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    ///
+    /// ### Panics
+    /// If the asset is not a reserve
+    fn verify_invariants(e: Env, asset: Address) -> InvariantCheck;
+
+    /// (Risk role only) Split a reserve's share of the pool emissions between its suppliers and
+    /// borrowers, without rewriting the rest of the emission configuration
+    ///
+    /// Changes will be applied in the next pool `update_emissions`, and affect the next emission
+    /// cycle
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `res_index` - The index of the reserve
+    /// * `supply_percent` - The percent of the reserve's share paid to suppliers, from 0 to 100.
+    ///                      The rest is paid to borrowers.
+    ///
+    /// ### Panics
+    /// * If the caller does not hold the risk role
+    /// * If the percent is over 100, or the reserve does not receive any emissions
+    fn set_emission_split(e: Env, caller: Address, res_index: u32, supply_percent: u32);
+
+    /// Fetch the percent of a reserve's share of the pool emissions paid to its suppliers, or
+    /// None if the reserve does not receive any emissions
+    ///
+    /// ### Arguments
+    /// * `res_index` - The index of the reserve
+    fn get_emission_split(e: Env, res_index: u32) -> Option<u32>;
+
+    /// (Risk role only) Defer liquidations of retail accounts until they are further underwater.
+    /// Accounts with less raw collateral than the threshold can only be liquidated once their
+    /// health factor is under `min_hf`, reducing the churn of liquidating dust accounts.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `config` - The retail liquidation configuration. A threshold of 0 disables the deferral.
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role, the threshold is negative, or `min_hf` is not
+    /// between 0.9 and 1
+    fn set_retail_liquidation(e: Env, caller: Address, config: RetailLiquidation);
+
+    /// Fetch the deferred liquidation start for retail accounts, if one is set
+    fn get_retail_liquidation(e: Env) -> Option<RetailLiquidation>;
+
+    /// Bump the rent for the pool's critical entries: the instance, the reserve list, each
+    /// reserve's config and data, and the emission data of the pool and its reserves. The first
+    /// bump every 7 days pays `caller` a bounty of 0.1% of each reserve's backstop credit.
+    ///
+    /// Returns the bounty paid to `caller` for each reserve
+    ///
+    /// ### Arguments
+    /// * `caller` - The address bumping the entries
+    fn bump_critical(e: Env, caller: Address) -> Map<Address, i128>;
+
+    /// Fetch the domain separator request bundles signed for `submit_signed` are bound to: the
+    /// sha256 of the type hash of `Domain(address pool,bytes32 network)`, the XDR encoding of this
+    /// pool's address, and the network ID
+    fn domain_separator(e: Env) -> BytesN<32>;
+
+    /// Fetch the message `from` signs to authorize a relayer to submit a set of requests with
+    /// `submit_signed`. This is `0x1901`, the domain separator, and the struct hash of
+    /// `SignedSubmit(address from,Request[] requests,u64 nonce,u64 expiry)`.
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `requests` - The requests being submitted
+    /// * `nonce` - `from`'s nonce the requests are valid for
+    /// * `expiry` - The last timestamp the requests can be submitted at
+    fn signed_submit_payload(
+        e: Env,
+        from: Address,
+        requests: Vec<Request>,
+        nonce: u64,
+        expiry: u64,
+    ) -> Bytes;
+
+    /// (Risk role only) Set the thresholds a user must meet for each risk tier from A to D. Users
+    /// that meet none of them are in tier E.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `config` - The minimum health factor, minimum number of collateral reserves, and maximum
+    ///              number of liquidations for each tier
+    ///
+    /// ### Panics
+    /// If the caller does not hold the risk role, there are not thresholds for exactly 4 tiers, a
+    /// minimum health factor is under 1, or a tier is stricter than the tier above it
+    fn set_risk_tiers(e: Env, caller: Address, config: RiskTierConfig);
+
+    /// Fetch the thresholds for each risk tier
+    fn get_risk_tiers(e: Env) -> RiskTierConfig;
+
+    /// Fetch the risk tier of a user, from 0 (A) to 4 (E), based on their health factor, the
+    /// number of reserves they hold collateral in, and the number of liquidation auction fills
+    /// against them
+    ///
+    /// ### Arguments
+    /// * `user` - The user
+    fn get_risk_tier(e: Env, user: Address) -> u32;
+
+    /// (Risk role only) Boost the emissions paid to a reserve's suppliers while it is
+    /// under-supplied. Once the reserve's utilization has stayed above the target for `ledgers`,
+    /// its supplier share of the pool emissions scales up with the utilization in steps of 0.25x,
+    /// to at most `max_boost` at 100% utilization. The boost applies from the next gulp of
+    /// emissions, and the pool's emissions are split between more shares rather than increased.
+    ///
+    /// ### Arguments
+    /// * `caller` - The address holding the risk role
+    /// * `asset` - The underlying asset of the reserve
+    /// * `target_util` - The utilization the boost starts above, with 7 decimals
+    /// * `ledgers` - The ledgers the utilization must stay above the target before it is boosted
+    /// * `max_boost` - The multiplier applied to the supplier share at 100% utilization, with 7
+    ///                 decimals, or 0 to disable the boost
+    ///
+    /// ### Panics
+    /// * If the caller does not hold the risk role
+    /// * If the reserve does not exist, the target utilization is not below 100%, or the max boost
+    ///   is not between 1 and `MAX_EMISSION_BOOST`
+    fn set_emission_boost(
+        e: Env,
+        caller: Address,
+        asset: Address,
+        target_util: u32,
+        ledgers: u32,
+        max_boost: u32,
+    );
+
+    /// Fetch the boost to the emissions paid to a reserve's suppliers, if it is enabled
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_emission_boost(e: Env, asset: Address) -> Option<EmissionBoost>;
+}
+
+#[contractimpl]
+impl PoolContract {
+    /// Initialize the pool
+    ///
+    /// ### Arguments
+    /// Creator supplied:
+    /// * `admin` - The Address for the admin
+    /// * `name` - The name of the pool
+    /// * `oracle` - The contract address of the oracle
+    /// * `backstop_take_rate` - The take rate for the backstop (7 decimals)
+    /// * `max_positions` - The maximum number of positions a user is permitted to have
+    /// * `min_collateral` - The minimum collateral required to open a borrow position in the oracles base asset
+    ///
+    /// Pool Factory supplied:
+    /// * `backstop_id` - The contract address of the pool's backstop module
+    /// * `blnd_id` - The contract ID of the BLND token
+    pub fn __constructor(
+        e: Env,
+        admin: Address,
+        name: String,
+        oracle: Address,
+        bstop_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+        backstop_id: Address,
+        blnd_id: Address,
+    ) {
+        admin.require_auth();
+
+        pool::execute_initialize(
+            &e,
+            &admin,
+            &name,
+            &oracle,
+            &bstop_rate,
+            &max_positions,
+            &min_collateral,
+            &backstop_id,
+            &blnd_id,
+        );
+    }
+}
+
+#[contractimpl]
+impl Pool for PoolContract {
+    fn propose_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_proposed_admin(&e, &new_admin);
+    }
+
+    fn accept_admin(e: Env) {
+        storage::extend_instance(&e);
+
+        if let Some(proposed_admin) = storage::get_proposed_admin(&e) {
+            proposed_admin.require_auth();
+            let cur_admin = storage::get_admin(&e);
+
+            storage::set_admin(&e, &proposed_admin);
+
+            PoolEvents::set_admin(&e, cur_admin, proposed_admin);
+        } else {
+            panic_with_error!(&e, PoolError::BadRequest);
+        }
+    }
+
+    fn update_pool(
+        e: Env,
+        caller: Address,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+    ) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_update_pool(&e, backstop_take_rate, max_positions, min_collateral);
+
+        PoolEvents::update_pool(
+            &e,
+            caller,
+            backstop_take_rate,
+            max_positions,
+            min_collateral,
+        );
+    }
+
+    fn queue_set_reserve(e: Env, caller: Address, asset: Address, metadata: ReserveConfig) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_queue_set_reserve(&e, &asset, &metadata);
+
+        PoolEvents::queue_set_reserve(&e, caller, asset, metadata);
+    }
+
+    fn cancel_set_reserve(e: Env, caller: Address, asset: Address) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_cancel_queued_set_reserve(&e, &asset);
+
+        PoolEvents::cancel_set_reserve(&e, caller, asset);
+    }
+
+    fn set_reserve(e: Env, asset: Address) -> u32 {
+        storage::extend_instance(&e);
+
+        let index = pool::execute_set_reserve(&e, &asset);
+
+        PoolEvents::set_reserve(&e, asset, index);
+        index
+    }
+
+    fn set_reserve_allowlist(e: Env, caller: Address, allowlist: Address) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_reserve_allowlist(&e, &allowlist);
+
+        PoolEvents::set_reserve_allowlist(&e, caller, allowlist);
+    }
+
+    fn get_reserve_allowlist(e: Env) -> Option<Address> {
+        storage::get_reserve_allowlist(&e)
+    }
+
+    fn set_proposal_bond(e: Env, caller: Address, bond: i128) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_proposal_bond(&e, bond);
+
+        PoolEvents::set_proposal_bond(&e, caller, bond);
+    }
+
+    fn propose_reserve(e: Env, proposer: Address, asset: Address, metadata: ReserveConfig) {
+        storage::extend_instance(&e);
+        proposer.require_auth();
+
+        let proposal = pool::execute_propose_reserve(&e, &proposer, &asset, &metadata);
+
+        PoolEvents::propose_reserve(&e, asset, proposal);
+    }
+
+    fn get_reserve_proposal(e: Env, asset: Address) -> Option<ReserveProposal> {
+        storage::get_reserve_proposal(&e, &asset)
+    }
+
+    fn accept_reserve_proposal(e: Env, asset: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let proposal = pool::execute_accept_reserve_proposal(&e, &asset);
+
+        PoolEvents::accept_reserve_proposal(&e, admin.clone(), asset.clone(), proposal.proposer);
+        PoolEvents::queue_set_reserve(&e, admin, asset, proposal.config);
+    }
+
+    fn reject_reserve_proposal(e: Env, asset: Address, slash: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let proposal = pool::execute_reject_reserve_proposal(&e, &asset, slash);
+
+        PoolEvents::reject_reserve_proposal(&e, admin, asset, proposal.proposer, slash);
+    }
+
+    fn reclaim_proposal_bond(e: Env, asset: Address) {
+        storage::extend_instance(&e);
+
+        let proposal = pool::execute_reclaim_proposal_bond(&e, &asset);
+
+        PoolEvents::reclaim_proposal_bond(&e, asset, proposal.proposer, proposal.bond);
+    }
+
+    fn get_config(e: Env) -> PoolConfig {
         storage::get_pool_config(&e)
     }
 
-    fn get_admin(e: Env) -> Address {
-        storage::get_admin(&e)
+    fn get_admin(e: Env) -> Address {
+        storage::get_admin(&e)
+    }
+
+    fn get_reserve_list(e: Env) -> Vec<Address> {
+        storage::get_res_list(&e)
+    }
+
+    fn get_reserve(e: Env, asset: Address) -> Reserve {
+        let pool_config = storage::get_pool_config(&e);
+        Reserve::load(&e, &pool_config, &asset)
+    }
+
+    fn get_positions(e: Env, address: Address) -> Positions {
+        storage::get_user_positions(&e, &address)
+    }
+
+    fn get_users_positions(e: Env, users: Vec<Address>) -> Vec<Positions> {
+        pool::load_users_positions(&e, &users)
+    }
+
+    fn export_positions(e: Env, user: Address) -> Bytes {
+        pool::User::load(&e, &user).export_positions(&e)
+    }
+
+    fn verify_positions(e: Env, user: Address, blob: Bytes) -> bool {
+        pool::User::load(&e, &user).verify_positions(&e, &blob)
+    }
+
+    fn submit(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        spender.require_auth();
+        if from != spender {
+            from.require_auth();
+        }
+
+        pool::execute_submit(&e, &from, &spender, &to, requests, false)
+    }
+
+    fn submit_with_allowance(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        spender.require_auth();
+        if from != spender {
+            from.require_auth();
+        }
+
+        pool::execute_submit(&e, &from, &spender, &to, requests, true)
+    }
+
+    fn flash_loan(
+        e: Env,
+        from: Address,
+        flash_loan: FlashLoan,
+        requests: Vec<Request>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        pool::execute_submit_with_flash_loan(&e, &from, flash_loan, requests)
+    }
+
+    fn update_status(e: Env) -> u32 {
+        storage::extend_instance(&e);
+        let new_status = pool::execute_update_pool_status(&e);
+
+        PoolEvents::set_status(&e, new_status);
+        new_status
+    }
+
+    fn set_status(e: Env, caller: Address, pool_status: u32) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, pool::set_pool_status_role(&e, pool_status), &caller);
+        pool::execute_set_pool_status(&e, pool_status);
+
+        PoolEvents::set_status_admin(&e, caller, pool_status);
+    }
+
+    fn gulp(e: Env, asset: Address) -> i128 {
+        storage::extend_instance(&e);
+        let token_delta = pool::execute_gulp(&e, &asset);
+
+        PoolEvents::gulp(&e, asset, token_delta);
+        token_delta
+    }
+
+    /********* Emission Functions **********/
+
+    fn gulp_emissions(e: Env) -> i128 {
+        storage::extend_instance(&e);
+        let emissions = emissions::gulp_emissions(&e);
+
+        PoolEvents::gulp_emissions(&e, emissions);
+        emissions
+    }
+
+    fn set_emissions_config(
+        e: Env,
+        caller: Address,
+        res_emission_metadata: Vec<ReserveEmissionMetadata>,
+    ) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        emissions::set_pool_emissions(&e, res_emission_metadata);
+    }
+
+    fn set_bootstrap_emissions(e: Env, caller: Address, reserve_indexes: Vec<u32>, days: u32) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        let bootstrap = emissions::set_bootstrap_emissions(&e, reserve_indexes, days);
+
+        PoolEvents::set_bootstrap_emissions(&e, caller, bootstrap.reserves, bootstrap.expiration);
+    }
+
+    fn get_bootstrap_emissions(e: Env) -> Option<BootstrapEmissions> {
+        storage::get_bootstrap_emissions(&e)
+    }
+
+    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> ClaimedEmissions {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let claimed = emissions::execute_claim(&e, &from, &reserve_token_ids, &to);
+        let nonce = pool::increment_nonce(&e, &from);
+
+        PoolEvents::claim(
+            &e,
+            from,
+            reserve_token_ids,
+            claimed.legacy,
+            claimed.migrated,
+            nonce,
+        );
+
+        claimed
+    }
+
+    fn get_reserve_emissions(e: Env, reserve_token_index: u32) -> Option<ReserveEmissionData> {
+        storage::get_res_emis_data(&e, &reserve_token_index)
+    }
+
+    fn get_user_emissions(
+        e: Env,
+        user: Address,
+        reserve_token_index: u32,
+    ) -> Option<UserEmissionData> {
+        storage::get_user_emissions(&e, &user, &reserve_token_index)
+    }
+
+    fn get_emission_config(e: Env, reserve_token_id: u32) -> Option<ReserveEmissionMetadata> {
+        emissions::get_emission_config(&e, reserve_token_id)
+    }
+
+    fn get_emission_apr(e: Env, asset: Address, is_supply: bool) -> i128 {
+        emissions::calc_emission_apr(&e, &asset, is_supply)
+    }
+
+    /***** Auction / Liquidation Functions *****/
+
+    fn new_auction(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        percent: u32,
+    ) -> AuctionData {
+        storage::extend_instance(&e);
+
+        let auction_data = auctions::create_auction(&e, auction_type, &user, &bid, &lot, percent);
+        let (hf_before, hf_after) =
+            auctions::estimate_health(&e, auction_type, &user, &auction_data);
+        if auction_type == auctions::AuctionType::UserLiquidation as u32 {
+            pool::notify_health_alert(&e, &user, "liquidation");
+        }
+
+        PoolEvents::new_auction(
+            &e,
+            auction_type,
+            user,
+            percent,
+            auction_data.clone(),
+            hf_before,
+            hf_after,
+        );
+        auction_data
+    }
+
+    fn set_auction_min_blocks(e: Env, caller: Address, min_blocks: u32) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_auction_min_blocks(&e, min_blocks);
+
+        PoolEvents::set_auction_min_blocks(&e, caller, min_blocks);
+    }
+
+    fn set_liquidator_allowlist(e: Env, caller: Address, enabled: bool) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        storage::set_liquidator_allowlist(&e, &enabled);
+
+        PoolEvents::set_liquidator_allowlist(&e, caller, enabled);
+    }
+
+    fn set_liquidator(e: Env, caller: Address, liquidator: Address, allowed: bool) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        storage::set_liquidator(&e, &liquidator, &allowed);
+
+        PoolEvents::set_liquidator(&e, caller, liquidator, allowed);
+    }
+
+    fn is_liquidator_allowed(e: Env, liquidator: Address) -> bool {
+        auctions::is_liquidator_allowed(&e, &liquidator)
+    }
+
+    fn get_auction_min_blocks(e: Env) -> u32 {
+        storage::get_auction_min_blocks(&e)
+    }
+
+    fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData {
+        storage::get_auction(&e, &auction_type, &user)
+    }
+
+    fn get_auction_underlying(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        percent_filled: u64,
+    ) -> AuctionData {
+        auctions::get_underlying_fill_quote(&e, auction_type, &user, percent_filled)
+    }
+
+    fn del_auction(e: Env, auction_type: u32, user: Address) {
+        storage::extend_instance(&e);
+
+        auctions::delete_stale_auction(&e, auction_type, &user);
+
+        PoolEvents::delete_auction(&e, auction_type, user);
+    }
+
+    fn bad_debt(e: Env, user: Address) {
+        storage::extend_instance(&e);
+
+        pool::bad_debt(&e, &user);
+    }
+
+    fn set_soft_liquidation_config(e: Env, caller: Address, config: SoftLiquidationConfig) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_soft_liquidation_config(&e, &config);
+
+        PoolEvents::set_soft_liquidation_config(&e, caller, config);
+    }
+
+    fn get_soft_liquidation_config(e: Env) -> Option<SoftLiquidationConfig> {
+        storage::get_soft_liquidation_config(&e)
+    }
+
+    fn soft_liquidate(
+        e: Env,
+        keeper: Address,
+        user: Address,
+        collateral_asset: Address,
+        debt_asset: Address,
+        amount: i128,
+        min_debt_out: i128,
+    ) -> i128 {
+        storage::extend_instance(&e);
+        keeper.require_auth();
+
+        let (d_tokens_burnt, fee) = pool::execute_soft_liquidation(
+            &e,
+            &keeper,
+            &user,
+            &collateral_asset,
+            &debt_asset,
+            amount,
+            min_debt_out,
+        );
+        pool::notify_health_alert(&e, &user, "soft_liquidation");
+
+        PoolEvents::soft_liquidation(
+            &e,
+            user,
+            keeper,
+            collateral_asset,
+            debt_asset,
+            amount,
+            d_tokens_burnt,
+            fee,
+        );
+        d_tokens_burnt
+    }
+
+    fn set_dex_adapter(e: Env, caller: Address, dex_adapter: Address) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        storage::set_dex_adapter(&e, &dex_adapter);
+
+        PoolEvents::set_dex_adapter(&e, caller, dex_adapter);
+    }
+
+    fn get_dex_adapter(e: Env) -> Option<Address> {
+        storage::get_dex_adapter(&e)
+    }
+
+    fn get_accrued_interest(e: Env, user: Address, debt_asset: Address) -> i128 {
+        let pool_config = storage::get_pool_config(&e);
+        let reserve = Reserve::load(&e, &pool_config, &debt_asset);
+        let d_tokens = storage::get_user_positions(&e, &user)
+            .liabilities
+            .get(reserve.config.index)
+            .unwrap_or(0);
+        pool::calc_accrued_interest(&e, &user, &reserve, d_tokens)
+    }
+
+    fn get_interest_paid_since(e: Env, user: Address, asset: Address) -> i128 {
+        let pool_config = storage::get_pool_config(&e);
+        let reserve = Reserve::load(&e, &pool_config, &asset);
+        let d_tokens = storage::get_user_positions(&e, &user)
+            .liabilities
+            .get(reserve.config.index)
+            .unwrap_or(0);
+        pool::calc_interest_since_index(&e, &user, &reserve, d_tokens)
+    }
+
+    fn repay_interest(
+        e: Env,
+        from: Address,
+        debt_asset: Address,
+        pay_asset: Address,
+        amount_in: i128,
+        min_debt_out: i128,
+    ) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let (repaid, d_tokens_burnt) = pool::execute_repay_interest(
+            &e,
+            &from,
+            &debt_asset,
+            &pay_asset,
+            amount_in,
+            min_debt_out,
+        );
+
+        PoolEvents::repay_interest(
+            &e,
+            debt_asset,
+            from,
+            pay_asset,
+            amount_in,
+            repaid,
+            d_tokens_burnt,
+        );
+        repaid
+    }
+
+    fn shutdown(e: Env) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let shutdown = pool::execute_shutdown(&e);
+
+        PoolEvents::shutdown(&e, admin, shutdown);
+    }
+
+    fn get_shutdown(e: Env) -> Option<ShutdownData> {
+        storage::get_shutdown(&e)
+    }
+
+    fn redeem(e: Env, from: Address, to: Address) -> Map<Address, i128> {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        pool::execute_redeem(&e, &from, &to)
+    }
+
+    fn get_rate_at(e: Env, asset: Address, timestamp: u64) -> Option<RateCheckpoint> {
+        pool::get_rate_at(&e, &asset, timestamp)
+    }
+
+    fn get_nonce(e: Env, user: Address) -> u64 {
+        storage::get_user_nonce(&e, &user)
+    }
+
+    fn get_max_borrow(e: Env, user: Address, asset: Address) -> i128 {
+        pool::calc_max_borrow(&e, &user, &asset)
+    }
+
+    fn get_max_withdraw(e: Env, user: Address, asset: Address) -> i128 {
+        pool::calc_max_withdraw(&e, &user, &asset)
+    }
+
+    fn get_min_collateral_in(e: Env, asset: Address) -> i128 {
+        pool::calc_min_collateral_in(&e, &asset)
+    }
+
+    fn required_auth_for(e: Env, spender: Address, requests: Vec<Request>) -> Vec<SubInvocation> {
+        pool::build_required_auth(&e, &spender, &requests)
+    }
+
+    fn set_reserve_strategy(
+        e: Env,
+        caller: Address,
+        asset: Address,
+        adapter: Address,
+        max_deploy: u32,
+    ) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_reserve_strategy(&e, &asset, &adapter, max_deploy);
+
+        PoolEvents::set_reserve_strategy(&e, caller, asset, adapter, max_deploy);
+    }
+
+    fn deploy_to_strategy(e: Env, asset: Address, amount: i128) {
+        storage::extend_instance(&e);
+        storage::get_admin(&e).require_auth();
+
+        pool::execute_deploy_to_strategy(&e, &asset, amount);
+    }
+
+    fn recall_from_strategy(e: Env, asset: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        storage::get_admin(&e).require_auth();
+
+        pool::execute_recall_from_strategy(&e, &asset, amount)
+    }
+
+    fn get_reserve_strategy(e: Env, asset: Address) -> Option<ReserveStrategy> {
+        storage::get_reserve_strategy(&e, &asset)
+    }
+
+    fn set_insurance_premium(e: Env, caller: Address, premium: u32) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Treasurer, &caller);
+
+        pool::execute_set_insurance_premium(&e, premium);
+
+        PoolEvents::set_insurance_premium(&e, caller, premium);
+    }
+
+    fn set_coverage(e: Env, from: Address, asset: Address, covered: bool) {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        pool::execute_set_coverage(&e, &from, &asset, covered);
+
+        PoolEvents::set_coverage(&e, asset, from, covered);
+    }
+
+    fn claim_coverage(e: Env, from: Address, asset: Address, to: Address) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let amount = pool::execute_claim_coverage(&e, &from, &asset, &to);
+
+        PoolEvents::claim_coverage(&e, asset, from, to, amount);
+        amount
+    }
+
+    fn get_coverage(e: Env, user: Address, asset: Address) -> Option<Coverage> {
+        let reserve_config = storage::get_res_config(&e, &asset);
+        storage::get_coverage(&e, &user, &reserve_config.index)
+    }
+
+    fn get_insurance_fund(e: Env, asset: Address) -> i128 {
+        storage::get_insurance_fund(&e, &asset)
+    }
+
+    fn set_request_hooks(e: Env, hooks: Vec<RequestHook>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_request_hooks(&e, &hooks);
+
+        PoolEvents::set_request_hooks(&e, admin, hooks);
+    }
+
+    fn get_request_hooks(e: Env) -> Vec<RequestHook> {
+        pool::load_request_hooks(&e)
+    }
+
+    fn export_risk_params(e: Env) -> RiskParams {
+        pool::export_risk_params(&e)
+    }
+
+    fn set_balance_delta(e: Env, caller: Address, asset: Address, enabled: bool) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_balance_delta(&e, &asset, enabled);
+
+        PoolEvents::set_balance_delta(&e, caller, asset, enabled);
+    }
+
+    fn get_balance_delta(e: Env, asset: Address) -> bool {
+        storage::get_balance_delta(&e, &asset)
+    }
+
+    fn donate_to_reserve(e: Env, from: Address, asset: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let (donated, b_rate) = pool::execute_donate_to_reserve(&e, &from, &asset, amount);
+
+        PoolEvents::donate_to_reserve(&e, asset, from, donated, b_rate);
+        b_rate
+    }
+
+    fn new_liquidation_auction(e: Env, user: Address, percent: u32) -> AuctionData {
+        storage::extend_instance(&e);
+
+        let auction_type = auctions::AuctionType::UserLiquidation as u32;
+        let auction_data = auctions::create_user_liquidation(&e, &user, percent);
+        let (hf_before, hf_after) =
+            auctions::estimate_health(&e, auction_type, &user, &auction_data);
+        pool::notify_health_alert(&e, &user, "liquidation");
+
+        PoolEvents::new_auction(
+            &e,
+            auction_type,
+            user,
+            percent,
+            auction_data.clone(),
+            hf_before,
+            hf_after,
+        );
+        auction_data
+    }
+
+    fn forgive_interest(e: Env, user: Address, asset: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let d_tokens_burnt = pool::execute_forgive_interest(&e, &user, &asset, amount);
+
+        PoolEvents::forgive_interest(&e, asset, user, admin, amount, d_tokens_burnt);
+        d_tokens_burnt
+    }
+
+    fn set_notification_key(e: Env, user: Address, notification_key: Option<BytesN<32>>) {
+        storage::extend_instance(&e);
+        user.require_auth();
+
+        storage::set_notification_key(&e, &user, &notification_key);
+
+        PoolEvents::set_notification_key(&e, user, notification_key);
+    }
+
+    fn get_notification_key(e: Env, user: Address) -> Option<BytesN<32>> {
+        storage::get_notification_key(&e, &user)
+    }
+
+    fn set_confidence_threshold(e: Env, caller: Address, threshold: u32) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_confidence_threshold(&e, threshold);
+
+        PoolEvents::set_confidence_threshold(&e, caller, threshold);
+    }
+
+    fn get_confidence_threshold(e: Env) -> u32 {
+        storage::get_confidence_threshold(&e)
+    }
+
+    fn set_twap_pricing(e: Env, caller: Address, asset: Address, enabled: bool) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_twap_pricing(&e, &asset, enabled);
+
+        PoolEvents::set_twap_pricing(&e, caller, asset, enabled);
+    }
+
+    fn get_twap_pricing(e: Env, asset: Address) -> bool {
+        storage::get_twap_pricing(&e, &asset)
+    }
+
+    fn check_reserve_price(e: Env, asset: Address) -> bool {
+        storage::extend_instance(&e);
+        pool::execute_check_reserve_price(&e, &asset)
+    }
+
+    fn queue_set_interest_split(e: Env, caller: Address, split: InterestSplit) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Treasurer, &caller);
+
+        let queued = pool::execute_queue_set_interest_split(&e, &split);
+
+        PoolEvents::queue_set_interest_split(&e, caller, queued);
+    }
+
+    fn cancel_set_interest_split(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Treasurer, &caller);
+
+        let split = pool::execute_cancel_set_interest_split(&e);
+
+        PoolEvents::cancel_set_interest_split(&e, caller, split);
+    }
+
+    fn set_interest_split(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Treasurer, &caller);
+
+        let split = pool::execute_set_interest_split(&e);
+
+        PoolEvents::set_interest_split(&e, caller, split);
+    }
+
+    fn disable_interest_split(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Treasurer, &caller);
+
+        pool::execute_disable_interest_split(&e);
+
+        PoolEvents::disable_interest_split(&e, caller);
+    }
+
+    fn get_interest_split(e: Env) -> Option<InterestSplit> {
+        storage::get_interest_split(&e)
+    }
+
+    fn get_queued_interest_split(e: Env) -> Option<QueuedInterestSplit> {
+        storage::get_queued_interest_split(&e)
+    }
+
+    fn set_borrow_freeze(e: Env, user: Address, frozen: bool) -> u64 {
+        storage::extend_instance(&e);
+        user.require_auth();
+
+        let unfreeze_time = pool::execute_set_borrow_freeze(&e, &user, frozen);
+
+        PoolEvents::set_borrow_freeze(&e, user, frozen, unfreeze_time);
+        unfreeze_time
     }
 
-    fn get_reserve_list(e: Env) -> Vec<Address> {
-        storage::get_res_list(&e)
+    fn get_borrow_freeze(e: Env, user: Address) -> Option<u64> {
+        storage::get_borrow_freeze(&e, &user)
     }
 
-    fn get_reserve(e: Env, asset: Address) -> Reserve {
-        let pool_config = storage::get_pool_config(&e);
-        Reserve::load(&e, &pool_config, &asset)
+    fn set_supply_only(e: Env, user: Address, enabled: bool) -> u64 {
+        storage::extend_instance(&e);
+        user.require_auth();
+
+        let exit_time = pool::execute_set_supply_only(&e, &user, enabled);
+
+        PoolEvents::set_supply_only(&e, user, enabled, exit_time);
+        exit_time
     }
 
-    fn get_positions(e: Env, address: Address) -> Positions {
-        storage::get_user_positions(&e, &address)
+    fn get_supply_only(e: Env, user: Address) -> Option<u64> {
+        storage::get_supply_only(&e, &user)
     }
 
-    fn submit(
+    fn set_signing_key(e: Env, user: Address, signing_key: Option<BytesN<32>>) {
+        storage::extend_instance(&e);
+        user.require_auth();
+
+        storage::set_signing_key(&e, &user, &signing_key);
+
+        PoolEvents::set_signing_key(&e, user, signing_key);
+    }
+
+    fn get_signing_key(e: Env, user: Address) -> Option<BytesN<32>> {
+        storage::get_signing_key(&e, &user)
+    }
+
+    fn hash_requests(e: Env, requests: Vec<Request>) -> BytesN<32> {
+        pool::hash_requests(&e, &requests)
+    }
+
+    fn submit_signed(
         e: Env,
         from: Address,
-        spender: Address,
-        to: Address,
         requests: Vec<Request>,
+        signature: BytesN<64>,
+        nonce: u64,
+        expiry: u64,
     ) -> Positions {
         storage::extend_instance(&e);
-        spender.require_auth();
-        if from != spender {
-            from.require_auth();
-        }
 
-        pool::execute_submit(&e, &from, &spender, &to, requests, false)
+        pool::execute_submit_signed(&e, &from, requests, &signature, nonce, expiry)
     }
 
-    fn submit_with_allowance(
+    fn set_emission_migration(e: Env, token: Address, ledger: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        emissions::set_emission_migration(&e, &token, ledger);
+
+        PoolEvents::set_emission_migration(&e, admin, token, ledger);
+    }
+
+    fn get_emission_migration(e: Env) -> Option<EmissionMigration> {
+        storage::get_emission_migration(&e)
+    }
+
+    fn get_legacy_reserve_emissions(e: Env, reserve_token_id: u32) -> Option<ReserveEmissionData> {
+        storage::get_legacy_res_emis_data(&e, &reserve_token_id)
+    }
+
+    fn get_legacy_user_emissions(
         e: Env,
-        from: Address,
-        spender: Address,
-        to: Address,
-        requests: Vec<Request>,
-    ) -> Positions {
+        user: Address,
+        reserve_token_id: u32,
+    ) -> Option<UserEmissionData> {
+        storage::get_legacy_user_emissions(&e, &user, &reserve_token_id)
+    }
+
+    fn state_root(e: Env) -> BytesN<32> {
+        pool::calc_state_root(&e, &pool::load_state_snapshot(&e))
+    }
+
+    fn state_snapshot(e: Env) -> StateSnapshot {
+        pool::load_state_snapshot(&e)
+    }
+
+    fn state_preimage(e: Env) -> Bytes {
+        pool::state_preimage(&e, &pool::load_state_snapshot(&e))
+    }
+
+    fn user_state_root(e: Env, user: Address) -> BytesN<32> {
+        pool::calc_user_state_root(&e, &pool::load_user_state_snapshot(&e, &user))
+    }
+
+    fn user_state_snapshot(e: Env, user: Address) -> UserStateSnapshot {
+        pool::load_user_state_snapshot(&e, &user)
+    }
+
+    fn user_state_preimage(e: Env, user: Address) -> Bytes {
+        pool::user_state_preimage(&e, &pool::load_user_state_snapshot(&e, &user))
+    }
+
+    fn queue_set_protocol_fee(e: Env, caller: Address, fee: ProtocolFee) {
         storage::extend_instance(&e);
-        spender.require_auth();
-        if from != spender {
-            from.require_auth();
-        }
+        pool::require_role(&e, Role::Treasurer, &caller);
 
-        pool::execute_submit(&e, &from, &spender, &to, requests, true)
+        let queued = pool::execute_queue_set_protocol_fee(&e, &fee);
+
+        PoolEvents::queue_set_protocol_fee(&e, caller, queued);
     }
 
-    fn flash_loan(
+    fn cancel_set_protocol_fee(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Treasurer, &caller);
+
+        let fee = pool::execute_cancel_set_protocol_fee(&e);
+
+        PoolEvents::cancel_set_protocol_fee(&e, caller, fee);
+    }
+
+    fn set_protocol_fee(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Treasurer, &caller);
+
+        let fee = pool::execute_set_protocol_fee(&e);
+
+        PoolEvents::set_protocol_fee(&e, caller, fee);
+    }
+
+    fn disable_protocol_fee(e: Env, caller: Address) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Treasurer, &caller);
+
+        pool::execute_disable_protocol_fee(&e);
+
+        PoolEvents::disable_protocol_fee(&e, caller);
+    }
+
+    fn get_protocol_fee(e: Env) -> Option<ProtocolFee> {
+        storage::get_protocol_fee(&e)
+    }
+
+    fn get_queued_protocol_fee(e: Env) -> Option<QueuedProtocolFee> {
+        storage::get_queued_protocol_fee(&e)
+    }
+
+    fn get_protocol_fee_credit(e: Env, asset: Address) -> i128 {
+        storage::get_protocol_fee_credit(&e, &asset)
+    }
+
+    fn claim_protocol_fees(e: Env, asset: Address) -> i128 {
+        storage::extend_instance(&e);
+
+        let (treasury, amount) = pool::execute_claim_protocol_fees(&e, &asset);
+
+        PoolEvents::claim_protocol_fees(&e, asset, treasury, amount);
+        amount
+    }
+
+    fn set_action_limits(e: Env, caller: Address, limits: ActionLimits) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_action_limits(&e, &limits);
+
+        PoolEvents::set_action_limits(&e, caller, limits);
+    }
+
+    fn get_action_limits(e: Env) -> ActionLimits {
+        storage::get_action_limits(&e)
+    }
+
+    fn preview_interest_auction(e: Env, assets: Vec<Address>) -> InterestAuctionPreview {
+        auctions::preview_interest_auction(&e, &assets)
+    }
+
+    fn status(e: Env) -> ContractStatus {
+        ContractStatus::from_pool_status(storage::get_pool_config(&e).status)
+    }
+
+    fn set_util_smoothing(e: Env, caller: Address, asset: Address, window: u64) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_util_smoothing(&e, &asset, window);
+
+        PoolEvents::set_util_smoothing(&e, caller, asset, window);
+    }
+
+    fn get_util_smoothing(e: Env, asset: Address) -> Option<UtilSmoothing> {
+        storage::get_util_smoothing(&e, &asset)
+    }
+
+    fn get_bad_debt(e: Env, asset: Address) -> BadDebt {
+        storage::get_bad_debt(&e, &asset)
+    }
+
+    fn get_total_bad_debt(e: Env) -> i128 {
+        pool::load_total_bad_debt(&e)
+    }
+
+    fn flash_fill_auction(
         e: Env,
-        from: Address,
-        flash_loan: FlashLoan,
-        requests: Vec<Request>,
-    ) -> Positions {
+        filler: Address,
+        user: Address,
+        percent_filled: u64,
+        min_profit: i128,
+    ) -> i128 {
         storage::extend_instance(&e);
-        from.require_auth();
+        filler.require_auth();
 
-        pool::execute_submit_with_flash_loan(&e, &from, flash_loan, requests)
+        pool::execute_flash_fill_auction(&e, &filler, &user, percent_filled, min_profit)
     }
 
-    fn update_status(e: Env) -> u32 {
+    fn set_status_guardian(e: Env, guardian: Option<Address>) {
         storage::extend_instance(&e);
-        let new_status = pool::execute_update_pool_status(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
 
-        PoolEvents::set_status(&e, new_status);
-        new_status
+        pool::execute_set_status_guardian(&e, &guardian);
+
+        PoolEvents::set_status_guardian(&e, admin, guardian);
+    }
+
+    fn get_status_guardian(e: Env) -> Option<Address> {
+        storage::get_status_guardian(&e)
+    }
+
+    fn set_status_restricted(e: Env, caller: Address) -> u32 {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Pauser, &caller);
+
+        let pool_status = pool::execute_set_status_restricted(&e);
+
+        PoolEvents::set_status_restricted(&e, caller, pool_status);
+        pool_status
     }
 
-    fn set_status(e: Env, pool_status: u32) {
+    fn grant_role(e: Env, role: Role, member: Address) {
         storage::extend_instance(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
-        pool::execute_set_pool_status(&e, pool_status);
 
-        PoolEvents::set_status_admin(&e, admin, pool_status);
+        pool::execute_grant_role(&e, role, &member);
+
+        PoolEvents::grant_role(&e, admin, role, member);
     }
 
-    fn gulp(e: Env, asset: Address) -> i128 {
+    fn revoke_role(e: Env, role: Role, member: Address) {
         storage::extend_instance(&e);
-        let token_delta = pool::execute_gulp(&e, &asset);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
 
-        PoolEvents::gulp(&e, asset, token_delta);
-        token_delta
+        pool::execute_revoke_role(&e, role, &member);
+
+        PoolEvents::revoke_role(&e, admin, role, member);
     }
 
-    /********* Emission Functions **********/
+    fn has_role(e: Env, role: Role, member: Address) -> bool {
+        pool::has_role(&e, role, &member)
+    }
 
-    fn gulp_emissions(e: Env) -> i128 {
+    fn last_action_timestamp(e: Env, user: Address) -> u64 {
+        storage::get_last_action(&e, &user)
+    }
+
+    fn set_metadata(e: Env, name: String, icon_uri: String, description: String) {
         storage::extend_instance(&e);
-        let emissions = emissions::gulp_emissions(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
 
-        PoolEvents::gulp_emissions(&e, emissions);
-        emissions
+        let metadata = PoolMetadata {
+            name,
+            icon_uri,
+            description,
+        };
+        pool::execute_set_metadata(&e, &metadata);
+
+        PoolEvents::set_metadata(&e, admin, metadata);
+    }
+
+    fn get_metadata(e: Env) -> PoolMetadata {
+        storage::get_pool_metadata(&e)
     }
 
-    fn set_emissions_config(e: Env, res_emission_metadata: Vec<ReserveEmissionMetadata>) {
+    fn set_base_asset(e: Env, base: Asset) {
         storage::extend_instance(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
 
-        emissions::set_pool_emissions(&e, res_emission_metadata);
+        pool::execute_set_base_asset(&e, &base);
+
+        PoolEvents::set_base_asset(&e, admin, base);
+    }
+
+    fn get_base_asset(e: Env) -> Option<Asset> {
+        storage::get_base_asset(&e)
     }
 
-    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128 {
+    fn queue_reserve_migration(e: Env, migration: ReserveMigration) {
         storage::extend_instance(&e);
-        from.require_auth();
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let queued = pool::execute_queue_reserve_migration(&e, &migration);
 
-        let amount_claimed = emissions::execute_claim(&e, &from, &reserve_token_ids, &to);
+        PoolEvents::queue_reserve_migration(&e, admin, queued);
+    }
+
+    fn cancel_reserve_migration(e: Env) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
 
-        PoolEvents::claim(&e, from, reserve_token_ids, amount_claimed);
+        let migration = pool::execute_cancel_reserve_migration(&e);
 
-        amount_claimed
+        PoolEvents::cancel_reserve_migration(&e, admin, migration);
     }
 
-    fn get_reserve_emissions(e: Env, reserve_token_index: u32) -> Option<ReserveEmissionData> {
-        storage::get_res_emis_data(&e, &reserve_token_index)
+    fn migrate_reserve(e: Env) -> i128 {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let (migration, old_amount, new_amount) = pool::execute_migrate_reserve(&e, &admin);
+
+        PoolEvents::migrate_reserve(&e, admin, migration, old_amount, new_amount);
+        new_amount
     }
 
-    fn get_user_emissions(
-        e: Env,
-        user: Address,
-        reserve_token_index: u32,
-    ) -> Option<UserEmissionData> {
-        storage::get_user_emissions(&e, &user, &reserve_token_index)
+    fn get_reserve_migration(e: Env) -> Option<QueuedReserveMigration> {
+        storage::get_reserve_migration(&e)
     }
 
-    /***** Auction / Liquidation Functions *****/
+    fn get_util_history(e: Env, asset: Address) -> Vec<UtilDay> {
+        pool::load_util_history(&e, &asset)
+    }
 
-    fn new_auction(
-        e: Env,
-        auction_type: u32,
-        user: Address,
-        bid: Vec<Address>,
-        lot: Vec<Address>,
-        percent: u32,
-    ) -> AuctionData {
+    fn set_auction_start_offset(e: Env, max_offset: u32) {
         storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
 
-        let auction_data = auctions::create_auction(&e, auction_type, &user, &bid, &lot, percent);
+        pool::execute_set_auction_start_offset(&e, max_offset);
 
-        PoolEvents::new_auction(&e, auction_type, user, percent, auction_data.clone());
-        auction_data
+        PoolEvents::set_auction_start_offset(&e, admin, max_offset);
     }
 
-    fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData {
-        storage::get_auction(&e, &auction_type, &user)
+    fn get_auction_start_offset(e: Env) -> u32 {
+        storage::get_auction_start_offset(&e)
     }
 
-    fn del_auction(e: Env, auction_type: u32, user: Address) {
+    fn set_withdraw_cooldown(e: Env, caller: Address, ledgers: u32) {
         storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
 
-        auctions::delete_stale_auction(&e, auction_type, &user);
+        pool::execute_set_withdraw_cooldown(&e, ledgers);
 
-        PoolEvents::delete_auction(&e, auction_type, user);
+        PoolEvents::set_withdraw_cooldown(&e, caller, ledgers);
     }
 
-    fn bad_debt(e: Env, user: Address) {
+    fn get_withdraw_cooldown(e: Env) -> u32 {
+        storage::get_withdraw_cooldown(&e)
+    }
+
+    fn verify_invariants(e: Env, asset: Address) -> InvariantCheck {
         storage::extend_instance(&e);
+        let check = pool::execute_verify_invariants(&e, &asset);
 
-        pool::bad_debt(&e, &user);
+        PoolEvents::verify_invariants(&e, asset, check.clone());
+        check
+    }
+
+    fn set_emission_split(e: Env, caller: Address, res_index: u32, supply_percent: u32) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        emissions::set_emission_split(&e, res_index, supply_percent);
+
+        PoolEvents::set_emission_split(&e, caller, res_index, supply_percent);
+    }
+
+    fn get_emission_split(e: Env, res_index: u32) -> Option<u32> {
+        emissions::get_emission_split(&e, res_index)
+    }
+
+    fn set_retail_liquidation(e: Env, caller: Address, config: RetailLiquidation) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_retail_liquidation(&e, &config);
+
+        PoolEvents::set_retail_liquidation(&e, caller, config);
+    }
+
+    fn get_retail_liquidation(e: Env) -> Option<RetailLiquidation> {
+        storage::get_retail_liquidation(&e)
+    }
+
+    fn bump_critical(e: Env, caller: Address) -> Map<Address, i128> {
+        caller.require_auth();
+
+        let bounty = pool::execute_bump_critical(&e, &caller);
+
+        PoolEvents::bump_critical(&e, caller, bounty.clone());
+        bounty
+    }
+
+    fn domain_separator(e: Env) -> BytesN<32> {
+        pool::domain_separator(&e)
+    }
+
+    fn signed_submit_payload(
+        e: Env,
+        from: Address,
+        requests: Vec<Request>,
+        nonce: u64,
+        expiry: u64,
+    ) -> Bytes {
+        pool::signed_submit_payload(&e, &from, &requests, nonce, expiry)
+    }
+
+    fn set_risk_tiers(e: Env, caller: Address, config: RiskTierConfig) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        pool::execute_set_risk_tiers(&e, &config);
+
+        PoolEvents::set_risk_tiers(&e, caller, config);
+    }
+
+    fn get_risk_tiers(e: Env) -> RiskTierConfig {
+        storage::get_risk_tiers(&e).unwrap_or_else(|| pool::default_risk_tiers(&e))
+    }
+
+    fn get_risk_tier(e: Env, user: Address) -> u32 {
+        pool::load_risk_tier(&e, &user)
+    }
+
+    fn set_emission_boost(
+        e: Env,
+        caller: Address,
+        asset: Address,
+        target_util: u32,
+        ledgers: u32,
+        max_boost: u32,
+    ) {
+        storage::extend_instance(&e);
+        pool::require_role(&e, Role::Risk, &caller);
+
+        emissions::set_emission_boost(&e, &asset, target_util, ledgers, max_boost);
+
+        PoolEvents::set_emission_boost(&e, caller, asset, target_util, ledgers, max_boost);
+    }
+
+    fn get_emission_boost(e: Env, asset: Address) -> Option<EmissionBoost> {
+        storage::get_emission_boost(&e, &asset)
     }
 }
@@ -0,0 +1,21 @@
+/**
+ * Partial client for a DEX adapter contract
+ */
+use soroban_sdk::{contractclient, Address, Env};
+
+#[allow(dead_code)]
+#[contractclient(name = "DexAdapterClient")]
+pub trait DexAdapter {
+    /// Swap an exact amount of `token_in` held by `from` for `token_out`, sent to `to`
+    ///
+    /// Returns the amount of `token_out` received
+    fn swap_exact_in(
+        e: Env,
+        from: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+        to: Address,
+    ) -> i128;
+}
@@ -1,2 +1,20 @@
 mod backstop;
 pub use backstop::{Client as BackstopClient, PoolBackstopData};
+
+mod dex_adapter;
+pub use dex_adapter::DexAdapterClient;
+
+mod pool_factory;
+pub use pool_factory::PoolFactoryClient;
+
+mod strategy;
+pub use strategy::StrategyClient;
+
+mod share_token;
+pub use share_token::ShareTokenClient;
+
+mod request_validator;
+pub use request_validator::RequestValidatorClient;
+
+mod oracle;
+pub use oracle::{PriceDataV2, PriceFeedV2Client};
@@ -0,0 +1,13 @@
+/**
+ * Partial client for an oracle that reports the confidence of its prices
+ */
+use soroban_sdk::{contractclient, Env};
+use trustbridge_common::Asset;
+pub use trustbridge_common::PriceDataV2;
+
+#[allow(dead_code)]
+#[contractclient(name = "PriceFeedV2Client")]
+pub trait PriceFeedV2 {
+    /// Fetch the last price for an asset with the oracle's confidence in it
+    fn lastprice_v2(e: Env, asset: Asset) -> Option<PriceDataV2>;
+}
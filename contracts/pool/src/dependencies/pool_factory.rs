@@ -0,0 +1,14 @@
+/**
+ * Partial client for the pool factory contract
+ */
+use soroban_sdk::{contractclient, Address, Env};
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolFactoryClient")]
+pub trait PoolFactory {
+    /// Checks if an asset is on the factory's reserve allowlist
+    ///
+    /// ### Arguments
+    /// * `asset` - The asset contract address
+    fn is_asset_allowed(e: Env, asset: Address) -> bool;
+}
@@ -0,0 +1,26 @@
+/**
+ * Partial client for an external request validator contract
+ */
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::pool::{Positions, Request};
+
+#[allow(dead_code)]
+#[contractclient(name = "RequestValidatorClient")]
+pub trait RequestValidator {
+    /// Validate a request `user` submitted to `pool` before it is applied. The validator panics
+    /// to reject the request.
+    ///
+    /// ### Arguments
+    /// * `pool` - The pool the request was submitted to
+    /// * `user` - The user whose positions the request modifies
+    /// * `positions` - The user's positions before the request is applied
+    /// * `request` - The request
+    fn validate_request(
+        e: Env,
+        pool: Address,
+        user: Address,
+        positions: Positions,
+        request: Request,
+    );
+}
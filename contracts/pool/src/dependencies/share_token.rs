@@ -0,0 +1,14 @@
+/**
+ * Partial client for the share token of a pool's backstop
+ */
+use soroban_sdk::{contractclient, Address, Env};
+
+#[allow(dead_code)]
+#[contractclient(name = "ShareTokenClient")]
+pub trait ShareToken {
+    /// Fetch the backstop the share token tracks shares of
+    fn backstop(e: Env) -> Address;
+
+    /// Fetch the pool whose backstop shares the token represents
+    fn pool(e: Env) -> Address;
+}
@@ -0,0 +1,19 @@
+/**
+ * Partial client for a strategy adapter contract
+ */
+use soroban_sdk::{contractclient, Address, Env};
+
+#[allow(dead_code)]
+#[contractclient(name = "StrategyClient")]
+pub trait Strategy {
+    /// Deposit `amount` of the strategy's asset from `from` into the external yield source
+    fn deposit(e: Env, from: Address, amount: i128);
+
+    /// Withdraw `amount` of the strategy's asset deposited by `owner`, sent to `owner`
+    ///
+    /// Returns the amount of the asset sent
+    fn withdraw(e: Env, owner: Address, amount: i128) -> i128;
+
+    /// Fetch the amount of the strategy's asset `owner` can withdraw, including any yield earned
+    fn balance(e: Env, owner: Address) -> i128;
+}
@@ -0,0 +1,296 @@
+use cast::{i128, u32};
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, unwrap::UnwrapOptimized, Address, Env};
+
+use crate::{
+    constants::{MAX_EMISSION_BOOST, SCALAR_7},
+    errors::PoolError,
+    pool::Reserve,
+    storage::{self, EmissionBoost},
+};
+
+/// The multiplier applied to the supplier share while a reserve is not boosted (7 decimals)
+const NO_BOOST: u32 = 1_0000000;
+
+/// The step the boost is rounded down to, so small changes in utilization do not update it
+/// (7 decimals)
+const BOOST_STEP: u32 = 0_2500000;
+
+/// Set the boost to the emissions paid to a reserve's suppliers while it is under-supplied.
+/// Changing the boost keeps how long the utilization has been above the target, and the new
+/// boost is applied the next time the reserve is updated.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `target_util` - The utilization the boost starts above, with 7 decimals
+/// * `ledgers` - The ledgers the utilization must stay above the target before it is boosted
+/// * `max_boost` - The multiplier applied to the supplier share at 100% utilization, with 7
+///                 decimals, or 0 to disable the boost
+///
+/// ### Panics
+/// If the reserve does not exist, the target utilization is not below 100%, or the max boost is
+/// not between 1 and `MAX_EMISSION_BOOST`
+pub fn set_emission_boost(
+    e: &Env,
+    asset: &Address,
+    target_util: u32,
+    ledgers: u32,
+    max_boost: u32,
+) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    if max_boost == 0 {
+        storage::set_emission_boost(e, asset, &None);
+        return;
+    }
+    if i128(target_util) >= SCALAR_7 || i128(max_boost) < SCALAR_7 || max_boost > MAX_EMISSION_BOOST
+    {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let (above_since, cur_boost) = match storage::get_emission_boost(e, asset) {
+        Some(boost) => (boost.above_since, boost.boost.min(max_boost)),
+        None => (0, NO_BOOST),
+    };
+    storage::set_emission_boost(
+        e,
+        asset,
+        &Some(EmissionBoost {
+            target_util,
+            ledgers,
+            max_boost,
+            above_since,
+            boost: cur_boost,
+        }),
+    );
+}
+
+/// Update the boost to the emissions paid to a reserve's suppliers. The reserve is boosted once
+/// its utilization has stayed above the target for long enough, and the boost is removed as soon
+/// as the utilization drops back to the target. The boost is only written when it changes.
+///
+/// ### Arguments
+/// * `reserve` - The reserve, updated to the current ledger
+pub fn update_emission_boost(e: &Env, reserve: &Reserve) {
+    if !storage::get_boosted_reserves(e).contains(&reserve.asset) {
+        return;
+    }
+    let mut boost = match storage::get_emission_boost(e, &reserve.asset) {
+        Some(boost) => boost,
+        None => return,
+    };
+    let cur_boost = boost.boost;
+    let cur_above_since = boost.above_since;
+
+    let util = reserve.utilization(e);
+    let target_util = i128(boost.target_util);
+    boost.boost = if util > target_util {
+        if boost.above_since == 0 {
+            boost.above_since = e.ledger().sequence();
+        }
+        if e.ledger().sequence() - boost.above_since >= boost.ledgers {
+            let boost_range = i128(boost.max_boost) - SCALAR_7;
+            let excess =
+                (util - target_util).fixed_div_floor(e, &(SCALAR_7 - target_util), &SCALAR_7);
+            let steps = boost_range.fixed_mul_floor(e, &excess, &SCALAR_7) / i128(BOOST_STEP);
+            NO_BOOST + u32(steps).unwrap_optimized() * BOOST_STEP
+        } else {
+            NO_BOOST
+        }
+    } else {
+        boost.above_since = 0;
+        NO_BOOST
+    };
+
+    if boost.boost != cur_boost || boost.above_since != cur_above_since {
+        storage::set_emission_boost(e, &reserve.asset, &Some(boost));
+    }
+}
+
+/// Fetch the multiplier applied to a reserve's supplier share of the pool emissions
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+pub(super) fn load_supplier_boost(e: &Env, asset: &Address) -> i128 {
+    if !storage::get_boosted_reserves(e).contains(asset) {
+        return i128(NO_BOOST);
+    }
+    match storage::get_emission_boost(e, asset) {
+        Some(boost) => i128(boost.boost),
+        None => i128(NO_BOOST),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils;
+    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+
+    fn setup_ledger(e: &Env) {
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500000000,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+    }
+
+    fn default_boost() -> EmissionBoost {
+        EmissionBoost {
+            target_util: 0_5000000,
+            ledgers: 10,
+            max_boost: 2_0000000,
+            above_since: 0,
+            boost: 1_0000000,
+        }
+    }
+
+    #[test]
+    fn test_update_emission_boost() {
+        let e = Env::default();
+        setup_ledger(&e);
+        let pool = testutils::create_pool(&e);
+
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.data.last_time = 1500000000;
+        e.as_contract(&pool, || {
+            storage::set_emission_boost(&e, &reserve.asset, &Some(default_boost()));
+
+            // the utilization of 75% must stay above the target for 10 ledgers
+            update_emission_boost(&e, &reserve);
+            let boost = storage::get_emission_boost(&e, &reserve.asset).unwrap();
+            assert_eq!(boost.above_since, 100);
+            assert_eq!(boost.boost, 1_0000000);
+        });
+
+        e.ledger().set_sequence_number(110);
+        e.as_contract(&pool, || {
+            // halfway from the target to 100% utilization, so half of the max boost applies
+            update_emission_boost(&e, &reserve);
+            let boost = storage::get_emission_boost(&e, &reserve.asset).unwrap();
+            assert_eq!(boost.above_since, 100);
+            assert_eq!(boost.boost, 1_5000000);
+            assert_eq!(load_supplier_boost(&e, &reserve.asset), 1_5000000);
+
+            // the boost is rounded down to the last step
+            reserve.data.d_supply = 87_0000000;
+            update_emission_boost(&e, &reserve);
+            let boost = storage::get_emission_boost(&e, &reserve.asset).unwrap();
+            assert_eq!(boost.boost, 1_5000000);
+
+            reserve.data.d_supply = 88_0000000;
+            update_emission_boost(&e, &reserve);
+            let boost = storage::get_emission_boost(&e, &reserve.asset).unwrap();
+            assert_eq!(boost.boost, 1_7500000);
+
+            // the boost is removed once the utilization drops to the target
+            reserve.data.d_supply = 50_0000000;
+            update_emission_boost(&e, &reserve);
+            let boost = storage::get_emission_boost(&e, &reserve.asset).unwrap();
+            assert_eq!(boost.above_since, 0);
+            assert_eq!(boost.boost, 1_0000000);
+            assert_eq!(load_supplier_boost(&e, &reserve.asset), 1_0000000);
+        });
+    }
+
+    #[test]
+    fn test_update_emission_boost_capped() {
+        let e = Env::default();
+        setup_ledger(&e);
+        let pool = testutils::create_pool(&e);
+
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.data.d_supply = 100_0000000;
+        e.as_contract(&pool, || {
+            let mut boost = default_boost();
+            boost.above_since = 90;
+            storage::set_emission_boost(&e, &reserve.asset, &Some(boost));
+
+            // the boost is capped at 100% utilization
+            update_emission_boost(&e, &reserve);
+            let boost = storage::get_emission_boost(&e, &reserve.asset).unwrap();
+            assert_eq!(boost.boost, 2_0000000);
+        });
+    }
+
+    #[test]
+    fn test_update_emission_boost_not_boosted() {
+        let e = Env::default();
+        setup_ledger(&e);
+        let pool = testutils::create_pool(&e);
+
+        let reserve = testutils::default_reserve(&e);
+        e.as_contract(&pool, || {
+            update_emission_boost(&e, &reserve);
+            assert_eq!(storage::get_emission_boost(&e, &reserve.asset), None);
+            assert_eq!(load_supplier_boost(&e, &reserve.asset), 1_0000000);
+        });
+    }
+
+    #[test]
+    fn test_set_emission_boost() {
+        let e = Env::default();
+        e.mock_all_auths();
+        setup_ledger(&e);
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 1500000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            set_emission_boost(&e, &underlying, 0_5000000, 10, 2_0000000);
+            assert_eq!(
+                storage::get_emission_boost(&e, &underlying),
+                Some(default_boost())
+            );
+            assert!(storage::get_boosted_reserves(&e).contains(&underlying));
+
+            // changing the boost keeps the current boost, up to the new max boost
+            let mut boost = default_boost();
+            boost.above_since = 50;
+            boost.boost = 1_7500000;
+            storage::set_emission_boost(&e, &underlying, &Some(boost));
+            set_emission_boost(&e, &underlying, 0_6000000, 20, 1_5000000);
+            let boost = storage::get_emission_boost(&e, &underlying).unwrap();
+            assert_eq!(boost.target_util, 0_6000000);
+            assert_eq!(boost.ledgers, 20);
+            assert_eq!(boost.max_boost, 1_5000000);
+            assert_eq!(boost.above_since, 50);
+            assert_eq!(boost.boost, 1_5000000);
+            assert_eq!(storage::get_boosted_reserves(&e).len(), 1);
+
+            // disabling the boost removes the reserve from the boosted reserves
+            set_emission_boost(&e, &underlying, 0, 0, 0);
+            assert_eq!(storage::get_emission_boost(&e, &underlying), None);
+            assert_eq!(storage::get_boosted_reserves(&e).len(), 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_emission_boost_over_max() {
+        let e = Env::default();
+        e.mock_all_auths();
+        setup_ledger(&e);
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 1500000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            set_emission_boost(&e, &underlying, 0_5000000, 10, 3_0000001);
+        });
+    }
+}
@@ -1,7 +1,8 @@
 use cast::i128;
 use sep_41_token::TokenClient;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{panic_with_error, Address, Env, Vec};
+use soroban_sdk::{contracttype, panic_with_error, Address, Env, Vec};
+use trustbridge_common::decimal_scalar;
 
 use crate::{
     constants::SCALAR_7,
@@ -11,11 +12,30 @@ use crate::{
     validator::require_nonnegative,
 };
 
+/// The emissions claimed by a user, split by the token they were paid in
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct ClaimedEmissions {
+    pub legacy: i128,   // the amount of the legacy emission token (BLND) claimed
+    pub migrated: i128, // the amount of the migrated emission token claimed
+}
+
 /// Performs a claim against the given "reserve_token_ids" for "from"
-pub fn execute_claim(e: &Env, from: &Address, reserve_token_ids: &Vec<u32>, to: &Address) -> i128 {
+///
+/// Emissions accrued before a reserve token's emissions were migrated are paid in the legacy
+/// emission token, and emissions accrued after in the migrated emission token.
+///
+/// Returns the amount of each token claimed
+pub fn execute_claim(
+    e: &Env,
+    from: &Address,
+    reserve_token_ids: &Vec<u32>,
+    to: &Address,
+) -> ClaimedEmissions {
     let from_state = User::load(e, from);
     let reserve_list = storage::get_res_list(e);
     let mut to_claim = 0;
+    let mut to_claim_migrated = 0;
     for reserve_token_id in reserve_token_ids.clone() {
         let reserve_index = reserve_token_id / 2;
         let reserve_addr = reserve_list.get(reserve_index);
@@ -34,14 +54,16 @@ pub fn execute_claim(e: &Env, from: &Address, reserve_token_ids: &Vec<u32>, to:
                     ),
                     _ => panic_with_error!(e, PoolError::BadRequest),
                 };
-                to_claim += claim_emissions(
+                let (claimed, claimed_migrated) = claim_emissions(
                     e,
                     reserve_token_id,
                     supply,
-                    10i128.pow(reserve_config.decimals),
+                    decimal_scalar(reserve_config.decimals),
                     from,
                     user_balance,
                 );
+                to_claim += claimed;
+                to_claim_migrated += claimed_migrated;
             }
             None => {
                 panic_with_error!(e, PoolError::BadRequest)
@@ -49,8 +71,8 @@ pub fn execute_claim(e: &Env, from: &Address, reserve_token_ids: &Vec<u32>, to:
         }
     }
 
+    let backstop = storage::get_backstop(e);
     if to_claim > 0 {
-        let backstop = storage::get_backstop(e);
         let blnd_token = storage::get_blnd_token(e);
         TokenClient::new(e, &blnd_token).transfer_from(
             &e.current_contract_address(),
@@ -59,7 +81,22 @@ pub fn execute_claim(e: &Env, from: &Address, reserve_token_ids: &Vec<u32>, to:
             &to_claim,
         );
     }
-    to_claim
+    if to_claim_migrated > 0 {
+        let migration = match storage::get_emission_migration(e) {
+            Some(migration) => migration,
+            None => panic_with_error!(e, PoolError::BadRequest),
+        };
+        TokenClient::new(e, &migration.token).transfer_from(
+            &e.current_contract_address(),
+            &backstop,
+            to,
+            &to_claim_migrated,
+        );
+    }
+    ClaimedEmissions {
+        legacy: to_claim,
+        migrated: to_claim_migrated,
+    }
 }
 
 /// Update the emissions information about a reserve token. Must be called before any update
@@ -88,6 +125,22 @@ pub fn update_emissions(
     user: &Address,
     balance: i128,
 ) {
+    // the legacy emissions must be updated first, as they take over the user's emission data
+    // the first time the user is updated after the reserve token's emissions were migrated
+    if let Some(legacy_emis_data) =
+        update_legacy_emission_data(e, res_token_id, supply, supply_scalar)
+    {
+        update_user_emissions(
+            e,
+            &legacy_emis_data,
+            res_token_id,
+            supply_scalar,
+            user,
+            balance,
+            false,
+            true,
+        );
+    }
     if let Some(res_emis_data) = update_emission_data(e, res_token_id, supply, supply_scalar) {
         update_user_emissions(
             e,
@@ -97,13 +150,15 @@ pub fn update_emissions(
             user,
             balance,
             false,
+            false,
         );
     }
 }
 
 /// Update and claim the emissions for a reserve token.
 ///
-/// Returns the amount of tokens to claim.
+/// Returns the amount of tokens to claim as (legacy emission token, migrated emission token).
+/// Emissions of a reserve token that has not been migrated are paid in the legacy token.
 ///
 /// ### Arguments
 /// * `res_token_id` - The reserve token being acted against => (reserve index * 2 + (0 for debtToken or 1 for blendToken))
@@ -121,9 +176,22 @@ fn claim_emissions(
     supply_scalar: i128,
     user: &Address,
     balance: i128,
-) -> i128 {
-    if let Some(res_emis_data) = update_emission_data(e, res_token_id, supply, supply_scalar) {
-        update_user_emissions(
+) -> (i128, i128) {
+    let legacy_claimed = match update_legacy_emission_data(e, res_token_id, supply, supply_scalar) {
+        Some(legacy_emis_data) => Some(update_user_emissions(
+            e,
+            &legacy_emis_data,
+            res_token_id,
+            supply_scalar,
+            user,
+            balance,
+            true,
+            true,
+        )),
+        None => None,
+    };
+    let claimed = match update_emission_data(e, res_token_id, supply, supply_scalar) {
+        Some(res_emis_data) => update_user_emissions(
             e,
             &res_emis_data,
             res_token_id,
@@ -131,9 +199,13 @@ fn claim_emissions(
             user,
             balance,
             true,
-        )
-    } else {
-        0
+            false,
+        ),
+        None => 0,
+    };
+    match legacy_claimed {
+        Some(legacy_claimed) => (legacy_claimed, claimed),
+        None => (claimed, 0),
     }
 }
 
@@ -157,33 +229,74 @@ pub(super) fn update_emission_data(
 ) -> Option<ReserveEmissionData> {
     match storage::get_res_emis_data(e, &res_token_id) {
         Some(mut res_emission_data) => {
-            if res_emission_data.last_time >= res_emission_data.expiration
-                || e.ledger().timestamp() == res_emission_data.last_time
-                || res_emission_data.eps == 0
-                || supply == 0
-            {
-                return Some(res_emission_data);
+            if accrue_emission_index(e, &mut res_emission_data, supply, supply_scalar) {
+                storage::set_res_emis_data(e, &res_token_id, &res_emission_data);
             }
-
-            let ledger_timestamp = if e.ledger().timestamp() > res_emission_data.expiration {
-                res_emission_data.expiration
-            } else {
-                e.ledger().timestamp()
-            };
-
-            let additional_idx = (i128(ledger_timestamp - res_emission_data.last_time)
-                * i128(res_emission_data.eps))
-            .fixed_div_floor(&e, &supply, &supply_scalar);
-
-            res_emission_data.index += additional_idx;
-            res_emission_data.last_time = ledger_timestamp;
-            storage::set_res_emis_data(e, &res_token_id, &res_emission_data);
             Some(res_emission_data)
         }
         None => return None, // no emission exist, no update is required
     }
 }
 
+/// Update the reserve token emission data for the token used before the emission migration
+///
+/// Returns the new ReserveEmissionData, or None if the reserve token's emissions have not been migrated
+///
+/// ### Arguments
+/// * `res_token_id` - The reserve token being acted against => (reserve index * 2 + (0 for debtToken or 1 for blendToken))
+/// * `supply` - The current supply of the reserve token
+/// * `supply_scalar` - The scalar of the reserve token
+fn update_legacy_emission_data(
+    e: &Env,
+    res_token_id: u32,
+    supply: i128,
+    supply_scalar: i128,
+) -> Option<ReserveEmissionData> {
+    match storage::get_legacy_res_emis_data(e, &res_token_id) {
+        Some(mut res_emission_data) => {
+            if accrue_emission_index(e, &mut res_emission_data, supply, supply_scalar) {
+                storage::set_legacy_res_emis_data(e, &res_token_id, &res_emission_data);
+            }
+            Some(res_emission_data)
+        }
+        None => None,
+    }
+}
+
+/// Accrue the emission index of a reserve token up to the current timestamp, or the expiration
+/// of the emissions if sooner
+///
+/// Returns true if the emission data changed
+fn accrue_emission_index(
+    e: &Env,
+    res_emission_data: &mut ReserveEmissionData,
+    supply: i128,
+    supply_scalar: i128,
+) -> bool {
+    if res_emission_data.last_time >= res_emission_data.expiration
+        || e.ledger().timestamp() == res_emission_data.last_time
+        || res_emission_data.eps == 0
+        || supply == 0
+    {
+        return false;
+    }
+
+    let ledger_timestamp = if e.ledger().timestamp() > res_emission_data.expiration {
+        res_emission_data.expiration
+    } else {
+        e.ledger().timestamp()
+    };
+
+    let additional_idx = (i128(ledger_timestamp - res_emission_data.last_time)
+        * i128(res_emission_data.eps))
+    .fixed_div_floor(&e, &supply, &supply_scalar);
+
+    res_emission_data.index += additional_idx;
+    res_emission_data.last_time = ledger_timestamp;
+    true
+}
+
+#[allow(clippy::too_many_arguments)]
 fn update_user_emissions(
     e: &Env,
     res_emis_data: &ReserveEmissionData,
@@ -192,8 +305,14 @@ fn update_user_emissions(
     user: &Address,
     balance: i128,
     claim: bool,
+    legacy: bool,
 ) -> i128 {
-    if let Some(user_data) = storage::get_user_emissions(e, user, &res_token_id) {
+    let user_data = if legacy {
+        load_legacy_user_emissions(e, user, res_token_id)
+    } else {
+        storage::get_user_emissions(e, user, &res_token_id)
+    };
+    if let Some(user_data) = user_data {
         if user_data.index != res_emis_data.index || claim {
             let mut accrual = user_data.accrued;
             if balance != 0 {
@@ -206,17 +325,53 @@ fn update_user_emissions(
                 );
                 accrual += to_accrue;
             }
-            return set_user_emissions(e, user, res_token_id, res_emis_data.index, accrual, claim);
+            return set_user_emissions(
+                e,
+                user,
+                res_token_id,
+                res_emis_data.index,
+                accrual,
+                claim,
+                legacy,
+            );
         }
         0
     } else if balance == 0 {
         // first time the user registered an action with the asset since emissions were added
-        return set_user_emissions(e, user, res_token_id, res_emis_data.index, 0, claim);
+        return set_user_emissions(e, user, res_token_id, res_emis_data.index, 0, claim, legacy);
     } else {
         // user had tokens before emissions began, they are due any historical emissions
         let to_accrue =
             balance.fixed_mul_floor(e, &res_emis_data.index, &(supply_scalar * SCALAR_7));
-        return set_user_emissions(e, user, res_token_id, res_emis_data.index, to_accrue, claim);
+        return set_user_emissions(
+            e,
+            user,
+            res_token_id,
+            res_emis_data.index,
+            to_accrue,
+            claim,
+            legacy,
+        );
+    }
+}
+
+/// Load the user's emission data for the token used before the emission migration. The first
+/// time the user is loaded after the reserve token's emissions were migrated, their emission data
+/// still tracks the legacy index, so it is moved to the legacy emission data.
+fn load_legacy_user_emissions(
+    e: &Env,
+    user: &Address,
+    res_token_id: u32,
+) -> Option<UserEmissionData> {
+    match storage::get_legacy_user_emissions(e, user, &res_token_id) {
+        Some(user_data) => Some(user_data),
+        None => {
+            let user_data = storage::get_user_emissions(e, user, &res_token_id);
+            if user_data.is_some() {
+                storage::del_user_emissions(e, user, &res_token_id);
+            }
+            user_data
+        }
     }
 }
 
@@ -227,24 +382,24 @@ fn set_user_emissions(
     index: i128,
     accrued: i128,
     claim: bool,
+    legacy: bool,
 ) -> i128 {
-    if claim {
-        storage::set_user_emissions(
-            e,
-            user,
-            &res_token_id,
-            &UserEmissionData { index, accrued: 0 },
-        );
-        accrued
+    let (user_data, claimed) = if claim {
+        (UserEmissionData { index, accrued: 0 }, accrued)
     } else {
-        storage::set_user_emissions(e, user, &res_token_id, &UserEmissionData { index, accrued });
-        0
+        (UserEmissionData { index, accrued }, 0)
+    };
+    if legacy {
+        storage::set_legacy_user_emissions(e, user, &res_token_id, &user_data);
+    } else {
+        storage::set_user_emissions(e, user, &res_token_id, &user_data);
     }
+    claimed
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{pool::Positions, testutils};
+    use crate::{pool::Positions, storage::EmissionMigration, testutils};
 
     use super::*;
     use soroban_sdk::{
@@ -516,7 +671,7 @@ mod tests {
                 user_position,
             );
 
-            assert_eq!(result, 400_3222222);
+            assert_eq!(result, (400_3222222, 0));
             let new_reserve_emission_data =
                 storage::get_res_emis_data(&e, &res_token_index).unwrap_optimized();
             let new_user_emission_data =
@@ -938,6 +1093,7 @@ mod tests {
                 &samwise,
                 user_balance,
                 false,
+                false,
             );
 
             let new_user_emission_data =
@@ -986,6 +1142,7 @@ mod tests {
                 &samwise,
                 user_balance,
                 false,
+                false,
             );
 
             let new_user_emission_data =
@@ -1040,6 +1197,7 @@ mod tests {
                 &samwise,
                 user_balance,
                 false,
+                false,
             );
 
             let new_user_emission_data =
@@ -1095,6 +1253,7 @@ mod tests {
                 &samwise,
                 user_balance,
                 false,
+                false,
             );
 
             let new_user_emission_data =
@@ -1149,6 +1308,7 @@ mod tests {
                 &samwise,
                 user_balance,
                 false,
+                false,
             );
 
             let new_user_emission_data =
@@ -1204,6 +1364,7 @@ mod tests {
                 &samwise,
                 user_balance,
                 true,
+                false,
             );
 
             let new_user_emission_data =
@@ -1254,6 +1415,7 @@ mod tests {
                 &samwise,
                 user_balance,
                 true,
+                false,
             );
 
             let new_user_emission_data =
@@ -1311,6 +1473,7 @@ mod tests {
                 &samwise,
                 user_balance,
                 true,
+                false,
             );
         });
     }
@@ -1431,7 +1594,13 @@ mod tests {
                 new_reserve_emission_data_1.index
             );
             assert_eq!(new_user_emission_data.accrued, 0);
-            assert_eq!(result, 400_3222222 + 301_0222222);
+            assert_eq!(
+                result,
+                ClaimedEmissions {
+                    legacy: 400_3222222 + 301_0222222,
+                    migrated: 0,
+                }
+            );
 
             // verify tokens are sent
             assert_eq!(blnd_token_client.balance(&merry), 400_3222222 + 301_0222222);
@@ -1442,6 +1611,160 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_claim_with_migrated_reserve() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let merry = Address::generate(&e);
+
+        let (blnd, blnd_token_client) = testutils::create_blnd_token(&e, &pool, &bombadil);
+        let (tbrg, tbrg_token_client) = testutils::create_token_contract(&e, &bombadil);
+        let (backstop, _) = testutils::create_backstop(
+            &e,
+            &pool,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            &blnd,
+        );
+        // mock backstop having emissions for pool in both tokens
+        e.as_contract(&backstop, || {
+            blnd_token_client.approve(&backstop, &pool, &100_000_0000000_i128, &1000000);
+            tbrg_token_client.approve(&backstop, &pool, &100_000_0000000_i128, &1000000);
+        });
+        blnd_token_client.mint(&backstop, &100_000_0000000);
+        tbrg_token_client.mint(&backstop, &100_000_0000000);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1501000000, // 10^6 seconds have passed
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.decimals = 5;
+        reserve_data.b_supply = 100_00000;
+        reserve_data.d_supply = 50_00000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.decimals = 9;
+        reserve_config.index = 1;
+        reserve_data.b_supply = 100_000_000_000;
+        reserve_data.d_supply = 50_000_000_000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 2_00000)],
+            collateral: map![&e, (1, 1_000_000_000)],
+            supply: map![&e, (1, 1_000_000_000)],
+        };
+        e.as_contract(&pool, || {
+            storage::set_backstop(&e, &backstop);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+            storage::set_emission_migration(
+                &e,
+                &EmissionMigration {
+                    token: tbrg.clone(),
+                    ledger: 100,
+                },
+            );
+
+            // reserve 0 d_token was migrated, reserve 1 b_token was not
+            let legacy_emission_data_0 = ReserveEmissionData {
+                expiration: 1600000000,
+                eps: 0_01000000000000,
+                index: 23456780000000,
+                last_time: 1500000000,
+            };
+            let reserve_emission_data_0 = ReserveEmissionData {
+                expiration: 1600000000,
+                eps: 0_01000000000000,
+                index: 0,
+                last_time: 1500000000,
+            };
+            let user_emission_data_0 = UserEmissionData {
+                index: 12345670000000,
+                accrued: 0_1000000,
+            };
+            let res_token_index_0 = 0 * 2 + 0; // d_token for reserve 0
+
+            let reserve_emission_data_1 = ReserveEmissionData {
+                expiration: 1600000000,
+                eps: 0_01500000000000,
+                index: 13456780000000,
+                last_time: 1500000000,
+            };
+            let user_emission_data_1 = UserEmissionData {
+                index: 12345670000000,
+                accrued: 1_0000000,
+            };
+            let res_token_index_1 = 1 * 2 + 1; // b_token for reserve 1
+
+            storage::set_legacy_res_emis_data(&e, &res_token_index_0, &legacy_emission_data_0);
+            storage::set_res_emis_data(&e, &res_token_index_0, &reserve_emission_data_0);
+            storage::set_user_emissions(&e, &samwise, &res_token_index_0, &user_emission_data_0);
+
+            storage::set_res_emis_data(&e, &res_token_index_1, &reserve_emission_data_1);
+            storage::set_user_emissions(&e, &samwise, &res_token_index_1, &user_emission_data_1);
+
+            let reserve_token_ids: Vec<u32> = vec![&e, res_token_index_0, res_token_index_1];
+            let result = execute_claim(&e, &samwise, &reserve_token_ids, &merry);
+
+            // the user's emission data was moved to the legacy index
+            let new_legacy_emission_data =
+                storage::get_legacy_res_emis_data(&e, &res_token_index_0).unwrap_optimized();
+            let new_legacy_user_emission_data =
+                storage::get_legacy_user_emissions(&e, &samwise, &res_token_index_0)
+                    .unwrap_optimized();
+            assert_eq!(new_legacy_emission_data.last_time, 1501000000);
+            assert_eq!(
+                new_legacy_user_emission_data.index,
+                new_legacy_emission_data.index
+            );
+            assert_eq!(new_legacy_user_emission_data.accrued, 0);
+
+            // the user accrued the new index from the start
+            let new_reserve_emission_data =
+                storage::get_res_emis_data(&e, &res_token_index_0).unwrap_optimized();
+            let new_user_emission_data =
+                storage::get_user_emissions(&e, &samwise, &res_token_index_0).unwrap_optimized();
+            assert_eq!(new_reserve_emission_data.index, 20000000000000000);
+            assert_eq!(
+                new_user_emission_data.index,
+                new_reserve_emission_data.index
+            );
+            assert_eq!(new_user_emission_data.accrued, 0);
+            assert!(storage::get_legacy_user_emissions(&e, &samwise, &res_token_index_1).is_none());
+            assert_eq!(
+                result,
+                ClaimedEmissions {
+                    legacy: 400_3222222 + 301_0222222,
+                    migrated: 400_0000000,
+                }
+            );
+
+            // verify legacy emissions are sent in the legacy token
+            assert_eq!(blnd_token_client.balance(&merry), 400_3222222 + 301_0222222);
+            assert_eq!(tbrg_token_client.balance(&merry), 400_0000000);
+            assert_eq!(
+                tbrg_token_client.balance(&backstop),
+                100_000_0000000 - 400_0000000
+            );
+        });
+    }
+
     #[test]
     fn test_execute_claim_with_already_claimed_reserve() {
         let e = Env::default();
@@ -1556,7 +1879,13 @@ mod tests {
                 new_reserve_emission_data_1.index
             );
             assert_eq!(new_user_emission_data.accrued, 0);
-            assert_eq!(result, 400_3222222);
+            assert_eq!(
+                result,
+                ClaimedEmissions {
+                    legacy: 400_3222222,
+                    migrated: 0,
+                }
+            );
 
             // verify tokens are sent
             assert_eq!(blnd_token_client.balance(&merry), 400_3222222);
@@ -1,5 +1,12 @@
 mod manager;
-pub use manager::{gulp_emissions, set_pool_emissions, ReserveEmissionMetadata};
+pub use manager::{
+    calc_emission_apr, get_emission_config, get_emission_split, gulp_emissions,
+    set_bootstrap_emissions, set_emission_migration, set_emission_split, set_pool_emissions,
+    ReserveEmissionMetadata,
+};
+
+mod boost;
+pub use boost::{set_emission_boost, update_emission_boost};
 
 mod distributor;
-pub use distributor::{execute_claim, update_emissions};
+pub use distributor::{execute_claim, update_emissions, ClaimedEmissions};
@@ -52,4 +52,23 @@ pub enum PoolError {
     InvalidLot = 1222,
     ReserveDisabled = 1223,
     MinCollateralNotMet = 1224,
+
+    // TrustBridge Errors
+    AuctionFillTooEarly = 1225,
+    AssetNotAllowed = 1226,
+    InvalidSoftLiquidation = 1227,
+    AllowanceExpired = 1228,
+    InvalidRedemption = 1230,
+    LiquidatorNotAllowed = 1231,
+    BorrowFrozen = 1232,
+    SupplyOnlyAccount = 1233,
+    TooManyRequests = 1234,
+    TooManyAuctionRequests = 1235,
+    InsufficientFlashFillProceeds = 1236,
+    InvalidReserveIndex = 1237,
+    OracleBaseMismatch = 1238,
+    InvalidScaledBid = 1240,
+    InvalidScaledLot = 1241,
+    CircularBackstopCollateral = 1242,
+    WithdrawCooldown = 1243,
 }
@@ -1,6 +1,12 @@
-use soroban_sdk::{Address, Env, Symbol, Vec};
+use soroban_sdk::{Address, BytesN, Env, Map, Symbol, Vec};
+use trustbridge_common::{Asset, Role};
 
-use crate::{AuctionData, ReserveConfig};
+use crate::{
+    ActionLimits, AuctionData, InterestSplit, InvariantCheck, PoolMetadata, ProtocolFee,
+    QueuedInterestSplit, QueuedProtocolFee, QueuedReserveMigration, RequestHook, ReserveConfig,
+    ReserveMigration, ReserveProposal, RetailLiquidation, RiskTierConfig, ShutdownData,
+    SoftLiquidationConfig,
+};
 
 pub struct PoolEvents {}
 
@@ -20,49 +26,231 @@ impl PoolEvents {
 
     /// Emitted when pool parameters are updated
     ///
-    /// - topics - `["update_pool", admin: Address]`
+    /// - topics - `["update_pool", caller: Address]`
     /// - data - `[backstop_take_rate: u32, max_positions: u32, min_collateral: i128]`
     ///
     /// ### Arguments
-    /// * admin - The current admin of the pool
+    /// * caller - The address holding the risk role
     /// * backstop_take_rate - The new backstop take rate
     /// * max_positions - The new maximum number of positions
     pub fn update_pool(
         e: &Env,
-        admin: Address,
+        caller: Address,
         backstop_take_rate: u32,
         max_positions: u32,
         min_collateral: i128,
     ) {
-        let topics = (Symbol::new(&e, "update_pool"), admin);
+        let topics = (Symbol::new(&e, "update_pool"), caller);
         e.events()
             .publish(topics, (backstop_take_rate, max_positions, min_collateral));
     }
 
+    /// Emitted when the minimum number of blocks before an auction can be filled is updated
+    ///
+    /// - topics - `["set_auction_min_blocks", caller: Address]`
+    /// - data - `min_blocks: u32`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * min_blocks - The new minimum number of blocks
+    pub fn set_auction_min_blocks(e: &Env, caller: Address, min_blocks: u32) {
+        let topics = (Symbol::new(&e, "set_auction_min_blocks"), caller);
+        e.events().publish(topics, min_blocks);
+    }
+
+    /// Emitted when the maximum number of blocks an auction's start is randomly delayed by is
+    /// updated
+    ///
+    /// - topics - `["set_auction_start_offset", admin: Address]`
+    /// - data - `max_offset: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * max_offset - The new maximum number of blocks
+    pub fn set_auction_start_offset(e: &Env, admin: Address, max_offset: u32) {
+        let topics = (Symbol::new(e, "set_auction_start_offset"), admin);
+        e.events().publish(topics, max_offset);
+    }
+
+    /// Emitted when the confidence threshold for oracle prices is updated
+    ///
+    /// - topics - `["set_confidence_threshold", caller: Address]`
+    /// - data - `threshold: u32`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * threshold - The new confidence threshold
+    pub fn set_confidence_threshold(e: &Env, caller: Address, threshold: u32) {
+        let topics = (Symbol::new(e, "set_confidence_threshold"), caller);
+        e.events().publish(topics, threshold);
+    }
+
+    /// Emitted when the pool's reserve allowlist is set
+    ///
+    /// - topics - `["set_reserve_allowlist", caller: Address]`
+    /// - data - `allowlist: Address`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * allowlist - The contract restricting which assets can be added as reserves
+    pub fn set_reserve_allowlist(e: &Env, caller: Address, allowlist: Address) {
+        let topics = (Symbol::new(&e, "set_reserve_allowlist"), caller);
+        e.events().publish(topics, allowlist);
+    }
+
+    /// Emitted when the pool's soft liquidation configuration is set
+    ///
+    /// - topics - `["set_soft_liquidation_config", caller: Address]`
+    /// - data - `config: SoftLiquidationConfig`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * config - The new soft liquidation configuration
+    pub fn set_soft_liquidation_config(e: &Env, caller: Address, config: SoftLiquidationConfig) {
+        let topics = (Symbol::new(&e, "set_soft_liquidation_config"), caller);
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when the pool's DEX adapter is set
+    ///
+    /// - topics - `["set_dex_adapter", caller: Address]`
+    /// - data - `dex_adapter: Address`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * dex_adapter - The new DEX adapter
+    pub fn set_dex_adapter(e: &Env, caller: Address, dex_adapter: Address) {
+        let topics = (Symbol::new(&e, "set_dex_adapter"), caller);
+        e.events().publish(topics, dex_adapter);
+    }
+
+    /// Emitted when the pool is shut down
+    ///
+    /// - topics - `["shutdown", admin: Address]`
+    /// - data - `shutdown: ShutdownData`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * shutdown - The shutdown state, including the frozen prices
+    pub fn shutdown(e: &Env, admin: Address, shutdown: ShutdownData) {
+        let topics = (Symbol::new(&e, "shutdown"), admin);
+        e.events().publish(topics, shutdown);
+    }
+
+    /// Emitted when a user redeems bTokens from a shut down pool
+    ///
+    /// - topics - `["redeem", asset: Address, from: Address]`
+    /// - data - `[amount: i128, b_tokens_burnt: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset redeemed
+    /// * from - The user redeeming
+    /// * amount - The amount of underlying sent to the user
+    /// * b_tokens_burnt - The amount of bTokens burnt
+    pub fn redeem(e: &Env, asset: Address, from: Address, amount: i128, b_tokens_burnt: i128) {
+        let topics = (Symbol::new(e, "redeem"), asset, from);
+        e.events().publish(topics, (amount, b_tokens_burnt));
+    }
+
     /// Emitted when a new reserve configuration change is queued
     ///
-    /// - topics - `["queue_set_reserve", admin: Address]`
+    /// - topics - `["queue_set_reserve", caller: Address]`
     /// - data - `[asset: Address, metadata: ReserveMetadata]`
     ///
     /// ### Arguments
-    /// * admin - The current admin of the pool
+    /// * caller - The address holding the risk role
     /// * asset - The asset to change the reserve configuration of
     /// * metadata - The new reserve configuration
-    pub fn queue_set_reserve(e: &Env, admin: Address, asset: Address, metadata: ReserveConfig) {
-        let topics = (Symbol::new(&e, "queue_set_reserve"), admin);
+    pub fn queue_set_reserve(e: &Env, caller: Address, asset: Address, metadata: ReserveConfig) {
+        let topics = (Symbol::new(&e, "queue_set_reserve"), caller);
         e.events().publish(topics, (asset, metadata));
     }
 
+    /// Emitted when the bond required to propose a reserve is set
+    ///
+    /// - topics - `["set_proposal_bond", caller: Address]`
+    /// - data - `bond: i128`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * bond - The amount of BLND to bond
+    pub fn set_proposal_bond(e: &Env, caller: Address, bond: i128) {
+        let topics = (Symbol::new(&e, "set_proposal_bond"), caller);
+        e.events().publish(topics, bond);
+    }
+
+    /// Emitted when a reserve is proposed
+    ///
+    /// - topics - `["propose_reserve", asset: Address]`
+    /// - data - `proposal: ReserveProposal`
+    ///
+    /// ### Arguments
+    /// * asset - The asset proposed as a reserve
+    /// * proposal - The reserve proposal
+    pub fn propose_reserve(e: &Env, asset: Address, proposal: ReserveProposal) {
+        let topics = (Symbol::new(&e, "propose_reserve"), asset);
+        e.events().publish(topics, proposal);
+    }
+
+    /// Emitted when a reserve proposal is accepted
+    ///
+    /// - topics - `["accept_reserve_proposal", admin: Address]`
+    /// - data - `[asset: Address, proposer: Address]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The asset of the proposal
+    /// * proposer - The proposer of the reserve
+    pub fn accept_reserve_proposal(e: &Env, admin: Address, asset: Address, proposer: Address) {
+        let topics = (Symbol::new(&e, "accept_reserve_proposal"), admin);
+        e.events().publish(topics, (asset, proposer));
+    }
+
+    /// Emitted when a reserve proposal is rejected
+    ///
+    /// - topics - `["reject_reserve_proposal", admin: Address]`
+    /// - data - `[asset: Address, proposer: Address, slashed: bool]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The asset of the proposal
+    /// * proposer - The proposer of the reserve
+    /// * slashed - If the bond was slashed
+    pub fn reject_reserve_proposal(
+        e: &Env,
+        admin: Address,
+        asset: Address,
+        proposer: Address,
+        slashed: bool,
+    ) {
+        let topics = (Symbol::new(&e, "reject_reserve_proposal"), admin);
+        e.events().publish(topics, (asset, proposer, slashed));
+    }
+
+    /// Emitted when the bond of an expired reserve proposal is reclaimed
+    ///
+    /// - topics - `["reclaim_proposal_bond", asset: Address]`
+    /// - data - `[proposer: Address, bond: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset of the proposal
+    /// * proposer - The proposer of the reserve
+    /// * bond - The amount of BLND returned
+    pub fn reclaim_proposal_bond(e: &Env, asset: Address, proposer: Address, bond: i128) {
+        let topics = (Symbol::new(&e, "reclaim_proposal_bond"), asset);
+        e.events().publish(topics, (proposer, bond));
+    }
+
     /// Emitted when a queued reserve configuration change is cancelled
     ///
-    /// - topics - `["cancel_set_reserve", admin: Address]`
+    /// - topics - `["cancel_set_reserve", caller: Address]`
     /// - data - `asset: Address`
     ///
     /// ### Arguments
-    /// * admin - The current admin of the pool
+    /// * caller - The address holding the risk role
     /// * asset - The asset to cancel the reserve configuration change of
-    pub fn cancel_set_reserve(e: &Env, admin: Address, asset: Address) {
-        let topics = (Symbol::new(&e, "cancel_set_reserve"), admin);
+    pub fn cancel_set_reserve(e: &Env, caller: Address, asset: Address) {
+        let topics = (Symbol::new(&e, "cancel_set_reserve"), caller);
         e.events().publish(topics, asset);
     }
 
@@ -91,16 +279,16 @@ impl PoolEvents {
         e.events().publish(topics, new_status);
     }
 
-    /// Emitted when pool status is updated by admin
+    /// Emitted when pool status is updated by the admin or a pauser
     ///
-    /// - topics - `["set_status", admin: Address]`
+    /// - topics - `["set_status", caller: Address]`
     /// - data - `pool_status: PoolStatus`
     ///
     /// ### Arguments
-    /// * admin - The admin setting the pool status
+    /// * caller - The admin or pauser setting the pool status
     /// * pool_status - The new pool status
-    pub fn set_status_admin(e: &Env, admin: Address, pool_status: u32) {
-        let topics = (Symbol::new(&e, "set_status"), admin);
+    pub fn set_status_admin(e: &Env, caller: Address, pool_status: u32) {
+        let topics = (Symbol::new(&e, "set_status"), caller);
         e.events().publish(topics, pool_status);
     }
 
@@ -130,19 +318,62 @@ impl PoolEvents {
         e.events().publish(topics, emissions);
     }
 
+    /// Emitted when a new pool starts bootstrapping emissions to its suppliers
+    ///
+    /// - topics - `["set_bootstrap_emissions", caller: Address]`
+    /// - data - `[reserve_indexes: Vec<u32>, expiration: u64]`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * reserve_indexes - The indexes of the reserves whose suppliers receive emissions
+    /// * expiration - The time the bootstrap period ends
+    pub fn set_bootstrap_emissions(
+        e: &Env,
+        caller: Address,
+        reserve_indexes: Vec<u32>,
+        expiration: u64,
+    ) {
+        let topics = (Symbol::new(e, "set_bootstrap_emissions"), caller);
+        e.events().publish(topics, (reserve_indexes, expiration));
+    }
+
     /// Emitted when emissions are claimed
     ///
     /// - topics - `["claim", from: Address]`
-    /// - data - `[reserve_token_ids: Vec<u32>, amount_claimed: i128]`
+    /// - data - `[reserve_token_ids: Vec<u32>, amount_claimed: i128, amount_migrated: i128, nonce: u64]`
     ///
     /// ### Arguments
     /// * from - The address claiming the emissions
     /// * reserve_token_ids - The reserve token IDs claimed
-    /// * amount_claimed - The amount claimed
-    pub fn claim(e: &Env, from: Address, reserve_token_ids: Vec<u32>, amount_claimed: i128) {
+    /// * amount_claimed - The amount of the legacy emission token (BLND) claimed
+    /// * amount_migrated - The amount of the migrated emission token claimed
+    /// * nonce - The user's operation nonce after the claim
+    pub fn claim(
+        e: &Env,
+        from: Address,
+        reserve_token_ids: Vec<u32>,
+        amount_claimed: i128,
+        amount_migrated: i128,
+        nonce: u64,
+    ) {
         let topics = (Symbol::new(&e, "claim"), from);
-        e.events()
-            .publish(topics, (reserve_token_ids, amount_claimed));
+        e.events().publish(
+            topics,
+            (reserve_token_ids, amount_claimed, amount_migrated, nonce),
+        );
+    }
+
+    /// Emitted after the events for each request when a user submits requests to the pool
+    ///
+    /// - topics - `["submit", from: Address]`
+    /// - data - `nonce: u64`
+    ///
+    /// ### Arguments
+    /// * from - The address whose positions were modified
+    /// * nonce - The user's operation nonce after the submit
+    pub fn submit(e: &Env, from: Address, nonce: u64) {
+        let topics = (Symbol::new(e, "submit"), from);
+        e.events().publish(topics, nonce);
     }
 
     /// Emitted when bad debt is recorded
@@ -159,6 +390,114 @@ impl PoolEvents {
         e.events().publish(topics, d_tokens);
     }
 
+    /// Emitted when a user repays interest with a different asset
+    ///
+    /// - topics - `["repay_interest", debt_asset: Address, from: Address]`
+    /// - data - `[pay_asset: Address, amount_in: i128, repaid: i128, d_tokens_burnt: i128]`
+    ///
+    /// ### Arguments
+    /// * debt_asset - The liability asset repaid
+    /// * from - The user paying the interest
+    /// * pay_asset - The asset used to pay the interest
+    /// * amount_in - The amount of the payment asset swapped
+    /// * repaid - The amount of the debt asset repaid
+    /// * d_tokens_burnt - The amount of d_tokens burnt
+    pub fn repay_interest(
+        e: &Env,
+        debt_asset: Address,
+        from: Address,
+        pay_asset: Address,
+        amount_in: i128,
+        repaid: i128,
+        d_tokens_burnt: i128,
+    ) {
+        let topics = (Symbol::new(e, "repay_interest"), debt_asset, from);
+        e.events()
+            .publish(topics, (pay_asset, amount_in, repaid, d_tokens_burnt));
+    }
+
+    /// Emitted when the admin forgives interest accrued on a user's liabilities
+    ///
+    /// - topics - `["forgive_interest", asset: Address, user: Address]`
+    /// - data - `[admin: Address, amount: i128, d_tokens_burnt: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The liability asset the interest accrued on
+    /// * user - The user whose interest is forgiven
+    /// * admin - The current admin of the pool
+    /// * amount - The amount of interest forgiven
+    /// * d_tokens_burnt - The amount of d_tokens burnt
+    pub fn forgive_interest(
+        e: &Env,
+        asset: Address,
+        user: Address,
+        admin: Address,
+        amount: i128,
+        d_tokens_burnt: i128,
+    ) {
+        let topics = (Symbol::new(e, "forgive_interest"), asset, user);
+        e.events().publish(topics, (admin, amount, d_tokens_burnt));
+    }
+
+    /// Emitted when a user sets or clears their notification key
+    ///
+    /// - topics - `["set_notification_key", user: Address]`
+    /// - data - `notification_key: Option<BytesN<32>>`
+    ///
+    /// ### Arguments
+    /// * user - The user
+    /// * notification_key - The hash of the notification key, if set
+    pub fn set_notification_key(e: &Env, user: Address, notification_key: Option<BytesN<32>>) {
+        let topics = (Symbol::new(e, "set_notification_key"), user);
+        e.events().publish(topics, notification_key);
+    }
+
+    /// Emitted alongside a health related event for a user with a notification key, so notifier
+    /// services can route an alert to the user
+    ///
+    /// - topics - `["health_alert", notification_key: BytesN<32>]`
+    /// - data - `[user: Address, reason: Symbol]`
+    ///
+    /// ### Arguments
+    /// * notification_key - The hash of the user's notification key
+    /// * user - The user
+    /// * reason - The health related event, one of `liquidation`, `soft_liquidation`, or `bad_debt`
+    pub fn health_alert(e: &Env, notification_key: BytesN<32>, user: Address, reason: Symbol) {
+        let topics = (Symbol::new(e, "health_alert"), notification_key);
+        e.events().publish(topics, (user, reason));
+    }
+
+    /// Emitted when a user is soft liquidated
+    ///
+    /// - topics - `["soft_liquidation", user: Address, keeper: Address]`
+    /// - data - `[collateral_asset: Address, debt_asset: Address, amount: i128, d_tokens_burnt: i128, fee: i128]`
+    ///
+    /// ### Arguments
+    /// * user - The user being soft liquidated
+    /// * keeper - The address executing the soft liquidation
+    /// * collateral_asset - The collateral asset sold
+    /// * debt_asset - The liability asset repaid
+    /// * amount - The amount of collateral sold, including the fee
+    /// * d_tokens_burnt - The amount of d_tokens repaid
+    /// * fee - The amount of collateral paid to the keeper
+    #[allow(clippy::too_many_arguments)]
+    pub fn soft_liquidation(
+        e: &Env,
+        user: Address,
+        keeper: Address,
+        collateral_asset: Address,
+        debt_asset: Address,
+        amount: i128,
+        d_tokens_burnt: i128,
+        fee: i128,
+    ) {
+        let topics = (Symbol::new(e, "soft_liquidation"), user, keeper);
+        e.events().publish(
+            topics,
+            (collateral_asset, debt_asset, amount, d_tokens_burnt, fee),
+        );
+    }
+
     /// Emitted when bad debt is defaulted
     ///
     /// - topics - `["defaulted_debt", asset: Address]`
@@ -316,31 +655,708 @@ impl PoolEvents {
         e.events().publish(topics, token_delta);
     }
 
+    /// Emitted when the rent for the pool's critical entries is bumped
+    ///
+    /// - topics - `["bump_critical", caller: Address]`
+    /// - data - `[bounty: Map<Address, i128>]`
+    ///
+    /// ### Arguments
+    /// * caller - The address that bumped the entries
+    /// * bounty - The bounty paid to the caller for each reserve
+    pub fn bump_critical(e: &Env, caller: Address, bounty: Map<Address, i128>) {
+        let topics = (Symbol::new(e, "bump_critical"), caller);
+        e.events().publish(topics, bounty);
+    }
+
+    /// Emitted when tokens are donated to the suppliers of a reserve
+    ///
+    /// - topics - `["donate_to_reserve", asset: Address, from: Address]`
+    /// - data - `[amount: i128, b_rate: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset of the reserve
+    /// * from - The address donating tokens
+    /// * amount - The amount of tokens donated
+    /// * b_rate - The new bRate of the reserve
+    pub fn donate_to_reserve(e: &Env, asset: Address, from: Address, amount: i128, b_rate: i128) {
+        let topics = (Symbol::new(e, "donate_to_reserve"), asset, from);
+        e.events().publish(topics, (amount, b_rate));
+    }
+
+    /// Emitted when the liquidator allowlist is enabled or disabled
+    ///
+    /// - topics - `["set_liquidator_allowlist", caller: Address]`
+    /// - data - `enabled: bool`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * enabled - If only allowed liquidators can fill liquidation auctions
+    pub fn set_liquidator_allowlist(e: &Env, caller: Address, enabled: bool) {
+        let topics = (Symbol::new(e, "set_liquidator_allowlist"), caller);
+        e.events().publish(topics, enabled);
+    }
+
+    /// Emitted when an address is added to or removed from the liquidator allowlist
+    ///
+    /// - topics - `["set_liquidator", caller: Address, liquidator: Address]`
+    /// - data - `allowed: bool`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * liquidator - The address of the liquidator
+    /// * allowed - If the liquidator can fill liquidation auctions
+    pub fn set_liquidator(e: &Env, caller: Address, liquidator: Address, allowed: bool) {
+        let topics = (Symbol::new(e, "set_liquidator"), caller, liquidator);
+        e.events().publish(topics, allowed);
+    }
+
+    /// Emitted when the strategy adapter for a reserve is set
+    ///
+    /// - topics - `["set_reserve_strategy", caller: Address, asset: Address]`
+    /// - data - `[adapter: Address, max_deploy: u32]`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * asset - The underlying asset of the reserve
+    /// * adapter - The strategy adapter
+    /// * max_deploy - The maximum fraction of idle liquidity that can be deployed
+    pub fn set_reserve_strategy(
+        e: &Env,
+        caller: Address,
+        asset: Address,
+        adapter: Address,
+        max_deploy: u32,
+    ) {
+        let topics = (Symbol::new(e, "set_reserve_strategy"), caller, asset);
+        e.events().publish(topics, (adapter, max_deploy));
+    }
+
+    /// Emitted when idle liquidity is deployed to a reserve's strategy adapter
+    ///
+    /// - topics - `["deploy_to_strategy", asset: Address]`
+    /// - data - `[adapter: Address, amount: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset of the reserve
+    /// * adapter - The strategy adapter
+    /// * amount - The amount of underlying tokens deployed
+    pub fn deploy_to_strategy(e: &Env, asset: Address, adapter: Address, amount: i128) {
+        let topics = (Symbol::new(e, "deploy_to_strategy"), asset);
+        e.events().publish(topics, (adapter, amount));
+    }
+
+    /// Emitted when liquidity is recalled from a reserve's strategy adapter
+    ///
+    /// - topics - `["recall_from_strategy", asset: Address]`
+    /// - data - `[adapter: Address, amount: i128, gain: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset of the reserve
+    /// * adapter - The strategy adapter
+    /// * amount - The amount of underlying tokens recalled
+    /// * gain - The amount recalled beyond the deployed liquidity, accrued to the reserve
+    pub fn recall_from_strategy(
+        e: &Env,
+        asset: Address,
+        adapter: Address,
+        amount: i128,
+        gain: i128,
+    ) {
+        let topics = (Symbol::new(e, "recall_from_strategy"), asset);
+        e.events().publish(topics, (adapter, amount, gain));
+    }
+
+    /// Emitted when the insurance premium is set
+    ///
+    /// - topics - `["set_insurance_premium", caller: Address]`
+    /// - data - `premium: u32`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the treasurer role
+    /// * premium - The fraction of a covered supplier's interest paid to the insurance fund
+    pub fn set_insurance_premium(e: &Env, caller: Address, premium: u32) {
+        let topics = (Symbol::new(e, "set_insurance_premium"), caller);
+        e.events().publish(topics, premium);
+    }
+
+    /// Emitted when a supplier opts into or out of coverage from a reserve's insurance fund
+    ///
+    /// - topics - `["set_coverage", asset: Address, user: Address]`
+    /// - data - `covered: bool`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset of the reserve
+    /// * user - The address of the supplier
+    /// * covered - If the supplier is covered
+    pub fn set_coverage(e: &Env, asset: Address, user: Address, covered: bool) {
+        let topics = (Symbol::new(e, "set_coverage"), asset, user);
+        e.events().publish(topics, covered);
+    }
+
+    /// Emitted when a supplier claims a loss from a reserve's insurance fund
+    ///
+    /// - topics - `["claim_coverage", asset: Address, user: Address]`
+    /// - data - `[to: Address, amount: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset of the reserve
+    /// * user - The address of the supplier
+    /// * to - The address receiving the claim
+    /// * amount - The amount of underlying claimed
+    pub fn claim_coverage(e: &Env, asset: Address, user: Address, to: Address, amount: i128) {
+        let topics = (Symbol::new(e, "claim_coverage"), asset, user);
+        e.events().publish(topics, (to, amount));
+    }
+
+    /// Emitted when the request hooks are set
+    ///
+    /// - topics - `["set_request_hooks", admin: Address]`
+    /// - data - `hooks: Vec<RequestHook>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * hooks - The ordered list of hooks run against each request
+    pub fn set_request_hooks(e: &Env, admin: Address, hooks: Vec<RequestHook>) {
+        let topics = (Symbol::new(e, "set_request_hooks"), admin);
+        e.events().publish(topics, hooks);
+    }
+
+    /// Emitted when the balance delta mode of a reserve is set
+    ///
+    /// - topics - `["set_balance_delta", caller: Address, asset: Address]`
+    /// - data - `enabled: bool`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * asset - The underlying asset of the reserve
+    /// * enabled - If transfers into the reserve are credited by the change in the pool's balance
+    pub fn set_balance_delta(e: &Env, caller: Address, asset: Address, enabled: bool) {
+        let topics = (Symbol::new(e, "set_balance_delta"), caller, asset);
+        e.events().publish(topics, enabled);
+    }
+
+    /// Emitted when the TWAP pricing mode of a reserve is set
+    ///
+    /// - topics - `["set_twap_pricing", caller: Address, asset: Address]`
+    /// - data - `enabled: bool`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * asset - The underlying asset of the reserve
+    /// * enabled - If health calculations price the reserve with the oracle's TWAP
+    pub fn set_twap_pricing(e: &Env, caller: Address, asset: Address, enabled: bool) {
+        let topics = (Symbol::new(e, "set_twap_pricing"), caller, asset);
+        e.events().publish(topics, enabled);
+    }
+
+    /// Emitted when a user freezes borrowing from their account, or requests the freeze be lifted
+    ///
+    /// - topics - `["set_borrow_freeze", user: Address]`
+    /// - data - `[frozen: bool, unfreeze_time: u64]`
+    ///
+    /// ### Arguments
+    /// * user - The user
+    /// * frozen - If the user froze borrowing (true) or requested the freeze be lifted (false)
+    /// * unfreeze_time - The time the freeze lifts, or `u64::MAX` if no unfreeze is requested
+    pub fn set_borrow_freeze(e: &Env, user: Address, frozen: bool, unfreeze_time: u64) {
+        let topics = (Symbol::new(e, "set_borrow_freeze"), user);
+        e.events().publish(topics, (frozen, unfreeze_time));
+    }
+
+    /// Emitted when an account opts into supply-only mode, or requests to leave it
+    ///
+    /// - topics - `["set_supply_only", user: Address]`
+    /// - data - `[enabled: bool, exit_time: u64]`
+    ///
+    /// ### Arguments
+    /// * user - The user
+    /// * enabled - If the user opted in (true) or requested to leave (false)
+    /// * exit_time - The time the account leaves supply-only mode, or `u64::MAX` if no exit is
+    ///   requested
+    pub fn set_supply_only(e: &Env, user: Address, enabled: bool, exit_time: u64) {
+        let topics = (Symbol::new(e, "set_supply_only"), user);
+        e.events().publish(topics, (enabled, exit_time));
+    }
+
+    /// Emitted when a user sets or clears the key they sign requests with for relayed submits
+    ///
+    /// - topics - `["set_signing_key", user: Address]`
+    /// - data - `signing_key: Option<BytesN<32>>`
+    ///
+    /// ### Arguments
+    /// * user - The user
+    /// * signing_key - The ed25519 public key, or None if relayed submits are disabled
+    pub fn set_signing_key(e: &Env, user: Address, signing_key: Option<BytesN<32>>) {
+        let topics = (Symbol::new(e, "set_signing_key"), user);
+        e.events().publish(topics, signing_key);
+    }
+
+    /// Emitted when the admin schedules the migration of the pool's emissions to a new token
+    ///
+    /// - topics - `["set_emission_migration", admin: Address]`
+    /// - data - `[token: Address, ledger: u32]`
+    ///
+    /// ### Arguments
+    /// * admin - The admin of the pool
+    /// * token - The new emission token
+    /// * ledger - The ledger sequence the migration takes effect at
+    pub fn set_emission_migration(e: &Env, admin: Address, token: Address, ledger: u32) {
+        let topics = (Symbol::new(e, "set_emission_migration"), admin);
+        e.events().publish(topics, (token, ledger));
+    }
+
+    /// Emitted when a reserve token's emissions are migrated to the new emission token
+    ///
+    /// - topics - `["reserve_emission_migrated"]`
+    /// - data - `[res_token_id: u32, legacy_index: i128]`
+    ///
+    /// ### Arguments
+    /// * res_token_id - The reserve token ID
+    /// * legacy_index - The emission index of the legacy token at the migration
+    pub fn reserve_emission_migrated(e: &Env, res_token_id: u32, legacy_index: i128) {
+        let topics = (Symbol::new(e, "reserve_emission_migrated"),);
+        e.events().publish(topics, (res_token_id, legacy_index));
+    }
+
+    /// Emitted when the protocol fee taken from accrued interest is set
+    ///
+    /// - topics - `["set_protocol_fee", caller: Address]`
+    /// - data - `fee: ProtocolFee`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the treasurer role
+    /// * fee - The protocol fee
+    pub fn set_protocol_fee(e: &Env, caller: Address, fee: ProtocolFee) {
+        let topics = (Symbol::new(e, "set_protocol_fee"), caller);
+        e.events().publish(topics, fee);
+    }
+
+    /// Emitted when a protocol fee is queued
+    ///
+    /// - topics - `["queue_set_protocol_fee", caller: Address]`
+    /// - data - `queued: QueuedProtocolFee`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the treasurer role
+    /// * queued - The queued protocol fee
+    pub fn queue_set_protocol_fee(e: &Env, caller: Address, queued: QueuedProtocolFee) {
+        let topics = (Symbol::new(e, "queue_set_protocol_fee"), caller);
+        e.events().publish(topics, queued);
+    }
+
+    /// Emitted when a queued protocol fee is cancelled
+    ///
+    /// - topics - `["cancel_set_protocol_fee", caller: Address]`
+    /// - data - `fee: ProtocolFee`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the treasurer role
+    /// * fee - The cancelled protocol fee
+    pub fn cancel_set_protocol_fee(e: &Env, caller: Address, fee: ProtocolFee) {
+        let topics = (Symbol::new(e, "cancel_set_protocol_fee"), caller);
+        e.events().publish(topics, fee);
+    }
+
+    /// Emitted when the protocol fee is disabled
+    ///
+    /// - topics - `["disable_protocol_fee", caller: Address]`
+    /// - data - `()`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the treasurer role
+    pub fn disable_protocol_fee(e: &Env, caller: Address) {
+        let topics = (Symbol::new(e, "disable_protocol_fee"), caller);
+        e.events().publish(topics, ());
+    }
+
+    /// Emitted when a reserve's protocol fees are sent to the treasury
+    ///
+    /// - topics - `["claim_protocol_fees", asset: Address]`
+    /// - data - `[treasury: Address, amount: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset of the reserve
+    /// * treasury - The treasury receiving the fees
+    /// * amount - The amount of underlying sent
+    pub fn claim_protocol_fees(e: &Env, asset: Address, treasury: Address, amount: i128) {
+        let topics = (Symbol::new(e, "claim_protocol_fees"), asset);
+        e.events().publish(topics, (treasury, amount));
+    }
+
+    /// Emitted when the maximum number of requests a single submit can make is set
+    ///
+    /// - topics - `["set_action_limits", caller: Address]`
+    /// - data - `limits: ActionLimits`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * limits - The action limits
+    pub fn set_action_limits(e: &Env, caller: Address, limits: ActionLimits) {
+        let topics = (Symbol::new(e, "set_action_limits"), caller);
+        e.events().publish(topics, limits);
+    }
+
+    /// Emitted when the cooldown between a borrow and a collateral withdrawal is set
+    ///
+    /// - topics - `["set_withdraw_cooldown", caller: Address]`
+    /// - data - `ledgers: u32`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * ledgers - The number of ledgers
+    pub fn set_withdraw_cooldown(e: &Env, caller: Address, ledgers: u32) {
+        let topics = (Symbol::new(e, "set_withdraw_cooldown"), caller);
+        e.events().publish(topics, ledgers);
+    }
+
+    /// Emitted when the deferred liquidation start for retail accounts is set
+    ///
+    /// - topics - `["set_retail_liquidation", caller: Address]`
+    /// - data - `config: RetailLiquidation`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * config - The retail liquidation configuration
+    pub fn set_retail_liquidation(e: &Env, caller: Address, config: RetailLiquidation) {
+        let topics = (Symbol::new(e, "set_retail_liquidation"), caller);
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when the thresholds for each risk tier are set
+    ///
+    /// - topics - `["set_risk_tiers", caller: Address]`
+    /// - data - `config: RiskTierConfig`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * config - The risk tier thresholds
+    pub fn set_risk_tiers(e: &Env, caller: Address, config: RiskTierConfig) {
+        let topics = (Symbol::new(e, "set_risk_tiers"), caller);
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when the accounting of a reserve is checked against the tokens held by the pool
+    ///
+    /// - topics - `["verify_invariants", asset: Address]`
+    /// - data - `[passed: bool, delta: i128, held: i128, expected: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset of the reserve
+    /// * check - The result of the check
+    pub fn verify_invariants(e: &Env, asset: Address, check: InvariantCheck) {
+        let topics = (Symbol::new(e, "verify_invariants"), asset);
+        e.events().publish(
+            topics,
+            (check.passed, check.delta, check.held, check.expected),
+        );
+    }
+
+    /// Emitted when a reserve's share of the pool emissions is split between its suppliers and
+    /// borrowers
+    ///
+    /// - topics - `["set_emission_split", caller: Address, res_index: u32]`
+    /// - data - `supply_percent: u32`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * res_index - The index of the reserve
+    /// * supply_percent - The percent of the reserve's share paid to suppliers
+    pub fn set_emission_split(e: &Env, caller: Address, res_index: u32, supply_percent: u32) {
+        let topics = (Symbol::new(e, "set_emission_split"), caller, res_index);
+        e.events().publish(topics, supply_percent);
+    }
+
+    /// Emitted when the boost to the emissions paid to a reserve's suppliers is set
+    ///
+    /// - topics - `["set_emission_boost", caller: Address, asset: Address]`
+    /// - data - `[target_util: u32, ledgers: u32, max_boost: u32]`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * asset - The underlying asset of the reserve
+    /// * target_util - The utilization the boost starts above
+    /// * ledgers - The ledgers the utilization must stay above the target before it is boosted
+    /// * max_boost - The multiplier applied to the supplier share at 100% utilization, or 0 if
+    ///               disabled
+    pub fn set_emission_boost(
+        e: &Env,
+        caller: Address,
+        asset: Address,
+        target_util: u32,
+        ledgers: u32,
+        max_boost: u32,
+    ) {
+        let topics = (Symbol::new(e, "set_emission_boost"), caller, asset);
+        e.events()
+            .publish(topics, (target_util, ledgers, max_boost));
+    }
+
+    /// Emitted when the utilization smoothing window of a reserve is set
+    ///
+    /// - topics - `["set_util_smoothing", caller: Address, asset: Address]`
+    /// - data - `window: u64`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the risk role
+    /// * asset - The underlying asset of the reserve
+    /// * window - The time in seconds the utilization is averaged over, or 0 if disabled
+    pub fn set_util_smoothing(e: &Env, caller: Address, asset: Address, window: u64) {
+        let topics = (Symbol::new(e, "set_util_smoothing"), caller, asset);
+        e.events().publish(topics, window);
+    }
+
+    /// Emitted when the pool's status guardian is set
+    ///
+    /// - topics - `["set_status_guardian", admin: Address]`
+    /// - data - `guardian: Option<Address>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * guardian - The guardian that can restrict the pool, or None if removed
+    pub fn set_status_guardian(e: &Env, admin: Address, guardian: Option<Address>) {
+        let topics = (Symbol::new(e, "set_status_guardian"), admin);
+        e.events().publish(topics, guardian);
+    }
+
+    /// Emitted when the pool is restricted by a pauser
+    ///
+    /// - topics - `["set_status_restricted", caller: Address]`
+    /// - data - `pool_status: u32`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the pauser role
+    /// * pool_status - The pool status after the restriction
+    pub fn set_status_restricted(e: &Env, caller: Address, pool_status: u32) {
+        let topics = (Symbol::new(e, "set_status_restricted"), caller);
+        e.events().publish(topics, pool_status);
+    }
+
+    /// Emitted when a role is granted
+    ///
+    /// - topics - `["grant_role", admin: Address, member: Address]`
+    /// - data - `role: Role`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * role - The role granted
+    /// * member - The address granted the role
+    pub fn grant_role(e: &Env, admin: Address, role: Role, member: Address) {
+        let topics = (Symbol::new(e, "grant_role"), admin, member);
+        e.events().publish(topics, role);
+    }
+
+    /// Emitted when a role is revoked
+    ///
+    /// - topics - `["revoke_role", admin: Address, member: Address]`
+    /// - data - `role: Role`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * role - The role revoked
+    /// * member - The address the role was revoked from
+    pub fn revoke_role(e: &Env, admin: Address, role: Role, member: Address) {
+        let topics = (Symbol::new(e, "revoke_role"), admin, member);
+        e.events().publish(topics, role);
+    }
+
+    /// Emitted when the pool's metadata is set
+    ///
+    /// - topics - `["set_metadata", admin: Address]`
+    /// - data - `metadata: PoolMetadata`
+    ///
+    /// ### Arguments
+    /// * admin - The admin of the pool
+    /// * metadata - The metadata of the pool
+    pub fn set_metadata(e: &Env, admin: Address, metadata: PoolMetadata) {
+        let topics = (Symbol::new(e, "set_metadata"), admin);
+        e.events().publish(topics, metadata);
+    }
+
+    /// Emitted when the pool's base asset is set
+    ///
+    /// - topics - `["set_base_asset", admin: Address]`
+    /// - data - `base: Asset`
+    ///
+    /// ### Arguments
+    /// * admin - The admin of the pool
+    /// * base - The asset the pool's values are denominated in
+    pub fn set_base_asset(e: &Env, admin: Address, base: Asset) {
+        let topics = (Symbol::new(e, "set_base_asset"), admin);
+        e.events().publish(topics, base);
+    }
+
+    /// Emitted when the migration of a reserve to a new underlying token is queued
+    ///
+    /// - topics - `["queue_reserve_migration", admin: Address]`
+    /// - data - `migration: QueuedReserveMigration`
+    ///
+    /// ### Arguments
+    /// * admin - The admin of the pool
+    /// * migration - The queued reserve migration
+    pub fn queue_reserve_migration(e: &Env, admin: Address, migration: QueuedReserveMigration) {
+        let topics = (Symbol::new(e, "queue_reserve_migration"), admin);
+        e.events().publish(topics, migration);
+    }
+
+    /// Emitted when a queued reserve migration is cancelled
+    ///
+    /// - topics - `["cancel_reserve_migration", admin: Address]`
+    /// - data - `migration: ReserveMigration`
+    ///
+    /// ### Arguments
+    /// * admin - The admin of the pool
+    /// * migration - The cancelled reserve migration
+    pub fn cancel_reserve_migration(e: &Env, admin: Address, migration: ReserveMigration) {
+        let topics = (Symbol::new(e, "cancel_reserve_migration"), admin);
+        e.events().publish(topics, migration);
+    }
+
+    /// Emitted when a reserve is migrated to a new underlying token
+    ///
+    /// - topics - `["migrate_reserve", admin: Address]`
+    /// - data - `[migration: ReserveMigration, old_amount: i128, new_amount: i128]`
+    ///
+    /// ### Arguments
+    /// * admin - The admin of the pool
+    /// * migration - The reserve migration
+    /// * old_amount - The amount of the old token sent to the admin
+    /// * new_amount - The amount of the new token received from the admin
+    pub fn migrate_reserve(
+        e: &Env,
+        admin: Address,
+        migration: ReserveMigration,
+        old_amount: i128,
+        new_amount: i128,
+    ) {
+        let topics = (Symbol::new(e, "migrate_reserve"), admin);
+        e.events()
+            .publish(topics, (migration, old_amount, new_amount));
+    }
+
+    /// Emitted when a reserve is first seen without a valid oracle price
+    ///
+    /// - topics - `["reserve_unpriced", asset: Address]`
+    /// - data - `since: u32`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset of the reserve
+    /// * since - The ledger the reserve was first seen unpriced
+    pub fn reserve_unpriced(e: &Env, asset: Address, since: u32) {
+        let topics = (Symbol::new(e, "reserve_unpriced"), asset);
+        e.events().publish(topics, since);
+    }
+
+    /// Emitted when a reserve is disabled after being without a valid oracle price for
+    /// `UNPRICED_SUNSET_LEDGERS`
+    ///
+    /// - topics - `["sunset_reserve", asset: Address]`
+    /// - data - `since: u32`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset of the reserve
+    /// * since - The ledger the reserve was first seen unpriced
+    pub fn sunset_reserve(e: &Env, asset: Address, since: u32) {
+        let topics = (Symbol::new(e, "sunset_reserve"), asset);
+        e.events().publish(topics, since);
+    }
+
+    /// Emitted when a split of interest auction proceeds is queued
+    ///
+    /// - topics - `["queue_set_interest_split", caller: Address]`
+    /// - data - `queued: QueuedInterestSplit`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the treasurer role
+    /// * queued - The queued interest split
+    pub fn queue_set_interest_split(e: &Env, caller: Address, queued: QueuedInterestSplit) {
+        let topics = (Symbol::new(e, "queue_set_interest_split"), caller);
+        e.events().publish(topics, queued);
+    }
+
+    /// Emitted when a queued split of interest auction proceeds is cancelled
+    ///
+    /// - topics - `["cancel_set_interest_split", caller: Address]`
+    /// - data - `split: InterestSplit`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the treasurer role
+    /// * split - The cancelled interest split
+    pub fn cancel_set_interest_split(e: &Env, caller: Address, split: InterestSplit) {
+        let topics = (Symbol::new(e, "cancel_set_interest_split"), caller);
+        e.events().publish(topics, split);
+    }
+
+    /// Emitted when the split of interest auction proceeds routed to the treasury is set
+    ///
+    /// - topics - `["set_interest_split", caller: Address]`
+    /// - data - `split: InterestSplit`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the treasurer role
+    /// * split - The new interest split
+    pub fn set_interest_split(e: &Env, caller: Address, split: InterestSplit) {
+        let topics = (Symbol::new(e, "set_interest_split"), caller);
+        e.events().publish(topics, split);
+    }
+
+    /// Emitted when the interest split is removed, so all proceeds go to the backstop
+    ///
+    /// - topics - `["disable_interest_split", caller: Address]`
+    /// - data - `()`
+    ///
+    /// ### Arguments
+    /// * caller - The address holding the treasurer role
+    pub fn disable_interest_split(e: &Env, caller: Address) {
+        let topics = (Symbol::new(e, "disable_interest_split"), caller);
+        e.events().publish(topics, ());
+    }
+
+    /// Emitted when the proceeds of an interest auction are paid out. Emitted once for the
+    /// backstop's share and once for the treasury's share, if any.
+    ///
+    /// - topics - `["interest_proceeds", recipient: Address]`
+    /// - data - `[filler: Address, amount: i128]`
+    ///
+    /// ### Arguments
+    /// * recipient - The backstop or treasury receiving the proceeds
+    /// * filler - The address that filled the interest auction
+    /// * amount - The amount of backstop tokens received
+    pub fn interest_proceeds(e: &Env, recipient: Address, filler: Address, amount: i128) {
+        let topics = (Symbol::new(e, "interest_proceeds"), recipient);
+        e.events().publish(topics, (filler, amount));
+    }
+
     /// Emitted when a new auction is created
     ///
     /// - topics - `["new_auction", auction_type: u32, user: Address]`
-    /// - data - `[percent: u32, auction_data: AuctionData]`
+    /// - data - `[percent: u32, auction_data: AuctionData, hf_before: i128, hf_after: i128]`
     ///
     /// ### Arguments
     /// * auction_type - The type of auction
     /// * user - The auction user
     /// * percent - The percent of assets auctioned off
     /// * auction_data - The auction data
+    /// * hf_before - The user's health factor when the auction was created (0 for backstop auctions)
+    /// * hf_after - The user's estimated health factor if the auction is filled in full (0 for
+    ///   backstop auctions)
     pub fn new_auction(
         e: &Env,
         auction_type: u32,
         user: Address,
         percent: u32,
         auction_data: AuctionData,
+        hf_before: i128,
+        hf_after: i128,
     ) {
         let topics = (Symbol::new(e, "new_auction"), auction_type, user);
-        e.events().publish(topics, (percent, auction_data));
+        e.events()
+            .publish(topics, (percent, auction_data, hf_before, hf_after));
     }
 
     /// Emitted when an auction is filled
     ///
     /// - topics - `["fill_auction", auction_type: u32, user: Address]`
-    /// - data - `[filler: Address, fill_percent: i128, filled_auction_data: AuctionData]`
+    /// - data - `[filler: Address, fill_percent: i128, filled_auction_data: AuctionData, hf_before: i128, hf_after: i128]`
     ///
     /// ### Arguments
     /// * auction_type - The type of auction
@@ -348,6 +1364,9 @@ impl PoolEvents {
     /// * filler - The address of the filler
     /// * fill_percent - The percentage of the auction filled
     /// * filled_auction_data - The filled auction data
+    /// * hf_before - The user's health factor before the fill (0 for backstop auctions)
+    /// * hf_after - The user's health factor after the fill (0 for backstop auctions)
+    #[allow(clippy::too_many_arguments)]
     pub fn fill_auction(
         e: &Env,
         auction_type: u32,
@@ -355,10 +1374,49 @@ impl PoolEvents {
         filler: Address,
         fill_percent: i128,
         filled_auction_data: AuctionData,
+        hf_before: i128,
+        hf_after: i128,
     ) {
         let topics = (Symbol::new(e, "fill_auction"), auction_type, user);
+        e.events().publish(
+            topics,
+            (
+                filler,
+                fill_percent,
+                filled_auction_data,
+                hf_before,
+                hf_after,
+            ),
+        );
+    }
+
+    /// Emitted when an auction is filled by an underlying denominated fill request. Emitted
+    /// alongside `fill_auction`.
+    ///
+    /// - topics - `["fill_auction_underlying", auction_type: u32, user: Address]`
+    /// - data - `[filler: Address, fill_percent: i128, filled_underlying: AuctionData]`
+    ///
+    /// ### Arguments
+    /// * auction_type - The type of auction
+    /// * user - The auction user
+    /// * filler - The address of the filler
+    /// * fill_percent - The percentage of the auction filled
+    /// * filled_underlying - The filled auction data in underlying tokens
+    pub fn fill_auction_underlying(
+        e: &Env,
+        auction_type: u32,
+        user: Address,
+        filler: Address,
+        fill_percent: i128,
+        filled_underlying: AuctionData,
+    ) {
+        let topics = (
+            Symbol::new(e, "fill_auction_underlying"),
+            auction_type,
+            user,
+        );
         e.events()
-            .publish(topics, (filler, fill_percent, filled_auction_data));
+            .publish(topics, (filler, fill_percent, filled_underlying));
     }
 
     /// Emitted when an auction is deleted
@@ -373,4 +1431,50 @@ impl PoolEvents {
         let topics = (Symbol::new(&e, "delete_auction"), auction_type, user);
         e.events().publish(topics, ());
     }
+
+    /// Emitted when a submit fails because the spender has not approved the pool to transfer
+    /// enough tokens. The submit reverts, so this is only seen in the failed transaction's
+    /// diagnostic events.
+    ///
+    /// - topics - `["allowance_insufficient", token: Address, spender: Address]`
+    /// - data - `[allowance: i128, amount_needed: i128]`
+    ///
+    /// ### Arguments
+    /// * token - The token the pool needed to transfer
+    /// * spender - The address the tokens are transferred from
+    /// * allowance - The spender's allowance for the pool, zero if it expired
+    /// * amount_needed - The amount of tokens the pool needed to transfer
+    pub fn allowance_insufficient(
+        e: &Env,
+        token: Address,
+        spender: Address,
+        allowance: i128,
+        amount_needed: i128,
+    ) {
+        let topics = (Symbol::new(e, "allowance_insufficient"), token, spender);
+        e.events().publish(topics, (allowance, amount_needed));
+    }
+
+    /// Emitted when a submit fails because the user's collateral is below the pool's minimum. The
+    /// submit reverts, so this is only seen in the failed transaction's diagnostic events.
+    ///
+    /// - topics - `["min_collateral_not_met", user: Address]`
+    /// - data - `[collateral: i128, min_collateral: i128, shortfall: i128]`
+    ///
+    /// ### Arguments
+    /// * user - The address of the user
+    /// * collateral - The user's collateral, in the oracle's base asset
+    /// * min_collateral - The pool's minimum collateral, in the oracle's base asset
+    /// * shortfall - The additional collateral required, in the oracle's base asset
+    pub fn min_collateral_not_met(
+        e: &Env,
+        user: Address,
+        collateral: i128,
+        min_collateral: i128,
+        shortfall: i128,
+    ) {
+        let topics = (Symbol::new(e, "min_collateral_not_met"), user);
+        e.events()
+            .publish(topics, (collateral, min_collateral, shortfall));
+    }
 }
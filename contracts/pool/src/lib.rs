@@ -15,15 +15,23 @@ mod errors;
 mod events;
 mod pool;
 mod storage;
-mod testutils;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 mod validator;
 
-pub use auctions::{AuctionData, AuctionType};
+pub use auctions::{AuctionData, AuctionType, InterestAuctionPreview};
 pub use contract::*;
-pub use emissions::ReserveEmissionMetadata;
+pub use emissions::{ClaimedEmissions, ReserveEmissionMetadata};
 pub use errors::PoolError;
-pub use pool::{FlashLoan, Positions, Request, RequestType};
+pub use pool::{
+    FlashLoan, InvariantCheck, Positions, Request, RequestType, ReserveRiskParams, RiskParams,
+    StateSnapshot, SubInvocation, UserStateSnapshot,
+};
 pub use storage::{
-    AuctionKey, PoolConfig, PoolDataKey, PoolEmissionConfig, ReserveConfig, ReserveData,
-    ReserveEmissionData, UserEmissionData, UserReserveKey,
+    ActionLimits, AuctionKey, BadDebt, BootstrapEmissions, Coverage, EmissionBoost,
+    EmissionMigration, InterestSplit, PoolConfig, PoolDataKey, PoolEmissionConfig, PoolMetadata,
+    ProtocolFee, QueuedInterestSplit, QueuedProtocolFee, QueuedReserveMigration, RateCheckpoint,
+    RequestHook, ReserveConfig, ReserveData, ReserveEmissionData, ReserveMigration,
+    ReserveProposal, ReserveRamp, ReserveStrategy, RetailLiquidation, RiskTierConfig, ShutdownData,
+    SoftLiquidationConfig, UserEmissionData, UserReserveKey, UtilDay, UtilSmoothing,
 };
@@ -0,0 +1,89 @@
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::errors::PoolError;
+
+/// Lock an account, or request that the lock lifts after `timelock`. Locking cancels any pending
+/// unlock, and requesting an unlock while one is pending does not restart the timelock.
+///
+/// Returns the time the lock lifts, or `u64::MAX` if no unlock is requested
+///
+/// ### Arguments
+/// * `unlock_time` - The time the account's current lock lifts, if the account is locked
+/// * `locked` - True to lock the account, false to request the lock be lifted
+/// * `timelock` - The time between requesting an unlock and the lock lifting
+///
+/// ### Panics
+/// If `locked` is false and the account is not locked
+pub(crate) fn update_account_lock(
+    e: &Env,
+    unlock_time: Option<u64>,
+    locked: bool,
+    timelock: u64,
+) -> u64 {
+    if locked {
+        return u64::MAX;
+    }
+    match unlock_time {
+        Some(u64::MAX) => e.ledger().timestamp() + timelock,
+        Some(unlock_time) if unlock_time > e.ledger().timestamp() => unlock_time,
+        _ => panic_with_error!(e, PoolError::BadRequest),
+    }
+}
+
+/// Check if an account's lock is still in effect
+///
+/// ### Arguments
+/// * `unlock_time` - The time the account's lock lifts, if the account is locked
+pub(crate) fn is_account_locked(e: &Env, unlock_time: Option<u64>) -> bool {
+    match unlock_time {
+        Some(unlock_time) => e.ledger().timestamp() < unlock_time,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Ledger;
+
+    #[test]
+    fn test_update_account_lock() {
+        let e = Env::default();
+        e.ledger().set_timestamp(1000);
+
+        assert_eq!(update_account_lock(&e, None, true, 100), u64::MAX);
+        assert_eq!(update_account_lock(&e, Some(1050), true, 100), u64::MAX);
+        assert_eq!(update_account_lock(&e, Some(u64::MAX), false, 100), 1100);
+        // requesting again does not restart the timelock
+        assert_eq!(update_account_lock(&e, Some(1050), false, 100), 1050);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_update_account_lock_not_locked() {
+        let e = Env::default();
+        e.ledger().set_timestamp(1000);
+
+        update_account_lock(&e, None, false, 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_update_account_lock_already_unlocked() {
+        let e = Env::default();
+        e.ledger().set_timestamp(1000);
+
+        update_account_lock(&e, Some(1000), false, 100);
+    }
+
+    #[test]
+    fn test_is_account_locked() {
+        let e = Env::default();
+        e.ledger().set_timestamp(1000);
+
+        assert!(!is_account_locked(&e, None));
+        assert!(is_account_locked(&e, Some(u64::MAX)));
+        assert!(is_account_locked(&e, Some(1001)));
+        assert!(!is_account_locked(&e, Some(1000)));
+    }
+}
@@ -0,0 +1,163 @@
+use soroban_sdk::{panic_with_error, Env, Vec};
+
+use crate::{
+    errors::PoolError,
+    storage::{self, ActionLimits},
+};
+
+use super::{Request, RequestType};
+
+/// Set the maximum number of requests a single submit can make
+///
+/// ### Arguments
+/// * `limits` - The action limits
+///
+/// ### Panics
+/// If either limit is zero, or the auction limit is over the request limit
+pub fn execute_set_action_limits(e: &Env, limits: &ActionLimits) {
+    if limits.max_requests == 0
+        || limits.max_auction_requests == 0
+        || limits.max_auction_requests > limits.max_requests
+    {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_action_limits(e, limits);
+}
+
+/// Require that a submit's requests are within the pool's action limits. This is checked before
+/// any request is applied, so an oversized submit fails up front instead of running out of budget
+/// partway through.
+///
+/// ### Arguments
+/// * `requests` - The requests of the submit
+///
+/// ### Panics
+/// * `TooManyRequests` if there are more requests than `max_requests`
+/// * `TooManyAuctionRequests` if there are more auction fills or deletes than
+///   `max_auction_requests`
+pub fn require_within_action_limits(e: &Env, requests: &Vec<Request>) {
+    let limits = storage::get_action_limits(e);
+    if requests.len() > limits.max_requests {
+        panic_with_error!(e, PoolError::TooManyRequests);
+    }
+    let auction_requests = requests
+        .iter()
+        .filter(|request| {
+            request.request_type >= RequestType::FillUserLiquidationAuction as u32
+                && request.request_type <= RequestType::FillInterestAuctionUnderlying as u32
+        })
+        .count() as u32;
+    if auction_requests > limits.max_auction_requests {
+        panic_with_error!(e, PoolError::TooManyAuctionRequests);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::{DEFAULT_MAX_AUCTION_REQUESTS, DEFAULT_MAX_REQUESTS},
+        testutils,
+    };
+    use soroban_sdk::{testutils::Address as _, vec, Address};
+
+    fn request(e: &Env, request_type: RequestType) -> Request {
+        Request {
+            request_type: request_type as u32,
+            address: Address::generate(e),
+            amount: 1_0000000,
+        }
+    }
+
+    #[test]
+    fn test_action_limits_default() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            let limits = storage::get_action_limits(&e);
+            assert_eq!(limits.max_requests, DEFAULT_MAX_REQUESTS);
+            assert_eq!(limits.max_auction_requests, DEFAULT_MAX_AUCTION_REQUESTS);
+
+            let mut requests = vec![&e];
+            for _ in 0..DEFAULT_MAX_REQUESTS {
+                requests.push_back(request(&e, RequestType::Supply));
+            }
+            require_within_action_limits(&e, &requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1234)")]
+    fn test_require_within_action_limits_too_many_requests() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_action_limits(
+                &e,
+                &ActionLimits {
+                    max_requests: 3,
+                    max_auction_requests: 1,
+                },
+            );
+            let requests = vec![
+                &e,
+                request(&e, RequestType::Supply),
+                request(&e, RequestType::SupplyCollateral),
+                request(&e, RequestType::Borrow),
+                request(&e, RequestType::Repay),
+            ];
+            require_within_action_limits(&e, &requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1235)")]
+    fn test_require_within_action_limits_too_many_auction_requests() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_action_limits(
+                &e,
+                &ActionLimits {
+                    max_requests: 4,
+                    max_auction_requests: 2,
+                },
+            );
+            let requests = vec![
+                &e,
+                request(&e, RequestType::FillUserLiquidationAuction),
+                request(&e, RequestType::Repay),
+                request(&e, RequestType::DeleteLiquidationAuction),
+            ];
+            require_within_action_limits(&e, &requests);
+
+            let requests = vec![
+                &e,
+                request(&e, RequestType::FillUserLiquidationAuction),
+                request(&e, RequestType::FillBadDebtAuctionUnderlying),
+                request(&e, RequestType::FillInterestAuction),
+            ];
+            require_within_action_limits(&e, &requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_action_limits_auction_over_requests() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_action_limits(
+                &e,
+                &ActionLimits {
+                    max_requests: 2,
+                    max_auction_requests: 3,
+                },
+            );
+        });
+    }
+}
@@ -6,7 +6,10 @@ use crate::AuctionType;
 use crate::{auctions, errors::PoolError, validator::require_nonnegative};
 
 use super::pool::Pool;
-use super::User;
+use super::{
+    accrue_coverage, load_request_hooks, record_borrow, record_borrow_ledger, record_repay,
+    require_borrow_not_frozen, require_not_supply_only, run_request_hooks, User,
+};
 
 /// A request a user makes against the pool
 #[derive(Clone)]
@@ -31,6 +34,9 @@ pub enum RequestType {
     FillBadDebtAuction = 7,
     FillInterestAuction = 8,
     DeleteLiquidationAuction = 9,
+    FillUserLiquidationAuctionUnderlying = 10,
+    FillBadDebtAuctionUnderlying = 11,
+    FillInterestAuctionUnderlying = 12,
 }
 
 impl RequestType {
@@ -50,6 +56,9 @@ impl RequestType {
             7 => RequestType::FillBadDebtAuction,
             8 => RequestType::FillInterestAuction,
             9 => RequestType::DeleteLiquidationAuction,
+            10 => RequestType::FillUserLiquidationAuctionUnderlying,
+            11 => RequestType::FillBadDebtAuctionUnderlying,
+            12 => RequestType::FillInterestAuctionUnderlying,
             _ => panic_with_error!(e, PoolError::BadRequest),
         }
     }
@@ -68,6 +77,7 @@ pub struct Actions {
     pub pool_transfer: Map<Address, i128>,
     pub check_health: bool,
     pub check_max_util: Vec<Address>,
+    pub check_withdraw_cooldown: bool,
 }
 
 impl Actions {
@@ -78,6 +88,7 @@ impl Actions {
             pool_transfer: Map::new(e),
             check_health: false,
             check_max_util: Vec::new(e),
+            check_withdraw_cooldown: false,
         }
     }
 
@@ -113,12 +124,14 @@ impl Actions {
 }
 
 /// Build a set of pool actions and the new positions from the supplied requests. Validates that the requests
-/// are valid based on the status and supported reserves in the pool.
+/// are valid based on the status and supported reserves in the pool, and runs the pool's request hooks against
+/// each request before it is applied.
 ///
 /// ### Arguments
 /// * pool - The pool
 /// * from - The sender of the requests
 /// * requests - The requests to be processed
+/// * use_allowance - If tokens the sender pays to fill auctions are moved with `transfer_from`
 ///
 /// ### Returns
 /// A tuple of (actions, positions, check_health) where:
@@ -133,12 +146,13 @@ pub fn build_actions_from_request(
     pool: &mut Pool,
     from_state: &mut User,
     requests: Vec<Request>,
+    use_allowance: bool,
 ) -> Actions {
     let mut actions = Actions::new(e);
+    let hooks = load_request_hooks(e);
     for request in requests.iter() {
         // verify the request is allowed
-        require_nonnegative(e, &request.amount);
-        pool.require_action_allowed(e, request.request_type);
+        run_request_hooks(e, &hooks, pool, from_state, &request);
         match RequestType::from_u32(e, request.request_type) {
             RequestType::Supply => {
                 let b_tokens_minted = apply_supply(e, &mut actions, pool, from_state, &request);
@@ -205,6 +219,8 @@ pub fn build_actions_from_request(
                 );
             }
             RequestType::FillUserLiquidationAuction => {
+                require_not_supply_only(e, &from_state.address);
+                let hf_before = auctions::user_health(e, pool, 0, &request.address);
                 let filled_auction = auctions::fill(
                     e,
                     pool,
@@ -212,8 +228,10 @@ pub fn build_actions_from_request(
                     &request.address,
                     from_state,
                     request.amount as u64,
+                    use_allowance,
                 );
                 actions.do_check_health();
+                let hf_after = auctions::user_health(e, pool, 0, &request.address);
 
                 PoolEvents::fill_auction(
                     e,
@@ -222,10 +240,13 @@ pub fn build_actions_from_request(
                     from_state.address.clone(),
                     request.amount,
                     filled_auction,
+                    hf_before,
+                    hf_after,
                 );
             }
             RequestType::FillBadDebtAuction => {
                 // Note: will fail if input address is not the backstop since there cannot be a bad debt auction for a different address in storage
+                require_not_supply_only(e, &from_state.address);
                 let filled_auction = auctions::fill(
                     e,
                     pool,
@@ -233,6 +254,7 @@ pub fn build_actions_from_request(
                     &request.address,
                     from_state,
                     request.amount as u64,
+                    use_allowance,
                 );
                 actions.do_check_health();
 
@@ -243,6 +265,8 @@ pub fn build_actions_from_request(
                     from_state.address.clone(),
                     request.amount,
                     filled_auction,
+                    0,
+                    0,
                 );
             }
             RequestType::FillInterestAuction => {
@@ -254,7 +278,15 @@ pub fn build_actions_from_request(
                     &request.address,
                     from_state,
                     request.amount as u64,
+                    use_allowance,
                 );
+                if auctions::requires_filler_health_check(
+                    e,
+                    AuctionType::InterestAuction as u32,
+                    &from_state.address,
+                ) {
+                    actions.do_check_health();
+                }
                 PoolEvents::fill_auction(
                     e,
                     2u32,
@@ -262,6 +294,51 @@ pub fn build_actions_from_request(
                     from_state.address.clone(),
                     request.amount,
                     filled_auction,
+                    0,
+                    0,
+                );
+            }
+            RequestType::FillUserLiquidationAuctionUnderlying
+            | RequestType::FillBadDebtAuctionUnderlying
+            | RequestType::FillInterestAuctionUnderlying => {
+                // Note: request amount is the maximum amount of underlying tokens of the bid asset to spend
+                let auction_type = request.request_type - 10;
+                if auction_type != AuctionType::InterestAuction as u32 {
+                    require_not_supply_only(e, &from_state.address);
+                }
+                let hf_before = auctions::user_health(e, pool, auction_type, &request.address);
+                let (percent_filled, filled_auction, filled_auction_underlying) =
+                    auctions::fill_underlying(
+                        e,
+                        pool,
+                        auction_type,
+                        &request.address,
+                        from_state,
+                        request.amount,
+                        use_allowance,
+                    );
+                if auctions::requires_filler_health_check(e, auction_type, &from_state.address) {
+                    actions.do_check_health();
+                }
+                let hf_after = auctions::user_health(e, pool, auction_type, &request.address);
+
+                PoolEvents::fill_auction(
+                    e,
+                    auction_type,
+                    request.address.clone(),
+                    from_state.address.clone(),
+                    percent_filled as i128,
+                    filled_auction,
+                    hf_before,
+                    hf_after,
+                );
+                PoolEvents::fill_auction_underlying(
+                    e,
+                    auction_type,
+                    request.address.clone(),
+                    from_state.address.clone(),
+                    percent_filled as i128,
+                    filled_auction_underlying,
                 );
             }
             RequestType::DeleteLiquidationAuction => {
@@ -317,6 +394,7 @@ fn apply_withdraw(
     request: &Request,
 ) -> (i128, i128) {
     let mut reserve = pool.load_reserve(e, &request.address, true);
+    accrue_coverage(e, user, &mut reserve);
     let cur_b_tokens = user.get_supply(reserve.config.index);
     let mut to_burn = reserve.to_b_token_up(e, request.amount);
     let mut tokens_out = request.amount;
@@ -368,6 +446,7 @@ fn apply_withdraw_collateral(
     request: &Request,
 ) -> (i128, i128) {
     let mut reserve = pool.load_reserve(e, &request.address, true);
+    accrue_coverage(e, user, &mut reserve);
     let cur_b_tokens = user.get_collateral(reserve.config.index);
     let mut to_burn = reserve.to_b_token_up(e, request.amount);
     let mut tokens_out = request.amount;
@@ -379,6 +458,7 @@ fn apply_withdraw_collateral(
     reserve.require_utilization_below_100(e);
     actions.add_for_pool_transfer(&reserve.asset, tokens_out);
     actions.do_check_health();
+    actions.check_withdraw_cooldown = true;
     pool.cache_reserve(reserve);
     (tokens_out, to_burn)
 }
@@ -395,9 +475,20 @@ fn apply_borrow(
     user: &mut User,
     request: &Request,
 ) -> i128 {
+    require_borrow_not_frozen(e, &user.address);
+    require_not_supply_only(e, &user.address);
+    record_borrow_ledger(e, &user.address);
     let mut reserve = pool.load_reserve(e, &request.address, true);
     reserve.require_action_allowed(e, request.request_type);
     let d_tokens_minted = reserve.to_d_token_up(e, request.amount);
+    record_borrow(
+        e,
+        &user.address,
+        reserve.config.index,
+        reserve.data.d_rate,
+        user.get_liabilities(reserve.config.index),
+        request.amount,
+    );
     user.add_liabilities(e, &mut reserve, d_tokens_minted);
     reserve.require_utilization_below_100(e);
     actions.do_check_max_util(&reserve.asset);
@@ -423,6 +514,14 @@ fn apply_repay(
     let cur_d_tokens = user.get_liabilities(reserve.config.index);
     let d_tokens_burnt = reserve.to_d_token_down(e, request.amount);
     let repayment_amount = request.amount;
+    record_repay(
+        e,
+        &user.address,
+        reserve.config.index,
+        reserve.data.d_rate,
+        cur_d_tokens,
+        d_tokens_burnt,
+    );
     if d_tokens_burnt > cur_d_tokens {
         let cur_underlying_borrowed = reserve.to_asset_from_d_token(e, cur_d_tokens);
         let amount_to_refund = request.amount - cur_underlying_borrowed;
@@ -503,7 +602,7 @@ mod tests {
             ];
 
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, false);
 
@@ -583,7 +682,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, false);
             assert_eq!(actions.check_max_util.len(), 0);
@@ -658,7 +757,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, false);
             assert_eq!(actions.check_max_util.len(), 0);
@@ -735,7 +834,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut user, requests, false);
         });
     }
 
@@ -785,7 +884,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, false);
 
@@ -864,7 +963,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, true);
             assert_eq!(actions.check_max_util.len(), 0);
@@ -939,7 +1038,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, true);
             assert_eq!(actions.check_max_util.len(), 0);
@@ -1016,7 +1115,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut user, requests, false);
         });
     }
 
@@ -1065,7 +1164,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, true);
             assert_eq!(actions.check_max_util, vec![&e, underlying.clone()]);
@@ -1087,6 +1186,104 @@ mod tests {
         });
     }
 
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1232)")]
+    fn test_build_actions_from_request_borrow_frozen() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_borrow_freeze(&e, &samwise, &Some(u64::MAX));
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying.clone(),
+                    amount: 10_1234567,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            build_actions_from_request(&e, &mut pool, &mut user, requests, false);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1233)")]
+    fn test_build_actions_from_request_borrow_supply_only() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_supply_only(&e, &samwise, &Some(u64::MAX));
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying.clone(),
+                    amount: 10_1234567,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            build_actions_from_request(&e, &mut pool, &mut user, requests, false);
+        });
+    }
+
     #[test]
     fn test_build_actions_from_request_borrow_adds_check_util_safely() {
         let e = Env::default();
@@ -1145,7 +1342,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, true);
             assert_eq!(
@@ -1230,7 +1427,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut user, requests, false);
         });
     }
 
@@ -1286,7 +1483,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, false);
 
@@ -1364,7 +1561,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, false);
 
@@ -1466,7 +1663,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, true);
 
@@ -1607,7 +1804,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &frodo);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, true);
             let exp_new_auction = AuctionData {
@@ -1738,7 +1935,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &frodo);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, true);
             assert_eq!(
@@ -1878,7 +2075,7 @@ mod tests {
             ];
             let pre_fill_backstop_token_balance = backstop_token_client.balance(&backstop_address);
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(backstop_token_client.balance(&samwise), 25_0000000);
             assert_eq!(
@@ -1887,7 +2084,8 @@ mod tests {
             );
             assert_eq!(underlying_0_client.balance(&samwise), 100_0000000);
             assert_eq!(underlying_1_client.balance(&samwise), 25_0000000);
-            assert_eq!(actions.check_health, false);
+            // generated addresses are contracts, so the filler's health is checked
+            assert_eq!(actions.check_health, true);
             assert_eq!(
                 storage::has_auction(
                     &e,
@@ -1964,7 +2162,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests, false);
 
             assert_eq!(actions.check_health, true);
             assert_eq!(
@@ -2015,7 +2213,7 @@ mod tests {
             let mut pool = Pool::load(&e);
 
             let mut user = User::load(&e, &samwise);
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut user, requests, false);
         });
     }
 
@@ -2056,7 +2254,7 @@ mod tests {
             let mut pool = Pool::load(&e);
             let mut user = User::load(&e, &samwise);
 
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut user, requests, false);
         });
     }
 
@@ -2097,7 +2295,7 @@ mod tests {
             let mut pool = Pool::load(&e);
             let mut user = User::load(&e, &samwise);
 
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut user, requests, false);
         });
     }
 
@@ -2138,7 +2336,7 @@ mod tests {
             let mut pool = Pool::load(&e);
             let mut user = User::load(&e, &samwise);
 
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut user, requests, false);
         });
     }
 }
@@ -0,0 +1,130 @@
+use soroban_sdk::{contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec};
+
+use crate::validator::require_nonnegative;
+
+use super::{Request, RequestType};
+
+/// A contract invocation performed by the pool that requires authorization from a user
+#[derive(Clone)]
+#[contracttype]
+pub struct SubInvocation {
+    pub contract: Address,
+    pub fn_name: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// Build the sub-invocations `spender` must authorize for a `submit` of `requests`, so the
+/// authorization entries can be constructed without authorizing every invocation under the
+/// root `submit` call.
+///
+/// Tokens the pool sends are authorized by the pool itself, and `submit_with_allowance` moves
+/// tokens with `transfer_from`, so neither require a sub-invocation. Interest auction fills are
+/// not included, as the backstop tokens donated depend on the ledger the fill is included in.
+///
+/// ### Arguments
+/// * `spender` - The address sending tokens to the pool
+/// * `requests` - The requests to be submitted
+///
+/// ### Panics
+/// If a request has an invalid type or a negative amount
+pub fn build_required_auth(
+    e: &Env,
+    spender: &Address,
+    requests: &Vec<Request>,
+) -> Vec<SubInvocation> {
+    // map of token -> amount transferred from the spender
+    let mut spender_transfer: Map<Address, i128> = Map::new(e);
+    for request in requests.iter() {
+        require_nonnegative(e, &request.amount);
+        match RequestType::from_u32(e, request.request_type) {
+            RequestType::Supply | RequestType::SupplyCollateral | RequestType::Repay => {
+                // repayments transfer the full amount and refund any excess from the pool
+                spender_transfer.set(
+                    request.address.clone(),
+                    request.amount + spender_transfer.get(request.address).unwrap_or(0),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    let mut invocations = Vec::new(e);
+    for (asset, amount) in spender_transfer.iter() {
+        invocations.push_back(SubInvocation {
+            contract: asset,
+            fn_name: Symbol::new(e, "transfer"),
+            args: (spender.clone(), e.current_contract_address(), amount).into_val(e),
+        });
+    }
+    invocations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils;
+    use soroban_sdk::{testutils::Address as _, vec};
+
+    #[test]
+    fn test_build_required_auth() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let asset_0 = Address::generate(&e);
+        let asset_1 = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::SupplyCollateral as u32,
+                address: asset_0.clone(),
+                amount: 10_0000000,
+            },
+            Request {
+                request_type: RequestType::Borrow as u32,
+                address: asset_1.clone(),
+                amount: 5_0000000,
+            },
+            Request {
+                request_type: RequestType::Repay as u32,
+                address: asset_0.clone(),
+                amount: 2_5000000,
+            },
+            Request {
+                request_type: RequestType::WithdrawCollateral as u32,
+                address: asset_1.clone(),
+                amount: 1_0000000,
+            },
+        ];
+        e.as_contract(&pool, || {
+            let invocations = build_required_auth(&e, &samwise, &requests);
+            assert_eq!(invocations.len(), 1);
+            let invocation = invocations.get_unchecked(0);
+            assert_eq!(invocation.contract, asset_0);
+            assert_eq!(invocation.fn_name, Symbol::new(&e, "transfer"));
+            let expected_args: Vec<Val> =
+                (samwise.clone(), pool.clone(), 12_5000000i128).into_val(&e);
+            assert_eq!(invocation.args, expected_args);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_build_required_auth_invalid_request_type() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: 13,
+                address: Address::generate(&e),
+                amount: 1_0000000,
+            },
+        ];
+        e.as_contract(&pool, || {
+            build_required_auth(&e, &samwise, &requests);
+        });
+    }
+}
@@ -1,8 +1,11 @@
+use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{panic_with_error, Address, Env};
 
 use crate::{dependencies::BackstopClient, events::PoolEvents, storage, AuctionType, PoolError};
 
-use super::{calc_pool_backstop_threshold, Pool, User};
+use super::{
+    calc_pool_backstop_threshold, notify_health_alert, record_borrow, record_repay, Pool, User,
+};
 
 /// Handles any bad debt that exists for "user"
 pub fn bad_debt(e: &Env, user: &Address) {
@@ -65,12 +68,30 @@ pub fn check_and_handle_user_bad_debt(
         for (reserve_index, liability_balance) in user_state.positions.liabilities.iter() {
             let asset = reserve_list.get_unchecked(reserve_index);
             let mut reserve = pool.load_reserve(e, &asset, true);
+            record_borrow(
+                e,
+                &backstop_address,
+                reserve_index,
+                reserve.data.d_rate,
+                backstop_state.get_liabilities(reserve_index),
+                reserve.to_asset_from_d_token(e, liability_balance),
+            );
+            record_repay(
+                e,
+                user,
+                reserve_index,
+                reserve.data.d_rate,
+                liability_balance,
+                liability_balance,
+            );
             backstop_state.add_liabilities(e, &mut reserve, liability_balance);
             user_state.remove_liabilities(e, &mut reserve, liability_balance);
             pool.cache_reserve(reserve);
+            record_assigned_bad_debt(e, &asset, liability_balance);
 
             PoolEvents::bad_debt(e, user.clone(), asset, liability_balance);
         }
+        notify_health_alert(e, user, "bad_debt");
         backstop_state.store(e);
         return true;
     }
@@ -113,8 +134,17 @@ pub fn check_and_handle_backstop_bad_debt(
             for (reserve_index, liability_balance) in backstop_state.positions.liabilities.iter() {
                 let res_asset_address = reserve_list.get_unchecked(reserve_index);
                 let mut reserve = pool.load_reserve(e, &res_asset_address, true);
+                record_repay(
+                    e,
+                    backstop_address,
+                    reserve_index,
+                    reserve.data.d_rate,
+                    liability_balance,
+                    liability_balance,
+                );
                 backstop_state.default_liabilities(e, &mut reserve, liability_balance);
                 pool.cache_reserve(reserve);
+                record_defaulted_bad_debt(e, &res_asset_address, liability_balance);
 
                 PoolEvents::defaulted_debt(e, res_asset_address, liability_balance);
             }
@@ -124,22 +154,66 @@ pub fn check_and_handle_backstop_bad_debt(
     return false;
 }
 
+/// Record bad debt assigned to the backstop
+///
+/// ### Arguments
+/// * asset - The underlying asset of the reserve
+/// * d_tokens - The dTokens of bad debt assigned
+fn record_assigned_bad_debt(e: &Env, asset: &Address, d_tokens: i128) {
+    let mut bad_debt = storage::get_bad_debt(e, asset);
+    bad_debt.assigned += d_tokens;
+    storage::set_bad_debt(e, asset, &bad_debt);
+}
+
+/// Record bad debt defaulted off the backstop onto the reserve's suppliers
+///
+/// ### Arguments
+/// * asset - The underlying asset of the reserve
+/// * d_tokens - The dTokens of bad debt defaulted
+fn record_defaulted_bad_debt(e: &Env, asset: &Address, d_tokens: i128) {
+    let mut bad_debt = storage::get_bad_debt(e, asset);
+    bad_debt.defaulted += d_tokens;
+    storage::set_bad_debt(e, asset, &bad_debt);
+}
+
+/// Fetch the value of the bad debt held by the backstop across all reserves of the pool, in the
+/// oracle's base asset with the oracle's decimals. Sunset reserves cannot be priced and are left
+/// out.
+pub fn load_total_bad_debt(e: &Env) -> i128 {
+    let mut pool = Pool::load(e);
+    let reserve_list = storage::get_res_list(e);
+    let backstop_positions = storage::get_user_positions(e, &storage::get_backstop(e));
+    let mut total = 0;
+    for (reserve_index, d_tokens) in backstop_positions.liabilities.iter() {
+        let asset = reserve_list.get_unchecked(reserve_index);
+        if storage::get_sunset(e, &asset) {
+            continue;
+        }
+        let reserve = pool.load_reserve(e, &asset, false);
+        let amount = reserve.to_asset_from_d_token(e, d_tokens);
+        let price = pool.load_price(e, &asset);
+        total += price.fixed_mul_floor(e, &amount, &reserve.scalar);
+    }
+    total
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         auctions::AuctionData,
-        storage::PoolConfig,
+        storage::{BadDebt, PoolConfig},
         testutils::{
             self, create_backstop, create_blnd_token, create_comet_lp_pool, create_pool,
             create_token_contract,
         },
         Positions,
     };
+    use sep_40_oracle::testutils::Asset;
     use soroban_sdk::{
         map,
         testutils::{Address as _, Ledger, LedgerInfo},
-        vec, Address,
+        vec, Address, Symbol,
     };
 
     /***** bad_debt *****/
@@ -787,6 +861,7 @@ mod tests {
             storage::set_pool_config(&e, &pool_config);
             storage::set_user_positions(&e, &samwise, &positions);
             storage::set_user_positions(&e, &backstop_address, &backstop_positions);
+            storage::set_user_principal(&e, &samwise, &1, &5_0000000);
 
             let mut pool = Pool::load(&e);
             let mut user = User::load(&e, &samwise);
@@ -794,6 +869,14 @@ mod tests {
             let result = check_and_handle_user_bad_debt(&e, &mut pool, &samwise, &mut user);
             assert_eq!(result, true);
 
+            // the principal moves to the backstop at the liability's current value
+            let reserve_1 = pool.load_reserve(&e, &underlying_1, false);
+            assert_eq!(storage::get_user_principal(&e, &samwise, &1), 0);
+            assert_eq!(
+                storage::get_user_principal(&e, &backstop_address, &1),
+                reserve_1.to_asset_from_d_token(&e, 50_987_654_321)
+            );
+
             // assert user forgiven liabilities and assigned to backstop
             assert_eq!(user.positions.liabilities.len(), 0);
             assert_eq!(user.positions.collateral.len(), 0);
@@ -807,6 +890,19 @@ mod tests {
             assert_eq!(post_backstop_positions.collateral.len(), 0);
             assert_eq!(post_backstop_positions.supply.len(), 0);
 
+            // assert bad debt tracked
+            assert_eq!(
+                storage::get_bad_debt(&e, &underlying_0),
+                BadDebt {
+                    assigned: 1_5000000,
+                    defaulted: 0,
+                }
+            );
+            assert_eq!(
+                storage::get_bad_debt(&e, &underlying_1).assigned,
+                50_987_654_321
+            );
+
             // store pool reserves and assert they got updated
             pool.store_cached_reserves(&e);
             let post_reserve_data_0 = storage::get_res_data(&e, &underlying_0);
@@ -1088,6 +1184,15 @@ mod tests {
             );
             assert_eq!(backstop_user.positions.supply, backstop_positions.supply);
 
+            // assert defaulted bad debt tracked
+            assert_eq!(
+                storage::get_bad_debt(&e, &underlying_0),
+                BadDebt {
+                    assigned: 0,
+                    defaulted: 1_5000000,
+                }
+            );
+
             // store pool reserves and assert they got updated
             pool.store_cached_reserves(&e);
             let post_reserve_data_0 = storage::get_res_data(&e, &underlying_0);
@@ -1104,4 +1209,74 @@ mod tests {
             assert!(post_reserve_data_1.b_rate < reserve_data_1.b_rate);
         });
     }
+
+    /***** load_total_bad_debt *****/
+
+    #[test]
+    fn test_load_total_bad_debt() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let pool = create_pool(&e);
+        let bombadil = Address::generate(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_2, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 2_5000000, 1000_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 5,
+        };
+        let backstop = Address::generate(&e);
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop);
+            assert_eq!(load_total_bad_debt(&e), 0);
+
+            // the backstop's liabilities are its bad debt
+            storage::set_user_positions(
+                &e,
+                &backstop,
+                &Positions {
+                    liabilities: map![&e, (0, 10_0000000), (1, 2_0000000)],
+                    collateral: map![&e],
+                    supply: map![&e],
+                },
+            );
+
+            assert_eq!(load_total_bad_debt(&e), 10_0000000 + 5_0000000);
+
+            // sunset reserves cannot be priced and are left out
+            storage::set_sunset(&e, &underlying_1, &true);
+            assert_eq!(load_total_bad_debt(&e), 10_0000000);
+        });
+    }
 }
@@ -0,0 +1,250 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{panic_with_error, Address, Env, Map, Vec};
+
+use crate::{errors::PoolError, storage};
+
+use super::{
+    actions::{Actions, Request},
+    submit::require_allowance,
+    RequestType,
+};
+
+/// Set if transfers into a reserve are credited by the change in the pool's balance instead of the
+/// amount requested. This allows tokens that charge a fee on transfer to be listed.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `enabled` - If transfers are credited by the change in the pool's balance
+///
+/// ### Panics
+/// If the reserve does not exist
+pub fn execute_set_balance_delta(e: &Env, asset: &Address, enabled: bool) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_balance_delta(e, asset, &enabled);
+}
+
+/// Transfer the tokens for each supply, supply collateral, and repay request against a balance
+/// delta reserve into the pool before the requests are applied. The amount of each request is
+/// replaced with the amount the pool's balance increased by, so the request only credits tokens
+/// the pool actually received.
+///
+/// Returns the updated requests and the amount received for each asset
+///
+/// ### Arguments
+/// * `requests` - The requests
+/// * `spender` - The address sending tokens to the pool
+/// * `use_allowance` - If `transfer_from` is used to move tokens from the spender
+pub(crate) fn pull_balance_delta_transfers(
+    e: &Env,
+    requests: Vec<Request>,
+    spender: &Address,
+    use_allowance: bool,
+) -> (Vec<Request>, Map<Address, i128>) {
+    let mut received: Map<Address, i128> = Map::new(e);
+    let mut updated_requests = Vec::new(e);
+    for mut request in requests.iter() {
+        let is_transfer_in = request.request_type == RequestType::Supply as u32
+            || request.request_type == RequestType::SupplyCollateral as u32
+            || request.request_type == RequestType::Repay as u32;
+        if is_transfer_in && request.amount > 0 && storage::get_balance_delta(e, &request.address) {
+            let amount = transfer_in(e, &request.address, spender, request.amount, use_allowance);
+            received.set(
+                request.address.clone(),
+                received.get(request.address.clone()).unwrap_or(0) + amount,
+            );
+            request.amount = amount;
+        }
+        updated_requests.push_back(request);
+    }
+    (updated_requests, received)
+}
+
+/// Remove the tokens already received by `pull_balance_delta_transfers` from the tokens the
+/// spender owes the pool
+///
+/// ### Arguments
+/// * `actions` - The actions built from the updated requests
+/// * `received` - The amount received for each asset
+pub(crate) fn settle_balance_delta_transfers(actions: &mut Actions, received: &Map<Address, i128>) {
+    for (asset, amount) in received.iter() {
+        let owed = actions.spender_transfer.get(asset.clone()).unwrap_or(0) - amount;
+        if owed > 0 {
+            actions.spender_transfer.set(asset, owed);
+        } else {
+            actions.spender_transfer.remove(asset);
+        }
+    }
+}
+
+/// Transfer `amount` tokens from `spender` to the pool
+///
+/// Returns the amount the pool's balance increased by, up to `amount`
+pub(super) fn transfer_in(
+    e: &Env,
+    asset: &Address,
+    spender: &Address,
+    amount: i128,
+    use_allowance: bool,
+) -> i128 {
+    let token = TokenClient::new(e, asset);
+    let pool = e.current_contract_address();
+    let balance_before = token.balance(&pool);
+    if use_allowance {
+        require_allowance(e, &token, spender, amount);
+        token.transfer_from(&pool, spender, &pool, &amount);
+    } else {
+        token.transfer(spender, &pool, &amount);
+    }
+    let delta = token.balance(&pool) - balance_before;
+    if delta < 0 {
+        panic_with_error!(e, PoolError::BalanceError);
+    }
+    delta.min(amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pool::{execute_submit, Positions},
+        testutils::{self, MockFeeTokenClient},
+    };
+
+    use super::*;
+    use soroban_sdk::{map, testutils::Address as _, vec};
+
+    /// Setup a pool with a single reserve of a token that charges a 1% fee on transfer, with
+    /// balance delta mode enabled
+    fn setup_pool<'a>(e: &Env) -> (Address, Address, MockFeeTokenClient<'a>) {
+        let (pool, _) = testutils::create_pool_with_oracle(e);
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let (underlying, underlying_client) = testutils::create_fee_token(e);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            execute_set_balance_delta(e, &underlying, true);
+        });
+        (pool, underlying, underlying_client)
+    }
+
+    #[test]
+    fn test_submit_balance_delta_supply() {
+        let e = Env::default();
+        let (pool, underlying, underlying_client) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+        underlying_client.mint(&samwise, &100_0000000);
+        let pre_pool_balance = underlying_client.balance(&pool);
+
+        e.as_contract(&pool, || {
+            let pre_b_supply = storage::get_res_data(&e, &underlying).b_supply;
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying.clone(),
+                    amount: 100_0000000,
+                },
+            ];
+            let positions = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+
+            // only the tokens received after the transfer fee are credited
+            assert_eq!(positions.collateral.get_unchecked(0), 99_0000000);
+            assert_eq!(
+                storage::get_res_data(&e, &underlying).b_supply,
+                pre_b_supply + 99_0000000
+            );
+        });
+        assert_eq!(underlying_client.balance(&samwise), 0);
+        assert_eq!(
+            underlying_client.balance(&pool),
+            pre_pool_balance + 99_0000000
+        );
+    }
+
+    #[test]
+    fn test_submit_balance_delta_supply_use_allowance() {
+        let e = Env::default();
+        let (pool, underlying, underlying_client) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+        underlying_client.mint(&samwise, &100_0000000);
+        underlying_client.approve(&samwise, &pool, &100_0000000, &1000);
+        let pre_pool_balance = underlying_client.balance(&pool);
+
+        e.as_contract(&pool, || {
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Supply as u32,
+                    address: underlying.clone(),
+                    amount: 50_0000000,
+                },
+            ];
+            let positions = execute_submit(&e, &samwise, &samwise, &samwise, requests, true);
+
+            assert_eq!(positions.supply.get_unchecked(0), 49_5000000);
+        });
+        assert_eq!(underlying_client.balance(&samwise), 50_0000000);
+        assert_eq!(underlying_client.allowance(&samwise, &pool), 50_0000000);
+        assert_eq!(
+            underlying_client.balance(&pool),
+            pre_pool_balance + 49_5000000
+        );
+    }
+
+    #[test]
+    fn test_submit_balance_delta_repay() {
+        let e = Env::default();
+        let (pool, underlying, underlying_client) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+        underlying_client.mint(&samwise, &20_0000000);
+        let pre_pool_balance = underlying_client.balance(&pool);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    collateral: map![&e],
+                    liabilities: map![&e, (0, 50_0000000)],
+                    supply: map![&e],
+                },
+            );
+            let pre_d_supply = storage::get_res_data(&e, &underlying).d_supply;
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying.clone(),
+                    amount: 20_0000000,
+                },
+            ];
+            let positions = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+
+            assert_eq!(positions.liabilities.get_unchecked(0), 30_2000000);
+            assert_eq!(
+                storage::get_res_data(&e, &underlying).d_supply,
+                pre_d_supply - 19_8000000
+            );
+        });
+        assert_eq!(underlying_client.balance(&samwise), 0);
+        assert_eq!(
+            underlying_client.balance(&pool),
+            pre_pool_balance + 19_8000000
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_balance_delta_no_reserve() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_balance_delta(&e, &Address::generate(&e), true);
+        });
+    }
+}
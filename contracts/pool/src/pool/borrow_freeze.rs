@@ -0,0 +1,110 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{constants::BORROW_FREEZE_TIMELOCK, errors::PoolError, storage};
+
+use super::account_lock::{is_account_locked, update_account_lock};
+
+/// Freeze borrowing from a user's account, or request that the freeze be lifted after
+/// `BORROW_FREEZE_TIMELOCK`. Freezing cancels any pending unfreeze, and requesting an unfreeze
+/// while one is pending does not restart the timelock.
+///
+/// Returns the time the freeze lifts, or `u64::MAX` if no unfreeze is requested
+///
+/// ### Arguments
+/// * `user` - The user
+/// * `frozen` - True to freeze borrowing, false to request the freeze be lifted
+///
+/// ### Panics
+/// If `frozen` is false and borrowing is not frozen for the user
+pub fn execute_set_borrow_freeze(e: &Env, user: &Address, frozen: bool) -> u64 {
+    let unfreeze_time = update_account_lock(
+        e,
+        storage::get_borrow_freeze(e, user),
+        frozen,
+        BORROW_FREEZE_TIMELOCK,
+    );
+    storage::set_borrow_freeze(e, user, &Some(unfreeze_time));
+    unfreeze_time
+}
+
+/// Require that the user has not frozen borrowing from their account
+///
+/// ### Panics
+/// If the user's borrow freeze has not lifted
+pub(crate) fn require_borrow_not_frozen(e: &Env, user: &Address) {
+    if is_account_locked(e, storage::get_borrow_freeze(e, user)) {
+        panic_with_error!(e, PoolError::BorrowFrozen);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testutils;
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    #[test]
+    fn test_set_borrow_freeze() {
+        let e = Env::default();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            require_borrow_not_frozen(&e, &samwise);
+
+            assert_eq!(execute_set_borrow_freeze(&e, &samwise, true), u64::MAX);
+            assert_eq!(storage::get_borrow_freeze(&e, &samwise), Some(u64::MAX));
+
+            let unfreeze_time = 1000 + BORROW_FREEZE_TIMELOCK;
+            assert_eq!(
+                execute_set_borrow_freeze(&e, &samwise, false),
+                unfreeze_time
+            );
+
+            // requesting again does not restart the timelock
+            e.ledger().set_timestamp(2000);
+            assert_eq!(
+                execute_set_borrow_freeze(&e, &samwise, false),
+                unfreeze_time
+            );
+
+            e.ledger().set_timestamp(unfreeze_time);
+            require_borrow_not_frozen(&e, &samwise);
+
+            // freezing again cancels the unfreeze
+            assert_eq!(execute_set_borrow_freeze(&e, &samwise, true), u64::MAX);
+            assert_eq!(storage::get_borrow_freeze(&e, &samwise), Some(u64::MAX));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1232)")]
+    fn test_require_borrow_not_frozen_before_timelock() {
+        let e = Env::default();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_borrow_freeze(&e, &samwise, true);
+            execute_set_borrow_freeze(&e, &samwise, false);
+
+            e.ledger().set_timestamp(1000 + BORROW_FREEZE_TIMELOCK - 1);
+            require_borrow_not_frozen(&e, &samwise);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_borrow_freeze_unfreeze_not_frozen() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_borrow_freeze(&e, &samwise, false);
+        });
+    }
+}
@@ -0,0 +1,182 @@
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{map, Address, Env, Map};
+
+use crate::{
+    constants::{BUMP_BOUNTY_PCT, BUMP_BOUNTY_PERIOD, SCALAR_7},
+    storage,
+};
+
+use super::{recall_shortfall, unbonded_balance};
+
+/// Bump the rent for the pool's critical entries, so the pool can't be bricked by them being
+/// archived. The first bump every 7 days pays `caller` a bounty of 0.1% of each reserve's backstop
+/// credit, up to the pool's balance of the reserve, so keepers are paid to keep the pool alive.
+///
+/// Returns the bounty paid to `caller` for each reserve
+///
+/// ### Arguments
+/// * `caller` - The address bumping the entries
+pub fn execute_bump_critical(e: &Env, caller: &Address) -> Map<Address, i128> {
+    storage::extend_critical(e);
+
+    let mut bounty = map![e];
+    let now = e.ledger().timestamp();
+    let last_bump = storage::get_last_bump(e);
+    if last_bump != 0 && now < last_bump + BUMP_BOUNTY_PERIOD {
+        return bounty;
+    }
+    storage::set_last_bump(e, &now);
+
+    let pool = e.current_contract_address();
+    for asset in storage::get_res_list(e).iter() {
+        let mut data = storage::get_res_data(e, &asset);
+        let token_client = TokenClient::new(e, &asset);
+        let amount = data
+            .backstop_credit
+            .fixed_mul_floor(e, &BUMP_BOUNTY_PCT, &SCALAR_7);
+        recall_shortfall(e, &asset, amount);
+        // cap the bounty at the pool's balance, so a fully borrowed reserve can't revert the bump
+        let amount = amount.min(unbonded_balance(e, &asset));
+        if amount > 0 {
+            data.backstop_credit -= amount;
+            storage::set_res_data(e, &asset, &data);
+            token_client.transfer(&pool, caller, &amount);
+            bounty.set(asset, amount);
+        }
+    }
+    bounty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pool::{deployed_to_strategy, execute_deploy_to_strategy, execute_set_reserve_strategy},
+        testutils,
+    };
+    use sep_41_token::testutils::MockTokenClient;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    /// Setup a pool with a reserve with 100 tokens of backstop credit and a reserve with none
+    fn setup_pool<'a>(e: &Env) -> (Address, Address, MockTokenClient<'a>, Address) {
+        let (pool, underlying_0, underlying_0_client) = testutils::create_pool_with_reserve(e);
+        e.as_contract(&pool, || {
+            let mut reserve_data = storage::get_res_data(e, &underlying_0);
+            reserve_data.backstop_credit = 100_0000000;
+            storage::set_res_data(e, &underlying_0, &reserve_data);
+        });
+        underlying_0_client.mint(&pool, &100_0000000);
+
+        let bombadil = Address::generate(e);
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 12345;
+        reserve_data.backstop_credit = 0;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config, &reserve_data);
+        (pool, underlying_0, underlying_0_client, underlying_1)
+    }
+
+    #[test]
+    fn test_execute_bump_critical() {
+        let e = Env::default();
+        let (pool, underlying_0, underlying_0_client, underlying_1) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            let bounty = execute_bump_critical(&e, &samwise);
+            assert_eq!(bounty.len(), 1);
+            assert_eq!(bounty.get_unchecked(underlying_0.clone()), 0_1000000);
+            assert_eq!(storage::get_last_bump(&e), 12345);
+
+            let data_0 = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(data_0.backstop_credit, 99_9000000);
+            let data_1 = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(data_1.backstop_credit, 0);
+        });
+        assert_eq!(underlying_0_client.balance(&samwise), 0_1000000);
+    }
+
+    #[test]
+    fn test_execute_bump_critical_bounty_once_per_period() {
+        let e = Env::default();
+        let (pool, underlying_0, underlying_0_client, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_bump_critical(&e, &samwise);
+        });
+
+        // entries are still bumped, but no bounty is paid until the period has passed
+        e.ledger().set_timestamp(12345 + BUMP_BOUNTY_PERIOD - 1);
+        e.as_contract(&pool, || {
+            let bounty = execute_bump_critical(&e, &samwise);
+            assert_eq!(bounty.len(), 0);
+            assert_eq!(storage::get_last_bump(&e), 12345);
+        });
+
+        e.ledger().set_timestamp(12345 + BUMP_BOUNTY_PERIOD);
+        e.as_contract(&pool, || {
+            let bounty = execute_bump_critical(&e, &samwise);
+            assert_eq!(bounty.get_unchecked(underlying_0.clone()), 0_0999000);
+            assert_eq!(storage::get_last_bump(&e), 12345 + BUMP_BOUNTY_PERIOD);
+        });
+        assert_eq!(underlying_0_client.balance(&samwise), 0_1999000);
+    }
+
+    #[test]
+    fn test_execute_bump_critical_bounty_capped_at_balance() {
+        let e = Env::default();
+        let (pool, underlying_0, underlying_0_client, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        // the reserve is borrowed until the pool only holds 0.05 tokens
+        let balance = underlying_0_client.balance(&pool);
+        e.as_contract(&pool, || {
+            underlying_0_client.transfer(&pool, &frodo, &(balance - 0_0500000));
+        });
+
+        e.as_contract(&pool, || {
+            let bounty = execute_bump_critical(&e, &samwise);
+            assert_eq!(bounty.get_unchecked(underlying_0.clone()), 0_0500000);
+            let data_0 = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(data_0.backstop_credit, 99_9500000);
+        });
+        assert_eq!(underlying_0_client.balance(&samwise), 0_0500000);
+        assert_eq!(underlying_0_client.balance(&pool), 0);
+
+        // the entries are still bumped once the pool holds none of the reserve
+        e.ledger().set_timestamp(12345 + BUMP_BOUNTY_PERIOD);
+        e.as_contract(&pool, || {
+            let bounty = execute_bump_critical(&e, &samwise);
+            assert_eq!(bounty.len(), 0);
+            assert_eq!(storage::get_last_bump(&e), 12345 + BUMP_BOUNTY_PERIOD);
+        });
+    }
+
+    #[test]
+    fn test_execute_bump_critical_recalls_from_strategy() {
+        let e = Env::default();
+        let (pool, underlying_0, underlying_0_client, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let adapter = testutils::create_mock_strategy(&e, &underlying_0);
+
+        // the pool only holds 0.05 tokens after deploying 20 tokens to the strategy
+        let balance = underlying_0_client.balance(&pool);
+        e.as_contract(&pool, || {
+            underlying_0_client.transfer(&pool, &frodo, &(balance - 20_0500000));
+            execute_set_reserve_strategy(&e, &underlying_0, &adapter, 1_0000000);
+            execute_deploy_to_strategy(&e, &underlying_0, 20_0000000);
+        });
+
+        e.as_contract(&pool, || {
+            let bounty = execute_bump_critical(&e, &samwise);
+            assert_eq!(bounty.get_unchecked(underlying_0.clone()), 0_1000000);
+            assert_eq!(deployed_to_strategy(&e, &underlying_0), 19_9500000);
+        });
+        assert_eq!(underlying_0_client.balance(&samwise), 0_1000000);
+        assert_eq!(underlying_0_client.balance(&pool), 0);
+    }
+}
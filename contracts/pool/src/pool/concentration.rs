@@ -0,0 +1,161 @@
+use cast::i128;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::Env;
+
+use crate::constants::SCALAR_7;
+
+use super::Reserve;
+
+/// Apply any concentration haircut to a user's effective collateral for a reserve. The haircut
+/// grows linearly from zero when the user's share of the reserve's supply is at the reserve's
+/// `conc_threshold`, to `conc_haircut` when the user holds the entire supply. The haircut is part
+/// of the reserve config, so it is timelocked like any other reserve update.
+///
+/// Returns the effective collateral after the haircut
+///
+/// ### Arguments
+/// * `reserve` - The reserve
+/// * `b_tokens` - The user's collateral bTokens for the reserve
+/// * `effective_collateral` - The user's effective collateral for the reserve, in the underlying
+pub fn apply_concentration_haircut(
+    e: &Env,
+    reserve: &Reserve,
+    b_tokens: i128,
+    effective_collateral: i128,
+) -> i128 {
+    if reserve.config.conc_haircut == 0 || reserve.data.b_supply <= 0 {
+        return effective_collateral;
+    }
+    let share = b_tokens
+        .fixed_div_floor(e, &reserve.data.b_supply, &SCALAR_7)
+        .min(SCALAR_7);
+    let threshold = i128(reserve.config.conc_threshold);
+    if share <= threshold {
+        return effective_collateral;
+    }
+    let haircut_pct = i128(reserve.config.conc_haircut).fixed_mul_ceil(
+        e,
+        &(share - threshold),
+        &(SCALAR_7 - threshold),
+    );
+    effective_collateral.fixed_mul_floor(e, &(SCALAR_7 - haircut_pct), &SCALAR_7)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pool::{health_factor::PositionData, Pool, Positions},
+        storage::{self, PoolConfig},
+        testutils,
+    };
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{map, testutils::Address as _, vec, Symbol};
+
+    #[test]
+    fn test_apply_concentration_haircut() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            let mut reserve = Reserve {
+                asset: underlying.clone(),
+                config: reserve_config.clone(),
+                data: reserve_data.clone(),
+                scalar: SCALAR_7,
+                protocol_fee: 0,
+                util_smoothing: None,
+            };
+            // no haircut while it is disabled
+            assert_eq!(
+                apply_concentration_haircut(&e, &reserve, 60_0000000, 45_0000000),
+                45_0000000
+            );
+
+            reserve.config.conc_threshold = 0_2000000;
+            reserve.config.conc_haircut = 0_4000000;
+            // at the threshold there is no haircut
+            assert_eq!(
+                apply_concentration_haircut(&e, &reserve, 20_0000000, 15_0000000),
+                15_0000000
+            );
+            // 60% of supply is halfway from the threshold, so the haircut is 20%
+            assert_eq!(
+                apply_concentration_haircut(&e, &reserve, 60_0000000, 45_0000000),
+                36_0000000
+            );
+            // the entire supply takes the max haircut
+            assert_eq!(
+                apply_concentration_haircut(&e, &reserve, 100_0000000, 75_0000000),
+                45_0000000
+            );
+        });
+    }
+
+    #[test]
+    fn test_concentration_haircut_lowers_health_factor() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+        };
+        let positions = Positions {
+            liabilities: map![&e, (1, 10_0000000)],
+            collateral: map![&e, (0, 60_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let mut pool = Pool::load(&e);
+            let position_data = PositionData::calculate_from_positions(&e, &mut pool, &positions);
+            assert_eq!(position_data.collateral_base, 45_0000000);
+
+            let mut reserve_config = storage::get_res_config(&e, &underlying_0);
+            reserve_config.conc_threshold = 0_2000000;
+            reserve_config.conc_haircut = 0_4000000;
+            storage::set_res_config(&e, &underlying_0, &reserve_config);
+            let mut pool = Pool::load(&e);
+            let position_data = PositionData::calculate_from_positions(&e, &mut pool, &positions);
+            assert_eq!(position_data.collateral_base, 36_0000000);
+            assert_eq!(position_data.collateral_raw, 60_0000000);
+        });
+    }
+}
@@ -1,13 +1,25 @@
 use crate::{
-    constants::{MAX_RESERVES, SCALAR_12, SCALAR_7, SECONDS_PER_WEEK},
+    constants::{
+        MAX_AUCTION_START_OFFSET, MAX_BACKSTOP_SHARE_C_FACTOR, MAX_DESCRIPTION_LEN,
+        MAX_ICON_URI_LEN, MAX_NAME_LEN, MAX_RESERVES, MAX_TREASURY_SPLIT,
+        MAX_UTIL_SMOOTHING_WINDOW, MIN_RETAIL_LIQ_HF, SCALAR_12, SCALAR_7, SECONDS_PER_WEEK,
+    },
+    dependencies::{BackstopClient, PoolFactoryClient, ShareTokenClient},
     errors::PoolError,
     storage::{
-        self, has_queued_reserve_set, PoolConfig, QueuedReserveInit, ReserveConfig, ReserveData,
+        self, has_queued_reserve_set, InterestSplit, PoolConfig, PoolMetadata, QueuedInterestSplit,
+        QueuedReserveInit, ReserveConfig, ReserveData, ReserveRamp, RetailLiquidation,
+        UtilSmoothing,
     },
 };
 use soroban_sdk::{panic_with_error, Address, Env, String};
+use trustbridge_common::{Asset, PriceFeedClient};
 
-use super::{pool::Pool, Reserve};
+use super::{
+    pool::Pool,
+    reserve_index::{require_reserve_index, require_valid_reserve_indexes},
+    Reserve,
+};
 
 /// Initialize the pool
 ///
@@ -40,6 +52,24 @@ pub fn execute_initialize(
     storage::set_blnd_token(e, blnd_id);
 }
 
+/// Set the pool's display metadata
+///
+/// ### Arguments
+/// * `metadata` - The metadata of the pool
+///
+/// ### Panics
+/// If the name is empty, or any field is over its maximum length
+pub fn execute_set_metadata(e: &Env, metadata: &PoolMetadata) {
+    if metadata.name.len() == 0
+        || metadata.name.len() > MAX_NAME_LEN
+        || metadata.icon_uri.len() > MAX_ICON_URI_LEN
+        || metadata.description.len() > MAX_DESCRIPTION_LEN
+    {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_pool_metadata(e, metadata);
+}
+
 /// Update the pool
 pub fn execute_update_pool(
     e: &Env,
@@ -63,16 +93,213 @@ pub fn execute_update_pool(
     storage::set_pool_config(e, &pool_config);
 }
 
+/// Set the minimum number of blocks that must pass after an auction begins before it can be filled
+///
+/// Panics if the minimum is not below the 200 block window where the lot scales up
+pub fn execute_set_auction_min_blocks(e: &Env, min_blocks: u32) {
+    if min_blocks >= 200 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_auction_min_blocks(e, &min_blocks);
+}
+
+/// Set the maximum number of blocks an auction's start is randomly delayed by, so the block with
+/// the best price cannot be known when the auction is created
+///
+/// Panics if the offset is over `MAX_AUCTION_START_OFFSET`
+pub fn execute_set_auction_start_offset(e: &Env, max_offset: u32) {
+    if max_offset > MAX_AUCTION_START_OFFSET {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_auction_start_offset(e, &max_offset);
+}
+
+/// Set the deferred liquidation start for retail accounts. Accounts with less raw collateral than
+/// the threshold can only be liquidated once their health factor is under `min_hf`. A threshold
+/// of 0 disables the deferral.
+///
+/// Panics if the threshold is negative, or `min_hf` is not between `MIN_RETAIL_LIQ_HF` and 1
+pub fn execute_set_retail_liquidation(e: &Env, config: &RetailLiquidation) {
+    if config.threshold < 0 || config.min_hf < MIN_RETAIL_LIQ_HF || config.min_hf as i128 > SCALAR_7
+    {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_retail_liquidation(e, config);
+}
+
+/// Set the width of an oracle price's confidence interval, relative to the price, above which
+/// collateral is valued at the bottom of the interval and liabilities at the top. A threshold of
+/// 0 disables confidence intervals, and the pool's oracle is only required to support
+/// `lastprice_v2` while enabled.
+///
+/// Panics if the threshold is over 100%
+pub fn execute_set_confidence_threshold(e: &Env, threshold: u32) {
+    if threshold > SCALAR_7 as u32 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_confidence_threshold(e, &threshold);
+}
+
+/// Set the asset the pool's values are denominated in. Position values, `min_collateral`, and
+/// auction values are all in the oracle's base asset, so the oracle must quote prices in `base`.
+/// Once set, the pool refuses to value positions if the oracle's base no longer matches.
+///
+/// ### Arguments
+/// * `base` - The base asset, such as `Asset::Other("EUR")`
+///
+/// ### Panics
+/// If the pool's oracle does not quote prices in `base`
+pub fn execute_set_base_asset(e: &Env, base: &Asset) {
+    let oracle = storage::get_pool_config(e).oracle;
+    if &PriceFeedClient::new(e, &oracle).base() != base {
+        panic_with_error!(e, PoolError::OracleBaseMismatch);
+    }
+    storage::set_base_asset(e, base);
+}
+
+/// Queue a new split of interest auction proceeds routed to the treasury. The split is
+/// timelocked like a reserve update, so proceeds cannot be redirected instantly. Any previously
+/// queued split is replaced.
+///
+/// Returns the queued interest split
+///
+/// ### Panics
+/// If the split is over `MAX_TREASURY_SPLIT`
+pub fn execute_queue_set_interest_split(e: &Env, split: &InterestSplit) -> QueuedInterestSplit {
+    if split.pct > MAX_TREASURY_SPLIT {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+
+    let mut unlock_time = e.ledger().timestamp();
+    // require a timelock if pool status is not setup
+    if storage::get_pool_config(e).status != 6 {
+        unlock_time += SECONDS_PER_WEEK;
+    }
+    let queued = QueuedInterestSplit {
+        split: split.clone(),
+        unlock_time,
+    };
+    storage::set_queued_interest_split(e, &Some(queued.clone()));
+    queued
+}
+
+/// Cancel the queued interest split
+///
+/// Returns the cancelled interest split
+///
+/// Panics if no interest split is queued
+pub fn execute_cancel_set_interest_split(e: &Env) -> InterestSplit {
+    let queued = match storage::get_queued_interest_split(e) {
+        Some(queued) => queued,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    storage::set_queued_interest_split(e, &None);
+    queued.split
+}
+
+/// Set the queued interest split once its timelock has passed. The remaining proceeds are
+/// donated to the backstop.
+///
+/// Returns the interest split
+///
+/// Panics if no interest split is queued or the timelock has not passed
+pub fn execute_set_interest_split(e: &Env) -> InterestSplit {
+    let queued = match storage::get_queued_interest_split(e) {
+        Some(queued) => queued,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if queued.unlock_time > e.ledger().timestamp() {
+        panic_with_error!(e, PoolError::InitNotUnlocked);
+    }
+    storage::set_queued_interest_split(e, &None);
+    storage::set_interest_split(e, &Some(queued.split.clone()));
+    queued.split
+}
+
+/// Remove the interest split, so all interest auction proceeds are donated to the backstop
+///
+/// Panics if no interest split is set
+pub fn execute_disable_interest_split(e: &Env) {
+    if storage::get_interest_split(e).is_none() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_interest_split(e, &None);
+}
+
+/// Set if health calculations price a reserve with the oracle's TWAP as well as its spot price.
+/// While enabled, collateral is valued at the lower of the two and liabilities at the higher, so
+/// volatile reserves cannot be borrowed against at a briefly inflated spot price.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `enabled` - If the reserve is priced with the oracle's TWAP
+///
+/// ### Panics
+/// If the reserve does not exist
+pub fn execute_set_twap_pricing(e: &Env, asset: &Address, enabled: bool) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_twap_pricing(e, asset, &enabled);
+}
+
+/// Set the window a reserve's utilization is smoothed over for its interest rate. The reserve is
+/// accrued with its current smoothing first. Smoothing starts from the reserve's current
+/// utilization when enabled.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `window` - The time in seconds the utilization is averaged over, or 0 to disable smoothing
+///
+/// ### Panics
+/// If the reserve does not exist or the window is over `MAX_UTIL_SMOOTHING_WINDOW`
+pub fn execute_set_util_smoothing(e: &Env, asset: &Address, window: u64) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    if window > MAX_UTIL_SMOOTHING_WINDOW {
+        panic_with_error!(e, PoolError::InvalidReserveMetadata);
+    }
+
+    let pool_config = storage::get_pool_config(e);
+    let reserve = Reserve::load(e, &pool_config, asset);
+    reserve.store(e);
+
+    let smoothing = if window == 0 {
+        None
+    } else {
+        let util = match &reserve.util_smoothing {
+            Some(smoothing) => smoothing.util,
+            None => reserve.utilization(e),
+        };
+        Some(UtilSmoothing { window, util })
+    };
+    storage::set_util_smoothing(e, asset, &smoothing);
+}
+
+/// Restrict the assets that can be added as reserves to those allowed by the allowlist contract
+///
+/// Panics if an allowlist is already set
+pub fn execute_set_reserve_allowlist(e: &Env, allowlist: &Address) {
+    if storage::get_reserve_allowlist(e).is_some() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_reserve_allowlist(e, allowlist);
+}
+
 /// Execute a queueing a reserve initialization for the pool
 pub fn execute_queue_set_reserve(e: &Env, asset: &Address, metadata: &ReserveConfig) {
     if has_queued_reserve_set(e, asset) {
         panic_with_error!(&e, PoolError::BadRequest)
     }
     require_valid_reserve_metadata(e, metadata);
+    require_valid_backstop_share_reserve(e, asset, metadata);
 
     // if the reserve config exists, ensure there are no invalid changes
     if storage::has_res(e, asset) {
         require_valid_reserve_metadata_changes(e, &storage::get_res_config(e, asset), metadata);
+    } else {
+        require_asset_allowed(e, asset);
     }
 
     let mut unlock_time = e.ledger().timestamp();
@@ -103,6 +330,11 @@ pub fn execute_set_reserve(e: &Env, asset: &Address) -> u32 {
         panic_with_error!(e, PoolError::InitNotUnlocked);
     }
 
+    // the allowlist may have changed since the reserve was queued
+    if !storage::has_res(e, asset) {
+        require_asset_allowed(e, asset);
+    }
+
     // remove queued reserve
     storage::del_queued_reserve_set(e, asset);
 
@@ -110,11 +342,58 @@ pub fn execute_set_reserve(e: &Env, asset: &Address) -> u32 {
     initialize_reserve(e, asset, &queued_init.new_config)
 }
 
+/// Require that an asset can be added as a new reserve
+///
+/// Panics if the pool has a reserve allowlist and the asset is not on it
+pub(super) fn require_asset_allowed(e: &Env, asset: &Address) {
+    if let Some(allowlist) = storage::get_reserve_allowlist(e) {
+        if !PoolFactoryClient::new(e, &allowlist).is_asset_allowed(asset) {
+            panic_with_error!(e, PoolError::AssetNotAllowed);
+        }
+    }
+}
+
+/// Require that a reserve of the share token of a pool's backstop is valid. The shares can only
+/// be listed by a permissioned pool, with a collateral factor of at most
+/// `MAX_BACKSTOP_SHARE_C_FACTOR`, and a pool cannot list the shares of its own backstop, as they
+/// lose value exactly when the pool takes bad debt.
+///
+/// Assets that are not share tokens of the pool's backstop are not checked.
+///
+/// ### Panics
+/// * `CircularBackstopCollateral` if the asset is the share token of the pool's own backstop
+/// * `AssetNotAllowed` if the pool does not have a reserve allowlist
+/// * `InvalidReserveMetadata` if the collateral factor is too high
+fn require_valid_backstop_share_reserve(e: &Env, asset: &Address, metadata: &ReserveConfig) {
+    let share_token_client = ShareTokenClient::new(e, asset);
+    let backstop = storage::get_backstop(e);
+    match share_token_client.try_backstop() {
+        Ok(Ok(share_backstop)) if share_backstop == backstop => {}
+        _ => return,
+    }
+    // the backstop must confirm the token, so any contract cannot claim to be its shares
+    let share_pool = share_token_client.pool();
+    if BackstopClient::new(e, &backstop).share_token(&share_pool) != Some(asset.clone()) {
+        return;
+    }
+
+    if share_pool == e.current_contract_address() {
+        panic_with_error!(e, PoolError::CircularBackstopCollateral);
+    }
+    if storage::get_reserve_allowlist(e).is_none() {
+        panic_with_error!(e, PoolError::AssetNotAllowed);
+    }
+    if metadata.c_factor > MAX_BACKSTOP_SHARE_C_FACTOR {
+        panic_with_error!(e, PoolError::InvalidReserveMetadata);
+    }
+}
+
 /// sets reserve data for the pool
 fn initialize_reserve(e: &Env, asset: &Address, config: &ReserveConfig) -> u32 {
     let index: u32;
     // if reserve already exists, ensure index and scalar do not change
     if storage::has_res(e, asset) {
+        require_reserve_index(e, asset, storage::get_res_config(e, asset).index);
         // accrue and store reserve data to the ledger
         let mut pool = Pool::load(e);
         // @dev: Store the reserve to ledger manually
@@ -131,6 +410,19 @@ fn initialize_reserve(e: &Env, asset: &Address, config: &ReserveConfig) -> u32 {
         {
             reserve.data.ir_mod = SCALAR_7;
         }
+        // ramp factor changes from the current factors to avoid pushing positions underwater
+        if reserve.config.c_factor != config.c_factor || reserve.config.l_factor != config.l_factor
+        {
+            storage::set_res_ramp(
+                e,
+                asset,
+                &ReserveRamp {
+                    c_factor: reserve.config.c_factor,
+                    l_factor: reserve.config.l_factor,
+                    start_ledger: e.ledger().sequence(),
+                },
+            );
+        }
         reserve.store(e);
     } else {
         index = storage::push_res_list(e, asset);
@@ -160,14 +452,17 @@ fn initialize_reserve(e: &Env, asset: &Address, config: &ReserveConfig) -> u32 {
         reactivity: config.reactivity,
         supply_cap: config.supply_cap,
         enabled: config.enabled,
+        conc_threshold: config.conc_threshold,
+        conc_haircut: config.conc_haircut,
     };
     storage::set_res_config(e, asset, &reserve_config);
+    require_valid_reserve_indexes(e);
 
     index
 }
 
 #[allow(clippy::zero_prefixed_literal)]
-fn require_valid_reserve_metadata(e: &Env, metadata: &ReserveConfig) {
+pub(super) fn require_valid_reserve_metadata(e: &Env, metadata: &ReserveConfig) {
     const SCALAR_7_U32: u32 = SCALAR_7 as u32;
     if metadata.decimals > 18
         || metadata.c_factor > SCALAR_7_U32
@@ -178,6 +473,9 @@ fn require_valid_reserve_metadata(e: &Env, metadata: &ReserveConfig) {
         || metadata.r_base < 0_0001000
         || (metadata.r_one > metadata.r_two || metadata.r_two > metadata.r_three)
         || (metadata.reactivity > 0_0001000)
+        || metadata.conc_haircut > SCALAR_7_U32
+        || (metadata.conc_haircut > 0
+            && (metadata.conc_threshold == 0 || metadata.conc_threshold >= SCALAR_7_U32))
     {
         panic_with_error!(e, PoolError::InvalidReserveMetadata);
     }
@@ -201,6 +499,13 @@ fn require_valid_pool_config(e: &Env, config: &PoolConfig) {
         panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
     }
 
+    // ensure the backstop and protocol fee leave suppliers some of the accrued interest
+    if let Some(fee) = storage::get_protocol_fee(e) {
+        if config.bstop_rate + fee.rate >= SCALAR_7 as u32 {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+
     // verify max positions is at least 2 and less than 2 * max reserves
     if config.max_positions < 2 || config.max_positions > 2 * MAX_RESERVES {
         panic_with_error!(&e, PoolError::InvalidPoolConfigArgs);
@@ -218,7 +523,10 @@ mod tests {
     use crate::testutils;
 
     use super::*;
-    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Symbol,
+    };
 
     #[test]
     fn test_execute_initialize() {
@@ -261,230 +569,854 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1201)")]
-    fn test_execute_initialize_bad_take_rate() {
+    fn test_execute_set_base_asset() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+        oracle_client.set_data(
+            &Address::generate(&e),
+            &sep_40_oracle::testutils::Asset::Other(Symbol::new(&e, "EUR")),
+            &vec![&e],
+            &7,
+            &300,
+        );
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            assert_eq!(storage::get_base_asset(&e), None);
+
+            let eur = Asset::Other(Symbol::new(&e, "EUR"));
+            execute_set_base_asset(&e, &eur);
+            assert_eq!(storage::get_base_asset(&e), Some(eur));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1238)")]
+    fn test_execute_set_base_asset_mismatch() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+        oracle_client.set_data(
+            &Address::generate(&e),
+            &sep_40_oracle::testutils::Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e],
+            &7,
+            &300,
+        );
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            execute_set_base_asset(&e, &Asset::Other(Symbol::new(&e, "EUR")));
+        });
+    }
+
+    #[test]
+    fn test_execute_set_metadata() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        let metadata = PoolMetadata {
+            name: String::from_str(&e, "Fixed Pool"),
+            icon_uri: String::from_str(&e, "https://example.com/icon.png"),
+            description: String::from_str(&e, "A pool of fiat backed stablecoins"),
+        };
+        e.as_contract(&pool, || {
+            storage::set_name(&e, &String::from_str(&e, "pool_name"));
+            let default_metadata = storage::get_pool_metadata(&e);
+            assert_eq!(default_metadata.name, String::from_str(&e, "pool_name"));
+            assert_eq!(default_metadata.icon_uri, String::from_str(&e, ""));
+            assert_eq!(default_metadata.description, String::from_str(&e, ""));
+
+            execute_set_metadata(&e, &metadata);
+            assert_eq!(storage::get_pool_metadata(&e), metadata);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_metadata_empty_name() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_metadata(
+                &e,
+                &PoolMetadata {
+                    name: String::from_str(&e, ""),
+                    icon_uri: String::from_str(&e, ""),
+                    description: String::from_str(&e, ""),
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_initialize_bad_take_rate() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let admin = Address::generate(&e);
+        let name = String::from_str(&e, "pool_name");
+        let oracle = Address::generate(&e);
+        let bstop_rate = 1_0000000;
+        let max_positions = 3;
+        let min_collateral = 1_0000000;
+        let backstop_address = Address::generate(&e);
+        let blnd_id = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_initialize(
+                &e,
+                &admin,
+                &name,
+                &oracle,
+                &bstop_rate,
+                &max_positions,
+                &min_collateral,
+                &backstop_address,
+                &blnd_id,
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_initialize_bad_max_positions() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let admin = Address::generate(&e);
+        let name = String::from_str(&e, "pool_name");
+        let oracle = Address::generate(&e);
+        let bstop_rate = 0_1000000;
+        let max_positions = 1;
+        let min_collateral = 1_0000000;
+        let backstop_address = Address::generate(&e);
+        let blnd_id = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_initialize(
+                &e,
+                &admin,
+                &name,
+                &oracle,
+                &bstop_rate,
+                &max_positions,
+                &min_collateral,
+                &backstop_address,
+                &blnd_id,
+            );
+        });
+    }
+
+    #[test]
+    fn test_execute_update_pool() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            // happy path
+            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000);
+            let new_pool_config = storage::get_pool_config(&e);
+            assert_eq!(new_pool_config.bstop_rate, 0_2000000);
+            assert_eq!(new_pool_config.oracle, pool_config.oracle);
+            assert_eq!(new_pool_config.status, pool_config.status);
+            assert_eq!(new_pool_config.max_positions, 4u32);
+            assert_eq!(new_pool_config.min_collateral, 2_0000000);
+        });
+    }
+
+    #[test]
+    fn test_execute_update_pool_updates_reserves_if_backstop_rate_changes() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_supply = 1000_0000000;
+        reserve_data_0.d_supply = 750_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.b_supply = 250_0000000;
+        reserve_data_1.d_supply = 100_5000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+        };
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000);
+
+            let new_pool_config = storage::get_pool_config(&e);
+            assert_eq!(new_pool_config.bstop_rate, 0_2000000);
+            assert_eq!(new_pool_config.oracle, pool_config.oracle);
+            assert_eq!(new_pool_config.status, pool_config.status);
+            assert_eq!(new_pool_config.max_positions, 4u32);
+            assert_eq!(new_pool_config.min_collateral, 2_0000000);
+
+            let new_reserve_data_0 = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(new_reserve_data_0.last_time, 12345 * 5);
+            assert!(new_reserve_data_0.d_rate > reserve_data_0.d_rate);
+            assert!(new_reserve_data_0.b_rate > reserve_data_0.b_rate);
+            let new_reserve_data_1 = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(new_reserve_data_1.last_time, 12345 * 5);
+            assert!(new_reserve_data_1.d_rate > reserve_data_1.d_rate);
+            assert!(new_reserve_data_1.b_rate > reserve_data_1.b_rate);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_update_pool_validates_b_stop_rate() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_update_pool(&e, 1_0000000, 4u32, 1_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_update_pool_validates_min_collateral() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_update_pool(&e, 0_2000000, 4u32, -1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_update_pool_validates_max_positions() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_update_pool(&e, 0_2000000, 1 + 2 * MAX_RESERVES, 2_0000000);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_auction_min_blocks() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            assert_eq!(storage::get_auction_min_blocks(&e), 0);
+
+            execute_set_auction_min_blocks(&e, 5);
+            assert_eq!(storage::get_auction_min_blocks(&e), 5);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_min_blocks_validates_max() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_min_blocks(&e, 200);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_auction_start_offset() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            assert_eq!(storage::get_auction_start_offset(&e), 0);
+
+            execute_set_auction_start_offset(&e, MAX_AUCTION_START_OFFSET);
+            assert_eq!(
+                storage::get_auction_start_offset(&e),
+                MAX_AUCTION_START_OFFSET
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_start_offset_validates_max() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_start_offset(&e, MAX_AUCTION_START_OFFSET + 1);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_retail_liquidation() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            assert!(storage::get_retail_liquidation(&e).is_none());
+
+            let config = RetailLiquidation {
+                threshold: 100_0000000,
+                min_hf: 0_9800000,
+            };
+            execute_set_retail_liquidation(&e, &config);
+            assert_eq!(storage::get_retail_liquidation(&e), Some(config));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_retail_liquidation_validates_min_hf() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_retail_liquidation(
+                &e,
+                &RetailLiquidation {
+                    threshold: 100_0000000,
+                    min_hf: MIN_RETAIL_LIQ_HF - 1,
+                },
+            );
+        });
+    }
+
+    #[test]
+    fn test_execute_set_confidence_threshold() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            assert_eq!(storage::get_confidence_threshold(&e), 0);
+
+            execute_set_confidence_threshold(&e, 0_0100000);
+            assert_eq!(storage::get_confidence_threshold(&e), 0_0100000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_confidence_threshold_validates_max() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_confidence_threshold(&e, 1_0000001);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_interest_split() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1500000000);
+        let pool = testutils::create_pool(&e);
+        let treasury = Address::generate(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            assert!(storage::get_interest_split(&e).is_none());
+
+            let queued = execute_queue_set_interest_split(
+                &e,
+                &InterestSplit {
+                    treasury: treasury.clone(),
+                    pct: MAX_TREASURY_SPLIT,
+                },
+            );
+            assert_eq!(queued.unlock_time, 1500000000 + SECONDS_PER_WEEK);
+            assert!(storage::get_interest_split(&e).is_none());
+        });
+
+        e.ledger().set_timestamp(1500000000 + SECONDS_PER_WEEK);
+        e.as_contract(&pool, || {
+            let split = execute_set_interest_split(&e);
+            assert_eq!(split.treasury, treasury);
+            assert_eq!(split.pct, MAX_TREASURY_SPLIT);
+            let split = storage::get_interest_split(&e).unwrap();
+            assert_eq!(split.treasury, treasury);
+            assert_eq!(split.pct, MAX_TREASURY_SPLIT);
+            assert!(storage::get_queued_interest_split(&e).is_none());
+
+            // disabling the split is not timelocked
+            execute_disable_interest_split(&e);
+            assert!(storage::get_interest_split(&e).is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1203)")]
+    fn test_execute_set_interest_split_before_unlock() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1500000000);
+        let pool = testutils::create_pool(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            execute_queue_set_interest_split(
+                &e,
+                &InterestSplit {
+                    treasury: Address::generate(&e),
+                    pct: MAX_TREASURY_SPLIT,
+                },
+            );
+        });
+
+        e.ledger().set_timestamp(1500000000 + SECONDS_PER_WEEK - 1);
+        e.as_contract(&pool, || {
+            execute_set_interest_split(&e);
+        });
+    }
+
+    #[test]
+    fn test_execute_cancel_set_interest_split() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let treasury = Address::generate(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            execute_queue_set_interest_split(
+                &e,
+                &InterestSplit {
+                    treasury: treasury.clone(),
+                    pct: 0_1000000,
+                },
+            );
+
+            let split = execute_cancel_set_interest_split(&e);
+            assert_eq!(split.treasury, treasury);
+            assert!(storage::get_queued_interest_split(&e).is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_queue_set_interest_split_validates_max() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_queue_set_interest_split(
+                &e,
+                &InterestSplit {
+                    treasury: Address::generate(&e),
+                    pct: MAX_TREASURY_SPLIT + 1,
+                },
+            );
+        });
+    }
+
+    #[test]
+    fn test_execute_set_twap_pricing() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            assert!(!storage::get_twap_pricing(&e, &underlying));
+
+            execute_set_twap_pricing(&e, &underlying, true);
+            assert!(storage::get_twap_pricing(&e, &underlying));
+
+            execute_set_twap_pricing(&e, &underlying, false);
+            assert!(!storage::get_twap_pricing(&e, &underlying));
+        });
+    }
+
+    #[test]
+    fn test_execute_set_util_smoothing() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = e.ledger().timestamp();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            assert_eq!(storage::get_util_smoothing(&e, &underlying), None);
+
+            // smoothing starts from the current utilization
+            execute_set_util_smoothing(&e, &underlying, 3600);
+            let smoothing = storage::get_util_smoothing(&e, &underlying).unwrap();
+            assert_eq!(smoothing.window, 3600);
+            assert_eq!(smoothing.util, 0_7500000);
+
+            // changing the window keeps the smoothed utilization
+            let mut stored = smoothing.clone();
+            stored.util = 0_5000000;
+            storage::set_util_smoothing(&e, &underlying, &Some(stored));
+            execute_set_util_smoothing(&e, &underlying, 7200);
+            let smoothing = storage::get_util_smoothing(&e, &underlying).unwrap();
+            assert_eq!(smoothing.window, 7200);
+            assert_eq!(smoothing.util, 0_5000000);
+
+            execute_set_util_smoothing(&e, &underlying, 0);
+            assert_eq!(storage::get_util_smoothing(&e, &underlying), None);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_execute_set_util_smoothing_over_max() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            execute_set_util_smoothing(&e, &underlying, MAX_UTIL_SMOOTHING_WINDOW + 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_set_twap_pricing_requires_reserve() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
 
-        let admin = Address::generate(&e);
-        let name = String::from_str(&e, "pool_name");
-        let oracle = Address::generate(&e);
-        let bstop_rate = 1_0000000;
-        let max_positions = 3;
-        let min_collateral = 1_0000000;
-        let backstop_address = Address::generate(&e);
-        let blnd_id = Address::generate(&e);
-
         e.as_contract(&pool, || {
-            execute_initialize(
-                &e,
-                &admin,
-                &name,
-                &oracle,
-                &bstop_rate,
-                &max_positions,
-                &min_collateral,
-                &backstop_address,
-                &blnd_id,
-            );
+            execute_set_twap_pricing(&e, &Address::generate(&e), true);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1201)")]
-    fn test_execute_initialize_bad_max_positions() {
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_set_reserve_allowlist_only_once() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
-
-        let admin = Address::generate(&e);
-        let name = String::from_str(&e, "pool_name");
-        let oracle = Address::generate(&e);
-        let bstop_rate = 0_1000000;
-        let max_positions = 1;
-        let min_collateral = 1_0000000;
-        let backstop_address = Address::generate(&e);
-        let blnd_id = Address::generate(&e);
+        let (allowlist, _) = testutils::create_mock_pool_factory(&e);
 
         e.as_contract(&pool, || {
-            execute_initialize(
-                &e,
-                &admin,
-                &name,
-                &oracle,
-                &bstop_rate,
-                &max_positions,
-                &min_collateral,
-                &backstop_address,
-                &blnd_id,
-            );
+            assert!(storage::get_reserve_allowlist(&e).is_none());
+
+            execute_set_reserve_allowlist(&e, &allowlist);
+            assert_eq!(storage::get_reserve_allowlist(&e), Some(allowlist.clone()));
+
+            execute_set_reserve_allowlist(&e, &Address::generate(&e));
         });
     }
 
     #[test]
-    fn test_execute_update_pool() {
+    fn test_queue_set_reserve_allowlist() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
 
+        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (allowlist, allowlist_client) = testutils::create_mock_pool_factory(&e);
+        allowlist_client.set_asset_allowed(&asset_id_0, &true);
+
+        let (metadata, _) = testutils::default_reserve_meta();
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
-            status: 0,
+            status: 6,
             max_positions: 2,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_reserve_allowlist(&e, &allowlist);
 
-            // happy path
-            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000);
-            let new_pool_config = storage::get_pool_config(&e);
-            assert_eq!(new_pool_config.bstop_rate, 0_2000000);
-            assert_eq!(new_pool_config.oracle, pool_config.oracle);
-            assert_eq!(new_pool_config.status, pool_config.status);
-            assert_eq!(new_pool_config.max_positions, 4u32);
-            assert_eq!(new_pool_config.min_collateral, 2_0000000);
+            execute_queue_set_reserve(&e, &asset_id_0, &metadata);
+            let index = execute_set_reserve(&e, &asset_id_0);
+            assert_eq!(index, 0);
         });
     }
 
     #[test]
-    fn test_execute_update_pool_updates_reserves_if_backstop_rate_changes() {
+    #[should_panic(expected = "Error(Contract, #1226)")]
+    fn test_queue_set_reserve_asset_not_allowed() {
         let e = Env::default();
         e.mock_all_auths();
-
-        e.ledger().set(LedgerInfo {
-            timestamp: 12345,
-            protocol_version: 22,
-            sequence_number: 123456,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 10,
-            min_persistent_entry_ttl: 10,
-            max_entry_ttl: 3110400,
-        });
-
-        let bombadil = Address::generate(&e);
         let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
 
-        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
-        reserve_data_0.last_time = 12345;
-        reserve_data_0.b_supply = 1000_0000000;
-        reserve_data_0.d_supply = 750_0000000;
-        reserve_config_0.index = 0;
-        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
-
-        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
-        reserve_data_1.last_time = 12345;
-        reserve_data_1.b_supply = 250_0000000;
-        reserve_data_1.d_supply = 100_5000000;
-        reserve_config_1.index = 1;
-        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (allowlist, _) = testutils::create_mock_pool_factory(&e);
 
+        let (metadata, _) = testutils::default_reserve_meta();
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
-            status: 0,
+            status: 6,
             max_positions: 2,
         };
-
-        e.ledger().set(LedgerInfo {
-            timestamp: 12345 * 5,
-            protocol_version: 22,
-            sequence_number: 123456,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 10,
-            min_persistent_entry_ttl: 10,
-            max_entry_ttl: 3110400,
-        });
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_reserve_allowlist(&e, &allowlist);
 
-            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000);
-
-            let new_pool_config = storage::get_pool_config(&e);
-            assert_eq!(new_pool_config.bstop_rate, 0_2000000);
-            assert_eq!(new_pool_config.oracle, pool_config.oracle);
-            assert_eq!(new_pool_config.status, pool_config.status);
-            assert_eq!(new_pool_config.max_positions, 4u32);
-            assert_eq!(new_pool_config.min_collateral, 2_0000000);
-
-            let new_reserve_data_0 = storage::get_res_data(&e, &underlying_0);
-            assert_eq!(new_reserve_data_0.last_time, 12345 * 5);
-            assert!(new_reserve_data_0.d_rate > reserve_data_0.d_rate);
-            assert!(new_reserve_data_0.b_rate > reserve_data_0.b_rate);
-            let new_reserve_data_1 = storage::get_res_data(&e, &underlying_1);
-            assert_eq!(new_reserve_data_1.last_time, 12345 * 5);
-            assert!(new_reserve_data_1.d_rate > reserve_data_1.d_rate);
-            assert!(new_reserve_data_1.b_rate > reserve_data_1.b_rate);
+            execute_queue_set_reserve(&e, &asset_id_0, &metadata);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1201)")]
-    fn test_execute_update_pool_validates_b_stop_rate() {
+    fn test_queue_set_reserve_backstop_shares() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
 
+        let share_token = testutils::create_mock_share_token(&e, &pool, &Address::generate(&e));
+        let (allowlist, allowlist_client) = testutils::create_mock_pool_factory(&e);
+        allowlist_client.set_asset_allowed(&share_token, &true);
+
+        let (mut metadata, _) = testutils::default_reserve_meta();
+        metadata.c_factor = MAX_BACKSTOP_SHARE_C_FACTOR;
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
-            status: 0,
+            status: 6,
             max_positions: 2,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_reserve_allowlist(&e, &allowlist);
 
-            execute_update_pool(&e, 1_0000000, 4u32, 1_0000000);
+            execute_queue_set_reserve(&e, &share_token, &metadata);
+            let index = execute_set_reserve(&e, &share_token);
+            assert_eq!(index, 0);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1201)")]
-    fn test_execute_update_pool_validates_min_collateral() {
+    #[should_panic(expected = "Error(Contract, #1242)")]
+    fn test_queue_set_reserve_own_backstop_shares() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
 
-        let pool_config = PoolConfig {
-            oracle: Address::generate(&e),
-            min_collateral: 1_0000000,
-            bstop_rate: 0_1000000,
-            status: 0,
-            max_positions: 2,
-        };
+        let share_token = testutils::create_mock_share_token(&e, &pool, &pool);
+        let (allowlist, allowlist_client) = testutils::create_mock_pool_factory(&e);
+        allowlist_client.set_asset_allowed(&share_token, &true);
+
+        let (mut metadata, _) = testutils::default_reserve_meta();
+        metadata.c_factor = MAX_BACKSTOP_SHARE_C_FACTOR;
         e.as_contract(&pool, || {
-            storage::set_pool_config(&e, &pool_config);
+            storage::set_reserve_allowlist(&e, &allowlist);
 
-            execute_update_pool(&e, 0_2000000, 4u32, -1);
+            execute_queue_set_reserve(&e, &share_token, &metadata);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1201)")]
-    fn test_execute_update_pool_validates_max_positions() {
+    #[should_panic(expected = "Error(Contract, #1226)")]
+    fn test_queue_set_reserve_backstop_shares_not_permissioned() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let share_token = testutils::create_mock_share_token(&e, &pool, &Address::generate(&e));
+
+        let (mut metadata, _) = testutils::default_reserve_meta();
+        metadata.c_factor = MAX_BACKSTOP_SHARE_C_FACTOR;
+        e.as_contract(&pool, || {
+            execute_queue_set_reserve(&e, &share_token, &metadata);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_queue_set_reserve_backstop_shares_c_factor_too_high() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let share_token = testutils::create_mock_share_token(&e, &pool, &Address::generate(&e));
+        let (allowlist, allowlist_client) = testutils::create_mock_pool_factory(&e);
+        allowlist_client.set_asset_allowed(&share_token, &true);
+
+        let (mut metadata, _) = testutils::default_reserve_meta();
+        metadata.c_factor = MAX_BACKSTOP_SHARE_C_FACTOR + 1;
+        e.as_contract(&pool, || {
+            storage::set_reserve_allowlist(&e, &allowlist);
+
+            execute_queue_set_reserve(&e, &share_token, &metadata);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1226)")]
+    fn test_set_reserve_asset_removed_from_allowlist() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (allowlist, allowlist_client) = testutils::create_mock_pool_factory(&e);
+        allowlist_client.set_asset_allowed(&asset_id_0, &true);
 
+        let (metadata, _) = testutils::default_reserve_meta();
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
-            status: 0,
+            status: 6,
             max_positions: 2,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_reserve_allowlist(&e, &allowlist);
 
-            execute_update_pool(&e, 0_2000000, 1 + 2 * MAX_RESERVES, 2_0000000);
+            execute_queue_set_reserve(&e, &asset_id_0, &metadata);
+        });
+
+        allowlist_client.set_asset_allowed(&asset_id_0, &false);
+        e.as_contract(&pool, || {
+            execute_set_reserve(&e, &asset_id_0);
         });
     }
 
@@ -511,6 +1443,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
@@ -562,6 +1496,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
@@ -616,6 +1552,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
@@ -659,6 +1597,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
@@ -696,6 +1636,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let metadata = ReserveConfig {
             index: 1,
@@ -711,6 +1653,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
@@ -766,6 +1710,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let metadata = ReserveConfig {
             index: 0,
@@ -781,6 +1727,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
@@ -820,6 +1768,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let metadata = ReserveConfig {
             index: 0,
@@ -835,6 +1785,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
@@ -873,6 +1825,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         e.as_contract(&pool, || {
             storage::set_queued_reserve_set(
@@ -913,6 +1867,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         e.as_contract(&pool, || {
             storage::set_queued_reserve_set(
@@ -962,6 +1918,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         e.as_contract(&pool, || {
             storage::set_queued_reserve_set(
@@ -1049,6 +2007,12 @@ mod tests {
             assert_eq!(res_config_updated.reactivity, new_metadata.reactivity);
             assert_eq!(res_config_updated.index, reserve_config.index);
 
+            // validate the factor changes are ramped from the previous factors
+            let res_ramp = storage::get_res_ramp(&e, &underlying).unwrap();
+            assert_eq!(res_ramp.c_factor, reserve_config.c_factor);
+            assert_eq!(res_ramp.l_factor, reserve_config.l_factor);
+            assert_eq!(res_ramp.start_ledger, 100);
+
             // validate interest was accrued
             let res_data = storage::get_res_data(&e, &underlying);
             assert!(res_data.d_rate > 1_000_000_000_000);
@@ -1172,6 +2136,8 @@ mod tests {
             reactivity: 105,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
 
         let pool_config = PoolConfig {
@@ -1196,6 +2162,74 @@ mod tests {
         });
     }
 
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1237)")]
+    fn test_execute_set_reserve_update_refuses_changed_index() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            // a misordered config write points reserve 1 at reserve 0's index
+            let mut corrupted_config = storage::get_res_config(&e, &underlying_1);
+            corrupted_config.index = 0;
+            storage::set_res_config(&e, &underlying_1, &corrupted_config);
+
+            storage::set_queued_reserve_set(
+                &e,
+                &QueuedReserveInit {
+                    new_config: reserve_config.clone(),
+                    unlock_time: e.ledger().timestamp(),
+                },
+                &underlying_1,
+            );
+            execute_set_reserve(&e, &underlying_1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1237)")]
+    fn test_execute_set_reserve_refuses_listed_asset() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            // the asset is listed without a config, so it would be listed again at a new index
+            storage::push_res_list(&e, &underlying_0);
+
+            storage::set_queued_reserve_set(
+                &e,
+                &QueuedReserveInit {
+                    new_config: reserve_config.clone(),
+                    unlock_time: e.ledger().timestamp(),
+                },
+                &underlying_0,
+            );
+            execute_set_reserve(&e, &underlying_0);
+        });
+    }
+
     #[test]
     fn test_initialize_reserve_sets_index() {
         let e = Env::default();
@@ -1220,6 +2254,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         e.as_contract(&pool, || {
             initialize_reserve(&e, &asset_id_0, &metadata);
@@ -1260,6 +2296,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         require_valid_reserve_metadata(&e, &metadata);
         // no panic
@@ -1285,6 +2323,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         require_valid_reserve_metadata(&e, &metadata);
     }
@@ -1308,6 +2348,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         require_valid_reserve_metadata(&e, &metadata);
     }
@@ -1331,6 +2373,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         require_valid_reserve_metadata(&e, &metadata);
     }
@@ -1354,6 +2398,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         require_valid_reserve_metadata(&e, &metadata);
     }
@@ -1377,6 +2423,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         require_valid_reserve_metadata(&e, &metadata);
     }
@@ -1400,6 +2448,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         require_valid_reserve_metadata(&e, &metadata);
     }
@@ -1423,6 +2473,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         require_valid_reserve_metadata(&e, &metadata);
     }
@@ -1446,6 +2498,8 @@ mod tests {
             reactivity: 100,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         require_valid_reserve_metadata(&e, &metadata);
     }
@@ -1469,6 +2523,33 @@ mod tests {
             reactivity: 0_0001001,
             supply_cap: 1000000000000000000,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
+        };
+        require_valid_reserve_metadata(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_validate_reserve_metadata_validates_conc_threshold() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+            conc_threshold: 1_0000000,
+            conc_haircut: 0_4000000,
         };
         require_valid_reserve_metadata(&e, &metadata);
     }
@@ -0,0 +1,194 @@
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{constants::SCALAR_12, errors::PoolError, storage};
+
+use super::{balance_delta::transfer_in, Pool};
+
+/// Donate tokens to the suppliers of a reserve. The donation is distributed to suppliers by
+/// increasing the reserve's bRate, so no portion of it is credited to the backstop.
+///
+/// Returns the amount of tokens donated and the new bRate of the reserve
+///
+/// ### Arguments
+/// * `from` - The address donating tokens
+/// * `asset` - The underlying asset of the reserve
+/// * `amount` - The amount of tokens to donate
+///
+/// ### Panics
+/// * If the amount is not positive
+/// * If the reserve has no suppliers to donate to
+pub fn execute_donate_to_reserve(
+    e: &Env,
+    from: &Address,
+    asset: &Address,
+    amount: i128,
+) -> (i128, i128) {
+    if amount <= 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut pool = Pool::load(e);
+    let mut reserve = pool.load_reserve(e, asset, true);
+    if reserve.data.b_supply == 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let donated = if storage::get_balance_delta(e, asset) {
+        transfer_in(e, asset, from, amount, false)
+    } else {
+        TokenClient::new(e, asset).transfer(from, &e.current_contract_address(), &amount);
+        amount
+    };
+
+    reserve.data.b_rate =
+        (reserve.total_supply(e) + donated).fixed_div_floor(e, &reserve.data.b_supply, &SCALAR_12);
+    let b_rate = reserve.data.b_rate;
+    pool.cache_reserve(reserve);
+    pool.store_cached_reserves(e);
+    (donated, b_rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constants::SCALAR_7, storage::PoolConfig, testutils};
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+
+    fn setup_pool_config(e: &Env, pool: &Address) {
+        let (oracle, _) = testutils::create_mock_oracle(e);
+        e.as_contract(pool, || {
+            storage::set_pool_config(
+                e,
+                &PoolConfig {
+                    oracle,
+                    min_collateral: 1_0000000,
+                    bstop_rate: 0_1000000,
+                    status: 0,
+                    max_positions: 4,
+                },
+            );
+        });
+    }
+
+    #[test]
+    fn test_execute_donate_to_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 100,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        setup_pool_config(&e, &pool);
+
+        let (underlying, underlying_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_rate = 1_000_000_000_000;
+        reserve_data.d_rate = 1_000_000_000_000;
+        reserve_data.d_supply = 500 * SCALAR_7;
+        reserve_data.b_supply = 1000 * SCALAR_7;
+        reserve_data.backstop_credit = 500;
+        reserve_data.last_time = 100;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+        underlying_client.mint(&samwise, &(10 * SCALAR_7));
+
+        let pool_balance = underlying_client.balance(&pool);
+        e.as_contract(&pool, || {
+            let (donated, b_rate) =
+                execute_donate_to_reserve(&e, &samwise, &underlying, 10 * SCALAR_7);
+            assert_eq!(donated, 10 * SCALAR_7);
+            assert_eq!(b_rate, 1_010_000_000_000);
+
+            let new_reserve_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(new_reserve_data.b_rate, 1_010_000_000_000);
+            assert_eq!(new_reserve_data.b_supply, 1000 * SCALAR_7);
+            assert_eq!(new_reserve_data.backstop_credit, 500);
+        });
+        assert_eq!(underlying_client.balance(&samwise), 0);
+        assert_eq!(
+            underlying_client.balance(&pool),
+            pool_balance + 10 * SCALAR_7
+        );
+    }
+
+    #[test]
+    fn test_execute_donate_to_reserve_balance_delta() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        setup_pool_config(&e, &pool);
+
+        let (underlying, underlying_client) = testutils::create_fee_token(&e);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_rate = 1_000_000_000_000;
+        reserve_data.d_rate = 1_000_000_000_000;
+        reserve_data.d_supply = 0;
+        reserve_data.b_supply = 100 * SCALAR_7;
+        reserve_data.last_time = e.ledger().timestamp();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+        underlying_client.mint(&samwise, &(10 * SCALAR_7));
+
+        e.as_contract(&pool, || {
+            storage::set_balance_delta(&e, &underlying, &true);
+
+            // the token burns 1% of each transfer
+            let (donated, b_rate) =
+                execute_donate_to_reserve(&e, &samwise, &underlying, 10 * SCALAR_7);
+            assert_eq!(donated, 9_9000000);
+            assert_eq!(b_rate, 1_099_000_000_000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_donate_to_reserve_no_suppliers() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        setup_pool_config(&e, &pool);
+
+        let (underlying, underlying_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_supply = 0;
+        reserve_data.b_supply = 0;
+        reserve_data.last_time = e.ledger().timestamp();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+        underlying_client.mint(&samwise, &(10 * SCALAR_7));
+
+        e.as_contract(&pool, || {
+            execute_donate_to_reserve(&e, &samwise, &underlying, 10 * SCALAR_7);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_donate_to_reserve_zero_amount() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        setup_pool_config(&e, &pool);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            execute_donate_to_reserve(&e, &samwise, &underlying, 0);
+        });
+    }
+}
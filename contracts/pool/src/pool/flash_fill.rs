@@ -0,0 +1,283 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+use crate::{auctions, errors::PoolError, events::PoolEvents, storage};
+
+use super::{
+    pool::Pool, recall_shortfall, record_repay, require_not_supply_only, soft_liquidation::swap,
+    RequestType, User,
+};
+
+/// Fill a user liquidation auction without holding any of the bid asset. The filler takes on the
+/// auction's bid as liabilities, like a flash loan of the bid asset. The lot is withdrawn and sold
+/// through the DEX adapter for the bid asset, and the proceeds repay the liabilities taken on.
+/// Any proceeds left over are sent to the filler. The filler's positions are unchanged.
+///
+/// Only auctions with a single bid asset can be flash filled.
+///
+/// Returns the amount of the bid asset sent to the filler
+///
+/// ### Arguments
+/// * `filler` - The address filling the auction
+/// * `user` - The user being liquidated
+/// * `percent_filled` - The percentage of the auction to fill
+/// * `min_profit` - The minimum amount of the bid asset the filler must receive
+///
+/// ### Panics
+/// * If no DEX adapter is set, or the auction bids more than one asset
+/// * If the proceeds of the lot do not cover the bid plus `min_profit`
+pub fn execute_flash_fill_auction(
+    e: &Env,
+    filler: &Address,
+    user: &Address,
+    percent_filled: u64,
+    min_profit: i128,
+) -> i128 {
+    let dex_adapter = match storage::get_dex_adapter(e) {
+        Some(dex_adapter) => dex_adapter,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if filler == &e.current_contract_address() || min_profit < 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    require_not_supply_only(e, filler);
+    if storage::get_auction(e, &0, user).bid.len() != 1 {
+        panic_with_error!(e, PoolError::InvalidBid);
+    }
+
+    let mut pool = Pool::load(e);
+    pool.require_action_allowed(e, RequestType::FillUserLiquidationAuction as u32);
+    let mut filler_state = User::load(e, filler);
+
+    let hf_before = auctions::user_health(e, &mut pool, 0, user);
+    let filled_auction = auctions::fill(
+        e,
+        &mut pool,
+        0,
+        user,
+        &mut filler_state,
+        percent_filled,
+        false,
+    );
+    let hf_after = auctions::user_health(e, &mut pool, 0, user);
+    let (bid_asset, bid_d_tokens) = filled_auction.bid.iter().next().unwrap();
+
+    // withdraw the lot and sell it for the bid asset
+    let mut proceeds = 0;
+    let mut lot_assets: Vec<Address> = Vec::new(e);
+    for (asset, b_tokens) in filled_auction.lot.iter() {
+        let mut reserve = pool.load_reserve(e, &asset, true);
+        let amount = reserve.to_asset_from_b_token(e, b_tokens);
+        filler_state.remove_collateral(e, &mut reserve, b_tokens);
+        pool.cache_reserve(reserve);
+        lot_assets.push_back(asset.clone());
+
+        recall_shortfall(e, &asset, amount);
+        if asset == bid_asset {
+            proceeds += amount;
+        } else if amount > 0 {
+            proceeds += swap(e, &dex_adapter, &asset, &bid_asset, amount, 0);
+        }
+    }
+
+    // repay the liabilities taken on with the proceeds
+    let mut bid_reserve = pool.load_reserve(e, &bid_asset, true);
+    let cost = bid_reserve.to_asset_from_d_token(e, bid_d_tokens);
+    let profit = proceeds - cost;
+    if profit < min_profit {
+        panic_with_error!(e, PoolError::InsufficientFlashFillProceeds);
+    }
+    record_repay(
+        e,
+        filler,
+        bid_reserve.config.index,
+        bid_reserve.data.d_rate,
+        filler_state.get_liabilities(bid_reserve.config.index),
+        bid_d_tokens,
+    );
+    filler_state.remove_liabilities(e, &mut bid_reserve, bid_d_tokens);
+    pool.cache_reserve(bid_reserve);
+
+    for asset in lot_assets.iter() {
+        pool.load_reserve(e, &asset, false)
+            .require_utilization_below_100(e);
+    }
+
+    if profit > 0 {
+        TokenClient::new(e, &bid_asset).transfer(&e.current_contract_address(), filler, &profit);
+    }
+
+    pool.store_cached_reserves(e);
+    filler_state.store(e);
+
+    PoolEvents::fill_auction(
+        e,
+        0u32,
+        user.clone(),
+        filler.clone(),
+        percent_filled as i128,
+        filled_auction,
+        hf_before,
+        hf_after,
+    );
+    profit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        auctions::{AuctionData, AuctionType},
+        pool::Positions,
+        testutils,
+    };
+    use sep_40_oracle::testutils::Asset;
+    use sep_41_token::testutils::MockTokenClient;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger},
+        vec, Symbol,
+    };
+
+    /// Setup a pool with a user who holds 100 collateral tokens of reserve 0 and 95 liability
+    /// tokens of reserve 1, both priced at 1, with a liquidation auction of the user 200 blocks
+    /// old
+    fn setup_pool<'a>(
+        e: &Env,
+        user: &Address,
+        bid: &[(u32, i128)],
+    ) -> (Address, Address, Address, MockTokenClient<'a>) {
+        let (pool_address, oracle_client) = testutils::create_pool_with_oracle(e);
+        e.ledger().set_sequence_number(250);
+
+        let bombadil = Address::generate(e);
+        let dex_adapter = testutils::create_mock_dex_adapter(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.d_supply = 0;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.b_supply = 200_0000000;
+        reserve_data_1.d_supply = 100_0000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        underlying_1_client.mint(&dex_adapter, &100_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 1_0000000]);
+
+        let positions = Positions {
+            collateral: map![e, (0, 100_0000000)],
+            liabilities: map![e, (1, 95_0000000)],
+            supply: map![e],
+        };
+        let reserves = [underlying_0.clone(), underlying_1.clone()];
+        let mut auction_bid = map![e];
+        for (index, amount) in bid.iter() {
+            auction_bid.set(reserves[*index as usize].clone(), *amount);
+        }
+        let auction_data = AuctionData {
+            bid: auction_bid,
+            lot: map![e, (underlying_0.clone(), 55_0000000)],
+            block: 50,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(e, user, &positions);
+            storage::set_backstop(e, &Address::generate(e));
+            storage::set_dex_adapter(e, &dex_adapter);
+            storage::set_auction(
+                e,
+                &(AuctionType::UserLiquidation as u32),
+                user,
+                &auction_data,
+            );
+        });
+        (
+            pool_address,
+            underlying_0,
+            underlying_1,
+            underlying_1_client,
+        )
+    }
+
+    #[test]
+    fn test_execute_flash_fill_auction() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let (pool, _, _, underlying_1_client) = setup_pool(&e, &samwise, &[(1, 50_0000000)]);
+
+        e.as_contract(&pool, || {
+            let profit = execute_flash_fill_auction(&e, &frodo, &samwise, 100, 5_0000000);
+            assert_eq!(profit, 5_0000000);
+
+            // the filler's positions are unchanged
+            let frodo_positions = storage::get_user_positions(&e, &frodo);
+            assert_eq!(frodo_positions.collateral.len(), 0);
+            assert_eq!(frodo_positions.liabilities.len(), 0);
+
+            let samwise_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(samwise_positions.collateral.get_unchecked(0), 45_0000000);
+            assert_eq!(samwise_positions.liabilities.get_unchecked(1), 45_0000000);
+            assert!(!storage::has_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise
+            ));
+        });
+        assert_eq!(underlying_1_client.balance(&frodo), 5_0000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1236)")]
+    fn test_execute_flash_fill_auction_under_min_profit() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let (pool, _, _, _) = setup_pool(&e, &samwise, &[(1, 50_0000000)]);
+
+        e.as_contract(&pool, || {
+            execute_flash_fill_auction(&e, &frodo, &samwise, 100, 5_0000001);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1221)")]
+    fn test_execute_flash_fill_auction_multiple_bid_assets() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let (pool, _, _, _) = setup_pool(&e, &samwise, &[(0, 1_0000000), (1, 50_0000000)]);
+
+        e.as_contract(&pool, || {
+            execute_flash_fill_auction(&e, &frodo, &samwise, 100, 0);
+        });
+    }
+}
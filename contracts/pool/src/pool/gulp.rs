@@ -1,7 +1,9 @@
-use sep_41_token::TokenClient;
 use soroban_sdk::{Address, Env};
 
-use super::{Pool, RequestType, Reserve};
+use super::{
+    invariants::{expected_balance, held_balance},
+    Pool, RequestType, Reserve,
+};
 
 /// Gulps the excess tokens in the pool, determined by the difference between the pool token balance
 /// and the reserve total supply, backstop credit, and liabiltiies.
@@ -22,10 +24,7 @@ pub fn execute_gulp(e: &Env, asset: &Address) -> i128 {
     pool.require_action_allowed(e, RequestType::Borrow as u32);
 
     let mut reserve = Reserve::load(e, &pool.config, asset);
-    let pool_token_balance = TokenClient::new(e, asset).balance(&e.current_contract_address());
-    let reserve_token_balance =
-        reserve.total_supply(e) + reserve.data.backstop_credit - reserve.total_liabilities(e);
-    let token_balance_delta = pool_token_balance - reserve_token_balance;
+    let token_balance_delta = held_balance(e, &reserve) - expected_balance(e, &reserve);
     if token_balance_delta <= 0 {
         return 0;
     }
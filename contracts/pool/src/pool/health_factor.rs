@@ -1,9 +1,10 @@
 use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::Env;
+use trustbridge_common::decimal_scalar;
 
 use crate::{constants::SCALAR_7, storage};
 
-use super::{pool::Pool, Positions};
+use super::{concentration::apply_concentration_haircut, pool::Pool, Positions};
 
 pub struct PositionData {
     /// The effective collateral balance denominated in the base asset
@@ -19,13 +20,15 @@ pub struct PositionData {
 }
 
 impl PositionData {
-    /// Calculate the position data for a given set of of positions
+    /// Calculate the position data for a given set of of positions. Collateral and liabilities
+    /// are valued at the conservative side of any wide price confidence interval, and collateral
+    /// concentrated in a reserve takes that reserve's concentration haircut.
     ///
     /// ### Arguments
     /// * pool - The pool
     /// * positions - The positions to calculate the health factor for
     pub fn calculate_from_positions(e: &Env, pool: &mut Pool, positions: &Positions) -> Self {
-        let oracle_scalar = 10i128.pow(pool.load_price_decimals(e));
+        let oracle_scalar = decimal_scalar(pool.load_price_decimals(e));
 
         let reserve_list = storage::get_res_list(e);
         let mut collateral_base = 0;
@@ -39,11 +42,16 @@ impl PositionData {
                 continue;
             }
             let reserve = pool.load_reserve(e, &reserve_list.get_unchecked(i), false);
-            let asset_to_base = pool.load_price(e, &reserve.asset);
 
             if b_token_balance > 0 {
+                let asset_to_base = pool.load_collateral_price(e, &reserve.asset);
                 // append users effective collateral to collateral_base
-                let asset_collateral = reserve.to_effective_asset_from_b_token(e, b_token_balance);
+                let asset_collateral = apply_concentration_haircut(
+                    e,
+                    &reserve,
+                    b_token_balance,
+                    reserve.to_effective_asset_from_b_token(e, b_token_balance),
+                );
                 collateral_base +=
                     asset_to_base.fixed_mul_floor(e, &asset_collateral, &reserve.scalar);
                 collateral_raw += asset_to_base.fixed_mul_floor(
@@ -54,6 +62,7 @@ impl PositionData {
             }
 
             if d_token_balance > 0 {
+                let asset_to_base = pool.load_liability_price(e, &reserve.asset);
                 // append users effective liability to liability_base
                 let asset_liability = reserve.to_effective_asset_from_d_token(e, d_token_balance);
                 liability_base +=
@@ -206,6 +215,69 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_calculate_from_positions_wide_confidence() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_confidence_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 2;
+        testutils::create_reserve(&e, &pool, &underlying_2, &reserve_config, &reserve_data);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 0,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        // 5% confidence is over the threshold, 0.5% is not
+        oracle_client.set_price(&underlying_0, &1_0000000, &0_0500000);
+        oracle_client.set_price(&underlying_1, &1_0000000, &0_0500000);
+        oracle_client.set_price(&underlying_2, &1_0000000, &0_0050000);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+        };
+
+        let positions = Positions {
+            liabilities: map![&e, (1, 50_0000000)],
+            collateral: map![&e, (0, 100_0000000), (2, 10_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_confidence_threshold(&e, &0_0100000);
+            let mut pool = Pool::load(&e);
+            let position_data = PositionData::calculate_from_positions(&e, &mut pool, &positions);
+            assert_eq!(position_data.collateral_base, 78_7500000);
+            assert_eq!(position_data.collateral_raw, 105_0000000);
+            assert_eq!(position_data.liability_raw, 52_5000000);
+        });
+    }
+
     #[test]
     fn test_as_health_factor_rounds_floor() {
         let e = Env::default();
@@ -0,0 +1,564 @@
+use cast::i128;
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    auctions::AuctionData,
+    constants::{INSURANCE_WAITING_PERIOD, MAX_INSURANCE_PREMIUM, SCALAR_12, SCALAR_7},
+    errors::PoolError,
+    storage::{self, Coverage},
+};
+
+use super::{pool::Pool, recall_shortfall, Reserve, User};
+
+/// Set the fraction of a covered supplier's interest paid to the insurance fund
+///
+/// ### Panics
+/// If the premium is over `MAX_INSURANCE_PREMIUM`
+pub fn execute_set_insurance_premium(e: &Env, premium: u32) {
+    if premium > MAX_INSURANCE_PREMIUM {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_insurance_premium(e, &premium);
+}
+
+/// Opt a supplier into or out of coverage from a reserve's insurance fund. Covered suppliers pay
+/// a premium from the interest their bTokens earn, and can claim any loss of their bTokens' value
+/// from the fund. Coverage takes effect after `INSURANCE_WAITING_PERIOD`, so a supplier can not
+/// opt in just ahead of a known loss.
+///
+/// ### Arguments
+/// * `from` - The supplier
+/// * `asset` - The underlying asset of the reserve
+/// * `covered` - If the supplier should be covered
+///
+/// ### Panics
+/// If opting out with an unclaimed loss
+pub fn execute_set_coverage(e: &Env, from: &Address, asset: &Address, covered: bool) {
+    let mut pool = Pool::load(e);
+    let mut user = User::load(e, from);
+    let mut reserve = pool.load_reserve(e, asset, true);
+    accrue_coverage(e, &mut user, &mut reserve);
+
+    match storage::get_coverage(e, from, &reserve.config.index) {
+        Some(coverage) if !covered => {
+            if coverage.claimable > 0 {
+                panic_with_error!(e, PoolError::BadRequest);
+            }
+            storage::del_coverage(e, from, &reserve.config.index);
+        }
+        None if covered => {
+            storage::set_coverage(
+                e,
+                from,
+                &reserve.config.index,
+                &Coverage {
+                    b_rate: reserve.data.b_rate,
+                    claimable: 0,
+                    start: e.ledger().timestamp() + INSURANCE_WAITING_PERIOD,
+                },
+            );
+        }
+        _ => {}
+    }
+
+    pool.cache_reserve(reserve);
+    pool.store_cached_reserves(e);
+    user.store(e);
+}
+
+/// Claim a covered supplier's losses from a reserve's insurance fund. Claims are paid in the
+/// order they are made, up to the amount held by the fund.
+///
+/// Returns the amount of underlying claimed
+///
+/// ### Arguments
+/// * `from` - The supplier
+/// * `asset` - The underlying asset of the reserve
+/// * `to` - The address receiving the claim
+///
+/// ### Panics
+/// If the supplier is not covered, or there is nothing to claim
+pub fn execute_claim_coverage(e: &Env, from: &Address, asset: &Address, to: &Address) -> i128 {
+    let mut pool = Pool::load(e);
+    let mut user = User::load(e, from);
+    let mut reserve = pool.load_reserve(e, asset, true);
+    accrue_coverage(e, &mut user, &mut reserve);
+
+    let mut coverage = match storage::get_coverage(e, from, &reserve.config.index) {
+        Some(coverage) => coverage,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    let fund = storage::get_insurance_fund(e, asset);
+    let amount = coverage.claimable.min(fund);
+    if amount <= 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    coverage.claimable -= amount;
+    storage::set_coverage(e, from, &reserve.config.index, &coverage);
+    storage::set_insurance_fund(e, asset, &(fund - amount));
+
+    pool.cache_reserve(reserve);
+    pool.store_cached_reserves(e);
+    user.store(e);
+
+    recall_shortfall(e, asset, amount);
+    TokenClient::new(e, asset).transfer(&e.current_contract_address(), to, &amount);
+    amount
+}
+
+/// Accrue a covered supplier's coverage for a reserve to the reserve's current b_rate. If the
+/// b_rate increased, the premium is charged by burning bTokens, from supply before collateral,
+/// and is added to the insurance fund. If the b_rate decreased, the loss of the supplier's bTokens'
+/// value becomes claimable. Neither happens before the coverage takes effect.
+///
+/// This must be called before the supplier's bTokens for the reserve change.
+///
+/// ### Arguments
+/// * `user` - The supplier
+/// * `reserve` - The reserve, updated to the current ledger
+pub fn accrue_coverage(e: &Env, user: &mut User, reserve: &mut Reserve) {
+    let index = reserve.config.index;
+    let mut coverage = match storage::get_coverage(e, &user.address, &index) {
+        Some(coverage) => coverage,
+        None => return,
+    };
+    if coverage.b_rate == reserve.data.b_rate {
+        return;
+    }
+
+    let b_tokens = user.get_total_supply(index);
+    if e.ledger().timestamp() < coverage.start {
+        // the coverage is still in its waiting period
+    } else if reserve.data.b_rate > coverage.b_rate {
+        let interest =
+            b_tokens.fixed_mul_floor(e, &(reserve.data.b_rate - coverage.b_rate), &SCALAR_12);
+        let premium =
+            interest.fixed_mul_floor(e, &i128(storage::get_insurance_premium(e)), &SCALAR_7);
+        if premium > 0 {
+            // accrue the reserve's supply emissions before its b_supply drops
+            user.update_b_emissions(e, reserve, b_tokens);
+            let b_tokens_burnt = reserve.to_b_token_up(e, premium).min(b_tokens);
+            let supply = user.get_supply(index);
+            let from_supply = b_tokens_burnt.min(supply);
+            if from_supply == supply {
+                user.positions.supply.remove(index);
+            } else {
+                user.positions.supply.set(index, supply - from_supply);
+            }
+            let from_collateral = b_tokens_burnt - from_supply;
+            if from_collateral > 0 {
+                let collateral = user.get_collateral(index);
+                if from_collateral == collateral {
+                    user.positions.collateral.remove(index);
+                } else {
+                    user.positions
+                        .collateral
+                        .set(index, collateral - from_collateral);
+                }
+            }
+            reserve.data.b_supply -= b_tokens_burnt;
+
+            let fund = storage::get_insurance_fund(e, &reserve.asset);
+            storage::set_insurance_fund(e, &reserve.asset, &(fund + premium));
+        }
+    } else {
+        coverage.claimable +=
+            b_tokens.fixed_mul_floor(e, &(coverage.b_rate - reserve.data.b_rate), &SCALAR_12);
+    }
+    coverage.b_rate = reserve.data.b_rate;
+    storage::set_coverage(e, &user.address, &index, &coverage);
+}
+
+/// Accrue the coverage of a user being liquidated for each reserve in an auction's lot, and clamp
+/// the lot to the user's collateral left after any premium is charged against it. This must be
+/// called before the lot is scaled for a fill, so the filler is never credited more collateral
+/// than is removed from the user.
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `user` - The user being liquidated
+/// * `auction_data` - The auction, whose lot is clamped
+pub(crate) fn accrue_lot_coverage(
+    e: &Env,
+    pool: &mut Pool,
+    user: &Address,
+    auction_data: &mut AuctionData,
+) {
+    let mut user_state = User::load(e, user);
+    for (asset, amount) in auction_data.lot.clone().iter() {
+        let mut reserve = pool.load_reserve(e, &asset, true);
+        accrue_coverage(e, &mut user_state, &mut reserve);
+        let collateral = user_state.get_collateral(reserve.config.index);
+        if amount > collateral {
+            auction_data.lot.set(asset, collateral);
+        }
+        pool.cache_reserve(reserve);
+    }
+    user_state.store(e);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pool::Positions,
+        storage::{PoolConfig, ReserveEmissionData, UserEmissionData},
+        testutils,
+    };
+    use sep_41_token::testutils::MockTokenClient;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+    };
+
+    /// Setup a pool with a reserve with 100 tokens supplied and 75 borrowed
+    fn setup_pool<'a>(e: &Env) -> (Address, Address, MockTokenClient<'a>) {
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, _) = testutils::create_mock_oracle(e);
+
+        let (underlying, underlying_client) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            execute_set_insurance_premium(e, 0_1000000);
+        });
+        (pool, underlying, underlying_client)
+    }
+
+    /// Pass the coverage waiting period, and set the reserve's b_rate as of the new timestamp
+    fn set_b_rate_after_waiting_period(e: &Env, underlying: &Address, b_rate: i128) {
+        let timestamp = e.ledger().timestamp() + INSURANCE_WAITING_PERIOD;
+        e.ledger().set_timestamp(timestamp);
+        let mut reserve_data = storage::get_res_data(e, underlying);
+        reserve_data.b_rate = b_rate;
+        reserve_data.last_time = timestamp;
+        storage::set_res_data(e, underlying, &reserve_data);
+    }
+
+    #[test]
+    fn test_accrue_coverage_charges_premium() {
+        let e = Env::default();
+        let (pool, underlying, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e],
+                    collateral: map![&e, (0, 20_0000000)],
+                    supply: map![&e, (0, 1_0000000)],
+                },
+            );
+            execute_set_coverage(&e, &samwise, &underlying, true);
+
+            // the reserve earns 10% interest for suppliers
+            set_b_rate_after_waiting_period(&e, &underlying, 1_100_000_000_000);
+
+            let mut pool_state = Pool::load(&e);
+            let mut user = User::load(&e, &samwise);
+            let mut reserve = pool_state.load_reserve(&e, &underlying, false);
+            accrue_coverage(&e, &mut user, &mut reserve);
+
+            // 21 bTokens earned 2.1 tokens of interest, so 0.21 tokens are charged
+            assert_eq!(storage::get_insurance_fund(&e, &underlying), 0_2100000);
+            // 0.1909091 bTokens are burnt from supply
+            assert_eq!(user.get_supply(0), 0_8090909);
+            assert_eq!(user.get_collateral(0), 20_0000000);
+            assert_eq!(reserve.data.b_supply, 100_0000000 - 0_1909091);
+            let coverage = storage::get_coverage(&e, &samwise, &0).unwrap();
+            assert_eq!(coverage.b_rate, 1_100_000_000_000);
+            assert_eq!(coverage.claimable, 0);
+        });
+    }
+
+    #[test]
+    fn test_accrue_coverage_charges_collateral() {
+        let e = Env::default();
+        let (pool, underlying, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e],
+                    collateral: map![&e, (0, 20_0000000)],
+                    supply: map![&e, (0, 0_1000000)],
+                },
+            );
+            execute_set_coverage(&e, &samwise, &underlying, true);
+
+            set_b_rate_after_waiting_period(&e, &underlying, 1_100_000_000_000);
+
+            let mut pool_state = Pool::load(&e);
+            let mut user = User::load(&e, &samwise);
+            let mut reserve = pool_state.load_reserve(&e, &underlying, false);
+            accrue_coverage(&e, &mut user, &mut reserve);
+
+            // 20.1 bTokens earned 2.01 tokens of interest, so 0.201 tokens are charged as
+            // 0.1827273 bTokens, first from supply and then from collateral
+            assert_eq!(user.get_supply(0), 0);
+            assert_eq!(user.get_collateral(0), 20_0000000 - 0_0827273);
+        });
+    }
+
+    #[test]
+    fn test_claim_coverage() {
+        let e = Env::default();
+        let (pool, underlying, underlying_client) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e],
+                    collateral: map![&e, (0, 20_0000000)],
+                    supply: map![&e],
+                },
+            );
+            execute_set_coverage(&e, &samwise, &underlying, true);
+            storage::set_insurance_fund(&e, &underlying, &1_0000000);
+            underlying_client.mint(&pool, &1_0000000);
+
+            // a default reduces the b_rate by 2%
+            set_b_rate_after_waiting_period(&e, &underlying, 0_980_000_000_000);
+
+            // the loss is 0.4 tokens
+            let claimed = execute_claim_coverage(&e, &samwise, &underlying, &frodo);
+            assert_eq!(claimed, 0_4000000);
+            assert_eq!(storage::get_insurance_fund(&e, &underlying), 0_6000000);
+            let coverage = storage::get_coverage(&e, &samwise, &0).unwrap();
+            assert_eq!(coverage.claimable, 0);
+            assert_eq!(coverage.b_rate, 0_980_000_000_000);
+        });
+        assert_eq!(underlying_client.balance(&frodo), 0_4000000);
+    }
+
+    #[test]
+    fn test_claim_coverage_limited_by_fund() {
+        let e = Env::default();
+        let (pool, underlying, underlying_client) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e],
+                    collateral: map![&e, (0, 20_0000000)],
+                    supply: map![&e],
+                },
+            );
+            execute_set_coverage(&e, &samwise, &underlying, true);
+            storage::set_insurance_fund(&e, &underlying, &0_1000000);
+            underlying_client.mint(&pool, &0_1000000);
+
+            set_b_rate_after_waiting_period(&e, &underlying, 0_980_000_000_000);
+
+            let claimed = execute_claim_coverage(&e, &samwise, &underlying, &samwise);
+            assert_eq!(claimed, 0_1000000);
+            assert_eq!(storage::get_insurance_fund(&e, &underlying), 0);
+            let coverage = storage::get_coverage(&e, &samwise, &0).unwrap();
+            assert_eq!(coverage.claimable, 0_3000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_coverage_opt_out_with_claimable() {
+        let e = Env::default();
+        let (pool, underlying, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e],
+                    collateral: map![&e, (0, 20_0000000)],
+                    supply: map![&e],
+                },
+            );
+            execute_set_coverage(&e, &samwise, &underlying, true);
+
+            set_b_rate_after_waiting_period(&e, &underlying, 0_980_000_000_000);
+
+            execute_set_coverage(&e, &samwise, &underlying, false);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_insurance_premium_too_large() {
+        let e = Env::default();
+        let (pool, _, _) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_insurance_premium(&e, MAX_INSURANCE_PREMIUM + 1);
+        });
+    }
+
+    #[test]
+    fn test_accrue_coverage_waiting_period() {
+        let e = Env::default();
+        let (pool, underlying, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e],
+                    collateral: map![&e, (0, 20_0000000)],
+                    supply: map![&e],
+                },
+            );
+            execute_set_coverage(&e, &samwise, &underlying, true);
+            let coverage = storage::get_coverage(&e, &samwise, &0).unwrap();
+            assert_eq!(coverage.start, 12345 + INSURANCE_WAITING_PERIOD);
+
+            // a loss before the coverage takes effect is not covered
+            let mut reserve_data = storage::get_res_data(&e, &underlying);
+            reserve_data.b_rate = 0_980_000_000_000;
+            storage::set_res_data(&e, &underlying, &reserve_data);
+
+            let mut pool_state = Pool::load(&e);
+            let mut user = User::load(&e, &samwise);
+            let mut reserve = pool_state.load_reserve(&e, &underlying, false);
+            accrue_coverage(&e, &mut user, &mut reserve);
+
+            let coverage = storage::get_coverage(&e, &samwise, &0).unwrap();
+            assert_eq!(coverage.claimable, 0);
+            assert_eq!(coverage.b_rate, 0_980_000_000_000);
+        });
+    }
+
+    #[test]
+    fn test_accrue_coverage_updates_emissions() {
+        let e = Env::default();
+        let (pool, underlying, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e],
+                    collateral: map![&e, (0, 20_0000000)],
+                    supply: map![&e, (0, 1_0000000)],
+                },
+            );
+            execute_set_coverage(&e, &samwise, &underlying, true);
+            storage::set_res_emis_data(
+                &e,
+                &1,
+                &ReserveEmissionData {
+                    expiration: 20000000,
+                    eps: 0_10000000000000,
+                    index: 0,
+                    last_time: 12345,
+                },
+            );
+            storage::set_user_emissions(
+                &e,
+                &samwise,
+                &1,
+                &UserEmissionData {
+                    index: 0,
+                    accrued: 0,
+                },
+            );
+            set_b_rate_after_waiting_period(&e, &underlying, 1_100_000_000_000);
+
+            let mut pool_state = Pool::load(&e);
+            let mut user = User::load(&e, &samwise);
+            let mut reserve = pool_state.load_reserve(&e, &underlying, false);
+            accrue_coverage(&e, &mut user, &mut reserve);
+
+            // the supply emissions are accrued to the user before the premium is burnt
+            let emis_data = storage::get_res_emis_data(&e, &1).unwrap();
+            assert_eq!(emis_data.last_time, 12345 + INSURANCE_WAITING_PERIOD);
+            assert!(emis_data.index > 0);
+            let user_emis_data = storage::get_user_emissions(&e, &samwise, &1).unwrap();
+            assert_eq!(user_emis_data.index, emis_data.index);
+            assert!(user_emis_data.accrued > 0);
+            assert_eq!(reserve.data.b_supply, 100_0000000 - 0_1909091);
+        });
+    }
+
+    #[test]
+    fn test_accrue_lot_coverage_clamps_lot() {
+        let e = Env::default();
+        let (pool, underlying, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e],
+                    collateral: map![&e, (0, 20_0000000)],
+                    supply: map![&e],
+                },
+            );
+            execute_set_coverage(&e, &samwise, &underlying, true);
+            set_b_rate_after_waiting_period(&e, &underlying, 1_100_000_000_000);
+
+            let mut auction_data = AuctionData {
+                bid: map![&e],
+                lot: map![&e, (underlying.clone(), 20_0000000)],
+                block: 100,
+            };
+            let mut pool_state = Pool::load(&e);
+            accrue_lot_coverage(&e, &mut pool_state, &samwise, &mut auction_data);
+
+            // 20 bTokens earned 2 tokens of interest, so 0.2 tokens are charged as 0.1818182
+            // bTokens, and the lot is clamped to the collateral left
+            let collateral = storage::get_user_positions(&e, &samwise)
+                .collateral
+                .get_unchecked(0);
+            assert_eq!(collateral, 20_0000000 - 0_1818182);
+            assert_eq!(
+                auction_data.lot.get_unchecked(underlying.clone()),
+                collateral
+            );
+        });
+    }
+}
@@ -122,6 +122,8 @@ mod tests {
             supply_cap: 1000000000000000000,
             index: 0,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let ir_mod: i128 = 1_0000000;
 
@@ -160,6 +162,8 @@ mod tests {
             supply_cap: 1000000000000000000,
             index: 0,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let ir_mod: i128 = 1_0000000;
 
@@ -198,6 +202,8 @@ mod tests {
             supply_cap: 1000000000000000000,
             index: 0,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let ir_mod: i128 = 1_0000000;
 
@@ -236,6 +242,8 @@ mod tests {
             supply_cap: 1000000000000000000,
             index: 0,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let ir_mod: i128 = 9_9970000;
 
@@ -273,6 +281,8 @@ mod tests {
             supply_cap: 1000000000000000000,
             index: 0,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let ir_mod: i128 = 0_1500000;
 
@@ -310,6 +320,8 @@ mod tests {
             supply_cap: 1000000000000000000,
             index: 0,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let ir_mod: i128 = 1_0000000;
 
@@ -348,6 +360,8 @@ mod tests {
             supply_cap: 1000000000000000000,
             index: 0,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let ir_mod: i128 = 0_1000000;
 
@@ -386,6 +400,8 @@ mod tests {
             supply_cap: 1000000000000000000,
             index: 0,
             enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
         };
         let ir_mod: i128 = 1_0000000;
 
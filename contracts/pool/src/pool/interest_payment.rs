@@ -0,0 +1,555 @@
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{constants::SCALAR_12, errors::PoolError, storage};
+
+use super::{pool::Pool, soft_liquidation::swap, Reserve, User};
+
+/// Record a borrow against the user's principal for a reserve, and reset the user's borrow index
+/// to the reserve's current d_rate. Liabilities received from another position, like a filled
+/// auction bid, are recorded as a borrow of their current value.
+///
+/// ### Arguments
+/// * `user` - The user borrowing
+/// * `reserve_index` - The index of the reserve borrowed from
+/// * `d_rate` - The reserve's current d_rate
+/// * `d_tokens_before` - The user's d_tokens for the reserve before the borrow
+/// * `amount` - The amount of underlying borrowed
+pub fn record_borrow(
+    e: &Env,
+    user: &Address,
+    reserve_index: u32,
+    d_rate: i128,
+    d_tokens_before: i128,
+    amount: i128,
+) {
+    let principal = load_principal(e, user, reserve_index, d_rate, d_tokens_before);
+    storage::set_user_principal(e, user, &reserve_index, &(principal + amount));
+    storage::set_user_borrow_index(e, user, &reserve_index, &d_rate);
+}
+
+/// Record a repayment against the user's principal for a reserve. The principal is reduced in
+/// proportion to the d_tokens burnt, so interest and principal are repaid at the same rate. The
+/// user's borrow index is reset to the reserve's current d_rate, or cleared if the liability is
+/// fully repaid. Liabilities moved out of the position, like a liquidated bid or bad debt, are
+/// recorded as a repayment.
+///
+/// ### Arguments
+/// * `user` - The user repaying
+/// * `reserve_index` - The index of the reserve repaid
+/// * `d_rate` - The reserve's current d_rate
+/// * `d_tokens_before` - The user's d_tokens for the reserve before the repayment
+/// * `d_tokens_burnt` - The amount of d_tokens burnt by the repayment
+pub fn record_repay(
+    e: &Env,
+    user: &Address,
+    reserve_index: u32,
+    d_rate: i128,
+    d_tokens_before: i128,
+    d_tokens_burnt: i128,
+) {
+    let is_full_repay = d_tokens_burnt >= d_tokens_before;
+    let borrow_index = if is_full_repay { 0 } else { d_rate };
+    storage::set_user_borrow_index(e, user, &reserve_index, &borrow_index);
+
+    let principal = load_principal(e, user, reserve_index, d_rate, d_tokens_before);
+    if principal == 0 {
+        return;
+    }
+    let new_principal = if is_full_repay {
+        0
+    } else {
+        principal - principal.fixed_mul_floor(e, &d_tokens_burnt, &d_tokens_before)
+    };
+    storage::set_user_principal(e, user, &reserve_index, &new_principal);
+}
+
+/// Calculate the interest a user has been charged on their liabilities for a reserve since they
+/// last borrowed or repaid, in the underlying asset
+///
+/// ### Arguments
+/// * `user` - The user
+/// * `reserve` - The reserve the user has borrowed from
+/// * `d_tokens` - The user's d_tokens for the reserve
+pub fn calc_interest_since_index(
+    e: &Env,
+    user: &Address,
+    reserve: &Reserve,
+    d_tokens: i128,
+) -> i128 {
+    let borrow_index = storage::get_user_borrow_index(e, user, &reserve.config.index);
+    if borrow_index == 0 || d_tokens == 0 {
+        return 0;
+    }
+    d_tokens.fixed_mul_floor(e, &(reserve.data.d_rate - borrow_index), &SCALAR_12)
+}
+
+/// Calculate the interest a user has accrued on their liabilities for a reserve, in the
+/// underlying asset
+///
+/// ### Arguments
+/// * `user` - The user
+/// * `reserve` - The reserve the user has borrowed from
+/// * `d_tokens` - The user's d_tokens for the reserve
+pub fn calc_accrued_interest(e: &Env, user: &Address, reserve: &Reserve, d_tokens: i128) -> i128 {
+    let debt = reserve.to_asset_from_d_token(e, d_tokens);
+    let principal = load_principal(e, user, reserve.config.index, reserve.data.d_rate, d_tokens);
+    debt - principal.min(debt)
+}
+
+/// Load the user's principal for a reserve. A liability opened before principals were tracked has
+/// no principal recorded, so its current value is used as the principal.
+///
+/// ### Arguments
+/// * `user` - The user
+/// * `reserve_index` - The index of the reserve
+/// * `d_rate` - The reserve's current d_rate
+/// * `d_tokens` - The user's d_tokens for the reserve
+fn load_principal(
+    e: &Env,
+    user: &Address,
+    reserve_index: u32,
+    d_rate: i128,
+    d_tokens: i128,
+) -> i128 {
+    if d_tokens == 0 {
+        return 0;
+    }
+    match storage::get_user_principal(e, user, &reserve_index) {
+        0 => d_tokens.fixed_mul_floor(e, &d_rate, &SCALAR_12),
+        principal => principal,
+    }
+}
+
+/// Repay the interest accrued on a user's liabilities with a different asset. The payment asset is
+/// swapped for the debt asset through the DEX adapter, and any debt asset received beyond the
+/// accrued interest is refunded to the user. The principal of the liability is unchanged.
+///
+/// Returns (amount of debt asset repaid, d_tokens burnt)
+///
+/// ### Arguments
+/// * `from` - The user paying the interest
+/// * `debt_asset` - The liability asset the interest accrued on
+/// * `pay_asset` - The asset used to pay the interest
+/// * `amount_in` - The amount of the payment asset to swap
+/// * `min_debt_out` - The minimum amount of the debt asset to receive from the swap
+///
+/// ### Panics
+/// If no DEX adapter is set, the payment asset is the debt asset, or the user has no accrued interest
+pub fn execute_repay_interest(
+    e: &Env,
+    from: &Address,
+    debt_asset: &Address,
+    pay_asset: &Address,
+    amount_in: i128,
+    min_debt_out: i128,
+) -> (i128, i128) {
+    let dex_adapter = match storage::get_dex_adapter(e) {
+        Some(dex_adapter) => dex_adapter,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if from == &e.current_contract_address() || pay_asset == debt_asset || amount_in <= 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let mut pool = Pool::load(e);
+    let mut user = User::load(e, from);
+    let mut reserve = pool.load_reserve(e, debt_asset, true);
+    let d_tokens = user.get_liabilities(reserve.config.index);
+    let interest = calc_accrued_interest(e, from, &reserve, d_tokens);
+    if interest <= 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    TokenClient::new(e, pay_asset).transfer(from, &e.current_contract_address(), &amount_in);
+    let debt_out = swap(
+        e,
+        &dex_adapter,
+        pay_asset,
+        debt_asset,
+        amount_in,
+        min_debt_out,
+    );
+
+    let repaid = debt_out.min(interest);
+    let d_tokens_burnt = reserve.to_d_token_down(e, repaid).min(d_tokens);
+    user.remove_liabilities(e, &mut reserve, d_tokens_burnt);
+    storage::set_user_borrow_index(e, from, &reserve.config.index, &reserve.data.d_rate);
+    pool.cache_reserve(reserve);
+
+    let excess = debt_out - repaid;
+    if excess > 0 {
+        TokenClient::new(e, debt_asset).transfer(&e.current_contract_address(), from, &excess);
+    }
+
+    pool.store_cached_reserves(e);
+    user.store(e);
+
+    (repaid, d_tokens_burnt)
+}
+
+/// Forgive interest accrued on a user's liabilities without payment. The forgiven interest is
+/// paid for by the reserve's backstop credit, so suppliers are unaffected. The principal of the
+/// liability is unchanged.
+///
+/// Returns the d_tokens burnt
+///
+/// ### Arguments
+/// * `user` - The user whose interest is forgiven
+/// * `asset` - The liability asset the interest accrued on
+/// * `amount` - The amount of interest to forgive, in the underlying asset
+///
+/// ### Panics
+/// If the amount is not positive, or is more than the user's accrued interest or the reserve's
+/// backstop credit
+pub fn execute_forgive_interest(e: &Env, user: &Address, asset: &Address, amount: i128) -> i128 {
+    if amount <= 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let mut pool = Pool::load(e);
+    let mut user_state = User::load(e, user);
+    let mut reserve = pool.load_reserve(e, asset, true);
+    let d_tokens = user_state.get_liabilities(reserve.config.index);
+    let interest = calc_accrued_interest(e, user, &reserve, d_tokens);
+    if amount > interest || amount > reserve.data.backstop_credit {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let d_tokens_burnt = reserve.to_d_token_down(e, amount).min(d_tokens);
+    user_state.remove_liabilities(e, &mut reserve, d_tokens_burnt);
+    reserve.data.backstop_credit -= amount;
+    storage::set_user_borrow_index(e, user, &reserve.config.index, &reserve.data.d_rate);
+
+    pool.cache_reserve(reserve);
+    pool.store_cached_reserves(e);
+    user_state.store(e);
+
+    d_tokens_burnt
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pool::Positions,
+        storage::PoolConfig,
+        testutils::{self, create_pool},
+    };
+
+    use super::*;
+    use sep_41_token::testutils::MockTokenClient;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+    };
+
+    /// Setup a pool with a user who borrowed 100 tokens of reserve 1, which has since accrued
+    /// 10 tokens of interest, and a payment token the user holds 100 of
+    fn setup_pool<'a>(
+        e: &Env,
+        user: &Address,
+    ) -> (
+        Address,
+        Address,
+        Address,
+        MockTokenClient<'a>,
+        MockTokenClient<'a>,
+    ) {
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let pool_address = create_pool(e);
+        let (oracle_address, _) = testutils::create_mock_oracle(e);
+        let dex_adapter = testutils::create_mock_dex_adapter(e);
+
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.b_supply = 200_0000000;
+        reserve_data_1.d_supply = 100_0000000;
+        reserve_data_1.d_rate = 1_100_000_000_000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        underlying_1_client.mint(&dex_adapter, &100_0000000);
+
+        let (pay_token, pay_token_client) = testutils::create_token_contract(e, &bombadil);
+        pay_token_client.mint(user, &100_0000000);
+
+        let positions = Positions {
+            collateral: map![e, (0, 100_0000000)],
+            liabilities: map![e, (1, 100_0000000)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(e, user, &positions);
+            storage::set_user_principal(e, user, &1, &100_0000000);
+            storage::set_pool_config(e, &pool_config);
+            storage::set_dex_adapter(e, &dex_adapter);
+        });
+        (
+            pool_address,
+            underlying_1,
+            pay_token,
+            underlying_1_client,
+            pay_token_client,
+        )
+    }
+
+    #[test]
+    fn test_record_borrow_and_repay() {
+        let e = Env::default();
+        let pool = create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            record_borrow(&e, &samwise, 1, SCALAR_12, 0, 100_0000000);
+            record_borrow(&e, &samwise, 1, SCALAR_12, 90_0000000, 50_0000000);
+            assert_eq!(storage::get_user_principal(&e, &samwise, &1), 150_0000000);
+
+            // repay a third of the d_tokens
+            record_repay(&e, &samwise, 1, SCALAR_12, 135_0000000, 45_0000000);
+            assert_eq!(storage::get_user_principal(&e, &samwise, &1), 100_0000000);
+
+            // a new borrow after the liability was closed resets the principal
+            record_borrow(&e, &samwise, 1, SCALAR_12, 0, 20_0000000);
+            assert_eq!(storage::get_user_principal(&e, &samwise, &1), 20_0000000);
+
+            record_repay(&e, &samwise, 1, SCALAR_12, 18_0000000, 18_0000000);
+            assert_eq!(storage::get_user_principal(&e, &samwise, &1), 0);
+        });
+    }
+
+    #[test]
+    fn test_legacy_position_principal() {
+        let e = Env::default();
+        let pool = create_pool(&e);
+        let samwise = Address::generate(&e);
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.config.index = 1;
+        reserve.data.d_rate = 1_100_000_000_000;
+
+        e.as_contract(&pool, || {
+            // a liability opened before principals were tracked has no interest accrued
+            assert_eq!(
+                calc_accrued_interest(&e, &samwise, &reserve, 100_0000000),
+                0
+            );
+
+            // the principal starts from the liability's current value
+            record_borrow(&e, &samwise, 1, 1_100_000_000_000, 100_0000000, 10_0000000);
+            assert_eq!(storage::get_user_principal(&e, &samwise, &1), 120_0000000);
+
+            let samwise_2 = Address::generate(&e);
+            record_repay(
+                &e,
+                &samwise_2,
+                1,
+                1_100_000_000_000,
+                100_0000000,
+                50_0000000,
+            );
+            assert_eq!(storage::get_user_principal(&e, &samwise_2, &1), 55_0000000);
+        });
+    }
+
+    #[test]
+    fn test_borrow_index() {
+        let e = Env::default();
+        let pool = create_pool(&e);
+        let samwise = Address::generate(&e);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        let mut reserve = Reserve {
+            asset: Address::generate(&e),
+            config: reserve_config,
+            data: reserve_data.clone(),
+            scalar: 1_0000000,
+            protocol_fee: 0,
+            util_smoothing: None,
+        };
+
+        e.as_contract(&pool, || {
+            record_borrow(&e, &samwise, 0, SCALAR_12, 0, 100_0000000);
+            assert_eq!(storage::get_user_borrow_index(&e, &samwise, &0), SCALAR_12);
+
+            reserve_data.d_rate = 1_050_000_000_000;
+            reserve.data = reserve_data.clone();
+            assert_eq!(
+                calc_interest_since_index(&e, &samwise, &reserve, 100_0000000),
+                5_0000000
+            );
+
+            // a partial repayment resets the index
+            record_repay(&e, &samwise, 0, 1_050_000_000_000, 100_0000000, 50_0000000);
+            assert_eq!(
+                calc_interest_since_index(&e, &samwise, &reserve, 50_0000000),
+                0
+            );
+
+            // a full repayment clears the index
+            record_repay(&e, &samwise, 0, 1_050_000_000_000, 50_0000000, 50_0000000);
+            assert_eq!(storage::get_user_borrow_index(&e, &samwise, &0), 0);
+            assert_eq!(calc_interest_since_index(&e, &samwise, &reserve, 0), 0);
+        });
+    }
+
+    #[test]
+    fn test_execute_repay_interest() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_1, pay_token, underlying_1_client, pay_token_client) =
+            setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            let reserve = Reserve::load(&e, &storage::get_pool_config(&e), &underlying_1);
+            assert_eq!(reserve.data.d_rate, 1_100_000_000_000);
+            assert_eq!(
+                calc_accrued_interest(&e, &samwise, &reserve, 100_0000000),
+                10_0000000
+            );
+
+            let (repaid, d_tokens_burnt) = execute_repay_interest(
+                &e,
+                &samwise,
+                &underlying_1,
+                &pay_token,
+                15_0000000,
+                15_0000000,
+            );
+            assert_eq!(repaid, 10_0000000);
+            assert_eq!(d_tokens_burnt, 9_0909090);
+
+            let positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(positions.liabilities.get_unchecked(1), 90_9090910);
+            // the principal is untouched
+            assert_eq!(storage::get_user_principal(&e, &samwise, &1), 100_0000000);
+
+            let reserve_data = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_data.d_supply, 90_9090910);
+        });
+        assert_eq!(pay_token_client.balance(&samwise), 85_0000000);
+        assert_eq!(underlying_1_client.balance(&samwise), 5_0000000);
+        assert_eq!(underlying_1_client.balance(&pool), 100_0000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_repay_interest_no_interest() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_1, pay_token, _, _) = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            storage::set_user_principal(&e, &samwise, &1, &110_0000000);
+            execute_repay_interest(&e, &samwise, &underlying_1, &pay_token, 15_0000000, 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_repay_interest_same_asset() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_1, _, _, _) = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            execute_repay_interest(&e, &samwise, &underlying_1, &underlying_1, 15_0000000, 0);
+        });
+    }
+
+    #[test]
+    fn test_execute_forgive_interest() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_1, _, underlying_1_client, _) = setup_pool(&e, &samwise);
+        let pool_balance = underlying_1_client.balance(&pool);
+
+        e.as_contract(&pool, || {
+            let mut reserve_data = storage::get_res_data(&e, &underlying_1);
+            reserve_data.backstop_credit = 20_0000000;
+            storage::set_res_data(&e, &underlying_1, &reserve_data);
+
+            let d_tokens_burnt = execute_forgive_interest(&e, &samwise, &underlying_1, 10_0000000);
+            assert_eq!(d_tokens_burnt, 9_0909090);
+
+            let positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(positions.liabilities.get_unchecked(1), 90_9090910);
+            // the principal is untouched
+            assert_eq!(storage::get_user_principal(&e, &samwise, &1), 100_0000000);
+
+            let reserve_data = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_data.d_supply, 90_9090910);
+            assert_eq!(reserve_data.backstop_credit, 10_0000000);
+        });
+        // no tokens are moved
+        assert_eq!(underlying_1_client.balance(&samwise), 0);
+        assert_eq!(underlying_1_client.balance(&pool), pool_balance);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_forgive_interest_over_interest() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_1, _, _, _) = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            let mut reserve_data = storage::get_res_data(&e, &underlying_1);
+            reserve_data.backstop_credit = 20_0000000;
+            storage::set_res_data(&e, &underlying_1, &reserve_data);
+
+            execute_forgive_interest(&e, &samwise, &underlying_1, 10_0000001);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_forgive_interest_over_backstop_credit() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_1, _, _, _) = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            let mut reserve_data = storage::get_res_data(&e, &underlying_1);
+            reserve_data.backstop_credit = 5_0000000;
+            storage::set_res_data(&e, &underlying_1, &reserve_data);
+
+            execute_forgive_interest(&e, &samwise, &underlying_1, 10_0000000);
+        });
+    }
+}
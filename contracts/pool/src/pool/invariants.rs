@@ -0,0 +1,125 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::storage;
+
+use super::{deployed_to_strategy, Pool, Reserve};
+
+/// The result of checking a reserve's accounting against the tokens held by the pool
+#[derive(Clone)]
+#[contracttype]
+pub struct InvariantCheck {
+    pub b_supply: i128, // the total bToken supply of the reserve
+    pub d_supply: i128, // the total dToken supply of the reserve
+    pub held: i128,     // the underlying tokens held on behalf of the reserve
+    pub expected: i128, // the underlying tokens the reserve's accounting requires to be held
+    pub delta: i128,    // the tokens held in excess of the expected tokens
+    pub passed: bool,   // if the pool holds at least the expected tokens
+}
+
+/// Fetch the underlying tokens held on behalf of a reserve. Liquidity deployed to the reserve's
+/// strategy is still held on behalf of the reserve, the insurance fund is held on behalf of
+/// covered suppliers, and protocol fees are held on behalf of the treasury.
+///
+/// ### Arguments
+/// * `reserve` - The reserve
+pub(crate) fn held_balance(e: &Env, reserve: &Reserve) -> i128 {
+    TokenClient::new(e, &reserve.asset).balance(&e.current_contract_address())
+        + deployed_to_strategy(e, &reserve.asset)
+        - storage::get_insurance_fund(e, &reserve.asset)
+        - reserve.protocol_fee_credit(e)
+}
+
+/// Fetch the underlying tokens a reserve's accounting requires to be held, determined by the
+/// reserve total supply, backstop credit, and liabilities
+///
+/// ### Arguments
+/// * `reserve` - The reserve
+pub(crate) fn expected_balance(e: &Env, reserve: &Reserve) -> i128 {
+    reserve.total_supply(e) + reserve.data.backstop_credit - reserve.total_liabilities(e)
+}
+
+/// Recompute a reserve's accounting from its bToken and dToken supplies and check it against
+/// the underlying tokens held by the pool. The check passes if the pool holds at least the
+/// expected tokens, as any excess tokens can be gulped to the backstop. Nothing is stored.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+///
+/// ### Panics
+/// If the asset is not a reserve
+pub fn execute_verify_invariants(e: &Env, asset: &Address) -> InvariantCheck {
+    let pool = Pool::load(e);
+    let reserve = Reserve::load(e, &pool.config, asset);
+
+    let held = held_balance(e, &reserve);
+    let expected = expected_balance(e, &reserve);
+    let delta = held - expected;
+    InvariantCheck {
+        b_supply: reserve.data.b_supply,
+        d_supply: reserve.data.d_supply,
+        held,
+        expected,
+        delta,
+        passed: delta >= 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::SCALAR_7, testutils};
+    use soroban_sdk::testutils::Address as _;
+
+    /// Setup a pool with a reserve with 1000 tokens supplied, 500 borrowed, and 500 of backstop
+    /// credit, with the pool holding exactly the expected tokens
+    fn setup_pool(e: &Env) -> (Address, Address) {
+        let (pool, _) = testutils::create_pool_with_oracle(e);
+        let bombadil = Address::generate(e);
+
+        let (underlying, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_rate = 1_000_000_000_000;
+        reserve_data.d_rate = 1_000_000_000_000;
+        reserve_data.d_supply = 500 * SCALAR_7;
+        reserve_data.b_supply = 1000 * SCALAR_7;
+        reserve_data.backstop_credit = 500;
+        reserve_data.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying, &reserve_config, &reserve_data);
+        (pool, underlying)
+    }
+
+    #[test]
+    fn test_verify_invariants() {
+        let e = Env::default();
+        let (pool, underlying) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            let check = execute_verify_invariants(&e, &underlying);
+            assert_eq!(check.b_supply, 1000 * SCALAR_7);
+            assert_eq!(check.d_supply, 500 * SCALAR_7);
+            assert_eq!(check.held, 500 * SCALAR_7 + 500);
+            assert_eq!(check.expected, 500 * SCALAR_7 + 500);
+            assert_eq!(check.delta, 0);
+            assert!(check.passed);
+        });
+    }
+
+    #[test]
+    fn test_verify_invariants_drift() {
+        let e = Env::default();
+        let (pool, underlying) = setup_pool(&e);
+
+        // tokens leave the pool without any change to the reserve's accounting
+        TokenClient::new(&e, &underlying).transfer(&pool, &Address::generate(&e), &1_0000000);
+        e.as_contract(&pool, || {
+            storage::set_insurance_fund(&e, &underlying, &0_5000000);
+
+            let check = execute_verify_invariants(&e, &underlying);
+            assert_eq!(check.held, 500 * SCALAR_7 + 500 - 1_5000000);
+            assert_eq!(check.expected, 500 * SCALAR_7 + 500);
+            assert_eq!(check.delta, -1_5000000);
+            assert!(!check.passed);
+        });
+    }
+}
@@ -0,0 +1,349 @@
+use cast::i128;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{Address, Env};
+
+use crate::{auctions::AuctionType, constants::SCALAR_7, storage};
+
+use super::{health_factor::PositionData, pool::Pool, User};
+
+/// The minimum health factor a submit leaves a user with, in 7 decimals
+const MIN_SUBMIT_HF: i128 = 1_0000100;
+
+/// Calculate the maximum amount of an asset a user can borrow in a single submit. The amount is
+/// limited by the user's health factor, the pool's minimum collateral, the user's maximum
+/// positions, and the reserve's maximum utilization.
+///
+/// Returns the maximum amount of underlying tokens, or 0 if the user cannot borrow the asset
+///
+/// ### Arguments
+/// * `user` - The user borrowing
+/// * `asset` - The asset to borrow
+pub fn calc_max_borrow(e: &Env, user: &Address, asset: &Address) -> i128 {
+    let mut pool = Pool::load(e);
+    if pool.config.status > 1
+        || storage::has_auction(e, &(AuctionType::UserLiquidation as u32), user)
+    {
+        return 0;
+    }
+    let reserve = pool.load_reserve(e, asset, false);
+    if !reserve.config.enabled {
+        return 0;
+    }
+
+    let user_state = User::load(e, user);
+    if user_state.get_liabilities(reserve.config.index) == 0
+        && user_state.positions.effective_count() + 1 > pool.config.max_positions
+    {
+        return 0;
+    }
+    let position_data = PositionData::calculate_from_positions(e, &mut pool, &user_state.positions);
+    if position_data.collateral_base < pool.config.min_collateral {
+        return 0;
+    }
+
+    let max_liability_base =
+        position_data
+            .collateral_base
+            .fixed_div_floor(e, &MIN_SUBMIT_HF, &SCALAR_7);
+    let available_base = max_liability_base - position_data.liability_base;
+    if available_base <= 0 {
+        return 0;
+    }
+    let price = pool.load_liability_price(e, asset);
+    let health_limit = available_base
+        .fixed_div_floor(e, &price, &reserve.scalar)
+        .fixed_mul_floor(e, &i128(reserve.config.l_factor), &SCALAR_7);
+
+    let util_limit =
+        reserve
+            .total_supply(e)
+            .fixed_mul_floor(e, &i128(reserve.config.max_util), &SCALAR_7)
+            - reserve.total_liabilities(e);
+
+    health_limit.min(util_limit).max(0)
+}
+
+/// Calculate the maximum amount of an asset a user can withdraw in a single submit, across both
+/// their supply and collateral. Collateral withdrawals are limited by the user's health factor
+/// and the pool's minimum collateral, and all withdrawals are limited by the reserve's available
+/// liquidity.
+///
+/// Returns the maximum amount of underlying tokens, or 0 if the user cannot withdraw the asset
+///
+/// ### Arguments
+/// * `user` - The user withdrawing
+/// * `asset` - The asset to withdraw
+pub fn calc_max_withdraw(e: &Env, user: &Address, asset: &Address) -> i128 {
+    let mut pool = Pool::load(e);
+    if pool.is_shutdown || storage::has_auction(e, &(AuctionType::UserLiquidation as u32), user) {
+        return 0;
+    }
+    let reserve = pool.load_reserve(e, asset, false);
+    let user_state = User::load(e, user);
+    let supply = reserve.to_asset_from_b_token(e, user_state.get_supply(reserve.config.index));
+    let mut collateral =
+        reserve.to_asset_from_b_token(e, user_state.get_collateral(reserve.config.index));
+
+    // collateral is only limited if it backs liabilities
+    if collateral > 0 && user_state.has_liabilities() && reserve.config.c_factor > 0 {
+        let position_data =
+            PositionData::calculate_from_positions(e, &mut pool, &user_state.positions);
+        let required_base = position_data
+            .liability_base
+            .fixed_mul_ceil(e, &MIN_SUBMIT_HF, &SCALAR_7)
+            .max(pool.config.min_collateral);
+        let excess_base = (position_data.collateral_base - required_base).max(0);
+        // collateral without a price does not count towards the user's health
+        let price = pool.load_collateral_price(e, asset);
+        if price > 0 {
+            let excess = excess_base
+                .fixed_div_floor(e, &price, &reserve.scalar)
+                .fixed_div_floor(e, &i128(reserve.config.c_factor), &SCALAR_7);
+            collateral = collateral.min(excess);
+        }
+    }
+
+    let liquidity = reserve.total_supply(e) - reserve.total_liabilities(e);
+    (supply + collateral).min(liquidity).max(0)
+}
+
+/// Convert the pool's minimum collateral, which is denominated in the oracle's base asset, into
+/// the amount of an asset that must be supplied as collateral to meet it at current prices
+///
+/// Returns the amount of underlying tokens, or `i128::MAX` if the asset does not count as collateral
+/// or is valued at 0
+///
+/// ### Arguments
+/// * `asset` - The asset to denominate the minimum collateral in
+///
+/// ### Panics
+/// If the asset is not a reserve or does not have a valid price
+pub fn calc_min_collateral_in(e: &Env, asset: &Address) -> i128 {
+    let mut pool = Pool::load(e);
+    let reserve = pool.load_reserve(e, asset, false);
+    if reserve.config.c_factor == 0 {
+        return i128::MAX;
+    }
+    let price = pool.load_collateral_price(e, asset);
+    if price == 0 {
+        return i128::MAX;
+    }
+    pool.config
+        .min_collateral
+        .fixed_div_ceil(e, &price, &reserve.scalar)
+        .fixed_div_ceil(e, &i128(reserve.config.c_factor), &SCALAR_7)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        auctions::AuctionData,
+        pool::Positions,
+        storage::PoolConfig,
+        testutils::{self, create_pool},
+    };
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Symbol,
+    };
+
+    /// Setup a pool with two reserves priced at 1, where reserve 0 has 200 bTokens and 75
+    /// dTokens outstanding and reserve 1 has 100 bTokens and 75 dTokens outstanding. `user` has
+    /// 100 bTokens of reserve 0 as collateral, 10 bTokens of reserve 1 supplied, and 30 dTokens
+    /// of reserve 1 borrowed.
+    fn setup_pool(e: &Env, user: &Address) -> (Address, Address, Address) {
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let pool_address = create_pool(e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(e);
+
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_supply = 200_0000000;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        let positions = Positions {
+            collateral: map![e, (0, 100_0000000)],
+            liabilities: map![e, (1, 30_0000000)],
+            supply: map![e, (1, 10_0000000)],
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, user, &positions);
+        });
+        (pool_address, underlying_0, underlying_1)
+    }
+
+    #[test]
+    fn test_calc_max_borrow() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_0, underlying_1) = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            // collateral of 75 supports 74.99925 of liabilities, 40 of which are used, and
+            // borrowing 1 token adds 1.3333333 of liabilities
+            assert_eq!(calc_max_borrow(&e, &samwise, &underlying_0), 26_2494375);
+
+            // reserve 1 can only lend 20 more tokens before reaching 95% utilization
+            assert_eq!(calc_max_borrow(&e, &samwise, &underlying_1), 20_0000000);
+
+            // a user without collateral can't borrow
+            assert_eq!(
+                calc_max_borrow(&e, &Address::generate(&e), &underlying_0),
+                0
+            );
+        });
+    }
+
+    #[test]
+    fn test_calc_max_borrow_max_positions() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_0, underlying_1) = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            let mut pool_config = storage::get_pool_config(&e);
+            pool_config.max_positions = 2;
+            storage::set_pool_config(&e, &pool_config);
+
+            // a new liability position would exceed the max positions
+            assert_eq!(calc_max_borrow(&e, &samwise, &underlying_0), 0);
+            assert_eq!(calc_max_borrow(&e, &samwise, &underlying_1), 20_0000000);
+        });
+    }
+
+    #[test]
+    fn test_calc_max_withdraw() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_0, underlying_1) = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            // 40.0004 of the 75 collateral is required to back the liabilities, and withdrawing
+            // 1 token removes 0.75 of collateral
+            assert_eq!(calc_max_withdraw(&e, &samwise, &underlying_0), 46_6661333);
+
+            // supply is not limited by the user's health
+            assert_eq!(calc_max_withdraw(&e, &samwise, &underlying_1), 10_0000000);
+        });
+    }
+
+    #[test]
+    fn test_calc_max_withdraw_liquidity() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let (pool, _, underlying_1) = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &frodo,
+                &Positions {
+                    collateral: map![&e, (1, 40_0000000)],
+                    liabilities: map![&e],
+                    supply: map![&e],
+                },
+            );
+
+            // only 25 tokens of reserve 1 are not borrowed
+            assert_eq!(calc_max_withdraw(&e, &frodo, &underlying_1), 25_0000000);
+        });
+    }
+
+    #[test]
+    fn test_calc_min_collateral_in() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_0, underlying_1) = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            // 1 of effective collateral requires 1.3333334 tokens at a 0.75 collateral factor
+            assert_eq!(calc_min_collateral_in(&e, &underlying_0), 1_3333334);
+
+            let mut pool_config = storage::get_pool_config(&e);
+            pool_config.min_collateral = 10_0000000;
+            storage::set_pool_config(&e, &pool_config);
+            assert_eq!(calc_min_collateral_in(&e, &underlying_1), 13_3333334);
+        });
+    }
+
+    #[test]
+    fn test_calc_max_amounts_auction_in_progress() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, underlying_0, underlying_1) = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            storage::set_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &AuctionData {
+                    bid: map![&e, (underlying_1.clone(), 10_0000000)],
+                    lot: map![&e, (underlying_0.clone(), 10_0000000)],
+                    block: 50,
+                },
+            );
+
+            assert_eq!(calc_max_borrow(&e, &samwise, &underlying_0), 0);
+            assert_eq!(calc_max_withdraw(&e, &samwise, &underlying_0), 0);
+        });
+    }
+}
@@ -1,13 +1,50 @@
 mod actions;
 pub use actions::{FlashLoan, Request, RequestType};
 
+mod action_limits;
+pub use action_limits::execute_set_action_limits;
+
+mod withdraw_cooldown;
+pub use withdraw_cooldown::execute_set_withdraw_cooldown;
+pub(crate) use withdraw_cooldown::{record_borrow_ledger, require_withdraw_cooldown_passed};
+
 mod bad_debt;
-pub use bad_debt::{bad_debt, check_and_handle_backstop_bad_debt, check_and_handle_user_bad_debt};
+pub use bad_debt::{
+    bad_debt, check_and_handle_backstop_bad_debt, check_and_handle_user_bad_debt,
+    load_total_bad_debt,
+};
 
 mod config;
 pub use config::{
-    execute_cancel_queued_set_reserve, execute_initialize, execute_queue_set_reserve,
-    execute_set_reserve, execute_update_pool,
+    execute_cancel_queued_set_reserve, execute_cancel_set_interest_split,
+    execute_disable_interest_split, execute_initialize, execute_queue_set_interest_split,
+    execute_queue_set_reserve, execute_set_auction_min_blocks, execute_set_auction_start_offset,
+    execute_set_base_asset, execute_set_confidence_threshold, execute_set_interest_split,
+    execute_set_metadata, execute_set_reserve, execute_set_reserve_allowlist,
+    execute_set_retail_liquidation, execute_set_twap_pricing, execute_set_util_smoothing,
+    execute_update_pool,
+};
+
+mod account_lock;
+
+mod borrow_freeze;
+pub use borrow_freeze::execute_set_borrow_freeze;
+pub(crate) use borrow_freeze::require_borrow_not_frozen;
+
+mod supply_only;
+pub use supply_only::execute_set_supply_only;
+pub(crate) use supply_only::require_not_supply_only;
+
+mod signed_submit;
+pub use signed_submit::{
+    domain_separator, execute_submit_signed, hash_request, hash_requests, signed_submit_payload,
+    verify_submit_signature,
+};
+
+mod state_proof;
+pub use state_proof::{
+    calc_state_root, calc_user_state_root, load_state_snapshot, load_user_state_snapshot,
+    state_preimage, user_state_preimage, StateSnapshot, UserStateSnapshot,
 };
 
 mod health_factor;
@@ -17,8 +54,12 @@ mod interest;
 
 mod submit;
 
+pub(crate) use submit::require_allowance;
 pub use submit::{execute_submit, execute_submit_with_flash_loan};
 
+mod balance_delta;
+pub use balance_delta::execute_set_balance_delta;
+
 #[allow(clippy::module_inception)]
 mod pool;
 pub use pool::Pool;
@@ -27,12 +68,103 @@ mod reserve;
 pub use reserve::Reserve;
 
 mod user;
-pub use user::{Positions, User};
+pub use user::{increment_nonce, load_users_positions, Positions, User};
 
 mod status;
 pub use status::{
-    calc_pool_backstop_threshold, execute_set_pool_status, execute_update_pool_status,
+    calc_pool_backstop_threshold, execute_set_pool_status, execute_set_status_restricted,
+    execute_update_pool_status, set_pool_status_role,
 };
 
+mod soft_liquidation;
+pub use soft_liquidation::{execute_set_soft_liquidation_config, execute_soft_liquidation};
+
+mod interest_payment;
+pub use interest_payment::{
+    calc_accrued_interest, calc_interest_since_index, execute_forgive_interest,
+    execute_repay_interest,
+};
+pub(crate) use interest_payment::{record_borrow, record_repay};
+
+mod shutdown;
+pub use shutdown::{execute_redeem, execute_shutdown};
+
+mod rate_history;
+pub use rate_history::{get_rate_at, load_util_history};
+
+mod reserve_proposal;
+pub(crate) use reserve_proposal::unbonded_balance;
+pub use reserve_proposal::{
+    execute_accept_reserve_proposal, execute_propose_reserve, execute_reclaim_proposal_bond,
+    execute_reject_reserve_proposal, execute_set_proposal_bond,
+};
+
+mod max_amounts;
+pub use max_amounts::{calc_max_borrow, calc_max_withdraw, calc_min_collateral_in};
+
 mod gulp;
 pub use gulp::execute_gulp;
+
+mod bump;
+pub use bump::execute_bump_critical;
+
+mod risk_tier;
+pub use risk_tier::{default_risk_tiers, execute_set_risk_tiers, load_risk_tier};
+
+mod invariants;
+pub use invariants::{execute_verify_invariants, InvariantCheck};
+
+mod donation;
+pub use donation::execute_donate_to_reserve;
+
+mod notification;
+pub use notification::notify_health_alert;
+
+mod auth_preflight;
+pub use auth_preflight::{build_required_auth, SubInvocation};
+
+mod strategy;
+pub(crate) use strategy::{deployed_to_strategy, recall_shortfall};
+pub use strategy::{
+    execute_deploy_to_strategy, execute_recall_from_strategy, execute_set_reserve_strategy,
+};
+
+mod protocol_fee;
+pub use protocol_fee::{
+    execute_cancel_set_protocol_fee, execute_claim_protocol_fees, execute_disable_protocol_fee,
+    execute_queue_set_protocol_fee, execute_set_protocol_fee,
+};
+
+mod insurance;
+pub(crate) use insurance::{accrue_coverage, accrue_lot_coverage};
+pub use insurance::{execute_claim_coverage, execute_set_coverage, execute_set_insurance_premium};
+
+mod request_hooks;
+pub use request_hooks::{
+    default_request_hooks, execute_set_request_hooks, load_request_hooks, run_request_hooks,
+};
+
+mod risk_params;
+pub use risk_params::{export_risk_params, ReserveRiskParams, RiskParams};
+
+mod flash_fill;
+pub use flash_fill::execute_flash_fill_auction;
+
+mod roles;
+pub use roles::{
+    execute_grant_role, execute_revoke_role, execute_set_status_guardian, has_role, require_role,
+};
+
+mod reserve_index;
+pub use reserve_index::{require_reserve_index, require_valid_reserve_indexes};
+
+mod concentration;
+pub use concentration::apply_concentration_haircut;
+
+mod price_sunset;
+pub use price_sunset::execute_check_reserve_price;
+
+mod reserve_migration;
+pub use reserve_migration::{
+    execute_cancel_reserve_migration, execute_migrate_reserve, execute_queue_reserve_migration,
+};
@@ -0,0 +1,50 @@
+use soroban_sdk::{Address, Env, Symbol};
+
+use crate::{events::PoolEvents, storage};
+
+/// Emit a health alert for a user if they have set a notification key
+///
+/// ### Arguments
+/// * `user` - The user
+/// * `reason` - The health related event
+pub fn notify_health_alert(e: &Env, user: &Address, reason: &str) {
+    if let Some(notification_key) = storage::get_notification_key(e, user) {
+        PoolEvents::health_alert(e, notification_key, user.clone(), Symbol::new(e, reason));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testutils;
+
+    use super::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Events},
+        BytesN,
+    };
+
+    #[test]
+    fn test_notify_health_alert() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            let notification_key = BytesN::from_array(&e, &[7; 32]);
+            storage::set_notification_key(&e, &samwise, &Some(notification_key.clone()));
+            assert_eq!(
+                storage::get_notification_key(&e, &samwise),
+                Some(notification_key)
+            );
+
+            notify_health_alert(&e, &samwise, "liquidation");
+            // users without a notification key are skipped
+            notify_health_alert(&e, &frodo, "liquidation");
+            assert_eq!(e.events().all().len(), 1);
+
+            storage::set_notification_key(&e, &samwise, &None);
+            assert_eq!(storage::get_notification_key(&e, &samwise), None);
+        });
+    }
+}
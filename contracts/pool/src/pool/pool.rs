@@ -1,8 +1,12 @@
+use cast::i128;
+use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{map, panic_with_error, unwrap::UnwrapOptimized, vec, Address, Env, Map, Vec};
 
-use sep_40_oracle::{Asset, PriceFeedClient};
+use trustbridge_common::{Asset, PriceFeedClient};
 
 use crate::{
+    constants::{SCALAR_7, TWAP_RECORDS},
+    dependencies::PriceFeedV2Client,
     errors::PoolError,
     storage::{self, PoolConfig},
     Positions,
@@ -16,18 +20,31 @@ pub struct Pool {
     reserves_to_store: Vec<Address>,
     price_decimals: Option<u32>,
     prices: Map<Address, i128>,
+    confidence_threshold: u32,
+    confidences: Map<Address, i128>,
+    twap_prices: Map<Address, i128>,
+    pub is_shutdown: bool,
 }
 
 impl Pool {
-    /// Load the Pool from the ledger
+    /// Load the Pool from the ledger. If the pool has been shut down, prices are frozen
+    /// at the values recorded at shutdown and confidence intervals and TWAPs are ignored.
     pub fn load(e: &Env) -> Self {
         let pool_config = storage::get_pool_config(e);
+        let (prices, confidence_threshold, is_shutdown) = match storage::get_shutdown(e) {
+            Some(shutdown) => (shutdown.prices, 0, true),
+            None => (map![e], storage::get_confidence_threshold(e), false),
+        };
         Pool {
             config: pool_config,
             reserves: map![e],
             reserves_to_store: vec![e],
             price_decimals: None,
-            prices: map![e],
+            prices,
+            confidence_threshold,
+            confidences: map![e],
+            twap_prices: map![e],
+            is_shutdown,
         }
     }
 
@@ -79,6 +96,10 @@ impl Pool {
         {
             panic_with_error!(e, PoolError::InvalidPoolStatus);
         }
+        // disable withdrawing for a shut down pool, as suppliers must redeem pro-rata
+        if self.is_shutdown && (action_type == 1 || action_type == 3) {
+            panic_with_error!(e, PoolError::InvalidPoolStatus);
+        }
     }
 
     /// Require that a position does not violate the maximum number of positions, or panic.
@@ -99,11 +120,22 @@ impl Pool {
 
     /// Load the decimals of the prices for the Pool's oracle. Returns a cached version if one
     /// already exists.
+    ///
+    /// Every value in the oracle's base asset is scaled by these decimals, so if the pool has a
+    /// base asset set, the oracle is checked to still quote prices in it.
+    ///
+    /// ### Panics
+    /// If the pool's base asset does not match the oracle's
     pub fn load_price_decimals(&mut self, e: &Env) -> u32 {
         if let Some(decimals) = self.price_decimals {
             return decimals;
         }
         let oracle_client = PriceFeedClient::new(e, &self.config.oracle);
+        if let Some(base) = storage::get_base_asset(e) {
+            if oracle_client.base() != base {
+                panic_with_error!(e, PoolError::OracleBaseMismatch);
+            }
+        }
         let decimals = oracle_client.decimals();
         self.price_decimals = Some(decimals);
         decimals
@@ -111,6 +143,9 @@ impl Pool {
 
     /// Load a price from the Pool's oracle. Returns a cached version if one already exists.
     ///
+    /// If the pool has a confidence threshold, the price is loaded with `lastprice_v2` and
+    /// the confidence is cached if the interval is wider than the threshold.
+    ///
     /// ### Arguments
     /// * asset - The address of the underlying asset
     ///
@@ -120,14 +155,128 @@ impl Pool {
         if let Some(price) = self.prices.get(asset.clone()) {
             return price;
         }
-        let oracle_client = PriceFeedClient::new(e, &self.config.oracle);
         let oracle_asset = Asset::Stellar(asset.clone());
-        let price_data = oracle_client.lastprice(&oracle_asset).unwrap_optimized();
-        if price_data.timestamp + 24 * 60 * 60 < e.ledger().timestamp() || price_data.price <= 0 {
+        let (price, timestamp, confidence) = if self.confidence_threshold > 0 {
+            let oracle_client = PriceFeedV2Client::new(e, &self.config.oracle);
+            let price_data = oracle_client.lastprice_v2(&oracle_asset).unwrap_optimized();
+            (
+                price_data.price,
+                price_data.timestamp,
+                price_data.confidence,
+            )
+        } else {
+            let oracle_client = PriceFeedClient::new(e, &self.config.oracle);
+            let price_data = oracle_client.lastprice(&oracle_asset).unwrap_optimized();
+            (price_data.price, price_data.timestamp, 0)
+        };
+        if timestamp + 24 * 60 * 60 < e.ledger().timestamp() || price <= 0 || confidence < 0 {
             panic_with_error!(e, PoolError::InvalidPrice);
         }
-        self.prices.set(asset.clone(), price_data.price);
-        price_data.price
+        if confidence > 0
+            && confidence.fixed_div_ceil(e, &price, &SCALAR_7) > i128(self.confidence_threshold)
+        {
+            self.confidences.set(asset.clone(), confidence);
+        }
+        self.prices.set(asset.clone(), price);
+        price
+    }
+
+    /// Load the time-weighted average price of an asset from the Pool's oracle if the reserve
+    /// is priced with a TWAP. The TWAP averages the oracle's last `TWAP_RECORDS` prices that are
+    /// at most a day old, each weighted by how long it was the latest price. If no time has
+    /// passed since the only valid price was set, that price is used. Returns a cached version
+    /// if one already exists.
+    ///
+    /// ### Arguments
+    /// * asset - The address of the underlying asset
+    ///
+    /// ### Panics
+    /// If the reserve is priced with a TWAP and the oracle has no valid prices for it
+    pub fn load_twap_price(&mut self, e: &Env, asset: &Address) -> Option<i128> {
+        if self.is_shutdown {
+            return None;
+        }
+        if let Some(twap) = self.twap_prices.get(asset.clone()) {
+            return Some(twap);
+        }
+        if !storage::get_twap_pricing(e, asset) {
+            return None;
+        }
+        let oracle_client = PriceFeedClient::new(e, &self.config.oracle);
+        let records = oracle_client
+            .prices(&Asset::Stellar(asset.clone()), &TWAP_RECORDS)
+            .unwrap_optimized();
+        let now = e.ledger().timestamp();
+        let mut latest: Option<i128> = None;
+        let mut weighted_total = 0;
+        let mut duration = 0;
+        // records are ordered newest first, and each is the latest price until the next one
+        let mut end = now;
+        for record in records.iter() {
+            if record.timestamp + 24 * 60 * 60 < now {
+                continue;
+            }
+            if record.price > 0 {
+                latest = latest.or(Some(record.price));
+                if record.timestamp < end {
+                    let record_duration = i128(end - record.timestamp);
+                    weighted_total += record.price * record_duration;
+                    duration += record_duration;
+                }
+            }
+            end = end.min(record.timestamp);
+        }
+        let twap = match latest {
+            Some(_) if duration > 0 => weighted_total / duration,
+            Some(price) => price,
+            None => panic_with_error!(e, PoolError::InvalidPrice),
+        };
+        self.twap_prices.set(asset.clone(), twap);
+        Some(twap)
+    }
+
+    /// Load the price to value an asset at when used as collateral. This is the lower of the
+    /// spot price and TWAP if the reserve is priced with a TWAP, lowered to the bottom of the
+    /// spot price's confidence interval if it is wider than the pool's threshold. Reserves
+    /// sunset for being without a valid price are valued at 0.
+    ///
+    /// ### Arguments
+    /// * asset - The address of the underlying asset
+    ///
+    /// ### Panics
+    /// If the price is invalid
+    pub fn load_collateral_price(&mut self, e: &Env, asset: &Address) -> i128 {
+        if storage::get_sunset(e, asset) {
+            return 0;
+        }
+        let mut price = self.load_price(e, asset);
+        if let Some(twap) = self.load_twap_price(e, asset) {
+            price = price.min(twap);
+        }
+        match self.confidences.get(asset.clone()) {
+            Some(confidence) => (price - confidence).max(0),
+            None => price,
+        }
+    }
+
+    /// Load the price to value an asset at when owed as a liability. This is the higher of the
+    /// spot price and TWAP if the reserve is priced with a TWAP, raised to the top of the spot
+    /// price's confidence interval if it is wider than the pool's threshold.
+    ///
+    /// ### Arguments
+    /// * asset - The address of the underlying asset
+    ///
+    /// ### Panics
+    /// If the price is invalid
+    pub fn load_liability_price(&mut self, e: &Env, asset: &Address) -> i128 {
+        let mut price = self.load_price(e, asset);
+        if let Some(twap) = self.load_twap_price(e, asset) {
+            price = price.max(twap);
+        }
+        match self.confidences.get(asset.clone()) {
+            Some(confidence) => price + confidence,
+            None => price,
+        }
     }
 }
 
@@ -563,6 +712,36 @@ mod tests {
         });
     }
 
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1206)")]
+    fn test_require_action_allowed_withdraw_while_shutdown_panics() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 4,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_shutdown(
+                &e,
+                &storage::ShutdownData {
+                    timestamp: 0,
+                    prices: map![&e],
+                },
+            );
+            let pool = Pool::load(&e);
+
+            pool.require_action_allowed(&e, 5);
+            pool.require_action_allowed(&e, 1);
+        });
+    }
+
     #[test]
     fn test_load_price_decimals() {
         let e = Env::default();
@@ -593,6 +772,40 @@ mod tests {
         });
     }
 
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1238)")]
+    fn test_load_price_decimals_base_mismatch() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+        oracle_client.set_data(
+            &Address::generate(&e),
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(Address::generate(&e))],
+            &7,
+            &300,
+        );
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_base_asset(
+                &e,
+                &trustbridge_common::Asset::Other(Symbol::new(&e, "EUR")),
+            );
+            let mut pool = Pool::load(&e);
+
+            pool.load_price_decimals(&e);
+        });
+    }
+
     #[test]
     fn test_load_price() {
         let e = Env::default();
@@ -641,6 +854,210 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_load_price_with_confidence() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let pool = testutils::create_pool(&e);
+        let asset_0 = Address::generate(&e);
+        let asset_1 = Address::generate(&e);
+        let (oracle, oracle_client) = testutils::create_mock_confidence_oracle(&e);
+        oracle_client.set_price(&asset_0, &2_0000000, &0_1000000);
+        oracle_client.set_price(&asset_1, &2_0000000, &0_0100000);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_confidence_threshold(&e, &0_0100000);
+            let mut pool = Pool::load(&e);
+
+            // 5% confidence is wider than the threshold
+            assert_eq!(pool.load_price(&e, &asset_0), 2_0000000);
+            assert_eq!(pool.load_collateral_price(&e, &asset_0), 1_9000000);
+            assert_eq!(pool.load_liability_price(&e, &asset_0), 2_1000000);
+
+            // 0.5% confidence is within the threshold
+            assert_eq!(pool.load_collateral_price(&e, &asset_1), 2_0000000);
+            assert_eq!(pool.load_liability_price(&e, &asset_1), 2_0000000);
+        });
+    }
+
+    #[test]
+    fn test_load_price_with_twap() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set(LedgerInfo {
+            timestamp: 100_000,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let (oracle, oracle_client) = testutils::create_mock_twap_oracle(&e);
+        // the records are 5 minutes apart, and the spot price spiked to 2 15 minutes ago, so the
+        // last 5 records average (2 * 900 + 1 * 1200) / 2100
+        oracle_client.set_history(
+            &underlying_0,
+            &vec![
+                &e, 2_0000000, 1_0000000, 1_0000000, 1_0000000, 1_0000000, 0_1000000,
+            ],
+        );
+        oracle_client.set_history(&underlying_1, &vec![&e, 2_0000000, 1_0000000]);
+        e.ledger().set_timestamp(100_000 + 900);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_twap_pricing(&e, &underlying_0, &true);
+            let mut pool = Pool::load(&e);
+
+            assert_eq!(pool.load_price(&e, &underlying_0), 2_0000000);
+            assert_eq!(pool.load_twap_price(&e, &underlying_0), Some(1_4285714));
+            assert_eq!(pool.load_collateral_price(&e, &underlying_0), 1_4285714);
+            assert_eq!(pool.load_liability_price(&e, &underlying_0), 2_0000000);
+
+            // reserves priced at spot ignore the oracle's history
+            assert_eq!(pool.load_twap_price(&e, &underlying_1), None);
+            assert_eq!(pool.load_collateral_price(&e, &underlying_1), 2_0000000);
+            assert_eq!(pool.load_liability_price(&e, &underlying_1), 2_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1210)")]
+    fn test_load_twap_price_panics_if_stale() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set(LedgerInfo {
+            timestamp: 100_000,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let (oracle, oracle_client) = testutils::create_mock_twap_oracle(&e);
+        oracle_client.set_history(&underlying, &vec![&e, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_twap_pricing(&e, &underlying, &true);
+            let mut pool = Pool::load(&e);
+
+            e.ledger().set_timestamp(100_000 + 24 * 60 * 60 + 1);
+            pool.load_twap_price(&e, &underlying);
+        });
+    }
+
+    #[test]
+    fn test_load_twap_price_only_price_set_now() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set_timestamp(100_000);
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let (oracle, oracle_client) = testutils::create_mock_twap_oracle(&e);
+        oracle_client.set_history(&underlying, &vec![&e, 1_5000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_twap_pricing(&e, &underlying, &true);
+            let mut pool = Pool::load(&e);
+
+            // no time has passed to weight the price by
+            assert_eq!(pool.load_twap_price(&e, &underlying), Some(1_5000000));
+        });
+    }
+
+    #[test]
+    fn test_load_price_ignores_confidence_without_threshold() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let asset_0 = Address::generate(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(asset_0.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            assert_eq!(pool.load_collateral_price(&e, &asset_0), 2_0000000);
+            assert_eq!(pool.load_liability_price(&e, &asset_0), 2_0000000);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1210)")]
     fn test_load_price_panics_if_stale() {
@@ -0,0 +1,205 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+use trustbridge_common::{Asset, PriceFeedClient};
+
+use crate::{
+    constants::UNPRICED_SUNSET_LEDGERS, dependencies::PriceFeedV2Client, errors::PoolError,
+    events::PoolEvents, storage,
+};
+
+/// Check if the pool's oracle has a valid price for a reserve. A reserve that has been without a
+/// valid price for `UNPRICED_SUNSET_LEDGERS` is sunset: it is disabled for supply and borrows,
+/// and is valued at 0 as collateral so users holding it can still manage their other positions.
+///
+/// The ledger a reserve is first seen unpriced is recorded, and is cleared once the reserve is
+/// seen priced again. Sunset reserves must be re-enabled by the admin.
+///
+/// Returns true if the reserve is sunset
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+///
+/// ### Panics
+/// If the reserve does not exist
+pub fn execute_check_reserve_price(e: &Env, asset: &Address) -> bool {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    if has_valid_price(e, asset) {
+        storage::set_unpriced_since(e, asset, &None);
+        storage::set_sunset(e, asset, &false);
+        return false;
+    }
+
+    let since = match storage::get_unpriced_since(e, asset) {
+        Some(since) => since,
+        None => {
+            let since = e.ledger().sequence();
+            storage::set_unpriced_since(e, asset, &Some(since));
+            PoolEvents::reserve_unpriced(e, asset.clone(), since);
+            since
+        }
+    };
+    if storage::get_sunset(e, asset) {
+        return true;
+    }
+    if e.ledger().sequence() - since < UNPRICED_SUNSET_LEDGERS {
+        return false;
+    }
+
+    let mut reserve_config = storage::get_res_config(e, asset);
+    reserve_config.enabled = false;
+    storage::set_res_config(e, asset, &reserve_config);
+    storage::set_sunset(e, asset, &true);
+    PoolEvents::sunset_reserve(e, asset.clone(), since);
+    true
+}
+
+/// Check if the pool's oracle reports a price for the asset that `Pool::load_price` would accept
+fn has_valid_price(e: &Env, asset: &Address) -> bool {
+    let oracle = storage::get_pool_config(e).oracle;
+    let oracle_asset = Asset::Stellar(asset.clone());
+    let (price, timestamp) = if storage::get_confidence_threshold(e) > 0 {
+        match PriceFeedV2Client::new(e, &oracle).try_lastprice_v2(&oracle_asset) {
+            Ok(Ok(Some(price_data))) if price_data.confidence >= 0 => {
+                (price_data.price, price_data.timestamp)
+            }
+            _ => return false,
+        }
+    } else {
+        match PriceFeedClient::new(e, &oracle).try_lastprice(&oracle_asset) {
+            Ok(Ok(Some(price_data))) => (price_data.price, price_data.timestamp),
+            _ => return false,
+        }
+    };
+    price > 0 && timestamp + 24 * 60 * 60 >= e.ledger().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pool::Pool, storage::PoolConfig, testutils};
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        vec, Symbol,
+    };
+
+    #[test]
+    fn test_check_reserve_price_sunsets_unpriced_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set_sequence_number(100);
+        e.ledger().set_timestamp(1000);
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(underlying.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price(&vec![&e, 1_0000000], &1000);
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            assert!(!execute_check_reserve_price(&e, &underlying));
+            assert_eq!(storage::get_unpriced_since(&e, &underlying), None);
+
+            // price goes stale
+            e.ledger().set_timestamp(1000 + 24 * 60 * 60 + 1);
+            assert!(!execute_check_reserve_price(&e, &underlying));
+            assert_eq!(storage::get_unpriced_since(&e, &underlying), Some(100));
+
+            e.ledger()
+                .set_sequence_number(100 + UNPRICED_SUNSET_LEDGERS - 1);
+            assert!(!execute_check_reserve_price(&e, &underlying));
+            assert!(storage::get_res_config(&e, &underlying).enabled);
+
+            e.ledger()
+                .set_sequence_number(100 + UNPRICED_SUNSET_LEDGERS);
+            assert!(execute_check_reserve_price(&e, &underlying));
+            assert!(!storage::get_res_config(&e, &underlying).enabled);
+            assert!(storage::get_sunset(&e, &underlying));
+
+            // sunset collateral is valued at 0 without calling the oracle
+            let mut pool = Pool::load(&e);
+            assert_eq!(pool.load_collateral_price(&e, &underlying), 0);
+        });
+    }
+
+    #[test]
+    fn test_check_reserve_price_clears_when_repriced() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set_sequence_number(100);
+        e.ledger().set_timestamp(1000);
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(underlying.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price(&vec![&e, 0], &1000);
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_unpriced_since(&e, &underlying, &Some(1));
+            storage::set_sunset(&e, &underlying, &true);
+
+            assert!(execute_check_reserve_price(&e, &underlying));
+        });
+
+        oracle_client.set_price(&vec![&e, 1_0000000], &1000);
+        e.as_contract(&pool, || {
+            assert!(!execute_check_reserve_price(&e, &underlying));
+            assert_eq!(storage::get_unpriced_since(&e, &underlying), None);
+            assert!(!storage::get_sunset(&e, &underlying));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_check_reserve_price_not_reserve() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_check_reserve_price(&e, &Address::generate(&e));
+        });
+    }
+}
@@ -0,0 +1,337 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    constants::{MAX_PROTOCOL_FEE, SCALAR_7, SECONDS_PER_WEEK},
+    errors::PoolError,
+    storage::{self, PoolConfig, ProtocolFee, QueuedProtocolFee},
+};
+
+use super::{pool::Pool, recall_shortfall, Reserve};
+
+/// Queue a new protocol fee taken from the interest accrued by each reserve. The fee is
+/// timelocked like a reserve update, so the treasury and rate cannot be changed instantly. Any
+/// previously queued fee is replaced.
+///
+/// Returns the queued protocol fee
+///
+/// ### Arguments
+/// * `fee` - The protocol fee
+///
+/// ### Panics
+/// If the rate is over `MAX_PROTOCOL_FEE`, or the rate and the backstop take rate would take all
+/// of the accrued interest
+pub fn execute_queue_set_protocol_fee(e: &Env, fee: &ProtocolFee) -> QueuedProtocolFee {
+    let pool_config = storage::get_pool_config(e);
+    require_valid_rate(e, &pool_config, fee.rate);
+
+    let mut unlock_time = e.ledger().timestamp();
+    // require a timelock if pool status is not setup
+    if pool_config.status != 6 {
+        unlock_time += SECONDS_PER_WEEK;
+    }
+    let queued = QueuedProtocolFee {
+        fee: fee.clone(),
+        unlock_time,
+    };
+    storage::set_queued_protocol_fee(e, &Some(queued.clone()));
+    queued
+}
+
+/// Cancel the queued protocol fee
+///
+/// Returns the cancelled protocol fee
+///
+/// ### Panics
+/// If no protocol fee is queued
+pub fn execute_cancel_set_protocol_fee(e: &Env) -> ProtocolFee {
+    let queued = match storage::get_queued_protocol_fee(e) {
+        Some(queued) => queued,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    storage::set_queued_protocol_fee(e, &None);
+    queued.fee
+}
+
+/// Set the queued protocol fee once its timelock has passed. Every reserve is accrued at the
+/// previous rate before the fee changes.
+///
+/// Returns the protocol fee
+///
+/// ### Panics
+/// If no protocol fee is queued, the timelock has not passed, or the rate and the backstop take
+/// rate would take all of the accrued interest
+pub fn execute_set_protocol_fee(e: &Env) -> ProtocolFee {
+    let queued = match storage::get_queued_protocol_fee(e) {
+        Some(queued) => queued,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if queued.unlock_time > e.ledger().timestamp() {
+        panic_with_error!(e, PoolError::InitNotUnlocked);
+    }
+    let pool_config = storage::get_pool_config(e);
+    // the backstop take rate can change while the fee is queued
+    require_valid_rate(e, &pool_config, queued.fee.rate);
+
+    storage::set_queued_protocol_fee(e, &None);
+    update_protocol_fee(e, &pool_config, &queued.fee);
+    queued.fee
+}
+
+/// Disable the protocol fee. The treasury is kept, so fees accrued before the fee was disabled
+/// can still be claimed. Every reserve is accrued at the previous rate first.
+///
+/// ### Panics
+/// If no protocol fee is set
+pub fn execute_disable_protocol_fee(e: &Env) {
+    let mut fee = match storage::get_protocol_fee(e) {
+        Some(fee) => fee,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    fee.rate = 0;
+    update_protocol_fee(e, &storage::get_pool_config(e), &fee);
+}
+
+/// Send the protocol fees owed from a reserve to the treasury
+///
+/// Returns the treasury and the amount of underlying sent
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+///
+/// ### Panics
+/// If no protocol fee has been set, or no fees are owed
+pub fn execute_claim_protocol_fees(e: &Env, asset: &Address) -> (Address, i128) {
+    let treasury = match storage::get_protocol_fee(e) {
+        Some(fee) => fee.treasury,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+
+    let mut pool = Pool::load(e);
+    let mut reserve = pool.load_reserve(e, asset, true);
+    let amount = reserve.protocol_fee_credit(e);
+    if amount <= 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    reserve.protocol_fee = 0;
+    storage::set_protocol_fee_credit(e, asset, &0);
+
+    pool.cache_reserve(reserve);
+    pool.store_cached_reserves(e);
+
+    recall_shortfall(e, asset, amount);
+    TokenClient::new(e, asset).transfer(&e.current_contract_address(), &treasury, &amount);
+    (treasury, amount)
+}
+
+fn require_valid_rate(e: &Env, pool_config: &PoolConfig, rate: u32) {
+    if rate > MAX_PROTOCOL_FEE || pool_config.bstop_rate + rate >= SCALAR_7 as u32 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+}
+
+/// Store the protocol fee, accruing every reserve at the previous rate if the rate changes
+fn update_protocol_fee(e: &Env, pool_config: &PoolConfig, fee: &ProtocolFee) {
+    let cur_rate = storage::get_protocol_fee(e).map_or(0, |fee| fee.rate);
+    if cur_rate != fee.rate {
+        for res in storage::get_res_list(e) {
+            let reserve = Reserve::load(e, pool_config, &res);
+            reserve.store(e);
+        }
+    }
+    storage::set_protocol_fee(e, fee);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::SCALAR_12, testutils};
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    fn jump(e: &Env, seconds: u64) {
+        testutils::set_ledger(
+            e,
+            e.ledger().timestamp() + seconds,
+            e.ledger().sequence() + (seconds / 5) as u32,
+        );
+    }
+
+    #[test]
+    fn test_protocol_fee_accrues_and_claims() {
+        let e = Env::default();
+        let (pool, underlying, underlying_client) = testutils::create_pool_with_reserve(&e);
+        let treasury = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_protocol_fee(
+                &e,
+                &ProtocolFee {
+                    treasury: treasury.clone(),
+                    rate: 0_1000000,
+                },
+            );
+        });
+
+        jump(&e, 30 * 24 * 60 * 60);
+        let credit = e.as_contract(&pool, || {
+            let pool_config = storage::get_pool_config(&e);
+            let pre_data = storage::get_res_data(&e, &underlying);
+            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            reserve.store(&e);
+
+            // the fee is half of the 20% backstop credit, as both floor the same accrual
+            let credit = storage::get_protocol_fee_credit(&e, &underlying);
+            assert!(credit > 0);
+            let backstop_credit = reserve.data.backstop_credit - pre_data.backstop_credit;
+            assert!(backstop_credit / 2 - credit <= 1);
+
+            // suppliers earn the accrual less both fees
+            let liabilities_delta = reserve.total_liabilities(&e) - 75_0000000;
+            let supply_delta = reserve.total_supply(&e) - 100_0000000;
+            assert!(liabilities_delta - backstop_credit - credit - supply_delta <= 1);
+            assert!(reserve.data.b_rate > SCALAR_12);
+
+            let (to, amount) = execute_claim_protocol_fees(&e, &underlying);
+            assert_eq!(to, treasury);
+            assert_eq!(amount, credit);
+            assert_eq!(storage::get_protocol_fee_credit(&e, &underlying), 0);
+            credit
+        });
+        assert_eq!(underlying_client.balance(&treasury), credit);
+    }
+
+    #[test]
+    fn test_set_protocol_fee_accrues_at_previous_rate() {
+        let e = Env::default();
+        let (pool, underlying, _) = testutils::create_pool_with_reserve(&e);
+        let treasury = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            let queued = execute_queue_set_protocol_fee(
+                &e,
+                &ProtocolFee {
+                    treasury: treasury.clone(),
+                    rate: 0_1000000,
+                },
+            );
+            assert_eq!(queued.unlock_time, 12345 + SECONDS_PER_WEEK);
+        });
+
+        jump(&e, 30 * 24 * 60 * 60);
+        e.as_contract(&pool, || {
+            let fee = execute_set_protocol_fee(&e);
+            assert_eq!(fee.treasury, treasury);
+            assert_eq!(storage::get_protocol_fee(&e).unwrap().rate, 0_1000000);
+            assert!(storage::get_queued_protocol_fee(&e).is_none());
+
+            // interest accrued before the fee was set is not charged
+            let reserve_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(reserve_data.last_time, e.ledger().timestamp());
+            assert!(reserve_data.backstop_credit > 0);
+            assert_eq!(storage::get_protocol_fee_credit(&e, &underlying), 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_protocol_fee_over_max() {
+        let e = Env::default();
+        let (pool, _, _) = testutils::create_pool_with_reserve(&e);
+
+        e.as_contract(&pool, || {
+            execute_queue_set_protocol_fee(
+                &e,
+                &ProtocolFee {
+                    treasury: Address::generate(&e),
+                    rate: MAX_PROTOCOL_FEE + 1,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1203)")]
+    fn test_set_protocol_fee_before_unlock() {
+        let e = Env::default();
+        let (pool, _, _) = testutils::create_pool_with_reserve(&e);
+
+        e.as_contract(&pool, || {
+            execute_queue_set_protocol_fee(
+                &e,
+                &ProtocolFee {
+                    treasury: Address::generate(&e),
+                    rate: 0_1000000,
+                },
+            );
+        });
+
+        jump(&e, SECONDS_PER_WEEK - 1);
+        e.as_contract(&pool, || {
+            execute_set_protocol_fee(&e);
+        });
+    }
+
+    #[test]
+    fn test_cancel_set_protocol_fee() {
+        let e = Env::default();
+        let (pool, _, _) = testutils::create_pool_with_reserve(&e);
+        let fee = ProtocolFee {
+            treasury: Address::generate(&e),
+            rate: 0_1000000,
+        };
+
+        e.as_contract(&pool, || {
+            execute_queue_set_protocol_fee(&e, &fee);
+            let cancelled = execute_cancel_set_protocol_fee(&e);
+            assert_eq!(cancelled.treasury, fee.treasury);
+            assert!(storage::get_queued_protocol_fee(&e).is_none());
+            assert!(storage::get_protocol_fee(&e).is_none());
+        });
+    }
+
+    #[test]
+    fn test_claim_protocol_fees_after_disable() {
+        let e = Env::default();
+        let (pool, underlying, underlying_client) = testutils::create_pool_with_reserve(&e);
+        let treasury = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_protocol_fee(
+                &e,
+                &ProtocolFee {
+                    treasury: treasury.clone(),
+                    rate: 0_1000000,
+                },
+            );
+        });
+
+        jump(&e, 30 * 24 * 60 * 60);
+        let credit = e.as_contract(&pool, || {
+            execute_disable_protocol_fee(&e);
+            let fee = storage::get_protocol_fee(&e).unwrap();
+            assert_eq!(fee.rate, 0);
+            assert_eq!(fee.treasury, treasury);
+
+            // the fee accrued before disabling is still owed to the treasury
+            let credit = storage::get_protocol_fee_credit(&e, &underlying);
+            assert!(credit > 0);
+            let (to, amount) = execute_claim_protocol_fees(&e, &underlying);
+            assert_eq!(to, treasury);
+            assert_eq!(amount, credit);
+            credit
+        });
+        assert_eq!(underlying_client.balance(&treasury), credit);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_claim_protocol_fees_no_fee() {
+        let e = Env::default();
+        let (pool, underlying, _) = testutils::create_pool_with_reserve(&e);
+
+        jump(&e, 30 * 24 * 60 * 60);
+        e.as_contract(&pool, || {
+            execute_claim_protocol_fees(&e, &underlying);
+        });
+    }
+}
@@ -0,0 +1,257 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{
+    constants::{
+        MAX_RATE_CHECKPOINTS, MAX_UTIL_HISTORY_DAYS, RATE_CHECKPOINT_INTERVAL, UTIL_HISTORY_DAY,
+    },
+    storage::{self, RateCheckpoint, UtilDay},
+};
+
+use super::Reserve;
+
+/// Record a checkpoint of the reserve's rates if at least `RATE_CHECKPOINT_INTERVAL` has passed
+/// since the last checkpoint. Only the most recent `MAX_RATE_CHECKPOINTS` are kept.
+///
+/// ### Arguments
+/// * `reserve` - The reserve, updated to the current ledger
+pub fn checkpoint_rates(e: &Env, reserve: &Reserve) {
+    let mut history = storage::get_rate_history(e, &reserve.asset);
+    if let Some(last) = history.last() {
+        if reserve.data.last_time < last.timestamp + RATE_CHECKPOINT_INTERVAL {
+            return;
+        }
+    }
+    if history.len() >= MAX_RATE_CHECKPOINTS {
+        history.pop_front();
+    }
+    history.push_back(RateCheckpoint {
+        timestamp: reserve.data.last_time,
+        b_rate: reserve.data.b_rate,
+        d_rate: reserve.data.d_rate,
+    });
+    storage::set_rate_history(e, &reserve.asset, &history);
+}
+
+/// Record the reserve's utilization in the day's utilization range. The day's range is kept in
+/// its own entry and is only added to the history once a new day starts, so the history is
+/// written at most once per day. Only the most recent `MAX_UTIL_HISTORY_DAYS` are kept.
+///
+/// ### Arguments
+/// * `reserve` - The reserve, updated to the current ledger
+pub fn record_utilization(e: &Env, reserve: &Reserve) {
+    let util = reserve.utilization(e);
+    let day = reserve.data.last_time / UTIL_HISTORY_DAY;
+    let today = match storage::get_util_today(e, &reserve.asset) {
+        Some(mut today) if today.day == day => {
+            if util >= today.min_util && util <= today.max_util {
+                return;
+            }
+            today.min_util = today.min_util.min(util);
+            today.max_util = today.max_util.max(util);
+            today
+        }
+        last_day => {
+            if let Some(last_day) = last_day {
+                let mut history = storage::get_util_history(e, &reserve.asset);
+                if history.len() >= MAX_UTIL_HISTORY_DAYS {
+                    history.pop_front();
+                }
+                history.push_back(last_day);
+                storage::set_util_history(e, &reserve.asset, &history);
+            }
+            UtilDay {
+                day,
+                min_util: util,
+                max_util: util,
+            }
+        }
+    };
+    storage::set_util_today(e, &reserve.asset, &today);
+}
+
+/// Load the daily utilization ranges of a reserve, including the current day, ordered from
+/// oldest to newest
+///
+/// ### Arguments
+/// * `asset` - The reserve asset
+pub fn load_util_history(e: &Env, asset: &Address) -> Vec<UtilDay> {
+    let mut history = storage::get_util_history(e, asset);
+    if let Some(today) = storage::get_util_today(e, asset) {
+        if history.len() >= MAX_UTIL_HISTORY_DAYS {
+            history.pop_front();
+        }
+        history.push_back(today);
+    }
+    history
+}
+
+/// Fetch the most recent rate checkpoint for a reserve recorded at or before `timestamp`
+///
+/// Returns None if no checkpoint exists at or before `timestamp`
+///
+/// ### Arguments
+/// * `asset` - The reserve asset
+/// * `timestamp` - The timestamp to fetch the rates at
+pub fn get_rate_at(e: &Env, asset: &Address, timestamp: u64) -> Option<RateCheckpoint> {
+    let history = storage::get_rate_history(e, asset);
+    history
+        .iter()
+        .rev()
+        .find(|checkpoint| checkpoint.timestamp <= timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{constants::SCALAR_12, testutils};
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_checkpoint_rates() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 1000;
+        let mut reserve = Reserve {
+            asset: Address::generate(&e),
+            config: reserve_config,
+            data: reserve_data,
+            scalar: 1_0000000,
+            protocol_fee: 0,
+            util_smoothing: None,
+        };
+
+        e.as_contract(&pool, || {
+            checkpoint_rates(&e, &reserve);
+
+            // too soon since the last checkpoint
+            reserve.data.last_time = 1000 + RATE_CHECKPOINT_INTERVAL - 1;
+            reserve.data.b_rate = 1_100_000_000_000;
+            checkpoint_rates(&e, &reserve);
+            assert_eq!(storage::get_rate_history(&e, &reserve.asset).len(), 1);
+
+            reserve.data.last_time = 1000 + RATE_CHECKPOINT_INTERVAL;
+            reserve.data.d_rate = 1_200_000_000_000;
+            checkpoint_rates(&e, &reserve);
+            let history = storage::get_rate_history(&e, &reserve.asset);
+            assert_eq!(history.len(), 2);
+            assert_eq!(
+                history.get_unchecked(1),
+                RateCheckpoint {
+                    timestamp: 1000 + RATE_CHECKPOINT_INTERVAL,
+                    b_rate: 1_100_000_000_000,
+                    d_rate: 1_200_000_000_000,
+                }
+            );
+
+            assert_eq!(get_rate_at(&e, &reserve.asset, 999), None);
+            let checkpoint = get_rate_at(&e, &reserve.asset, 1000 + 100).unwrap();
+            assert_eq!(checkpoint.timestamp, 1000);
+            assert_eq!(checkpoint.b_rate, SCALAR_12);
+            let checkpoint =
+                get_rate_at(&e, &reserve.asset, 1000 + 2 * RATE_CHECKPOINT_INTERVAL).unwrap();
+            assert_eq!(checkpoint.d_rate, 1_200_000_000_000);
+        });
+    }
+
+    #[test]
+    fn test_record_utilization() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 1000;
+        let mut reserve = Reserve {
+            asset: Address::generate(&e),
+            config: reserve_config,
+            data: reserve_data,
+            scalar: 1_0000000,
+            protocol_fee: 0,
+            util_smoothing: None,
+        };
+
+        e.as_contract(&pool, || {
+            record_utilization(&e, &reserve);
+
+            reserve.data.last_time = 2000;
+            reserve.data.d_supply = 50_0000000;
+            record_utilization(&e, &reserve);
+            reserve.data.d_supply = 90_0000000;
+            record_utilization(&e, &reserve);
+            let history = load_util_history(&e, &reserve.asset);
+            assert_eq!(history.len(), 1);
+            assert_eq!(
+                history.get_unchecked(0),
+                UtilDay {
+                    day: 0,
+                    min_util: 0_5000000,
+                    max_util: 0_9000000,
+                }
+            );
+            // the history is not written until the day ends
+            assert_eq!(storage::get_util_history(&e, &reserve.asset).len(), 0);
+
+            // a new day starts a new range
+            reserve.data.last_time = UTIL_HISTORY_DAY;
+            record_utilization(&e, &reserve);
+            assert_eq!(storage::get_util_history(&e, &reserve.asset).len(), 1);
+            let history = load_util_history(&e, &reserve.asset);
+            assert_eq!(history.len(), 2);
+            assert_eq!(history.get_unchecked(1).day, 1);
+            assert_eq!(history.get_unchecked(1).min_util, 0_9000000);
+        });
+    }
+
+    #[test]
+    fn test_record_utilization_drops_oldest() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let mut reserve = Reserve {
+            asset: Address::generate(&e),
+            config: reserve_config,
+            data: reserve_data,
+            scalar: 1_0000000,
+            protocol_fee: 0,
+            util_smoothing: None,
+        };
+
+        e.as_contract(&pool, || {
+            for i in 0..(MAX_UTIL_HISTORY_DAYS as u64 + 1) {
+                reserve.data.last_time = i * UTIL_HISTORY_DAY;
+                record_utilization(&e, &reserve);
+            }
+            let history = load_util_history(&e, &reserve.asset);
+            assert_eq!(history.len(), MAX_UTIL_HISTORY_DAYS);
+            assert_eq!(history.first_unchecked().day, 1);
+        });
+    }
+
+    #[test]
+    fn test_checkpoint_rates_drops_oldest() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let mut reserve = Reserve {
+            asset: Address::generate(&e),
+            config: reserve_config,
+            data: reserve_data,
+            scalar: 1_0000000,
+            protocol_fee: 0,
+            util_smoothing: None,
+        };
+
+        e.as_contract(&pool, || {
+            for i in 0..(MAX_RATE_CHECKPOINTS as u64 + 1) {
+                reserve.data.last_time = i * RATE_CHECKPOINT_INTERVAL;
+                checkpoint_rates(&e, &reserve);
+            }
+            let history = storage::get_rate_history(&e, &reserve.asset);
+            assert_eq!(history.len(), MAX_RATE_CHECKPOINTS);
+            assert_eq!(
+                history.first_unchecked().timestamp,
+                RATE_CHECKPOINT_INTERVAL
+            );
+            assert_eq!(get_rate_at(&e, &reserve.asset, 0), None);
+        });
+    }
+}
@@ -0,0 +1,230 @@
+use soroban_sdk::{panic_with_error, vec, Env, Vec};
+
+use crate::{
+    constants::MAX_REQUEST_HOOKS,
+    dependencies::RequestValidatorClient,
+    errors::PoolError,
+    storage::{self, RequestHook},
+    validator::require_nonnegative,
+};
+
+use super::{pool::Pool, Request, User};
+
+/// The hooks run against each request when the admin has not set any
+pub fn default_request_hooks(e: &Env) -> Vec<RequestHook> {
+    vec![e, RequestHook::NonNegative, RequestHook::PoolStatus]
+}
+
+/// Load the ordered list of hooks run against each request
+pub fn load_request_hooks(e: &Env) -> Vec<RequestHook> {
+    storage::get_request_hooks(e).unwrap_or_else(|| default_request_hooks(e))
+}
+
+/// Set the ordered list of hooks run against each request. The default hooks must be included,
+/// so the admin can order them or add external validators, but not remove them.
+///
+/// ### Arguments
+/// * `hooks` - The request hooks
+///
+/// ### Panics
+/// * If a default hook is missing or a hook is included more than once
+/// * If there are more than `MAX_REQUEST_HOOKS` hooks
+pub fn execute_set_request_hooks(e: &Env, hooks: &Vec<RequestHook>) {
+    if hooks.len() > MAX_REQUEST_HOOKS {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    for default_hook in default_request_hooks(e).iter() {
+        if !hooks.contains(&default_hook) {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+    for (index, hook) in hooks.iter().enumerate() {
+        if hooks.first_index_of(&hook) != Some(index as u32) {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+    storage::set_request_hooks(e, hooks);
+}
+
+/// Run the hooks against a request before it is applied, in order
+///
+/// ### Arguments
+/// * `hooks` - The request hooks
+/// * `pool` - The pool
+/// * `user` - The user whose positions the request modifies
+/// * `request` - The request
+///
+/// ### Panics
+/// If any hook rejects the request
+pub fn run_request_hooks(
+    e: &Env,
+    hooks: &Vec<RequestHook>,
+    pool: &Pool,
+    user: &User,
+    request: &Request,
+) {
+    for hook in hooks.iter() {
+        match hook {
+            RequestHook::NonNegative => require_nonnegative(e, &request.amount),
+            RequestHook::PoolStatus => pool.require_action_allowed(e, request.request_type),
+            RequestHook::External(validator) => {
+                RequestValidatorClient::new(e, &validator).validate_request(
+                    &e.current_contract_address(),
+                    &user.address,
+                    &user.positions,
+                    request,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pool::{Positions, RequestType},
+        testutils,
+    };
+    use soroban_sdk::{testutils::Address as _, Address};
+
+    fn setup_pool(e: &Env, status: u32) -> Address {
+        let (pool, _, _) = testutils::create_pool_with_reserve(e);
+        e.as_contract(&pool, || {
+            let mut pool_config = storage::get_pool_config(e);
+            pool_config.status = status;
+            storage::set_pool_config(e, &pool_config);
+        });
+        pool
+    }
+
+    #[test]
+    fn test_load_request_hooks_default() {
+        let e = Env::default();
+        let pool = setup_pool(&e, 0);
+
+        e.as_contract(&pool, || {
+            assert_eq!(load_request_hooks(&e), default_request_hooks(&e));
+
+            let validator = Address::generate(&e);
+            let hooks = vec![
+                &e,
+                RequestHook::PoolStatus,
+                RequestHook::External(validator),
+                RequestHook::NonNegative,
+            ];
+            execute_set_request_hooks(&e, &hooks);
+            assert_eq!(load_request_hooks(&e), hooks);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_request_hooks_missing_default() {
+        let e = Env::default();
+        let pool = setup_pool(&e, 0);
+
+        e.as_contract(&pool, || {
+            let hooks = vec![
+                &e,
+                RequestHook::NonNegative,
+                RequestHook::External(Address::generate(&e)),
+            ];
+            execute_set_request_hooks(&e, &hooks);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_request_hooks_duplicate() {
+        let e = Env::default();
+        let pool = setup_pool(&e, 0);
+
+        e.as_contract(&pool, || {
+            let validator = Address::generate(&e);
+            let hooks = vec![
+                &e,
+                RequestHook::NonNegative,
+                RequestHook::PoolStatus,
+                RequestHook::External(validator.clone()),
+                RequestHook::External(validator),
+            ];
+            execute_set_request_hooks(&e, &hooks);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1206)")]
+    fn test_run_request_hooks_pool_status() {
+        let e = Env::default();
+        let pool = setup_pool(&e, 2);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            let pool_state = Pool::load(&e);
+            let user = User::load(&e, &samwise);
+            let request = Request {
+                request_type: RequestType::Borrow as u32,
+                address: Address::generate(&e),
+                amount: 1_0000000,
+            };
+            run_request_hooks(&e, &load_request_hooks(&e), &pool_state, &user, &request);
+        });
+    }
+
+    #[test]
+    fn test_run_request_hooks_external() {
+        let e = Env::default();
+        let pool = setup_pool(&e, 0);
+        let validator = testutils::create_mock_request_validator(&e, 10_0000000);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(&e, &samwise, &Positions::env_default(&e));
+            let hooks = vec![
+                &e,
+                RequestHook::NonNegative,
+                RequestHook::PoolStatus,
+                RequestHook::External(validator.clone()),
+            ];
+            execute_set_request_hooks(&e, &hooks);
+
+            let pool_state = Pool::load(&e);
+            let user = User::load(&e, &samwise);
+            let request = Request {
+                request_type: RequestType::Supply as u32,
+                address: Address::generate(&e),
+                amount: 10_0000000,
+            };
+            run_request_hooks(&e, &load_request_hooks(&e), &pool_state, &user, &request);
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_run_request_hooks_external_rejects() {
+        let e = Env::default();
+        let pool = setup_pool(&e, 0);
+        let validator = testutils::create_mock_request_validator(&e, 10_0000000);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            let hooks = vec![
+                &e,
+                RequestHook::NonNegative,
+                RequestHook::PoolStatus,
+                RequestHook::External(validator.clone()),
+            ];
+            execute_set_request_hooks(&e, &hooks);
+
+            let pool_state = Pool::load(&e);
+            let user = User::load(&e, &samwise);
+            let request = Request {
+                request_type: RequestType::Supply as u32,
+                address: Address::generate(&e),
+                amount: 10_0000001,
+            };
+            run_request_hooks(&e, &load_request_hooks(&e), &pool_state, &user, &request);
+        });
+    }
+}
@@ -1,15 +1,20 @@
 use cast::i128;
 use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{contracttype, panic_with_error, Address, Env};
+use trustbridge_common::decimal_scalar;
 
 use crate::{
-    constants::{SCALAR_12, SCALAR_7},
+    constants::{FACTOR_RAMP_LEDGERS, SCALAR_12, SCALAR_7},
+    emissions::update_emission_boost,
     errors::PoolError,
     pool::actions::RequestType,
-    storage::{self, PoolConfig, ReserveConfig, ReserveData},
+    storage::{self, PoolConfig, ReserveConfig, ReserveData, UtilSmoothing},
 };
 
-use super::interest::calc_accrual;
+use super::{
+    interest::calc_accrual,
+    rate_history::{checkpoint_rates, record_utilization},
+};
 
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -18,6 +23,8 @@ pub struct Reserve {
     pub config: ReserveConfig, // the reserve configuration
     pub data: ReserveData,     // the reserve data
     pub scalar: i128,
+    pub protocol_fee: i128, // the protocol fees accrued since the reserve was loaded
+    pub util_smoothing: Option<UtilSmoothing>, // the utilization smoothing, if enabled
 }
 
 impl Reserve {
@@ -30,17 +37,24 @@ impl Reserve {
     /// * asset - The address of the underlying asset
     ///
     /// ### Panics
-    /// Panics if the asset is not supported, if emissions cannot be updated, or if the reserve
-    /// cannot be updated to the current ledger timestamp.
+    /// Panics if the asset is not a listed reserve, if emissions cannot be updated, or if the
+    /// reserve cannot be updated to the current ledger timestamp.
     pub fn load(e: &Env, pool_config: &PoolConfig, asset: &Address) -> Reserve {
+        // only listed assets are reserves, as a migrated reserve is re-mapped to its new asset
+        if !storage::get_res_list(e).contains(asset) {
+            panic_with_error!(e, PoolError::BadRequest);
+        }
         let reserve_config = storage::get_res_config(e, asset);
         let reserve_data = storage::get_res_data(e, asset);
         let mut reserve = Reserve {
             asset: asset.clone(),
-            scalar: 10i128.pow(reserve_config.decimals),
+            scalar: decimal_scalar(reserve_config.decimals),
             config: reserve_config,
             data: reserve_data,
+            protocol_fee: 0,
+            util_smoothing: storage::get_util_smoothing(e, asset),
         };
+        reserve.apply_factor_ramp(e);
 
         // short circuit if the reserve has already been updated this ledger
         if e.ledger().timestamp() == reserve.data.last_time {
@@ -48,11 +62,13 @@ impl Reserve {
         }
 
         if reserve.data.b_supply == 0 {
+            reserve.smooth_utilization(e, 0);
             reserve.data.last_time = e.ledger().timestamp();
             return reserve;
         }
 
         let cur_util = reserve.utilization(e);
+        let rate_util = reserve.smooth_utilization(e, cur_util);
         if cur_util == 0 {
             // if there are no assets borrowed, we don't need to update the reserve
             reserve.data.last_time = e.ledger().timestamp();
@@ -62,7 +78,7 @@ impl Reserve {
         let (loan_accrual, new_ir_mod) = calc_accrual(
             e,
             &reserve.config,
-            cur_util,
+            rate_util,
             reserve.data.ir_mod,
             reserve.data.last_time,
         );
@@ -72,23 +88,89 @@ impl Reserve {
         reserve.data.d_rate = loan_accrual.fixed_mul_ceil(e, &reserve.data.d_rate, &SCALAR_12);
         let accrued_interest = reserve.total_liabilities(e) - pre_update_liabilities;
 
-        reserve.accrue(e, pool_config.bstop_rate, accrued_interest);
+        reserve.accrue(
+            e,
+            pool_config.bstop_rate,
+            protocol_fee_rate(e),
+            accrued_interest,
+        );
 
         reserve.data.last_time = e.ledger().timestamp();
         reserve
     }
 
+    /// Apply any in progress factor ramp to the reserve's collateral and liability factors.
+    ///
+    /// The ramped factors are never stored, as `store` only writes the reserve data.
+    fn apply_factor_ramp(&mut self, e: &Env) {
+        if let Some(ramp) = storage::get_res_ramp(e, &self.asset) {
+            let elapsed = e.ledger().sequence().saturating_sub(ramp.start_ledger);
+            if elapsed < FACTOR_RAMP_LEDGERS {
+                self.config.c_factor =
+                    interpolate_factor(ramp.c_factor, self.config.c_factor, elapsed);
+                self.config.l_factor =
+                    interpolate_factor(ramp.l_factor, self.config.l_factor, elapsed);
+            }
+        }
+    }
+
+    /// Update the reserve's smoothed utilization with the utilization since it was last updated,
+    /// and return the utilization its interest rate is calculated from. The smoothed utilization
+    /// moves towards the current utilization by the fraction of the smoothing window that has
+    /// passed.
+    ///
+    /// Returns the current utilization if smoothing is disabled
+    ///
+    /// ### Arguments
+    /// * cur_util - The utilization of the reserve since it was last updated
+    fn smooth_utilization(&mut self, e: &Env, cur_util: i128) -> i128 {
+        match self.util_smoothing.as_mut() {
+            Some(smoothing) => {
+                let elapsed = e.ledger().timestamp() - self.data.last_time;
+                if elapsed >= smoothing.window {
+                    smoothing.util = cur_util;
+                } else {
+                    smoothing.util += (cur_util - smoothing.util).fixed_mul_floor(
+                        e,
+                        &i128(elapsed),
+                        &i128(smoothing.window),
+                    );
+                }
+                smoothing.util
+            }
+            None => cur_util,
+        }
+    }
+
     /// Store the updated reserve to the ledger.
     pub fn store(&self, e: &Env) {
         storage::set_res_data(e, &self.asset, &self.data);
+        if self.util_smoothing.is_some() {
+            storage::set_util_smoothing(e, &self.asset, &self.util_smoothing);
+        }
+        if self.protocol_fee > 0 {
+            let credit = storage::get_protocol_fee_credit(e, &self.asset);
+            storage::set_protocol_fee_credit(e, &self.asset, &(credit + self.protocol_fee));
+        }
+        checkpoint_rates(e, self);
+        record_utilization(e, self);
+        update_emission_boost(e, self);
     }
 
-    /// Accrue tokens to the reserve supply. This issues any `backstop_credit` required and updates the reserve's bRate to account for the additional tokens.
+    /// Accrue tokens to the reserve supply. This issues any `backstop_credit` required, takes any
+    /// protocol fee, and updates the reserve's bRate to account for the additional tokens.
     ///
     /// ### Arguments
     /// * bstop_rate - The backstop take rate for the pool
+    /// * protocol_fee_rate - The protocol fee rate for the pool
     /// * accrued - The amount of additional underlying tokens
-    fn accrue(&mut self, e: &Env, bstop_rate: u32, accrued: i128) {
+    pub(super) fn accrue(
+        &mut self,
+        e: &Env,
+        bstop_rate: u32,
+        protocol_fee_rate: u32,
+        accrued: i128,
+    ) {
         let pre_update_supply = self.total_supply(e);
 
         if accrued > 0 {
@@ -99,14 +181,25 @@ impl Reserve {
                 new_backstop_credit = accrued.fixed_mul_floor(e, &i128(bstop_rate), &SCALAR_7);
                 self.data.backstop_credit += new_backstop_credit;
             }
-            self.data.b_rate = (pre_update_supply + accrued - new_backstop_credit).fixed_div_floor(
-                e,
-                &self.data.b_supply,
-                &SCALAR_12,
-            );
+            // the protocol fee is taken from the same accrual, so any rounding is left with
+            // the suppliers
+            let mut new_protocol_fee: i128 = 0;
+            if protocol_fee_rate > 0 {
+                new_protocol_fee = accrued.fixed_mul_floor(e, &i128(protocol_fee_rate), &SCALAR_7);
+                self.protocol_fee += new_protocol_fee;
+            }
+            self.data.b_rate =
+                (pre_update_supply + accrued - new_backstop_credit - new_protocol_fee)
+                    .fixed_div_floor(e, &self.data.b_supply, &SCALAR_12);
         }
     }
 
+    /// Fetch the amount of underlying tokens owed to the treasury from protocol fees, including
+    /// any fees accrued since the reserve was loaded
+    pub fn protocol_fee_credit(&self, e: &Env) -> i128 {
+        storage::get_protocol_fee_credit(e, &self.asset) + self.protocol_fee
+    }
+
     /// Fetch the current utilization rate for the reserve normalized to 7 decimals
     ///
     /// This is capped at 100% to ensure interest calculations are fair.
@@ -236,6 +329,18 @@ impl Reserve {
     }
 }
 
+/// Fetch the fraction of accrued interest taken as a protocol fee (7 decimals). Defaults to 0.
+pub(super) fn protocol_fee_rate(e: &Env) -> u32 {
+    storage::get_protocol_fee(e).map_or(0, |fee| fee.rate)
+}
+
+/// Linearly interpolate a factor `elapsed` ledgers into a ramp from `start` to `end`
+fn interpolate_factor(start: u32, end: u32, elapsed: u32) -> u32 {
+    let start = start as i64;
+    let delta = (end as i64 - start) * elapsed as i64 / FACTOR_RAMP_LEDGERS as i64;
+    (start + delta) as u32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +397,131 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_load_reserve_smooths_utilization() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_rate = 1_345_678_123_000;
+        reserve_data.b_rate = 1_123_456_789_000;
+        reserve_data.d_supply = 65_0000000;
+        reserve_data.b_supply = 99_0000000;
+        reserve_data.last_time = 123456 * 5 - 600;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let unsmoothed = Reserve::load(&e, &pool_config, &underlying);
+
+            // a borrow spike from 0% to 78.6% utilization moves the smoothed utilization a sixth
+            // of the way over 10 minutes of an hour window
+            storage::set_util_smoothing(
+                &e,
+                &underlying,
+                &Some(UtilSmoothing {
+                    window: 3600,
+                    util: 0,
+                }),
+            );
+            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            let smoothing = reserve.util_smoothing.clone().unwrap();
+            assert_eq!(smoothing.util, 0_1310725);
+            assert!(reserve.data.d_rate < unsmoothed.data.d_rate);
+            assert!(reserve.data.ir_mod < unsmoothed.data.ir_mod);
+
+            reserve.store(&e);
+            assert_eq!(
+                storage::get_util_smoothing(&e, &underlying),
+                Some(smoothing)
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_reserve_applies_factor_ramp() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 123456 * 5;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            // ramping from (0.95, 0.55) to (0.75, 0.75), a quarter of the way through
+            storage::set_res_ramp(
+                &e,
+                &underlying,
+                &storage::ReserveRamp {
+                    c_factor: 0_9500000,
+                    l_factor: 0_5500000,
+                    start_ledger: 123456 - FACTOR_RAMP_LEDGERS / 4,
+                },
+            );
+            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            assert_eq!(reserve.config.c_factor, 0_9000000);
+            assert_eq!(reserve.config.l_factor, 0_6000000);
+
+            // the ramp is complete
+            e.ledger().set_sequence_number(123456 + FACTOR_RAMP_LEDGERS);
+            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            assert_eq!(reserve.config.c_factor, 0_7500000);
+            assert_eq!(reserve.config.l_factor, 0_7500000);
+
+            // the configured factors are unchanged
+            let stored_config = storage::get_res_config(&e, &underlying);
+            assert_eq!(stored_config.c_factor, 0_7500000);
+            assert_eq!(stored_config.l_factor, 0_7500000);
+        });
+    }
+
     #[test]
     fn test_load_reserve_accrues_b_rate() {
         let e = Env::default();
@@ -317,7 +547,7 @@ mod tests {
         let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
         reserve_config.decimals = 18;
-        let scalar = 10i128.pow(reserve_config.decimals);
+        let scalar = decimal_scalar(reserve_config.decimals);
         reserve_data.d_rate = 1_500_000_000_000;
         reserve_data.b_rate = 1_300_000_000_000;
         reserve_data.ir_mod = SCALAR_7;
@@ -863,12 +1093,26 @@ mod tests {
         let mut reserve = testutils::default_reserve(&e);
         reserve.data.backstop_credit = 0_1234567;
 
-        reserve.accrue(&e, 0_2000000, 100_0000000);
+        reserve.accrue(&e, 0_2000000, 0, 100_0000000);
         assert_eq!(reserve.data.backstop_credit, 20_0000000 + 0_1234567);
         assert_eq!(reserve.data.b_rate, 1_800_000_000_000);
         assert_eq!(reserve.data.last_time, 0);
     }
 
+    #[test]
+    fn test_accrue_with_protocol_fee() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.data.backstop_credit = 0_1234567;
+
+        reserve.accrue(&e, 0_2000000, 0_1000000, 100_0000000);
+        assert_eq!(reserve.data.backstop_credit, 20_0000000 + 0_1234567);
+        assert_eq!(reserve.protocol_fee, 10_0000000);
+        assert_eq!(reserve.data.b_rate, 1_700_000_000_000);
+    }
+
     #[test]
     fn test_accrue_negative_delta_no_change() {
         let e = Env::default();
@@ -888,7 +1132,7 @@ mod tests {
         let mut reserve = testutils::default_reserve(&e);
         reserve.data.backstop_credit = 0_1234567;
 
-        reserve.accrue(&e, 0_2000000, -10_0000000);
+        reserve.accrue(&e, 0_2000000, 0, -10_0000000);
         assert_eq!(reserve.data.backstop_credit, 0_1234567);
         assert_eq!(reserve.data.b_rate, 1_000_000_000_000);
         assert_eq!(reserve.data.last_time, 0);
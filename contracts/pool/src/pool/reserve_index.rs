@@ -0,0 +1,97 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{errors::PoolError, storage};
+
+/// Require that a reserve's index points back to the reserve in the reserve list. Positions are
+/// keyed by reserve index, so a reserve whose index changes would take over another reserve's
+/// balances.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `index` - The index of the reserve
+///
+/// ### Panics
+/// If the reserve list does not have the asset at the index
+pub fn require_reserve_index(e: &Env, asset: &Address, index: u32) {
+    if storage::get_res_list(e).get(index) != Some(asset.clone()) {
+        panic_with_error!(e, PoolError::InvalidReserveIndex);
+    }
+}
+
+/// Require that every reserve's index maps to its position in the reserve list, and that no asset
+/// is listed twice
+///
+/// ### Panics
+/// If any reserve's stored index differs from its position in the reserve list
+pub fn require_valid_reserve_indexes(e: &Env) {
+    let res_list = storage::get_res_list(e);
+    for (index, asset) in res_list.iter().enumerate() {
+        if storage::get_res_config(e, &asset).index != index as u32 {
+            panic_with_error!(e, PoolError::InvalidReserveIndex);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_require_valid_reserve_indexes() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            require_valid_reserve_indexes(&e);
+            require_reserve_index(&e, &underlying_0, 0);
+            require_reserve_index(&e, &underlying_1, 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1237)")]
+    fn test_require_reserve_index_wrong_asset() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            require_reserve_index(&e, &underlying_0, 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1237)")]
+    fn test_require_valid_reserve_indexes_duplicate_asset() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::push_res_list(&e, &underlying_0);
+            require_valid_reserve_indexes(&e);
+        });
+    }
+}
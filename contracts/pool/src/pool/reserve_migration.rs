@@ -0,0 +1,394 @@
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    constants::{SCALAR_12, SECONDS_PER_WEEK},
+    errors::PoolError,
+    storage::{self, QueuedReserveMigration, ReserveMigration},
+};
+
+use super::{deployed_to_strategy, unbonded_balance, Reserve};
+
+/// Queue the migration of a reserve to a new underlying token. The migration is timelocked like
+/// a reserve update, so users can exit the reserve before it is executed. Only one migration can
+/// be queued at a time.
+///
+/// Returns the queued migration
+///
+/// ### Arguments
+/// * `migration` - The reserve migration
+///
+/// ### Panics
+/// If a migration is already queued, the asset is not a reserve, the new asset is already a
+/// reserve, or the decimals or conversion rate are invalid
+pub fn execute_queue_reserve_migration(
+    e: &Env,
+    migration: &ReserveMigration,
+) -> QueuedReserveMigration {
+    if storage::get_reserve_migration(e).is_some()
+        || !storage::has_res(e, &migration.asset)
+        || storage::has_res(e, &migration.new_asset)
+        || migration.decimals > 18
+        || migration.conversion_rate <= 0
+    {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let mut unlock_time = e.ledger().timestamp();
+    // require a timelock if pool status is not setup
+    if storage::get_pool_config(e).status != 6 {
+        unlock_time += SECONDS_PER_WEEK;
+    }
+    let queued = QueuedReserveMigration {
+        migration: migration.clone(),
+        unlock_time,
+    };
+    storage::set_reserve_migration(e, &Some(queued.clone()));
+    queued
+}
+
+/// Cancel the queued reserve migration
+///
+/// Returns the cancelled migration
+///
+/// ### Panics
+/// If no migration is queued
+pub fn execute_cancel_reserve_migration(e: &Env) -> ReserveMigration {
+    let queued = match storage::get_reserve_migration(e) {
+        Some(queued) => queued,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    storage::set_reserve_migration(e, &None);
+    queued.migration
+}
+
+/// Execute the queued reserve migration. The reserve is accrued on the old token, then re-mapped
+/// to the new token at the same index, so positions and emissions carry over unchanged. The
+/// bToken and dToken supplies are kept, and the b_rate and d_rate are scaled by the conversion
+/// rate so each token is worth the same amount of the new token. The pool's balance of the old
+/// token is exchanged with `migrator` for the new token at the conversion rate, and the old
+/// token's reserve is removed.
+///
+/// Auctions and the principal and borrow index tracked for each borrower's interest are kept in
+/// the old token's units, so the migration cannot be executed while any auction includes the old
+/// token or any borrower has interest tracked for the reserve.
+///
+/// Returns (the migration, amount of the old token sent, amount of the new token received)
+///
+/// ### Arguments
+/// * `migrator` - The address exchanging the new token for the pool's old token
+///
+/// ### Panics
+/// If no migration is queued, the timelock has not passed, the new asset was added as a reserve
+/// since the migration was queued, the reserve has liquidity deployed to a strategy, an open
+/// auction includes the old token, or a borrower has interest tracked for the reserve
+pub fn execute_migrate_reserve(e: &Env, migrator: &Address) -> (ReserveMigration, i128, i128) {
+    let queued = match storage::get_reserve_migration(e) {
+        Some(queued) => queued,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if queued.unlock_time > e.ledger().timestamp() {
+        panic_with_error!(e, PoolError::InitNotUnlocked);
+    }
+    storage::set_reserve_migration(e, &None);
+    let migration = queued.migration;
+    let asset = &migration.asset;
+    let new_asset = &migration.new_asset;
+    let rate = migration.conversion_rate;
+    let mut config = storage::get_res_config(e, asset);
+    if storage::has_res(e, new_asset)
+        || deployed_to_strategy(e, asset) > 0
+        || has_open_auction(e, asset)
+        || storage::get_borrow_index_count(e, &config.index) > 0
+    {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    // accrue the reserve on the old token before converting it
+    let pool_config = storage::get_pool_config(e);
+    let reserve = Reserve::load(e, &pool_config, asset);
+    reserve.store(e);
+
+    config.decimals = migration.decimals;
+    config.supply_cap = config
+        .supply_cap
+        .checked_mul(rate)
+        .map_or(i128::MAX, |cap| cap / SCALAR_12);
+    let mut data = reserve.data;
+    data.b_rate = data.b_rate.fixed_mul_floor(e, &rate, &SCALAR_12);
+    data.d_rate = data.d_rate.fixed_mul_ceil(e, &rate, &SCALAR_12);
+    data.backstop_credit = data.backstop_credit.fixed_mul_floor(e, &rate, &SCALAR_12);
+    storage::set_res_config(e, new_asset, &config);
+    storage::set_res_data(e, new_asset, &data);
+    storage::set_res_list_asset(e, config.index, new_asset);
+    storage::del_res(e, asset);
+
+    // carry over the reserve's settings and underlying balances owed
+    if let Some(ramp) = storage::get_res_ramp(e, asset) {
+        storage::set_res_ramp(e, new_asset, &ramp);
+    }
+    storage::set_util_smoothing(e, new_asset, &storage::get_util_smoothing(e, asset));
+    storage::set_util_smoothing(e, asset, &None);
+    storage::set_emission_boost(e, new_asset, &storage::get_emission_boost(e, asset));
+    storage::set_emission_boost(e, asset, &None);
+    storage::set_bad_debt(e, new_asset, &storage::get_bad_debt(e, asset));
+    storage::set_balance_delta(e, new_asset, &storage::get_balance_delta(e, asset));
+    storage::set_twap_pricing(e, new_asset, &storage::get_twap_pricing(e, asset));
+    let protocol_fee_credit = storage::get_protocol_fee_credit(e, asset);
+    storage::set_protocol_fee_credit(
+        e,
+        new_asset,
+        &protocol_fee_credit.fixed_mul_floor(e, &rate, &SCALAR_12),
+    );
+    storage::set_protocol_fee_credit(e, asset, &0);
+    let insurance_fund = storage::get_insurance_fund(e, asset);
+    storage::set_insurance_fund(
+        e,
+        new_asset,
+        &insurance_fund.fixed_mul_floor(e, &rate, &SCALAR_12),
+    );
+    storage::set_insurance_fund(e, asset, &0);
+
+    let pool = e.current_contract_address();
+    let old_balance = unbonded_balance(e, asset);
+    let new_balance = old_balance.fixed_mul_ceil(e, &rate, &SCALAR_12);
+    TokenClient::new(e, new_asset).transfer(migrator, &pool, &new_balance);
+    TokenClient::new(e, asset).transfer(&pool, migrator, &old_balance);
+    (migration, old_balance, new_balance)
+}
+
+/// Check if an open auction bids on or auctions off an asset
+///
+/// ### Arguments
+/// * `asset` - The underlying asset
+fn has_open_auction(e: &Env, asset: &Address) -> bool {
+    for key in storage::get_open_auctions(e).iter() {
+        if storage::has_auction(e, &key.auct_type, &key.user) {
+            let auction = storage::get_auction(e, &key.auct_type, &key.user);
+            if auction.bid.contains_key(asset.clone()) || auction.lot.contains_key(asset.clone()) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{auctions::AuctionData, testutils};
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger},
+    };
+
+    /// Setup a pool with a 7 decimal reserve with 100 tokens supplied and 50 borrowed, and a
+    /// migration of it to an 18 decimal token
+    fn setup_pool(e: &Env) -> (Address, Address, ReserveMigration) {
+        let (pool, _) = testutils::create_pool_with_oracle(e);
+        let bombadil = Address::generate(e);
+        let migrator = Address::generate(e);
+
+        let (underlying, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 12345;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let (new_underlying, new_underlying_client) =
+            testutils::create_token_contract(e, &bombadil);
+        new_underlying_client.mint(&migrator, &(100 * 10i128.pow(18)));
+
+        let migration = ReserveMigration {
+            asset: underlying,
+            new_asset: new_underlying,
+            decimals: 18,
+            conversion_rate: 10i128.pow(11) * SCALAR_12,
+        };
+        (pool, migrator, migration)
+    }
+
+    #[test]
+    fn test_migrate_reserve() {
+        let e = Env::default();
+        let (pool, migrator, migration) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            let queued = execute_queue_reserve_migration(&e, &migration);
+            assert_eq!(queued.unlock_time, 12345 + SECONDS_PER_WEEK);
+        });
+        e.ledger().set_timestamp(12345 + SECONDS_PER_WEEK);
+        let (old_sent, new_received) = e.as_contract(&pool, || {
+            let pre_data = Reserve::load(&e, &storage::get_pool_config(&e), &migration.asset).data;
+            let (_, old_sent, new_received) = execute_migrate_reserve(&e, &migrator);
+
+            assert!(storage::get_reserve_migration(&e).is_none());
+            assert!(!storage::has_res(&e, &migration.asset));
+            assert_eq!(
+                storage::get_res_list(&e).get_unchecked(0),
+                migration.new_asset
+            );
+            let config = storage::get_res_config(&e, &migration.new_asset);
+            assert_eq!(config.index, 0);
+            assert_eq!(config.decimals, 18);
+
+            // b and d token supplies are unchanged, and each token is worth the same
+            let data = storage::get_res_data(&e, &migration.new_asset);
+            assert_eq!(data.b_supply, pre_data.b_supply);
+            assert_eq!(data.d_supply, pre_data.d_supply);
+            assert_eq!(data.b_rate, pre_data.b_rate * 10i128.pow(11));
+            assert_eq!(data.d_rate, pre_data.d_rate * 10i128.pow(11));
+            (old_sent, new_received)
+        });
+        assert_eq!(old_sent, 50_0000000);
+        assert_eq!(new_received, 50 * 10i128.pow(18));
+        assert_eq!(
+            TokenClient::new(&e, &migration.new_asset).balance(&pool),
+            new_received
+        );
+        assert_eq!(TokenClient::new(&e, &migration.asset).balance(&pool), 0);
+        assert_eq!(
+            TokenClient::new(&e, &migration.asset).balance(&migrator),
+            50_0000000
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1203)")]
+    fn test_migrate_reserve_before_unlock() {
+        let e = Env::default();
+        let (pool, migrator, migration) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_queue_reserve_migration(&e, &migration);
+            // the reserve can still be used while the migration is timelocked
+            Reserve::load(&e, &storage::get_pool_config(&e), &migration.asset);
+        });
+        e.ledger().set_timestamp(12345 + SECONDS_PER_WEEK - 1);
+        e.as_contract(&pool, || {
+            execute_migrate_reserve(&e, &migrator);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_old_asset_not_a_reserve_after_migration() {
+        let e = Env::default();
+        let (pool, migrator, migration) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_queue_reserve_migration(&e, &migration);
+        });
+        e.ledger().set_timestamp(12345 + SECONDS_PER_WEEK);
+        e.as_contract(&pool, || {
+            execute_migrate_reserve(&e, &migrator);
+            Reserve::load(&e, &storage::get_pool_config(&e), &migration.asset);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_migrate_reserve_with_open_auction() {
+        let e = Env::default();
+        let (pool, migrator, migration) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (migration.asset.clone(), 10_0000000)],
+            lot: map![&e, (Address::generate(&e), 20_0000000)],
+            block: 100,
+        };
+        e.as_contract(&pool, || {
+            storage::set_auction(&e, &0, &samwise, &auction_data);
+            execute_queue_reserve_migration(&e, &migration);
+        });
+        e.ledger().set_timestamp(12345 + SECONDS_PER_WEEK);
+        e.as_contract(&pool, || {
+            execute_migrate_reserve(&e, &migrator);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_migrate_reserve_with_interest_tracked() {
+        let e = Env::default();
+        let (pool, migrator, migration) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_principal(&e, &samwise, &0, &10_0000000);
+            storage::set_user_borrow_index(&e, &samwise, &0, &1_000_000_000_000);
+            execute_queue_reserve_migration(&e, &migration);
+        });
+        e.ledger().set_timestamp(12345 + SECONDS_PER_WEEK);
+        e.as_contract(&pool, || {
+            execute_migrate_reserve(&e, &migrator);
+        });
+    }
+
+    #[test]
+    fn test_migrate_reserve_after_auctions_and_interest_cleared() {
+        let e = Env::default();
+        let (pool, migrator, migration) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (migration.asset.clone(), 10_0000000)],
+            lot: map![&e, (Address::generate(&e), 20_0000000)],
+            block: 100,
+        };
+        let other_auction_data = AuctionData {
+            bid: map![&e, (Address::generate(&e), 10_0000000)],
+            lot: map![&e, (Address::generate(&e), 20_0000000)],
+            block: 100,
+        };
+        e.as_contract(&pool, || {
+            storage::set_auction(&e, &0, &samwise, &auction_data);
+            storage::set_auction(&e, &0, &frodo, &other_auction_data);
+            storage::set_user_borrow_index(&e, &samwise, &0, &1_000_000_000_000);
+            storage::set_user_borrow_index(&e, &frodo, &0, &1_000_000_000_000);
+            assert_eq!(storage::get_borrow_index_count(&e, &0), 2);
+            execute_queue_reserve_migration(&e, &migration);
+        });
+        e.ledger().set_timestamp(12345 + SECONDS_PER_WEEK);
+        e.as_contract(&pool, || {
+            // the auction including the old token is filled and the borrowers repay
+            storage::del_auction(&e, &0, &samwise);
+            storage::set_user_borrow_index(&e, &samwise, &0, &0);
+            storage::set_user_borrow_index(&e, &frodo, &0, &0);
+            assert_eq!(storage::get_borrow_index_count(&e, &0), 0);
+            assert_eq!(storage::get_open_auctions(&e).len(), 1);
+
+            execute_migrate_reserve(&e, &migrator);
+            assert!(!storage::has_res(&e, &migration.asset));
+        });
+    }
+
+    #[test]
+    fn test_cancel_reserve_migration() {
+        let e = Env::default();
+        let (pool, _, migration) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_queue_reserve_migration(&e, &migration);
+            assert_eq!(execute_cancel_reserve_migration(&e), migration);
+            assert!(storage::get_reserve_migration(&e).is_none());
+            Reserve::load(&e, &storage::get_pool_config(&e), &migration.asset);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_queue_reserve_migration_to_existing_reserve() {
+        let e = Env::default();
+        let (pool, _, mut migration) = setup_pool(&e);
+
+        migration.new_asset = migration.asset.clone();
+        e.as_contract(&pool, || {
+            execute_queue_reserve_migration(&e, &migration);
+        });
+    }
+}
@@ -0,0 +1,363 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    constants::PROPOSAL_REVIEW_WINDOW,
+    errors::PoolError,
+    storage::{self, ReserveConfig, ReserveProposal},
+};
+
+use super::config::{
+    execute_queue_set_reserve, require_asset_allowed, require_valid_reserve_metadata,
+};
+
+/// Set the BLND bond required to propose a reserve
+///
+/// Panics if the bond is not positive
+pub fn execute_set_proposal_bond(e: &Env, bond: i128) {
+    if bond <= 0 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_proposal_bond(e, &bond);
+}
+
+/// Propose adding an asset as a reserve by bonding BLND. The bond is returned if the proposal is
+/// accepted or not reviewed within `PROPOSAL_REVIEW_WINDOW`, and may be slashed if it is rejected.
+///
+/// Returns the proposal
+///
+/// ### Arguments
+/// * `proposer` - The address posting the bond
+/// * `asset` - The underlying asset to add as a reserve
+/// * `metadata` - The proposed reserve configuration
+///
+/// ### Panics
+/// If reserve proposals are not enabled, the asset is already a reserve or proposed, the asset is
+/// not allowed, or the metadata is invalid
+pub fn execute_propose_reserve(
+    e: &Env,
+    proposer: &Address,
+    asset: &Address,
+    metadata: &ReserveConfig,
+) -> ReserveProposal {
+    let bond = match storage::get_proposal_bond(e) {
+        Some(bond) => bond,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if storage::has_res(e, asset) || storage::get_reserve_proposal(e, asset).is_some() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    require_valid_reserve_metadata(e, metadata);
+    require_asset_allowed(e, asset);
+
+    TokenClient::new(e, &storage::get_blnd_token(e)).transfer(
+        proposer,
+        &e.current_contract_address(),
+        &bond,
+    );
+    storage::set_bonded_blnd(e, &(storage::get_bonded_blnd(e) + bond));
+
+    let proposal = ReserveProposal {
+        proposer: proposer.clone(),
+        config: metadata.clone(),
+        bond,
+        expiration: e.ledger().timestamp() + PROPOSAL_REVIEW_WINDOW,
+    };
+    storage::set_reserve_proposal(e, asset, &proposal);
+    proposal
+}
+
+/// Accept a reserve proposal. The proposed configuration is queued as if the admin had called
+/// `queue_set_reserve`, and the bond is returned to the proposer.
+///
+/// Returns the accepted proposal
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the proposal
+///
+/// ### Panics
+/// If no proposal exists for the asset or the configuration can no longer be queued
+pub fn execute_accept_reserve_proposal(e: &Env, asset: &Address) -> ReserveProposal {
+    let proposal = load_proposal(e, asset);
+    storage::del_reserve_proposal(e, asset);
+
+    execute_queue_set_reserve(e, asset, &proposal.config);
+
+    release_bond(e, &proposal, &proposal.proposer);
+    proposal
+}
+
+/// Reject a reserve proposal. The bond is sent to the admin if `slash` is set, and is otherwise
+/// returned to the proposer.
+///
+/// Returns the rejected proposal
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the proposal
+/// * `slash` - If the bond should be slashed
+///
+/// ### Panics
+/// If no proposal exists for the asset
+pub fn execute_reject_reserve_proposal(e: &Env, asset: &Address, slash: bool) -> ReserveProposal {
+    let proposal = load_proposal(e, asset);
+    storage::del_reserve_proposal(e, asset);
+
+    let to = if slash {
+        storage::get_admin(e)
+    } else {
+        proposal.proposer.clone()
+    };
+    release_bond(e, &proposal, &to);
+    proposal
+}
+
+/// Reclaim the bond of a reserve proposal that was not reviewed before it expired
+///
+/// Returns the expired proposal
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the proposal
+///
+/// ### Panics
+/// If no proposal exists for the asset or the proposal has not expired
+pub fn execute_reclaim_proposal_bond(e: &Env, asset: &Address) -> ReserveProposal {
+    let proposal = load_proposal(e, asset);
+    if e.ledger().timestamp() < proposal.expiration {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::del_reserve_proposal(e, asset);
+
+    release_bond(e, &proposal, &proposal.proposer);
+    proposal
+}
+
+/// Fetch the pool's balance of an asset, excluding any BLND bonded to open reserve proposals
+///
+/// ### Arguments
+/// * `asset` - The asset
+pub(crate) fn unbonded_balance(e: &Env, asset: &Address) -> i128 {
+    let balance = TokenClient::new(e, asset).balance(&e.current_contract_address());
+    let bonded = storage::get_bonded_blnd(e);
+    if bonded > 0 && *asset == storage::get_blnd_token(e) {
+        balance - bonded
+    } else {
+        balance
+    }
+}
+
+/// Release the bond of a closed proposal to `to`
+fn release_bond(e: &Env, proposal: &ReserveProposal, to: &Address) {
+    storage::set_bonded_blnd(e, &(storage::get_bonded_blnd(e) - proposal.bond));
+    TokenClient::new(e, &storage::get_blnd_token(e)).transfer(
+        &e.current_contract_address(),
+        to,
+        &proposal.bond,
+    );
+}
+
+/// Load the proposal for an asset, or panic if none exists
+fn load_proposal(e: &Env, asset: &Address) -> ReserveProposal {
+    match storage::get_reserve_proposal(e, asset) {
+        Some(proposal) => proposal,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{storage::PoolConfig, testutils};
+    use sep_41_token::testutils::MockTokenClient;
+    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+
+    fn set_timestamp(e: &Env, timestamp: u64) {
+        e.ledger().set(LedgerInfo {
+            timestamp,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+    }
+
+    /// Setup an active pool that requires a 100 BLND bond to propose a reserve, and mint
+    /// 150 BLND to `proposer`
+    fn setup_pool<'a>(e: &Env, proposer: &Address) -> (Address, MockTokenClient<'a>) {
+        e.mock_all_auths();
+        set_timestamp(e, 500);
+
+        let bombadil = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (blnd, blnd_client) = testutils::create_token_contract(e, &bombadil);
+        blnd_client.mint(proposer, &150_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_blnd_token(e, &blnd);
+            execute_set_proposal_bond(e, 100_0000000);
+        });
+        (pool, blnd_client)
+    }
+
+    #[test]
+    fn test_propose_and_accept_reserve() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, blnd_client) = setup_pool(&e, &samwise);
+        let asset = Address::generate(&e);
+        let (metadata, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            let proposal = execute_propose_reserve(&e, &samwise, &asset, &metadata);
+            assert_eq!(proposal.bond, 100_0000000);
+            assert_eq!(proposal.expiration, 500 + PROPOSAL_REVIEW_WINDOW);
+            assert!(storage::get_reserve_proposal(&e, &asset).is_some());
+            assert_eq!(storage::get_bonded_blnd(&e), 100_0000000);
+        });
+        assert_eq!(blnd_client.balance(&samwise), 50_0000000);
+        assert_eq!(blnd_client.balance(&pool), 100_0000000);
+
+        e.as_contract(&pool, || {
+            execute_accept_reserve_proposal(&e, &asset);
+            assert!(storage::get_reserve_proposal(&e, &asset).is_none());
+            assert_eq!(storage::get_bonded_blnd(&e), 0);
+
+            // the proposal is queued behind the normal timelock
+            let queued = storage::get_queued_reserve_set(&e, &asset);
+            assert_eq!(queued.new_config.c_factor, metadata.c_factor);
+            assert_eq!(queued.unlock_time, 500 + crate::constants::SECONDS_PER_WEEK);
+        });
+        assert_eq!(blnd_client.balance(&samwise), 150_0000000);
+        assert_eq!(blnd_client.balance(&pool), 0);
+    }
+
+    #[test]
+    fn test_reject_reserve_proposal() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, blnd_client) = setup_pool(&e, &samwise);
+        let asset_0 = Address::generate(&e);
+        let asset_1 = Address::generate(&e);
+        let (metadata, _) = testutils::default_reserve_meta();
+        blnd_client.mint(&samwise, &50_0000000);
+
+        e.as_contract(&pool, || {
+            execute_propose_reserve(&e, &samwise, &asset_0, &metadata);
+            execute_propose_reserve(&e, &samwise, &asset_1, &metadata);
+
+            execute_reject_reserve_proposal(&e, &asset_0, false);
+            execute_reject_reserve_proposal(&e, &asset_1, true);
+            assert!(storage::get_reserve_proposal(&e, &asset_0).is_none());
+            assert!(storage::get_reserve_proposal(&e, &asset_1).is_none());
+            assert!(!storage::has_queued_reserve_set(&e, &asset_0));
+            assert_eq!(storage::get_bonded_blnd(&e), 0);
+        });
+        let admin = e.as_contract(&pool, || storage::get_admin(&e));
+        assert_eq!(blnd_client.balance(&samwise), 100_0000000);
+        assert_eq!(blnd_client.balance(&admin), 100_0000000);
+        assert_eq!(blnd_client.balance(&pool), 0);
+    }
+
+    #[test]
+    fn test_reclaim_proposal_bond() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, blnd_client) = setup_pool(&e, &samwise);
+        let asset = Address::generate(&e);
+        let (metadata, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            execute_propose_reserve(&e, &samwise, &asset, &metadata);
+        });
+
+        set_timestamp(&e, 500 + PROPOSAL_REVIEW_WINDOW);
+        e.as_contract(&pool, || {
+            execute_reclaim_proposal_bond(&e, &asset);
+            assert!(storage::get_reserve_proposal(&e, &asset).is_none());
+            assert_eq!(storage::get_bonded_blnd(&e), 0);
+        });
+        assert_eq!(blnd_client.balance(&samwise), 150_0000000);
+    }
+
+    #[test]
+    fn test_unbonded_balance() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, blnd_client) = setup_pool(&e, &samwise);
+        let (metadata, _) = testutils::default_reserve_meta();
+        let bombadil = Address::generate(&e);
+        let (underlying, underlying_client) = testutils::create_token_contract(&e, &bombadil);
+        underlying_client.mint(&pool, &10_0000000);
+
+        e.as_contract(&pool, || {
+            execute_propose_reserve(&e, &samwise, &underlying, &metadata);
+        });
+        blnd_client.mint(&pool, &25_0000000);
+
+        // the bonded BLND is excluded from the pool's balance
+        e.as_contract(&pool, || {
+            let blnd = storage::get_blnd_token(&e);
+            assert_eq!(unbonded_balance(&e, &blnd), 25_0000000);
+            assert_eq!(unbonded_balance(&e, &underlying), 10_0000000);
+        });
+        assert_eq!(blnd_client.balance(&pool), 125_0000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_reclaim_proposal_bond_before_expiration() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, _) = setup_pool(&e, &samwise);
+        let asset = Address::generate(&e);
+        let (metadata, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            execute_propose_reserve(&e, &samwise, &asset, &metadata);
+        });
+
+        set_timestamp(&e, 500 + PROPOSAL_REVIEW_WINDOW - 1);
+        e.as_contract(&pool, || {
+            execute_reclaim_proposal_bond(&e, &asset);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_propose_reserve_twice() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let (pool, _) = setup_pool(&e, &samwise);
+        let asset = Address::generate(&e);
+        let (metadata, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            execute_propose_reserve(&e, &samwise, &asset, &metadata);
+            execute_propose_reserve(&e, &samwise, &asset, &metadata);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_propose_reserve_not_enabled() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let (metadata, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            execute_propose_reserve(&e, &samwise, &Address::generate(&e), &metadata);
+        });
+    }
+}
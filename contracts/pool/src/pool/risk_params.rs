@@ -0,0 +1,234 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+use trustbridge_common::{Asset, PriceFeedClient};
+
+use crate::{constants::RISK_PARAMS_VERSION, storage};
+
+use super::pool::Pool;
+
+/// The risk parameters of a reserve
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveRiskParams {
+    pub asset: Address,          // the underlying asset address
+    pub index: u32,              // the index of the reserve in the list
+    pub decimals: u32,           // the decimals of the underlying asset
+    pub enabled: bool,           // the enabled flag of the reserve
+    pub c_factor: u32, // the collateral factor currently in effect, including any factor ramp, in 7 decimals
+    pub l_factor: u32, // the liability factor currently in effect, including any factor ramp, in 7 decimals
+    pub util: u32,     // the target utilization rate in 7 decimals
+    pub max_util: u32, // the maximum allowed utilization rate in 7 decimals
+    pub r_base: u32,   // the R0 value (base rate) in the interest rate formula in 7 decimals
+    pub r_one: u32,    // the R1 value in the interest rate formula in 7 decimals
+    pub r_two: u32,    // the R2 value in the interest rate formula in 7 decimals
+    pub r_three: u32,  // the R3 value in the interest rate formula in 7 decimals
+    pub reactivity: u32, // the reactivity constant for the reserve in 7 decimals
+    pub ir_mod: i128,  // the current interest rate curve modifier in 7 decimals
+    pub supply_cap: i128, // the total amount of underlying tokens that can be supplied to the reserve
+    pub total_supply: i128, // the total amount of underlying tokens supplied to the reserve
+    pub total_liabilities: i128, // the total amount of underlying tokens borrowed from the reserve
+    pub price: i128,      // the oracle price of the asset, or 0 if the oracle has no price for it
+    pub price_timestamp: u64, // the timestamp of the oracle price, or 0 if the oracle has no price for it
+    pub twap_pricing: bool, // if health calculations also price the reserve with the oracle's TWAP
+}
+
+/// The risk parameters of the pool, in a canonical form for off-chain risk simulators
+#[derive(Clone)]
+#[contracttype]
+pub struct RiskParams {
+    pub version: u32, // the version of the schema, incremented whenever a field is added, removed, or changes meaning
+    pub oracle: Address, // the contract address of the oracle
+    pub oracle_decimals: u32, // the decimals of the oracle's prices
+    pub status: u32,  // the status of the pool
+    pub bstop_rate: u32, // the rate the backstop takes on accrued debt interest in 7 decimals
+    pub min_collateral: i128, // the minimum collateral required to open a liability position, in oracle decimals
+    pub max_positions: u32,   // the maximum number of effective positions a single user can hold
+    pub reserves: Vec<ReserveRiskParams>, // the risk parameters of each reserve, ordered by index
+}
+
+/// Export the risk parameters of the pool and each of its reserves. Reserves are accrued to the
+/// current ledger, but nothing is written to the ledger.
+///
+/// Prices are read from the oracle without validation, so simulators can decide how to handle
+/// stale or missing prices.
+pub fn export_risk_params(e: &Env) -> RiskParams {
+    let mut pool = Pool::load(e);
+    let oracle_client = PriceFeedClient::new(e, &pool.config.oracle);
+
+    let mut reserves = Vec::new(e);
+    for asset in storage::get_res_list(e).iter() {
+        let reserve = pool.load_reserve(e, &asset, false);
+        let (price, price_timestamp) = match oracle_client.lastprice(&Asset::Stellar(asset.clone()))
+        {
+            Some(price_data) => (price_data.price, price_data.timestamp),
+            None => (0, 0),
+        };
+        let twap_pricing = storage::get_twap_pricing(e, &asset);
+        reserves.push_back(ReserveRiskParams {
+            asset,
+            index: reserve.config.index,
+            decimals: reserve.config.decimals,
+            enabled: reserve.config.enabled,
+            c_factor: reserve.config.c_factor,
+            l_factor: reserve.config.l_factor,
+            util: reserve.config.util,
+            max_util: reserve.config.max_util,
+            r_base: reserve.config.r_base,
+            r_one: reserve.config.r_one,
+            r_two: reserve.config.r_two,
+            r_three: reserve.config.r_three,
+            reactivity: reserve.config.reactivity,
+            ir_mod: reserve.data.ir_mod,
+            supply_cap: reserve.config.supply_cap,
+            total_supply: reserve.total_supply(e),
+            total_liabilities: reserve.total_liabilities(e),
+            price,
+            price_timestamp,
+            twap_pricing,
+        });
+    }
+
+    RiskParams {
+        version: RISK_PARAMS_VERSION,
+        oracle: pool.config.oracle.clone(),
+        oracle_decimals: pool.load_price_decimals(e),
+        status: pool.config.status,
+        bstop_rate: pool.config.bstop_rate,
+        min_collateral: pool.config.min_collateral,
+        max_positions: pool.config.max_positions,
+        reserves,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        constants::FACTOR_RAMP_LEDGERS,
+        storage::{PoolConfig, ReserveRamp},
+        testutils,
+    };
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Symbol,
+    };
+
+    #[test]
+    fn test_export_risk_params() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50000,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_config_1.decimals = 9;
+        reserve_config_1.supply_cap = 500_000_000000000;
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.ir_mod = 1_5000000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 0_2500000]);
+
+        let pool_config = PoolConfig {
+            oracle: oracle.clone(),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 1,
+            max_positions: 6,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            // reserve 1 is halfway through ramping from (0.95, 0.55) to (0.75, 0.75)
+            storage::set_res_ramp(
+                &e,
+                &underlying_1,
+                &ReserveRamp {
+                    c_factor: 0_9500000,
+                    l_factor: 0_5500000,
+                    start_ledger: 50000 - FACTOR_RAMP_LEDGERS / 2,
+                },
+            );
+            storage::set_twap_pricing(&e, &underlying_1, &true);
+
+            let risk_params = export_risk_params(&e);
+            assert_eq!(risk_params.version, RISK_PARAMS_VERSION);
+            assert_eq!(risk_params.oracle, oracle);
+            assert_eq!(risk_params.oracle_decimals, 7);
+            assert_eq!(risk_params.status, 1);
+            assert_eq!(risk_params.bstop_rate, 0_1000000);
+            assert_eq!(risk_params.min_collateral, 1_0000000);
+            assert_eq!(risk_params.max_positions, 6);
+            assert_eq!(risk_params.reserves.len(), 2);
+
+            let reserve_0 = risk_params.reserves.get_unchecked(0);
+            assert_eq!(reserve_0.asset, underlying_0);
+            assert_eq!(reserve_0.index, 0);
+            assert_eq!(reserve_0.decimals, 7);
+            assert!(reserve_0.enabled);
+            assert_eq!(reserve_0.c_factor, 0_7500000);
+            assert_eq!(reserve_0.l_factor, 0_7500000);
+            assert_eq!(reserve_0.util, 0_7500000);
+            assert_eq!(reserve_0.max_util, 0_9500000);
+            assert_eq!(reserve_0.r_base, 0_0100000);
+            assert_eq!(reserve_0.r_one, 0_0500000);
+            assert_eq!(reserve_0.r_two, 0_5000000);
+            assert_eq!(reserve_0.r_three, 1_5000000);
+            assert_eq!(reserve_0.reactivity, 0_0000020);
+            assert_eq!(reserve_0.ir_mod, 1_0000000);
+            assert_eq!(reserve_0.supply_cap, reserve_config_0.supply_cap);
+            assert_eq!(reserve_0.total_supply, 100_0000000);
+            assert_eq!(reserve_0.total_liabilities, 75_0000000);
+            assert_eq!(reserve_0.price, 1_0000000);
+            assert_eq!(reserve_0.price_timestamp, 12345);
+            assert!(!reserve_0.twap_pricing);
+
+            let reserve_1 = risk_params.reserves.get_unchecked(1);
+            assert_eq!(reserve_1.asset, underlying_1);
+            assert_eq!(reserve_1.index, 1);
+            assert_eq!(reserve_1.decimals, 9);
+            assert_eq!(reserve_1.c_factor, 0_8500000);
+            assert_eq!(reserve_1.l_factor, 0_6500000);
+            assert_eq!(reserve_1.ir_mod, 1_5000000);
+            assert_eq!(reserve_1.supply_cap, 500_000_000000000);
+            assert_eq!(reserve_1.price, 0_2500000);
+            assert!(reserve_1.twap_pricing);
+
+            // nothing is written to the ledger
+            assert_eq!(
+                storage::get_res_config(&e, &underlying_1).c_factor,
+                0_7500000
+            );
+        });
+    }
+}
@@ -0,0 +1,205 @@
+use cast::i128;
+use soroban_sdk::{panic_with_error, vec, Address, Env};
+
+use crate::{
+    constants::{RISK_TIERS, SCALAR_7},
+    errors::PoolError,
+    storage::{self, RiskTierConfig},
+};
+
+use super::{health_factor::PositionData, pool::Pool, User};
+
+/// The thresholds for each risk tier used until the pool sets its own
+pub fn default_risk_tiers(e: &Env) -> RiskTierConfig {
+    RiskTierConfig {
+        min_hf: vec![e, 2_0000000, 1_5000000, 1_2000000, 1_0500000],
+        min_assets: vec![e, 3, 2, 1, 1],
+        max_liquidations: vec![e, 0, 1, 2, 3],
+    }
+}
+
+/// Set the thresholds a user must meet for each risk tier from A to D. Each tier must be at
+/// least as lenient as the tier above it.
+///
+/// ### Arguments
+/// * `config` - The risk tier thresholds
+///
+/// ### Panics
+/// If there are not thresholds for exactly 4 tiers, a minimum health factor is under 1, or a tier
+/// is stricter than the tier above it
+pub fn execute_set_risk_tiers(e: &Env, config: &RiskTierConfig) {
+    let tiers = RISK_TIERS - 1;
+    if config.min_hf.len() != tiers
+        || config.min_assets.len() != tiers
+        || config.max_liquidations.len() != tiers
+    {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    for tier in 0..tiers {
+        if i128(config.min_hf.get_unchecked(tier)) < SCALAR_7 {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+        if tier > 0
+            && (config.min_hf.get_unchecked(tier) > config.min_hf.get_unchecked(tier - 1)
+                || config.min_assets.get_unchecked(tier)
+                    > config.min_assets.get_unchecked(tier - 1)
+                || config.max_liquidations.get_unchecked(tier)
+                    < config.max_liquidations.get_unchecked(tier - 1))
+        {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+    storage::set_risk_tiers(e, config);
+}
+
+/// Load the risk tier of a user, from 0 (A) to 4 (E). A user is in the first tier whose minimum
+/// health factor, minimum number of collateral reserves, and maximum number of liquidations they
+/// meet, or in tier E if they meet none.
+///
+/// ### Arguments
+/// * `user` - The user
+pub fn load_risk_tier(e: &Env, user: &Address) -> u32 {
+    let config = storage::get_risk_tiers(e).unwrap_or_else(|| default_risk_tiers(e));
+    let mut pool = Pool::load(e);
+    let user_state = User::load(e, user);
+    let position_data = PositionData::calculate_from_positions(e, &mut pool, &user_state.positions);
+    let assets = user_state.positions.collateral.len();
+    let liquidations = storage::get_liquidation_count(e, user);
+
+    for tier in 0..RISK_TIERS - 1 {
+        if !position_data.is_hf_under(e, i128(config.min_hf.get_unchecked(tier)))
+            && assets >= config.min_assets.get_unchecked(tier)
+            && liquidations <= config.max_liquidations.get_unchecked(tier)
+        {
+            return tier;
+        }
+    }
+    RISK_TIERS - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pool::Positions, storage::PoolConfig, testutils};
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{map, testutils::Address as _, Symbol};
+
+    /// Setup a pool with two reserves priced at 1, and a user with 100 tokens of collateral in
+    /// each and 50 tokens of liabilities, for a health factor of 2.25
+    fn setup_pool(e: &Env, user: &Address) -> Address {
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0),
+                Asset::Stellar(underlying_1),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 4,
+        };
+        let positions = Positions {
+            liabilities: map![e, (0, 50_0000000)],
+            collateral: map![e, (0, 100_0000000), (1, 100_0000000)],
+            supply: map![e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, user, &positions);
+        });
+        pool
+    }
+
+    #[test]
+    fn test_load_risk_tier() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = setup_pool(&e, &samwise);
+
+        e.as_contract(&pool, || {
+            // tier A requires collateral in 3 reserves
+            assert_eq!(load_risk_tier(&e, &samwise), 1);
+
+            // tier B allows 1 liquidation
+            storage::set_liquidation_count(&e, &samwise, &2);
+            assert_eq!(load_risk_tier(&e, &samwise), 2);
+
+            // a user without collateral meets no tier
+            assert_eq!(load_risk_tier(&e, &frodo), 4);
+
+            let config = RiskTierConfig {
+                min_hf: vec![&e, 2_0000000, 1_5000000, 1_2000000, 1_0500000],
+                min_assets: vec![&e, 2, 2, 1, 1],
+                max_liquidations: vec![&e, 2, 2, 3, 4],
+            };
+            execute_set_risk_tiers(&e, &config);
+            assert_eq!(storage::get_risk_tiers(&e), Some(config));
+            assert_eq!(load_risk_tier(&e, &samwise), 0);
+
+            let config = RiskTierConfig {
+                min_hf: vec![&e, 3_0000000, 2_5000000, 2_2000000, 1_0500000],
+                min_assets: vec![&e, 2, 2, 1, 1],
+                max_liquidations: vec![&e, 2, 2, 3, 4],
+            };
+            execute_set_risk_tiers(&e, &config);
+            assert_eq!(load_risk_tier(&e, &samwise), 2);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_risk_tiers_stricter_than_tier_above() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        let config = RiskTierConfig {
+            min_hf: vec![&e, 2_0000000, 1_5000000, 1_2000000, 1_0500000],
+            min_assets: vec![&e, 3, 2, 1, 1],
+            max_liquidations: vec![&e, 1, 0, 2, 3],
+        };
+        e.as_contract(&pool, || {
+            execute_set_risk_tiers(&e, &config);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_risk_tiers_missing_tier() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        let config = RiskTierConfig {
+            min_hf: vec![&e, 2_0000000, 1_5000000, 1_2000000],
+            min_assets: vec![&e, 3, 2, 1],
+            max_liquidations: vec![&e, 0, 1, 2],
+        };
+        e.as_contract(&pool, || {
+            execute_set_risk_tiers(&e, &config);
+        });
+    }
+}
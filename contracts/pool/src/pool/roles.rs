@@ -0,0 +1,162 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+use trustbridge_common::{roles, Role};
+
+use crate::{errors::PoolError, storage};
+
+/// Grant a role to an address. The admin role is only held by the pool's admin, and is
+/// transferred through `propose_admin` and `accept_admin`.
+///
+/// ### Arguments
+/// * `role` - The role to grant
+/// * `member` - The address to grant the role to
+///
+/// ### Panics
+/// If the role is the admin role
+pub fn execute_grant_role(e: &Env, role: Role, member: &Address) {
+    if role == Role::Admin {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    roles::grant_role(e, role, member);
+}
+
+/// Revoke a role from an address
+///
+/// ### Arguments
+/// * `role` - The role to revoke
+/// * `member` - The address to revoke the role from
+///
+/// ### Panics
+/// If the role is the admin role
+pub fn execute_revoke_role(e: &Env, role: Role, member: &Address) {
+    if role == Role::Admin {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    roles::revoke_role(e, role, member);
+}
+
+/// Set the guardian that can restrict the pool. The guardian is granted the pauser role, and the
+/// previous guardian's pauser role is revoked.
+///
+/// ### Arguments
+/// * `guardian` - The address of the guardian, or None to remove it
+pub fn execute_set_status_guardian(e: &Env, guardian: &Option<Address>) {
+    if let Some(cur_guardian) = storage::get_status_guardian(e) {
+        roles::revoke_role(e, Role::Pauser, &cur_guardian);
+    }
+    if let Some(guardian) = guardian {
+        roles::grant_role(e, Role::Pauser, guardian);
+    }
+    storage::set_status_guardian(e, guardian);
+}
+
+/// Check if an address holds a role. The admin holds every role.
+///
+/// ### Arguments
+/// * `role` - The role
+/// * `member` - The address to check
+pub fn has_role(e: &Env, role: Role, member: &Address) -> bool {
+    *member == storage::get_admin(e) || roles::has_role(e, role, member)
+}
+
+/// Require that the caller authorized the invocation and holds a role
+///
+/// ### Arguments
+/// * `role` - The role the caller must hold
+/// * `caller` - The address calling the pool
+///
+/// ### Panics
+/// If the caller does not hold the role
+pub fn require_role(e: &Env, role: Role, caller: &Address) {
+    caller.require_auth();
+    if !has_role(e, role, caller) {
+        panic_with_error!(e, PoolError::UnauthorizedError);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_roles() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_admin(&e, &bombadil);
+
+            // the admin holds every role
+            assert!(has_role(&e, Role::Admin, &bombadil));
+            assert!(has_role(&e, Role::Treasurer, &bombadil));
+            assert!(!has_role(&e, Role::Risk, &samwise));
+
+            execute_grant_role(&e, Role::Risk, &samwise);
+            assert!(has_role(&e, Role::Risk, &samwise));
+            assert!(!has_role(&e, Role::Pauser, &samwise));
+            require_role(&e, Role::Risk, &samwise);
+
+            execute_revoke_role(&e, Role::Risk, &samwise);
+            assert!(!has_role(&e, Role::Risk, &samwise));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #4)")]
+    fn test_require_role_not_held() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_admin(&e, &bombadil);
+            execute_grant_role(&e, Role::Pauser, &samwise);
+
+            require_role(&e, Role::Risk, &samwise);
+        });
+    }
+
+    #[test]
+    fn test_set_status_guardian() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_admin(&e, &bombadil);
+
+            execute_set_status_guardian(&e, &Some(samwise.clone()));
+            assert_eq!(storage::get_status_guardian(&e), Some(samwise.clone()));
+            assert!(has_role(&e, Role::Pauser, &samwise));
+
+            // replacing the guardian revokes the previous guardian's pauser role
+            execute_set_status_guardian(&e, &Some(frodo.clone()));
+            assert!(!has_role(&e, Role::Pauser, &samwise));
+            assert!(has_role(&e, Role::Pauser, &frodo));
+
+            execute_set_status_guardian(&e, &None);
+            assert_eq!(storage::get_status_guardian(&e), None);
+            assert!(!has_role(&e, Role::Pauser, &frodo));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_grant_admin_role() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_grant_role(&e, Role::Admin, &samwise);
+        });
+    }
+}
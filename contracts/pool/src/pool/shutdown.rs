@@ -0,0 +1,367 @@
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{map, panic_with_error, Address, Env, Map};
+
+use crate::{
+    constants::SHUTDOWN_REDEEM_DELAY,
+    errors::PoolError,
+    events::PoolEvents,
+    storage::{self, ShutdownData},
+};
+
+use super::{
+    accrue_coverage, deployed_to_strategy, pool::Pool, recall_shortfall, unbonded_balance, User,
+};
+
+/// Shut down the pool. The pool is frozen, withdrawals are disabled, and the price of every reserve
+/// is frozen at the current oracle price, except for sunset reserves which have none. Outstanding
+/// positions can still be repaid or settled through auctions, after which suppliers can redeem
+/// their bTokens pro-rata for the underlying assets remaining in the pool.
+///
+/// Returns the shutdown state
+///
+/// ### Panics
+/// If the pool has already been shut down or any reserve that is not sunset does not have a valid
+/// price
+pub fn execute_shutdown(e: &Env) -> ShutdownData {
+    if storage::get_shutdown(e).is_some() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let mut pool = Pool::load(e);
+    let mut prices = map![e];
+    for asset in storage::get_res_list(e).iter() {
+        if storage::get_sunset(e, &asset) {
+            continue;
+        }
+        let price = pool.load_price(e, &asset);
+        prices.set(asset, price);
+    }
+
+    // admin frozen supersedes any permissionless status update
+    pool.config.status = 4;
+    storage::set_pool_config(e, &pool.config);
+
+    let shutdown = ShutdownData {
+        timestamp: e.ledger().timestamp(),
+        prices,
+    };
+    storage::set_shutdown(e, &shutdown);
+    shutdown
+}
+
+/// Redeem all of a user's bTokens for their pro-rata share of the underlying assets held by a
+/// shut down pool. Each reserve's share is the user's bTokens over the reserve's bToken supply,
+/// applied to the pool's balance of the asset less any backstop credit, insurance fund, and
+/// protocol fees.
+///
+/// Returns a map of asset to the amount of underlying sent to `to`
+///
+/// ### Arguments
+/// * `from` - The user redeeming
+/// * `to` - The address receiving the underlying assets
+///
+/// ### Panics
+/// If the pool is not shut down, the redemption delay has not passed, or the user has liabilities
+pub fn execute_redeem(e: &Env, from: &Address, to: &Address) -> Map<Address, i128> {
+    let shutdown = match storage::get_shutdown(e) {
+        Some(shutdown) => shutdown,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if e.ledger().timestamp() < shutdown.timestamp + SHUTDOWN_REDEEM_DELAY {
+        panic_with_error!(e, PoolError::InvalidRedemption);
+    }
+
+    let mut pool = Pool::load(e);
+    let mut user = User::load(e, from);
+    // liabilities must be repaid or settled before redeeming
+    if user.has_liabilities() {
+        panic_with_error!(e, PoolError::InvalidRedemption);
+    }
+
+    let mut redeemed = map![e];
+    for (index, asset) in storage::get_res_list(e).iter().enumerate() {
+        let index = index as u32;
+        if user.get_total_supply(index) == 0 {
+            continue;
+        }
+
+        let mut reserve = pool.load_reserve(e, &asset, true);
+        accrue_coverage(e, &mut user, &mut reserve);
+        let supply = user.get_supply(index);
+        let collateral = user.get_collateral(index);
+        let b_tokens = supply + collateral;
+        let token_client = TokenClient::new(e, &asset);
+        let available = (unbonded_balance(e, &asset) + deployed_to_strategy(e, &asset)
+            - reserve.data.backstop_credit
+            - storage::get_insurance_fund(e, &asset)
+            - reserve.protocol_fee_credit(e))
+        .max(0);
+        let amount = available.fixed_mul_floor(e, &b_tokens, &reserve.data.b_supply);
+        if supply > 0 {
+            user.remove_supply(e, &mut reserve, supply);
+        }
+        if collateral > 0 {
+            user.remove_collateral(e, &mut reserve, collateral);
+        }
+        pool.cache_reserve(reserve);
+
+        if amount > 0 {
+            recall_shortfall(e, &asset, amount);
+            token_client.transfer(&e.current_contract_address(), to, &amount);
+        }
+        PoolEvents::redeem(e, asset.clone(), from.clone(), amount, b_tokens);
+        redeemed.set(asset, amount);
+    }
+
+    pool.store_cached_reserves(e);
+    user.store(e);
+    redeemed
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pool::Positions,
+        storage::PoolConfig,
+        testutils::{self, create_pool},
+    };
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use sep_41_token::testutils::MockTokenClient;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Symbol,
+    };
+
+    fn set_timestamp(e: &Env, timestamp: u64) {
+        e.ledger().set(LedgerInfo {
+            timestamp,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+    }
+
+    /// Setup a pool with two reserves, where reserve 0 has 100 bTokens and 75 dTokens outstanding
+    /// and reserve 1 has no liabilities
+    fn setup_pool<'a>(e: &Env) -> (Address, Address, Address, MockTokenClient<'a>) {
+        e.mock_all_auths();
+        set_timestamp(e, 12345);
+
+        let bombadil = Address::generate(e);
+        let pool_address = create_pool(e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(e);
+
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.d_supply = 0;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 2_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(e, &pool_config);
+        });
+        (
+            pool_address,
+            underlying_0,
+            underlying_1,
+            underlying_0_client,
+        )
+    }
+
+    #[test]
+    fn test_execute_shutdown() {
+        let e = Env::default();
+        let (pool, underlying_0, underlying_1, _) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            let shutdown = execute_shutdown(&e);
+            assert_eq!(shutdown.timestamp, 12345);
+            assert_eq!(
+                shutdown.prices.get_unchecked(underlying_0.clone()),
+                1_0000000
+            );
+            assert_eq!(
+                shutdown.prices.get_unchecked(underlying_1.clone()),
+                2_0000000
+            );
+            assert_eq!(storage::get_pool_config(&e).status, 4);
+
+            // prices stay frozen after the oracle goes stale
+            set_timestamp(&e, 12345 + 2 * 24 * 60 * 60);
+            let mut pool = Pool::load(&e);
+            assert!(pool.is_shutdown);
+            assert_eq!(pool.load_price(&e, &underlying_1), 2_0000000);
+        });
+    }
+
+    #[test]
+    fn test_execute_shutdown_skips_sunset_reserve() {
+        let e = Env::default();
+        let (pool, underlying_0, underlying_1, _) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_sunset(&e, &underlying_1, &true);
+
+            let shutdown = execute_shutdown(&e);
+            assert_eq!(
+                shutdown.prices.get_unchecked(underlying_0.clone()),
+                1_0000000
+            );
+            assert!(shutdown.prices.get(underlying_1.clone()).is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_shutdown_twice() {
+        let e = Env::default();
+        let (pool, _, _, _) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_shutdown(&e);
+            execute_shutdown(&e);
+        });
+    }
+
+    #[test]
+    fn test_execute_redeem() {
+        let e = Env::default();
+        let (pool, underlying_0, _, underlying_0_client) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            // samwise holds 40 of the 100 bTokens of reserve 0
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e],
+                    collateral: map![&e, (0, 30_0000000)],
+                    supply: map![&e, (0, 10_0000000)],
+                },
+            );
+            execute_shutdown(&e);
+        });
+
+        set_timestamp(&e, 12345 + SHUTDOWN_REDEEM_DELAY);
+        let expected = e.as_contract(&pool, || {
+            // the pool holds 25 underlying, as 75 is still borrowed, some of which is owed
+            // to the backstop for the interest accrued since shutdown
+            let redeemed = execute_redeem(&e, &samwise, &frodo);
+            let reserve_data = storage::get_res_data(&e, &underlying_0);
+            assert!(reserve_data.backstop_credit > 0);
+            let expected = (25_0000000 - reserve_data.backstop_credit) * 4 / 10;
+            assert_eq!(redeemed.len(), 1);
+            assert_eq!(redeemed.get_unchecked(underlying_0.clone()), expected);
+            assert_eq!(reserve_data.b_supply, 60_0000000);
+
+            let positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(positions.collateral.len(), 0);
+            assert_eq!(positions.supply.len(), 0);
+            expected
+        });
+        assert_eq!(underlying_0_client.balance(&frodo), expected);
+        assert_eq!(underlying_0_client.balance(&pool), 25_0000000 - expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1230)")]
+    fn test_execute_redeem_before_delay() {
+        let e = Env::default();
+        let (pool, _, _, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_shutdown(&e);
+        });
+
+        set_timestamp(&e, 12345 + SHUTDOWN_REDEEM_DELAY - 1);
+        e.as_contract(&pool, || {
+            execute_redeem(&e, &samwise, &samwise);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1230)")]
+    fn test_execute_redeem_with_liabilities() {
+        let e = Env::default();
+        let (pool, _, _, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e, (1, 1_0000000)],
+                    collateral: map![&e, (0, 30_0000000)],
+                    supply: map![&e],
+                },
+            );
+            execute_shutdown(&e);
+        });
+
+        set_timestamp(&e, 12345 + SHUTDOWN_REDEEM_DELAY);
+        e.as_contract(&pool, || {
+            execute_redeem(&e, &samwise, &samwise);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_redeem_not_shutdown() {
+        let e = Env::default();
+        let (pool, _, _, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_redeem(&e, &samwise, &samwise);
+        });
+    }
+}
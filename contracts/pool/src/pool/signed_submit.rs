@@ -0,0 +1,391 @@
+use soroban_sdk::{panic_with_error, xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+use crate::{errors::PoolError, storage};
+
+use super::{actions::Request, execute_submit, Positions};
+
+/// The type of the domain signed request bundles are bound to
+const DOMAIN_TYPE: &str = "Domain(address pool,bytes32 network)";
+
+/// The type of a request
+const REQUEST_TYPE: &str = "Request(u32 request_type,address address,i128 amount)";
+
+/// The type of a bundle of requests signed for a relayed submit, followed by the types it uses
+const SIGNED_SUBMIT_TYPE: &str = concat!(
+    "SignedSubmit(address from,Request[] requests,u64 nonce,u64 expiry)",
+    "Request(u32 request_type,address address,i128 amount)"
+);
+
+/// Hash a type, so struct hashes commit to the names and types of their fields
+fn type_hash(e: &Env, type_str: &str) -> Bytes {
+    e.crypto()
+        .sha256(&Bytes::from_slice(e, type_str.as_bytes()))
+        .to_bytes()
+        .into()
+}
+
+/// Fetch the domain separator signed request bundles are bound to, so a signature is only valid
+/// for this pool on this network. This is the sha256 of the type hash of
+/// `Domain(address pool,bytes32 network)`, the XDR encoding of the pool's address, and the
+/// network ID.
+pub fn domain_separator(e: &Env) -> BytesN<32> {
+    let mut encoded = type_hash(e, DOMAIN_TYPE);
+    encoded.append(&e.current_contract_address().to_xdr(e));
+    encoded.append(&e.ledger().network_id().into());
+    e.crypto().sha256(&encoded).into()
+}
+
+/// Hash a request as a typed struct. This is the sha256 of the type hash of
+/// `Request(u32 request_type,address address,i128 amount)` and the XDR encoding of each field.
+///
+/// ### Arguments
+/// * `request` - The request to hash
+pub fn hash_request(e: &Env, request: &Request) -> BytesN<32> {
+    let mut encoded = type_hash(e, REQUEST_TYPE);
+    encoded.append(&request.request_type.to_xdr(e));
+    encoded.append(&request.address.clone().to_xdr(e));
+    encoded.append(&request.amount.to_xdr(e));
+    e.crypto().sha256(&encoded).into()
+}
+
+/// Hash a set of requests canonically. The hash is the sha256 of the concatenated hashes of
+/// each request, so any client that can hash a request can reproduce it.
+///
+/// ### Arguments
+/// * `requests` - The requests to hash
+pub fn hash_requests(e: &Env, requests: &Vec<Request>) -> BytesN<32> {
+    let mut encoded = Bytes::new(e);
+    for request in requests.iter() {
+        encoded.append(&hash_request(e, &request).into());
+    }
+    e.crypto().sha256(&encoded).into()
+}
+
+/// Build the message a user signs to authorize a relayer to submit a set of requests on their
+/// behalf. This is `0x1901`, the domain separator, and the struct hash of
+/// `SignedSubmit(address from,Request[] requests,u64 nonce,u64 expiry)`, where the requests are
+/// encoded with `hash_requests`.
+///
+/// ### Arguments
+/// * `from` - The address of the user whose positions are being modified
+/// * `requests` - The requests being submitted
+/// * `nonce` - The user's nonce the requests are valid for
+/// * `expiry` - The last timestamp the requests can be submitted at
+pub fn signed_submit_payload(
+    e: &Env,
+    from: &Address,
+    requests: &Vec<Request>,
+    nonce: u64,
+    expiry: u64,
+) -> Bytes {
+    let mut encoded = type_hash(e, SIGNED_SUBMIT_TYPE);
+    encoded.append(&from.clone().to_xdr(e));
+    encoded.append(&hash_requests(e, requests).into());
+    encoded.append(&nonce.to_xdr(e));
+    encoded.append(&expiry.to_xdr(e));
+    let struct_hash: BytesN<32> = e.crypto().sha256(&encoded).into();
+
+    let mut payload = Bytes::from_array(e, &[0x19, 0x01]);
+    payload.append(&domain_separator(e).into());
+    payload.append(&struct_hash.into());
+    payload
+}
+
+/// Verify a user signed a set of requests off-chain with their signing key. The signature is
+/// bound to this pool and network by the domain separator, and to the user's current nonce, so it
+/// can't be replayed once the nonce is incremented.
+///
+/// ### Arguments
+/// * `from` - The address of the user whose positions are being modified
+/// * `requests` - The requests being submitted
+/// * `signature` - The ed25519 signature of `signed_submit_payload` by the user's signing key
+/// * `nonce` - The user's current nonce
+/// * `expiry` - The last timestamp the requests can be submitted at
+///
+/// ### Panics
+/// * If the user has no signing key or the signature is invalid
+/// * If the nonce is not the user's current nonce or the requests have expired
+pub fn verify_submit_signature(
+    e: &Env,
+    from: &Address,
+    requests: &Vec<Request>,
+    signature: &BytesN<64>,
+    nonce: u64,
+    expiry: u64,
+) {
+    let signing_key = match storage::get_signing_key(e, from) {
+        Some(signing_key) => signing_key,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if nonce != storage::get_user_nonce(e, from) || expiry < e.ledger().timestamp() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let payload = signed_submit_payload(e, from, requests, nonce, expiry);
+    e.crypto().ed25519_verify(&signing_key, &payload, signature);
+}
+
+/// Submit a set of requests for a user that were signed off-chain by the user's signing key.
+/// `from` sends and receives all tokens, and tokens sent to the pool are moved with transfer_from.
+///
+/// ### Arguments
+/// * `from` - The address of the user whose positions are being modified
+/// * `requests` - A vec of requests to be processed
+/// * `signature` - The ed25519 signature of `signed_submit_payload` by the user's signing key
+/// * `nonce` - The user's current nonce
+/// * `expiry` - The last timestamp the requests can be submitted at
+///
+/// ### Panics
+/// * If the user has no signing key or the signature is invalid
+/// * If the nonce is not the user's current nonce or the requests have expired
+pub fn execute_submit_signed(
+    e: &Env,
+    from: &Address,
+    requests: Vec<Request>,
+    signature: &BytesN<64>,
+    nonce: u64,
+    expiry: u64,
+) -> Positions {
+    verify_submit_signature(e, from, &requests, signature, nonce, expiry);
+
+    // the submit increments the nonce, so the signature cannot be replayed
+    execute_submit(e, from, from, from, requests, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pool::RequestType, storage::PoolConfig, testutils};
+
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        vec,
+    };
+
+    #[test]
+    fn test_hash_requests() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let underlying = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: underlying.clone(),
+                amount: 10_0000000,
+            },
+        ];
+        let other_requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: underlying.clone(),
+                amount: 10_0000001,
+            },
+        ];
+        e.as_contract(&pool, || {
+            assert_eq!(
+                hash_requests(&e, &requests),
+                hash_requests(&e, &requests.clone())
+            );
+            assert_ne!(
+                hash_requests(&e, &requests),
+                hash_requests(&e, &other_requests)
+            );
+        });
+    }
+
+    #[test]
+    fn test_signed_submit_payload_bound_to_domain() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let other_pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: Address::generate(&e),
+                amount: 10_0000000,
+            },
+        ];
+        let (domain, payload) = e.as_contract(&pool, || {
+            let payload = signed_submit_payload(&e, &samwise, &requests, 0, 600);
+            assert_eq!(payload.len(), 66);
+            assert_eq!(payload.slice(0..2), Bytes::from_array(&e, &[0x19, 0x01]));
+            assert_eq!(payload.slice(2..34), domain_separator(&e).into());
+            assert_ne!(
+                payload,
+                signed_submit_payload(&e, &samwise, &requests, 1, 600)
+            );
+            (domain_separator(&e), payload)
+        });
+
+        // a signature for one pool or network is not valid for another
+        e.as_contract(&other_pool, || {
+            assert_ne!(domain_separator(&e), domain);
+            assert_ne!(
+                signed_submit_payload(&e, &samwise, &requests, 0, 600),
+                payload
+            );
+        });
+        e.ledger().set_network_id([1; 32]);
+        e.as_contract(&pool, || {
+            assert_ne!(domain_separator(&e), domain);
+            assert_ne!(
+                signed_submit_payload(&e, &samwise, &requests, 0, 600),
+                payload
+            );
+        });
+    }
+
+    fn setup_signed_submit(e: &Env) -> (Address, Address, Address, SigningKey) {
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+
+        let (underlying, underlying_client) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(e, &pool, &underlying, &reserve_config, &reserve_data);
+        underlying_client.mint(&samwise, &10_0000000);
+        underlying_client.approve(&samwise, &pool, &10_0000000, &1000);
+
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let pool_config = PoolConfig {
+            oracle: Address::generate(e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_signing_key(
+                e,
+                &samwise,
+                &Some(BytesN::from_array(
+                    e,
+                    &signing_key.verifying_key().to_bytes(),
+                )),
+            );
+        });
+        (pool, samwise, underlying, signing_key)
+    }
+
+    fn sign(e: &Env, signing_key: &SigningKey, payload: &Bytes) -> BytesN<64> {
+        let payload: std::vec::Vec<u8> = payload.iter().collect();
+        BytesN::from_array(e, &signing_key.sign(&payload).to_bytes())
+    }
+
+    #[test]
+    fn test_submit_signed() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set_timestamp(600);
+
+        let (pool, samwise, underlying, signing_key) = setup_signed_submit(&e);
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: underlying.clone(),
+                amount: 10_0000000,
+            },
+        ];
+        e.as_contract(&pool, || {
+            let payload = signed_submit_payload(&e, &samwise, &requests, 0, 600);
+            let signature = sign(&e, &signing_key, &payload);
+
+            let positions = execute_submit_signed(&e, &samwise, requests, &signature, 0, 600);
+            assert_eq!(positions.supply.len(), 1);
+            assert_eq!(storage::get_user_nonce(&e, &samwise), 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Crypto, InvalidInput)")]
+    fn test_submit_signed_wrong_requests() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set_timestamp(600);
+
+        let (pool, samwise, underlying, signing_key) = setup_signed_submit(&e);
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: underlying.clone(),
+                amount: 5_0000000,
+            },
+        ];
+        let tampered_requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: underlying.clone(),
+                amount: 10_0000000,
+            },
+        ];
+        e.as_contract(&pool, || {
+            let payload = signed_submit_payload(&e, &samwise, &requests, 0, 600);
+            let signature = sign(&e, &signing_key, &payload);
+
+            execute_submit_signed(&e, &samwise, tampered_requests, &signature, 0, 600);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_submit_signed_expired() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set_timestamp(600);
+
+        let (pool, samwise, underlying, signing_key) = setup_signed_submit(&e);
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: underlying.clone(),
+                amount: 10_0000000,
+            },
+        ];
+        e.as_contract(&pool, || {
+            let payload = signed_submit_payload(&e, &samwise, &requests, 0, 599);
+            let signature = sign(&e, &signing_key, &payload);
+
+            execute_submit_signed(&e, &samwise, requests, &signature, 0, 599);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_submit_signed_replayed() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set_timestamp(600);
+
+        let (pool, samwise, underlying, signing_key) = setup_signed_submit(&e);
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: underlying.clone(),
+                amount: 5_0000000,
+            },
+        ];
+        e.as_contract(&pool, || {
+            let payload = signed_submit_payload(&e, &samwise, &requests, 0, 600);
+            let signature = sign(&e, &signing_key, &payload);
+
+            execute_submit_signed(&e, &samwise, requests.clone(), &signature, 0, 600);
+            execute_submit_signed(&e, &samwise, requests, &signature, 0, 600);
+        });
+    }
+}
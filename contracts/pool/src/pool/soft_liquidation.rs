@@ -0,0 +1,460 @@
+use cast::i128;
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    panic_with_error, vec, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+
+use crate::{
+    constants::{SCALAR_7, SOFT_LIQ_MIN_HF},
+    dependencies::DexAdapterClient,
+    errors::PoolError,
+    storage::{self, SoftLiquidationConfig},
+};
+
+use super::{
+    accrue_coverage, health_factor::PositionData, pool::Pool, recall_shortfall, record_repay, User,
+};
+
+/// Set the soft liquidation configuration
+///
+/// Panics if the max percent is not within (0, 1] or the fee is not below 10%
+pub fn execute_set_soft_liquidation_config(e: &Env, config: &SoftLiquidationConfig) {
+    if config.max_pct == 0 || config.max_pct > SCALAR_7 as u32 || config.fee >= 0_1000000 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_soft_liquidation_config(e, config);
+}
+
+/// Soft liquidate a user with a health factor just under 1 by selling some of their collateral
+/// through the DEX adapter and using the proceeds to repay their liabilities. A fee is taken from
+/// the sold collateral and sent to the keeper.
+///
+/// Returns (debt tokens repaid, collateral tokens paid to the keeper)
+///
+/// ### Arguments
+/// * `keeper` - The address executing the soft liquidation
+/// * `user` - The user being soft liquidated
+/// * `collateral_asset` - The collateral asset to sell
+/// * `debt_asset` - The liability asset to repay
+/// * `amount` - The amount of the collateral asset to sell, including the fee
+/// * `min_debt_out` - The minimum amount of the debt asset to receive from the swap
+///
+/// ### Panics
+/// If soft liquidations are not enabled, the user is not eligible for a soft liquidation, the
+/// collateral is sunset, the amount sold is too large, or the user's health factor is not above 1
+/// afterwards
+pub fn execute_soft_liquidation(
+    e: &Env,
+    keeper: &Address,
+    user: &Address,
+    collateral_asset: &Address,
+    debt_asset: &Address,
+    amount: i128,
+    min_debt_out: i128,
+) -> (i128, i128) {
+    let config = match storage::get_soft_liquidation_config(e) {
+        Some(config) => config,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if user == &e.current_contract_address()
+        || user == &storage::get_backstop(e)
+        || collateral_asset == debt_asset
+        || amount <= 0
+    {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let mut pool = Pool::load(e);
+    let mut user_state = User::load(e, user);
+
+    // only positions just under a health factor of 1 can be soft liquidated
+    let position_data = PositionData::calculate_from_positions(e, &mut pool, &user_state.positions);
+    if !position_data.is_hf_under(e, SCALAR_7) || position_data.is_hf_under(e, SOFT_LIQ_MIN_HF) {
+        panic_with_error!(e, PoolError::InvalidSoftLiquidation);
+    }
+
+    // limit the amount of collateral sold in a single call. Sunset collateral cannot be priced,
+    // so the amount sold cannot be limited and it must be liquidated through an auction instead
+    if storage::get_sunset(e, collateral_asset) {
+        panic_with_error!(e, PoolError::InvalidSoftLiquidation);
+    }
+    let collateral_price = pool.load_price(e, collateral_asset);
+    let mut collateral_reserve = pool.load_reserve(e, collateral_asset, true);
+    // charge any premium against the collateral before the amount sold is burnt from it
+    accrue_coverage(e, &mut user_state, &mut collateral_reserve);
+    let amount_base = collateral_price.fixed_mul_ceil(e, &amount, &collateral_reserve.scalar);
+    let max_base = position_data
+        .liability_raw
+        .fixed_mul_floor(e, &i128(config.max_pct), &SCALAR_7);
+    if amount_base > max_base {
+        panic_with_error!(e, PoolError::InvalidSoftLiquidation);
+    }
+
+    let b_tokens_burnt = collateral_reserve.to_b_token_up(e, amount);
+    user_state.remove_collateral(e, &mut collateral_reserve, b_tokens_burnt);
+    pool.cache_reserve(collateral_reserve);
+
+    let fee = amount.fixed_mul_ceil(e, &i128(config.fee), &SCALAR_7);
+    recall_shortfall(e, collateral_asset, amount);
+    let debt_out = swap(
+        e,
+        &config.dex_adapter,
+        collateral_asset,
+        debt_asset,
+        amount - fee,
+        min_debt_out,
+    );
+
+    let mut debt_reserve = pool.load_reserve(e, debt_asset, true);
+    let d_token_balance = user_state.get_liabilities(debt_reserve.config.index);
+    let mut d_tokens_burnt = debt_reserve.to_d_token_down(e, debt_out);
+    let mut excess = 0;
+    if d_tokens_burnt > d_token_balance {
+        d_tokens_burnt = d_token_balance;
+        excess = debt_out - debt_reserve.to_asset_from_d_token(e, d_token_balance);
+    }
+    record_repay(
+        e,
+        &user_state.address,
+        debt_reserve.config.index,
+        debt_reserve.data.d_rate,
+        d_token_balance,
+        d_tokens_burnt,
+    );
+    user_state.remove_liabilities(e, &mut debt_reserve, d_tokens_burnt);
+    pool.cache_reserve(debt_reserve);
+
+    // the soft liquidation must restore the user's health
+    let new_position_data =
+        PositionData::calculate_from_positions(e, &mut pool, &user_state.positions);
+    if new_position_data.is_hf_under(e, SCALAR_7) {
+        panic_with_error!(e, PoolError::InvalidSoftLiquidation);
+    }
+
+    if fee > 0 {
+        TokenClient::new(e, collateral_asset).transfer(&e.current_contract_address(), keeper, &fee);
+    }
+    if excess > 0 {
+        TokenClient::new(e, debt_asset).transfer(&e.current_contract_address(), user, &excess);
+    }
+
+    pool.store_cached_reserves(e);
+    user_state.store(e);
+
+    (d_tokens_burnt, fee)
+}
+
+/// Swap `amount_in` of `token_in` held by the pool for `token_out` through the DEX adapter
+///
+/// Returns the amount of `token_out` received by the pool
+pub(super) fn swap(
+    e: &Env,
+    dex_adapter: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: i128,
+    min_amount_out: i128,
+) -> i128 {
+    let args: Vec<Val> = vec![
+        e,
+        (&e.current_contract_address()).into_val(e),
+        dex_adapter.into_val(e),
+        (&amount_in).into_val(e),
+    ];
+    e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: token_in.clone(),
+                fn_name: Symbol::new(e, "transfer"),
+                args,
+            },
+            sub_invocations: vec![e],
+        }),
+    ]);
+    DexAdapterClient::new(e, dex_adapter).swap_exact_in(
+        &e.current_contract_address(),
+        token_in,
+        token_out,
+        &amount_in,
+        &min_amount_out,
+        &e.current_contract_address(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pool::Positions,
+        storage::PoolConfig,
+        testutils::{self, create_pool},
+    };
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use sep_41_token::testutils::MockTokenClient;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+    };
+
+    /// Setup a pool with a user who holds 100 collateral tokens of reserve 0 (c_factor 0.9) and
+    /// `liabilities` tokens of reserve 1 (l_factor 1), both priced at 1
+    fn setup_pool<'a>(
+        e: &Env,
+        user: &Address,
+        liabilities: i128,
+    ) -> (Address, Address, Address, MockTokenClient<'a>) {
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let pool_address = create_pool(e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(e);
+        let dex_adapter = testutils::create_mock_dex_adapter(e);
+
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_config_0.c_factor = 0_9000000;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.b_supply = 200_0000000;
+        reserve_data_1.d_supply = 100_0000000;
+        reserve_config_1.l_factor = 1_0000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        underlying_1_client.mint(&dex_adapter, &100_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 1_0000000]);
+
+        let positions = Positions {
+            collateral: map![e, (0, 100_0000000)],
+            liabilities: map![e, (1, liabilities)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(e, user, &positions);
+            storage::set_pool_config(e, &pool_config);
+            storage::set_backstop(e, &Address::generate(e));
+            storage::set_soft_liquidation_config(
+                e,
+                &SoftLiquidationConfig {
+                    dex_adapter: dex_adapter.clone(),
+                    max_pct: 0_2500000,
+                    fee: 0_0100000,
+                },
+            );
+        });
+        (
+            pool_address,
+            underlying_0,
+            underlying_1,
+            underlying_0_client,
+        )
+    }
+
+    #[test]
+    fn test_execute_set_soft_liquidation_config() {
+        let e = Env::default();
+        let pool = create_pool(&e);
+
+        let config = SoftLiquidationConfig {
+            dex_adapter: Address::generate(&e),
+            max_pct: 0_5000000,
+            fee: 0_0050000,
+        };
+        e.as_contract(&pool, || {
+            execute_set_soft_liquidation_config(&e, &config);
+            let new_config = storage::get_soft_liquidation_config(&e).unwrap();
+            assert_eq!(new_config.dex_adapter, config.dex_adapter);
+            assert_eq!(new_config.max_pct, config.max_pct);
+            assert_eq!(new_config.fee, config.fee);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_soft_liquidation_config_fee_too_large() {
+        let e = Env::default();
+        let pool = create_pool(&e);
+
+        let config = SoftLiquidationConfig {
+            dex_adapter: Address::generate(&e),
+            max_pct: 0_5000000,
+            fee: 0_1000000,
+        };
+        e.as_contract(&pool, || {
+            execute_set_soft_liquidation_config(&e, &config);
+        });
+    }
+
+    #[test]
+    fn test_execute_soft_liquidation() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        // hf = 90 / 91.5 = 0.9836
+        let (pool, underlying_0, underlying_1, underlying_0_client) =
+            setup_pool(&e, &samwise, 91_5000000);
+
+        e.as_contract(&pool, || {
+            let (repaid, fee) = execute_soft_liquidation(
+                &e,
+                &frodo,
+                &samwise,
+                &underlying_0,
+                &underlying_1,
+                20_0000000,
+                19_8000000,
+            );
+            assert_eq!(repaid, 19_8000000);
+            assert_eq!(fee, 0_2000000);
+
+            // hf = 72 / 71.7 = 1.0042
+            let positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(positions.collateral.get_unchecked(0), 80_0000000);
+            assert_eq!(positions.liabilities.get_unchecked(1), 71_7000000);
+
+            let reserve_data_0 = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(reserve_data_0.b_supply, 80_0000000);
+            let reserve_data_1 = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_data_1.d_supply, 80_2000000);
+        });
+        assert_eq!(underlying_0_client.balance(&frodo), 0_2000000);
+        assert_eq!(underlying_0_client.balance(&pool), 5_0000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1227)")]
+    fn test_execute_soft_liquidation_hf_too_low() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        // hf = 90 / 92 = 0.9783
+        let (pool, underlying_0, underlying_1, _) = setup_pool(&e, &samwise, 92_0000000);
+
+        e.as_contract(&pool, || {
+            execute_soft_liquidation(
+                &e,
+                &frodo,
+                &samwise,
+                &underlying_0,
+                &underlying_1,
+                20_0000000,
+                0,
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1227)")]
+    fn test_execute_soft_liquidation_healthy_user() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        // hf = 90 / 85 = 1.0588
+        let (pool, underlying_0, underlying_1, _) = setup_pool(&e, &samwise, 85_0000000);
+
+        e.as_contract(&pool, || {
+            execute_soft_liquidation(
+                &e,
+                &frodo,
+                &samwise,
+                &underlying_0,
+                &underlying_1,
+                5_0000000,
+                0,
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1227)")]
+    fn test_execute_soft_liquidation_over_max() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let (pool, underlying_0, underlying_1, _) = setup_pool(&e, &samwise, 91_5000000);
+
+        // max is 25% of 91.5 = 22.875
+        e.as_contract(&pool, || {
+            execute_soft_liquidation(
+                &e,
+                &frodo,
+                &samwise,
+                &underlying_0,
+                &underlying_1,
+                23_0000000,
+                0,
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1227)")]
+    fn test_execute_soft_liquidation_does_not_restore_health() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let (pool, underlying_0, underlying_1, _) = setup_pool(&e, &samwise, 91_5000000);
+
+        // hf = 81 / 81.6 = 0.9926
+        e.as_contract(&pool, || {
+            execute_soft_liquidation(
+                &e,
+                &frodo,
+                &samwise,
+                &underlying_0,
+                &underlying_1,
+                10_0000000,
+                0,
+            );
+        });
+    }
+}
@@ -0,0 +1,174 @@
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+use crate::storage::{self, ReserveData};
+
+use super::user::Positions;
+
+/// The state of the pool's reserves committed to by the pool's state root
+#[derive(Clone)]
+#[contracttype]
+pub struct StateSnapshot {
+    pub pool: Address,                  // the address of the pool contract
+    pub ledger: u32,                    // the ledger sequence the snapshot was taken at
+    pub timestamp: u64,                 // the ledger timestamp the snapshot was taken at
+    pub reserves: Vec<Address>,         // the underlying asset of each reserve, ordered by index
+    pub reserve_data: Vec<ReserveData>, // the stored data of each reserve, ordered by index
+}
+
+/// The positions of a user committed to by the user's state root
+#[derive(Clone)]
+#[contracttype]
+pub struct UserStateSnapshot {
+    pub state_root: BytesN<32>, // the state root of the pool the positions were taken with
+    pub user: Address,          // the address of the user
+    pub positions: Positions,   // the positions of the user
+}
+
+/// Load a snapshot of the pool's reserves at the current ledger. Reserve data is committed as
+/// stored, without accruing interest, so the commitment only changes when the pool's state does.
+pub fn load_state_snapshot(e: &Env) -> StateSnapshot {
+    let reserves = storage::get_res_list(e);
+    let mut reserve_data = Vec::new(e);
+    for asset in reserves.iter() {
+        reserve_data.push_back(storage::get_res_data(e, &asset));
+    }
+    StateSnapshot {
+        pool: e.current_contract_address(),
+        ledger: e.ledger().sequence(),
+        timestamp: e.ledger().timestamp(),
+        reserves,
+        reserve_data,
+    }
+}
+
+/// Load a snapshot of a user's positions at the current ledger, bound to the pool's state root
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn load_user_state_snapshot(e: &Env, user: &Address) -> UserStateSnapshot {
+    UserStateSnapshot {
+        state_root: calc_state_root(e, &load_state_snapshot(e)),
+        user: user.clone(),
+        positions: storage::get_user_positions(e, user),
+    }
+}
+
+/// Calculate the state root of a snapshot of the pool's reserves. The root is the sha256 of the
+/// XDR encoding of the snapshot, so any verifier holding the snapshot can reproduce it.
+///
+/// ### Arguments
+/// * `snapshot` - The snapshot of the pool's reserves
+pub fn calc_state_root(e: &Env, snapshot: &StateSnapshot) -> BytesN<32> {
+    e.crypto().sha256(&state_preimage(e, snapshot)).into()
+}
+
+/// Calculate the state root of a snapshot of a user's positions. The root is the sha256 of the
+/// XDR encoding of the snapshot.
+///
+/// ### Arguments
+/// * `snapshot` - The snapshot of the user's positions
+pub fn calc_user_state_root(e: &Env, snapshot: &UserStateSnapshot) -> BytesN<32> {
+    e.crypto().sha256(&user_state_preimage(e, snapshot)).into()
+}
+
+/// Encode a snapshot of the pool's reserves as the preimage of its state root
+///
+/// ### Arguments
+/// * `snapshot` - The snapshot of the pool's reserves
+pub fn state_preimage(e: &Env, snapshot: &StateSnapshot) -> Bytes {
+    snapshot.clone().to_xdr(e)
+}
+
+/// Encode a snapshot of a user's positions as the preimage of its state root
+///
+/// ### Arguments
+/// * `snapshot` - The snapshot of the user's positions
+pub fn user_state_preimage(e: &Env, snapshot: &UserStateSnapshot) -> Bytes {
+    snapshot.clone().to_xdr(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testutils;
+
+    use super::*;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+    };
+
+    #[test]
+    fn test_state_root() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50000,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_0, reserve_data_0) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        e.as_contract(&pool, || {
+            let snapshot = load_state_snapshot(&e);
+            assert_eq!(snapshot.pool, pool);
+            assert_eq!(snapshot.ledger, 50000);
+            assert_eq!(snapshot.timestamp, 12345);
+            assert_eq!(snapshot.reserves.len(), 2);
+            assert_eq!(snapshot.reserves.get_unchecked(1), underlying_1);
+            assert_eq!(
+                snapshot.reserve_data.get_unchecked(0).b_supply,
+                reserve_data_0.b_supply
+            );
+
+            let state_root = calc_state_root(&e, &snapshot);
+            let expected: BytesN<32> = e.crypto().sha256(&snapshot.clone().to_xdr(&e)).into();
+            assert_eq!(state_root, expected);
+
+            // the state root changes with the pool's state
+            let mut new_reserve_data = reserve_data_0.clone();
+            new_reserve_data.b_supply += 1;
+            storage::set_res_data(&e, &underlying_0, &new_reserve_data);
+            assert_ne!(calc_state_root(&e, &load_state_snapshot(&e)), state_root);
+
+            // the user's state root commits to the pool's state root
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e, (1, 10_0000000)],
+                    collateral: map![&e, (0, 20_0000000)],
+                    supply: map![&e],
+                },
+            );
+            let user_snapshot = load_user_state_snapshot(&e, &samwise);
+            assert_eq!(
+                user_snapshot.state_root,
+                calc_state_root(&e, &load_state_snapshot(&e))
+            );
+            assert_eq!(
+                user_snapshot.positions.collateral.get_unchecked(0),
+                20_0000000
+            );
+            let user_state_root = calc_user_state_root(&e, &user_snapshot);
+            let expected: BytesN<32> = e.crypto().sha256(&user_snapshot.clone().to_xdr(&e)).into();
+            assert_eq!(user_state_root, expected);
+        });
+    }
+}
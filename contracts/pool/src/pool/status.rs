@@ -4,6 +4,7 @@ use crate::{
     storage, PoolError,
 };
 use soroban_sdk::{panic_with_error, Env};
+use trustbridge_common::Role;
 
 /// Update the pool status based on the backstop module
 #[allow(clippy::zero_prefixed_literal)]
@@ -69,6 +70,10 @@ pub fn execute_update_pool_status(e: &Env) -> u32 {
 #[allow(clippy::zero_prefixed_literal)]
 #[allow(clippy::inconsistent_digit_grouping)]
 pub fn execute_set_pool_status(e: &Env, pool_status: u32) {
+    // a shut down pool stays frozen
+    if storage::get_shutdown(e).is_some() {
+        panic_with_error!(e, PoolError::StatusNotAllowed);
+    }
     let mut pool_config = storage::get_pool_config(e);
 
     // check the pool has met minimum backstop deposits
@@ -116,6 +121,40 @@ pub fn execute_set_pool_status(e: &Env, pool_status: u32) {
     storage::set_pool_config(e, &pool_config);
 }
 
+/// Restrict the pool on behalf of a pauser, disabling new borrows. An active or backstop on-ice
+/// pool is set to admin on-ice, so it stays on-ice until the admin lifts it. Pools that are
+/// already admin on-ice, frozen, or being set up are left unchanged.
+///
+/// Returns the pool status
+pub fn execute_set_status_restricted(e: &Env) -> u32 {
+    let mut pool_config = storage::get_pool_config(e);
+    if matches!(pool_config.status, 0 | 1 | 3) {
+        pool_config.status = 2;
+        storage::set_pool_config(e, &pool_config);
+    }
+    pool_config.status
+}
+
+/// Fetch the role required to set the pool status to `pool_status`. The pauser role can only
+/// restrict the pool further, by setting an active or on-ice pool to admin on-ice, or by freezing
+/// it. Any other status change requires the admin.
+///
+/// ### Arguments
+/// * `pool_status` - The pool status to be set
+pub fn set_pool_status_role(e: &Env, pool_status: u32) -> Role {
+    let cur_status = storage::get_pool_config(e).status;
+    let restricts = match pool_status {
+        2 => cur_status <= 3,
+        4 => true,
+        _ => false,
+    };
+    if restricts {
+        Role::Pauser
+    } else {
+        Role::Admin
+    }
+}
+
 /// Calculate the threshold for the pool's backstop balance
 ///
 /// Returns the threshold as a percentage^5 in SCALAR_7 points such that SCALAR_7 = 100%
@@ -1150,6 +1189,56 @@ mod tests {
         assert_eq!(result, 1701411_8346046);
     }
 
+    #[test]
+    fn test_set_status_restricted() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+
+        let mut pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 0,
+            bstop_rate: 0,
+            status: 1,
+            max_positions: 4,
+        };
+        e.as_contract(&pool_id, || {
+            for (status, restricted) in [(0, 2), (1, 2), (2, 2), (3, 2), (4, 4), (5, 5), (6, 6)] {
+                pool_config.status = status;
+                storage::set_pool_config(&e, &pool_config);
+
+                assert_eq!(execute_set_status_restricted(&e), restricted);
+                assert_eq!(storage::get_pool_config(&e).status, restricted);
+            }
+        });
+    }
+
+    #[test]
+    fn test_set_pool_status_role() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+
+        let mut pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 0,
+            bstop_rate: 0,
+            status: 0,
+            max_positions: 4,
+        };
+        e.as_contract(&pool_id, || {
+            storage::set_pool_config(&e, &pool_config);
+            assert_eq!(set_pool_status_role(&e, 0), Role::Admin);
+            assert_eq!(set_pool_status_role(&e, 2), Role::Pauser);
+            assert_eq!(set_pool_status_role(&e, 3), Role::Admin);
+            assert_eq!(set_pool_status_role(&e, 4), Role::Pauser);
+
+            // lifting a frozen pool to on-ice requires the admin
+            pool_config.status = 4;
+            storage::set_pool_config(&e, &pool_config);
+            assert_eq!(set_pool_status_role(&e, 2), Role::Admin);
+            assert_eq!(set_pool_status_role(&e, 4), Role::Pauser);
+        });
+    }
+
     #[test]
     fn test_calc_pool_backstop_threshold_10_percent() {
         let e = Env::default();
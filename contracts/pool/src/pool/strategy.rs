@@ -0,0 +1,308 @@
+use cast::i128;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    panic_with_error, vec, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+
+use crate::{
+    constants::SCALAR_7,
+    dependencies::StrategyClient,
+    errors::PoolError,
+    events::PoolEvents,
+    storage::{self, ReserveStrategy},
+};
+
+use super::{pool::Pool, reserve::protocol_fee_rate, unbonded_balance};
+
+/// Set the strategy adapter a reserve's idle liquidity can be deployed to. Any liquidity
+/// deployed to the current adapter must be recalled before the adapter can be changed.
+///
+/// Returns the reserve strategy
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `adapter` - The strategy adapter
+/// * `max_deploy` - The maximum fraction of idle liquidity that can be deployed (7 decimals)
+///
+/// ### Panics
+/// If the asset is not a reserve, `max_deploy` is over 100%, or liquidity is still deployed to
+/// a different adapter
+pub fn execute_set_reserve_strategy(
+    e: &Env,
+    asset: &Address,
+    adapter: &Address,
+    max_deploy: u32,
+) -> ReserveStrategy {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    if i128(max_deploy) > SCALAR_7 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+
+    let deployed = match storage::get_reserve_strategy(e, asset) {
+        Some(strategy) if strategy.adapter == *adapter => strategy.deployed,
+        Some(strategy) if strategy.deployed > 0 => panic_with_error!(e, PoolError::BadRequest),
+        _ => 0,
+    };
+    let strategy = ReserveStrategy {
+        adapter: adapter.clone(),
+        max_deploy,
+        deployed,
+    };
+    storage::set_reserve_strategy(e, asset, &strategy);
+    strategy
+}
+
+/// Deploy idle liquidity of a reserve to its strategy adapter. The total deployed liquidity
+/// cannot exceed `max_deploy` of the reserve's unborrowed supply.
+///
+/// Returns the reserve strategy
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `amount` - The amount of underlying tokens to deploy
+///
+/// ### Panics
+/// If the pool is shut down, the reserve has no strategy, or the amount is not positive or
+/// exceeds the deployable liquidity
+pub fn execute_deploy_to_strategy(e: &Env, asset: &Address, amount: i128) -> ReserveStrategy {
+    if amount <= 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut pool = Pool::load(e);
+    if pool.is_shutdown {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut strategy = load_strategy(e, asset);
+
+    let reserve = pool.load_reserve(e, asset, false);
+    let idle = (reserve.total_supply(e) - reserve.total_liabilities(e)).max(0);
+    let max_deployed = idle.fixed_mul_floor(e, &i128(strategy.max_deploy), &SCALAR_7);
+    if strategy.deployed + amount > max_deployed {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let args: Vec<Val> = vec![
+        e,
+        (&e.current_contract_address()).into_val(e),
+        (&strategy.adapter).into_val(e),
+        (&amount).into_val(e),
+    ];
+    e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: asset.clone(),
+                fn_name: Symbol::new(e, "transfer"),
+                args,
+            },
+            sub_invocations: vec![e],
+        }),
+    ]);
+    StrategyClient::new(e, &strategy.adapter).deposit(&e.current_contract_address(), &amount);
+
+    strategy.deployed += amount;
+    storage::set_reserve_strategy(e, asset, &strategy);
+    PoolEvents::deploy_to_strategy(e, asset.clone(), strategy.adapter.clone(), amount);
+    strategy
+}
+
+/// Recall liquidity from a reserve's strategy adapter. Any amount recalled beyond the deployed
+/// liquidity is yield, and is accrued to the reserve's suppliers and backstop like interest.
+///
+/// Returns the amount of underlying tokens recalled
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `amount` - The amount of underlying tokens to recall
+///
+/// ### Panics
+/// If the reserve has no strategy or the amount is not positive
+pub fn execute_recall_from_strategy(e: &Env, asset: &Address, amount: i128) -> i128 {
+    if amount <= 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut strategy = load_strategy(e, asset);
+
+    let recalled =
+        StrategyClient::new(e, &strategy.adapter).withdraw(&e.current_contract_address(), &amount);
+    let gain = (recalled - strategy.deployed).max(0);
+    strategy.deployed -= recalled - gain;
+    storage::set_reserve_strategy(e, asset, &strategy);
+
+    if gain > 0 {
+        let mut pool = Pool::load(e);
+        let mut reserve = pool.load_reserve(e, asset, true);
+        reserve.accrue(e, pool.config.bstop_rate, protocol_fee_rate(e), gain);
+        pool.cache_reserve(reserve);
+        pool.store_cached_reserves(e);
+    }
+    PoolEvents::recall_from_strategy(e, asset.clone(), strategy.adapter, recalled, gain);
+    recalled
+}
+
+/// Recall enough deployed liquidity from a reserve's strategy adapter for the pool to hold at
+/// least `amount` of the asset. Only deployed liquidity is recalled, so no yield is accrued.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset
+/// * `amount` - The amount of the asset the pool needs to hold
+pub fn recall_shortfall(e: &Env, asset: &Address, amount: i128) {
+    let mut strategy = match storage::get_reserve_strategy(e, asset) {
+        Some(strategy) if strategy.deployed > 0 => strategy,
+        _ => return,
+    };
+    let balance = unbonded_balance(e, asset);
+    if balance >= amount {
+        return;
+    }
+
+    let to_recall = (amount - balance).min(strategy.deployed);
+    let recalled = StrategyClient::new(e, &strategy.adapter)
+        .withdraw(&e.current_contract_address(), &to_recall);
+    strategy.deployed -= recalled.min(strategy.deployed);
+    storage::set_reserve_strategy(e, asset, &strategy);
+    PoolEvents::recall_from_strategy(e, asset.clone(), strategy.adapter, recalled, 0);
+}
+
+/// Fetch the amount of an asset deployed to its reserve's strategy adapter
+///
+/// ### Arguments
+/// * `asset` - The underlying asset
+pub fn deployed_to_strategy(e: &Env, asset: &Address) -> i128 {
+    match storage::get_reserve_strategy(e, asset) {
+        Some(strategy) => strategy.deployed,
+        None => 0,
+    }
+}
+
+/// Load the strategy for a reserve, or panic if none exists
+fn load_strategy(e: &Env, asset: &Address) -> ReserveStrategy {
+    match storage::get_reserve_strategy(e, asset) {
+        Some(strategy) => strategy,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils;
+    use sep_41_token::testutils::MockTokenClient;
+    use soroban_sdk::testutils::Address as _;
+
+    /// Setup a pool with a reserve with 100 tokens supplied and 75 borrowed, where the pool holds
+    /// the 25 unborrowed tokens and a strategy adapter can deploy up to half of them
+    fn setup_pool<'a>(e: &Env) -> (Address, Address, MockTokenClient<'a>, Address) {
+        let (pool, underlying, underlying_client) = testutils::create_pool_with_reserve(e);
+        let adapter = testutils::create_mock_strategy(e, &underlying);
+        e.as_contract(&pool, || {
+            execute_set_reserve_strategy(e, &underlying, &adapter, 0_5000000);
+        });
+        (pool, underlying, underlying_client, adapter)
+    }
+
+    #[test]
+    fn test_set_reserve_strategy() {
+        let e = Env::default();
+        let (pool, underlying, _, adapter) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_deploy_to_strategy(&e, &underlying, 5_0000000);
+
+            // updating the current adapter keeps the deployed liquidity
+            let strategy = execute_set_reserve_strategy(&e, &underlying, &adapter, 0_2500000);
+            assert_eq!(strategy.max_deploy, 0_2500000);
+            assert_eq!(strategy.deployed, 5_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_reserve_strategy_change_adapter_with_deployed() {
+        let e = Env::default();
+        let (pool, underlying, _, _) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_deploy_to_strategy(&e, &underlying, 5_0000000);
+            execute_set_reserve_strategy(&e, &underlying, &Address::generate(&e), 0_5000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_reserve_strategy_max_deploy_too_large() {
+        let e = Env::default();
+        let (pool, underlying, _, adapter) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_reserve_strategy(&e, &underlying, &adapter, 1_0000001);
+        });
+    }
+
+    #[test]
+    fn test_deploy_and_recall() {
+        let e = Env::default();
+        let (pool, underlying, underlying_client, adapter) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            let strategy = execute_deploy_to_strategy(&e, &underlying, 10_0000000);
+            assert_eq!(strategy.deployed, 10_0000000);
+        });
+        assert_eq!(underlying_client.balance(&pool), 15_0000000);
+        assert_eq!(underlying_client.balance(&adapter), 10_0000000);
+
+        // the strategy earns 1 token of yield
+        underlying_client.mint(&adapter, &1_0000000);
+        e.as_contract(&pool, || {
+            let pre_b_rate = storage::get_res_data(&e, &underlying).b_rate;
+            let recalled = execute_recall_from_strategy(&e, &underlying, 4_0000000);
+            assert_eq!(recalled, 4_0000000);
+            assert_eq!(deployed_to_strategy(&e, &underlying), 6_0000000);
+            assert_eq!(storage::get_res_data(&e, &underlying).b_rate, pre_b_rate);
+
+            let recalled = execute_recall_from_strategy(&e, &underlying, 7_0000000);
+            assert_eq!(recalled, 7_0000000);
+            assert_eq!(deployed_to_strategy(&e, &underlying), 0);
+
+            // the yield is accrued to suppliers and the backstop
+            let reserve_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(reserve_data.backstop_credit, 0_2000000);
+            assert_eq!(reserve_data.b_rate, 1_008_000_000_000);
+        });
+        assert_eq!(underlying_client.balance(&pool), 26_0000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_deploy_over_max() {
+        let e = Env::default();
+        let (pool, underlying, _, _) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_deploy_to_strategy(&e, &underlying, 10_0000000);
+            execute_deploy_to_strategy(&e, &underlying, 2_5000001);
+        });
+    }
+
+    #[test]
+    fn test_recall_shortfall() {
+        let e = Env::default();
+        let (pool, underlying, underlying_client, adapter) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_deploy_to_strategy(&e, &underlying, 10_0000000);
+
+            // the pool holds enough to cover the amount
+            recall_shortfall(&e, &underlying, 15_0000000);
+            assert_eq!(deployed_to_strategy(&e, &underlying), 10_0000000);
+
+            recall_shortfall(&e, &underlying, 20_0000000);
+            assert_eq!(deployed_to_strategy(&e, &underlying), 5_0000000);
+        });
+        assert_eq!(underlying_client.balance(&pool), 20_0000000);
+        assert_eq!(underlying_client.balance(&adapter), 5_0000000);
+    }
+}
@@ -5,10 +5,15 @@ use soroban_sdk::{panic_with_error, Address, Env, Map, Vec};
 use crate::{events::PoolEvents, storage, AuctionType, PoolError};
 
 use super::{
+    action_limits::require_within_action_limits,
     actions::{build_actions_from_request, Actions, Request},
+    balance_delta::{pull_balance_delta_transfers, settle_balance_delta_transfers},
     health_factor::PositionData,
+    increment_nonce,
     pool::Pool,
-    FlashLoan, Positions, RequestType, User,
+    recall_shortfall, record_borrow, record_borrow_ledger, require_borrow_not_frozen,
+    require_not_supply_only, require_withdraw_cooldown_passed, FlashLoan, Positions, RequestType,
+    User,
 };
 
 /// Execute a set of updates for a user against the pool.
@@ -36,12 +41,16 @@ pub fn execute_submit(
     {
         panic_with_error!(e, &PoolError::BadRequest);
     }
+    require_within_action_limits(e, &requests);
     let mut pool = Pool::load(e);
     let mut from_state = User::load(e, from);
 
     let prev_positions_count = from_state.positions.effective_count();
 
-    let actions = build_actions_from_request(e, &mut pool, &mut from_state, requests);
+    let (requests, received) = pull_balance_delta_transfers(e, requests, spender, use_allowance);
+    let mut actions =
+        build_actions_from_request(e, &mut pool, &mut from_state, requests, use_allowance);
+    settle_balance_delta_transfers(&mut actions, &received);
 
     validate_submit(
         e,
@@ -50,6 +59,7 @@ pub fn execute_submit(
         prev_positions_count,
         actions.check_health,
         &actions.check_max_util,
+        actions.check_withdraw_cooldown,
     );
 
     if use_allowance {
@@ -62,6 +72,9 @@ pub fn execute_submit(
     pool.store_cached_reserves(e);
     from_state.store(e);
 
+    let nonce = increment_nonce(e, from);
+    PoolEvents::submit(e, from.clone(), nonce);
+
     from_state.positions
 }
 
@@ -76,6 +89,7 @@ pub fn execute_submit_with_flash_loan(
     if from == &e.current_contract_address() {
         panic_with_error!(e, &PoolError::BadRequest);
     }
+    require_within_action_limits(e, &requests);
     let mut pool = Pool::load(e);
     let mut from_state = User::load(e, from);
 
@@ -85,8 +99,19 @@ pub fn execute_submit_with_flash_loan(
     // requests.
     {
         pool.require_action_allowed(e, RequestType::Borrow as u32);
+        require_borrow_not_frozen(e, from);
+        require_not_supply_only(e, from);
+        record_borrow_ledger(e, from);
         let mut reserve = pool.load_reserve(e, &flash_loan.asset, true);
         let d_tokens_minted = reserve.to_d_token_up(e, flash_loan.amount);
+        record_borrow(
+            e,
+            from,
+            reserve.config.index,
+            reserve.data.d_rate,
+            from_state.get_liabilities(reserve.config.index),
+            flash_loan.amount,
+        );
         from_state.add_liabilities(e, &mut reserve, d_tokens_minted);
         reserve.require_action_allowed(e, RequestType::Borrow as u32);
         reserve.require_utilization_below_100(e);
@@ -103,7 +128,10 @@ pub fn execute_submit_with_flash_loan(
         );
     }
 
-    let mut actions = build_actions_from_request(e, &mut pool, &mut from_state, requests);
+    // tokens for balance delta reserves are received before the flash loan is sent
+    let (requests, received) = pull_balance_delta_transfers(e, requests, from, true);
+    let mut actions = build_actions_from_request(e, &mut pool, &mut from_state, requests, true);
+    settle_balance_delta_transfers(&mut actions, &received);
 
     // require flash loaned asset is added to check_max_util
     if !actions.check_max_util.contains(&flash_loan.asset) {
@@ -118,10 +146,12 @@ pub fn execute_submit_with_flash_loan(
         prev_positions_count,
         true,
         &actions.check_max_util,
+        actions.check_withdraw_cooldown,
     );
 
     // we deal with the flashloan transfer before the others to allow the flash
     // loan to yield the repaid or supplied amount in the transfers.
+    recall_shortfall(e, &flash_loan.asset, flash_loan.amount);
     TokenClient::new(e, &flash_loan.asset).transfer(
         &e.current_contract_address(),
         &flash_loan.contract,
@@ -147,6 +177,9 @@ pub fn execute_submit_with_flash_loan(
     pool.store_cached_reserves(e);
     from_state.store(e);
 
+    let nonce = increment_nonce(e, from);
+    PoolEvents::submit(e, from.clone(), nonce);
+
     from_state.positions
 }
 
@@ -157,6 +190,9 @@ pub fn execute_submit_with_flash_loan(
 /// * from_state - The user state for "from"
 /// * prev_positions_count - The initial number of positions for "from"
 /// * check_health - A bool indicating if the health factor should be checked
+/// * check_max_util - The reserves to check the max utilization for
+/// * check_withdraw_cooldown - A bool indicating if collateral was withdrawn. Checked after all
+///                             requests are applied, so a borrow anywhere in the batch counts.
 fn validate_submit(
     e: &Env,
     pool: &mut Pool,
@@ -164,6 +200,7 @@ fn validate_submit(
     prev_positions_count: u32,
     check_health: bool,
     check_max_util: &Vec<Address>,
+    check_withdraw_cooldown: bool,
 ) {
     // Verify max positions haven't been exceeded
     pool.require_under_max(e, &from_state.positions, prev_positions_count);
@@ -184,6 +221,11 @@ fn validate_submit(
         reserve.require_utilization_below_max(e);
     }
 
+    // borrows in this submit were recorded at the current ledger
+    if check_withdraw_cooldown {
+        require_withdraw_cooldown_passed(e, from_state);
+    }
+
     // panics if the new positions set does not meet the health factor requirement
     // min is 1.0000100 to prevent rounding errors
     if check_health && from_state.has_liabilities() {
@@ -191,6 +233,13 @@ fn validate_submit(
         if position_data.is_hf_under(e, 1_0000100) {
             panic_with_error!(e, PoolError::InvalidHf);
         } else if position_data.collateral_base < pool.config.min_collateral {
+            PoolEvents::min_collateral_not_met(
+                e,
+                from_state.address.clone(),
+                position_data.collateral_base,
+                pool.config.min_collateral,
+                pool.config.min_collateral - position_data.collateral_base,
+            );
             panic_with_error!(e, PoolError::MinCollateralNotMet);
         }
     }
@@ -220,6 +269,7 @@ fn handle_transfer_with_allowance(e: &Env, actions: &Actions, spender: &Address,
         let token = TokenClient::new(e, &address);
         if amount < 0 {
             // transfer tokens from sender to pool
+            require_allowance(e, &token, spender, amount.abs());
             token.transfer_from(
                 &e.current_contract_address(),
                 spender,
@@ -228,11 +278,37 @@ fn handle_transfer_with_allowance(e: &Env, actions: &Actions, spender: &Address,
             );
         } else if amount > 0 {
             // transfer tokens from pool to "to"
+            recall_shortfall(e, &address, amount);
             token.transfer(&e.current_contract_address(), to, &amount);
         }
     }
 }
 
+/// Require that `spender` has approved the pool to transfer at least `amount_needed` tokens.
+/// SEP-41 tokens report an expired allowance as zero, so this also catches allowances that
+/// expired before the submit was included in a ledger.
+///
+/// ### Panics
+/// If the allowance is less than `amount_needed`
+pub(crate) fn require_allowance(
+    e: &Env,
+    token: &TokenClient,
+    spender: &Address,
+    amount_needed: i128,
+) {
+    let allowance = token.allowance(spender, &e.current_contract_address());
+    if allowance < amount_needed {
+        PoolEvents::allowance_insufficient(
+            e,
+            token.address.clone(),
+            spender.clone(),
+            allowance,
+            amount_needed,
+        );
+        panic_with_error!(e, PoolError::AllowanceExpired);
+    }
+}
+
 fn handle_transfers(e: &Env, actions: &Actions, spender: &Address, to: &Address) {
     // transfer tokens from sender to pool
     for (address, amount) in actions.spender_transfer.iter() {
@@ -241,6 +317,7 @@ fn handle_transfers(e: &Env, actions: &Actions, spender: &Address, to: &Address)
 
     // transfer tokens from pool to "to"
     for (address, amount) in actions.pool_transfer.iter() {
+        recall_shortfall(e, &address, amount);
         TokenClient::new(e, &address).transfer(&e.current_contract_address(), to, &amount);
     }
 }
@@ -248,7 +325,7 @@ fn handle_transfers(e: &Env, actions: &Actions, spender: &Address, to: &Address)
 #[cfg(test)]
 mod tests {
     use crate::{
-        storage::{self, PoolConfig},
+        storage::{self, ActionLimits, PoolConfig},
         testutils, AuctionData, RequestType,
     };
 
@@ -370,6 +447,10 @@ mod tests {
 
             assert_eq!(underlying_0_client.balance(&frodo), 1_0000000);
             assert_eq!(underlying_1_client.balance(&merry), 1_5000000);
+
+            // the nonce is incremented for the position owner
+            assert_eq!(storage::get_user_nonce(&e, &samwise), 1);
+            assert_eq!(storage::get_user_nonce(&e, &frodo), 0);
         });
     }
 
@@ -650,7 +731,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #9)")]
+    #[should_panic(expected = "Error(Contract, #1228)")]
     fn test_submit_use_allowance_no_allowance() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
@@ -726,6 +807,86 @@ mod tests {
         });
     }
     #[test]
+    #[should_panic(expected = "Error(Contract, #1228)")]
+    fn test_submit_use_allowance_expired() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&frodo, &16_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+        };
+
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            underlying_0_client.approve(&frodo, &pool, &15_0000000, &e.ledger().sequence());
+
+            // allowance expires before the submit is included
+            e.ledger().set_sequence_number(e.ledger().sequence() + 1);
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0,
+                    amount: 15_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1,
+                    amount: 1_5000000,
+                },
+            ];
+
+            execute_submit(&e, &samwise, &frodo, &merry, requests, true);
+        });
+    }
+    #[test]
     fn test_submit_no_liabilities_does_not_load_oracle() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
@@ -2672,4 +2833,121 @@ mod tests {
             execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
         });
     }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1234)")]
+    fn test_submit_over_action_limits() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_action_limits(
+                &e,
+                &ActionLimits {
+                    max_requests: 2,
+                    max_auction_requests: 1,
+                },
+            );
+
+            let asset = Address::generate(&e);
+            let mut requests = vec![&e];
+            for _ in 0..3 {
+                requests.push_back(Request {
+                    request_type: RequestType::Supply as u32,
+                    address: asset.clone(),
+                    amount: 1_0000000,
+                });
+            }
+            execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1243)")]
+    fn test_submit_withdraw_before_borrow_checks_cooldown() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&frodo, &16_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_withdraw_cooldown(&e, &10);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0.clone(),
+                    amount: 15_0000000,
+                },
+            ];
+            execute_submit(&e, &samwise, &frodo, &frodo, requests, false);
+
+            // the borrow is applied after the withdrawal but still blocks it
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::WithdrawCollateral as u32,
+                    address: underlying_0,
+                    amount: 1_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1,
+                    amount: 1_0000000,
+                },
+            ];
+            execute_submit(&e, &samwise, &frodo, &frodo, requests, false);
+        });
+    }
 }
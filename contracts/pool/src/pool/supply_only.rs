@@ -0,0 +1,116 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{constants::SUPPLY_ONLY_TIMELOCK, errors::PoolError, storage};
+
+use super::account_lock::{is_account_locked, update_account_lock};
+
+/// Opt an account into supply-only mode, or request that it leaves supply-only mode after
+/// `SUPPLY_ONLY_TIMELOCK`. Opting in cancels any pending exit, and requesting an exit while one
+/// is pending does not restart the timelock.
+///
+/// Returns the time the account leaves supply-only mode, or `u64::MAX` if no exit is requested
+///
+/// ### Arguments
+/// * `user` - The user
+/// * `enabled` - True to opt into supply-only mode, false to request to leave it
+///
+/// ### Panics
+/// * If `enabled` is true and the user has liabilities
+/// * If `enabled` is false and the user is not in supply-only mode
+pub fn execute_set_supply_only(e: &Env, user: &Address, enabled: bool) -> u64 {
+    if enabled && !storage::get_user_positions(e, user).liabilities.is_empty() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let exit_time = update_account_lock(
+        e,
+        storage::get_supply_only(e, user),
+        enabled,
+        SUPPLY_ONLY_TIMELOCK,
+    );
+    storage::set_supply_only(e, user, &Some(exit_time));
+    exit_time
+}
+
+/// Require that the account is not in supply-only mode
+///
+/// ### Panics
+/// If the account has not left supply-only mode
+pub(crate) fn require_not_supply_only(e: &Env, user: &Address) {
+    if is_account_locked(e, storage::get_supply_only(e, user)) {
+        panic_with_error!(e, PoolError::SupplyOnlyAccount);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pool::Positions, testutils};
+
+    use super::*;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger},
+    };
+
+    #[test]
+    fn test_set_supply_only() {
+        let e = Env::default();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            require_not_supply_only(&e, &samwise);
+
+            assert_eq!(execute_set_supply_only(&e, &samwise, true), u64::MAX);
+            assert_eq!(storage::get_supply_only(&e, &samwise), Some(u64::MAX));
+
+            let exit_time = 1000 + SUPPLY_ONLY_TIMELOCK;
+            assert_eq!(execute_set_supply_only(&e, &samwise, false), exit_time);
+
+            // requesting again does not restart the timelock
+            e.ledger().set_timestamp(2000);
+            assert_eq!(execute_set_supply_only(&e, &samwise, false), exit_time);
+
+            e.ledger().set_timestamp(exit_time);
+            require_not_supply_only(&e, &samwise);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1233)")]
+    fn test_require_not_supply_only_before_timelock() {
+        let e = Env::default();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_supply_only(&e, &samwise, true);
+            execute_set_supply_only(&e, &samwise, false);
+
+            e.ledger().set_timestamp(1000 + SUPPLY_ONLY_TIMELOCK - 1);
+            require_not_supply_only(&e, &samwise);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_supply_only_with_liabilities() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e, (0, 10_0000000)],
+                    collateral: map![&e, (1, 50_0000000)],
+                    supply: map![&e],
+                },
+            );
+            execute_set_supply_only(&e, &samwise, true);
+        });
+    }
+}
@@ -1,9 +1,14 @@
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{contracttype, panic_with_error, Address, Env, Map};
+use soroban_sdk::{contracttype, panic_with_error, xdr::ToXdr, Address, Bytes, Env, Map, Vec};
 
-use crate::{constants::SCALAR_12, emissions, storage, validator::require_nonnegative, PoolError};
+use crate::{
+    constants::{MAX_POSITIONS_BATCH, SCALAR_12},
+    emissions, storage,
+    validator::require_nonnegative,
+    PoolError,
+};
 
-use super::{Pool, Reserve};
+use super::{accrue_coverage, record_borrow, record_repay, Pool, Reserve};
 
 /// A user / contracts position's with the pool, stored in the Reserve's decimals
 #[derive(Clone)]
@@ -33,6 +38,37 @@ impl Positions {
     }
 }
 
+/// Increment the user's operation nonce, and record the operation as the user's last action
+///
+/// Returns the new nonce
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn increment_nonce(e: &Env, user: &Address) -> u64 {
+    let nonce = storage::get_user_nonce(e, user) + 1;
+    storage::set_user_nonce(e, user, &nonce);
+    storage::set_last_action(e, user, &e.ledger().timestamp());
+    nonce
+}
+
+/// Fetch the positions of a batch of users, in the order of `users`
+///
+/// ### Arguments
+/// * `users` - The addresses of the users
+///
+/// ### Panics
+/// If there are more than `MAX_POSITIONS_BATCH` users
+pub fn load_users_positions(e: &Env, users: &Vec<Address>) -> Vec<Positions> {
+    if users.len() > MAX_POSITIONS_BATCH {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut positions = Vec::new(e);
+    for user in users.iter() {
+        positions.push_back(storage::get_user_positions(e, &user));
+    }
+    positions
+}
+
 /// A user / contracts position's with the pool
 #[derive(Clone)]
 pub struct User {
@@ -54,6 +90,20 @@ impl User {
         storage::set_user_positions(e, &self.address, &self.positions);
     }
 
+    /// Encode the user's positions as the XDR of their SCVal representation. SCVal maps are sorted
+    /// by key, so the encoding is deterministic and equal positions always produce equal bytes.
+    pub fn export_positions(&self, e: &Env) -> Bytes {
+        self.positions.clone().to_xdr(e)
+    }
+
+    /// Check if `blob` is the encoding of the user's current positions
+    ///
+    /// ### Arguments
+    /// * `blob` - The encoded positions, as returned by `export_positions`
+    pub fn verify_positions(&self, e: &Env, blob: &Bytes) -> bool {
+        self.export_positions(e) == *blob
+    }
+
     /// Check if the user has liabilities
     pub fn has_liabilities(&self) -> bool {
         !self.positions.liabilities.is_empty()
@@ -131,8 +181,9 @@ impl User {
         if amount <= 0 {
             panic_with_error!(e, PoolError::InvalidBTokenMintAmount)
         }
-        let balance = self.get_collateral(reserve.config.index);
         self.update_b_emissions(e, reserve, self.get_total_supply(reserve.config.index));
+        accrue_coverage(e, self, reserve);
+        let balance = self.get_collateral(reserve.config.index);
         self.positions
             .collateral
             .set(reserve.config.index, balance + amount);
@@ -145,8 +196,9 @@ impl User {
         if amount <= 0 {
             panic_with_error!(e, PoolError::InvalidBTokenBurnAmount)
         }
-        let balance = self.get_collateral(reserve.config.index);
         self.update_b_emissions(e, reserve, self.get_total_supply(reserve.config.index));
+        accrue_coverage(e, self, reserve);
+        let balance = self.get_collateral(reserve.config.index);
         let new_balance = balance - amount;
         require_nonnegative(e, &new_balance);
         if new_balance == 0 {
@@ -170,8 +222,9 @@ impl User {
         if amount <= 0 {
             panic_with_error!(e, PoolError::InvalidBTokenMintAmount)
         }
-        let balance = self.get_supply(reserve.config.index);
         self.update_b_emissions(e, reserve, self.get_total_supply(reserve.config.index));
+        accrue_coverage(e, self, reserve);
+        let balance = self.get_supply(reserve.config.index);
         self.positions
             .supply
             .set(reserve.config.index, balance + amount);
@@ -184,8 +237,9 @@ impl User {
         if amount <= 0 {
             panic_with_error!(e, PoolError::InvalidBTokenBurnAmount)
         }
-        let balance = self.get_supply(reserve.config.index);
         self.update_b_emissions(e, reserve, self.get_total_supply(reserve.config.index));
+        accrue_coverage(e, self, reserve);
+        let balance = self.get_supply(reserve.config.index);
         let new_balance = balance - amount;
         require_nonnegative(e, &new_balance);
         if new_balance == 0 {
@@ -201,7 +255,8 @@ impl User {
         self.get_collateral(reserve_index) + self.get_supply(reserve_index)
     }
 
-    /// Removes positions from a user - does not consider supply
+    /// Removes positions from a user - does not consider supply. Removed liabilities are recorded
+    /// as a repayment of the user's principal.
     pub fn rm_positions(
         &mut self,
         e: &Env,
@@ -212,20 +267,34 @@ impl User {
         for (asset, amount) in collateral_amounts.iter() {
             if amount > 0 {
                 let mut reserve = pool.load_reserve(e, &asset, true);
-                self.remove_collateral(e, &mut reserve, amount);
+                // a covered user's premium may have been charged against the collateral
+                accrue_coverage(e, self, &mut reserve);
+                let amount = amount.min(self.get_collateral(reserve.config.index));
+                if amount > 0 {
+                    self.remove_collateral(e, &mut reserve, amount);
+                }
                 pool.cache_reserve(reserve);
             }
         }
         for (asset, amount) in liability_amounts.iter() {
             if amount > 0 {
                 let mut reserve = pool.load_reserve(e, &asset, true);
+                record_repay(
+                    e,
+                    &self.address,
+                    reserve.config.index,
+                    reserve.data.d_rate,
+                    self.get_liabilities(reserve.config.index),
+                    amount,
+                );
                 self.remove_liabilities(e, &mut reserve, amount);
                 pool.cache_reserve(reserve);
             }
         }
     }
 
-    /// Adds positions to a user - does not consider supply
+    /// Adds positions to a user - does not consider supply. Added liabilities are recorded as a
+    /// borrow of their current value.
     pub fn add_positions(
         &mut self,
         e: &Env,
@@ -243,6 +312,14 @@ impl User {
         for (asset, amount) in liability_amounts.iter() {
             if amount > 0 {
                 let mut reserve = pool.load_reserve(e, &asset, true);
+                record_borrow(
+                    e,
+                    &self.address,
+                    reserve.config.index,
+                    reserve.data.d_rate,
+                    self.get_liabilities(reserve.config.index),
+                    reserve.to_asset_from_d_token(e, amount),
+                );
                 self.add_liabilities(e, &mut reserve, amount);
                 pool.cache_reserve(reserve);
             }
@@ -260,7 +337,7 @@ impl User {
         );
     }
 
-    fn update_b_emissions(&self, e: &Env, reserve: &Reserve, amount: i128) {
+    pub(super) fn update_b_emissions(&self, e: &Env, reserve: &Reserve, amount: i128) {
         emissions::update_emissions(
             e,
             reserve.config.index * 2 + 1,
@@ -280,8 +357,79 @@ mod tests {
     use soroban_sdk::{
         map,
         testutils::{Address as _, Ledger, LedgerInfo},
+        vec,
+        xdr::FromXdr,
     };
 
+    #[test]
+    fn test_load_users_positions() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let positions = Positions {
+            liabilities: map![&e, (1, 50_0000000)],
+            collateral: map![&e, (0, 100_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_user_positions(&e, &frodo, &positions);
+
+            let batch = load_users_positions(&e, &vec![&e, samwise.clone(), frodo.clone()]);
+            assert_eq!(batch.len(), 2);
+            assert_eq!(batch.get_unchecked(0).effective_count(), 0);
+            let frodo_positions = batch.get_unchecked(1);
+            assert_eq!(frodo_positions.liabilities, positions.liabilities);
+            assert_eq!(frodo_positions.collateral, positions.collateral);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_load_users_positions_over_max_batch() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        let mut users = vec![&e];
+        for _ in 0..=MAX_POSITIONS_BATCH {
+            users.push_back(Address::generate(&e));
+        }
+        e.as_contract(&pool, || {
+            load_users_positions(&e, &users);
+        });
+    }
+
+    #[test]
+    fn test_increment_nonce_records_last_action() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.as_contract(&pool, || {
+            assert_eq!(storage::get_last_action(&e, &samwise), 0);
+
+            assert_eq!(increment_nonce(&e, &samwise), 1);
+            assert_eq!(storage::get_last_action(&e, &samwise), 12345);
+        });
+
+        e.ledger().set_timestamp(23456);
+        e.as_contract(&pool, || {
+            assert_eq!(increment_nonce(&e, &samwise), 2);
+            assert_eq!(storage::get_last_action(&e, &samwise), 23456);
+        });
+    }
+
     #[test]
     fn test_load_and_store() {
         let e = Env::default();
@@ -308,6 +456,48 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_export_and_verify_positions() {
+        let e = Env::default();
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let mut user = User {
+            address: samwise.clone(),
+            positions: Positions {
+                collateral: map![&e, (2, 10000), (0, 5000)],
+                liabilities: map![&e, (1, 2500)],
+                supply: map![&e],
+            },
+        };
+        e.as_contract(&pool, || {
+            user.store(&e);
+            let loaded_user = User::load(&e, &samwise);
+            let blob = loaded_user.export_positions(&e);
+
+            // the encoding round trips and is independent of insertion order
+            let decoded = Positions::from_xdr(&e, &blob).unwrap();
+            assert_eq!(decoded.collateral, user.positions.collateral);
+            assert_eq!(decoded.liabilities, user.positions.liabilities);
+            let reordered = User {
+                address: samwise.clone(),
+                positions: Positions {
+                    collateral: map![&e, (0, 5000), (2, 10000)],
+                    liabilities: map![&e, (1, 2500)],
+                    supply: map![&e],
+                },
+            };
+            assert_eq!(reordered.export_positions(&e), blob);
+            assert!(loaded_user.verify_positions(&e, &blob));
+
+            // positions change
+            user.positions.liabilities.set(1, 2501);
+            user.store(&e);
+            let loaded_user = User::load(&e, &samwise);
+            assert!(!loaded_user.verify_positions(&e, &blob));
+        });
+    }
+
     #[test]
     fn test_liabilities() {
         let e = Env::default();
@@ -0,0 +1,135 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{constants::MAX_WITHDRAW_COOLDOWN, errors::PoolError, storage};
+
+use super::User;
+
+/// Set the number of ledgers a user must wait after a borrow to withdraw collateral. A cooldown
+/// stops an account from borrowing against a manipulated price and withdrawing the collateral
+/// backing the borrow before the price recovers. A cooldown of 0 disables it.
+///
+/// ### Arguments
+/// * `ledgers` - The number of ledgers
+///
+/// ### Panics
+/// If the cooldown is over `MAX_WITHDRAW_COOLDOWN`
+pub fn execute_set_withdraw_cooldown(e: &Env, ledgers: u32) {
+    if ledgers > MAX_WITHDRAW_COOLDOWN {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_withdraw_cooldown(e, &ledgers);
+}
+
+/// Record the ledger of a user's borrow, if the pool has a withdraw cooldown
+pub(crate) fn record_borrow_ledger(e: &Env, user: &Address) {
+    if storage::get_withdraw_cooldown(e) > 0 {
+        storage::set_last_borrow(e, user, &e.ledger().sequence());
+    }
+}
+
+/// Require that the pool's withdraw cooldown has passed since the user's last borrow. Users
+/// without liabilities are exempt, so an account that repaid its borrows can withdraw right away.
+///
+/// ### Panics
+/// If the user has liabilities and borrowed within the cooldown
+pub(crate) fn require_withdraw_cooldown_passed(e: &Env, user: &User) {
+    let cooldown = storage::get_withdraw_cooldown(e);
+    if cooldown == 0 || user.positions.liabilities.is_empty() {
+        return;
+    }
+    if let Some(last_borrow) = storage::get_last_borrow(e, &user.address) {
+        if e.ledger().sequence() < last_borrow.saturating_add(cooldown) {
+            panic_with_error!(e, PoolError::WithdrawCooldown);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pool::Positions, testutils};
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger},
+    };
+
+    fn borrower(e: &Env) -> User {
+        User {
+            address: Address::generate(e),
+            positions: Positions {
+                collateral: map![e, (0, 100_0000000)],
+                liabilities: map![e, (1, 50_0000000)],
+                supply: map![e],
+            },
+        }
+    }
+
+    #[test]
+    fn test_withdraw_cooldown() {
+        let e = Env::default();
+        e.ledger().set_sequence_number(100);
+        let pool = testutils::create_pool(&e);
+        let samwise = borrower(&e);
+
+        e.as_contract(&pool, || {
+            // no cooldown is set, so borrows are not recorded
+            record_borrow_ledger(&e, &samwise.address);
+            assert!(storage::get_last_borrow(&e, &samwise.address).is_none());
+
+            execute_set_withdraw_cooldown(&e, 10);
+            record_borrow_ledger(&e, &samwise.address);
+            assert_eq!(storage::get_last_borrow(&e, &samwise.address), Some(100));
+        });
+
+        e.ledger().set_sequence_number(110);
+        e.as_contract(&pool, || {
+            require_withdraw_cooldown_passed(&e, &samwise);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1243)")]
+    fn test_withdraw_cooldown_not_passed() {
+        let e = Env::default();
+        e.ledger().set_sequence_number(100);
+        let pool = testutils::create_pool(&e);
+        let samwise = borrower(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_withdraw_cooldown(&e, 10);
+            record_borrow_ledger(&e, &samwise.address);
+        });
+
+        e.ledger().set_sequence_number(109);
+        e.as_contract(&pool, || {
+            require_withdraw_cooldown_passed(&e, &samwise);
+        });
+    }
+
+    #[test]
+    fn test_withdraw_cooldown_exempts_repaid_users() {
+        let e = Env::default();
+        e.ledger().set_sequence_number(100);
+        let pool = testutils::create_pool(&e);
+        let mut samwise = borrower(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_withdraw_cooldown(&e, 10);
+            record_borrow_ledger(&e, &samwise.address);
+
+            samwise.positions.liabilities = map![&e];
+            require_withdraw_cooldown_passed(&e, &samwise);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_withdraw_cooldown_over_max() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_withdraw_cooldown(&e, MAX_WITHDRAW_COOLDOWN + 1);
+        });
+    }
+}
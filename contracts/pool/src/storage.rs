@@ -1,9 +1,15 @@
 use soroban_sdk::{
-    contracttype, map, panic_with_error, unwrap::UnwrapOptimized, vec, Address, Env, IntoVal, Map,
-    String, Symbol, TryFromVal, Val, Vec,
+    contracttype, map, panic_with_error, unwrap::UnwrapOptimized, vec, Address, BytesN, Env,
+    IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
+use trustbridge_common::Asset;
 
-use crate::{auctions::AuctionData, constants::MAX_RESERVES, pool::Positions, PoolError};
+use crate::{
+    auctions::AuctionData,
+    constants::{DEFAULT_MAX_AUCTION_REQUESTS, DEFAULT_MAX_REQUESTS, MAX_RESERVES},
+    pool::Positions,
+    PoolError,
+};
 
 /********** Ledger Thresholds **********/
 
@@ -39,16 +45,233 @@ pub struct PoolEmissionConfig {
     pub last_time: u64,
 }
 
+/// The configuration for soft liquidations
+#[derive(Clone)]
+#[contracttype]
+pub struct SoftLiquidationConfig {
+    pub dex_adapter: Address, // the DEX adapter used to swap collateral for debt
+    pub max_pct: u32, // the max percent of a user's liabilities that can be repaid per soft liquidation, expressed in 7 decimals
+    pub fee: u32, // the percent of the collateral sold that is paid to the keeper, expressed in 7 decimals
+}
+
+/// A switch of the token the pool's emissions are paid in
+#[derive(Clone)]
+#[contracttype]
+pub struct EmissionMigration {
+    pub token: Address, // the token emissions are paid in after the migration
+    pub ledger: u32,    // the ledger emissions gulped from the backstop switch to the new token
+}
+
+/// The split of interest auction proceeds between the backstop and the treasury
+#[derive(Clone)]
+#[contracttype]
+pub struct InterestSplit {
+    pub treasury: Address, // the treasury contract that receives its share of the proceeds
+    pub pct: u32, // the percent of the backstop tokens bid that is sent to the treasury, expressed in 7 decimals
+}
+
+/// A fee taken from the interest accrued by each reserve for the protocol's treasury
+#[derive(Clone)]
+#[contracttype]
+pub struct ProtocolFee {
+    pub treasury: Address, // the treasury contract that receives the fees
+    pub rate: u32, // the percent of accrued interest taken as a fee, expressed in 7 decimals
+}
+
+/// A protocol fee waiting for its timelock to pass
+#[derive(Clone)]
+#[contracttype]
+pub struct QueuedProtocolFee {
+    pub fee: ProtocolFee,
+    pub unlock_time: u64, // the time the fee can be set
+}
+
+/// An interest split waiting for its timelock to pass
+#[derive(Clone)]
+#[contracttype]
+pub struct QueuedInterestSplit {
+    pub split: InterestSplit,
+    pub unlock_time: u64, // the time the split can be set
+}
+
+/// The maximum number of requests a single submit can make
+#[derive(Clone)]
+#[contracttype]
+pub struct ActionLimits {
+    pub max_requests: u32,         // the maximum number of requests per submit
+    pub max_auction_requests: u32, // the maximum number of auction fills or deletes per submit
+}
+
+/// The display metadata of a pool, for explorers and frontends
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolMetadata {
+    pub name: String,        // the name of the pool
+    pub icon_uri: String,    // the URI of the pool's icon
+    pub description: String, // a description of the pool
+}
+
+/// The deferred liquidation start for accounts with little collateral, so dust accounts are not
+/// liquidated for less than the fees spent doing it
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RetailLiquidation {
+    pub threshold: i128, // retail accounts have less raw collateral than this, in the base asset
+    pub min_hf: u32,     // the health factor retail accounts can be liquidated under (7 decimals)
+}
+
+/// The thresholds a user must meet for each risk tier from A to D. Users that meet none of them
+/// are in tier E.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RiskTierConfig {
+    pub min_hf: Vec<u32>,     // the minimum health factor for each tier (7 decimals)
+    pub min_assets: Vec<u32>, // the minimum number of collateral reserves for each tier
+    pub max_liquidations: Vec<u32>, // the maximum number of liquidations for each tier
+}
+
+/// The migration of a reserve from its underlying token to a new token, like a re-issued token
+/// or one that changed decimals
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveMigration {
+    pub asset: Address,        // the current underlying token of the reserve
+    pub new_asset: Address,    // the token the reserve is migrated to
+    pub decimals: u32,         // the decimals of the new token
+    pub conversion_rate: i128, // the amount of the new token per old token with 12 decimals
+}
+
+/// A reserve migration waiting for its timelock to pass
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct QueuedReserveMigration {
+    pub migration: ReserveMigration,
+    pub unlock_time: u64, // the time the migration can be executed
+}
+
+/// The bad debt of a reserve assigned to the backstop, in dTokens
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct BadDebt {
+    pub assigned: i128,  // the bad debt ever assigned to the backstop
+    pub defaulted: i128, // the bad debt ever defaulted onto the reserve's suppliers
+}
+
+/// The smoothing of a reserve's utilization for its interest rate. Rates are calculated from an
+/// exponential moving average of the utilization instead of the instantaneous utilization.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct UtilSmoothing {
+    pub window: u64, // the time in seconds the utilization is averaged over
+    pub util: i128,  // the smoothed utilization with 7 decimals
+}
+
+/// A boost to the emissions paid to a reserve's suppliers while the reserve is under-supplied.
+/// Once the utilization has stayed above `target_util` for `ledgers`, the boost scales up with the
+/// utilization in steps of `BOOST_STEP`, to at most `max_boost` at 100% utilization. The boost
+/// multiplies the reserve's supplier share of the pool emissions the next time they are gulped, so
+/// the boosted suppliers receive more of the pool's emissions and the other reserves receive less.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EmissionBoost {
+    pub target_util: u32, // the utilization the boost starts above, with 7 decimals
+    pub ledgers: u32,     // the ledgers the utilization must stay above the target for
+    pub max_boost: u32,   // the largest multiplier applied to the supplier share, with 7 decimals
+    pub above_since: u32, // the ledger the utilization rose above the target, or 0 if below it
+    pub boost: u32,       // the multiplier applied to the supplier share, with 7 decimals
+}
+
+/// A gradual change of a reserve's collateral and liability factors. The factors move linearly
+/// from the values below to the values in the reserve configuration over `FACTOR_RAMP_LEDGERS`.
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveRamp {
+    pub c_factor: u32,     // the collateral factor at the start of the ramp
+    pub l_factor: u32,     // the liability factor at the start of the ramp
+    pub start_ledger: u32, // the ledger sequence the ramp started at
+}
+
+/// A proposal to add a reserve to the pool, backed by a bond from the proposer
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveProposal {
+    pub proposer: Address,     // the address that posted the bond
+    pub config: ReserveConfig, // the proposed reserve configuration
+    pub bond: i128,            // the amount of BLND bonded
+    pub expiration: u64, // the time after which the proposer can reclaim the bond if it was not reviewed
+}
+
+/// A reserve's b_rate and d_rate at a point in time
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct RateCheckpoint {
+    pub timestamp: u64, // the ledger timestamp the rates were recorded at
+    pub b_rate: i128,   // the conversion rate from bToken to underlying with 12 decimals
+    pub d_rate: i128,   // the conversion rate from dToken to underlying with 12 decimals
+}
+
+/// The lowest and highest utilization of a reserve seen over a day
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct UtilDay {
+    pub day: u64,       // the day, as the ledger timestamp divided by the length of a day
+    pub min_util: i128, // the lowest utilization stored during the day with 7 decimals
+    pub max_util: i128, // the highest utilization stored during the day with 7 decimals
+}
+
+/// The state of a pool that has been shut down
+#[derive(Clone)]
+#[contracttype]
+pub struct ShutdownData {
+    pub timestamp: u64,             // the ledger timestamp the pool was shut down at
+    pub prices: Map<Address, i128>, // the oracle price of each reserve asset at shutdown
+}
+
+/// A strategy adapter that a reserve's idle liquidity can be deployed to
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveStrategy {
+    pub adapter: Address, // the strategy adapter holding the deployed liquidity
+    pub max_deploy: u32,  // the maximum fraction of idle liquidity to deploy (7 decimals)
+    pub deployed: i128,   // the amount of underlying tokens deployed to the adapter
+}
+
+/// A supplier's coverage from a reserve's insurance fund
+#[derive(Clone)]
+#[contracttype]
+pub struct Coverage {
+    pub b_rate: i128,    // the reserve's b_rate when the coverage was last accrued
+    pub claimable: i128, // the amount of underlying lost while covered that can be claimed
+    pub start: u64,      // the time the coverage takes effect, after the opt-in waiting period
+}
+
+/// A check run against each request submitted to the pool before it is applied
+#[derive(Clone, PartialEq)]
+#[contracttype]
+pub enum RequestHook {
+    NonNegative,       // the request amount is not negative
+    PoolStatus,        // the pool's status allows the request type
+    External(Address), // the request is approved by an external validator contract
+}
+
+/// Emissions directed entirely to the suppliers of a set of reserves while a new pool bootstraps
+#[derive(Clone)]
+#[contracttype]
+pub struct BootstrapEmissions {
+    pub reserves: Vec<u32>, // the indexes of the reserves whose suppliers receive all emissions
+    pub expiration: u64,    // the ledger timestamp the bootstrap period ends at
+}
+
 /// The configuration information about a reserve asset
 #[derive(Clone, Debug)]
 #[contracttype]
 pub struct ReserveConfig {
-    pub index: u32,       // the index of the reserve in the list
-    pub decimals: u32,    // the decimals used in both the bToken and underlying contract
-    pub c_factor: u32,    // the collateral factor for the reserve scaled expressed in 7 decimals
-    pub l_factor: u32,    // the liability factor for the reserve scaled expressed in 7 decimals
-    pub util: u32,        // the target utilization rate scaled expressed in 7 decimals
-    pub max_util: u32,    // the maximum allowed utilization rate scaled expressed in 7 decimals
+    pub index: u32,          // the index of the reserve in the list
+    pub decimals: u32,       // the decimals used in both the bToken and underlying contract
+    pub c_factor: u32,       // the collateral factor for the reserve scaled expressed in 7 decimals
+    pub l_factor: u32,       // the liability factor for the reserve scaled expressed in 7 decimals
+    pub util: u32,           // the target utilization rate scaled expressed in 7 decimals
+    pub max_util: u32,       // the maximum allowed utilization rate scaled expressed in 7 decimals
     pub r_base: u32, // the R0 value (base rate) in the interest rate formula scaled expressed in 7 decimals
     pub r_one: u32,  // the R1 value in the interest rate formula scaled expressed in 7 decimals
     pub r_two: u32,  // the R2 value in the interest rate formula scaled expressed in 7 decimals
@@ -56,6 +279,8 @@ pub struct ReserveConfig {
     pub reactivity: u32, // the reactivity constant for the reserve scaled expressed in 7 decimals
     pub supply_cap: i128, // the total amount of underlying tokens that can be supplied to the reserve
     pub enabled: bool,    // the enabled flag of the reserve
+    pub conc_threshold: u32, // the share of bTokens a user can hold before a concentration haircut
+    pub conc_haircut: u32, // the collateral haircut for holding the entire supply, or 0 for none
 }
 
 #[derive(Clone)]
@@ -101,11 +326,40 @@ pub struct UserEmissionData {
 const ADMIN_KEY: &str = "Admin";
 const PROPOSED_ADMIN_KEY: &str = "PropAdmin";
 const NAME_KEY: &str = "Name";
+const METADATA_KEY: &str = "Metadata";
 const BACKSTOP_KEY: &str = "Backstop";
 const BLND_TOKEN_KEY: &str = "BLNDTkn";
 const POOL_CONFIG_KEY: &str = "Config";
 const RES_LIST_KEY: &str = "ResList";
 const POOL_EMIS_KEY: &str = "PoolEmis";
+const AUCT_MIN_BLOCKS_KEY: &str = "AuctMinBlk";
+const AUCT_START_OFFSET_KEY: &str = "AuctStrtOff";
+const RES_ALLOWLIST_KEY: &str = "ResAllowLst";
+const SOFT_LIQ_CONFIG_KEY: &str = "SoftLiqCfg";
+const INTEREST_SPLIT_KEY: &str = "IntSplit";
+const EMIS_MIGRATION_KEY: &str = "EmisMig";
+const DEX_ADAPTER_KEY: &str = "DexAdptr";
+const SHUTDOWN_KEY: &str = "Shutdown";
+const PROPOSAL_BOND_KEY: &str = "PropBond";
+const BONDED_BLND_KEY: &str = "BondedBlnd";
+const BOOTSTRAP_EMIS_KEY: &str = "BootEmis";
+const LIQ_ALLOWLIST_KEY: &str = "LiqAllowLst";
+const INS_PREMIUM_KEY: &str = "InsPremium";
+const REQUEST_HOOKS_KEY: &str = "ReqHooks";
+const CONF_THRESHOLD_KEY: &str = "ConfThld";
+const PROTOCOL_FEE_KEY: &str = "ProtoFee";
+const QUEUED_PROTOCOL_FEE_KEY: &str = "QProtoFee";
+const QUEUED_INTEREST_SPLIT_KEY: &str = "QIntSplit";
+const ACTION_LIMITS_KEY: &str = "ActLimits";
+const BASE_ASSET_KEY: &str = "BaseAsset";
+const RES_MIGRATION_KEY: &str = "ResMig";
+const WITHDRAW_COOLDOWN_KEY: &str = "WdrwCool";
+const RETAIL_LIQ_KEY: &str = "RetailLiq";
+const LAST_BUMP_KEY: &str = "LastBump";
+const RISK_TIERS_KEY: &str = "RiskTiers";
+const STATUS_GUARDIAN_KEY: &str = "StatGuard";
+const BOOSTED_RES_KEY: &str = "EmisBoosts";
+const OPEN_AUCTIONS_KEY: &str = "OpenAucts";
 
 #[derive(Clone)]
 #[contracttype]
@@ -117,8 +371,8 @@ pub struct UserReserveKey {
 #[derive(Clone)]
 #[contracttype]
 pub struct AuctionKey {
-    user: Address,  // the Address whose assets are involved in the auction
-    auct_type: u32, // the type of auction taking place
+    pub user: Address,  // the Address whose assets are involved in the auction
+    pub auct_type: u32, // the type of auction taking place
 }
 
 #[derive(Clone)]
@@ -138,6 +392,66 @@ pub enum PoolDataKey {
     UserEmis(UserReserveKey),
     // The auction's data
     Auction(AuctionKey),
+    // The underlying amount a user has borrowed from a reserve, excluding interest
+    Principal(UserReserveKey),
+    // A map of underlying asset's contract address to reserve factor ramp
+    ResRamp(Address),
+    // A map of underlying asset's contract address to its most recent rate checkpoints
+    RateHist(Address),
+    // The number of submits and claims a user has made
+    Nonce(Address),
+    // A map of underlying asset's contract address to a proposal to add it as a reserve
+    ResProp(Address),
+    // If an address is allowed to fill liquidation auctions
+    Liquidator(Address),
+    // The d_rate of a reserve when a user last borrowed or repaid
+    BorrowIdx(UserReserveKey),
+    // The number of users with a borrow index recorded for a reserve
+    BorrowIdxs(u32),
+    // The strategy adapter for a reserve's idle liquidity
+    Strategy(Address),
+    // A supplier's coverage from a reserve's insurance fund
+    Coverage(UserReserveKey),
+    // The underlying held by a reserve's insurance fund
+    InsFund(Address),
+    // If transfers into a reserve are credited by the change in the pool's balance
+    BalDelta(Address),
+    // The hash of the key notifier services use to route a user's health alerts
+    NotifyKey(Address),
+    // If health calculations price a reserve with the oracle's TWAP as well as its spot price
+    TwapPrice(Address),
+    // The ledger a reserve was first seen without a valid oracle price
+    Unpriced(Address),
+    // If a reserve was sunset for being without a valid oracle price
+    Sunset(Address),
+    // The time a user's self-imposed borrow freeze lifts
+    BorrowFreeze(Address),
+    // The time an account leaves supply-only mode
+    SupplyOnly(Address),
+    // The ed25519 public key a user signs requests with for relayed submits
+    SigningKey(Address),
+    // The reserve token's emission data for the token used before the emission migration
+    LegacyEmis(u32),
+    // The user's emission data for the token used before the emission migration
+    LegacyUserEmis(UserReserveKey),
+    // The underlying owed to the treasury from a reserve's protocol fees
+    ProtoFee(Address),
+    // The smoothing of a reserve's utilization for its interest rate
+    UtilSmooth(Address),
+    // The bad debt of a reserve assigned to the backstop
+    BadDebt(Address),
+    // The ledger timestamp of a user's last submit or claim
+    LastAction(Address),
+    // A map of underlying asset's contract address to its most recent daily utilization ranges
+    UtilHist(Address),
+    // The ledger of a user's last borrow
+    LastBorrow(Address),
+    // The number of liquidation auction fills against a user
+    LiqCount(Address),
+    // The boost to the emissions paid to a reserve's suppliers
+    EmisBoost(Address),
+    // A map of underlying asset's contract address to its utilization range for the current day
+    UtilToday(Address),
 }
 
 /********** Storage **********/
@@ -149,6 +463,36 @@ pub fn extend_instance(e: &Env) {
         .extend_ttl(LEDGER_THRESHOLD_INSTANCE, LEDGER_BUMP_INSTANCE);
 }
 
+/// Bump the rent for the entries every action on the pool depends on: the instance, the reserve
+/// list, each reserve's config and data, and the emission data of the pool and its reserves
+pub fn extend_critical(e: &Env) {
+    extend_instance(e);
+    let res_list_key = Symbol::new(e, RES_LIST_KEY);
+    extend_shared(e, &res_list_key);
+    extend_shared(e, &Symbol::new(e, POOL_EMIS_KEY));
+    let res_list = e
+        .storage()
+        .persistent()
+        .get::<Symbol, Vec<Address>>(&res_list_key)
+        .unwrap_or(vec![e]);
+    for (index, asset) in res_list.iter().enumerate() {
+        let index = index as u32;
+        extend_shared(e, &PoolDataKey::ResConfig(asset.clone()));
+        extend_shared(e, &PoolDataKey::ResData(asset));
+        extend_shared(e, &PoolDataKey::EmisData(index * 2));
+        extend_shared(e, &PoolDataKey::EmisData(index * 2 + 1));
+    }
+}
+
+/// Bump the rent for a shared entry in persistent storage, if it exists
+fn extend_shared<K: IntoVal<Env, Val>>(e: &Env, key: &K) {
+    if e.storage().persistent().has(key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+    }
+}
+
 /// Fetch an entry in persistent storage that has a default value if it doesn't exist
 fn get_persistent_default<K: IntoVal<Env, Val>, V: TryFromVal<Env, Val>, F: FnOnce() -> V>(
     e: &Env,
@@ -259,6 +603,40 @@ pub fn set_name(e: &Env, name: &String) {
         .set::<Symbol, String>(&Symbol::new(e, NAME_KEY), name);
 }
 
+/// Fetch the pool's metadata. Defaults to the pool's name with no icon or description.
+pub fn get_pool_metadata(e: &Env) -> PoolMetadata {
+    get_persistent_default(
+        e,
+        &Symbol::new(e, METADATA_KEY),
+        || PoolMetadata {
+            name: e
+                .storage()
+                .instance()
+                .get::<Symbol, String>(&Symbol::new(e, NAME_KEY))
+                .unwrap_or_else(|| String::from_str(e, "")),
+            icon_uri: String::from_str(e, ""),
+            description: String::from_str(e, ""),
+        },
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the pool's metadata. The pool's name is updated to match.
+///
+/// ### Arguments
+/// * `metadata` - The metadata of the pool
+pub fn set_pool_metadata(e: &Env, metadata: &PoolMetadata) {
+    set_name(e, &metadata.name);
+    let key = Symbol::new(e, METADATA_KEY);
+    e.storage()
+        .persistent()
+        .set::<Symbol, PoolMetadata>(&key, metadata);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
 /********** Backstop **********/
 
 /// Fetch the backstop ID for the pool
@@ -360,6 +738,126 @@ pub fn set_res_config(e: &Env, asset: &Address, config: &ReserveConfig) {
         .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
 
+/// Fetch the factor ramp for an asset, if one has been set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_res_ramp(e: &Env, asset: &Address) -> Option<ReserveRamp> {
+    let key = PoolDataKey::ResRamp(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the factor ramp for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `ramp` - The factor ramp for the asset
+pub fn set_res_ramp(e: &Env, asset: &Address, ramp: &ReserveRamp) {
+    let key = PoolDataKey::ResRamp(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, ReserveRamp>(&key, ramp);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the rate checkpoints for an asset, ordered from oldest to newest
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_rate_history(e: &Env, asset: &Address) -> Vec<RateCheckpoint> {
+    let key = PoolDataKey::RateHist(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || vec![e],
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the rate checkpoints for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `history` - The rate checkpoints, ordered from oldest to newest
+pub fn set_rate_history(e: &Env, asset: &Address, history: &Vec<RateCheckpoint>) {
+    let key = PoolDataKey::RateHist(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, Vec<RateCheckpoint>>(&key, history);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the daily utilization ranges for an asset, ordered from oldest to newest
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_util_history(e: &Env, asset: &Address) -> Vec<UtilDay> {
+    let key = PoolDataKey::UtilHist(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || vec![e],
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the daily utilization ranges for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `history` - The daily utilization ranges, ordered from oldest to newest
+pub fn set_util_history(e: &Env, asset: &Address, history: &Vec<UtilDay>) {
+    let key = PoolDataKey::UtilHist(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, Vec<UtilDay>>(&key, history);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the utilization range of the current day for an asset, if any
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_util_today(e: &Env, asset: &Address) -> Option<UtilDay> {
+    let key = PoolDataKey::UtilToday(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the utilization range of the current day for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `today` - The utilization range of the current day
+pub fn set_util_today(e: &Env, asset: &Address, today: &UtilDay) {
+    let key = PoolDataKey::UtilToday(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, UtilDay>(&key, today);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
 /// Checks if a reserve exists for an asset
 ///
 /// ### Arguments
@@ -455,6 +953,19 @@ pub fn set_res_data(e: &Env, asset: &Address, data: &ReserveData) {
         .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
 
+/// Remove the reserve configuration and data for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn del_res(e: &Env, asset: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&PoolDataKey::ResConfig(asset.clone()));
+    e.storage()
+        .persistent()
+        .remove(&PoolDataKey::ResData(asset.clone()));
+}
+
 /********** Reserve List (ResList) **********/
 
 /// Fetch the list of reserves
@@ -495,49 +1006,225 @@ pub fn push_res_list(e: &Env, asset: &Address) -> u32 {
     new_index
 }
 
-/********** Reserve Emissions **********/
-
-/// Fetch the emission data for the reserve b or d token
+/// Replace the asset at an index of the reserve list
 ///
 /// ### Arguments
-/// * `res_token_index` - The d/bToken index for the reserve
-pub fn get_res_emis_data(e: &Env, res_token_index: &u32) -> Option<ReserveEmissionData> {
-    let key = PoolDataKey::EmisData(*res_token_index);
-    get_persistent_default(
-        e,
-        &key,
-        || None,
+/// * `index` - The index of the reserve
+/// * `asset` - The contract address of the new underlying asset
+pub fn set_res_list_asset(e: &Env, index: u32, asset: &Address) {
+    let mut res_list = get_res_list(e);
+    res_list.set(index, asset.clone());
+    e.storage()
+        .persistent()
+        .set::<Symbol, Vec<Address>>(&Symbol::new(e, RES_LIST_KEY), &res_list);
+    e.storage().persistent().extend_ttl(
+        &Symbol::new(e, RES_LIST_KEY),
         LEDGER_THRESHOLD_SHARED,
         LEDGER_BUMP_SHARED,
-    )
+    );
 }
 
-/// Set the emission data for the reserve b or d token
+/********** Reserve Allowlist **********/
+
+/// Fetch the contract restricting which assets can be added as reserves, if one is set
+pub fn get_reserve_allowlist(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, RES_ALLOWLIST_KEY))
+}
+
+/// Set the contract restricting which assets can be added as reserves
 ///
 /// ### Arguments
-/// * `res_token_index` - The d/bToken index for the reserve
-/// * `res_emis_data` - The new emission data for the reserve token
-pub fn set_res_emis_data(e: &Env, res_token_index: &u32, res_emis_data: &ReserveEmissionData) {
-    let key = PoolDataKey::EmisData(*res_token_index);
-    e.storage()
-        .persistent()
-        .set::<PoolDataKey, ReserveEmissionData>(&key, res_emis_data);
+/// * `allowlist` - The address of the allowlist contract
+pub fn set_reserve_allowlist(e: &Env, allowlist: &Address) {
     e.storage()
-        .persistent()
-        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, RES_ALLOWLIST_KEY), allowlist);
 }
 
-/********** User Emissions **********/
+/********** Soft Liquidation **********/
 
-/// Fetch the users emission data for a reserve's b or d token
+/// Fetch the soft liquidation configuration, if soft liquidations are enabled
+pub fn get_soft_liquidation_config(e: &Env) -> Option<SoftLiquidationConfig> {
+    e.storage()
+        .instance()
+        .get::<Symbol, SoftLiquidationConfig>(&Symbol::new(e, SOFT_LIQ_CONFIG_KEY))
+}
+
+/// Set the soft liquidation configuration
 ///
 /// ### Arguments
-/// * `user` - The address of the user
-/// * `res_token_index` - The d/bToken index for the reserve
-pub fn get_user_emissions(
-    e: &Env,
-    user: &Address,
-    res_token_index: &u32,
+/// * `config` - The soft liquidation configuration
+pub fn set_soft_liquidation_config(e: &Env, config: &SoftLiquidationConfig) {
+    e.storage()
+        .instance()
+        .set::<Symbol, SoftLiquidationConfig>(&Symbol::new(e, SOFT_LIQ_CONFIG_KEY), config);
+}
+
+/********** Emission Migration **********/
+
+/// Fetch the pool's emission token migration, if one is configured
+pub fn get_emission_migration(e: &Env) -> Option<EmissionMigration> {
+    e.storage()
+        .instance()
+        .get::<Symbol, EmissionMigration>(&Symbol::new(e, EMIS_MIGRATION_KEY))
+}
+
+/// Set the pool's emission token migration
+///
+/// ### Arguments
+/// * `migration` - The emission token migration
+pub fn set_emission_migration(e: &Env, migration: &EmissionMigration) {
+    e.storage()
+        .instance()
+        .set::<Symbol, EmissionMigration>(&Symbol::new(e, EMIS_MIGRATION_KEY), migration);
+}
+
+/********** Interest Split **********/
+
+/// Fetch the split of interest auction proceeds routed to the treasury, if set
+pub fn get_interest_split(e: &Env) -> Option<InterestSplit> {
+    e.storage()
+        .instance()
+        .get::<Symbol, InterestSplit>(&Symbol::new(e, INTEREST_SPLIT_KEY))
+}
+
+/// Set the split of interest auction proceeds routed to the treasury
+///
+/// ### Arguments
+/// * `split` - The interest split, or None to send all proceeds to the backstop
+pub fn set_interest_split(e: &Env, split: &Option<InterestSplit>) {
+    let key = Symbol::new(e, INTEREST_SPLIT_KEY);
+    match split {
+        Some(split) => e
+            .storage()
+            .instance()
+            .set::<Symbol, InterestSplit>(&key, split),
+        None => e.storage().instance().remove(&key),
+    }
+}
+
+/// Fetch the queued interest split, if any
+pub fn get_queued_interest_split(e: &Env) -> Option<QueuedInterestSplit> {
+    e.storage()
+        .instance()
+        .get::<Symbol, QueuedInterestSplit>(&Symbol::new(e, QUEUED_INTEREST_SPLIT_KEY))
+}
+
+/// Set the queued interest split
+///
+/// ### Arguments
+/// * `queued` - The queued interest split, or None to clear it
+pub fn set_queued_interest_split(e: &Env, queued: &Option<QueuedInterestSplit>) {
+    let key = Symbol::new(e, QUEUED_INTEREST_SPLIT_KEY);
+    match queued {
+        Some(queued) => e
+            .storage()
+            .instance()
+            .set::<Symbol, QueuedInterestSplit>(&key, queued),
+        None => e.storage().instance().remove(&key),
+    }
+}
+
+/********** Shutdown **********/
+
+/// Fetch the shutdown state of the pool, if the pool has been shut down
+pub fn get_shutdown(e: &Env) -> Option<ShutdownData> {
+    e.storage()
+        .instance()
+        .get::<Symbol, ShutdownData>(&Symbol::new(e, SHUTDOWN_KEY))
+}
+
+/// Set the shutdown state of the pool
+///
+/// ### Arguments
+/// * `shutdown` - The shutdown state
+pub fn set_shutdown(e: &Env, shutdown: &ShutdownData) {
+    e.storage()
+        .instance()
+        .set::<Symbol, ShutdownData>(&Symbol::new(e, SHUTDOWN_KEY), shutdown);
+}
+
+/********** Reserve Emissions **********/
+
+/// Fetch the emission data for the reserve b or d token
+///
+/// ### Arguments
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn get_res_emis_data(e: &Env, res_token_index: &u32) -> Option<ReserveEmissionData> {
+    let key = PoolDataKey::EmisData(*res_token_index);
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the emission data for the reserve b or d token
+///
+/// ### Arguments
+/// * `res_token_index` - The d/bToken index for the reserve
+/// * `res_emis_data` - The new emission data for the reserve token
+pub fn set_res_emis_data(e: &Env, res_token_index: &u32, res_emis_data: &ReserveEmissionData) {
+    let key = PoolDataKey::EmisData(*res_token_index);
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, ReserveEmissionData>(&key, res_emis_data);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the emission data for a reserve's b or d token for the token used before the emission
+/// migration. Only exists once the reserve token's emissions have been migrated.
+///
+/// ### Arguments
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn get_legacy_res_emis_data(e: &Env, res_token_index: &u32) -> Option<ReserveEmissionData> {
+    let key = PoolDataKey::LegacyEmis(*res_token_index);
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the emission data for a reserve's b or d token for the token used before the emission
+/// migration
+///
+/// ### Arguments
+/// * `res_token_index` - The d/bToken index for the reserve
+/// * `res_emis_data` - The new emission data for the reserve token
+pub fn set_legacy_res_emis_data(
+    e: &Env,
+    res_token_index: &u32,
+    res_emis_data: &ReserveEmissionData,
+) {
+    let key = PoolDataKey::LegacyEmis(*res_token_index);
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, ReserveEmissionData>(&key, res_emis_data);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** User Emissions **********/
+
+/// Fetch the users emission data for a reserve's b or d token
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn get_user_emissions(
+    e: &Env,
+    user: &Address,
+    res_token_index: &u32,
 ) -> Option<UserEmissionData> {
     let key = PoolDataKey::UserEmis(UserReserveKey {
         user: user.clone(),
@@ -565,97 +1252,1441 @@ pub fn set_user_emissions(e: &Env, user: &Address, res_token_index: &u32, data:
         .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
 }
 
-/********** Pool Emissions **********/
+/// Remove the users emission data for a reserve's b or d token
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn del_user_emissions(e: &Env, user: &Address, res_token_index: &u32) {
+    let key = PoolDataKey::UserEmis(UserReserveKey {
+        user: user.clone(),
+        reserve_id: *res_token_index,
+    });
+    e.storage().persistent().remove(&key);
+}
 
-/// Fetch the pool reserve emissions
-pub fn get_pool_emissions(e: &Env) -> Map<u32, u64> {
-    get_persistent_default(
-        e,
-        &Symbol::new(e, POOL_EMIS_KEY),
-        || map![e],
-        LEDGER_THRESHOLD_SHARED,
-        LEDGER_BUMP_SHARED,
-    )
+/// Fetch the users emission data for a reserve's b or d token for the token used before the
+/// emission migration
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn get_legacy_user_emissions(
+    e: &Env,
+    user: &Address,
+    res_token_index: &u32,
+) -> Option<UserEmissionData> {
+    let key = PoolDataKey::LegacyUserEmis(UserReserveKey {
+        user: user.clone(),
+        reserve_id: *res_token_index,
+    });
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
 }
 
-/// Set the pool reserve emissions
+/// Set the users emission data for a reserve's b or d token for the token used before the
+/// emission migration
 ///
 /// ### Arguments
-/// * `emissions` - The map of emissions by reserve token id to share of emissions as
-///                 a percentage of 1e7 (e.g. 15% = 1500000)
-pub fn set_pool_emissions(e: &Env, emissions: &Map<u32, u64>) {
+/// * `user` - The address of the user
+/// * `res_token_index` - The d/bToken index for the reserve
+/// * `data` - The new user emission data for the d/bToken
+pub fn set_legacy_user_emissions(
+    e: &Env,
+    user: &Address,
+    res_token_index: &u32,
+    data: &UserEmissionData,
+) {
+    let key = PoolDataKey::LegacyUserEmis(UserReserveKey {
+        user: user.clone(),
+        reserve_id: *res_token_index,
+    });
     e.storage()
         .persistent()
-        .set::<Symbol, Map<u32, u64>>(&Symbol::new(e, POOL_EMIS_KEY), emissions);
-    e.storage().persistent().extend_ttl(
-        &Symbol::new(e, POOL_EMIS_KEY),
-        LEDGER_THRESHOLD_SHARED,
-        LEDGER_BUMP_SHARED,
-    );
+        .set::<PoolDataKey, UserEmissionData>(&key, data);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
 }
 
-/********** Auctions ***********/
+/// Fetch the number of submits and claims a user has made. Defaults to 0 if not set.
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_user_nonce(e: &Env, user: &Address) -> u64 {
+    let key = PoolDataKey::Nonce(user.clone());
+    get_persistent_default(e, &key, || 0u64, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
 
-/// Fetch the auction data for an auction
+/// Set the number of submits and claims a user has made
 ///
 /// ### Arguments
-/// * `auction_type` - The type of auction
-/// * `user` - The user who is auctioning off assets
+/// * `user` - The address of the user
+/// * `nonce` - The new nonce
+pub fn set_user_nonce(e: &Env, user: &Address, nonce: &u64) {
+    let key = PoolDataKey::Nonce(user.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, u64>(&key, nonce);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the ledger timestamp of a user's last submit or claim. Defaults to 0 if not set.
 ///
-/// ### Panics
-/// If the auction does not exist
-pub fn get_auction(e: &Env, auction_type: &u32, user: &Address) -> AuctionData {
-    let key = PoolDataKey::Auction(AuctionKey {
-        user: user.clone(),
-        auct_type: *auction_type,
-    });
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_last_action(e: &Env, user: &Address) -> u64 {
+    let key = PoolDataKey::LastAction(user.clone());
+    get_persistent_default(e, &key, || 0u64, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the ledger timestamp of a user's last submit or claim
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `timestamp` - The ledger timestamp of the action
+pub fn set_last_action(e: &Env, user: &Address, timestamp: &u64) {
+    let key = PoolDataKey::LastAction(user.clone());
     e.storage()
-        .temporary()
-        .get::<PoolDataKey, AuctionData>(&key)
-        .unwrap_optimized()
+        .persistent()
+        .set::<PoolDataKey, u64>(&key, timestamp);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
 }
 
-/// Check if an auction exists for the given type and user
+/********** User Principal **********/
+
+/// Fetch the underlying amount a user has borrowed from a reserve, excluding interest.
+/// Defaults to 0 if not set.
 ///
 /// ### Arguments
-/// * `auction_type` - The type of auction
-/// * `user` - The user who is auctioning off assets
-pub fn has_auction(e: &Env, auction_type: &u32, user: &Address) -> bool {
-    let key = PoolDataKey::Auction(AuctionKey {
+/// * `user` - The address of the user
+/// * `reserve_index` - The index of the reserve
+pub fn get_user_principal(e: &Env, user: &Address, reserve_index: &u32) -> i128 {
+    let key = PoolDataKey::Principal(UserReserveKey {
         user: user.clone(),
-        auct_type: *auction_type,
+        reserve_id: *reserve_index,
     });
-    e.storage().temporary().has(&key)
+    get_persistent_default(e, &key, || 0i128, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
 }
 
-/// Set the the starting block for an auction
+/// Set the underlying amount a user has borrowed from a reserve, excluding interest.
+/// Removes the entry if the principal is zero.
 ///
 /// ### Arguments
-/// * `auction_type` - The type of auction
-/// * `user` - The user who is auctioning off assets
-/// * `auction_data` - The auction data
-pub fn set_auction(e: &Env, auction_type: &u32, user: &Address, auction_data: &AuctionData) {
-    let key = PoolDataKey::Auction(AuctionKey {
+/// * `user` - The address of the user
+/// * `reserve_index` - The index of the reserve
+/// * `principal` - The new principal
+pub fn set_user_principal(e: &Env, user: &Address, reserve_index: &u32, principal: &i128) {
+    let key = PoolDataKey::Principal(UserReserveKey {
         user: user.clone(),
-        auct_type: *auction_type,
+        reserve_id: *reserve_index,
     });
+    if *principal == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
     e.storage()
-        .temporary()
-        .set::<PoolDataKey, AuctionData>(&key, auction_data);
+        .persistent()
+        .set::<PoolDataKey, i128>(&key, principal);
     e.storage()
-        .temporary()
-        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
 }
 
-/// Remove an auction
+/// Fetch the d_rate of a reserve when the user last borrowed or repaid. Defaults to 0 if the
+/// user has no liabilities for the reserve.
 ///
 /// ### Arguments
-/// * `auction_type` - The type of auction
-/// * `user` - The user who is auctioning off assets
-pub fn del_auction(e: &Env, auction_type: &u32, user: &Address) {
-    let key = PoolDataKey::Auction(AuctionKey {
+/// * `user` - The address of the user
+/// * `reserve_index` - The index of the reserve
+pub fn get_user_borrow_index(e: &Env, user: &Address, reserve_index: &u32) -> i128 {
+    let key = PoolDataKey::BorrowIdx(UserReserveKey {
         user: user.clone(),
-        auct_type: *auction_type,
+        reserve_id: *reserve_index,
     });
-    e.storage().temporary().remove(&key);
+    get_persistent_default(e, &key, || 0i128, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the d_rate of a reserve when the user last borrowed or repaid.
+/// Removes the entry if the index is zero.
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `reserve_index` - The index of the reserve
+/// * `d_rate` - The d_rate of the reserve
+pub fn set_user_borrow_index(e: &Env, user: &Address, reserve_index: &u32, d_rate: &i128) {
+    let key = PoolDataKey::BorrowIdx(UserReserveKey {
+        user: user.clone(),
+        reserve_id: *reserve_index,
+    });
+    let has_index = e.storage().persistent().has(&key);
+    if *d_rate == 0 {
+        if has_index {
+            e.storage().persistent().remove(&key);
+            let count = get_borrow_index_count(e, reserve_index);
+            set_borrow_index_count(e, reserve_index, &count.saturating_sub(1));
+        }
+        return;
+    }
+    if !has_index {
+        let count = get_borrow_index_count(e, reserve_index);
+        set_borrow_index_count(e, reserve_index, &(count + 1));
+    }
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, i128>(&key, d_rate);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the number of users with a borrow index recorded for a reserve
+///
+/// ### Arguments
+/// * `reserve_index` - The index of the reserve
+pub fn get_borrow_index_count(e: &Env, reserve_index: &u32) -> u32 {
+    let key = PoolDataKey::BorrowIdxs(*reserve_index);
+    get_persistent_default(
+        e,
+        &key,
+        || 0u32,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the number of users with a borrow index recorded for a reserve
+///
+/// ### Arguments
+/// * `reserve_index` - The index of the reserve
+/// * `count` - The number of users
+fn set_borrow_index_count(e: &Env, reserve_index: &u32, count: &u32) {
+    let key = PoolDataKey::BorrowIdxs(*reserve_index);
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, u32>(&key, count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** Pool Emissions **********/
+
+/// Fetch the pool reserve emissions
+pub fn get_pool_emissions(e: &Env) -> Map<u32, u64> {
+    get_persistent_default(
+        e,
+        &Symbol::new(e, POOL_EMIS_KEY),
+        || map![e],
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the pool reserve emissions
+///
+/// ### Arguments
+/// * `emissions` - The map of emissions by reserve token id to share of emissions as
+///                 a percentage of 1e7 (e.g. 15% = 1500000)
+pub fn set_pool_emissions(e: &Env, emissions: &Map<u32, u64>) {
+    e.storage()
+        .persistent()
+        .set::<Symbol, Map<u32, u64>>(&Symbol::new(e, POOL_EMIS_KEY), emissions);
+    e.storage().persistent().extend_ttl(
+        &Symbol::new(e, POOL_EMIS_KEY),
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    );
+}
+
+/// Fetch the bootstrap emissions of the pool
+pub fn get_bootstrap_emissions(e: &Env) -> Option<BootstrapEmissions> {
+    e.storage()
+        .instance()
+        .get::<Symbol, BootstrapEmissions>(&Symbol::new(e, BOOTSTRAP_EMIS_KEY))
+}
+
+/// Set the bootstrap emissions of the pool
+///
+/// ### Arguments
+/// * `bootstrap` - The bootstrap emissions
+pub fn set_bootstrap_emissions(e: &Env, bootstrap: &BootstrapEmissions) {
+    e.storage()
+        .instance()
+        .set::<Symbol, BootstrapEmissions>(&Symbol::new(e, BOOTSTRAP_EMIS_KEY), bootstrap);
+}
+
+/********** Auctions ***********/
+
+/// Fetch the auction data for an auction
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction
+/// * `user` - The user who is auctioning off assets
+///
+/// ### Panics
+/// If the auction does not exist
+pub fn get_auction(e: &Env, auction_type: &u32, user: &Address) -> AuctionData {
+    let key = PoolDataKey::Auction(AuctionKey {
+        user: user.clone(),
+        auct_type: *auction_type,
+    });
+    e.storage()
+        .temporary()
+        .get::<PoolDataKey, AuctionData>(&key)
+        .unwrap_optimized()
+}
+
+/// Check if an auction exists for the given type and user
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction
+/// * `user` - The user who is auctioning off assets
+pub fn has_auction(e: &Env, auction_type: &u32, user: &Address) -> bool {
+    let key = PoolDataKey::Auction(AuctionKey {
+        user: user.clone(),
+        auct_type: *auction_type,
+    });
+    e.storage().temporary().has(&key)
+}
+
+/// Set the the starting block for an auction
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction
+/// * `user` - The user who is auctioning off assets
+/// * `auction_data` - The auction data
+pub fn set_auction(e: &Env, auction_type: &u32, user: &Address, auction_data: &AuctionData) {
+    let auction_key = AuctionKey {
+        user: user.clone(),
+        auct_type: *auction_type,
+    };
+    let key = PoolDataKey::Auction(auction_key.clone());
+    e.storage()
+        .temporary()
+        .set::<PoolDataKey, AuctionData>(&key, auction_data);
+    e.storage()
+        .temporary()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+
+    let open_auctions = get_open_auctions(e);
+    if !open_auctions.contains(&auction_key) {
+        let mut new_open_auctions = vec![e, auction_key];
+        for open_key in open_auctions.iter() {
+            // drop auctions that expired before they were filled or deleted
+            if e.storage()
+                .temporary()
+                .has(&PoolDataKey::Auction(open_key.clone()))
+            {
+                new_open_auctions.push_back(open_key);
+            }
+        }
+        set_open_auctions(e, &new_open_auctions);
+    }
+}
+
+/// Fetch the minimum number of blocks that must pass after an auction begins before it can be filled.
+/// Defaults to 0 if not set.
+pub fn get_auction_min_blocks(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, AUCT_MIN_BLOCKS_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the minimum number of blocks that must pass after an auction begins before it can be filled
+///
+/// ### Arguments
+/// * `min_blocks` - The minimum number of blocks
+pub fn set_auction_min_blocks(e: &Env, min_blocks: &u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, AUCT_MIN_BLOCKS_KEY), min_blocks);
+}
+
+/// Fetch the maximum number of blocks an auction's start is randomly delayed by. Defaults to 0.
+pub fn get_auction_start_offset(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, AUCT_START_OFFSET_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the maximum number of blocks an auction's start is randomly delayed by
+///
+/// ### Arguments
+/// * `max_offset` - The maximum number of blocks
+pub fn set_auction_start_offset(e: &Env, max_offset: &u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, AUCT_START_OFFSET_KEY), max_offset);
+}
+
+/// Fetch the width of an oracle price's confidence interval, relative to the price, above which
+/// positions are valued conservatively. Defaults to 0, which disables confidence intervals.
+pub fn get_confidence_threshold(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, CONF_THRESHOLD_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the width of an oracle price's confidence interval, relative to the price, above which
+/// positions are valued conservatively
+///
+/// ### Arguments
+/// * `threshold` - The threshold (7 decimals)
+pub fn set_confidence_threshold(e: &Env, threshold: &u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, CONF_THRESHOLD_KEY), threshold);
+}
+
+/// Fetch the BLND bond required to propose a reserve, if reserve proposals are enabled
+pub fn get_proposal_bond(e: &Env) -> Option<i128> {
+    e.storage()
+        .instance()
+        .get::<Symbol, i128>(&Symbol::new(e, PROPOSAL_BOND_KEY))
+}
+
+/// Set the BLND bond required to propose a reserve
+///
+/// ### Arguments
+/// * `bond` - The amount of BLND to bond
+pub fn set_proposal_bond(e: &Env, bond: &i128) {
+    e.storage()
+        .instance()
+        .set::<Symbol, i128>(&Symbol::new(e, PROPOSAL_BOND_KEY), bond);
+}
+
+/// Fetch the total BLND bonded to open reserve proposals
+pub fn get_bonded_blnd(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get::<Symbol, i128>(&Symbol::new(e, BONDED_BLND_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the total BLND bonded to open reserve proposals
+///
+/// ### Arguments
+/// * `amount` - The total amount of BLND bonded
+pub fn set_bonded_blnd(e: &Env, amount: &i128) {
+    e.storage()
+        .instance()
+        .set::<Symbol, i128>(&Symbol::new(e, BONDED_BLND_KEY), amount);
+}
+
+/// Fetch the proposal to add an asset as a reserve, if one exists
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_reserve_proposal(e: &Env, asset: &Address) -> Option<ReserveProposal> {
+    let key = PoolDataKey::ResProp(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the proposal to add an asset as a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `proposal` - The reserve proposal
+pub fn set_reserve_proposal(e: &Env, asset: &Address, proposal: &ReserveProposal) {
+    let key = PoolDataKey::ResProp(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, ReserveProposal>(&key, proposal);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Delete the proposal to add an asset as a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn del_reserve_proposal(e: &Env, asset: &Address) {
+    let key = PoolDataKey::ResProp(asset.clone());
+    e.storage().persistent().remove(&key);
+}
+
+/// Fetch the DEX adapter used to swap assets for the pool, if one is set
+pub fn get_dex_adapter(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, DEX_ADAPTER_KEY))
+}
+
+/// Set the DEX adapter used to swap assets for the pool
+///
+/// ### Arguments
+/// * `dex_adapter` - The address of the DEX adapter
+pub fn set_dex_adapter(e: &Env, dex_adapter: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, DEX_ADAPTER_KEY), dex_adapter);
+}
+
+/// Fetch if the liquidator allowlist is enabled. Defaults to false.
+pub fn get_liquidator_allowlist(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get::<Symbol, bool>(&Symbol::new(e, LIQ_ALLOWLIST_KEY))
+        .unwrap_or(false)
+}
+
+/// Set if the liquidator allowlist is enabled
+///
+/// ### Arguments
+/// * `enabled` - If only allowed liquidators can fill liquidation auctions
+pub fn set_liquidator_allowlist(e: &Env, enabled: &bool) {
+    e.storage()
+        .instance()
+        .set::<Symbol, bool>(&Symbol::new(e, LIQ_ALLOWLIST_KEY), enabled);
+}
+
+/// Fetch if an address is on the liquidator allowlist. Defaults to false.
+///
+/// ### Arguments
+/// * `liquidator` - The address of the liquidator
+pub fn get_liquidator(e: &Env, liquidator: &Address) -> bool {
+    let key = PoolDataKey::Liquidator(liquidator.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || false,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set if an address is on the liquidator allowlist
+///
+/// ### Arguments
+/// * `liquidator` - The address of the liquidator
+/// * `allowed` - If the liquidator can fill liquidation auctions
+pub fn set_liquidator(e: &Env, liquidator: &Address, allowed: &bool) {
+    let key = PoolDataKey::Liquidator(liquidator.clone());
+    if !*allowed {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, bool>(&key, allowed);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Remove an auction
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction
+/// * `user` - The user who is auctioning off assets
+pub fn del_auction(e: &Env, auction_type: &u32, user: &Address) {
+    let auction_key = AuctionKey {
+        user: user.clone(),
+        auct_type: *auction_type,
+    };
+    e.storage()
+        .temporary()
+        .remove(&PoolDataKey::Auction(auction_key.clone()));
+
+    let mut open_auctions = get_open_auctions(e);
+    if let Some(index) = open_auctions.first_index_of(&auction_key) {
+        open_auctions.remove(index);
+        set_open_auctions(e, &open_auctions);
+    }
+}
+
+/// Fetch the auctions that have been created and not yet filled or deleted. An auction can expire
+/// before it is removed, so each should be checked with `has_auction`.
+pub fn get_open_auctions(e: &Env) -> Vec<AuctionKey> {
+    get_persistent_default(
+        e,
+        &Symbol::new(e, OPEN_AUCTIONS_KEY),
+        || vec![e],
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the auctions that have been created and not yet filled or deleted
+///
+/// ### Arguments
+/// * `open_auctions` - The type and user of each open auction
+fn set_open_auctions(e: &Env, open_auctions: &Vec<AuctionKey>) {
+    let key = Symbol::new(e, OPEN_AUCTIONS_KEY);
+    e.storage()
+        .persistent()
+        .set::<Symbol, Vec<AuctionKey>>(&key, open_auctions);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** Reserve Strategy **********/
+
+/// Fetch the strategy adapter for a reserve's idle liquidity, if one is set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_reserve_strategy(e: &Env, asset: &Address) -> Option<ReserveStrategy> {
+    let key = PoolDataKey::Strategy(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the strategy adapter for a reserve's idle liquidity
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `strategy` - The reserve strategy
+pub fn set_reserve_strategy(e: &Env, asset: &Address, strategy: &ReserveStrategy) {
+    let key = PoolDataKey::Strategy(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, ReserveStrategy>(&key, strategy);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** Insurance **********/
+
+/// Fetch the fraction of a covered supplier's interest paid to the insurance fund (7 decimals).
+/// Defaults to 0.
+pub fn get_insurance_premium(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<Symbol, u32>(&Symbol::new(e, INS_PREMIUM_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the fraction of a covered supplier's interest paid to the insurance fund
+///
+/// ### Arguments
+/// * `premium` - The premium (7 decimals)
+pub fn set_insurance_premium(e: &Env, premium: &u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, INS_PREMIUM_KEY), premium);
+}
+
+/// Fetch a supplier's coverage from a reserve's insurance fund, if they have opted in
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `reserve_index` - The index of the reserve
+pub fn get_coverage(e: &Env, user: &Address, reserve_index: &u32) -> Option<Coverage> {
+    let key = PoolDataKey::Coverage(UserReserveKey {
+        user: user.clone(),
+        reserve_id: *reserve_index,
+    });
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set a supplier's coverage from a reserve's insurance fund
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `reserve_index` - The index of the reserve
+/// * `coverage` - The user's coverage
+pub fn set_coverage(e: &Env, user: &Address, reserve_index: &u32, coverage: &Coverage) {
+    let key = PoolDataKey::Coverage(UserReserveKey {
+        user: user.clone(),
+        reserve_id: *reserve_index,
+    });
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, Coverage>(&key, coverage);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Delete a supplier's coverage from a reserve's insurance fund
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `reserve_index` - The index of the reserve
+pub fn del_coverage(e: &Env, user: &Address, reserve_index: &u32) {
+    let key = PoolDataKey::Coverage(UserReserveKey {
+        user: user.clone(),
+        reserve_id: *reserve_index,
+    });
+    e.storage().persistent().remove(&key);
+}
+
+/// Fetch the amount of underlying held by a reserve's insurance fund. Defaults to 0.
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_insurance_fund(e: &Env, asset: &Address) -> i128 {
+    let key = PoolDataKey::InsFund(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || 0i128,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the amount of underlying held by a reserve's insurance fund.
+/// Removes the entry if the amount is zero.
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `amount` - The amount of underlying
+pub fn set_insurance_fund(e: &Env, asset: &Address, amount: &i128) {
+    let key = PoolDataKey::InsFund(asset.clone());
+    if *amount == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, i128>(&key, amount);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** Request Hooks **********/
+
+/// Fetch the ordered list of hooks run against each request, if the admin has set one
+pub fn get_request_hooks(e: &Env) -> Option<Vec<RequestHook>> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Vec<RequestHook>>(&Symbol::new(e, REQUEST_HOOKS_KEY))
+}
+
+/// Set the ordered list of hooks run against each request
+///
+/// ### Arguments
+/// * `hooks` - The request hooks
+pub fn set_request_hooks(e: &Env, hooks: &Vec<RequestHook>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Vec<RequestHook>>(&Symbol::new(e, REQUEST_HOOKS_KEY), hooks);
+}
+
+/********** Balance Delta **********/
+
+/// Fetch if transfers into a reserve are credited by the change in the pool's balance. Defaults
+/// to false.
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_balance_delta(e: &Env, asset: &Address) -> bool {
+    let key = PoolDataKey::BalDelta(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || false,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set if transfers into a reserve are credited by the change in the pool's balance
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `enabled` - If transfers are credited by the change in the pool's balance
+pub fn set_balance_delta(e: &Env, asset: &Address, enabled: &bool) {
+    let key = PoolDataKey::BalDelta(asset.clone());
+    if !*enabled {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, bool>(&key, enabled);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the hash of the key notifier services use to route a user's health alerts, if set
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_notification_key(e: &Env, user: &Address) -> Option<BytesN<32>> {
+    let key = PoolDataKey::NotifyKey(user.clone());
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the hash of the key notifier services use to route a user's health alerts.
+/// Removes the entry if no key is given.
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `notification_key` - The hash of the notification key
+pub fn set_notification_key(e: &Env, user: &Address, notification_key: &Option<BytesN<32>>) {
+    let key = PoolDataKey::NotifyKey(user.clone());
+    match notification_key {
+        Some(notification_key) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, BytesN<32>>(&key, notification_key);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch the time a user's self-imposed borrow freeze lifts, if the user has frozen borrowing.
+/// `u64::MAX` if no unfreeze has been requested.
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_borrow_freeze(e: &Env, user: &Address) -> Option<u64> {
+    get_account_lock(e, &PoolDataKey::BorrowFreeze(user.clone()))
+}
+
+/// Set the time a user's self-imposed borrow freeze lifts. Removes the entry if no time is given.
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `unfreeze_time` - The time the borrow freeze lifts
+pub fn set_borrow_freeze(e: &Env, user: &Address, unfreeze_time: &Option<u64>) {
+    set_account_lock(e, &PoolDataKey::BorrowFreeze(user.clone()), unfreeze_time);
+}
+
+/// Fetch the time an account lock lifts, if the account is locked
+///
+/// ### Arguments
+/// * `key` - The key of the account lock
+fn get_account_lock(e: &Env, key: &PoolDataKey) -> Option<u64> {
+    get_persistent_default(e, key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the time an account lock lifts. Removes the entry if no time is given.
+///
+/// ### Arguments
+/// * `key` - The key of the account lock
+/// * `unlock_time` - The time the account lock lifts
+fn set_account_lock(e: &Env, key: &PoolDataKey, unlock_time: &Option<u64>) {
+    match unlock_time {
+        Some(unlock_time) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, u64>(key, unlock_time);
+            e.storage()
+                .persistent()
+                .extend_ttl(key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+        }
+        None => e.storage().persistent().remove(key),
+    }
+}
+
+/// Fetch the time an account leaves supply-only mode, if the account opted into it. `u64::MAX`
+/// if no exit has been requested.
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_supply_only(e: &Env, user: &Address) -> Option<u64> {
+    get_account_lock(e, &PoolDataKey::SupplyOnly(user.clone()))
+}
+
+/// Set the time an account leaves supply-only mode. Removes the entry if no time is given.
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `exit_time` - The time the account leaves supply-only mode
+pub fn set_supply_only(e: &Env, user: &Address, exit_time: &Option<u64>) {
+    set_account_lock(e, &PoolDataKey::SupplyOnly(user.clone()), exit_time);
+}
+
+/// Fetch the ed25519 public key a user signs requests with for relayed submits, if set
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_signing_key(e: &Env, user: &Address) -> Option<BytesN<32>> {
+    let key = PoolDataKey::SigningKey(user.clone());
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the ed25519 public key a user signs requests with for relayed submits. Removes the entry
+/// if no key is given.
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `signing_key` - The ed25519 public key
+pub fn set_signing_key(e: &Env, user: &Address, signing_key: &Option<BytesN<32>>) {
+    let key = PoolDataKey::SigningKey(user.clone());
+    match signing_key {
+        Some(signing_key) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, BytesN<32>>(&key, signing_key);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch if health calculations price a reserve with the oracle's TWAP as well as its spot
+/// price. Defaults to false.
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_twap_pricing(e: &Env, asset: &Address) -> bool {
+    let key = PoolDataKey::TwapPrice(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || false,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set if health calculations price a reserve with the oracle's TWAP as well as its spot price
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `enabled` - If the reserve is priced with the oracle's TWAP
+pub fn set_twap_pricing(e: &Env, asset: &Address, enabled: &bool) {
+    let key = PoolDataKey::TwapPrice(asset.clone());
+    if !*enabled {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, bool>(&key, enabled);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the ledger a reserve was first seen without a valid oracle price, if it is unpriced
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_unpriced_since(e: &Env, asset: &Address) -> Option<u32> {
+    let key = PoolDataKey::Unpriced(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the ledger a reserve was first seen without a valid oracle price
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `ledger` - The ledger the reserve was first seen unpriced, or None if it is priced
+pub fn set_unpriced_since(e: &Env, asset: &Address, ledger: &Option<u32>) {
+    let key = PoolDataKey::Unpriced(asset.clone());
+    match ledger {
+        Some(ledger) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, u32>(&key, ledger);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch if a reserve was sunset for being without a valid oracle price
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_sunset(e: &Env, asset: &Address) -> bool {
+    let key = PoolDataKey::Sunset(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || false,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set if a reserve was sunset for being without a valid oracle price
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `sunset` - If the reserve is sunset
+pub fn set_sunset(e: &Env, asset: &Address, sunset: &bool) {
+    let key = PoolDataKey::Sunset(asset.clone());
+    if !*sunset {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, bool>(&key, sunset);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** Protocol Fee **********/
+
+/// Fetch the protocol fee taken from accrued interest, if set
+pub fn get_protocol_fee(e: &Env) -> Option<ProtocolFee> {
+    e.storage()
+        .instance()
+        .get::<Symbol, ProtocolFee>(&Symbol::new(e, PROTOCOL_FEE_KEY))
+}
+
+/// Set the protocol fee taken from accrued interest
+///
+/// ### Arguments
+/// * `fee` - The protocol fee
+pub fn set_protocol_fee(e: &Env, fee: &ProtocolFee) {
+    e.storage()
+        .instance()
+        .set::<Symbol, ProtocolFee>(&Symbol::new(e, PROTOCOL_FEE_KEY), fee);
+}
+
+/// Fetch the queued protocol fee, if any
+pub fn get_queued_protocol_fee(e: &Env) -> Option<QueuedProtocolFee> {
+    e.storage()
+        .instance()
+        .get::<Symbol, QueuedProtocolFee>(&Symbol::new(e, QUEUED_PROTOCOL_FEE_KEY))
+}
+
+/// Set the queued protocol fee
+///
+/// ### Arguments
+/// * `queued` - The queued protocol fee, or None to clear it
+pub fn set_queued_protocol_fee(e: &Env, queued: &Option<QueuedProtocolFee>) {
+    let key = Symbol::new(e, QUEUED_PROTOCOL_FEE_KEY);
+    match queued {
+        Some(queued) => e
+            .storage()
+            .instance()
+            .set::<Symbol, QueuedProtocolFee>(&key, queued),
+        None => e.storage().instance().remove(&key),
+    }
+}
+
+/// Fetch the amount of underlying owed to the treasury from a reserve's protocol fees.
+/// Defaults to 0.
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_protocol_fee_credit(e: &Env, asset: &Address) -> i128 {
+    let key = PoolDataKey::ProtoFee(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || 0i128,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the amount of underlying owed to the treasury from a reserve's protocol fees.
+/// Removes the entry if the amount is zero.
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `amount` - The amount of underlying
+pub fn set_protocol_fee_credit(e: &Env, asset: &Address, amount: &i128) {
+    let key = PoolDataKey::ProtoFee(asset.clone());
+    if *amount == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, i128>(&key, amount);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** Action Limits **********/
+
+/// Fetch the maximum number of requests a single submit can make. Defaults to
+/// `DEFAULT_MAX_REQUESTS` and `DEFAULT_MAX_AUCTION_REQUESTS`.
+pub fn get_action_limits(e: &Env) -> ActionLimits {
+    e.storage()
+        .instance()
+        .get::<Symbol, ActionLimits>(&Symbol::new(e, ACTION_LIMITS_KEY))
+        .unwrap_or(ActionLimits {
+            max_requests: DEFAULT_MAX_REQUESTS,
+            max_auction_requests: DEFAULT_MAX_AUCTION_REQUESTS,
+        })
+}
+
+/// Set the maximum number of requests a single submit can make
+///
+/// ### Arguments
+/// * `limits` - The action limits
+pub fn set_action_limits(e: &Env, limits: &ActionLimits) {
+    e.storage()
+        .instance()
+        .set::<Symbol, ActionLimits>(&Symbol::new(e, ACTION_LIMITS_KEY), limits);
+}
+
+/********** Utilization Smoothing **********/
+
+/// Fetch the utilization smoothing of a reserve, if it is enabled
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_util_smoothing(e: &Env, asset: &Address) -> Option<UtilSmoothing> {
+    let key = PoolDataKey::UtilSmooth(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the utilization smoothing of a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `smoothing` - The utilization smoothing, or None to disable it
+pub fn set_util_smoothing(e: &Env, asset: &Address, smoothing: &Option<UtilSmoothing>) {
+    let key = PoolDataKey::UtilSmooth(asset.clone());
+    match smoothing {
+        Some(smoothing) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, UtilSmoothing>(&key, smoothing);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/********** Bad Debt **********/
+
+/// Fetch the bad debt of a reserve assigned to the backstop. Defaults to no bad debt.
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_bad_debt(e: &Env, asset: &Address) -> BadDebt {
+    let key = PoolDataKey::BadDebt(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        BadDebt::default,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the bad debt of a reserve assigned to the backstop
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `bad_debt` - The bad debt of the reserve
+pub fn set_bad_debt(e: &Env, asset: &Address, bad_debt: &BadDebt) {
+    let key = PoolDataKey::BadDebt(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, BadDebt>(&key, bad_debt);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** Base Asset **********/
+
+/// Fetch the asset the pool's oracle must quote prices in, if one is set
+pub fn get_base_asset(e: &Env) -> Option<Asset> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Asset>(&Symbol::new(e, BASE_ASSET_KEY))
+}
+
+/// Set the asset the pool's oracle must quote prices in
+///
+/// ### Arguments
+/// * `base` - The base asset
+pub fn set_base_asset(e: &Env, base: &Asset) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Asset>(&Symbol::new(e, BASE_ASSET_KEY), base);
+}
+
+/********** Reserve Migration **********/
+
+/// Fetch the queued reserve migration, if any
+pub fn get_reserve_migration(e: &Env) -> Option<QueuedReserveMigration> {
+    e.storage()
+        .instance()
+        .get::<Symbol, QueuedReserveMigration>(&Symbol::new(e, RES_MIGRATION_KEY))
+}
+
+/// Set the queued reserve migration
+///
+/// ### Arguments
+/// * `migration` - The reserve migration, or None to clear it
+pub fn set_reserve_migration(e: &Env, migration: &Option<QueuedReserveMigration>) {
+    let key = Symbol::new(e, RES_MIGRATION_KEY);
+    match migration {
+        Some(migration) => e
+            .storage()
+            .instance()
+            .set::<Symbol, QueuedReserveMigration>(&key, migration),
+        None => e.storage().instance().remove(&key),
+    }
+}
+
+/********** Withdraw Cooldown **********/
+
+/// Fetch the number of ledgers a user must wait after a borrow to withdraw collateral. Defaults
+/// to 0, which disables the cooldown.
+pub fn get_withdraw_cooldown(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<Symbol, u32>(&Symbol::new(e, WITHDRAW_COOLDOWN_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the number of ledgers a user must wait after a borrow to withdraw collateral
+///
+/// ### Arguments
+/// * `ledgers` - The number of ledgers
+pub fn set_withdraw_cooldown(e: &Env, ledgers: &u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, WITHDRAW_COOLDOWN_KEY), ledgers);
+}
+
+/// Fetch the ledger of a user's last borrow, if it was recorded
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_last_borrow(e: &Env, user: &Address) -> Option<u32> {
+    let key = PoolDataKey::LastBorrow(user.clone());
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the ledger of a user's last borrow
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `ledger` - The ledger of the borrow
+pub fn set_last_borrow(e: &Env, user: &Address, ledger: &u32) {
+    let key = PoolDataKey::LastBorrow(user.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, u32>(&key, ledger);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/********** Retail Liquidation **********/
+
+/// Fetch the deferred liquidation start for retail accounts, if one is set
+pub fn get_retail_liquidation(e: &Env) -> Option<RetailLiquidation> {
+    e.storage()
+        .instance()
+        .get::<Symbol, RetailLiquidation>(&Symbol::new(e, RETAIL_LIQ_KEY))
+}
+
+/// Set the deferred liquidation start for retail accounts
+///
+/// ### Arguments
+/// * `config` - The retail liquidation configuration
+pub fn set_retail_liquidation(e: &Env, config: &RetailLiquidation) {
+    e.storage()
+        .instance()
+        .set::<Symbol, RetailLiquidation>(&Symbol::new(e, RETAIL_LIQ_KEY), config);
+}
+
+/********** Critical Entry Bumps **********/
+
+/// Fetch the time the last bounty for bumping the pool's critical entries was paid
+pub fn get_last_bump(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get::<Symbol, u64>(&Symbol::new(e, LAST_BUMP_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the time the last bounty for bumping the pool's critical entries was paid
+///
+/// ### Arguments
+/// * `time` - The time the bounty was paid
+pub fn set_last_bump(e: &Env, time: &u64) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u64>(&Symbol::new(e, LAST_BUMP_KEY), time);
+}
+
+/********** Risk Tiers **********/
+
+/// Fetch the thresholds for each risk tier, if they are set
+pub fn get_risk_tiers(e: &Env) -> Option<RiskTierConfig> {
+    get_persistent_default(
+        e,
+        &Symbol::new(e, RISK_TIERS_KEY),
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the thresholds for each risk tier
+///
+/// ### Arguments
+/// * `config` - The risk tier thresholds
+pub fn set_risk_tiers(e: &Env, config: &RiskTierConfig) {
+    let key = Symbol::new(e, RISK_TIERS_KEY);
+    e.storage()
+        .persistent()
+        .set::<Symbol, RiskTierConfig>(&key, config);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the number of liquidation auction fills against a user
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_liquidation_count(e: &Env, user: &Address) -> u32 {
+    let key = PoolDataKey::LiqCount(user.clone());
+    get_persistent_default(e, &key, || 0u32, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the number of liquidation auction fills against a user
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `count` - The number of liquidations
+pub fn set_liquidation_count(e: &Env, user: &Address, count: &u32) {
+    let key = PoolDataKey::LiqCount(user.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, u32>(&key, count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/********** Emission Boosts **********/
+
+/// Fetch the boost to the emissions paid to a reserve's suppliers, if it is enabled
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_emission_boost(e: &Env, asset: &Address) -> Option<EmissionBoost> {
+    let key = PoolDataKey::EmisBoost(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the boost to the emissions paid to a reserve's suppliers, and add or remove the reserve
+/// from the list of boosted reserves
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `boost` - The emission boost, or None to disable it
+pub fn set_emission_boost(e: &Env, asset: &Address, boost: &Option<EmissionBoost>) {
+    let key = PoolDataKey::EmisBoost(asset.clone());
+    let mut boosted = get_boosted_reserves(e);
+    let index = boosted.first_index_of(asset);
+    match boost {
+        Some(boost) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, EmissionBoost>(&key, boost);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+            if index.is_none() {
+                boosted.push_back(asset.clone());
+                set_boosted_reserves(e, &boosted);
+            }
+        }
+        None => {
+            e.storage().persistent().remove(&key);
+            if let Some(index) = index {
+                boosted.remove(index);
+                set_boosted_reserves(e, &boosted);
+            }
+        }
+    }
+}
+
+/// Fetch the reserves with an emission boost enabled
+pub fn get_boosted_reserves(e: &Env) -> Vec<Address> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Vec<Address>>(&Symbol::new(e, BOOSTED_RES_KEY))
+        .unwrap_or(vec![e])
+}
+
+/// Set the reserves with an emission boost enabled
+///
+/// ### Arguments
+/// * `boosted` - The underlying assets of the boosted reserves
+fn set_boosted_reserves(e: &Env, boosted: &Vec<Address>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Vec<Address>>(&Symbol::new(e, BOOSTED_RES_KEY), boosted);
+}
+
+/********** Status Guardian **********/
+
+/// Fetch the guardian that can restrict the pool, if one is set
+pub fn get_status_guardian(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, STATUS_GUARDIAN_KEY))
+}
+
+/// Set the guardian that can restrict the pool
+///
+/// ### Arguments
+/// * `guardian` - The address of the guardian, or None to remove it
+pub fn set_status_guardian(e: &Env, guardian: &Option<Address>) {
+    let key = Symbol::new(e, STATUS_GUARDIAN_KEY);
+    match guardian {
+        Some(guardian) => e
+            .storage()
+            .instance()
+            .set::<Symbol, Address>(&key, guardian),
+        None => e.storage().instance().remove(&key),
+    }
 }
@@ -0,0 +1,697 @@
+#![cfg(test)]
+
+use crate::{
+    constants::{SCALAR_12, SCALAR_7},
+    dependencies::PriceDataV2,
+    pool::{Positions, Request, Reserve},
+    storage::{self, PoolConfig, ReserveConfig, ReserveData},
+    PoolContract,
+};
+use blend_contract_sdk::emitter::{Client as EmitterClient, WASM as EmitterWASM};
+use sep_40_oracle::testutils::{MockPriceOracleClient, MockPriceOracleWASM};
+use sep_41_token::testutils::{MockTokenClient, MockTokenWASM};
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger, LedgerInfo},
+    vec, Address, BytesN, Env, IntoVal, String, Symbol, Vec,
+};
+use trustbridge_common::{Asset, PriceData};
+
+use backstop::{BackstopClient, BackstopContract};
+use mock_pool_factory::{MockPoolFactory, MockPoolFactoryClient, PoolInitMeta};
+use moderc3156_example::{
+    FlashLoanReceiverModifiedERC3156, FlashLoanReceiverModifiedERC3156Client,
+};
+
+/// Create a pool contract.
+///
+/// This sets random data in the constructor, so unit tests that
+/// rely on any constructor data need to reset it.
+pub(crate) fn create_pool(e: &Env) -> Address {
+    e.register(
+        PoolContract {},
+        (
+            Address::generate(e),
+            String::from_str(e, "teapot"),
+            Address::generate(e),
+            0_1000000u32,
+            4u32,
+            1_0000000i128,
+            Address::generate(e),
+            Address::generate(e),
+        ),
+    )
+}
+
+/// Create an account address, as `Address::generate` creates contract addresses
+pub(crate) fn create_account_address(e: &Env) -> Address {
+    Address::from_string(&String::from_str(
+        e,
+        "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+    ))
+}
+
+/// Set the ledger to a timestamp and sequence number, with the default test network settings
+pub(crate) fn set_ledger(e: &Env, timestamp: u64, sequence_number: u32) {
+    e.ledger().set(LedgerInfo {
+        timestamp,
+        protocol_version: 22,
+        sequence_number,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: 10,
+        min_persistent_entry_ttl: 10,
+        max_entry_ttl: 3110400,
+    });
+}
+
+/// Create a pool with a mock oracle and no reserves, with the ledger set to timestamp 12345 and
+/// sequence 100.
+///
+/// Returns (pool, oracle_client)
+pub(crate) fn create_pool_with_oracle(e: &Env) -> (Address, MockPriceOracleClient) {
+    e.mock_all_auths();
+    e.cost_estimate().budget().reset_unlimited();
+    set_ledger(e, 12345, 100);
+
+    let pool = create_pool(e);
+    let (oracle, oracle_client) = create_mock_oracle(e);
+
+    let pool_config = PoolConfig {
+        oracle,
+        min_collateral: 1_0000000,
+        bstop_rate: 0_2000000,
+        status: 0,
+        max_positions: 4,
+    };
+    e.as_contract(&pool, || {
+        storage::set_pool_config(e, &pool_config);
+    });
+    (pool, oracle_client)
+}
+
+/// Create a pool with a mock oracle and a single default reserve, with the ledger set to
+/// timestamp 12345 and sequence 100.
+///
+/// Returns (pool, underlying, underlying_client)
+pub(crate) fn create_pool_with_reserve<'a>(e: &Env) -> (Address, Address, MockTokenClient<'a>) {
+    let (pool, _) = create_pool_with_oracle(e);
+
+    let bombadil = Address::generate(e);
+    let (underlying, underlying_client) = create_token_contract(e, &bombadil);
+    let (reserve_config, mut reserve_data) = default_reserve_meta();
+    reserve_data.last_time = 12345;
+    create_reserve(e, &pool, &underlying, &reserve_config, &reserve_data);
+    (pool, underlying, underlying_client)
+}
+
+//************************************************
+//           External Contract Helpers
+//************************************************
+
+// ***** Token *****
+
+pub(crate) fn create_token_contract<'a>(
+    e: &Env,
+    admin: &Address,
+) -> (Address, MockTokenClient<'a>) {
+    let contract_address = Address::generate(e);
+    e.register_at(&contract_address, MockTokenWASM, ());
+    let client = MockTokenClient::new(e, &contract_address);
+    client.initialize(admin, &7, &"unit".into_val(e), &"test".into_val(e));
+    (contract_address, client)
+}
+
+pub(crate) fn create_blnd_token<'a>(
+    e: &Env,
+    pool_address: &Address,
+    admin: &Address,
+) -> (Address, MockTokenClient<'a>) {
+    let (contract_address, client) = create_token_contract(e, admin);
+
+    e.as_contract(pool_address, || {
+        storage::set_blnd_token(e, &contract_address);
+    });
+    (contract_address, client)
+}
+
+/// A token that burns 1% of every transfer, so the recipient receives less than the amount sent
+#[contract]
+pub struct MockFeeToken;
+
+#[contractimpl]
+impl MockFeeToken {
+    pub fn mint(e: Env, to: Address, amount: i128) {
+        let balance = Self::balance(e.clone(), to.clone());
+        e.storage().instance().set(&to, &(balance + amount));
+    }
+
+    pub fn balance(e: Env, id: Address) -> i128 {
+        e.storage().instance().get(&id).unwrap_or(0)
+    }
+
+    pub fn approve(e: Env, from: Address, spender: Address, amount: i128, _expiration_ledger: u32) {
+        from.require_auth();
+        e.storage().instance().set(&(from, spender), &amount);
+    }
+
+    pub fn allowance(e: Env, from: Address, spender: Address) -> i128 {
+        e.storage().instance().get(&(from, spender)).unwrap_or(0)
+    }
+
+    pub fn transfer(e: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        Self::move_balance(&e, from, to, amount);
+    }
+
+    pub fn transfer_from(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        let allowance = Self::allowance(e.clone(), from.clone(), spender.clone());
+        if allowance < amount {
+            panic!("insufficient allowance");
+        }
+        e.storage()
+            .instance()
+            .set(&(from.clone(), spender), &(allowance - amount));
+        Self::move_balance(&e, from, to, amount);
+    }
+
+    fn move_balance(e: &Env, from: Address, to: Address, amount: i128) {
+        let from_balance = Self::balance(e.clone(), from.clone());
+        if from_balance < amount {
+            panic!("insufficient balance");
+        }
+        e.storage().instance().set(&from, &(from_balance - amount));
+        Self::mint(e.clone(), to, amount - amount / 100);
+    }
+}
+
+pub(crate) fn create_fee_token<'a>(e: &Env) -> (Address, MockFeeTokenClient<'a>) {
+    let contract_address = e.register(MockFeeToken {}, ());
+    (
+        contract_address.clone(),
+        MockFeeTokenClient::new(e, &contract_address),
+    )
+}
+
+//***** Oracle ******
+
+pub(crate) fn create_mock_oracle(e: &Env) -> (Address, MockPriceOracleClient) {
+    let contract_address = e.register(MockPriceOracleWASM, ());
+    (
+        contract_address.clone(),
+        MockPriceOracleClient::new(e, &contract_address),
+    )
+}
+
+/// An oracle that reports the confidence of its prices, priced in 7 decimals
+#[contract]
+pub struct MockConfidenceOracle;
+
+#[contractimpl]
+impl MockConfidenceOracle {
+    pub fn set_price(e: Env, asset: Address, price: i128, confidence: i128) {
+        e.storage()
+            .instance()
+            .set(&asset, &(price, e.ledger().timestamp(), confidence));
+    }
+
+    pub fn decimals(_e: Env) -> u32 {
+        7
+    }
+
+    pub fn lastprice_v2(e: Env, asset: Asset) -> Option<PriceDataV2> {
+        let asset = match asset {
+            Asset::Stellar(address) => address,
+            Asset::Other(_) => return None,
+        };
+        e.storage()
+            .instance()
+            .get::<Address, (i128, u64, i128)>(&asset)
+            .map(|(price, timestamp, confidence)| PriceDataV2 {
+                price,
+                timestamp,
+                confidence,
+            })
+    }
+}
+
+pub(crate) fn create_mock_confidence_oracle(e: &Env) -> (Address, MockConfidenceOracleClient) {
+    let contract_address = e.register(MockConfidenceOracle {}, ());
+    (
+        contract_address.clone(),
+        MockConfidenceOracleClient::new(e, &contract_address),
+    )
+}
+
+/// An oracle with a price history for each asset, priced in 7 decimals. The history is ordered
+/// newest first, with records 5 minutes apart ending at the time it was set.
+#[contract]
+pub struct MockTwapOracle;
+
+#[contractimpl]
+impl MockTwapOracle {
+    pub fn set_history(e: Env, asset: Address, history: Vec<i128>) {
+        let mut records: Vec<PriceData> = vec![&e];
+        for (i, price) in history.iter().enumerate() {
+            records.push_back(PriceData {
+                price,
+                timestamp: e.ledger().timestamp() - 300 * i as u64,
+            });
+        }
+        e.storage().instance().set(&asset, &records);
+    }
+
+    pub fn decimals(_e: Env) -> u32 {
+        7
+    }
+
+    pub fn lastprice(e: Env, asset: Asset) -> Option<PriceData> {
+        Self::prices(e, asset, 1).map(|records| records.get_unchecked(0))
+    }
+
+    pub fn prices(e: Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        let asset = match asset {
+            Asset::Stellar(address) => address,
+            Asset::Other(_) => return None,
+        };
+        e.storage()
+            .instance()
+            .get::<Address, Vec<PriceData>>(&asset)
+            .map(|history| history.slice(0..records.min(history.len())))
+    }
+}
+
+pub(crate) fn create_mock_twap_oracle(e: &Env) -> (Address, MockTwapOracleClient) {
+    let contract_address = e.register(MockTwapOracle {}, ());
+    (
+        contract_address.clone(),
+        MockTwapOracleClient::new(e, &contract_address),
+    )
+}
+
+//***** DEX Adapter ******
+
+/// Swaps 1:1 between any tokens it holds
+#[contract]
+pub struct MockDexAdapter;
+
+#[contractimpl]
+impl MockDexAdapter {
+    pub fn swap_exact_in(
+        e: Env,
+        from: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+        to: Address,
+    ) -> i128 {
+        from.require_auth();
+        MockTokenClient::new(&e, &token_in).transfer(
+            &from,
+            &e.current_contract_address(),
+            &amount_in,
+        );
+        assert!(amount_in >= min_amount_out);
+        MockTokenClient::new(&e, &token_out).transfer(
+            &e.current_contract_address(),
+            &to,
+            &amount_in,
+        );
+        amount_in
+    }
+}
+
+pub(crate) fn create_mock_dex_adapter(e: &Env) -> Address {
+    e.register(MockDexAdapter {}, ())
+}
+
+//***** Strategy ******
+
+/// Holds deposits of a single asset, and treats any tokens it holds as the balance of the
+/// depositor
+#[contract]
+pub struct MockStrategy;
+
+#[contractimpl]
+impl MockStrategy {
+    pub fn __constructor(e: Env, asset: Address) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "asset"), &asset);
+    }
+
+    pub fn deposit(e: Env, from: Address, amount: i128) {
+        from.require_auth();
+        MockTokenClient::new(&e, &Self::asset(&e)).transfer(
+            &from,
+            &e.current_contract_address(),
+            &amount,
+        );
+    }
+
+    pub fn withdraw(e: Env, owner: Address, amount: i128) -> i128 {
+        owner.require_auth();
+        MockTokenClient::new(&e, &Self::asset(&e)).transfer(
+            &e.current_contract_address(),
+            &owner,
+            &amount,
+        );
+        amount
+    }
+
+    pub fn balance(e: Env, _owner: Address) -> i128 {
+        MockTokenClient::new(&e, &Self::asset(&e)).balance(&e.current_contract_address())
+    }
+
+    fn asset(e: &Env) -> Address {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(e, "asset"))
+            .unwrap()
+    }
+}
+
+pub(crate) fn create_mock_strategy(e: &Env, asset: &Address) -> Address {
+    e.register(MockStrategy {}, (asset.clone(),))
+}
+
+//***** Request Validator ******
+
+/// Rejects any request with an amount over a maximum
+#[contract]
+pub struct MockRequestValidator;
+
+#[contractimpl]
+impl MockRequestValidator {
+    pub fn __constructor(e: Env, max_amount: i128) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "max"), &max_amount);
+    }
+
+    pub fn validate_request(
+        e: Env,
+        _pool: Address,
+        _user: Address,
+        _positions: Positions,
+        request: Request,
+    ) {
+        let max_amount: i128 = e.storage().instance().get(&Symbol::new(&e, "max")).unwrap();
+        if request.amount > max_amount {
+            panic!("request amount over max");
+        }
+    }
+}
+
+pub(crate) fn create_mock_request_validator(e: &Env, max_amount: i128) -> Address {
+    e.register(MockRequestValidator {}, (max_amount,))
+}
+
+//***** Backstop Shares ******
+
+/// A backstop that only tracks the share token of each pool's backstop
+#[contract]
+pub struct MockShareBackstop;
+
+#[contractimpl]
+impl MockShareBackstop {
+    pub fn set_share_token(e: Env, pool: Address, share_token: Address) {
+        e.storage().instance().set(&pool, &share_token);
+    }
+
+    pub fn share_token(e: Env, pool: Address) -> Option<Address> {
+        e.storage().instance().get(&pool)
+    }
+}
+
+/// The share token of a pool's backstop
+#[contract]
+pub struct MockShareToken;
+
+#[contractimpl]
+impl MockShareToken {
+    pub fn __constructor(e: Env, backstop: Address, pool: Address) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "backstop"), &backstop);
+        e.storage().instance().set(&Symbol::new(&e, "pool"), &pool);
+    }
+
+    pub fn backstop(e: Env) -> Address {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "backstop"))
+            .unwrap()
+    }
+
+    pub fn pool(e: Env) -> Address {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "pool"))
+            .unwrap()
+    }
+}
+
+/// Create the share token of `share_pool`'s backstop, and set that backstop as the backstop of
+/// the pool at `pool_address`
+pub(crate) fn create_mock_share_token(
+    e: &Env,
+    pool_address: &Address,
+    share_pool: &Address,
+) -> Address {
+    let backstop = e.register(MockShareBackstop {}, ());
+    let share_token = e.register(MockShareToken {}, (backstop.clone(), share_pool.clone()));
+    MockShareBackstopClient::new(e, &backstop).set_share_token(share_pool, &share_token);
+    e.as_contract(pool_address, || {
+        storage::set_backstop(e, &backstop);
+    });
+    share_token
+}
+
+//***** Pool Factory ******
+
+pub(crate) fn create_mock_pool_factory(e: &Env) -> (Address, MockPoolFactoryClient) {
+    let pool_init_meta = PoolInitMeta {
+        backstop: Address::generate(e),
+        pool_hash: BytesN::<32>::from_array(&e, &[0u8; 32]),
+        blnd_id: Address::generate(e),
+    };
+    let contract_address = e.register(MockPoolFactory {}, (pool_init_meta,));
+    (
+        contract_address.clone(),
+        MockPoolFactoryClient::new(e, &contract_address),
+    )
+}
+
+//***** Pool Factory ******
+
+pub(crate) fn create_emitter<'a>(
+    e: &Env,
+    backstop_id: &Address,
+    backstop_token: &Address,
+    blnd_token: &Address,
+) -> (Address, EmitterClient<'a>) {
+    let contract_address = e.register(EmitterWASM, ());
+    let client = EmitterClient::new(e, &contract_address);
+    client.initialize(blnd_token, backstop_id, backstop_token);
+    (contract_address.clone(), client)
+}
+
+//***** Backstop ******
+
+mod comet {
+    soroban_sdk::contractimport!(file = "../comet.wasm");
+}
+
+pub(crate) fn create_backstop<'a>(
+    e: &Env,
+    pool_address: &Address,
+    backstop_token: &Address,
+    usdc_token: &Address,
+    blnd_token: &Address,
+) -> (Address, BackstopClient<'a>) {
+    let backstop_id = Address::generate(e);
+    let (pool_factory, mock_pool_factory_client) = create_mock_pool_factory(e);
+    mock_pool_factory_client.set_pool(pool_address);
+    let (emitter, _) = create_emitter(e, &backstop_id, backstop_token, blnd_token);
+    e.register_at(
+        &backstop_id,
+        BackstopContract {},
+        (
+            backstop_token,
+            emitter,
+            blnd_token,
+            usdc_token,
+            pool_factory,
+            Address::generate(e),
+            Address::generate(e),
+            vec![e, (pool_address.clone(), 40_000_000 * SCALAR_7)],
+        ),
+    );
+    e.as_contract(pool_address, || {
+        storage::set_backstop(e, &backstop_id);
+    });
+    (backstop_id.clone(), BackstopClient::new(e, &backstop_id))
+}
+
+/// Deploy a test Comet LP pool of 80% BLND / 20% USDC and set it as the backstop token.
+///
+/// Initializes the pool with the following settings:
+/// - Swap fee: 0.3%
+/// - BLND: 1,000
+/// - USDC: 25
+/// - Shares: 100
+pub(crate) fn create_comet_lp_pool<'a>(
+    e: &Env,
+    admin: &Address,
+    blnd_token: &Address,
+    usdc_token: &Address,
+) -> (Address, comet::Client<'a>) {
+    let contract_address = Address::generate(e);
+    e.register_at(&contract_address, comet::WASM, ());
+    let client = comet::Client::new(e, &contract_address);
+
+    let blnd_client = MockTokenClient::new(e, blnd_token);
+    let usdc_client = MockTokenClient::new(e, usdc_token);
+    blnd_client.mint(&admin, &1_000_0000000);
+    usdc_client.mint(&admin, &25_0000000);
+
+    client.init(
+        admin,
+        &vec![e, blnd_token.clone(), usdc_token.clone()],
+        &vec![e, 0_8000000, 0_2000000],
+        &vec![e, 1_000_0000000, 25_0000000],
+        &0_0030000,
+    );
+
+    (contract_address, client)
+}
+
+//***** Flash Loan *****
+
+/// Create a flash loan receiver contract.
+///
+/// This returns the tokens received from the flash loan to the "caller" for
+/// test purposes.
+pub fn create_flashloan_receiver<'a>(
+    e: &Env,
+) -> (Address, FlashLoanReceiverModifiedERC3156Client<'a>) {
+    let contract_id = Address::generate(e);
+    e.register_at(&contract_id, FlashLoanReceiverModifiedERC3156 {}, ());
+
+    (
+        contract_id.clone(),
+        FlashLoanReceiverModifiedERC3156Client::new(e, &contract_id),
+    )
+}
+
+//************************************************
+//            Object Creation Helpers
+//************************************************
+
+//***** Reserve *****
+
+pub(crate) fn default_reserve(e: &Env) -> Reserve {
+    Reserve {
+        asset: Address::generate(e),
+        config: ReserveConfig {
+            decimals: 7,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_7500000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 0_0000020, // 2e-6
+            index: 0,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
+        },
+        data: ReserveData {
+            b_rate: SCALAR_12,
+            d_rate: SCALAR_12,
+            ir_mod: SCALAR_7,
+            b_supply: 100_0000000,
+            d_supply: 75_0000000,
+            last_time: 0,
+            backstop_credit: 0,
+        },
+        scalar: SCALAR_7,
+        protocol_fee: 0,
+        util_smoothing: None,
+    }
+}
+
+pub(crate) fn default_reserve_meta() -> (ReserveConfig, ReserveData) {
+    (
+        ReserveConfig {
+            decimals: 7,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_7500000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 0_0000020, // 2e-6
+            index: 0,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+            conc_threshold: 0,
+            conc_haircut: 0,
+        },
+        ReserveData {
+            b_rate: SCALAR_12,
+            d_rate: SCALAR_12,
+            ir_mod: SCALAR_7,
+            b_supply: 100_0000000,
+            d_supply: 75_0000000,
+            last_time: 0,
+            backstop_credit: 0,
+        },
+    )
+}
+
+/// Create a reserve based on the supplied config and data.
+///
+/// Mints the appropriate amount of underlying tokens to the pool based on the
+/// b and d token supply and rates.
+///
+/// Returns the underlying asset address.
+pub(crate) fn create_reserve(
+    e: &Env,
+    pool_address: &Address,
+    token_address: &Address,
+    reserve_config: &ReserveConfig,
+    reserve_data: &ReserveData,
+) {
+    let mut new_reserve_config = reserve_config.clone();
+    e.as_contract(pool_address, || {
+        let index = storage::push_res_list(e, &token_address);
+        new_reserve_config.index = index;
+        storage::set_res_config(e, &token_address, &new_reserve_config);
+        storage::set_res_data(e, &token_address, &reserve_data);
+    });
+    let underlying_client = MockTokenClient::new(e, token_address);
+
+    // mint pool assets to set expected b_rate
+    let total_supply = reserve_data
+        .b_supply
+        .fixed_mul_floor(e, &reserve_data.b_rate, &SCALAR_12);
+    let total_liabilities =
+        reserve_data
+            .d_supply
+            .fixed_mul_floor(e, &reserve_data.d_rate, &SCALAR_12);
+    let to_mint_pool = total_supply - total_liabilities + reserve_data.backstop_credit;
+    underlying_client
+        .mock_all_auths()
+        .mint(&pool_address, &to_mint_pool);
+}
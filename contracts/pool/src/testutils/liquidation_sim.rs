@@ -0,0 +1,347 @@
+//! Simulate user liquidation auctions from a snapshot of a pool's state.
+//!
+//! Liquidation bots can load a pool's reserves, prices and user positions into a `SimPoolState`
+//! and compute the exact auction the pool would create for a user, and what a fill would pay out
+//! at any block, without deploying a pool, backstop or oracle.
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, testutils::Address as _, Address, Env, Map, Symbol, Vec,
+};
+use trustbridge_common::{Asset, PriceData};
+
+use crate::{
+    auctions::{self, AuctionData, AuctionType},
+    pool::{Pool, Positions},
+    storage::{self, PoolConfig, ReserveConfig, ReserveData},
+};
+
+/// A reserve in a simulated pool
+#[derive(Clone)]
+#[contracttype]
+pub struct SimReserve {
+    pub asset: Address,
+    pub config: ReserveConfig,
+    pub data: ReserveData,
+    /// The oracle price of the asset, with the pool state's `oracle_decimals`
+    pub price: i128,
+}
+
+/// A serializable snapshot of a pool that liquidations can be simulated against
+#[derive(Clone)]
+#[contracttype]
+pub struct SimPoolState {
+    /// The pool's config. The oracle is replaced with a simulated one.
+    pub config: PoolConfig,
+    pub oracle_decimals: u32,
+    /// The pool's reserves, in reserve index order
+    pub reserves: Vec<SimReserve>,
+    pub positions: Map<Address, Positions>,
+}
+
+/// A contract that holds the simulated pool's storage
+#[contract]
+pub struct SimPool;
+
+/// An oracle that reports the simulated pool's prices as of the current ledger
+#[contract]
+pub struct SimOracle;
+
+#[contractimpl]
+impl SimOracle {
+    pub fn set_decimals(e: Env, decimals: u32) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "decimals"), &decimals);
+    }
+
+    pub fn set_price(e: Env, asset: Address, price: i128) {
+        e.storage().instance().set(&asset, &price);
+    }
+
+    pub fn decimals(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "decimals"))
+            .unwrap()
+    }
+
+    pub fn lastprice(e: Env, asset: Asset) -> Option<PriceData> {
+        match asset {
+            Asset::Stellar(address) => {
+                e.storage()
+                    .instance()
+                    .get(&address)
+                    .map(|price: i128| PriceData {
+                        price,
+                        timestamp: e.ledger().timestamp(),
+                    })
+            }
+            Asset::Other(_) => None,
+        }
+    }
+}
+
+/// A pool loaded from a `SimPoolState`
+pub struct LiquidationSim {
+    pub pool: Address,
+    pub oracle: Address,
+}
+
+impl LiquidationSim {
+    /// Load the pool state into the environment. This registers a pool and oracle contract with `e`.
+    ///
+    /// Reserve interest is accrued to the environment's ledger timestamp whenever the pool is
+    /// loaded, and the oracle prices are always reported as current.
+    ///
+    /// ### Arguments
+    /// * `state` - The pool state to simulate
+    pub fn new(e: &Env, state: &SimPoolState) -> Self {
+        let pool = e.register(SimPool, ());
+        let oracle = e.register(SimOracle, ());
+        let oracle_client = SimOracleClient::new(e, &oracle);
+        oracle_client.set_decimals(&state.oracle_decimals);
+
+        let mut config = state.config.clone();
+        config.oracle = oracle.clone();
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &config);
+            storage::set_backstop(e, &Address::generate(e));
+            for reserve in state.reserves.iter() {
+                let mut reserve_config = reserve.config.clone();
+                reserve_config.index = storage::push_res_list(e, &reserve.asset);
+                storage::set_res_config(e, &reserve.asset, &reserve_config);
+                storage::set_res_data(e, &reserve.asset, &reserve.data);
+            }
+            for (user, positions) in state.positions.iter() {
+                storage::set_user_positions(e, &user, &positions);
+            }
+        });
+        for reserve in state.reserves.iter() {
+            oracle_client.set_price(&reserve.asset, &reserve.price);
+        }
+        LiquidationSim { pool, oracle }
+    }
+
+    /// Update the oracle price of an asset
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset
+    /// * `price` - The new price, with the pool state's `oracle_decimals`
+    pub fn set_price(&self, e: &Env, asset: &Address, price: i128) {
+        SimOracleClient::new(e, &self.oracle).set_price(asset, &price);
+    }
+
+    /// Create the user liquidation auction the pool would create for all of a user's positions
+    /// at the environment's current ledger. The auction is not kept, so it can be recreated
+    /// after changing prices or the ledger.
+    ///
+    /// ### Arguments
+    /// * `user` - The user being liquidated
+    /// * `percent` - The percentage of the user's positions being liquidated
+    ///
+    /// ### Panics
+    /// If the pool would not allow the liquidation
+    pub fn create_user_liquidation(&self, e: &Env, user: &Address, percent: u32) -> AuctionData {
+        e.as_contract(&self.pool, || {
+            let auction_data = auctions::create_user_liquidation(e, user, percent);
+            storage::del_auction(e, &(AuctionType::UserLiquidation as u32), user);
+            auction_data
+        })
+    }
+
+    /// Fetch the bid and lot a filler would receive for filling a user liquidation auction at
+    /// `block`, in bTokens and dTokens
+    ///
+    /// ### Arguments
+    /// * `auction_data` - The auction being filled
+    /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+    /// * `block` - The block the auction is filled at
+    pub fn auction_at_block(
+        &self,
+        e: &Env,
+        auction_data: &AuctionData,
+        percent_filled: u64,
+        block: u32,
+    ) -> AuctionData {
+        let (to_fill_auction, _) =
+            auctions::scale_auction_at_block(e, auction_data, percent_filled, block);
+        to_fill_auction
+    }
+
+    /// Fetch the bid and lot a filler would receive for filling a user liquidation auction at
+    /// `block`, in underlying tokens. Token rates are accrued to the environment's current
+    /// ledger timestamp.
+    ///
+    /// ### Arguments
+    /// * `auction_data` - The auction being filled
+    /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+    /// * `block` - The block the auction is filled at
+    pub fn underlying_auction_at_block(
+        &self,
+        e: &Env,
+        auction_data: &AuctionData,
+        percent_filled: u64,
+        block: u32,
+    ) -> AuctionData {
+        let to_fill_auction = self.auction_at_block(e, auction_data, percent_filled, block);
+        e.as_contract(&self.pool, || {
+            let mut pool = Pool::load(e);
+            auctions::to_underlying_auction(
+                e,
+                &mut pool,
+                AuctionType::UserLiquidation as u32,
+                &to_fill_auction,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils;
+    use soroban_sdk::{
+        map,
+        testutils::{Ledger, LedgerInfo},
+        vec,
+    };
+
+    fn sim_state(e: &Env, assets: &[Address; 3], user: &Address) -> SimPoolState {
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+
+        SimPoolState {
+            config: PoolConfig {
+                oracle: Address::generate(e),
+                min_collateral: 1_0000000,
+                bstop_rate: 0_1000000,
+                status: 0,
+                max_positions: 4,
+            },
+            oracle_decimals: 7,
+            reserves: vec![
+                e,
+                SimReserve {
+                    asset: assets[0].clone(),
+                    config: reserve_config_0,
+                    data: reserve_data_0,
+                    price: 2_0000000,
+                },
+                SimReserve {
+                    asset: assets[1].clone(),
+                    config: reserve_config_1,
+                    data: reserve_data_1,
+                    price: 4_0000000,
+                },
+                SimReserve {
+                    asset: assets[2].clone(),
+                    config: reserve_config_2,
+                    data: reserve_data_2,
+                    price: 50_0000000,
+                },
+            ],
+            positions: map![
+                e,
+                (
+                    user.clone(),
+                    Positions {
+                        collateral: map![e, (0, 90_9100000), (1, 04_5800000)],
+                        liabilities: map![e, (2, 02_7500000)],
+                        supply: map![e],
+                    }
+                )
+            ],
+        }
+    }
+
+    #[test]
+    fn test_liquidation_sim() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.cost_estimate().budget().reset_unlimited();
+
+        let samwise = Address::generate(&e);
+        let assets = [
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+        ];
+        let sim = LiquidationSim::new(&e, &sim_state(&e, &assets, &samwise));
+
+        let auction_data = sim.create_user_liquidation(&e, &samwise, 45);
+        assert_eq!(auction_data.block, 51);
+        assert_eq!(auction_data.bid.keys(), vec![&e, assets[2].clone()]);
+        assert_eq!(auction_data.lot.len(), 2);
+        assert!(auction_data.lot.contains_key(assets[0].clone()));
+        assert!(auction_data.lot.contains_key(assets[1].clone()));
+
+        // the auction is not stored, so it can be recreated
+        let recreated = sim.create_user_liquidation(&e, &samwise, 45);
+        assert_eq!(recreated.bid, auction_data.bid);
+        assert_eq!(recreated.lot, auction_data.lot);
+
+        // half the lot is available 100 blocks in
+        let at_block = sim.auction_at_block(&e, &auction_data, 100, 151);
+        assert_eq!(at_block.bid, auction_data.bid);
+        for (asset, amount) in auction_data.lot.iter() {
+            assert_eq!(at_block.lot.get_unchecked(asset), amount / 2);
+        }
+
+        // half the bid is owed 300 blocks in
+        let at_block = sim.auction_at_block(&e, &auction_data, 100, 351);
+        assert_eq!(at_block.lot, auction_data.lot);
+        for (asset, amount) in auction_data.bid.iter() {
+            assert_eq!(at_block.bid.get_unchecked(asset), (amount + 1) / 2);
+        }
+
+        // lot bTokens are converted at the reserve's b_rate
+        let underlying = sim.underlying_auction_at_block(&e, &auction_data, 100, 251);
+        let lot_0 = auction_data.lot.get_unchecked(assets[0].clone());
+        assert_eq!(
+            underlying.lot.get_unchecked(assets[0].clone()),
+            lot_0 * 11 / 10
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1211)")]
+    fn test_liquidation_sim_healthy_after_price_change() {
+        let e = Env::default();
+        e.ledger().set_timestamp(12345);
+        e.cost_estimate().budget().reset_unlimited();
+
+        let samwise = Address::generate(&e);
+        let assets = [
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+        ];
+        let sim = LiquidationSim::new(&e, &sim_state(&e, &assets, &samwise));
+        sim.set_price(&e, &assets[2], 1_0000000);
+
+        sim.create_user_liquidation(&e, &samwise, 45);
+    }
+}
@@ -0,0 +1,10 @@
+#[cfg(test)]
+mod fixtures;
+#[cfg(test)]
+pub(crate) use fixtures::*;
+
+#[cfg(any(test, feature = "testutils"))]
+pub mod liquidation_sim;
+
+#[cfg(any(test, feature = "testutils"))]
+pub use crate::pool::{domain_separator, hash_requests, signed_submit_payload};